@@ -2,8 +2,15 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::any::Any;
+use std::ops::{Range, RangeInclusive};
+use std::path::PathBuf;
 use std::sync::Arc;
 
+use chrono::{NaiveDate, NaiveTime};
+
+use crate::widget::BreakpointClass;
+use crate::Color;
+
 // TODO - Refactor - See issue #1
 
 // TODO - TextCursor changed, ImeChanged, EnterKey, MouseEnter
@@ -17,6 +24,22 @@ pub enum Action {
     TextChanged(String),
     TextEntered(String),
     CheckboxChecked(bool),
+    ContextMenuItemSelected(usize),
+    DropdownSelected(usize),
+    VisibleRangeChanged(Range<usize>),
+    FilesDropped(Vec<PathBuf>),
+    LinkClicked,
+    LongPressed,
+    BreakpointChanged(BreakpointClass),
+    RatingChanged(f32),
+    RangeChanged(RangeInclusive<f64>),
+    NumberChanged(f64),
+    TagsChanged(Vec<String>),
+    ColorChanged(Color),
+    DateSelected(NaiveDate),
+    DateRangeSelected(RangeInclusive<NaiveDate>),
+    SpinnerColumnSelected(usize),
+    TimeChanged(NaiveTime),
     // FIXME - This is a huge hack
     Other(Arc<dyn Any>),
 }
@@ -28,6 +51,22 @@ impl PartialEq for Action {
             (Self::TextChanged(l0), Self::TextChanged(r0)) => l0 == r0,
             (Self::TextEntered(l0), Self::TextEntered(r0)) => l0 == r0,
             (Self::CheckboxChecked(l0), Self::CheckboxChecked(r0)) => l0 == r0,
+            (Self::ContextMenuItemSelected(l0), Self::ContextMenuItemSelected(r0)) => l0 == r0,
+            (Self::DropdownSelected(l0), Self::DropdownSelected(r0)) => l0 == r0,
+            (Self::VisibleRangeChanged(l0), Self::VisibleRangeChanged(r0)) => l0 == r0,
+            (Self::FilesDropped(l0), Self::FilesDropped(r0)) => l0 == r0,
+            (Self::LinkClicked, Self::LinkClicked) => true,
+            (Self::LongPressed, Self::LongPressed) => true,
+            (Self::BreakpointChanged(l0), Self::BreakpointChanged(r0)) => l0 == r0,
+            (Self::RatingChanged(l0), Self::RatingChanged(r0)) => l0 == r0,
+            (Self::RangeChanged(l0), Self::RangeChanged(r0)) => l0 == r0,
+            (Self::NumberChanged(l0), Self::NumberChanged(r0)) => l0 == r0,
+            (Self::TagsChanged(l0), Self::TagsChanged(r0)) => l0 == r0,
+            (Self::ColorChanged(l0), Self::ColorChanged(r0)) => l0 == r0,
+            (Self::DateSelected(l0), Self::DateSelected(r0)) => l0 == r0,
+            (Self::DateRangeSelected(l0), Self::DateRangeSelected(r0)) => l0 == r0,
+            (Self::SpinnerColumnSelected(l0), Self::SpinnerColumnSelected(r0)) => l0 == r0,
+            (Self::TimeChanged(l0), Self::TimeChanged(r0)) => l0 == r0,
             #[allow(ambiguous_wide_pointer_comparisons)]
             // FIXME
             (Self::Other(val_l), Self::Other(val_r)) => Arc::ptr_eq(val_l, val_r),
@@ -43,6 +82,32 @@ impl std::fmt::Debug for Action {
             Self::TextChanged(text) => f.debug_tuple("TextChanged").field(text).finish(),
             Self::TextEntered(text) => f.debug_tuple("TextEntered").field(text).finish(),
             Self::CheckboxChecked(b) => f.debug_tuple("CheckboxChecked").field(b).finish(),
+            Self::ContextMenuItemSelected(idx) => {
+                f.debug_tuple("ContextMenuItemSelected").field(idx).finish()
+            }
+            Self::DropdownSelected(idx) => f.debug_tuple("DropdownSelected").field(idx).finish(),
+            Self::VisibleRangeChanged(range) => {
+                f.debug_tuple("VisibleRangeChanged").field(range).finish()
+            }
+            Self::FilesDropped(paths) => f.debug_tuple("FilesDropped").field(paths).finish(),
+            Self::LinkClicked => write!(f, "LinkClicked"),
+            Self::LongPressed => write!(f, "LongPressed"),
+            Self::BreakpointChanged(class) => {
+                f.debug_tuple("BreakpointChanged").field(class).finish()
+            }
+            Self::RatingChanged(value) => f.debug_tuple("RatingChanged").field(value).finish(),
+            Self::RangeChanged(range) => f.debug_tuple("RangeChanged").field(range).finish(),
+            Self::NumberChanged(value) => f.debug_tuple("NumberChanged").field(value).finish(),
+            Self::TagsChanged(tags) => f.debug_tuple("TagsChanged").field(tags).finish(),
+            Self::ColorChanged(color) => f.debug_tuple("ColorChanged").field(color).finish(),
+            Self::DateSelected(date) => f.debug_tuple("DateSelected").field(date).finish(),
+            Self::DateRangeSelected(range) => {
+                f.debug_tuple("DateRangeSelected").field(range).finish()
+            }
+            Self::SpinnerColumnSelected(idx) => {
+                f.debug_tuple("SpinnerColumnSelected").field(idx).finish()
+            }
+            Self::TimeChanged(time) => f.debug_tuple("TimeChanged").field(time).finish(),
             Self::Other(_) => write!(f, "Other(...)"),
         }
     }
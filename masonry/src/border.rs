@@ -0,0 +1,203 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Borders drawn around widgets.
+
+use kurbo::{Affine, Cap, Insets, Line, RoundedRectRadii, Shape, Size, Stroke};
+use vello::peniko::{Color, Fill};
+use vello::Scene;
+
+/// A border drawn along the edges of a widget.
+///
+/// This follows the model of the CSS `border` property: the border is drawn inside the
+/// bounds of the widget, and each side can have its own width.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Border {
+    /// The width of each side of the border.
+    pub widths: BorderWidths,
+    /// The color of the border.
+    pub color: Color,
+    /// Whether the border is solid, dashed or dotted.
+    pub style: BorderStyle,
+}
+
+/// The width of each side of a [`Border`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[allow(missing_docs)]
+pub struct BorderWidths {
+    pub top: f64,
+    pub right: f64,
+    pub bottom: f64,
+    pub left: f64,
+}
+
+/// How the line of a [`Border`] is drawn.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum BorderStyle {
+    /// A continuous line.
+    #[default]
+    Solid,
+    /// A dashed line, with dashes and gaps of the given length.
+    Dashed(f64),
+    /// A dotted line, with round dots as large as the border is wide.
+    Dotted,
+}
+
+impl Border {
+    /// Create a solid border with the same width on each side.
+    pub fn new(color: impl Into<Color>, width: f64) -> Self {
+        Self {
+            widths: BorderWidths::uniform(width),
+            color: color.into(),
+            style: BorderStyle::Solid,
+        }
+    }
+
+    /// Builder-style method for setting the width of each side.
+    pub fn with_widths(mut self, widths: impl Into<BorderWidths>) -> Self {
+        self.widths = widths.into();
+        self
+    }
+
+    /// Builder-style method for setting whether the border is solid, dashed or dotted.
+    pub fn with_style(mut self, style: BorderStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Paint the border along the edges of a widget of the given size.
+    ///
+    /// When the sides have different widths, rounded corners are only supported for
+    /// solid borders; dashed and dotted sides are drawn as straight lines.
+    pub fn paint(&self, scene: &mut Scene, size: Size, radii: RoundedRectRadii) {
+        let bounds = size.to_rect();
+        if let Some(width) = self.widths.uniform_width() {
+            if width > 0.0 {
+                let rect = bounds.inset(width / -2.0).to_rounded_rect(radii);
+                scene.stroke(
+                    &self.stroke(width),
+                    Affine::IDENTITY,
+                    self.color,
+                    None,
+                    &rect,
+                );
+            }
+            return;
+        }
+
+        let widths = &self.widths;
+        if self.style == BorderStyle::Solid {
+            // Fill the area between the outer edge and the inner edge of the border.
+            let inner_radii = RoundedRectRadii::new(
+                (radii.top_left - widths.top.max(widths.left)).max(0.0),
+                (radii.top_right - widths.top.max(widths.right)).max(0.0),
+                (radii.bottom_right - widths.bottom.max(widths.right)).max(0.0),
+                (radii.bottom_left - widths.bottom.max(widths.left)).max(0.0),
+            );
+            let inner = (bounds - widths.to_insets()).to_rounded_rect(inner_radii);
+            let mut path = bounds.to_rounded_rect(radii).to_path(0.1);
+            path.extend(inner.path_elements(0.1));
+            scene.fill(Fill::EvenOdd, Affine::IDENTITY, self.color, None, &path);
+            return;
+        }
+
+        // Each side is drawn along the middle of its part of the border.
+        let inner = bounds
+            - Insets::new(
+                widths.left / 2.0,
+                widths.top / 2.0,
+                widths.right / 2.0,
+                widths.bottom / 2.0,
+            );
+        let sides = [
+            (widths.top, (inner.x0, inner.y0), (inner.x1, inner.y0)),
+            (widths.right, (inner.x1, inner.y0), (inner.x1, inner.y1)),
+            (widths.bottom, (inner.x1, inner.y1), (inner.x0, inner.y1)),
+            (widths.left, (inner.x0, inner.y1), (inner.x0, inner.y0)),
+        ];
+        for (width, start, end) in sides {
+            if width > 0.0 {
+                let line = Line::new(start, end);
+                scene.stroke(
+                    &self.stroke(width),
+                    Affine::IDENTITY,
+                    self.color,
+                    None,
+                    &line,
+                );
+            }
+        }
+    }
+
+    fn stroke(&self, width: f64) -> Stroke {
+        match self.style {
+            BorderStyle::Solid => Stroke::new(width),
+            BorderStyle::Dashed(length) => Stroke::new(width).with_dashes(0.0, [length, length]),
+            // Zero-length dashes with round caps are drawn as dots.
+            BorderStyle::Dotted => Stroke::new(width)
+                .with_caps(Cap::Round)
+                .with_dashes(0.0, [0.0, 2.0 * width]),
+        }
+    }
+}
+
+impl BorderWidths {
+    /// The same width on each side.
+    pub const fn uniform(width: f64) -> Self {
+        Self {
+            top: width,
+            right: width,
+            bottom: width,
+            left: width,
+        }
+    }
+
+    /// The width of the `top` and `bottom` sides, and of the `left` and `right` sides.
+    pub const fn symmetric(vertical: f64, horizontal: f64) -> Self {
+        Self {
+            top: vertical,
+            right: horizontal,
+            bottom: vertical,
+            left: horizontal,
+        }
+    }
+
+    /// The width of each side, if they are all the same.
+    pub fn uniform_width(&self) -> Option<f64> {
+        (self.top == self.right && self.top == self.bottom && self.top == self.left)
+            .then_some(self.top)
+    }
+
+    /// The space taken by the border inside the bounds of a widget.
+    pub fn to_insets(&self) -> Insets {
+        Insets::new(self.left, self.top, self.right, self.bottom)
+    }
+}
+
+impl From<f64> for BorderWidths {
+    fn from(width: f64) -> Self {
+        Self::uniform(width)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn widths_to_insets() {
+        let widths = BorderWidths {
+            top: 1.0,
+            right: 2.0,
+            bottom: 3.0,
+            left: 4.0,
+        };
+        assert_eq!(widths.to_insets(), Insets::new(4.0, 1.0, 2.0, 3.0));
+        assert_eq!(widths.uniform_width(), None);
+        assert_eq!(BorderWidths::from(2.0).uniform_width(), Some(2.0));
+        assert_eq!(
+            BorderWidths::symmetric(1.0, 2.0).to_insets(),
+            Insets::uniform_xy(2.0, 1.0)
+        );
+    }
+}
@@ -0,0 +1,124 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Drop shadows drawn behind widgets.
+
+use kurbo::{Insets, Rect, RoundedRect, RoundedRectRadii, Vec2};
+use vello::peniko::Color;
+use vello::Scene;
+
+use crate::paint_scene_helpers::fill_color;
+
+/// The most layers used to approximate the blur of a shadow.
+const MAX_BLUR_LAYERS: usize = 16;
+
+/// A shadow drawn behind a widget, to give it an impression of depth or elevation.
+///
+/// This follows the model of the CSS `box-shadow` property: the shadow has the shape of
+/// the widget, grown by `spread` and moved by `offset`, and its edges are blurred over
+/// a distance of `blur` on each side.
+///
+/// Several shadows can be stacked on the same widget; they are painted in order, so
+/// the first one ends up at the bottom.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BoxShadow {
+    /// How far the shadow is moved from the widget.
+    pub offset: Vec2,
+    /// The distance over which the edges of the shadow fade out.
+    pub blur: f64,
+    /// How much the shadow is grown (or shrunk, if negative) on each side.
+    pub spread: f64,
+    /// The color of the shadow where it isn't faded out.
+    pub color: Color,
+}
+
+impl BoxShadow {
+    /// Create a new shadow.
+    pub fn new(offset: impl Into<Vec2>, blur: f64, spread: f64, color: Color) -> Self {
+        Self {
+            offset: offset.into(),
+            blur: blur.max(0.0),
+            spread,
+            color,
+        }
+    }
+
+    /// How far the shadow extends past each side of the rectangle it's painted for.
+    ///
+    /// Widgets painting shadows pass these to
+    /// [`LayoutCtx::set_paint_insets`](crate::LayoutCtx::set_paint_insets).
+    pub fn paint_insets(&self) -> Insets {
+        let extent = self.spread + self.blur;
+        Insets::new(
+            extent - self.offset.x,
+            extent - self.offset.y,
+            extent + self.offset.x,
+            extent + self.offset.y,
+        )
+        .nonnegative()
+    }
+
+    /// The paint insets covering all of `shadows`.
+    pub(crate) fn union_paint_insets(shadows: &[Self]) -> Insets {
+        shadows
+            .iter()
+            .map(Self::paint_insets)
+            .fold(Insets::ZERO, |a, b| {
+                Insets::new(
+                    a.x0.max(b.x0),
+                    a.y0.max(b.y0),
+                    a.x1.max(b.x1),
+                    a.y1.max(b.y1),
+                )
+            })
+    }
+
+    /// Paint the shadow of a rounded rectangle.
+    ///
+    /// Vello doesn't have a blur filter, so the blur is approximated by stacking
+    /// translucent copies of the shape, each slightly larger than the last.
+    pub fn paint(&self, scene: &mut Scene, rect: Rect, radii: RoundedRectRadii) {
+        let rect = rect + self.offset;
+        let layers = (self.blur.ceil() as usize).clamp(1, MAX_BLUR_LAYERS);
+        // The alpha of each layer, chosen so that where all of them overlap,
+        // the shadow has the alpha of its color.
+        let alpha = 1.0 - (1.0 - self.color.a as f64 / 255.0).powf(1.0 / layers as f64);
+        let color = Color {
+            a: (alpha * 255.0).round() as u8,
+            ..self.color
+        };
+
+        for layer in 0..layers {
+            // Spread the layers evenly from `blur` outside the edge to `blur` inside it.
+            let grow = if layers == 1 {
+                self.spread
+            } else {
+                let t = layer as f64 / (layers - 1) as f64;
+                self.spread + self.blur * (1.0 - 2.0 * t)
+            };
+            let layer_rect = rect.inflate(grow, grow);
+            if layer_rect.width() <= 0.0 || layer_rect.height() <= 0.0 {
+                continue;
+            }
+            // Like in CSS, square corners stay square.
+            let grow_radius = |radius: f64| {
+                if radius > 0.0 {
+                    (radius + grow).max(0.0)
+                } else {
+                    0.0
+                }
+            };
+            let layer_radii = RoundedRectRadii::new(
+                grow_radius(radii.top_left),
+                grow_radius(radii.top_right),
+                grow_radius(radii.bottom_right),
+                grow_radius(radii.bottom_left),
+            );
+            fill_color(
+                scene,
+                &RoundedRect::from_rect(layer_rect, layer_radii),
+                color,
+            );
+        }
+    }
+}
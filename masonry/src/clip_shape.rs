@@ -0,0 +1,84 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Clipping the content of widgets to a shape.
+
+use kurbo::{BezPath, Ellipse, RoundedRectRadii, Shape, Size};
+
+/// A shape the painted content of a widget and its descendants is clipped to.
+///
+/// All shapes except [`Custom`](Self::Custom) are fitted to the layout size of the widget.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ClipShape {
+    /// The layout rectangle of the widget.
+    Rect,
+    /// The layout rectangle of the widget, with rounded corners.
+    RoundedRect(RoundedRectRadii),
+    /// The ellipse inscribed in the layout rectangle of the widget.
+    ///
+    /// For a square widget, this is a circle.
+    Ellipse,
+    /// An arbitrary path, in the coordinate space of the widget.
+    Custom(BezPath),
+}
+
+impl ClipShape {
+    /// The path of this shape for a widget of the given size.
+    pub fn to_path(&self, size: Size) -> BezPath {
+        const TOLERANCE: f64 = 0.1;
+        let rect = size.to_rect();
+        match self {
+            Self::Rect => rect.to_path(TOLERANCE),
+            Self::RoundedRect(radii) => rect.to_rounded_rect(*radii).to_path(TOLERANCE),
+            Self::Ellipse => Ellipse::from_rect(rect).to_path(TOLERANCE),
+            Self::Custom(path) => path.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use kurbo::{Point, Rect};
+
+    use super::*;
+    use crate::widget::Label;
+    use crate::WidgetPod;
+
+    #[test]
+    fn shapes_fit_widget_size() {
+        let size = Size::new(40., 20.);
+        let bounds = Rect::new(0., 0., 40., 20.);
+        for shape in [
+            ClipShape::Rect,
+            ClipShape::RoundedRect(5.0.into()),
+            ClipShape::Ellipse,
+        ] {
+            let path_bounds = shape.to_path(size).bounding_box();
+            assert!(
+                (path_bounds.x0 - bounds.x0).abs() < 1e-6
+                    && (path_bounds.y0 - bounds.y0).abs() < 1e-6
+                    && (path_bounds.x1 - bounds.x1).abs() < 1e-6
+                    && (path_bounds.y1 - bounds.y1).abs() < 1e-6,
+                "{shape:?} has bounds {path_bounds:?}"
+            );
+        }
+        assert!(!ClipShape::Ellipse
+            .to_path(size)
+            .contains(Point::new(1., 1.)));
+
+        let mut triangle = BezPath::new();
+        triangle.move_to((0., 0.));
+        triangle.line_to((100., 0.));
+        triangle.line_to((0., 100.));
+        triangle.close_path();
+        assert_eq!(ClipShape::Custom(triangle.clone()).to_path(size), triangle);
+    }
+
+    #[test]
+    fn clip_is_kept_when_boxed() {
+        let pod = WidgetPod::new(Label::new("hello"))
+            .with_clip(Some(ClipShape::Ellipse))
+            .boxed();
+        assert_eq!(pod.state.clip, Some(ClipShape::Ellipse));
+    }
+}
@@ -0,0 +1,162 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Reading and writing text on the clipboard.
+//!
+//! Some platforms only allow the clipboard to be used from the main thread, so the
+//! functions in this module must be called from the thread running the event loop,
+//! for instance from event handlers and app callbacks. Calls from other threads are
+//! logged as errors and ignored.
+//!
+//! With the `system-clipboard` feature, which is enabled by default, text is copied to
+//! and pasted from the system clipboard. Without it, or if the system clipboard can't be
+//! opened (e.g. without a display server), the clipboard only holds text within the app.
+//! Applications can use another clipboard by implementing [`ClipboardProvider`] and
+//! passing it to [`set_provider`].
+
+use std::cell::RefCell;
+use std::sync::OnceLock;
+use std::thread::{self, ThreadId};
+
+use tracing::error;
+
+/// A place text can be copied to and pasted from.
+pub trait ClipboardProvider {
+    /// The text currently on the clipboard, if any.
+    fn get_text(&mut self) -> Option<String>;
+
+    /// Replace the content of the clipboard with `text`.
+    fn set_text(&mut self, text: String);
+}
+
+/// A clipboard which only holds text within the app.
+#[derive(Debug, Default)]
+pub struct MemoryClipboard {
+    text: Option<String>,
+}
+
+impl ClipboardProvider for MemoryClipboard {
+    fn get_text(&mut self) -> Option<String> {
+        self.text.clone()
+    }
+
+    fn set_text(&mut self, text: String) {
+        self.text = Some(text);
+    }
+}
+
+/// The system clipboard.
+#[cfg(feature = "system-clipboard")]
+pub struct SystemClipboard {
+    clipboard: arboard::Clipboard,
+}
+
+#[cfg(feature = "system-clipboard")]
+impl SystemClipboard {
+    /// Open the system clipboard.
+    pub fn new() -> Result<Self, arboard::Error> {
+        Ok(Self {
+            clipboard: arboard::Clipboard::new()?,
+        })
+    }
+}
+
+#[cfg(feature = "system-clipboard")]
+impl ClipboardProvider for SystemClipboard {
+    fn get_text(&mut self) -> Option<String> {
+        match self.clipboard.get_text() {
+            Ok(text) => Some(text),
+            Err(arboard::Error::ContentNotAvailable) => None,
+            Err(err) => {
+                error!("Couldn't read the clipboard: {err}");
+                None
+            }
+        }
+    }
+
+    fn set_text(&mut self, text: String) {
+        if let Err(err) = self.clipboard.set_text(text) {
+            error!("Couldn't write to the clipboard: {err}");
+        }
+    }
+}
+
+/// The provider used until one is set.
+fn default_provider() -> Box<dyn ClipboardProvider> {
+    #[cfg(feature = "system-clipboard")]
+    match SystemClipboard::new() {
+        Ok(clipboard) => return Box::new(clipboard),
+        Err(err) => {
+            tracing::warn!(
+                "Couldn't open the system clipboard, only copying within the app: {err}"
+            );
+        }
+    }
+    Box::new(MemoryClipboard::default())
+}
+
+static MAIN_THREAD: OnceLock<ThreadId> = OnceLock::new();
+
+thread_local! {
+    static PROVIDER: RefCell<Box<dyn ClipboardProvider>> = RefCell::new(default_provider());
+}
+
+/// Record the current thread as the only one allowed to use the clipboard.
+///
+/// This is called when the event loop starts.
+pub(crate) fn set_main_thread() {
+    let _ = MAIN_THREAD.set(thread::current().id());
+}
+
+/// Returns `false` and logs an error if called from another thread than the main one.
+///
+/// Before the event loop has started, e.g. in tests, any thread is allowed.
+fn check_thread(operation: &str) -> bool {
+    match MAIN_THREAD.get() {
+        Some(main_thread) if *main_thread != thread::current().id() => {
+            error!("Clipboard {operation} from a thread other than the main thread, ignoring.");
+            false
+        }
+        _ => true,
+    }
+}
+
+/// Use `provider` for all subsequent clipboard operations.
+pub fn set_provider(provider: impl ClipboardProvider + 'static) {
+    if check_thread("provider change") {
+        // This doesn't open the default clipboard if it isn't open yet.
+        PROVIDER.set(Box::new(provider));
+    }
+}
+
+/// The text currently on the clipboard, if any.
+pub fn get_text() -> Option<String> {
+    if !check_thread("read") {
+        return None;
+    }
+    PROVIDER.with(|provider| provider.borrow_mut().get_text())
+}
+
+/// Replace the content of the clipboard with `text`.
+pub fn set_text(text: impl Into<String>) {
+    if check_thread("write") {
+        PROVIDER.with(|provider| provider.borrow_mut().set_text(text.into()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn memory_clipboard_starts_empty() {
+        assert_eq!(MemoryClipboard::default().get_text(), None);
+    }
+
+    #[test]
+    fn memory_clipboard_round_trip() {
+        set_provider(MemoryClipboard::default());
+        set_text("hello");
+        assert_eq!(get_text().as_deref(), Some("hello"));
+    }
+}
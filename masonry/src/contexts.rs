@@ -9,14 +9,20 @@ use std::time::Duration;
 use accesskit::{NodeBuilder, TreeUpdate};
 use parley::FontContext;
 use tracing::{trace, warn};
+use vello::Scene;
 use winit::dpi::LogicalPosition;
 
 use crate::action::Action;
 use crate::promise::PromiseToken;
-use crate::render_root::{RenderRootSignal, RenderRootState};
+use crate::render_root::{Overlay, RenderRootSignal, RenderRootState};
+use crate::text2::IconGlyph;
 use crate::text_helpers::{ImeChangeSignal, TextFieldRegistration};
+use crate::theme::Theme;
 use crate::widget::{CursorChange, WidgetMut, WidgetState};
-use crate::{CursorIcon, Insets, Point, Rect, Size, Widget, WidgetId, WidgetPod};
+use crate::{
+    ClipShape, Cursor, CursorIcon, CustomCursorImage, Insets, Point, Rect, Size, Widget, WidgetId,
+    WidgetPod,
+};
 
 /// A macro for implementing methods on multiple contexts.
 ///
@@ -92,7 +98,6 @@ pub struct AccessCtx<'a> {
     pub(crate) tree_update: &'a mut TreeUpdate,
     pub(crate) current_node: NodeBuilder,
     pub(crate) rebuild_all: bool,
-    pub(crate) scale_factor: f64,
 }
 
 pub struct WorkerCtx<'a> {
@@ -116,6 +121,34 @@ impl_context_method!(
             self.widget_state.id
         }
 
+        /// The colors widgets are painted with, unless they were given colors of their own.
+        pub fn theme(&self) -> &Theme {
+            &self.global_state.theme
+        }
+
+        /// Whether the user asked for high contrast, in the platform's accessibility settings.
+        ///
+        /// Widgets which draw colors of their own, rather than the [theme's](Self::theme),
+        /// can use this to make them more legible.
+        ///
+        /// The setting is read on Windows, macOS, and on Linux through the XDG settings
+        /// portal, when the window is created, when the platform's theme changes, and
+        /// whenever the window is focused.
+        pub fn high_contrast(&self) -> bool {
+            self.global_state.high_contrast
+        }
+
+        /// Whether the user asked for less motion, in the platform's accessibility settings.
+        ///
+        /// Widgets should then skip animations and transitions which aren't needed to
+        /// understand the UI, and show their end state right away. Of the widgets in this
+        /// crate, only [`Spinner`](crate::widget::Spinner) animates, and it stands still.
+        ///
+        /// The setting is read at the same times as [`high_contrast`](Self::high_contrast).
+        pub fn reduced_motion(&self) -> bool {
+            self.global_state.reduced_motion
+        }
+
         /// Skip iterating over the given child.
         ///
         /// Normally, container widgets are supposed to iterate over each of their
@@ -248,24 +281,75 @@ impl_context_method!(
         pub fn is_stashed(&self) -> bool {
             self.widget_state.is_stashed
         }
+
+        /// The opacity this widget and its descendants are painted with, between 0 and 1.
+        ///
+        /// This doesn't take the opacity of ancestors into account.
+        pub fn opacity(&self) -> f64 {
+            self.widget_state.opacity
+        }
+
+        /// The shape this widget and its descendants are clipped to when painted, if any.
+        pub fn clip(&self) -> Option<&ClipShape> {
+            self.widget_state.clip.as_ref()
+        }
+
+        /// How far the painted content of this widget and its descendants is blurred, in pixels.
+        pub fn blur(&self) -> f64 {
+            self.widget_state.blur
+        }
     }
 );
 
 impl_context_method!(EventCtx<'_>, {
+    /// The text currently on the clipboard, if any.
+    ///
+    /// See the [`clipboard`](crate::clipboard) module for details.
+    pub fn get_clipboard(&self) -> Option<String> {
+        crate::clipboard::get_text()
+    }
+
+    /// Replace the content of the clipboard with `text`.
+    ///
+    /// See the [`clipboard`](crate::clipboard) module for details.
+    pub fn set_clipboard(&mut self, text: impl Into<String>) {
+        crate::clipboard::set_text(text);
+    }
+});
+
+impl_context_method!(EventCtx<'_>, LifeCycleCtx<'_>, {
     /// Set the cursor icon.
     ///
     /// This setting will be retained until [`clear_cursor`] is called, but it will only take
     /// effect when this widget is either [`hot`] or [`active`]. If a child widget also sets a
-    /// cursor, the child widget's cursor will take precedence. (If that isn't what you want, use
+    /// cursor, the child widget's cursor will take precedence, so the innermost widget under
+    /// the pointer decides which cursor is shown. (If that isn't what you want, use
     /// [`override_cursor`] instead.)
     ///
+    /// Widgets which always show the same cursor, such as buttons, can call this once when
+    /// handling [`LifeCycle::WidgetAdded`]. The cursor is updated on the next pointer event.
+    ///
+    /// [`LifeCycle::WidgetAdded`]: crate::LifeCycle::WidgetAdded
     /// [`clear_cursor`]: EventCtx::clear_cursor
     /// [`override_cursor`]: EventCtx::override_cursor
     /// [`hot`]: EventCtx::is_hot
     /// [`active`]: EventCtx::is_active
     pub fn set_cursor(&mut self, cursor: &CursorIcon) {
         trace!("set_cursor {:?}", cursor);
-        self.widget_state.cursor_change = CursorChange::Set(*cursor);
+        self.widget_state.cursor_change = CursorChange::Set(Cursor::Icon(*cursor));
+    }
+
+    /// Set the cursor to a custom image.
+    ///
+    /// This behaves like [`set_cursor`]: the cursor is only shown while this widget is [`hot`]
+    /// or [`active`], and is reset when the pointer leaves it.
+    ///
+    /// [`set_cursor`]: EventCtx::set_cursor
+    /// [`hot`]: EventCtx::is_hot
+    /// [`active`]: EventCtx::is_active
+    pub fn set_custom_cursor(&mut self, image: &CustomCursorImage) {
+        trace!("set_custom_cursor {:?}", image);
+        self.widget_state.cursor_change = CursorChange::Set(Cursor::Custom(image.clone()));
     }
 
     /// Override the cursor icon.
@@ -275,12 +359,12 @@ impl_context_method!(EventCtx<'_>, {
     /// preferences of a child widget. (If that isn't what you want, use [`set_cursor`] instead.)
     ///
     /// [`clear_cursor`]: EventCtx::clear_cursor
-    /// [`set_cursor`]: EventCtx::override_cursor
+    /// [`set_cursor`]: EventCtx::set_cursor
     /// [`hot`]: EventCtx::is_hot
     /// [`active`]: EventCtx::is_active
     pub fn override_cursor(&mut self, cursor: &CursorIcon) {
         trace!("override_cursor {:?}", cursor);
-        self.widget_state.cursor_change = CursorChange::Override(*cursor);
+        self.widget_state.cursor_change = CursorChange::Override(Cursor::Icon(*cursor));
     }
 
     /// Clear the cursor icon.
@@ -358,6 +442,7 @@ impl_context_method!(WidgetCtx<'_>, EventCtx<'_>, LifeCycleCtx<'_>, {
     pub fn request_paint(&mut self) {
         trace!("request_paint");
         self.widget_state.needs_paint = true;
+        self.widget_state.is_damaged = true;
     }
 
     /// Request a layout pass.
@@ -412,6 +497,48 @@ impl_context_method!(WidgetCtx<'_>, EventCtx<'_>, LifeCycleCtx<'_>, {
         self.widget_state.is_explicitly_disabled_new = disabled;
     }
 
+    /// Set the opacity this widget and its descendants are painted with.
+    ///
+    /// `opacity` is clamped to the range `[0, 1]`, where 0 is fully transparent.
+    /// A fully transparent widget still takes part in layout and receives events.
+    pub fn set_opacity(&mut self, opacity: f64) {
+        let opacity = opacity.clamp(0.0, 1.0);
+        if opacity != self.widget_state.opacity {
+            self.widget_state.opacity = opacity;
+            // The opacity is applied when the parent paints this widget.
+            self.request_paint();
+        }
+    }
+
+    /// Clip the painted content of this widget and its descendants to `clip`.
+    ///
+    /// Pass `None` to stop clipping. This doesn't affect layout or which widgets
+    /// receive pointer events.
+    pub fn set_clip(&mut self, clip: Option<ClipShape>) {
+        if clip != self.widget_state.clip {
+            self.widget_state.clip = clip;
+            // The clip is applied when the parent paints this widget.
+            self.request_paint();
+        }
+    }
+
+    /// Blur the painted content of this widget and its descendants by `radius` pixels.
+    ///
+    /// A radius of 0 disables the blur; negative values are treated as 0.
+    /// Only this widget's own content is blurred, not what is painted behind it.
+    ///
+    /// Vello has no blur filter, so the blur is approximated by painting the content
+    /// a fixed number of times at offsets within `radius`. The result is reused until
+    /// the content or the radius change, but it's still expensive for large subtrees.
+    pub fn set_blur(&mut self, radius: f64) {
+        let radius = radius.max(0.0);
+        if radius != self.widget_state.blur {
+            self.widget_state.blur = radius;
+            // The blur is applied when the parent paints this widget.
+            self.request_paint();
+        }
+    }
+
     /// Mark child widget as stashed.
     ///
     /// **Note:** Stashed widgets are a WIP feature
@@ -442,6 +569,10 @@ impl_context_method!(
         ///
         /// Note: Actions are still a WIP feature.
         pub fn submit_action(&mut self, action: Action) {
+            if self.global_state.measuring {
+                trace!("submit_action: dropped while measuring");
+                return;
+            }
             trace!("submit_action");
             self.global_state
                 .signal_queue
@@ -503,6 +634,35 @@ impl EventCtx<'_> {
         // TODO: plumb mouse grab through to platform (through druid-shell)
     }
 
+    /// Capture the pointer, so that this widget receives all pointer events, wherever the
+    /// pointer is, until it calls [`release_pointer`](Self::release_pointer).
+    ///
+    /// This is meant for drag interactions, such as moving a slider's thumb: while the
+    /// pointer is captured, other widgets don't receive pointer events or become hot, and
+    /// overlays are ignored. The capture is released automatically if this widget is
+    /// removed from the tree.
+    pub fn capture_pointer(&mut self) {
+        trace!("capture_pointer");
+        self.global_state.pointer_capture = Some(self.widget_state.id);
+    }
+
+    /// Release the pointer captured with [`capture_pointer`](Self::capture_pointer).
+    ///
+    /// This does nothing if another widget has captured the pointer.
+    pub fn release_pointer(&mut self) {
+        trace!("release_pointer");
+        if self.has_pointer_capture() {
+            self.global_state.pointer_capture = None;
+        }
+    }
+
+    /// Returns `true` if this widget has captured the pointer.
+    ///
+    /// See [`capture_pointer`](Self::capture_pointer).
+    pub fn has_pointer_capture(&self) -> bool {
+        self.global_state.pointer_capture == Some(self.widget_state.id)
+    }
+
     /// Set the event as "handled", which stops its propagation to other
     /// widgets.
     pub fn set_handled(&mut self) {
@@ -540,6 +700,18 @@ impl EventCtx<'_> {
         self.global_state.next_focused_widget = Some(target);
     }
 
+    /// Change the title of the window this widget is in.
+    ///
+    /// The title is applied once the current event has been handled. If this is called
+    /// several times during the same event, only the last title is used.
+    pub fn set_window_title(&mut self, title: impl Into<String>) {
+        let title = title.into();
+        trace!("set_window_title {:?}", title);
+        let signal_queue = &mut self.global_state.signal_queue;
+        signal_queue.retain(|signal| !matches!(signal, RenderRootSignal::SetTitle(_)));
+        signal_queue.push_back(RenderRootSignal::SetTitle(title));
+    }
+
     /// Give up focus.
     ///
     /// This should only be called by a widget that currently has focus.
@@ -602,6 +774,18 @@ impl LifeCycleCtx<'_> {
 }
 
 impl LayoutCtx<'_> {
+    /// Returns the icon with the given name from the registered icon fonts.
+    pub(crate) fn icon_glyph(&self, name: &str) -> Option<IconGlyph> {
+        self.global_state.icons.get(name).cloned()
+    }
+
+    /// The disabled state of the widget, see [`EventCtx::is_disabled`].
+    ///
+    /// Widgets which pick their colors during layout, such as text widgets, use this.
+    pub fn is_disabled(&self) -> bool {
+        self.widget_state.is_disabled()
+    }
+
     /// Set explicit paint [`Insets`] for this widget.
     ///
     /// You are not required to set explicit paint bounds unless you need
@@ -616,9 +800,61 @@ impl LayoutCtx<'_> {
     pub fn set_paint_insets(&mut self, insets: impl Into<Insets>) {
         let insets = insets.into();
         trace!("set_paint_insets {:?}", insets);
+        // The insets are set again once the widget is laid out for real.
+        if self.global_state.measuring {
+            return;
+        }
         self.widget_state.paint_insets = insets.nonnegative();
     }
 
+    /// Open an overlay for this widget, or move the existing one, to `rect`.
+    ///
+    /// Overlays are meant for popups, such as menus and tooltips. Their content is set with
+    /// [`PaintCtx::paint_overlay`], and is drawn above every other widget.
+    ///
+    /// `rect` is in this widget's coordinate space, and may lie outside of its layout rect.
+    /// Pointer events inside `rect` are sent to this widget, even if it isn't hot, and aren't
+    /// sent to the widgets below the overlay.
+    ///
+    /// The overlay stays open until [`close_overlay`](Self::close_overlay) is called, or
+    /// until the user clicks outside of it, in which case the widget receives
+    /// [`LifeCycle::OverlayDismissed`].
+    ///
+    /// [`LifeCycle::OverlayDismissed`]: crate::LifeCycle::OverlayDismissed
+    pub fn set_overlay_rect(&mut self, rect: Rect) {
+        trace!("set_overlay_rect {}", rect);
+        self.set_overlay(rect, false);
+    }
+
+    /// Open a passive overlay for this widget, or move the existing one, to `rect`.
+    ///
+    /// This works like [`set_overlay_rect`](Self::set_overlay_rect), except that the overlay
+    /// is only painted: pointer events go to the widgets below it, and clicks elsewhere don't
+    /// dismiss it. This is meant for content the user doesn't interact with, such as tooltips.
+    pub fn set_passive_overlay_rect(&mut self, rect: Rect) {
+        trace!("set_passive_overlay_rect {}", rect);
+        self.set_overlay(rect, true);
+    }
+
+    fn set_overlay(&mut self, rect: Rect, passive: bool) {
+        if self.global_state.measuring {
+            return;
+        }
+        let id = self.widget_state.id;
+        let overlays = &mut self.global_state.overlays;
+        if let Some(overlay) = overlays.iter_mut().find(|o| o.owner == id) {
+            overlay.rect = rect;
+            overlay.passive = passive;
+        } else {
+            overlays.push(Overlay {
+                owner: id,
+                rect,
+                scene: Scene::new(),
+                passive,
+            });
+        }
+    }
+
     /// Set an explicit baseline position for this widget.
     ///
     /// The baseline position is used to align widgets that contain text,
@@ -630,6 +866,10 @@ impl LayoutCtx<'_> {
     /// widget to the baseline.
     pub fn set_baseline_offset(&mut self, baseline: f64) {
         trace!("set_baseline_offset {}", baseline);
+        // The baseline is set again once the widget is laid out for real.
+        if self.global_state.measuring {
+            return;
+        }
         self.widget_state.baseline_offset = baseline;
     }
 
@@ -638,12 +878,22 @@ impl LayoutCtx<'_> {
     ///
     /// Container widgets must call this method with each non-stashed child in their
     /// layout method, after calling `child.layout(...)`.
+    ///
+    /// The origin is rounded to the nearest physical pixel.
     pub fn place_child(&mut self, child: &mut WidgetPod<impl Widget>, origin: Point) {
+        child.state.is_expecting_place_child_call = false;
+        // The child is placed again once it's laid out for real.
+        if self.global_state.measuring {
+            return;
+        }
+        // Fractional scale factors would otherwise leave the child's edges between pixels.
+        let origin = self.global_state.scale_factor.snap_point(origin);
         if origin != child.state.origin {
             child.state.origin = origin;
             child.state.needs_window_origin = true;
+            // The child's fragment is appended to ours at its origin.
+            self.widget_state.needs_paint = true;
         }
-        child.state.is_expecting_place_child_call = false;
 
         self.widget_state.local_paint_rect =
             self.widget_state.local_paint_rect.union(child.paint_rect());
@@ -662,6 +912,50 @@ impl LayoutCtx<'_> {
     }
 }
 
+// --- Overlays ---
+
+impl_context_method!(
+    WidgetCtx<'_>,
+    EventCtx<'_>,
+    LifeCycleCtx<'_>,
+    LayoutCtx<'_>,
+    PaintCtx<'_>,
+    {
+        /// Whether this widget currently has an overlay.
+        ///
+        /// See [`LayoutCtx::set_overlay_rect`].
+        pub fn has_overlay(&self) -> bool {
+            let id = self.widget_state.id;
+            self.global_state
+                .overlays
+                .iter()
+                .any(|overlay| overlay.owner == id)
+        }
+
+        /// Remove this widget's overlay, if it has one.
+        ///
+        /// See [`LayoutCtx::set_overlay_rect`].
+        pub fn close_overlay(&mut self) {
+            trace!("close_overlay");
+            let id = self.widget_state.id;
+            let Some(idx) = self
+                .global_state
+                .overlays
+                .iter()
+                .position(|o| o.owner == id)
+            else {
+                return;
+            };
+            let overlay = self.global_state.overlays.remove(idx);
+            let window_origin = self.widget_state.window_origin().to_vec2();
+            self.global_state.add_damage(overlay.rect + window_origin);
+            self.global_state
+                .signal_queue
+                .push_back(RenderRootSignal::RequestRedraw);
+        }
+    }
+);
+
 impl_context_method!(LayoutCtx<'_>, PaintCtx<'_>, {
     pub fn font_ctx(&mut self) -> &mut FontContext {
         &mut self.global_state.font_context
@@ -680,6 +974,29 @@ impl PaintCtx<'_> {
     pub fn depth(&self) -> u32 {
         self.depth
     }
+
+    /// Set the content of this widget's overlay.
+    ///
+    /// The scene is in this widget's coordinate space. It is painted above every other
+    /// widget, and isn't clipped by this widget's ancestors.
+    ///
+    /// This has no effect unless [`LayoutCtx::set_overlay_rect`] was called first.
+    pub fn paint_overlay(&mut self, scene: Scene) {
+        let id = self.widget_state.id;
+        match self
+            .global_state
+            .overlays
+            .iter_mut()
+            .find(|o| o.owner == id)
+        {
+            Some(overlay) => {
+                overlay.scene = scene;
+                let rect = overlay.rect + self.widget_state.window_origin().to_vec2();
+                self.global_state.add_damage(rect);
+            }
+            None => warn!("paint_overlay called on widget {id:?}, which has no overlay"),
+        }
+    }
 }
 
 impl AccessCtx<'_> {
@@ -0,0 +1,124 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Mouse cursors which widgets can request.
+
+use std::sync::Arc;
+
+use winit::window::{BadImage, CustomCursor, CustomCursorSource};
+
+use crate::CursorIcon;
+
+/// The mouse cursor shown while the pointer is over a widget.
+///
+/// See [`EventCtx::set_cursor`](crate::EventCtx::set_cursor) and
+/// [`EventCtx::set_custom_cursor`](crate::EventCtx::set_custom_cursor).
+#[derive(Clone, Debug, PartialEq)]
+pub enum Cursor {
+    /// One of the cursors provided by the platform.
+    Icon(CursorIcon),
+    /// A cursor made from an image.
+    Custom(CustomCursorImage),
+}
+
+/// An RGBA image which can be used as the mouse cursor.
+///
+/// The image data is reference-counted, so cloning this is cheap.
+#[derive(Clone)]
+pub struct CustomCursorImage {
+    rgba: Arc<[u8]>,
+    width: u16,
+    height: u16,
+    hotspot_x: u16,
+    hotspot_y: u16,
+}
+
+impl Default for Cursor {
+    fn default() -> Self {
+        Cursor::Icon(CursorIcon::Default)
+    }
+}
+
+impl From<CursorIcon> for Cursor {
+    fn from(icon: CursorIcon) -> Self {
+        Cursor::Icon(icon)
+    }
+}
+
+impl From<CustomCursorImage> for Cursor {
+    fn from(image: CustomCursorImage) -> Self {
+        Cursor::Custom(image)
+    }
+}
+
+impl CustomCursorImage {
+    /// Create a cursor image from non-premultiplied RGBA8 pixels.
+    ///
+    /// The hotspot is the pixel of the image which is placed at the pointer position.
+    ///
+    /// Returns an error if `rgba` doesn't have `width * height * 4` bytes, if the image is
+    /// too large, or if the hotspot is outside the image.
+    pub fn from_rgba(
+        rgba: impl Into<Vec<u8>>,
+        width: u16,
+        height: u16,
+        hotspot_x: u16,
+        hotspot_y: u16,
+    ) -> Result<Self, BadImage> {
+        let rgba = rgba.into();
+        // Validate the image now, so that errors are reported where the cursor is created
+        // rather than when the event loop tries to use it.
+        CustomCursor::from_rgba(rgba.as_slice(), width, height, hotspot_x, hotspot_y)?;
+        Ok(Self {
+            rgba: rgba.into(),
+            width,
+            height,
+            hotspot_x,
+            hotspot_y,
+        })
+    }
+
+    /// The width and height of the image, in pixels.
+    pub fn size(&self) -> (u16, u16) {
+        (self.width, self.height)
+    }
+
+    /// The position of the hotspot within the image, in pixels.
+    pub fn hotspot(&self) -> (u16, u16) {
+        (self.hotspot_x, self.hotspot_y)
+    }
+
+    pub(crate) fn to_source(&self) -> CustomCursorSource {
+        CustomCursor::from_rgba(
+            &*self.rgba,
+            self.width,
+            self.height,
+            self.hotspot_x,
+            self.hotspot_y,
+        )
+        .expect("image was validated in CustomCursorImage::from_rgba")
+    }
+}
+
+/// Two images are equal if they share the same pixel buffer.
+///
+/// Comparing the pixels themselves would be too costly, as cursors are compared on every
+/// pointer event.
+impl PartialEq for CustomCursorImage {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.rgba, &other.rgba)
+            && self.size() == other.size()
+            && self.hotspot() == other.hotspot()
+    }
+}
+
+impl std::fmt::Debug for CustomCursorImage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CustomCursorImage")
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .field("hotspot_x", &self.hotspot_x)
+            .field("hotspot_y", &self.hotspot_y)
+            .finish_non_exhaustive()
+    }
+}
@@ -3,12 +3,16 @@
 
 //! Events.
 
-use crate::kurbo::Rect;
+use crate::kurbo::{Rect, Vec2};
 // TODO - See issue #14
-use crate::WidgetId;
+use crate::{ScaleCoefficient, WidgetId};
 
+use std::time::Duration;
 use std::{collections::HashSet, path::PathBuf};
 
+// Automatically defaults to std::time::Instant on non Wasm platforms
+use instant::Instant;
+
 use accesskit::{Action, ActionData};
 use winit::dpi::{LogicalPosition, PhysicalPosition, PhysicalSize};
 use winit::event::{Ime, KeyEvent, Modifiers, MouseButton};
@@ -24,7 +28,7 @@ use winit::keyboard::ModifiersState;
 // (this will help in cases where we want to skip anim frames)
 #[derive(Debug, Clone)]
 pub enum WindowEvent {
-    Rescale(f64),
+    Rescale(ScaleCoefficient),
     Resize(PhysicalSize<u32>),
     AnimFrame,
     RebuildAccessTree,
@@ -41,12 +45,45 @@ pub enum PointerEvent {
     PointerMove(PointerState),
     PointerEnter(PointerState),
     PointerLeave(PointerState),
-    MouseWheel(LogicalPosition<f64>, PointerState),
+    MouseWheel(ScrollDelta, PointerState),
     HoverFile(PathBuf, PointerState),
     DropFile(PathBuf, PointerState),
     HoverFileCancel(PointerState),
 }
 
+/// How far a [`PointerEvent::MouseWheel`] scrolled.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScrollDelta {
+    /// A number of lines, from a mouse wheel which scrolls in steps.
+    ///
+    /// This already accounts for the number of lines the platform scrolls for each step
+    /// of the wheel. Scrolling widgets decide how tall a line is.
+    Lines(Vec2),
+    /// A number of logical pixels, from a touchpad or a mouse wheel which scrolls smoothly.
+    Precise(Vec2),
+}
+
+impl ScrollDelta {
+    /// The distance scrolled in logical pixels, with lines `line_height` pixels tall.
+    pub fn to_pixels(self, line_height: f64) -> Vec2 {
+        match self {
+            ScrollDelta::Lines(lines) => lines * line_height,
+            ScrollDelta::Precise(pixels) => pixels,
+        }
+    }
+}
+
+impl std::ops::Neg for ScrollDelta {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        match self {
+            ScrollDelta::Lines(lines) => ScrollDelta::Lines(-lines),
+            ScrollDelta::Precise(pixels) => ScrollDelta::Precise(-pixels),
+        }
+    }
+}
+
 // TODO - Clipboard Paste?
 // TODO skip is_synthetic=true events
 #[derive(Debug, Clone)]
@@ -74,8 +111,78 @@ pub struct PointerState {
     pub position: LogicalPosition<f64>,
     pub buttons: HashSet<MouseButton>,
     pub mods: Modifiers,
+    /// The number of consecutive clicks of the button, in [`PointerEvent::PointerDown`]
+    /// and [`PointerEvent::PointerUp`] events: 1 for a single click, 2 for a double click,
+    /// and so on.
+    ///
+    /// See [`ClickSettings`] for how clicks are counted.
     pub count: u8,
     pub focus: bool,
+    /// The force applied by the pointer, normalized between `0.0` and `1.0`.
+    ///
+    /// This is `None` on devices which don't report pressure.
+    pub force: Option<f64>,
+}
+
+/// How close in time and space consecutive clicks must be to be counted as a double
+/// (or triple, etc.) click.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ClickSettings {
+    /// The longest time between the presses of two consecutive clicks.
+    pub interval: Duration,
+    /// The farthest the pointer can move between two consecutive clicks, in logical pixels.
+    pub distance: f64,
+}
+
+impl Default for ClickSettings {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_millis(500),
+            distance: 4.0,
+        }
+    }
+}
+
+/// Counts consecutive clicks of the same button.
+#[derive(Debug, Default)]
+pub(crate) struct ClickCounter {
+    pub(crate) settings: ClickSettings,
+    last_press: Option<(MouseButton, LogicalPosition<f64>, Instant)>,
+    count: u8,
+}
+
+impl ClickCounter {
+    /// Record a press of `button`, and return the number of consecutive clicks it makes.
+    pub(crate) fn press(
+        &mut self,
+        button: MouseButton,
+        position: LogicalPosition<f64>,
+        now: Instant,
+    ) -> u8 {
+        let continues = self
+            .last_press
+            .is_some_and(|(last_button, last_position, time)| {
+                let moved = (position.x - last_position.x).hypot(position.y - last_position.y);
+                last_button == button
+                    && now.saturating_duration_since(time) <= self.settings.interval
+                    && moved <= self.settings.distance
+            });
+        self.count = if continues {
+            self.count.saturating_add(1)
+        } else {
+            1
+        };
+        self.last_press = Some((button, position, now));
+        self.count
+    }
+
+    /// The number of consecutive clicks ending with a release of `button`.
+    pub(crate) fn release(&self, button: MouseButton) -> u8 {
+        match self.last_press {
+            Some((last_button, _, _)) if last_button == button => self.count,
+            _ => 1,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -150,6 +257,13 @@ pub enum LifeCycle {
     /// [`EventCtx::request_pan_to_this`](crate::EventCtx::request_pan_to_this).
     RequestPanToChild(Rect),
 
+    /// Sent to a widget when its overlay was dismissed by a click outside of it.
+    ///
+    /// The overlay has already been removed when this is received.
+    ///
+    /// See [`LayoutCtx::set_overlay_rect`](crate::LayoutCtx::set_overlay_rect).
+    OverlayDismissed,
+
     /// Internal Masonry lifecycle event.
     ///
     /// This should always be passed down to descendant [`WidgetPod`]s.
@@ -180,6 +294,12 @@ pub enum InternalLifeCycle {
     /// Used to route the `DisabledChanged` event to the required widgets.
     RouteDisabledChanged,
 
+    /// Used to route the `OverlayDismissed` event to the owner of the overlay.
+    RouteOverlayDismissed {
+        /// the widget which painted the overlay
+        owner: WidgetId,
+    },
+
     /// The parents widget origin in window coordinate space has changed.
     ParentWindowOrigin {
         mouse_pos: Option<LogicalPosition<f64>>,
@@ -227,6 +347,23 @@ impl PointerEvent {
         }
     }
 
+    /// The button which was pressed or released, if any.
+    pub fn button(&self) -> Option<MouseButton> {
+        match self {
+            PointerEvent::PointerDown(button, _) | PointerEvent::PointerUp(button, _) => {
+                Some(*button)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if this is a press of the secondary (usually right) mouse button.
+    ///
+    /// Widgets should open their context menu in response to this event.
+    pub fn is_secondary_button_press(&self) -> bool {
+        matches!(self, PointerEvent::PointerDown(MouseButton::Right, _))
+    }
+
     pub fn short_name(&self) -> &'static str {
         match self {
             PointerEvent::PointerDown(_, _) => "PointerDown",
@@ -278,6 +415,21 @@ impl TextEvent {
 }
 
 impl AccessEvent {
+    /// The value requested by a [`SetValue`](accesskit::Action::SetValue) action, if it is
+    /// a number.
+    ///
+    /// Text values are parsed as numbers, since some platforms only send those.
+    pub fn numeric_value(&self) -> Option<f64> {
+        if self.action != accesskit::Action::SetValue {
+            return None;
+        }
+        match self.data.as_ref()? {
+            ActionData::NumericValue(value) => Some(*value),
+            ActionData::Value(text) => text.trim().parse().ok(),
+            _ => None,
+        }
+    }
+
     pub fn short_name(&self) -> &'static str {
         match self.action {
             accesskit::Action::Default => "Default",
@@ -328,6 +480,7 @@ impl PointerState {
             mods: Default::default(),
             count: 0,
             focus: false,
+            force: None,
         }
     }
 }
@@ -350,6 +503,7 @@ impl LifeCycle {
             LifeCycle::DisabledChanged(_) => true,
             LifeCycle::BuildFocusChain => false,
             LifeCycle::RequestPanToChild(_) => false,
+            LifeCycle::OverlayDismissed => false,
         }
     }
 
@@ -362,6 +516,7 @@ impl LifeCycle {
                 InternalLifeCycle::RouteWidgetAdded => "RouteWidgetAdded",
                 InternalLifeCycle::RouteFocusChanged { .. } => "RouteFocusChanged",
                 InternalLifeCycle::RouteDisabledChanged => "RouteDisabledChanged",
+                InternalLifeCycle::RouteOverlayDismissed { .. } => "RouteOverlayDismissed",
                 InternalLifeCycle::ParentWindowOrigin { .. } => "ParentWindowOrigin",
             },
             LifeCycle::WidgetAdded => "WidgetAdded",
@@ -369,6 +524,7 @@ impl LifeCycle {
             LifeCycle::DisabledChanged(_) => "DisabledChanged",
             LifeCycle::BuildFocusChain => "BuildFocusChain",
             LifeCycle::RequestPanToChild(_) => "RequestPanToChild",
+            LifeCycle::OverlayDismissed => "OverlayDismissed",
         }
     }
 }
@@ -386,8 +542,37 @@ impl InternalLifeCycle {
         match self {
             InternalLifeCycle::RouteWidgetAdded
             | InternalLifeCycle::RouteFocusChanged { .. }
-            | InternalLifeCycle::RouteDisabledChanged => true,
+            | InternalLifeCycle::RouteDisabledChanged
+            | InternalLifeCycle::RouteOverlayDismissed { .. } => true,
             InternalLifeCycle::ParentWindowOrigin { .. } => false,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn click_counter_groups_close_clicks() {
+        let mut counter = ClickCounter::default();
+        let start = Instant::now();
+        let at = |ms: u64| start + Duration::from_millis(ms);
+        let origin = LogicalPosition::new(10.0, 10.0);
+
+        assert_eq!(counter.press(MouseButton::Left, origin, at(0)), 1);
+        assert_eq!(counter.release(MouseButton::Left), 1);
+        assert_eq!(counter.press(MouseButton::Left, origin, at(200)), 2);
+        assert_eq!(counter.release(MouseButton::Left), 2);
+        assert_eq!(counter.press(MouseButton::Left, origin, at(400)), 3);
+
+        // Too late.
+        assert_eq!(counter.press(MouseButton::Left, origin, at(1000)), 1);
+        // Too far.
+        let far = LogicalPosition::new(20.0, 10.0);
+        assert_eq!(counter.press(MouseButton::Left, far, at(1100)), 1);
+        // Another button.
+        assert_eq!(counter.press(MouseButton::Right, far, at(1200)), 1);
+        assert_eq!(counter.release(MouseButton::Left), 1);
+    }
+}
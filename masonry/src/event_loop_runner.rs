@@ -1,33 +1,50 @@
 // Copyright 2024 the Xilem Authors
 // SPDX-License-Identifier: Apache-2.0
 
+use std::any::Any;
 use std::num::NonZeroUsize;
-use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::Duration;
 
 use accesskit_winit::Adapter;
+// Automatically defaults to std::time::Instant on non Wasm platforms
+use instant::Instant;
 use tracing::subscriber::SetGlobalDefaultError;
-use tracing::{debug, warn};
-use vello::kurbo::Affine;
+use tracing::{debug, error, trace, warn};
 use vello::util::{RenderContext, RenderSurface};
-use vello::{peniko::Color, AaSupport, RenderParams, Renderer, RendererOptions, Scene};
-use wgpu::PresentMode;
+use vello::{AaConfig, AaSupport, RenderParams, Renderer, RendererOptions, Scene};
+pub use wgpu::PresentMode;
+use wgpu::{
+    Backends, CompositeAlphaMode, Device, DeviceDescriptor, DeviceLostReason, Features, Instance,
+    InstanceDescriptor, Limits, PowerPreference, Queue, RequestAdapterOptions,
+    SurfaceConfiguration, SurfaceError, TextureFormat, TextureUsages,
+};
 use winit::application::ApplicationHandler;
 use winit::dpi::LogicalPosition;
 use winit::error::EventLoopError;
-use winit::event::WindowEvent as WinitWindowEvent;
-use winit::event_loop::{ActiveEventLoop, EventLoopProxy};
-use winit::window::{Window, WindowAttributes, WindowId};
+use winit::event::{MouseButton, StartCause, WindowEvent as WinitWindowEvent};
+use winit::event_loop::{ActiveEventLoop, ControlFlow, EventLoopProxy};
+use winit::window::{CustomCursor, Window, WindowAttributes, WindowId};
 
 use crate::app_driver::{AppDriver, DriverCtx};
 use crate::event::{PointerState, WindowEvent};
+use crate::frame_stats::FrameStats;
 use crate::render_root::{self, RenderRoot, WindowSizePolicy};
-use crate::{PointerEvent, TextEvent, Widget};
+use crate::text2::IconFont;
+use crate::theme::Theme;
+use crate::{
+    Action, ClickSettings, Cursor, CustomCursorImage, PointerEvent, ScaleCoefficient, ScrollDelta,
+    TextEvent, Vec2, Widget, WidgetId,
+};
 
 pub enum WindowState<'a> {
     Uninitialized(WindowAttributes),
     Rendering {
         window: Arc<Window>,
-        surface: RenderSurface<'a>,
+        /// The surface the window is rendered to, or `None` if it couldn't be created yet.
+        surface: Option<RenderSurface<'a>>,
         accesskit_adapter: Adapter,
     },
     Suspended {
@@ -36,29 +53,192 @@ pub enum WindowState<'a> {
     },
 }
 
+/// The level of multisample antialiasing used to render the window.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MsaaLevel {
+    /// Don't use multisampling, and use Vello's analytic area antialiasing instead.
+    #[default]
+    Off,
+    /// 8x multisample antialiasing.
+    Msaa8,
+    /// 16x multisample antialiasing.
+    Msaa16,
+}
+
+impl MsaaLevel {
+    fn aa_support(self) -> AaSupport {
+        AaSupport {
+            area: self == Self::Off,
+            msaa8: self == Self::Msaa8,
+            msaa16: self == Self::Msaa16,
+        }
+    }
+
+    fn aa_config(self) -> AaConfig {
+        match self {
+            Self::Off => AaConfig::Area,
+            Self::Msaa8 => AaConfig::Msaa8,
+            Self::Msaa16 => AaConfig::Msaa16,
+        }
+    }
+}
+
+/// Options for how the window's contents are rendered and presented.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RenderOptions {
+    /// How frames are presented to the window surface.
+    ///
+    /// Defaults to [`PresentMode::AutoVsync`]. Use [`PresentMode::AutoNoVsync`] or
+    /// [`PresentMode::Mailbox`] for lower latency, at the cost of tearing or wasted frames.
+    pub present_mode: PresentMode,
+    /// The antialiasing used when rendering.
+    pub msaa: MsaaLevel,
+    /// Whether to run Vello's rendering pipeline on the CPU, rather than in compute shaders.
+    ///
+    /// A wgpu adapter is still needed to present frames. When no hardware adapter can be
+    /// used, a software one is picked if there is one, and this is turned on automatically.
+    pub use_cpu: bool,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self {
+            present_mode: PresentMode::AutoVsync,
+            msaa: MsaaLevel::Off,
+            use_cpu: false,
+        }
+    }
+}
+
 struct MainState<'a> {
     render_cx: RenderContext,
+    app_options: AppOptions,
+    render_options: RenderOptions,
+    input_options: InputOptions,
     render_root: RenderRoot,
     pointer_state: PointerState,
     app_driver: Box<dyn AppDriver>,
     renderer: Option<Renderer>,
     // TODO: Winit doesn't seem to let us create these proxies from within the loop
     // The reasons for this are unclear
-    proxy: EventLoopProxy<accesskit_winit::Event>,
+    proxy: EventLoopProxy<MasonryUserEvent>,
+    /// The platform cursor created for the most recently used custom cursor image.
+    custom_cursor: Option<(CustomCursorImage, CustomCursor)>,
+    /// The timings shown in the debug overlay, if it's enabled.
+    frame_stats: Option<FrameStats>,
+    /// Whether the window's content may have been lost since the last render, in which
+    /// case the next frame is rendered even if nothing changed.
+    surface_outdated: bool,
+    /// Whether the pending redraw was requested by Masonry, rather than by the platform.
+    ///
+    /// Redraws requested by the platform are always rendered, as the window's content may
+    /// need to be presented again even if nothing changed.
+    redraw_requested_by_us: bool,
+    /// Set when the GPU device used by the window's surface is lost, for instance after a
+    /// driver reset, in which case the device, surface and renderer are recreated.
+    device_lost: Arc<AtomicBool>,
+    /// The device of the software adapter used for the window's surface when no hardware
+    /// adapter could be used, in which case the CPU runs the rendering pipeline.
+    software_device: Option<SoftwareDevice>,
+    /// The number of lines scrolled for each step of a mouse wheel.
+    wheel_scroll_lines: f64,
+    /// The number of frames in a row which couldn't be rendered.
+    render_failures: u32,
+    /// How long to wait before rendering again after a frame couldn't be rendered, if that
+    /// hasn't been scheduled yet.
+    retry_delay: Option<Duration>,
+    /// Why the window can't be rendered at all, in which case the event loop exits and
+    /// this is returned by [`run_with_options`].
+    render_error: Option<String>,
+    /// Asks the thread reading the platform's accessibility settings to read them again,
+    /// once it was started.
+    settings_reader: Option<mpsc::Sender<()>>,
 
     // Per-Window state
     // In future, this will support multiple windows
     window: WindowState<'a>,
 }
 
+/// A device created from a software adapter, along with its queue.
+struct SoftwareDevice {
+    device: Device,
+    queue: Queue,
+}
+
+/// An error which stopped Masonry's event loop.
+#[derive(Debug)]
+pub enum RunError {
+    /// The event loop couldn't be created or run.
+    EventLoop(EventLoopError),
+    /// The window couldn't be rendered, neither with a GPU nor with a software adapter.
+    Render(String),
+}
+
+impl From<EventLoopError> for RunError {
+    fn from(err: EventLoopError) -> Self {
+        Self::EventLoop(err)
+    }
+}
+
+impl std::fmt::Display for RunError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::EventLoop(err) => write!(f, "{err}"),
+            Self::Render(err) => write!(f, "Couldn't render the window: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for RunError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::EventLoop(err) => Some(err),
+            Self::Render(_) => None,
+        }
+    }
+}
+
+/// The events sent to Masonry's event loop from outside of it.
+///
+/// This is the user event type of [`EventLoop`]. Code which sent `accesskit_winit::Event`s
+/// to the event loop directly should wrap them in [`MasonryUserEvent::AccessKit`], which
+/// the `From` implementation does.
+pub enum MasonryUserEvent {
+    /// An event from the accessibility adapter.
+    AccessKit(accesskit_winit::Event),
+    /// An [`Action::Other`] with the given payload, to handle as if the widget had submitted it.
+    ///
+    /// See [`ExtEventSink`](crate::ext_event::ExtEventSink).
+    Action(Box<dyn Any + Send>, WidgetId),
+    /// The platform's accessibility settings, read on a background thread.
+    ///
+    /// This is sent by Masonry itself, after the settings may have changed.
+    AccessibilitySettings {
+        high_contrast: bool,
+        reduced_motion: bool,
+    },
+}
+
+impl From<accesskit_winit::Event> for MasonryUserEvent {
+    fn from(event: accesskit_winit::Event) -> Self {
+        Self::AccessKit(event)
+    }
+}
+
 /// The type of the event loop used by Masonry.
 ///
 /// This *will* be changed to allow custom event types, but is implemented this way for expedience
-pub type EventLoop = winit::event_loop::EventLoop<accesskit_winit::Event>;
+///
+/// Its user events are [`MasonryUserEvent`]s, rather than `accesskit_winit::Event`s as in
+/// previous versions, so that actions can also be sent to the app from other threads.
+pub type EventLoop = winit::event_loop::EventLoop<MasonryUserEvent>;
 /// The type of the event loop builder used by Masonry.
 ///
 /// This *will* be changed to allow custom event types, but is implemented this way for expedience
-pub type EventLoopBuilder = winit::event_loop::EventLoopBuilder<accesskit_winit::Event>;
+///
+/// Its user events are [`MasonryUserEvent`]s, rather than `accesskit_winit::Event`s as in
+/// previous versions, so that actions can also be sent to the app from other threads.
+pub type EventLoopBuilder = winit::event_loop::EventLoopBuilder<MasonryUserEvent>;
 
 pub fn run(
     // Clearly, this API needs to be refactored, so we don't mind forcing this to be passed in here directly
@@ -68,42 +248,417 @@ pub fn run(
     window_attributes: WindowAttributes,
     root_widget: impl Widget,
     app_driver: impl AppDriver + 'static,
-) -> Result<(), EventLoopError> {
+) -> Result<(), RunError> {
     let event_loop = loop_builder.build()?;
 
     run_with(window_attributes, event_loop, root_widget, app_driver)
 }
 
+/// Options for the app's fonts and appearance, applied when the window is created.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AppOptions {
+    /// The contents of the font files registered when the window is created.
+    ///
+    /// See [`RenderRoot::add_font`].
+    pub fonts: Vec<Arc<[u8]>>,
+    /// The icon fonts registered when the window is created.
+    pub icon_fonts: Vec<IconFont>,
+    /// The colors widgets are painted with, and the window is filled with.
+    ///
+    /// See [`RenderRoot::set_theme`].
+    pub theme: Theme,
+    /// The theme used instead of [`theme`](Self::theme) while the platform's high contrast
+    /// setting is on, or `None` to keep using `theme`.
+    ///
+    /// Defaults to [`Theme::high_contrast`]. Whether the setting is on can also be read by
+    /// widgets, see [`EventCtx::high_contrast`](crate::EventCtx::high_contrast).
+    ///
+    /// The setting is read on Windows, macOS, and on Linux through the XDG settings portal.
+    pub high_contrast_theme: Option<Theme>,
+    /// Whether to show an overlay with the frame rate and a graph of how long recent
+    /// frames took, for debugging.
+    ///
+    /// Frames are only timed when this is enabled.
+    pub debug_overlay: bool,
+}
+
+impl Default for AppOptions {
+    fn default() -> Self {
+        Self {
+            fonts: Vec::new(),
+            icon_fonts: Vec::new(),
+            theme: Theme::default(),
+            high_contrast_theme: Some(Theme::high_contrast()),
+            debug_overlay: false,
+        }
+    }
+}
+
+/// Options for how the window's input is interpreted.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct InputOptions {
+    /// How consecutive clicks are grouped into double clicks.
+    pub click_settings: ClickSettings,
+    /// Whether to invert the direction of mouse wheel and touchpad scrolling, so that
+    /// content moves the same way as the fingers on a touchpad.
+    ///
+    /// Defaults to `false`, which passes the platform's deltas through unchanged.
+    pub natural_scrolling: bool,
+}
+
+/// The delay before rendering again after the second failed frame in a row.
+const MIN_RETRY_DELAY: Duration = Duration::from_millis(50);
+/// The longest delay before rendering again after a failed frame.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(2);
+
+/// How long to wait before rendering again after `failures` frames in a row couldn't be
+/// rendered.
+fn retry_delay(failures: u32) -> Duration {
+    if failures <= 1 {
+        return Duration::ZERO;
+    }
+    MIN_RETRY_DELAY
+        .saturating_mul(1 << (failures - 2).min(16))
+        .min(MAX_RETRY_DELAY)
+}
+
+/// The number of lines scrolled for each step of a mouse wheel when the platform's
+/// setting can't be queried, which is the default on most platforms.
+const DEFAULT_WHEEL_SCROLL_LINES: f64 = 3.;
+
+/// The number of lines scrolled for each step of a mouse wheel, according to the
+/// platform's settings.
+fn wheel_scroll_lines() -> f64 {
+    #[cfg(windows)]
+    {
+        use windows_sys::Win32::UI::WindowsAndMessaging::{
+            SystemParametersInfoW, SPI_GETWHEELSCROLLLINES, WHEEL_PAGESCROLL,
+        };
+
+        let mut lines: u32 = 0;
+        // SAFETY: `SPI_GETWHEELSCROLLLINES` writes a `u32` to the pointer it's given.
+        #[allow(unsafe_code)]
+        let ok = unsafe {
+            SystemParametersInfoW(
+                SPI_GETWHEELSCROLLLINES,
+                0,
+                (&mut lines as *mut u32).cast(),
+                0,
+            )
+        };
+        // Scrolling by a whole page for each step isn't supported, so it falls back to
+        // the default too.
+        if ok != 0 && lines != WHEEL_PAGESCROLL {
+            return lines as f64;
+        }
+    }
+    DEFAULT_WHEEL_SCROLL_LINES
+}
+
+/// Whether the platform's high contrast setting is on.
+///
+/// On macOS, this is the setting to increase contrast. On Linux, it's read from the XDG
+/// settings portal, and is off if the portal isn't available.
+fn high_contrast() -> bool {
+    #[cfg(windows)]
+    {
+        use windows_sys::Win32::UI::Accessibility::{HCF_HIGHCONTRASTON, HIGHCONTRASTW};
+        use windows_sys::Win32::UI::WindowsAndMessaging::{
+            SystemParametersInfoW, SPI_GETHIGHCONTRAST,
+        };
+
+        let mut high_contrast = HIGHCONTRASTW {
+            cbSize: std::mem::size_of::<HIGHCONTRASTW>() as u32,
+            dwFlags: 0,
+            lpszDefaultScheme: std::ptr::null_mut(),
+        };
+        // SAFETY: `SPI_GETHIGHCONTRAST` fills in the `HIGHCONTRASTW` it's given, whose
+        // size is passed along with it.
+        #[allow(unsafe_code)]
+        let ok = unsafe {
+            SystemParametersInfoW(
+                SPI_GETHIGHCONTRAST,
+                high_contrast.cbSize,
+                (&mut high_contrast as *mut HIGHCONTRASTW).cast(),
+                0,
+            )
+        };
+        if ok != 0 && high_contrast.dwFlags & HCF_HIGHCONTRASTON != 0 {
+            return true;
+        }
+    }
+    #[cfg(target_os = "macos")]
+    {
+        use objc2_app_kit::NSWorkspace;
+
+        // This runs on a background thread, which has no autorelease pool of its own.
+        // SAFETY: The shared workspace is always available, and reading its
+        // accessibility settings has no preconditions.
+        #[allow(unsafe_code)]
+        let on = objc2::rc::autoreleasepool(|_| unsafe {
+            NSWorkspace::sharedWorkspace().accessibilityDisplayShouldIncreaseContrast()
+        });
+        if on {
+            return true;
+        }
+    }
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    ))]
+    {
+        // 1 means higher contrast, 0 no preference.
+        if appearance_setting("contrast") == Some(1) {
+            return true;
+        }
+    }
+    false
+}
+
+/// Whether the platform's settings ask for less motion.
+///
+/// On Windows, this is the setting to turn off animations, and on macOS the one to reduce
+/// motion. On Linux, it's read from the XDG settings portal, and is off if the portal
+/// isn't available.
+fn reduced_motion() -> bool {
+    #[cfg(windows)]
+    {
+        use windows_sys::Win32::UI::WindowsAndMessaging::{
+            SystemParametersInfoW, SPI_GETCLIENTAREAANIMATION,
+        };
+
+        let mut animations: i32 = 1;
+        // SAFETY: `SPI_GETCLIENTAREAANIMATION` writes a `BOOL` to the pointer it's given.
+        #[allow(unsafe_code)]
+        let ok = unsafe {
+            SystemParametersInfoW(
+                SPI_GETCLIENTAREAANIMATION,
+                0,
+                (&mut animations as *mut i32).cast(),
+                0,
+            )
+        };
+        if ok != 0 && animations == 0 {
+            return true;
+        }
+    }
+    #[cfg(target_os = "macos")]
+    {
+        use objc2_app_kit::NSWorkspace;
+
+        // This runs on a background thread, which has no autorelease pool of its own.
+        // SAFETY: The shared workspace is always available, and reading its
+        // accessibility settings has no preconditions.
+        #[allow(unsafe_code)]
+        let on = objc2::rc::autoreleasepool(|_| unsafe {
+            NSWorkspace::sharedWorkspace().accessibilityDisplayShouldReduceMotion()
+        });
+        if on {
+            return true;
+        }
+    }
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    ))]
+    {
+        // 1 means reduced motion, 0 no preference.
+        if appearance_setting("reduced-motion") == Some(1) {
+            return true;
+        }
+    }
+    false
+}
+
+/// Start the thread reading the platform's accessibility settings whenever it's asked
+/// to, and sending them to the event loop through `proxy`.
+fn spawn_settings_reader(proxy: EventLoopProxy<MasonryUserEvent>) -> mpsc::Sender<()> {
+    let (sender, receiver) = mpsc::channel::<()>();
+    let spawned = thread::Builder::new()
+        .name("masonry settings".into())
+        .spawn(move || {
+            while receiver.recv().is_ok() {
+                // Requests made while the settings were read last are answered by one read.
+                while receiver.try_recv().is_ok() {}
+                let event = MasonryUserEvent::AccessibilitySettings {
+                    high_contrast: high_contrast(),
+                    reduced_motion: reduced_motion(),
+                };
+                if proxy.send_event(event).is_err() {
+                    break;
+                }
+            }
+        });
+    if let Err(err) = spawned {
+        warn!("Couldn't start a thread to read the accessibility settings: {err}");
+    }
+    sender
+}
+
+/// Read a setting of the `org.freedesktop.appearance` namespace from the XDG settings
+/// portal, which is how Linux desktops share their accessibility settings with apps.
+///
+/// Returns `None` if the portal isn't available, or doesn't know the setting.
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+fn appearance_setting(key: &str) -> Option<u32> {
+    use std::sync::OnceLock;
+
+    use zbus::blocking::Connection;
+    use zbus::zvariant::{OwnedValue, Value};
+
+    // The settings are read again whenever the window is focused, so the connection to
+    // the session bus is kept.
+    static CONNECTION: OnceLock<Option<Connection>> = OnceLock::new();
+    let connection = CONNECTION
+        .get_or_init(|| match Connection::session() {
+            Ok(connection) => Some(connection),
+            Err(err) => {
+                debug!("Couldn't connect to the session bus to read the settings: {err}");
+                None
+            }
+        })
+        .as_ref()?;
+    let reply = connection
+        .call_method(
+            Some("org.freedesktop.portal.Desktop"),
+            "/org/freedesktop/portal/desktop",
+            Some("org.freedesktop.portal.Settings"),
+            "Read",
+            &("org.freedesktop.appearance", key),
+        )
+        .map_err(|err| debug!("Couldn't read the {key:?} setting from the portal: {err}"))
+        .ok()?;
+    let value: OwnedValue = reply.body().ok()?;
+    // `Read` returns the setting in a variant, which some versions of the portal wrap
+    // in another one.
+    let mut value: &Value = &value;
+    while let Value::Value(inner) = value {
+        value = inner;
+    }
+    match value {
+        Value::U32(setting) => Some(*setting),
+        _ => None,
+    }
+}
+
 pub fn run_with(
     window: WindowAttributes,
     event_loop: EventLoop,
     root_widget: impl Widget,
     app_driver: impl AppDriver + 'static,
-) -> Result<(), EventLoopError> {
-    let render_cx = RenderContext::new().unwrap();
-    // TODO: We can't know this scale factor until later?
-    let scale_factor = 1.0;
+) -> Result<(), RunError> {
+    run_with_options(
+        window,
+        AppOptions::default(),
+        RenderOptions::default(),
+        InputOptions::default(),
+        event_loop,
+        root_widget,
+        app_driver,
+    )
+}
+
+/// Like [`run_with`], with the given options for the app's appearance, rendering the
+/// window and interpreting its input.
+///
+/// If no GPU can be used, the window is rendered with a software adapter instead. If
+/// that fails too, the event loop exits, and a [`RunError::Render`] is returned.
+pub fn run_with_options(
+    window: WindowAttributes,
+    app_options: AppOptions,
+    render_options: RenderOptions,
+    input_options: InputOptions,
+    event_loop: EventLoop,
+    root_widget: impl Widget,
+    app_driver: impl AppDriver + 'static,
+) -> Result<(), RunError> {
+    let render_cx = RenderContext::new().map_err(|err| RunError::Render(err.to_string()))?;
+    // The real scale factor is only known once the window is created, at which
+    // point a `Rescale` event is sent.
+    let scale_factor = ScaleCoefficient::ONE;
+    let mut render_root = RenderRoot::new(root_widget, WindowSizePolicy::User, scale_factor);
+    render_root.set_click_settings(input_options.click_settings);
+    for font in &app_options.fonts {
+        render_root.add_font(font);
+    }
+    for font in &app_options.icon_fonts {
+        render_root.add_icon_font(font);
+    }
+    let frame_stats = app_options.debug_overlay.then(FrameStats::new);
     let mut main_state = MainState {
         render_cx,
-        render_root: RenderRoot::new(root_widget, WindowSizePolicy::User, scale_factor),
+        app_options,
+        render_options,
+        input_options,
+        render_root,
         renderer: None,
         pointer_state: PointerState::empty(),
         app_driver: Box::new(app_driver),
         proxy: event_loop.create_proxy(),
+        custom_cursor: None,
+        frame_stats,
+        surface_outdated: true,
+        redraw_requested_by_us: false,
+        device_lost: Arc::new(AtomicBool::new(false)),
+        software_device: None,
+        wheel_scroll_lines: wheel_scroll_lines(),
+        render_failures: 0,
+        retry_delay: None,
+        render_error: None,
+        settings_reader: None,
 
         window: WindowState::Uninitialized(window),
     };
+    main_state.read_accessibility_settings();
 
     // If there is no default tracing subscriber, we set our own. If one has
     // already been set, we get an error which we swallow.
     // By now, we're about to take control of the event loop. The user is unlikely
     // to try to set their own subscriber once the event loop has started.
     let _ = try_init_tracing();
+    crate::clipboard::set_main_thread();
 
-    event_loop.run_app(&mut main_state)
+    event_loop.run_app(&mut main_state)?;
+    match main_state.render_error {
+        Some(err) => Err(RunError::Render(err)),
+        None => Ok(()),
+    }
 }
 
-impl ApplicationHandler<accesskit_winit::Event> for MainState<'_> {
+impl ApplicationHandler<MasonryUserEvent> for MainState<'_> {
+    fn new_events(&mut self, event_loop: &ActiveEventLoop, cause: StartCause) {
+        // The delay before rendering again after a failed frame has elapsed.
+        if let StartCause::ResumeTimeReached { .. } = cause {
+            event_loop.set_control_flow(ControlFlow::Wait);
+            if let WindowState::Rendering { window, .. } = &self.window {
+                self.redraw_requested_by_us = true;
+                window.request_redraw();
+            }
+        }
+    }
+
+    fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
+        if self.render_error.is_some() {
+            event_loop.exit();
+            return;
+        }
+        if let Some(delay) = self.retry_delay.take() {
+            event_loop.set_control_flow(ControlFlow::wait_duration(delay));
+        }
+    }
+
     fn resumed(&mut self, event_loop: &ActiveEventLoop) {
         match std::mem::replace(
             &mut self.window,
@@ -121,20 +676,15 @@ impl ApplicationHandler<accesskit_winit::Event> for MainState<'_> {
                 // TODO: Use signals or some other mechanism to do fine grained ime enable
                 window.set_ime_allowed(true);
                 let window = Arc::new(window);
-                let size = window.inner_size();
-                let surface = pollster::block_on(self.render_cx.create_surface(
-                    window.clone(),
-                    size.width,
-                    size.height,
-                    PresentMode::AutoVsync,
-                ))
-                .unwrap();
-                let scale_factor = window.scale_factor();
+                let scale_factor = ScaleCoefficient::new(window.scale_factor());
                 self.window = WindowState::Rendering {
                     window,
-                    surface,
+                    surface: None,
                     accesskit_adapter: adapter,
                 };
+                if !self.ensure_surface() {
+                    self.retry_render();
+                }
                 self.render_root
                     .handle_window_event(WindowEvent::Rescale(scale_factor));
             }
@@ -142,18 +692,13 @@ impl ApplicationHandler<accesskit_winit::Event> for MainState<'_> {
                 window,
                 accesskit_adapter,
             } => {
-                let size = window.inner_size();
-                let surface = pollster::block_on(self.render_cx.create_surface(
-                    window.clone(),
-                    size.width,
-                    size.height,
-                    PresentMode::AutoVsync,
-                ))
-                .unwrap();
                 self.window = WindowState::Rendering {
                     window,
-                    surface,
+                    surface: None,
                     accesskit_adapter,
+                };
+                if !self.ensure_surface() {
+                    self.retry_render();
                 }
             }
             _ => {
@@ -201,12 +746,63 @@ impl ApplicationHandler<accesskit_winit::Event> for MainState<'_> {
 
         match event {
             WinitWindowEvent::ScaleFactorChanged { scale_factor, .. } => {
+                // The window usually moved to another monitor. Winit sends a `Resized` event
+                // next if its physical size changes, and the surface is resized on the next render.
+                let scale_factor = ScaleCoefficient::new(scale_factor);
+                self.pointer_state.position = self
+                    .pointer_state
+                    .physical_position
+                    .to_logical(scale_factor.get());
                 self.render_root
                     .handle_window_event(WindowEvent::Rescale(scale_factor));
+                self.surface_outdated = true;
             }
+            // The accessibility settings often change along with the platform's theme,
+            // e.g. turning high contrast on switches to a dark theme.
+            WinitWindowEvent::ThemeChanged(_) => self.read_accessibility_settings(),
             WinitWindowEvent::RedrawRequested => {
-                let (scene, tree_update) = self.render_root.redraw();
-                self.render(scene);
+                let frame_start = self.frame_stats.is_some().then(Instant::now);
+                let requested_by_us = std::mem::take(&mut self.redraw_requested_by_us);
+                // Actions submitted during layout can change the widget tree, e.g. when a
+                // responsive container moves to another breakpoint class. They're handled
+                // before painting, so that the frame already shows their result.
+                self.render_root.layout_if_needed();
+                self.handle_signals(event_loop);
+                let (mut scene, tree_update) = self.render_root.redraw();
+                let redraw_end = frame_start.map(|_| Instant::now());
+                // Rendering the same scene again for one of our own redraw requests would
+                // only waste GPU time, unless the window's previous content was lost. The
+                // debug overlay changes every frame.
+                let needs_render = !requested_by_us
+                    || self.render_root.last_damage().is_some()
+                    || self.surface_outdated
+                    || self.frame_stats.is_some();
+                if needs_render {
+                    if let Some(stats) = &mut self.frame_stats {
+                        let window_size = self
+                            .render_root
+                            .scale_factor()
+                            .to_logical_size(self.render_root.size);
+                        stats.paint(
+                            &mut scene,
+                            &mut self.render_root.state.font_context,
+                            window_size,
+                        );
+                    }
+                    self.surface_outdated = false;
+                    if self.render(scene) {
+                        self.render_failures = 0;
+                    } else {
+                        self.retry_render();
+                    }
+                    if let (Some(stats), Some(frame_start), Some(redraw_end)) =
+                        (&mut self.frame_stats, frame_start, redraw_end)
+                    {
+                        stats.record(frame_start, redraw_end - frame_start, redraw_end.elapsed());
+                    }
+                } else {
+                    trace!("Nothing changed since the last frame, skipping render");
+                }
                 let WindowState::Rendering {
                     accesskit_adapter, ..
                 } = &mut self.window
@@ -217,7 +813,13 @@ impl ApplicationHandler<accesskit_winit::Event> for MainState<'_> {
                 accesskit_adapter.update_if_active(|| tree_update);
             }
             WinitWindowEvent::CloseRequested => event_loop.exit(),
+            WinitWindowEvent::Occluded(false) => {
+                self.surface_outdated = true;
+                window.request_redraw();
+            }
             WinitWindowEvent::Resized(size) => {
+                // The platform's redraw request may be merged with one of ours.
+                self.surface_outdated = true;
                 self.render_root
                     .handle_window_event(WindowEvent::Resize(size));
             }
@@ -240,12 +842,18 @@ impl ApplicationHandler<accesskit_winit::Event> for MainState<'_> {
                 self.render_root.handle_text_event(TextEvent::Ime(ime));
             }
             WinitWindowEvent::Focused(new_focus) => {
+                // The settings are usually changed in another app, and not all platforms
+                // tell us when they change, so they're read again when the user comes back.
+                if new_focus {
+                    self.read_accessibility_settings();
+                }
                 self.render_root
                     .handle_text_event(TextEvent::FocusChange(new_focus));
             }
             WinitWindowEvent::CursorMoved { position, .. } => {
                 self.pointer_state.physical_position = position;
-                self.pointer_state.position = position.to_logical(window.scale_factor());
+                self.pointer_state.position =
+                    position.to_logical(self.render_root.scale_factor().get());
                 self.render_root
                     .handle_pointer_event(PointerEvent::PointerMove(self.pointer_state.clone()));
             }
@@ -270,38 +878,116 @@ impl ApplicationHandler<accesskit_winit::Event> for MainState<'_> {
                 }
             },
             WinitWindowEvent::MouseWheel { delta, .. } => {
-                let delta = match delta {
+                let mut delta = match delta {
                     winit::event::MouseScrollDelta::LineDelta(x, y) => {
-                        LogicalPosition::new(x as f64, y as f64)
+                        ScrollDelta::Lines(Vec2::new(x as f64, y as f64) * self.wheel_scroll_lines)
                     }
                     winit::event::MouseScrollDelta::PixelDelta(delta) => {
-                        delta.to_logical(window.scale_factor())
+                        let delta: LogicalPosition<f64> =
+                            delta.to_logical(self.render_root.scale_factor().get());
+                        ScrollDelta::Precise(Vec2::new(delta.x, delta.y))
                     }
                 };
+                if self.input_options.natural_scrolling {
+                    delta = -delta;
+                }
                 self.render_root
                     .handle_pointer_event(PointerEvent::MouseWheel(
                         delta,
                         self.pointer_state.clone(),
                     ));
             }
+            // TODO - Handle multi-touch; for now touches are treated as a single pointer.
+            WinitWindowEvent::Touch(touch) => {
+                self.pointer_state.physical_position = touch.location;
+                self.pointer_state.position = touch
+                    .location
+                    .to_logical(self.render_root.scale_factor().get());
+                self.pointer_state.force = touch.force.map(|force| force.normalized());
+                match touch.phase {
+                    winit::event::TouchPhase::Started => {
+                        self.render_root
+                            .handle_pointer_event(PointerEvent::PointerDown(
+                                MouseButton::Left,
+                                self.pointer_state.clone(),
+                            ));
+                    }
+                    winit::event::TouchPhase::Moved => {
+                        self.render_root
+                            .handle_pointer_event(PointerEvent::PointerMove(
+                                self.pointer_state.clone(),
+                            ));
+                    }
+                    winit::event::TouchPhase::Ended | winit::event::TouchPhase::Cancelled => {
+                        self.pointer_state.force = None;
+                        self.render_root
+                            .handle_pointer_event(PointerEvent::PointerUp(
+                                MouseButton::Left,
+                                self.pointer_state.clone(),
+                            ));
+                    }
+                }
+            }
+            // winit doesn't report the cursor position while files are dragged over the
+            // window on most platforms, so these use the last known position.
+            WinitWindowEvent::HoveredFile(path) => {
+                self.render_root
+                    .handle_pointer_event(PointerEvent::HoverFile(
+                        path,
+                        self.pointer_state.clone(),
+                    ));
+            }
+            WinitWindowEvent::DroppedFile(path) => {
+                self.render_root
+                    .handle_pointer_event(PointerEvent::DropFile(path, self.pointer_state.clone()));
+            }
+            WinitWindowEvent::HoveredFileCancelled => {
+                self.render_root
+                    .handle_pointer_event(PointerEvent::HoverFileCancel(
+                        self.pointer_state.clone(),
+                    ));
+            }
+            WinitWindowEvent::TouchpadPressure { pressure, .. } => {
+                self.pointer_state.force = Some(pressure as f64);
+                self.render_root
+                    .handle_pointer_event(PointerEvent::PointerMove(self.pointer_state.clone()));
+            }
             _ => (),
         }
 
         self.handle_signals(event_loop);
     }
 
-    fn user_event(&mut self, event_loop: &ActiveEventLoop, event: accesskit_winit::Event) {
-        match event.window_event {
-            // Note that this event can be called at any time, even multiple times if
-            // the user restarts their screen reader.
-            accesskit_winit::WindowEvent::InitialTreeRequested => {
-                self.render_root
-                    .handle_window_event(WindowEvent::RebuildAccessTree);
-            }
-            accesskit_winit::WindowEvent::ActionRequested(action_request) => {
-                self.render_root.root_on_access_event(action_request);
+    fn user_event(&mut self, event_loop: &ActiveEventLoop, event: MasonryUserEvent) {
+        match event {
+            MasonryUserEvent::AccessKit(event) => match event.window_event {
+                // Note that this event can be called at any time, even multiple times if
+                // the user restarts their screen reader.
+                accesskit_winit::WindowEvent::InitialTreeRequested => {
+                    self.render_root
+                        .handle_window_event(WindowEvent::RebuildAccessTree);
+                }
+                accesskit_winit::WindowEvent::ActionRequested(action_request) => {
+                    self.render_root.root_on_access_event(action_request);
+                }
+                accesskit_winit::WindowEvent::AccessibilityDeactivated => {}
+            },
+            MasonryUserEvent::Action(payload, widget_id) => {
+                let payload: Box<dyn Any> = payload;
+                let action = Action::Other(payload.into());
+                self.render_root.edit_root_widget(|root| {
+                    debug!("External action {:?} on widget {:?}", action, widget_id);
+                    let mut driver_ctx = DriverCtx {
+                        main_root_widget: root,
+                    };
+                    self.app_driver
+                        .on_action(&mut driver_ctx, widget_id, action);
+                });
             }
-            accesskit_winit::WindowEvent::AccessibilityDeactivated => {}
+            MasonryUserEvent::AccessibilitySettings {
+                high_contrast,
+                reduced_motion,
+            } => self.update_accessibility_settings(high_contrast, reduced_motion),
         }
 
         self.handle_signals(event_loop);
@@ -309,64 +995,265 @@ impl ApplicationHandler<accesskit_winit::Event> for MainState<'_> {
 }
 
 impl MainState<'_> {
-    fn render(&mut self, scene: Scene) {
+    /// Render `scene` to the window.
+    ///
+    /// Returns `false` if the frame couldn't be rendered because of an error which may go
+    /// away, in which case it should be rendered again later.
+    fn render(&mut self, scene: Scene) -> bool {
+        if self.device_lost.load(Ordering::Relaxed) {
+            if let Err(err) = self.recover_from_device_loss() {
+                error!("Couldn't recover from the loss of the GPU device: {err}");
+                return false;
+            }
+        }
+        if !self.ensure_surface() {
+            return false;
+        }
         let WindowState::Rendering {
-            window, surface, ..
+            window,
+            surface: Some(surface),
+            ..
         } = &mut self.window
         else {
             tracing::warn!("Tried to render whilst suspended or before window created");
-            return;
+            return true;
         };
-        let scale = window.scale_factor();
+        // Use the scale factor the widget tree was laid out with, so that painting matches layout
+        // even if the window's factor changed since the last layout pass.
+        let scale = self.render_root.scale_factor();
         let size = window.inner_size();
         let width = size.width;
         let height = size.height;
 
+        let (device, queue) = device_and_queue(
+            &self.render_cx,
+            self.software_device.as_ref(),
+            surface.dev_id,
+        );
         if surface.config.width != width || surface.config.height != height {
-            self.render_cx.resize_surface(surface, width, height);
+            surface.config.width = width;
+            surface.config.height = height;
+            surface.surface.configure(device, &surface.config);
         }
 
-        let transformed_scene = if scale == 1.0 {
+        let transformed_scene = if scale == ScaleCoefficient::ONE {
             None
         } else {
             let mut new_scene = Scene::new();
-            new_scene.append(&scene, Some(Affine::scale(scale)));
+            new_scene.append(&scene, Some(scale.transform()));
             Some(new_scene)
         };
         let scene_ref = transformed_scene.as_ref().unwrap_or(&scene);
 
-        let Ok(surface_texture) = surface.surface.get_current_texture() else {
-            warn!("failed to acquire next swapchain texture");
-            return;
+        let surface_texture = match surface.surface.get_current_texture() {
+            Ok(surface_texture) => surface_texture,
+            Err(err @ (SurfaceError::Lost | SurfaceError::Outdated)) => {
+                // The swapchain no longer matches the window, e.g. after the system slept.
+                // Reconfiguring the surface is enough to recover, so try again right away.
+                debug!("{err}, reconfiguring the surface");
+                surface.surface.configure(device, &surface.config);
+                match surface.surface.get_current_texture() {
+                    Ok(surface_texture) => surface_texture,
+                    Err(err) => {
+                        warn!("failed to acquire next swapchain texture: {err}");
+                        return false;
+                    }
+                }
+            }
+            Err(SurfaceError::Timeout) => {
+                // Transient, so skip this frame and render the next one instead.
+                warn!("timed out acquiring next swapchain texture");
+                return false;
+            }
+            Err(err @ SurfaceError::OutOfMemory) => {
+                error!("failed to acquire next swapchain texture: {err}");
+                return true;
+            }
         };
-        let dev_id = surface.dev_id;
-        let device = &self.render_cx.devices[dev_id].device;
-        let queue = &self.render_cx.devices[dev_id].queue;
         let renderer_options = RendererOptions {
             surface_format: Some(surface.format),
-            use_cpu: false,
-            antialiasing_support: AaSupport {
-                area: true,
-                msaa8: false,
-                msaa16: false,
-            },
+            use_cpu: self.render_options.use_cpu || self.software_device.is_some(),
+            antialiasing_support: self.render_options.msaa.aa_support(),
             num_init_threads: NonZeroUsize::new(1),
         };
         let render_params = RenderParams {
-            base_color: Color::BLACK,
+            base_color: self.render_root.theme().background,
             width,
             height,
-            antialiasing_method: vello::AaConfig::Area,
+            antialiasing_method: self.render_options.msaa.aa_config(),
         };
-        self.renderer
-            .get_or_insert_with(|| Renderer::new(device, renderer_options).unwrap())
-            .render_to_surface(device, queue, scene_ref, &surface_texture, &render_params)
-            .expect("failed to render to surface");
+        let renderer = match &mut self.renderer {
+            Some(renderer) => renderer,
+            None => match Renderer::new(device, renderer_options) {
+                Ok(renderer) => self.renderer.insert(renderer),
+                Err(err) => {
+                    error!("failed to create renderer: {err}");
+                    return false;
+                }
+            },
+        };
+        if let Err(err) =
+            renderer.render_to_surface(device, queue, scene_ref, &surface_texture, &render_params)
+        {
+            // The renderer's resources may be unusable, so start afresh on the next frame.
+            error!("failed to render to surface: {err}");
+            self.renderer = None;
+            return false;
+        }
         surface_texture.present();
         device.poll(wgpu::Maintain::Wait);
+        true
+    }
+
+    /// Render again after a frame couldn't be rendered.
+    ///
+    /// The first retry happens right away, as most failures only last for a frame. After
+    /// that, the delay doubles with each failure in a row, so that a lasting failure doesn't
+    /// keep recreating the surface or renderer in a busy loop.
+    fn retry_render(&mut self) {
+        self.surface_outdated = true;
+        self.render_failures = self.render_failures.saturating_add(1);
+        self.retry_delay = Some(retry_delay(self.render_failures));
+    }
+
+    /// Set [`device_lost`](Self::device_lost) when the device at `dev_id` is lost.
+    fn watch_for_device_loss(&self, dev_id: usize) {
+        let device_lost = self.device_lost.clone();
+        let (device, _) = device_and_queue(&self.render_cx, self.software_device.as_ref(), dev_id);
+        device.set_device_lost_callback(move |reason, message| {
+            // The device is also "lost" when we drop it, or replace this callback.
+            if matches!(reason, DeviceLostReason::Unknown) {
+                warn!("GPU device lost: {message}");
+                device_lost.store(true, Ordering::Relaxed);
+            }
+        });
+    }
+
+    /// Recreate the render context, the window's surface and the renderer, after the GPU
+    /// device was lost.
+    ///
+    /// If this fails, the window is left without a surface, and recovering is tried again
+    /// when the next frame is rendered.
+    fn recover_from_device_loss(&mut self) -> Result<(), vello::Error> {
+        let WindowState::Rendering { surface, .. } = &mut self.window else {
+            return Ok(());
+        };
+        debug!("Recreating GPU device and surface");
+        // Some platforms don't allow two surfaces for the same window, so drop the old one first.
+        *surface = None;
+        self.renderer = None;
+        self.software_device = None;
+        self.render_cx = RenderContext::new()?;
+        self.device_lost.store(false, Ordering::Relaxed);
+        if self.ensure_surface() {
+            Ok(())
+        } else {
+            Err("couldn't create a surface for the window".into())
+        }
     }
 
-    fn handle_signals(&mut self, _event_loop: &ActiveEventLoop) {
+    /// Create the window's surface if it doesn't have one yet, and return whether it has one.
+    ///
+    /// If the surface can't be created, not even with a software adapter, the window can't
+    /// be rendered at all, so the event loop exits with the error.
+    fn ensure_surface(&mut self) -> bool {
+        let window = match &self.window {
+            WindowState::Rendering {
+                surface: Some(_), ..
+            } => return true,
+            WindowState::Rendering { window, .. } => window.clone(),
+            _ => return false,
+        };
+        match self.create_surface(&window) {
+            Ok(new_surface) => {
+                if let WindowState::Rendering { surface, .. } = &mut self.window {
+                    *surface = Some(new_surface);
+                }
+                self.surface_outdated = true;
+                true
+            }
+            Err(err) => {
+                error!("Couldn't initialize rendering: {err}");
+                self.render_error = Some(err.to_string());
+                false
+            }
+        }
+    }
+
+    /// Create a surface for `window`, using a software adapter if no hardware adapter can
+    /// be used.
+    ///
+    /// Fails if no adapter at all can be used.
+    fn create_surface(
+        &mut self,
+        window: &Arc<Window>,
+    ) -> Result<RenderSurface<'static>, vello::Error> {
+        let size = window.inner_size();
+        let present_mode = self.render_options.present_mode;
+        let create_surface = |render_cx: &mut RenderContext| {
+            pollster::block_on(render_cx.create_surface(
+                window.clone(),
+                size.width,
+                size.height,
+                present_mode,
+            ))
+        };
+        let surface = match create_surface(&mut self.render_cx) {
+            Ok(surface) => {
+                // The renderer was created for the software adapter's device.
+                if self.software_device.take().is_some() {
+                    self.renderer = None;
+                }
+                surface
+            }
+            Err(err) => {
+                warn!("Couldn't use a GPU for rendering ({err}), falling back to the CPU");
+                let (surface, software_device) =
+                    create_software_surface(window.clone(), size.width, size.height, present_mode)
+                        .map_err(|fallback_err| {
+                            format!(
+                                "no GPU could be used ({err}), \
+                                and no software adapter either ({fallback_err})"
+                            )
+                        })?;
+                self.renderer = None;
+                self.software_device = Some(software_device);
+                surface
+            }
+        };
+        self.watch_for_device_loss(surface.dev_id);
+        Ok(surface)
+    }
+
+    /// Read the platform's accessibility settings again.
+    ///
+    /// Reading them can mean waiting on another process, such as the XDG settings portal,
+    /// so it's done on a background thread, which sends them back as a
+    /// [`MasonryUserEvent::AccessibilitySettings`].
+    fn read_accessibility_settings(&mut self) {
+        let proxy = &self.proxy;
+        let reader = self
+            .settings_reader
+            .get_or_insert_with(|| spawn_settings_reader(proxy.clone()));
+        // The thread only stops once the event loop is gone.
+        let _ = reader.send(());
+    }
+
+    /// Apply the platform's accessibility settings, and use the high contrast theme
+    /// while its high contrast setting is on.
+    fn update_accessibility_settings(&mut self, high_contrast: bool, reduced_motion: bool) {
+        self.render_root.set_high_contrast(high_contrast);
+        self.render_root.set_reduced_motion(reduced_motion);
+        let theme = match &self.app_options.high_contrast_theme {
+            Some(theme) if high_contrast => theme,
+            _ => &self.app_options.theme,
+        };
+        if self.render_root.theme() != theme {
+            self.render_root.set_theme(theme.clone());
+        }
+    }
+
+    fn handle_signals(&mut self, event_loop: &ActiveEventLoop) {
         let WindowState::Rendering { window, .. } = &mut self.window else {
             tracing::warn!("Tried to handle a signal whilst suspended or before window created");
             return;
@@ -402,10 +1289,12 @@ impl MainState<'_> {
                     // TODO
                 }
                 render_root::RenderRootSignal::RequestRedraw => {
+                    self.redraw_requested_by_us = true;
                     window.request_redraw();
                 }
                 render_root::RenderRootSignal::RequestAnimFrame => {
                     // TODO
+                    self.redraw_requested_by_us = true;
                     window.request_redraw();
                 }
                 render_root::RenderRootSignal::SpawnWorker(_worker_fn) => {
@@ -414,7 +1303,20 @@ impl MainState<'_> {
                 render_root::RenderRootSignal::TakeFocus => {
                     window.focus_window();
                 }
-                render_root::RenderRootSignal::SetCursor(cursor) => {
+                render_root::RenderRootSignal::SetCursor(Cursor::Icon(icon)) => {
+                    window.set_cursor(icon);
+                }
+                render_root::RenderRootSignal::SetCursor(Cursor::Custom(image)) => {
+                    // Creating a platform cursor can be expensive, so we reuse the last one
+                    // if the image hasn't changed.
+                    let cursor = match &self.custom_cursor {
+                        Some((cached_image, cursor)) if *cached_image == image => cursor.clone(),
+                        _ => {
+                            let cursor = event_loop.create_custom_cursor(image.to_source());
+                            self.custom_cursor = Some((image, cursor.clone()));
+                            cursor
+                        }
+                    };
                     window.set_cursor(cursor);
                 }
                 render_root::RenderRootSignal::SetSize(size) => {
@@ -429,6 +1331,81 @@ impl MainState<'_> {
     }
 }
 
+/// The device and queue of the surface with the given `dev_id`.
+///
+/// Surfaces on a software adapter don't use the render context, so their `dev_id` is unused.
+fn device_and_queue<'a>(
+    render_cx: &'a RenderContext,
+    software_device: Option<&'a SoftwareDevice>,
+    dev_id: usize,
+) -> (&'a Device, &'a Queue) {
+    match software_device {
+        Some(software_device) => (&software_device.device, &software_device.queue),
+        None => {
+            let device_handle = &render_cx.devices[dev_id];
+            (&device_handle.device, &device_handle.queue)
+        }
+    }
+}
+
+/// Create a surface for `window` on a software adapter, such as llvmpipe or WARP, along
+/// with the device to render to it with.
+fn create_software_surface(
+    window: Arc<Window>,
+    width: u32,
+    height: u32,
+    present_mode: PresentMode,
+) -> Result<(RenderSurface<'static>, SoftwareDevice), vello::Error> {
+    // Software adapters, such as llvmpipe, are often only available through OpenGL.
+    let instance = Instance::new(InstanceDescriptor {
+        backends: Backends::all(),
+        ..Default::default()
+    });
+    let surface = instance.create_surface(window)?;
+    let adapter = pollster::block_on(instance.request_adapter(&RequestAdapterOptions {
+        power_preference: PowerPreference::default(),
+        force_fallback_adapter: true,
+        compatible_surface: Some(&surface),
+    }))
+    .ok_or("no software adapter is available")?;
+    debug!(
+        "Rendering with the software adapter {:?}",
+        adapter.get_info().name
+    );
+    let (device, queue) = pollster::block_on(adapter.request_device(
+        &DeviceDescriptor {
+            label: None,
+            required_features: adapter.features() & Features::CLEAR_TEXTURE,
+            required_limits: Limits::default(),
+        },
+        None,
+    ))?;
+    let format = surface
+        .get_capabilities(&adapter)
+        .formats
+        .into_iter()
+        .find(|it| matches!(it, TextureFormat::Rgba8Unorm | TextureFormat::Bgra8Unorm))
+        .ok_or("the software adapter can't render to the window")?;
+    let config = SurfaceConfiguration {
+        usage: TextureUsages::RENDER_ATTACHMENT,
+        format,
+        width,
+        height,
+        present_mode,
+        desired_maximum_frame_latency: 2,
+        alpha_mode: CompositeAlphaMode::Auto,
+        view_formats: vec![],
+    };
+    surface.configure(&device, &config);
+    let surface = RenderSurface {
+        surface,
+        config,
+        dev_id: 0,
+        format,
+    };
+    Ok((surface, SoftwareDevice { device, queue }))
+}
+
 pub(crate) fn try_init_tracing() -> Result<(), SetGlobalDefaultError> {
     #[cfg(not(target_arch = "wasm32"))]
     {
@@ -2,47 +2,31 @@
 // SPDX-License-Identifier: Apache-2.0
 
 //! Simple handle for submitting external events.
-#![allow(unused)]
 
 use std::any::Any;
-use std::collections::VecDeque;
 use std::sync::{Arc, Mutex};
 
-use druid_shell::IdleHandle;
+use winit::event_loop::EventLoopProxy;
 
-use crate::promise::PromiseResult;
+use crate::event_loop_runner::MasonryUserEvent;
 use crate::widget::WidgetId;
 
-// FIXME - Remove
-pub struct WindowId;
-
-pub(crate) enum ExtMessage {
-    Promise(PromiseResult, WidgetId, WindowId),
-}
-
-/// A thing that can move into other threads and be used to submit commands back
+/// A thing that can move into other threads and be used to submit actions back
 /// to the running application.
 ///
+/// Actions submitted before the sink is connected to an event loop with
+/// [`set_proxy`](Self::set_proxy) are queued, and sent once it is.
+///
 /// This API is preliminary, and may be changed or removed without warning.
-#[derive(Clone)]
+#[derive(Clone, Default)]
 pub struct ExtEventSink {
-    queue: Arc<Mutex<VecDeque<ExtMessage>>>,
-    handle: Arc<Mutex<Option<IdleHandle>>>,
+    inner: Arc<Mutex<SinkState>>,
 }
 
-/// The stuff that we hold onto inside the app that is related to the
-/// handling of external events.
 #[derive(Default)]
-pub(crate) struct ExtEventQueue {
-    /// A shared queue of items that have been sent to us.
-    queue: Arc<Mutex<VecDeque<ExtMessage>>>,
-    /// This doesn't exist when the app starts and it can go away if a window closes, so we keep a
-    /// reference here and can update it when needed. Note that this reference is shared with all
-    /// `ExtEventSink`s, so that we can update them too.
-    handle: Arc<Mutex<Option<IdleHandle>>>,
-    /// The window that the handle belongs to, so we can keep track of when
-    /// we need to get a new handle.
-    pub(crate) handle_window_id: Option<WindowId>,
+struct SinkState {
+    proxy: Option<EventLoopProxy<MasonryUserEvent>>,
+    pending: Vec<(Box<dyn Any + Send>, WidgetId)>,
 }
 
 /// An error that occurs if an external event cannot be submitted.
@@ -50,54 +34,51 @@ pub(crate) struct ExtEventQueue {
 #[derive(Debug, Clone)]
 pub struct ExtEventError;
 
-impl ExtEventQueue {
-    pub(crate) fn new() -> Self {
-        Default::default()
+impl ExtEventSink {
+    /// Create a sink which isn't connected to an event loop yet.
+    pub fn new() -> Self {
+        Self::default()
     }
 
-    pub(crate) fn make_sink(&self) -> ExtEventSink {
-        ExtEventSink {
-            queue: self.queue.clone(),
-            handle: self.handle.clone(),
+    /// Connect the sink to the event loop of the application, and send it the queued actions.
+    pub fn set_proxy(&self, proxy: EventLoopProxy<MasonryUserEvent>) {
+        let mut state = self.inner.lock().unwrap();
+        for (payload, target) in std::mem::take(&mut state.pending) {
+            // If the event loop is already gone, there's nobody left to tell.
+            let _ = proxy.send_event(MasonryUserEvent::Action(payload, target));
         }
+        state.proxy = Some(proxy);
     }
 
-    pub(crate) fn set_idle(&mut self, handle: IdleHandle, window_id: WindowId) {
-        self.handle.lock().unwrap().replace(handle);
-        self.handle_window_id = Some(window_id);
+    /// Take the actions submitted since the last call, while the sink isn't connected to
+    /// an event loop, e.g. to handle them when rendering offscreen.
+    pub fn take_pending(&self) -> Vec<(Box<dyn Any + Send>, WidgetId)> {
+        std::mem::take(&mut self.inner.lock().unwrap().pending)
     }
 
-    pub(crate) fn has_pending_items(&self) -> bool {
-        !self.queue.lock().unwrap().is_empty()
-    }
-
-    pub(crate) fn recv(&mut self) -> Option<ExtMessage> {
-        self.queue.lock().unwrap().pop_front()
-    }
-}
-
-impl ExtEventSink {
-    #[allow(missing_docs)]
-    pub fn resolve_promise(
+    /// Submit an [`Action::Other`](crate::Action::Other) with the given payload,
+    /// as if it was emitted by the `target` widget.
+    pub fn submit_action(
         &self,
-        result: PromiseResult,
-        target_widget: WidgetId,
-        target_window: WindowId,
+        payload: Box<dyn Any + Send>,
+        target: WidgetId,
     ) -> Result<(), ExtEventError> {
-        if let Some(handle) = self.handle.lock().unwrap().as_mut() {
-            todo!()
+        let mut state = self.inner.lock().map_err(|_| ExtEventError)?;
+        match &state.proxy {
+            Some(proxy) => proxy
+                .send_event(MasonryUserEvent::Action(payload, target))
+                .map_err(|_| ExtEventError),
+            None => {
+                state.pending.push((payload, target));
+                Ok(())
+            }
         }
-        self.queue
-            .lock()
-            .map_err(|_| ExtEventError)?
-            .push_back(ExtMessage::Promise(result, target_widget, target_window));
-        Ok(())
     }
 }
 
 impl std::fmt::Display for ExtEventError {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "Window missing for external event")
+        write!(f, "Event loop missing for external event")
     }
 }
 
@@ -0,0 +1,590 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Asking the user for a file to open or save.
+//!
+//! The functions in this module block until the dialog is closed. Calling them from an
+//! event handler freezes the app while the dialog is open, so prefer running them on
+//! another thread and sending the result back to the app, e.g. with an
+//! [`ExtEventSink`](crate::ext_event::ExtEventSink).
+//!
+//! By default, dialogs are the native ones of the platform on Windows and macOS. On Linux
+//! and the BSDs, they are shown through the file chooser of the XDG desktop portal, or by
+//! running the `zenity` program where the portal isn't available. Other platforms have no
+//! default, so showing a dialog returns an error there. Applications can show dialogs some other way by implementing
+//! [`FileDialogProvider`] and passing it to [`set_provider`].
+
+use std::io;
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::{Arc, Mutex};
+
+/// A set of file extensions the user can pick from, such as "Text files" for `txt` and `md`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FileFilter {
+    /// The label shown to the user.
+    pub name: String,
+    /// The extensions of the files shown, without the leading dot.
+    pub extensions: Vec<String>,
+}
+
+impl FileFilter {
+    /// Create a filter showing the files with one of the given `extensions`.
+    pub fn new(name: impl Into<String>, extensions: &[&str]) -> Self {
+        Self {
+            name: name.into(),
+            extensions: extensions.iter().map(|ext| (*ext).to_string()).collect(),
+        }
+    }
+}
+
+/// Something which can show file dialogs.
+///
+/// Several dialogs can be shown at the same time, from different threads.
+pub trait FileDialogProvider: Send + Sync {
+    /// Ask the user for an existing file, returning `None` if they cancel.
+    fn open_file(&self, filters: &[FileFilter]) -> io::Result<Option<PathBuf>>;
+
+    /// Ask the user where to save a file, returning `None` if they cancel.
+    ///
+    /// `file_name` is the name suggested to the user.
+    fn save_file(
+        &self,
+        filters: &[FileFilter],
+        file_name: Option<&str>,
+    ) -> io::Result<Option<PathBuf>>;
+}
+
+/// Shows the native file dialogs of the platform, on Windows and macOS.
+#[cfg(any(windows, target_os = "macos"))]
+#[derive(Debug, Default)]
+pub struct NativeProvider;
+
+#[cfg(any(windows, target_os = "macos"))]
+impl NativeProvider {
+    fn dialog(filters: &[FileFilter]) -> rfd::FileDialog {
+        filters
+            .iter()
+            .fold(rfd::FileDialog::new(), |dialog, filter| {
+                dialog.add_filter(filter.name.clone(), &filter.extensions)
+            })
+    }
+}
+
+#[cfg(any(windows, target_os = "macos"))]
+impl FileDialogProvider for NativeProvider {
+    fn open_file(&self, filters: &[FileFilter]) -> io::Result<Option<PathBuf>> {
+        Ok(Self::dialog(filters).pick_file())
+    }
+
+    fn save_file(
+        &self,
+        filters: &[FileFilter],
+        file_name: Option<&str>,
+    ) -> io::Result<Option<PathBuf>> {
+        let mut dialog = Self::dialog(filters);
+        if let Some(file_name) = file_name {
+            dialog = dialog.set_file_name(file_name);
+        }
+        Ok(dialog.save_file())
+    }
+}
+
+/// Shows file dialogs by running the `zenity` program.
+#[derive(Debug, Default)]
+pub struct ZenityProvider;
+
+impl ZenityProvider {
+    fn run(&self, mut command: Command, filters: &[FileFilter]) -> io::Result<Option<PathBuf>> {
+        command.arg("--file-selection");
+        for filter in filters {
+            command.arg(format!("--file-filter={}", zenity_filter(filter)));
+        }
+        let output = command
+            .output()
+            .map_err(|err| io::Error::new(err.kind(), format!("couldn't run zenity: {err}")))?;
+        if !output.status.success() {
+            // zenity exits with status 1 when the dialog is cancelled.
+            if output.status.code() == Some(1) {
+                return Ok(None);
+            }
+            return Err(io::Error::other(format!(
+                "zenity failed: {}",
+                output.status
+            )));
+        }
+        match String::from_utf8(output.stdout) {
+            Ok(path) => Ok(Some(PathBuf::from(path.trim_end_matches('\n')))),
+            Err(_) => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "zenity returned a path which isn't valid UTF-8",
+            )),
+        }
+    }
+}
+
+impl FileDialogProvider for ZenityProvider {
+    fn open_file(&self, filters: &[FileFilter]) -> io::Result<Option<PathBuf>> {
+        self.run(Command::new("zenity"), filters)
+    }
+
+    fn save_file(
+        &self,
+        filters: &[FileFilter],
+        file_name: Option<&str>,
+    ) -> io::Result<Option<PathBuf>> {
+        let mut command = Command::new("zenity");
+        command.args(["--save", "--confirm-overwrite"]);
+        if let Some(file_name) = file_name {
+            command.arg(format!("--filename={file_name}"));
+        }
+        self.run(command, filters)
+    }
+}
+
+/// The `--file-filter` argument of zenity, e.g. `Text files | *.txt *.md`.
+fn zenity_filter(filter: &FileFilter) -> String {
+    let patterns: Vec<_> = filter
+        .extensions
+        .iter()
+        .map(|ext| format!("*.{ext}"))
+        .collect();
+    format!("{} | {}", filter.name, patterns.join(" "))
+}
+
+/// Shows file dialogs through the file chooser of the XDG desktop portal, which uses the
+/// dialogs of the desktop environment, and works from inside sandboxes such as Flatpak.
+///
+/// If the portal isn't available, showing a dialog returns an error of kind
+/// [`NotFound`](io::ErrorKind::NotFound).
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+#[derive(Debug, Default)]
+pub struct PortalProvider;
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+impl PortalProvider {
+    /// Call `method` of the file chooser, and wait for the user to close the dialog.
+    fn run(
+        &self,
+        method: &str,
+        title: &str,
+        filters: &[FileFilter],
+        file_name: Option<&str>,
+    ) -> io::Result<Option<PathBuf>> {
+        use std::collections::HashMap;
+        use std::sync::atomic::{AtomicU64, Ordering};
+
+        use zbus::blocking::{Connection, MessageIterator};
+        use zbus::zvariant::{OwnedObjectPath, OwnedValue, Value};
+        use zbus::{MatchRule, MessageType};
+
+        static NEXT_TOKEN: AtomicU64 = AtomicU64::new(0);
+
+        let connection = Connection::session().map_err(|err| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("couldn't connect to the session bus: {err}"),
+            )
+        })?;
+        // The portal answers on a request object whose path is derived from our name on
+        // the bus and a token of ours. We listen on it before calling the portal, so that
+        // the answer can't be missed.
+        let token = format!(
+            "masonry_{}_{}",
+            std::process::id(),
+            NEXT_TOKEN.fetch_add(1, Ordering::Relaxed)
+        );
+        let sender = connection
+            .unique_name()
+            .map(|name| name.trim_start_matches(':').replace('.', "_"))
+            .unwrap_or_default();
+        let request_path = format!("/org/freedesktop/portal/desktop/request/{sender}/{token}");
+        let listen = |path: &str| {
+            let rule = MatchRule::builder()
+                .msg_type(MessageType::Signal)
+                .interface("org.freedesktop.portal.Request")
+                .and_then(|rule| rule.member("Response"))
+                .and_then(|rule| rule.path(path))
+                .map_err(portal_error)?
+                .build();
+            MessageIterator::for_match_rule(rule, &connection, None).map_err(portal_error)
+        };
+        let mut responses = listen(&request_path)?;
+
+        let filters: Vec<(String, Vec<(u32, String)>)> = filters
+            .iter()
+            .map(|filter| {
+                let patterns = filter
+                    .extensions
+                    .iter()
+                    // 0 marks glob patterns, rather than MIME types.
+                    .map(|ext| (0, format!("*.{ext}")))
+                    .collect();
+                (filter.name.clone(), patterns)
+            })
+            .collect();
+        let mut options: HashMap<&str, Value> = HashMap::new();
+        options.insert("handle_token", token.as_str().into());
+        if !filters.is_empty() {
+            options.insert("filters", filters.into());
+        }
+        if let Some(file_name) = file_name {
+            options.insert("current_name", file_name.into());
+        }
+        let reply = connection
+            .call_method(
+                Some("org.freedesktop.portal.Desktop"),
+                "/org/freedesktop/portal/desktop",
+                Some("org.freedesktop.portal.FileChooser"),
+                method,
+                // We have no handle to the parent window to give.
+                &("", title, options),
+            )
+            .map_err(portal_error)?;
+        // Old versions of the portal ignore the token, and answer on another object.
+        let handle: OwnedObjectPath = reply.body().map_err(portal_error)?;
+        if handle.as_str() != request_path {
+            responses = listen(handle.as_str())?;
+        }
+
+        let response = responses
+            .next()
+            .ok_or_else(|| io::Error::other("the portal closed the connection"))?
+            .map_err(portal_error)?;
+        let (status, results): (u32, HashMap<String, OwnedValue>) =
+            response.body().map_err(portal_error)?;
+        match status {
+            0 => {}
+            // The user cancelled the dialog.
+            1 => return Ok(None),
+            _ => return Err(io::Error::other("the portal couldn't show the file dialog")),
+        }
+        let uri = match results.get("uris").map(|uris| &**uris) {
+            Some(Value::Array(uris)) => match uris.first() {
+                Some(Value::Str(uri)) => uri.to_string(),
+                _ => return Ok(None),
+            },
+            _ => return Ok(None),
+        };
+        file_uri_to_path(&uri).map(Some).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("the portal returned {uri:?}, which isn't a local file"),
+            )
+        })
+    }
+}
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+impl FileDialogProvider for PortalProvider {
+    fn open_file(&self, filters: &[FileFilter]) -> io::Result<Option<PathBuf>> {
+        self.run("OpenFile", "Open File", filters, None)
+    }
+
+    fn save_file(
+        &self,
+        filters: &[FileFilter],
+        file_name: Option<&str>,
+    ) -> io::Result<Option<PathBuf>> {
+        self.run("SaveFile", "Save File", filters, file_name)
+    }
+}
+
+/// Turn an error from the bus into an [`io::Error`], of kind
+/// [`NotFound`](io::ErrorKind::NotFound) if it means that the file chooser portal isn't
+/// available.
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+fn portal_error(err: zbus::Error) -> io::Error {
+    use zbus::fdo;
+
+    let unavailable = match &err {
+        zbus::Error::MethodError(name, _, _) => [
+            "org.freedesktop.DBus.Error.ServiceUnknown",
+            "org.freedesktop.DBus.Error.UnknownMethod",
+            "org.freedesktop.DBus.Error.UnknownInterface",
+            "org.freedesktop.DBus.Error.UnknownObject",
+        ]
+        .contains(&name.as_str()),
+        zbus::Error::FDO(err) => matches!(
+            **err,
+            fdo::Error::ServiceUnknown(_)
+                | fdo::Error::UnknownMethod(_)
+                | fdo::Error::UnknownInterface(_)
+                | fdo::Error::UnknownObject(_)
+        ),
+        _ => false,
+    };
+    if unavailable {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("the file chooser portal isn't available: {err}"),
+        )
+    } else {
+        io::Error::other(format!("the file chooser portal failed: {err}"))
+    }
+}
+
+/// The path of a `file://` URI, with its percent-encoded bytes decoded.
+#[cfg(unix)]
+fn file_uri_to_path(uri: &str) -> Option<PathBuf> {
+    use std::ffi::OsString;
+    use std::os::unix::ffi::OsStringExt;
+
+    let encoded = uri.strip_prefix("file://")?.as_bytes();
+    let mut path = Vec::with_capacity(encoded.len());
+    let mut bytes = encoded.iter();
+    while let Some(&byte) = bytes.next() {
+        if byte == b'%' {
+            let hex = [*bytes.next()?, *bytes.next()?];
+            path.push(u8::from_str_radix(std::str::from_utf8(&hex).ok()?, 16).ok()?);
+        } else {
+            path.push(byte);
+        }
+    }
+    Some(OsString::from_vec(path).into())
+}
+
+/// Shows file dialogs through the XDG desktop portal, or with zenity where the portal
+/// isn't available.
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+struct PortalOrZenityProvider;
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+impl PortalOrZenityProvider {
+    fn show(
+        portal: impl FnOnce() -> io::Result<Option<PathBuf>>,
+        zenity: impl FnOnce() -> io::Result<Option<PathBuf>>,
+    ) -> io::Result<Option<PathBuf>> {
+        let portal_err = match portal() {
+            Err(err) if err.kind() == io::ErrorKind::NotFound => err,
+            result => return result,
+        };
+        match zenity() {
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!(
+                    "no file dialog is available, install xdg-desktop-portal or zenity \
+                     ({portal_err}; {err})"
+                ),
+            )),
+            result => result,
+        }
+    }
+}
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+impl FileDialogProvider for PortalOrZenityProvider {
+    fn open_file(&self, filters: &[FileFilter]) -> io::Result<Option<PathBuf>> {
+        Self::show(
+            || PortalProvider.open_file(filters),
+            || ZenityProvider.open_file(filters),
+        )
+    }
+
+    fn save_file(
+        &self,
+        filters: &[FileFilter],
+        file_name: Option<&str>,
+    ) -> io::Result<Option<PathBuf>> {
+        Self::show(
+            || PortalProvider.save_file(filters, file_name),
+            || ZenityProvider.save_file(filters, file_name),
+        )
+    }
+}
+
+/// The provider used until one is set, if the platform has one.
+fn default_provider() -> Option<Arc<dyn FileDialogProvider>> {
+    #[cfg(any(windows, target_os = "macos"))]
+    return Some(Arc::new(NativeProvider));
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    ))]
+    return Some(Arc::new(PortalOrZenityProvider));
+    #[cfg(all(
+        unix,
+        not(any(
+            target_os = "macos",
+            target_os = "android",
+            target_os = "ios",
+            target_os = "linux",
+            target_os = "dragonfly",
+            target_os = "freebsd",
+            target_os = "netbsd",
+            target_os = "openbsd"
+        ))
+    ))]
+    return Some(Arc::new(ZenityProvider));
+    #[allow(unreachable_code)]
+    None
+}
+
+static PROVIDER: Mutex<Option<Arc<dyn FileDialogProvider>>> = Mutex::new(None);
+
+/// The provider to show the next dialog with.
+///
+/// The lock is only held while it's looked up, so that dialogs shown from several
+/// threads don't wait for each other.
+fn provider() -> io::Result<Arc<dyn FileDialogProvider>> {
+    let mut provider = PROVIDER.lock().unwrap_or_else(|err| err.into_inner());
+    if provider.is_none() {
+        *provider = default_provider();
+    }
+    provider.clone().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::Unsupported,
+            "file dialogs aren't supported on this platform without a provider",
+        )
+    })
+}
+
+/// Use `provider` for all subsequent file dialogs.
+pub fn set_provider(provider: impl FileDialogProvider + 'static) {
+    *PROVIDER.lock().unwrap_or_else(|err| err.into_inner()) = Some(Arc::new(provider));
+}
+
+/// Ask the user for a file to open, among the files matching one of `filters`.
+///
+/// If `filters` is empty, all files are shown. Returns `None` if the user cancels, and
+/// an error if the dialog couldn't be shown.
+pub fn open_file_dialog(filters: &[FileFilter]) -> io::Result<Option<PathBuf>> {
+    provider()?.open_file(filters)
+}
+
+/// Ask the user where to save a file, suggesting `file_name`.
+///
+/// Returns `None` if the user cancels, and an error if the dialog couldn't be shown.
+pub fn save_file_dialog(
+    filters: &[FileFilter],
+    file_name: Option<&str>,
+) -> io::Result<Option<PathBuf>> {
+    provider()?.save_file(filters, file_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zenity_filter_lists_extensions() {
+        let filter = FileFilter::new("Text files", &["txt", "md"]);
+        assert_eq!(zenity_filter(&filter), "Text files | *.txt *.md");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn file_uri_is_decoded() {
+        assert_eq!(
+            file_uri_to_path("file:///home/user/My%20Notes/caf%C3%A9.txt"),
+            Some(PathBuf::from("/home/user/My Notes/café.txt"))
+        );
+        assert_eq!(file_uri_to_path("https://example.com/file.txt"), None);
+        assert_eq!(file_uri_to_path("file:///broken%2"), None);
+    }
+
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    ))]
+    #[test]
+    fn zenity_is_used_without_portal() {
+        let not_found = |what: &str| io::Error::new(io::ErrorKind::NotFound, what.to_string());
+
+        let path = PortalOrZenityProvider::show(
+            || Err(not_found("no portal")),
+            || Ok(Some(PathBuf::from("chosen.txt"))),
+        );
+        assert_eq!(path.unwrap(), Some(PathBuf::from("chosen.txt")));
+
+        // Errors other than a missing portal are reported as they are.
+        let err = PortalOrZenityProvider::show(
+            || Err(io::Error::other("portal failed")),
+            || unreachable!(),
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "portal failed");
+
+        let err = PortalOrZenityProvider::show(
+            || Err(not_found("no portal")),
+            || Err(not_found("no zenity")),
+        )
+        .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+        assert!(err.to_string().contains("no portal"));
+        assert!(err.to_string().contains("no zenity"));
+    }
+
+    struct ReplacingProvider;
+
+    impl FileDialogProvider for ReplacingProvider {
+        fn open_file(&self, _filters: &[FileFilter]) -> io::Result<Option<PathBuf>> {
+            // Would deadlock if the provider lock were held while the dialog is open.
+            set_provider(ReplacingProvider);
+            Ok(Some(PathBuf::from("chosen.txt")))
+        }
+
+        fn save_file(
+            &self,
+            _filters: &[FileFilter],
+            _file_name: Option<&str>,
+        ) -> io::Result<Option<PathBuf>> {
+            Ok(None)
+        }
+    }
+
+    #[test]
+    fn provider_lock_released_during_dialog() {
+        set_provider(ReplacingProvider);
+        let path = open_file_dialog(&[]).unwrap();
+        assert_eq!(path, Some(PathBuf::from("chosen.txt")));
+    }
+}
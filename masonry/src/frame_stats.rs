@@ -0,0 +1,166 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! An overlay showing how long recent frames took, to help find slow layout and paint code.
+//!
+//! See [`AppOptions::debug_overlay`](crate::event_loop_runner::AppOptions::debug_overlay).
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use instant::Instant;
+use kurbo::{Line, Point, Rect, Size};
+use parley::FontContext;
+use vello::peniko::Color;
+use vello::Scene;
+
+use crate::paint_scene_helpers::{fill_color, stroke};
+use crate::text2::TextLayout;
+
+/// How many frames are shown in the graph and averaged in the summary.
+const FRAME_COUNT: usize = 120;
+/// The width of each frame's bar in the graph.
+const BAR_WIDTH: f64 = 2.;
+const GRAPH_HEIGHT: f64 = 50.;
+/// The frame time shown at the top of the graph; longer frames are cut off.
+const GRAPH_MAX: Duration = Duration::from_millis(50);
+/// The frame time of a 60Hz display, drawn as a line across the graph.
+const TARGET_FRAME_TIME: Duration = Duration::from_micros(16_667);
+const MARGIN: f64 = 8.;
+const PADDING: f64 = 4.;
+
+const BACKGROUND_COLOR: Color = Color::rgba8(0, 0, 0, 0xc0);
+const REDRAW_COLOR: Color = Color::rgb8(0x5c, 0xc4, 0xff);
+const RENDER_COLOR: Color = Color::rgb8(0xff, 0xb8, 0x4c);
+const TARGET_COLOR: Color = Color::rgba8(0xff, 0xff, 0xff, 0x60);
+
+/// The timings of one frame.
+#[derive(Clone, Copy, Debug)]
+struct FrameTiming {
+    /// The time since the previous frame started.
+    interval: Option<Duration>,
+    /// The time spent running the layout and paint passes.
+    redraw: Duration,
+    /// The time spent rendering the scene and presenting it.
+    render: Duration,
+}
+
+/// The timings of recent frames, and the text displaying them.
+pub(crate) struct FrameStats {
+    frames: VecDeque<FrameTiming>,
+    last_frame_start: Option<Instant>,
+    text_layout: TextLayout<String>,
+}
+
+impl FrameStats {
+    pub(crate) fn new() -> Self {
+        let mut text_layout = TextLayout::new(String::new(), 12.);
+        text_layout.set_brush(Color::WHITE);
+        Self {
+            frames: VecDeque::with_capacity(FRAME_COUNT),
+            last_frame_start: None,
+            text_layout,
+        }
+    }
+
+    /// Record the timings of a frame which started at `start`.
+    pub(crate) fn record(&mut self, start: Instant, redraw: Duration, render: Duration) {
+        let interval = self.last_frame_start.map(|last| start - last);
+        self.last_frame_start = Some(start);
+        if self.frames.len() == FRAME_COUNT {
+            self.frames.pop_front();
+        }
+        self.frames.push_back(FrameTiming {
+            interval,
+            redraw,
+            render,
+        });
+    }
+
+    /// Returns a one-line summary of the recent frames.
+    fn summary(&self) -> String {
+        let count = self.frames.len().max(1) as u32;
+        let intervals: Vec<Duration> = self.frames.iter().filter_map(|f| f.interval).collect();
+        let fps = if intervals.is_empty() {
+            0.
+        } else {
+            let total: Duration = intervals.iter().sum();
+            intervals.len() as f64 / total.as_secs_f64()
+        };
+        let redraw = self.frames.iter().map(|f| f.redraw).sum::<Duration>() / count;
+        let render = self.frames.iter().map(|f| f.render).sum::<Duration>() / count;
+        format!(
+            "{fps:.0} fps | redraw {:.1}ms | render {:.1}ms",
+            redraw.as_secs_f64() * 1000.,
+            render.as_secs_f64() * 1000.,
+        )
+    }
+
+    /// Paint the overlay in the top right corner of a window of the given logical size.
+    pub(crate) fn paint(&mut self, scene: &mut Scene, font_ctx: &mut FontContext, window: Size) {
+        self.text_layout.set_text(self.summary());
+        self.text_layout.rebuild(font_ctx);
+        let text_size = self.text_layout.size();
+
+        let width = text_size.width.max(FRAME_COUNT as f64 * BAR_WIDTH) + 2. * PADDING;
+        let height = text_size.height + GRAPH_HEIGHT + 3. * PADDING;
+        let origin = Point::new(window.width - width - MARGIN, MARGIN);
+        fill_color(
+            scene,
+            &Rect::from_origin_size(origin, (width, height)),
+            BACKGROUND_COLOR,
+        );
+        self.text_layout.draw(scene, origin + (PADDING, PADDING));
+
+        // One bar per frame, from the oldest on the left, with the redraw time stacked
+        // below the render time.
+        let graph_bottom = origin.y + height - PADDING;
+        let scale = GRAPH_HEIGHT / GRAPH_MAX.as_secs_f64();
+        for (i, frame) in self.frames.iter().enumerate() {
+            let x = origin.x + PADDING + i as f64 * BAR_WIDTH;
+            let redraw = (frame.redraw.as_secs_f64() * scale).min(GRAPH_HEIGHT);
+            let render = (frame.render.as_secs_f64() * scale).min(GRAPH_HEIGHT - redraw);
+            let redraw_top = graph_bottom - redraw;
+            fill_color(
+                scene,
+                &Rect::new(x, redraw_top, x + BAR_WIDTH, graph_bottom),
+                REDRAW_COLOR,
+            );
+            fill_color(
+                scene,
+                &Rect::new(x, redraw_top - render, x + BAR_WIDTH, redraw_top),
+                RENDER_COLOR,
+            );
+        }
+        let target_y = graph_bottom - TARGET_FRAME_TIME.as_secs_f64() * scale;
+        stroke(
+            scene,
+            &Line::new(
+                (origin.x + PADDING, target_y),
+                (origin.x + width - PADDING, target_y),
+            ),
+            TARGET_COLOR,
+            1.,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary_averages_recent_frames() {
+        let mut stats = FrameStats::new();
+        let start = Instant::now();
+        for i in 0..(FRAME_COUNT as u32 + 10) {
+            stats.record(
+                start + Duration::from_millis(20) * i,
+                Duration::from_millis(2),
+                Duration::from_millis(4),
+            );
+        }
+        assert_eq!(stats.frames.len(), FRAME_COUNT);
+        assert_eq!(stats.summary(), "50 fps | redraw 2.0ms | render 4.0ms");
+    }
+}
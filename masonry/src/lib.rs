@@ -85,6 +85,7 @@
 
 // TODO - Add logo
 
+pub use chrono;
 pub use cursor_icon::{CursorIcon, ParseError as CursorIconParseError};
 pub use kurbo;
 pub use parley;
@@ -95,12 +96,25 @@ mod util;
 
 mod action;
 mod bloom;
+mod border;
 mod box_constraints;
+mod box_shadow;
+mod clip_shape;
+pub mod clipboard;
 mod contexts;
+mod cursor;
 mod event;
+mod frame_stats;
+pub mod ext_event;
+pub mod file_dialog;
+pub mod offscreen;
+mod padding;
 pub mod paint_scene_helpers;
 pub mod promise;
 pub mod render_root;
+mod scale;
+#[cfg(any(test, feature = "test-util"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "test-util")))]
 pub mod testing;
 // mod text;
 pub mod text_helpers;
@@ -115,13 +129,20 @@ pub mod event_loop_runner;
 pub mod text2;
 
 pub use action::Action;
+pub use border::{Border, BorderStyle, BorderWidths};
 pub use box_constraints::BoxConstraints;
+pub use box_shadow::BoxShadow;
+pub use clip_shape::ClipShape;
 pub use contexts::{AccessCtx, EventCtx, LayoutCtx, LifeCycleCtx, PaintCtx, WidgetCtx};
+pub use cursor::{Cursor, CustomCursorImage};
 pub use event::{
-    AccessEvent, InternalLifeCycle, LifeCycle, PointerEvent, StatusChange, TextEvent, WindowTheme,
+    AccessEvent, ClickSettings, InternalLifeCycle, LifeCycle, PointerEvent, ScrollDelta,
+    StatusChange, TextEvent, WindowTheme,
 };
 pub use kurbo::{Affine, Insets, Point, Rect, Size, Vec2};
+pub use padding::Padding;
 pub use parley::layout::Alignment as TextAlignment;
+pub use scale::ScaleCoefficient;
 pub use util::{AsAny, Handled};
 pub use vello::peniko::{Color, Gradient};
 pub use widget::{BackgroundBrush, Widget, WidgetId, WidgetPod, WidgetState};
@@ -0,0 +1,285 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Rendering a widget tree to an image, without a window.
+
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+
+use vello::peniko::{Blob, Format, Image};
+use vello::util::RenderContext;
+use vello::{block_on_wgpu, AaConfig, AaSupport, RenderParams, Renderer, RendererOptions, Scene};
+use wgpu::{
+    BufferDescriptor, BufferUsages, CommandEncoderDescriptor, Device, Extent3d, ImageCopyBuffer,
+    Queue, TextureDescriptor, TextureFormat, TextureUsages,
+};
+
+use crate::app_driver::{AppDriver, DriverCtx};
+use crate::event::WindowEvent;
+use crate::render_root::{RenderRoot, WindowSizePolicy};
+use crate::text2::IconFont;
+use crate::theme::Theme;
+use crate::widget::WidgetMut;
+use crate::{theme, Action, Color, ScaleCoefficient, Size, Widget, WidgetId};
+
+/// Renders a widget tree to images, e.g. to generate thumbnails or export the state of the UI.
+///
+/// The widget tree is laid out at the given logical size, and can be modified between
+/// renders with [`edit_root_widget`](Self::edit_root_widget). Since there is no window,
+/// the widgets don't receive any input, and the actions they submit are discarded.
+/// Actions from other sources can be passed to an [`AppDriver`] with
+/// [`handle_action`](Self::handle_action).
+///
+/// The GPU device and renderer are created on the first call to [`render`](Self::render),
+/// and reused for later renders.
+pub struct OffscreenRenderer {
+    render_root: RenderRoot,
+    size: Size,
+    scale_factor: ScaleCoefficient,
+    background_color: Color,
+    gpu: Option<GpuState>,
+}
+
+struct GpuState {
+    context: RenderContext,
+    device_id: usize,
+    renderer: Renderer,
+}
+
+impl OffscreenRenderer {
+    /// Create a renderer for `root_widget`, laid out at `size`.
+    pub fn new(root_widget: impl Widget, size: Size) -> Self {
+        let mut this = Self {
+            render_root: RenderRoot::new(
+                root_widget,
+                WindowSizePolicy::User,
+                ScaleCoefficient::ONE,
+            ),
+            size,
+            scale_factor: ScaleCoefficient::ONE,
+            background_color: theme::WINDOW_BACKGROUND_COLOR,
+            gpu: None,
+        };
+        this.resize();
+        this
+    }
+
+    /// Builder-style method for setting the color drawn behind the widgets.
+    ///
+    /// The default is the theme's window background color.
+    pub fn with_background_color(mut self, color: Color) -> Self {
+        self.background_color = color;
+        self
+    }
+
+    /// Builder-style method for setting the colors the widgets are painted with.
+    ///
+    /// This also sets the color drawn behind the widgets to the theme's window background.
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.background_color = theme.background;
+        self.render_root.set_theme(theme);
+        self
+    }
+
+    /// Builder-style method for setting the number of image pixels per logical pixel.
+    ///
+    /// The widgets are still laid out at the logical size. A factor below 1 can be used
+    /// to generate small thumbnails of a large layout.
+    pub fn with_scale_factor(mut self, scale_factor: ScaleCoefficient) -> Self {
+        self.set_scale_factor(scale_factor);
+        self
+    }
+
+    /// The logical size the widgets are laid out at.
+    pub fn size(&self) -> Size {
+        self.size
+    }
+
+    /// Set the logical size the widgets are laid out at.
+    pub fn set_size(&mut self, size: Size) {
+        self.size = size;
+        self.resize();
+    }
+
+    /// Set the number of image pixels per logical pixel.
+    pub fn set_scale_factor(&mut self, scale_factor: ScaleCoefficient) {
+        self.scale_factor = scale_factor;
+        self.render_root
+            .handle_window_event(WindowEvent::Rescale(scale_factor));
+        self.resize();
+    }
+
+    /// Register the fonts in the contents of a font file, so that text can use them.
+    ///
+    /// Like [`RenderRoot::add_font`], this should be called before the first render.
+    pub fn add_font(&mut self, data: &[u8]) -> Vec<String> {
+        self.render_root.add_font(data)
+    }
+
+    /// Register an icon font, so that icons can be shown by name.
+    ///
+    /// Like [`RenderRoot::add_icon_font`], this should be called before the first render.
+    pub fn add_icon_font(&mut self, font: &IconFont) {
+        self.render_root.add_icon_font(font);
+    }
+
+    pub fn edit_root_widget<R>(
+        &mut self,
+        f: impl FnOnce(WidgetMut<'_, Box<dyn Widget>>) -> R,
+    ) -> R {
+        self.render_root.edit_root_widget(f)
+    }
+
+    /// Pass `action` to `app_driver`, as if it was submitted by the `widget_id` widget.
+    pub fn handle_action(
+        &mut self,
+        app_driver: &mut dyn AppDriver,
+        widget_id: WidgetId,
+        action: Action,
+    ) {
+        self.render_root.edit_root_widget(|root| {
+            let mut driver_ctx = DriverCtx {
+                main_root_widget: root,
+            };
+            app_driver.on_action(&mut driver_ctx, widget_id, action);
+        });
+    }
+
+    /// Lay out and paint the widgets, and return the resulting image.
+    ///
+    /// The image is in the [`Rgba8`](Format::Rgba8) format, and its size in pixels is the
+    /// logical size multiplied by the scale factor, rounded up.
+    ///
+    /// # Panics
+    ///
+    /// If no compatible GPU device can be found.
+    pub fn render(&mut self) -> Image {
+        let (scene, _tree_update) = self.render_root.redraw();
+        // Nothing handles signals such as actions or cursor changes, so we drop them.
+        while self.render_root.pop_signal().is_some() {}
+
+        let scene = if self.scale_factor == ScaleCoefficient::ONE {
+            scene
+        } else {
+            let mut scaled_scene = Scene::new();
+            scaled_scene.append(&scene, Some(self.scale_factor.transform()));
+            scaled_scene
+        };
+
+        let gpu = self.gpu.get_or_insert_with(|| {
+            let mut context =
+                RenderContext::new().expect("Got non-Send/Sync error from creating render context");
+            let device_id =
+                pollster::block_on(context.device(None)).expect("No compatible device found");
+            let renderer = Renderer::new(
+                &context.devices[device_id].device,
+                RendererOptions {
+                    surface_format: None,
+                    use_cpu: false,
+                    num_init_threads: NonZeroUsize::new(1),
+                    antialiasing_support: AaSupport::area_only(),
+                },
+            )
+            .expect("Got non-Send/Sync error from creating renderer");
+            GpuState {
+                context,
+                device_id,
+                renderer,
+            }
+        });
+
+        let physical_size = self.scale_factor.to_physical_size(self.size);
+        let (width, height) = (physical_size.width, physical_size.height);
+        let render_params = RenderParams {
+            base_color: self.background_color,
+            width,
+            height,
+            antialiasing_method: AaConfig::Area,
+        };
+        let device_handle = &gpu.context.devices[gpu.device_id];
+        let data = render_to_rgba8(
+            &device_handle.device,
+            &device_handle.queue,
+            &mut gpu.renderer,
+            &scene,
+            &render_params,
+        );
+
+        Image::new(Blob::new(Arc::new(data)), Format::Rgba8, width, height)
+    }
+
+    fn resize(&mut self) {
+        let physical_size = self.scale_factor.to_physical_size(self.size);
+        self.render_root
+            .handle_window_event(WindowEvent::Resize(physical_size));
+    }
+}
+
+/// Render `scene` to a new texture, and read back its pixels as tightly packed RGBA8 rows.
+pub(crate) fn render_to_rgba8(
+    device: &Device,
+    queue: &Queue,
+    renderer: &mut Renderer,
+    scene: &Scene,
+    render_params: &RenderParams,
+) -> Vec<u8> {
+    let (width, height) = (render_params.width, render_params.height);
+    let size = Extent3d {
+        width,
+        height,
+        depth_or_array_layers: 1,
+    };
+    let target = device.create_texture(&TextureDescriptor {
+        label: Some("Target texture"),
+        size,
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: TextureFormat::Rgba8Unorm,
+        usage: TextureUsages::STORAGE_BINDING | TextureUsages::COPY_SRC,
+        view_formats: &[],
+    });
+    let view = target.create_view(&wgpu::TextureViewDescriptor::default());
+    renderer
+        .render_to_texture(device, queue, scene, &view, render_params)
+        .expect("Got non-Send/Sync error from rendering");
+    let padded_byte_width = (width * 4).next_multiple_of(256);
+    let buffer_size = padded_byte_width as u64 * height as u64;
+    let buffer = device.create_buffer(&BufferDescriptor {
+        label: Some("val"),
+        size: buffer_size,
+        usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+    let mut encoder = device.create_command_encoder(&CommandEncoderDescriptor {
+        label: Some("Copy out buffer"),
+    });
+    encoder.copy_texture_to_buffer(
+        target.as_image_copy(),
+        ImageCopyBuffer {
+            buffer: &buffer,
+            layout: wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(padded_byte_width),
+                rows_per_image: None,
+            },
+        },
+        size,
+    );
+
+    queue.submit([encoder.finish()]);
+    let buf_slice = buffer.slice(..);
+
+    let (sender, receiver) = futures_intrusive::channel::shared::oneshot_channel();
+    buf_slice.map_async(wgpu::MapMode::Read, move |v| sender.send(v).unwrap());
+    let recv_result = block_on_wgpu(device, receiver.receive()).expect("channel was closed");
+    recv_result.expect("failed to map buffer");
+
+    let data = buf_slice.get_mapped_range();
+    let mut result_unpadded = Vec::<u8>::with_capacity((width * height * 4).try_into().unwrap());
+    for row in 0..height {
+        let start = (row * padded_byte_width).try_into().unwrap();
+        result_unpadded.extend(&data[start..start + (width * 4) as usize]);
+    }
+    result_unpadded
+}
@@ -0,0 +1,90 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Empty space inside the edges of a widget.
+
+use kurbo::Insets;
+
+/// The empty space between the edges of a widget and its content, with a value for
+/// each side.
+///
+/// This converts to [`Insets`], so it can be passed wherever padding is set, such as
+/// [`SizedBox::with_padding`](crate::widget::SizedBox::with_padding).
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Padding {
+    /// The space above the content.
+    pub top: f64,
+    /// The space right of the content.
+    pub right: f64,
+    /// The space below the content.
+    pub bottom: f64,
+    /// The space left of the content.
+    pub left: f64,
+}
+
+impl Padding {
+    /// The same padding on each side.
+    pub const fn uniform(padding: f64) -> Self {
+        Self::axes(padding, padding)
+    }
+
+    /// Padding on the `left` and `right` sides only.
+    pub const fn horizontal(padding: f64) -> Self {
+        Self::axes(padding, 0.0)
+    }
+
+    /// Padding on the `top` and `bottom` sides only.
+    pub const fn vertical(padding: f64) -> Self {
+        Self::axes(0.0, padding)
+    }
+
+    /// The padding of the `left` and `right` sides, and of the `top` and `bottom` sides.
+    pub const fn axes(horizontal: f64, vertical: f64) -> Self {
+        Self {
+            top: vertical,
+            right: horizontal,
+            bottom: vertical,
+            left: horizontal,
+        }
+    }
+}
+
+impl From<f64> for Padding {
+    fn from(padding: f64) -> Self {
+        Self::uniform(padding)
+    }
+}
+
+impl From<Padding> for Insets {
+    fn from(padding: Padding) -> Self {
+        Insets::new(padding.left, padding.top, padding.right, padding.bottom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn padding_to_insets() {
+        let padding = Padding {
+            top: 1.0,
+            right: 2.0,
+            bottom: 3.0,
+            left: 4.0,
+        };
+        assert_eq!(Insets::from(padding), Insets::new(4.0, 1.0, 2.0, 3.0));
+        assert_eq!(
+            Insets::from(Padding::axes(2.0, 1.0)),
+            Insets::uniform_xy(2.0, 1.0)
+        );
+        assert_eq!(
+            Insets::from(Padding::horizontal(2.0)),
+            Insets::uniform_xy(2.0, 0.0)
+        );
+        assert_eq!(
+            Insets::from(Padding::vertical(2.0)),
+            Insets::uniform_xy(0.0, 2.0)
+        );
+    }
+}
@@ -11,7 +11,7 @@ use vello::{
 
 // TODO - Remove this file
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct UnitPoint {
     u: f64,
     v: f64,
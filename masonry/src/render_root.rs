@@ -1,7 +1,7 @@
 // Copyright 2019 the Xilem Authors and the Druid Authors
 // SPDX-License-Identifier: Apache-2.0
 
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
 
 use accesskit::{ActionRequest, NodeBuilder, Tree, TreeUpdate};
 // Automatically defaults to std::time::Instant on non Wasm platforms
@@ -11,35 +11,38 @@ use parley::FontContext;
 use tracing::{debug, info_span, warn};
 use vello::peniko::{Color, Fill};
 use vello::Scene;
-use winit::dpi::{LogicalPosition, LogicalSize, PhysicalSize};
+use winit::dpi::{LogicalPosition, PhysicalSize};
 use winit::keyboard::{KeyCode, PhysicalKey};
 
 use crate::contexts::{EventCtx, LayoutCtx, LifeCycleCtx, PaintCtx, WidgetCtx, WorkerFn};
 use crate::debug_logger::DebugLogger;
-use crate::event::{PointerEvent, TextEvent, WindowEvent};
-use crate::kurbo::Point;
+use crate::event::{ClickCounter, ClickSettings, PointerEvent, TextEvent, WindowEvent};
+use crate::kurbo::{Point, Rect, Size};
+use crate::text2::{IconFont, IconGlyph};
+use crate::theme::Theme;
 use crate::widget::{WidgetMut, WidgetState};
 use crate::{
-    AccessCtx, AccessEvent, Action, BoxConstraints, CursorIcon, Handled, InternalLifeCycle,
-    LifeCycle, Widget, WidgetId, WidgetPod,
+    AccessCtx, AccessEvent, Action, ArcStr, BoxConstraints, Cursor, Handled, InternalLifeCycle,
+    LifeCycle, ScaleCoefficient, Widget, WidgetId, WidgetPod,
 };
 
 // TODO - Remove pub(crate)
 pub struct RenderRoot {
     pub(crate) root: WidgetPod<Box<dyn Widget>>,
     pub(crate) size_policy: WindowSizePolicy,
+    /// The size of the window, in physical pixels.
     pub(crate) size: PhysicalSize<u32>,
-    // TODO - Currently this is always 1.0
-    // kurbo coordinates are assumed to be in logical pixels
-    pub(crate) scale_factor: f64,
     /// Is `Some` if the most recently displayed frame was an animation frame.
     pub(crate) last_anim: Option<Instant>,
     pub(crate) last_mouse_pos: Option<LogicalPosition<f64>>,
-    pub(crate) cursor_icon: CursorIcon,
+    pub(crate) click_counter: ClickCounter,
+    pub(crate) cursor: Cursor,
     pub(crate) state: RenderRootState,
     // TODO - Add "access_tree_active" to detect when you don't need to update the
     // access tree
     pub(crate) rebuild_access_tree: bool,
+    /// The area of the window which changed in the most recent paint pass.
+    pub(crate) last_damage: Option<Rect>,
 }
 
 pub(crate) struct RenderRootState {
@@ -48,6 +51,49 @@ pub(crate) struct RenderRootState {
     pub(crate) focused_widget: Option<WidgetId>,
     pub(crate) next_focused_widget: Option<WidgetId>,
     pub(crate) font_context: FontContext,
+    // kurbo coordinates are assumed to be in logical pixels
+    pub(crate) scale_factor: ScaleCoefficient,
+    /// The icons of the registered icon fonts, by name.
+    pub(crate) icons: HashMap<String, IconGlyph>,
+    pub(crate) overlays: Vec<Overlay>,
+    /// The owner of the overlay under the pointer, during a pointer event pass.
+    pub(crate) overlay_target: Option<WidgetId>,
+    /// The widget which captured the pointer, and receives all pointer events until it
+    /// releases it.
+    ///
+    /// See [`EventCtx::capture_pointer`].
+    pub(crate) pointer_capture: Option<WidgetId>,
+    /// The widget which had captured the pointer when the current pointer event pass
+    /// started, which receives the event even if it releases the capture meanwhile.
+    pub(crate) capture_target: Option<WidgetId>,
+    /// The area of the window, in logical pixels, whose content changed since the
+    /// last paint pass.
+    pub(crate) damage: Option<Rect>,
+    pub(crate) theme: Theme,
+    pub(crate) high_contrast: bool,
+    pub(crate) reduced_motion: bool,
+    /// Whether every widget is painted on the next paint pass, such as after the theme changed.
+    pub(crate) repaint_all: bool,
+    /// Whether widgets are only laid out to measure their intrinsic size.
+    ///
+    /// See [`Widget::intrinsic_size`]. Their state is left as it was, and the actions and
+    /// overlays they submit are dropped, since they're laid out for real afterwards.
+    pub(crate) measuring: bool,
+}
+
+/// A scene painted by a widget above the rest of the widget tree.
+///
+/// See [`LayoutCtx::set_overlay_rect`].
+pub(crate) struct Overlay {
+    pub(crate) owner: WidgetId,
+    /// The area which receives pointer events, in the owner's coordinate space.
+    pub(crate) rect: Rect,
+    pub(crate) scene: Scene,
+    /// Passive overlays are only painted: they don't receive pointer events,
+    /// and clicks outside of them don't dismiss them.
+    ///
+    /// See [`LayoutCtx::set_passive_overlay_rect`].
+    pub(crate) passive: bool,
 }
 
 /// Defines how a windows size should be determined
@@ -63,7 +109,6 @@ pub enum WindowSizePolicy {
     User,
 }
 
-// TODO - Handle custom cursors?
 // TODO - handling timers
 // TODO - Text fields
 pub enum RenderRootSignal {
@@ -78,29 +123,46 @@ pub enum RenderRootSignal {
     RequestAnimFrame,
     SpawnWorker(WorkerFn),
     TakeFocus,
-    SetCursor(CursorIcon),
+    SetCursor(Cursor),
     SetSize(PhysicalSize<u32>),
     SetTitle(String),
 }
 
 impl RenderRoot {
-    pub fn new(root_widget: impl Widget, size_policy: WindowSizePolicy, scale_factor: f64) -> Self {
+    pub fn new(
+        root_widget: impl Widget,
+        size_policy: WindowSizePolicy,
+        scale_factor: ScaleCoefficient,
+    ) -> Self {
         let mut root = RenderRoot {
             root: WidgetPod::new(root_widget).boxed(),
             size_policy,
             size: PhysicalSize::new(0, 0),
-            scale_factor,
             last_anim: None,
             last_mouse_pos: None,
-            cursor_icon: CursorIcon::Default,
+            click_counter: ClickCounter::default(),
+            cursor: Cursor::default(),
             state: RenderRootState {
                 debug_logger: DebugLogger::new(false),
                 signal_queue: VecDeque::new(),
                 focused_widget: None,
                 next_focused_widget: None,
                 font_context: FontContext::default(),
+                scale_factor,
+                icons: HashMap::new(),
+                overlays: Vec::new(),
+                overlay_target: None,
+                pointer_capture: None,
+                capture_target: None,
+                damage: None,
+                theme: Theme::default(),
+                high_contrast: false,
+                reduced_motion: false,
+                repaint_all: false,
+                measuring: false,
             },
             rebuild_access_tree: true,
+            last_damage: None,
         };
 
         // We send WidgetAdded to all widgets right away
@@ -117,10 +179,16 @@ impl RenderRoot {
     pub fn handle_window_event(&mut self, event: WindowEvent) -> Handled {
         match event {
             WindowEvent::Rescale(scale_factor) => {
-                self.scale_factor = scale_factor;
-                // TODO - What we'd really like is to request a repaint and an accessibility
-                // pass for every single widget.
+                // The pointer hasn't moved on the screen, but its logical position changed.
+                self.last_mouse_pos = self.last_mouse_pos.map(|pos| {
+                    pos.to_physical::<f64>(self.state.scale_factor.get())
+                        .to_logical(scale_factor.get())
+                });
+                self.state.scale_factor = scale_factor;
+                // Widgets are placed on the physical pixel grid, so they're laid out again.
+                // Accessibility nodes have physical bounds, so they're rebuilt too.
                 self.root.state.needs_layout = true;
+                self.rebuild_access_tree = true;
                 self.state
                     .signal_queue
                     .push_back(RenderRootSignal::RequestRedraw);
@@ -159,7 +227,143 @@ impl RenderRoot {
         }
     }
 
-    pub fn handle_pointer_event(&mut self, event: PointerEvent) -> Handled {
+    /// Set how consecutive clicks are grouped into double clicks.
+    pub fn set_click_settings(&mut self, settings: ClickSettings) {
+        self.click_counter.settings = settings;
+    }
+
+    /// Register the fonts in the contents of a font file, so that text can use them, and
+    /// return the names of their families.
+    ///
+    /// Text refers to fonts by family name, such as with
+    /// [`TextStyle::font_family`](crate::text2::TextStyle::font_family). If no
+    /// registered or installed font has the requested family, the text falls back to the
+    /// system's sans-serif font.
+    ///
+    /// This should be called before the first frame: text which has already been laid out
+    /// only picks up new fonts once it's laid out again.
+    pub fn add_font(&mut self, data: &[u8]) -> Vec<String> {
+        let collection = &mut self.state.font_context.collection;
+        let mut families = Vec::new();
+        for (id, _) in collection.register_fonts(data.to_vec()) {
+            if let Some(family) = collection.family_name(id) {
+                families.push(family.to_string());
+            }
+        }
+        if families.is_empty() {
+            warn!("Couldn't load font: no font family found in the data.");
+        }
+        self.root.state.needs_layout = true;
+        families
+    }
+
+    /// Register an icon font, so that its icons can be shown by [`Icon`](crate::widget::Icon)
+    /// widgets.
+    ///
+    /// This should be called before the first frame: `Icon` widgets which have already been
+    /// laid out only pick up new icons once they're laid out again.
+    ///
+    /// If several icon fonts have an icon with the same name, the last one registered is used.
+    pub fn add_icon_font(&mut self, font: &IconFont) {
+        let collection = &mut self.state.font_context.collection;
+        let families = collection.register_fonts(font.data.to_vec());
+        let Some(family) = families
+            .first()
+            .and_then(|(id, _)| collection.family_name(*id))
+        else {
+            warn!("Couldn't load icon font: no font family found in the data.");
+            return;
+        };
+        let family: ArcStr = family.into();
+        for (name, codepoint) in &font.codepoints {
+            let Some(character) = char::from_u32(*codepoint) else {
+                warn!("Icon {name:?} has invalid codepoint {codepoint:#x}, ignoring.");
+                continue;
+            };
+            self.state.icons.insert(
+                name.clone(),
+                IconGlyph {
+                    family: family.clone(),
+                    character,
+                },
+            );
+        }
+        self.root.state.needs_layout = true;
+    }
+
+    /// The colors widgets are painted with.
+    pub fn theme(&self) -> &Theme {
+        &self.state.theme
+    }
+
+    /// Set the colors widgets are painted with, and lay out and paint every widget again.
+    ///
+    /// Widgets pick the colors of their text during layout, so that it's only shaped once.
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.state.theme = theme;
+        self.root.state.needs_layout = true;
+        self.repaint_all();
+    }
+
+    /// Whether the user asked for high contrast, in the platform's accessibility settings.
+    pub fn high_contrast(&self) -> bool {
+        self.state.high_contrast
+    }
+
+    /// Set whether the user asked for high contrast.
+    ///
+    /// This only exposes the setting to widgets, with [`EventCtx::high_contrast`] and
+    /// the other contexts. The colors are changed with [`set_theme`](Self::set_theme).
+    pub fn set_high_contrast(&mut self, high_contrast: bool) {
+        if self.state.high_contrast != high_contrast {
+            self.state.high_contrast = high_contrast;
+            self.repaint_all();
+        }
+    }
+
+    /// Whether the user asked for less motion, in the platform's accessibility settings.
+    pub fn reduced_motion(&self) -> bool {
+        self.state.reduced_motion
+    }
+
+    /// Set whether the user asked for less motion, so that widgets skip their animations.
+    ///
+    /// See [`EventCtx::reduced_motion`].
+    pub fn set_reduced_motion(&mut self, reduced_motion: bool) {
+        if self.state.reduced_motion != reduced_motion {
+            self.state.reduced_motion = reduced_motion;
+            // Widgets which stopped animating start again on the next animation frame,
+            // which is sent to every widget.
+            if !reduced_motion {
+                self.root.state.request_anim = true;
+                self.state
+                    .signal_queue
+                    .push_back(RenderRootSignal::RequestAnimFrame);
+            }
+        }
+    }
+
+    /// Paint every widget on the next frame, not only those which requested it.
+    fn repaint_all(&mut self) {
+        self.state.repaint_all = true;
+        self.root.state.needs_paint = true;
+        self.state
+            .signal_queue
+            .push_back(RenderRootSignal::RequestRedraw);
+    }
+
+    pub fn handle_pointer_event(&mut self, mut event: PointerEvent) -> Handled {
+        match &mut event {
+            PointerEvent::PointerDown(button, state) => {
+                state.count = self
+                    .click_counter
+                    .press(*button, state.position, Instant::now());
+            }
+            PointerEvent::PointerUp(button, state) => {
+                state.count = self.click_counter.release(*button);
+            }
+            _ => {}
+        }
         self.root_on_pointer_event(event)
     }
 
@@ -167,6 +371,17 @@ impl RenderRoot {
         self.root_on_text_event(event)
     }
 
+    /// Run the layout pass if a widget requested it.
+    ///
+    /// [`redraw`](Self::redraw) does this too. Calling this first lets the caller handle
+    /// the actions submitted during layout, such as [`Action::BreakpointChanged`], before
+    /// the frame is painted.
+    pub fn layout_if_needed(&mut self) {
+        if self.root.state().needs_layout {
+            self.root_layout();
+        }
+    }
+
     pub fn redraw(&mut self) -> (Scene, TreeUpdate) {
         // TODO - Xilem's reconciliation logic will have to be called
         // by the function that calls this
@@ -199,8 +414,24 @@ impl RenderRoot {
         self.state.signal_queue.remove(idx)
     }
 
-    pub fn cursor_icon(&self) -> CursorIcon {
-        self.cursor_icon
+    /// The cursor requested by the widgets under the pointer.
+    pub fn cursor(&self) -> &Cursor {
+        &self.cursor
+    }
+
+    /// The scale factor used to convert between the logical pixels of the widget tree
+    /// and the physical pixels of the window.
+    pub fn scale_factor(&self) -> ScaleCoefficient {
+        self.state.scale_factor
+    }
+
+    /// The area of the window, in logical pixels, whose content changed in the most
+    /// recent call to [`redraw`](Self::redraw).
+    ///
+    /// Returns `None` if nothing changed, in which case the previous frame doesn't
+    /// need to be rendered again.
+    pub fn last_damage(&self) -> Option<Rect> {
+        self.last_damage
     }
 
     pub fn edit_root_widget<R>(
@@ -229,10 +460,38 @@ impl RenderRoot {
     }
 
     fn root_on_pointer_event(&mut self, event: PointerEvent) -> Handled {
+        let overlay_pos = match event {
+            PointerEvent::PointerDown(_, _)
+            | PointerEvent::PointerUp(_, _)
+            | PointerEvent::PointerMove(_)
+            | PointerEvent::MouseWheel(_, _) => {
+                let pos = event.pointer_state().position;
+                Some(Point::new(pos.x, pos.y))
+            }
+            _ => None,
+        };
+        // While the pointer is captured, overlays are ignored like the rest of the tree.
+        let overlay_target = overlay_pos
+            .filter(|_| self.state.pointer_capture.is_none())
+            .and_then(|pos| self.overlay_at(pos));
+
+        // A click outside of every overlay dismisses them, and isn't passed on to widgets.
+        if overlay_target.is_none()
+            && self.state.pointer_capture.is_none()
+            && self.state.overlays.iter().any(|overlay| !overlay.passive)
+            && matches!(event, PointerEvent::PointerDown(_, _))
+        {
+            self.last_mouse_pos = Some(event.pointer_state().position);
+            self.dismiss_overlays();
+            return Handled::Yes;
+        }
+
         let mut widget_state =
             WidgetState::new(self.root.id(), Some(self.get_kurbo_size()), "<root>");
 
         self.state.next_focused_widget = self.state.focused_widget;
+        self.state.overlay_target = overlay_target;
+        self.state.capture_target = self.state.pointer_capture;
         let mut ctx = EventCtx {
             global_state: &mut self.state,
             widget_state: &mut widget_state,
@@ -242,7 +501,7 @@ impl RenderRoot {
 
         // TODO - Only for primary pointer
         self.last_mouse_pos = match event {
-            PointerEvent::PointerLeave(_) | PointerEvent::HoverFile(_, _) => None,
+            PointerEvent::PointerLeave(_) | PointerEvent::HoverFileCancel(_) => None,
             _ => Some(event.pointer_state().position),
         };
 
@@ -256,18 +515,18 @@ impl RenderRoot {
             }
             self.root.on_pointer_event(&mut ctx, &event);
             ctx.global_state.debug_logger.pop_span();
-            Handled::from(ctx.is_handled)
+            // Events which land on an overlay never reach the widgets below it.
+            Handled::from(ctx.is_handled || overlay_target.is_some())
         };
+        ctx.global_state.overlay_target = None;
+        ctx.global_state.capture_target = None;
 
-        if let Some(cursor) = &ctx.widget_state.cursor {
-            // TODO - Add methods and `into()` impl to make this more concise.
+        let cursor = ctx.widget_state.cursor.take().unwrap_or_default();
+        if cursor != self.cursor {
+            self.cursor = cursor.clone();
             ctx.global_state
                 .signal_queue
-                .push_back(RenderRootSignal::SetCursor(*cursor));
-        } else {
-            ctx.global_state
-                .signal_queue
-                .push_back(RenderRootSignal::SetCursor(CursorIcon::Default));
+                .push_back(RenderRootSignal::SetCursor(cursor));
         }
 
         self.post_event_processing(&mut widget_state);
@@ -322,6 +581,7 @@ impl RenderRoot {
         let mut widget_state =
             WidgetState::new(self.root.id(), Some(self.get_kurbo_size()), "<root>");
 
+        self.state.next_focused_widget = self.state.focused_widget;
         let mut ctx = EventCtx {
             global_state: &mut self.state,
             widget_state: &mut widget_state,
@@ -348,12 +608,22 @@ impl RenderRoot {
             self.root.on_access_event(&mut ctx, &event);
             ctx.global_state.debug_logger.pop_span();
         }
+        let handled = ctx.is_handled;
+
+        // Assistive technology can move the focus to any widget which could get it from the
+        // keyboard, unless the widget handled the request itself.
+        if event.action == accesskit::Action::Focus
+            && !handled
+            && self.focus_chain().contains(&event.target)
+        {
+            self.state.next_focused_widget = Some(event.target);
+        }
 
         self.post_event_processing(&mut widget_state);
         self.root.as_dyn().debug_validate(false);
     }
 
-    fn root_lifecycle(&mut self, event: LifeCycle) {
+    pub(crate) fn root_lifecycle(&mut self, event: LifeCycle) {
         let mut widget_state =
             WidgetState::new(self.root.id(), Some(self.get_kurbo_size()), "<root>");
         let mut ctx = LifeCycleCtx {
@@ -377,9 +647,17 @@ impl RenderRoot {
     }
 
     pub(crate) fn root_layout(&mut self) {
+        let bc = match self.size_policy {
+            WindowSizePolicy::User => BoxConstraints::tight(self.get_kurbo_size()),
+            WindowSizePolicy::Content => BoxConstraints::UNBOUNDED,
+        };
+        self.root_layout_with_constraints(&bc);
+    }
+
+    /// Run the layout pass with the given constraints, and return the size of the root widget.
+    pub(crate) fn root_layout_with_constraints(&mut self, bc: &BoxConstraints) -> Size {
         let mut widget_state =
             WidgetState::new(self.root.id(), Some(self.get_kurbo_size()), "<root>");
-        let size = self.get_kurbo_size();
         let mouse_pos = self.last_mouse_pos.map(|pos| (pos.x, pos.y).into());
         let mut layout_ctx = LayoutCtx {
             global_state: &mut self.state,
@@ -387,23 +665,18 @@ impl RenderRoot {
             mouse_pos,
         };
 
-        let bc = match self.size_policy {
-            WindowSizePolicy::User => BoxConstraints::tight(size),
-            WindowSizePolicy::Content => BoxConstraints::UNBOUNDED,
-        };
-
         let size = {
             layout_ctx
                 .global_state
                 .debug_logger
                 .push_important_span("LAYOUT");
             let _span = info_span!("layout").entered();
-            self.root.layout(&mut layout_ctx, &bc)
+            self.root.layout(&mut layout_ctx, bc)
         };
         layout_ctx.global_state.debug_logger.pop_span();
 
         if let WindowSizePolicy::Content = self.size_policy {
-            let new_size = LogicalSize::new(size.width, size.height).to_physical(self.scale_factor);
+            let new_size = layout_ctx.global_state.scale_factor.to_physical_size(size);
             if self.size != new_size {
                 self.size = new_size;
                 layout_ctx
@@ -414,7 +687,11 @@ impl RenderRoot {
         }
 
         layout_ctx.place_child(&mut self.root, Point::ORIGIN);
+        // Widgets may have moved without being repainted, so we can't know which areas
+        // changed.
+        self.state.add_damage(size.to_rect());
         self.post_event_processing(&mut widget_state);
+        size
     }
 
     fn root_paint(&mut self) -> Scene {
@@ -434,6 +711,19 @@ impl RenderRoot {
             let _span = info_span!("paint").entered();
             self.root.paint(&mut ctx, &mut scene);
         }
+        self.state.repaint_all = false;
+
+        // Overlays are painted last, so that they're drawn above every widget.
+        let root = self.root.as_dyn();
+        self.state.overlays.retain(|overlay| {
+            let Some(owner) = root.find_widget_by_id(overlay.owner) else {
+                // The owner was removed from the tree.
+                return false;
+            };
+            let transform = Affine::translate(owner.state().window_origin().to_vec2());
+            scene.append(&overlay.scene, Some(transform));
+            true
+        });
 
         // FIXME - This is a workaround to Vello panicking when given an
         // empty scene
@@ -447,6 +737,7 @@ impl RenderRoot {
             &empty_path,
         );
 
+        self.last_damage = self.state.damage.take();
         scene
     }
 
@@ -465,7 +756,6 @@ impl RenderRoot {
             tree_update: &mut tree_update,
             current_node: NodeBuilder::default(),
             rebuild_all: self.rebuild_access_tree,
-            scale_factor: self.scale_factor,
         };
 
         {
@@ -490,8 +780,7 @@ impl RenderRoot {
     }
 
     fn get_kurbo_size(&self) -> kurbo::Size {
-        let size = self.size.to_logical(self.scale_factor);
-        kurbo::Size::new(size.width, size.height)
+        self.state.scale_factor.to_logical_size(self.size)
     }
 
     fn post_event_processing(&mut self, widget_state: &mut WidgetState) {
@@ -502,6 +791,16 @@ impl RenderRoot {
             // Send TextFieldRemoved signal
 
             self.root_lifecycle(LifeCycle::Internal(InternalLifeCycle::RouteWidgetAdded));
+
+            // A widget which was removed from the tree can't release its capture.
+            // Widgets are only removed when children change, so the tree is only
+            // searched then, rather than on every pointer event.
+            if let Some(id) = self.state.pointer_capture {
+                if self.root.as_dyn().find_widget_by_id(id).is_none() {
+                    debug!("Widget {id:?} was removed while capturing the pointer, releasing it.");
+                    self.state.pointer_capture = None;
+                }
+            }
         }
 
         if self.state.debug_logger.layout_tree.root.is_none() {
@@ -553,7 +852,7 @@ impl RenderRoot {
     }
 
     /// `true` iff any child requested an animation frame since the last `AnimFrame` event.
-    fn wants_animation_frame(&self) -> bool {
+    pub(crate) fn wants_animation_frame(&self) -> bool {
         self.root.state().request_anim
     }
 
@@ -575,6 +874,37 @@ impl RenderRoot {
         }
     }
 
+    /// Return the owner of the topmost non-passive overlay containing `pos`, in window coordinates.
+    fn overlay_at(&self, pos: Point) -> Option<WidgetId> {
+        let root = self.root.as_dyn();
+        self.state.overlays.iter().rev().find_map(|overlay| {
+            if overlay.passive {
+                return None;
+            }
+            let owner = root.find_widget_by_id(overlay.owner)?;
+            let rect = overlay.rect + owner.state().window_origin().to_vec2();
+            rect.contains(pos).then_some(overlay.owner)
+        })
+    }
+
+    /// Remove every non-passive overlay, and notify their owners.
+    fn dismiss_overlays(&mut self) {
+        let (passive, overlays) = std::mem::take(&mut self.state.overlays)
+            .into_iter()
+            .partition(|overlay| overlay.passive);
+        self.state.overlays = passive;
+        for overlay in overlays {
+            let event = LifeCycle::Internal(InternalLifeCycle::RouteOverlayDismissed {
+                owner: overlay.owner,
+            });
+            self.root_lifecycle(event);
+        }
+        self.state.add_damage(self.get_kurbo_size().to_rect());
+        self.state
+            .signal_queue
+            .push_back(RenderRootSignal::RequestRedraw);
+    }
+
     fn widget_from_focus_chain(&self, forward: bool) -> Option<WidgetId> {
         self.state.focused_widget.and_then(|focus| {
             self.focus_chain()
@@ -616,3 +946,13 @@ TODO:
 - prepare_paint
 - Focus-related stuff
 */
+
+impl RenderRootState {
+    /// Mark an area of the window, in logical pixels, as changed.
+    pub(crate) fn add_damage(&mut self, rect: Rect) {
+        self.damage = Some(match self.damage {
+            Some(damage) => damage.union(rect),
+            None => rect,
+        });
+    }
+}
@@ -0,0 +1,131 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Conversions between logical and physical pixels.
+
+use kurbo::{Affine, Point, Rect, Size};
+use tracing::warn;
+use winit::dpi::PhysicalSize;
+
+/// The number of physical pixels per logical pixel.
+///
+/// Widgets are laid out and painted in logical pixels, while the window surface and the
+/// accessibility tree use physical pixels. This type marks the places where values cross
+/// from one space to the other. The layout pass places widgets on the physical pixel grid,
+/// so that their edges stay sharp with fractional scale factors.
+///
+/// Conversions keep the full `f64` precision, so fractional scale factors (e.g. 1.25 or 1.5)
+/// don't accumulate rounding errors. Values are only rounded when converting to a whole number
+/// of physical pixels.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ScaleCoefficient(f64);
+
+impl ScaleCoefficient {
+    /// A scale factor of 1, where logical and physical pixels are the same.
+    pub const ONE: Self = Self(1.0);
+
+    /// Create a scale coefficient from the factor reported by the platform.
+    ///
+    /// If `factor` isn't a finite, strictly positive number, a warning is logged and a
+    /// factor of 1 is used instead.
+    pub fn new(factor: f64) -> Self {
+        if factor.is_finite() && factor > 0.0 {
+            Self(factor)
+        } else {
+            warn!("Invalid scale factor {factor}, using 1 instead.");
+            Self::ONE
+        }
+    }
+
+    /// The number of physical pixels per logical pixel.
+    pub fn get(self) -> f64 {
+        self.0
+    }
+
+    /// The transform from logical to physical coordinates.
+    pub fn transform(self) -> Affine {
+        Affine::scale(self.0)
+    }
+
+    /// Convert a size in physical pixels to logical pixels.
+    pub fn to_logical_size(self, size: PhysicalSize<u32>) -> Size {
+        Size::new(size.width as f64 / self.0, size.height as f64 / self.0)
+    }
+
+    /// Convert a size in logical pixels to a whole number of physical pixels.
+    ///
+    /// Partial pixels are rounded up, so that the result always covers `size`.
+    pub fn to_physical_size(self, size: Size) -> PhysicalSize<u32> {
+        // Multiplying can introduce error in the last bits of the result, which we don't
+        // want to round up to a whole extra pixel.
+        let to_physical = |value: f64| {
+            let value = value * self.0;
+            let rounded = value.round();
+            if (value - rounded).abs() < 1e-9 {
+                rounded as u32
+            } else {
+                value.ceil() as u32
+            }
+        };
+        PhysicalSize::new(to_physical(size.width), to_physical(size.height))
+    }
+
+    /// Round a point in logical pixels to the nearest physical pixel.
+    pub fn snap_point(self, point: Point) -> Point {
+        let snap = |value: f64| (value * self.0).round() / self.0;
+        Point::new(snap(point.x), snap(point.y))
+    }
+
+    /// Convert a rectangle in logical pixels to physical pixels.
+    pub fn to_physical_rect(self, rect: Rect) -> Rect {
+        self.transform().transform_rect_bbox(rect)
+    }
+}
+
+impl Default for ScaleCoefficient {
+    fn default() -> Self {
+        Self::ONE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fractional_round_trip() {
+        for factor in [1.0, 1.25, 1.5, 1.75, 2.0, 2.25] {
+            let scale = ScaleCoefficient::new(factor);
+            let physical = PhysicalSize::new(1001, 667);
+            let logical = scale.to_logical_size(physical);
+            assert_eq!(scale.to_physical_size(logical), physical, "factor {factor}");
+        }
+    }
+
+    #[test]
+    fn partial_pixels_round_up() {
+        let scale = ScaleCoefficient::new(1.25);
+        // 10.1 * 1.25 = 12.625
+        assert_eq!(
+            scale.to_physical_size(Size::new(10.1, 10.)),
+            PhysicalSize::new(13, 13)
+        );
+    }
+
+    #[test]
+    fn points_snap_to_physical_pixels() {
+        let scale = ScaleCoefficient::new(1.25);
+        // 10.1 * 1.25 = 12.625, the nearest physical pixel is 13.
+        assert_eq!(
+            scale.snap_point(Point::new(10.1, 8.0)),
+            Point::new(10.4, 8.0)
+        );
+    }
+
+    #[test]
+    fn invalid_factor_falls_back_to_one() {
+        for factor in [0.0, -2.0, f64::NAN, f64::INFINITY] {
+            assert_eq!(ScaleCoefficient::new(factor), ScaleCoefficient::ONE);
+        }
+    }
+}
@@ -4,26 +4,29 @@
 //! Tools and infrastructure for testing widgets.
 
 use std::num::NonZeroUsize;
+use std::path::PathBuf;
+use std::time::Duration;
 
+use accesskit::ActionRequest;
 use image::io::Reader as ImageReader;
 use image::{Rgba, RgbaImage};
 use vello::util::RenderContext;
-use vello::{block_on_wgpu, RendererOptions};
-use wgpu::{
-    BufferDescriptor, BufferUsages, CommandEncoderDescriptor, Extent3d, ImageCopyBuffer,
-    TextureDescriptor, TextureFormat, TextureUsages,
-};
-use winit::dpi::{LogicalPosition, PhysicalPosition, PhysicalSize};
+use vello::{RendererOptions, Scene};
+use winit::dpi::{PhysicalPosition, PhysicalSize};
 use winit::event::{Ime, MouseButton};
 
 use super::screenshots::get_image_diff;
 use super::snapshot_utils::get_cargo_workspace;
 use crate::action::Action;
-use crate::event::{PointerEvent, PointerState, TextEvent, WindowEvent};
+use crate::event::{PointerEvent, PointerState, ScrollDelta, TextEvent, WindowEvent};
 use crate::event_loop_runner::try_init_tracing;
+use crate::offscreen::render_to_rgba8;
 use crate::render_root::{RenderRoot, RenderRootSignal, WindowSizePolicy};
 use crate::widget::{WidgetMut, WidgetRef};
-use crate::{Color, Handled, Point, Size, Vec2, Widget, WidgetId};
+use crate::{
+    BoxConstraints, Color, Cursor, Handled, LifeCycle, Point, Rect, ScaleCoefficient, Size, Vec2,
+    Widget, WidgetId,
+};
 
 // TODO - Get shorter names
 // TODO - Make them associated consts
@@ -181,8 +184,15 @@ impl TestHarness {
         // harnesses.
         let _ = try_init_tracing();
 
+        // Tests shouldn't read or overwrite the user's clipboard.
+        crate::clipboard::set_provider(crate::clipboard::MemoryClipboard::default());
+
         let mut harness = TestHarness {
-            render_root: RenderRoot::new(root_widget, WindowSizePolicy::User, 1.0),
+            render_root: RenderRoot::new(
+                root_widget,
+                WindowSizePolicy::User,
+                ScaleCoefficient::ONE,
+            ),
             mouse_state,
             window_size,
             background_color,
@@ -227,12 +237,67 @@ impl TestHarness {
         handled
     }
 
+    /// Send an action to the widget, as assistive technology would.
+    ///
+    /// If this event triggers lifecycle events, they will also be dispatched.
+    pub fn process_access_event(&mut self, event: ActionRequest) {
+        self.render_root.root_on_access_event(event);
+        self.process_state_after_event();
+    }
+
+    /// Set whether the user asks for less motion, as if it was the platform's setting.
+    ///
+    /// See [`RenderRoot::set_reduced_motion`].
+    pub fn set_reduced_motion(&mut self, reduced_motion: bool) {
+        self.render_root.set_reduced_motion(reduced_motion);
+        self.process_state_after_event();
+    }
+
+    /// Send an animation frame to the widgets, as if `interval` passed since the last one.
+    ///
+    /// [`WindowEvent::AnimFrame`] measures the time since the last frame, so this is how
+    /// tests move animations forward by a known amount. Like it, this does nothing if no
+    /// widget requested an animation frame.
+    pub fn animate_by(&mut self, interval: Duration) {
+        if self.render_root.wants_animation_frame() {
+            let interval = interval.as_nanos() as u64;
+            self.render_root
+                .root_lifecycle(LifeCycle::AnimFrame(interval));
+        }
+        self.process_state_after_event();
+    }
+
     fn process_state_after_event(&mut self) {
         if self.root_widget().state().needs_layout {
             self.render_root.root_layout();
         }
     }
 
+    /// Run the layout and paint passes without rendering, and return the area of the window
+    /// whose content changed since they last ran.
+    ///
+    /// See [`RenderRoot::last_damage`].
+    pub fn paint_damage(&mut self) -> Option<Rect> {
+        let _ = self.render_root.redraw();
+        self.render_root.last_damage()
+    }
+
+    /// Run the layout and paint passes, and return the painted scene without rendering it.
+    ///
+    /// Unlike [`render`](Self::render), this doesn't need a GPU, so tests can make
+    /// assertions about what was painted, for instance with [`Scene::encoding`].
+    pub fn paint_scene(&mut self) -> Scene {
+        self.render_root.redraw().0
+    }
+
+    /// Run the layout pass with the given constraints instead of the tight constraints of
+    /// the window size, and return the size of the root widget.
+    ///
+    /// The next layout pass, for instance after an event, uses the window size again.
+    pub fn layout_with_constraints(&mut self, bc: BoxConstraints) -> Size {
+        self.render_root.root_layout_with_constraints(&bc)
+    }
+
     // TODO - We add way too many dependencies in this code
     // TODO - Should be async?
     /// Create a bitmap (an array of pixels), paint the window and return the bitmap as an 8-bits-per-channel RGB image.
@@ -271,66 +336,9 @@ impl TestHarness {
             antialiasing_method: vello::AaConfig::Area,
         };
 
-        let size = Extent3d {
-            width,
-            height,
-            depth_or_array_layers: 1,
-        };
-        let target = device.create_texture(&TextureDescriptor {
-            label: Some("Target texture"),
-            size,
-            mip_level_count: 1,
-            sample_count: 1,
-            dimension: wgpu::TextureDimension::D2,
-            format: TextureFormat::Rgba8Unorm,
-            usage: TextureUsages::STORAGE_BINDING | TextureUsages::COPY_SRC,
-            view_formats: &[],
-        });
-        let view = target.create_view(&wgpu::TextureViewDescriptor::default());
-        renderer
-            .render_to_texture(device, queue, &scene, &view, &render_params)
-            .expect("Got non-Send/Sync error from rendering");
-        let padded_byte_width = (width * 4).next_multiple_of(256);
-        let buffer_size = padded_byte_width as u64 * height as u64;
-        let buffer = device.create_buffer(&BufferDescriptor {
-            label: Some("val"),
-            size: buffer_size,
-            usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
-            mapped_at_creation: false,
-        });
-        let mut encoder = device.create_command_encoder(&CommandEncoderDescriptor {
-            label: Some("Copy out buffer"),
-        });
-        encoder.copy_texture_to_buffer(
-            target.as_image_copy(),
-            ImageCopyBuffer {
-                buffer: &buffer,
-                layout: wgpu::ImageDataLayout {
-                    offset: 0,
-                    bytes_per_row: Some(padded_byte_width),
-                    rows_per_image: None,
-                },
-            },
-            size,
-        );
-
-        queue.submit([encoder.finish()]);
-        let buf_slice = buffer.slice(..);
-
-        let (sender, receiver) = futures_intrusive::channel::shared::oneshot_channel();
-        buf_slice.map_async(wgpu::MapMode::Read, move |v| sender.send(v).unwrap());
-        let recv_result = block_on_wgpu(device, receiver.receive()).expect("channel was closed");
-        recv_result.expect("failed to map buffer");
-
-        let data = buf_slice.get_mapped_range();
-        let mut result_unpadded =
-            Vec::<u8>::with_capacity((width * height * 4).try_into().unwrap());
-        for row in 0..height {
-            let start = (row * padded_byte_width).try_into().unwrap();
-            result_unpadded.extend(&data[start..start + (width * 4) as usize]);
-        }
+        let data = render_to_rgba8(device, queue, &mut renderer, &scene, &render_params);
 
-        RgbaImage::from_vec(width, height, result_unpadded).expect("failed to create image")
+        RgbaImage::from_vec(width, height, data).expect("failed to create image")
     }
 
     // --- Event helpers ---
@@ -342,8 +350,8 @@ impl TestHarness {
         let pos = PhysicalPosition::new(pos.x, pos.y);
         self.mouse_state.physical_position = dbg!(pos);
         // TODO: may want to support testing with non-unity scale factors.
-        let scale_factor = 1.0;
-        self.mouse_state.position = pos.to_logical(scale_factor);
+        let scale_factor = self.render_root.scale_factor();
+        self.mouse_state.position = pos.to_logical(scale_factor.get());
 
         self.process_pointer_event(PointerEvent::PointerMove(self.mouse_state.clone()));
     }
@@ -360,15 +368,35 @@ impl TestHarness {
         self.process_pointer_event(PointerEvent::PointerUp(button, self.mouse_state.clone()));
     }
 
-    /// Send a Wheel event to the window
+    /// Send a Wheel event to the window, scrolling by `wheel_delta` logical pixels.
     pub fn mouse_wheel(&mut self, wheel_delta: Vec2) {
-        let pixel_delta = LogicalPosition::new(wheel_delta.x, wheel_delta.y);
         self.process_pointer_event(PointerEvent::MouseWheel(
-            pixel_delta,
+            ScrollDelta::Precise(wheel_delta),
+            self.mouse_state.clone(),
+        ));
+    }
+
+    /// Send a HoverFile event to the window, at the internal mouse position.
+    pub fn hover_file(&mut self, path: impl Into<PathBuf>) {
+        self.process_pointer_event(PointerEvent::HoverFile(
+            path.into(),
+            self.mouse_state.clone(),
+        ));
+    }
+
+    /// Send a DropFile event to the window, at the internal mouse position.
+    pub fn drop_file(&mut self, path: impl Into<PathBuf>) {
+        self.process_pointer_event(PointerEvent::DropFile(
+            path.into(),
             self.mouse_state.clone(),
         ));
     }
 
+    /// Send a HoverFileCancel event to the window.
+    pub fn cancel_file_hover(&mut self) {
+        self.process_pointer_event(PointerEvent::HoverFileCancel(self.mouse_state.clone()));
+    }
+
     /// Send events that lead to a given widget being clicked.
     ///
     /// Combines [`mouse_move`](Self::mouse_move), [`mouse_button_press`](Self::mouse_button_press), and [`mouse_button_release`](Self::mouse_button_release).
@@ -454,6 +482,11 @@ impl TestHarness {
             .find_widget_by_id(self.render_root.state.focused_widget?)
     }
 
+    /// Return the cursor requested by the widgets under the mouse.
+    pub fn cursor(&self) -> &Cursor {
+        self.render_root.cursor()
+    }
+
     /// Call the provided visitor on every widget in the widget tree.
     pub fn inspect_widgets(&mut self, f: impl Fn(WidgetRef<'_, dyn Widget>) + 'static) {
         fn inspect(
@@ -495,6 +528,18 @@ impl TestHarness {
         }
     }
 
+    /// Pop the window title requested by widgets, if any.
+    pub fn pop_window_title(&mut self) -> Option<String> {
+        let signal = self
+            .render_root
+            .pop_signal_matching(|signal| matches!(signal, RenderRootSignal::SetTitle(..)));
+        match signal {
+            Some(RenderRootSignal::SetTitle(title)) => Some(title),
+            Some(_) => unreachable!(),
+            _ => None,
+        }
+    }
+
     // --- Screenshots ---
 
     /// Method used by [`assert_render_snapshot`]. Use the macro instead.
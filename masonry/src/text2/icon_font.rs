@@ -0,0 +1,95 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use tracing::warn;
+
+use crate::ArcStr;
+
+/// A font whose glyphs are icons, such as Material Icons or Font Awesome, with the
+/// names of its icons.
+///
+/// Icon fonts are registered when the app starts, and their icons are shown by name
+/// with an [`Icon`](crate::widget::Icon) widget.
+#[derive(Clone, PartialEq, Eq)]
+pub struct IconFont {
+    pub(crate) data: Arc<[u8]>,
+    pub(crate) codepoints: HashMap<String, u32>,
+}
+
+impl IconFont {
+    /// Create an icon font from the contents of a font file, without any named icons.
+    pub fn new(data: impl Into<Arc<[u8]>>) -> Self {
+        Self {
+            data: data.into(),
+            codepoints: HashMap::new(),
+        }
+    }
+
+    /// Builder-style method to name the icon at `codepoint`.
+    pub fn with_icon(mut self, name: impl Into<String>, codepoint: u32) -> Self {
+        self.codepoints.insert(name.into(), codepoint);
+        self
+    }
+
+    /// Builder-style method to name icons from a list with one icon per line, made of
+    /// its name and hexadecimal codepoint separated by a space.
+    ///
+    /// This is the format of the `codepoints` file distributed with Material Icons.
+    /// Invalid lines are skipped.
+    pub fn with_codepoints(mut self, list: &str) -> Self {
+        for line in list.lines().filter(|line| !line.trim().is_empty()) {
+            let parsed = line
+                .split_once(' ')
+                .and_then(|(name, hex)| Some((name, u32::from_str_radix(hex.trim(), 16).ok()?)));
+            match parsed {
+                Some((name, codepoint)) => {
+                    self.codepoints.insert(name.to_string(), codepoint);
+                }
+                None => warn!("Invalid line in icon codepoints: {line:?}"),
+            }
+        }
+        self
+    }
+
+    /// Returns the codepoint of the icon with the given name, if there is one.
+    pub fn codepoint(&self, name: &str) -> Option<u32> {
+        self.codepoints.get(name).copied()
+    }
+}
+
+impl std::fmt::Debug for IconFont {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IconFont")
+            .field("data", &format_args!("{} bytes", self.data.len()))
+            .field("icons", &self.codepoints.len())
+            .finish()
+    }
+}
+
+/// An icon from a registered [`IconFont`].
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct IconGlyph {
+    pub(crate) family: ArcStr,
+    pub(crate) character: char,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_codepoints() {
+        let font = IconFont::new(Vec::new())
+            .with_codepoints("home e88a\nsearch e8b6\n\nnot-an-icon\nbad zzzz\n")
+            .with_icon("star", 0xe838);
+
+        assert_eq!(font.codepoint("home"), Some(0xe88a));
+        assert_eq!(font.codepoint("search"), Some(0xe8b6));
+        assert_eq!(font.codepoint("star"), Some(0xe838));
+        assert_eq!(font.codepoint("bad"), None);
+        assert_eq!(font.codepoint("not-an-icon"), None);
+    }
+}
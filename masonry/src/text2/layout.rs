@@ -10,11 +10,13 @@ use parley::context::RangedBuilder;
 use parley::fontique::{Style, Weight};
 use parley::layout::{Alignment, Cursor};
 use parley::style::{Brush as BrushTrait, FontFamily, FontStack, GenericFamily, StyleProperty};
+use parley::swash::text::{BidiClass, Codepoint};
 use parley::{FontContext, Layout, LayoutContext};
 use vello::peniko::{self, Color, Gradient};
 use vello::Scene;
 
-use super::{Link, TextStorage};
+use super::{Link, TextStorage, TextStyle};
+use crate::ArcStr;
 
 /// A component for displaying text on screen.
 ///
@@ -44,11 +46,16 @@ pub struct TextLayout<T> {
 
     brush: TextBrush,
     font: FontStack<'static>,
+    /// A named font family, used instead of `font` if set.
+    font_name: Option<ArcStr>,
     text_size: f32,
     weight: Weight,
     style: Style,
+    line_height: f32,
+    letter_spacing: f32,
 
     alignment: Alignment,
+    direction: TextDirection,
     max_advance: Option<f32>,
 
     links: Rc<[(Rect, usize)]>,
@@ -98,6 +105,28 @@ impl Default for TextBrush {
     }
 }
 
+/// The base direction of a paragraph of text.
+///
+/// Within a paragraph, runs of text in the other direction (such as an English word in
+/// Hebrew text) are reordered by Parley according to the Unicode bidirectional algorithm.
+/// The base direction decides which side the lines are aligned to by default.
+///
+/// TODO: Parley currently always resolves the bidirectional levels of a left-to-right
+/// paragraph. Right-to-left text on its own is laid out correctly, but in a right-to-left
+/// paragraph which contains left-to-right text (such as an English word in an Arabic
+/// sentence), the right-to-left runs on either side of it are shown in left-to-right order.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TextDirection {
+    /// The direction of the first strongly directional character, or left-to-right if
+    /// there is none.
+    #[default]
+    Auto,
+    /// Left-to-right.
+    Ltr,
+    /// Right-to-left.
+    Rtl,
+}
+
 /// Metrics describing the layout text.
 #[derive(Debug, Clone, Copy, Default)]
 pub struct LayoutMetrics {
@@ -119,12 +148,16 @@ impl<T> TextLayout<T> {
 
             brush: crate::theme::TEXT_COLOR.into(),
             font: FontStack::Single(FontFamily::Generic(GenericFamily::SansSerif)),
+            font_name: None,
             text_size,
             weight: Weight::NORMAL,
             style: Style::Normal,
+            line_height: 1.0,
+            letter_spacing: 0.0,
 
             max_advance: None,
             alignment: Default::default(),
+            direction: TextDirection::Auto,
 
             links: Rc::new([]),
 
@@ -167,8 +200,21 @@ impl<T> TextLayout<T> {
 
     /// Set the default font stack.
     pub fn set_font(&mut self, font: FontStack<'static>) {
-        if font != self.font {
+        if font != self.font || self.font_name.is_some() {
             self.font = font;
+            self.font_name = None;
+            self.invalidate();
+        }
+    }
+
+    /// Set the default font to the family with the given name.
+    ///
+    /// Unlike [`set_font`](Self::set_font), this accepts names which aren't known
+    /// at compile time. If the font isn't available, a sans-serif font is used.
+    pub fn set_font_name(&mut self, name: impl Into<ArcStr>) {
+        let name = name.into();
+        if self.font_name.as_ref() != Some(&name) {
+            self.font_name = Some(name);
             self.invalidate();
         }
     }
@@ -197,6 +243,43 @@ impl<T> TextLayout<T> {
         }
     }
 
+    /// Set the height of each line, relative to the line height given by the metrics
+    /// of the font.
+    ///
+    /// This is a multiplier rather than an absolute length, so it scales with the font
+    /// size. The default is `1.0`.
+    pub fn set_line_height(&mut self, line_height: f32) {
+        if line_height != self.line_height {
+            self.line_height = line_height;
+            self.invalidate();
+        }
+    }
+
+    /// Set the extra space added after each character, in logical pixels.
+    ///
+    /// Unlike the line height, this is an absolute length, which doesn't scale with the
+    /// font size. It can be negative to bring characters closer together.
+    pub fn set_letter_spacing(&mut self, letter_spacing: f32) {
+        if letter_spacing != self.letter_spacing {
+            self.letter_spacing = letter_spacing;
+            self.invalidate();
+        }
+    }
+
+    /// Set the font, size, weight, color and line height of the text at once.
+    pub fn set_text_style(&mut self, text_style: &TextStyle) {
+        match &text_style.font_family {
+            Some(name) => self.set_font_name(name.clone()),
+            None => self.set_font(FontStack::Single(FontFamily::Generic(
+                GenericFamily::SansSerif,
+            ))),
+        }
+        self.set_text_size(text_style.font_size);
+        self.set_weight(text_style.font_weight);
+        self.set_brush(text_style.color);
+        self.set_line_height(text_style.line_height);
+    }
+
     /// Set the [`Alignment`] for this layout.
     pub fn set_text_alignment(&mut self, alignment: Alignment) {
         if self.alignment != alignment {
@@ -205,6 +288,24 @@ impl<T> TextLayout<T> {
         }
     }
 
+    /// The [`Alignment`] of this layout.
+    pub fn text_alignment(&self) -> Alignment {
+        self.alignment
+    }
+
+    /// Set the base [`TextDirection`] of this layout.
+    pub fn set_text_direction(&mut self, direction: TextDirection) {
+        if self.direction != direction {
+            self.direction = direction;
+            self.needs_line_breaks = true;
+        }
+    }
+
+    /// The base [`TextDirection`] of this layout, which may be [`TextDirection::Auto`].
+    pub fn text_direction(&self) -> TextDirection {
+        self.direction
+    }
+
     /// Set the width at which to wrap words.
     ///
     /// You may pass `None` to disable word wrapping
@@ -233,16 +334,6 @@ impl<T> TextLayout<T> {
     pub fn needs_rebuild(&self) -> bool {
         self.needs_layout || self.needs_line_breaks
     }
-
-    // TODO: What are the valid use cases for this, where we shouldn't use a run-specific check instead?
-    // /// Returns `true` if this layout's text appears to be right-to-left.
-    // ///
-    // /// See [`piet::util::first_strong_rtl`] for more information.
-    // ///
-    // /// [`piet::util::first_strong_rtl`]: crate::piet::util::first_strong_rtl
-    // pub fn text_is_rtl(&self) -> bool {
-    //     self.text_is_rtl
-    // }
 }
 
 impl<T: TextStorage> TextLayout<T> {
@@ -264,6 +355,30 @@ impl<T: TextStorage> TextLayout<T> {
         }
     }
 
+    /// Returns `true` if the base direction of this layout is right-to-left.
+    ///
+    /// With [`TextDirection::Auto`], this is decided by the first strongly directional
+    /// character of the text.
+    pub fn is_rtl(&self) -> bool {
+        match self.direction {
+            TextDirection::Ltr => false,
+            TextDirection::Rtl => true,
+            TextDirection::Auto => first_strong_rtl(self.text.as_str()),
+        }
+    }
+
+    /// The alignment the lines are actually laid out with.
+    ///
+    /// Parley always aligns [`Alignment::Start`] to the left, so in right-to-left text
+    /// `Start` and `End` are swapped.
+    pub fn line_alignment(&self) -> Alignment {
+        match self.alignment {
+            Alignment::Start if self.is_rtl() => Alignment::End,
+            Alignment::End if self.is_rtl() => Alignment::Start,
+            alignment => alignment,
+        }
+    }
+
     /// Returns the [`TextStorage`] backing this layout, if it exists.
     pub fn text(&self) -> &T {
         &self.text
@@ -444,9 +559,20 @@ impl<T: TextStorage> TextLayout<T> {
                     .ranged_builder(fcx, self.text.as_str(), self.scale);
             builder.push_default(&StyleProperty::Brush(self.brush.clone()));
             builder.push_default(&StyleProperty::FontSize(self.text_size));
-            builder.push_default(&StyleProperty::FontStack(self.font));
+            match &self.font_name {
+                Some(name) => {
+                    let families = [
+                        FontFamily::Named(name),
+                        FontFamily::Generic(GenericFamily::SansSerif),
+                    ];
+                    builder.push_default(&StyleProperty::FontStack(FontStack::List(&families)));
+                }
+                None => builder.push_default(&StyleProperty::FontStack(self.font)),
+            }
             builder.push_default(&StyleProperty::FontWeight(self.weight));
             builder.push_default(&StyleProperty::FontStyle(self.style));
+            builder.push_default(&StyleProperty::LineHeight(self.line_height));
+            builder.push_default(&StyleProperty::LetterSpacing(self.letter_spacing));
             // For more advanced features (e.g. variable font axes), these can be set in add_attributes
 
             let builder = self.text.add_attributes(builder);
@@ -457,8 +583,8 @@ impl<T: TextStorage> TextLayout<T> {
         }
         if self.needs_line_breaks {
             self.needs_line_breaks = false;
-            self.layout
-                .break_all_lines(self.max_advance, self.alignment);
+            let alignment = self.line_alignment();
+            self.layout.break_all_lines(self.max_advance, alignment);
 
             // TODO:
             // self.links = text
@@ -496,7 +622,10 @@ impl<T: TextStorage> std::fmt::Debug for TextLayout<T> {
             .field("text_size", &self.text_size)
             .field("weight", &self.weight)
             .field("style", &self.style)
+            .field("line_height", &self.line_height)
+            .field("letter_spacing", &self.letter_spacing)
             .field("alignment", &self.alignment)
+            .field("direction", &self.direction)
             .field("wrap_width", &self.max_advance)
             .field("outdated?", &self.needs_rebuild())
             .field("width", &self.layout.width())
@@ -505,6 +634,17 @@ impl<T: TextStorage> std::fmt::Debug for TextLayout<T> {
     }
 }
 
+/// Returns `true` if the first strongly directional character of `text` is right-to-left.
+fn first_strong_rtl(text: &str) -> bool {
+    text.chars()
+        .find_map(|c| match c.bidi_class() {
+            BidiClass::L => Some(false),
+            BidiClass::R | BidiClass::AL => Some(true),
+            _ => None,
+        })
+        .unwrap_or(false)
+}
+
 impl<T: TextStorage + Default> Default for TextLayout<T> {
     fn default() -> Self {
         Self::new(Default::default(), crate::theme::TEXT_SIZE_NORMAL as f32)
@@ -14,7 +14,17 @@ mod store;
 pub use store::{Link, TextStorage};
 
 mod layout;
-pub use layout::{LayoutMetrics, TextBrush, TextLayout};
+pub use layout::{LayoutMetrics, TextBrush, TextDirection, TextLayout};
+
+mod icon_font;
+pub(crate) use icon_font::IconGlyph;
+pub use icon_font::IconFont;
+
+mod style;
+pub use style::TextStyle;
+
+mod styled;
+pub use styled::{StyledText, TextSpanStyle};
 
 mod selection;
 pub use selection::{
@@ -80,10 +80,22 @@ impl<T: Selectable> TextWithSelection<T> {
                     return true;
                 }
             }
-            self.selection = Some(Selection::caret(
-                position.insert_point,
-                Affinity::Downstream,
-            ));
+            let text = self.layout.text();
+            let offset = position.insert_point;
+            // A double click selects a word, and a triple click a line. Dragging after them
+            // would shrink the selection back to the pointer, so they don't start a drag.
+            let range = match state.count {
+                0 | 1 => None,
+                2 => Some(word_range(text.as_str(), offset)),
+                _ => Some(text.preceding_line_break(offset)..text.next_line_break(offset)),
+            };
+            self.selection = Some(match range {
+                Some(range) => {
+                    self.selecting_with_mouse = false;
+                    Selection::new(range.start, range.end, Affinity::Downstream)
+                }
+                None => Selection::caret(offset, Affinity::Downstream),
+            });
             true
         } else {
             false
@@ -161,23 +173,19 @@ impl<T: Selectable> TextWithSelection<T> {
                     winit::keyboard::Key::Character(chr) => match &*chr {
                         "a" if mods.control_key() || /* macOS, yes this is a hack */ mods.super_key() =>
                         {
-                            self.selection =
-                                Some(Selection::new(0, self.text().len(), Affinity::Downstream));
-                            self.needs_selection_update = true;
+                            self.select_all();
                             Handled::Yes
                         }
                         "c" if mods.control_key() || mods.super_key() => {
-                            let selection = self.selection.unwrap_or(Selection {
-                                anchor: 0,
-                                active: 0,
-                                active_affinity: Affinity::Downstream,
-                                h_pos: None,
-                            });
+                            let Some(selection) = self.selection.filter(|s| !s.is_caret()) else {
+                                // Copying nothing would still clear the clipboard.
+                                return Handled::Yes;
+                            };
                             // TODO: We know this is not the fullest model of copy-paste, and that we should work with the inner text
                             // e.g. to put HTML code if supported by the rich text kind
                             if let Some(text) = self.text().slice(selection.min()..selection.max())
                             {
-                                println!(r#"Copying "{text}""#);
+                                crate::clipboard::set_text(text);
                             } else {
                                 debug_panic!("Had invalid selection");
                             }
@@ -203,6 +211,18 @@ impl<T: Selectable> TextWithSelection<T> {
         }
     }
 
+    /// Select all of the text.
+    pub fn select_all(&mut self) {
+        self.selection = Some(Selection::new(0, self.text().len(), Affinity::Downstream));
+        self.needs_selection_update = true;
+    }
+
+    /// Put the caret at the end of the text.
+    pub fn move_caret_to_end(&mut self) {
+        self.selection = Some(Selection::caret(self.text().len(), Affinity::Downstream));
+        self.needs_selection_update = true;
+    }
+
     /// Call when another widget becomes focused
     pub fn focus_lost(&mut self) {
         self.selection = None;
@@ -263,6 +283,20 @@ impl<T: Selectable> TextWithSelection<T> {
     }
 }
 
+/// The range of the word at `offset`, or of the whitespace or punctuation between words.
+///
+/// This uses Unicode word boundaries, as defined in [UAX#29].
+///
+/// [UAX#29]: http://www.unicode.org/reports/tr29/
+fn word_range(text: &str, offset: usize) -> Range<usize> {
+    text.split_word_bound_indices()
+        .map(|(ix, word)| ix..(ix + word.len()))
+        // Past the end of the text, this is the last word.
+        .take_while(|range| range.start <= offset)
+        .last()
+        .unwrap_or(offset..offset)
+}
+
 /// Get the key which should be used for shortcuts from the underlying event
 ///
 /// `key_without_modifiers` is only available on some platforms
@@ -556,7 +590,10 @@ pub trait EditableTextCursor {
 }
 
 impl<Str: Deref<Target = str> + TextStorage> Selectable for Str {
-    type Cursor<'a> = StringCursor<'a> where Self: 'a;
+    type Cursor<'a>
+        = StringCursor<'a>
+    where
+        Self: 'a;
 
     fn cursor<'a>(&self, position: usize) -> Option<StringCursor> {
         let new_cursor = StringCursor {
@@ -907,4 +944,15 @@ mod tests {
         assert_eq!(b.len(), b.next_line_break(13));
         assert_eq!(b.len(), b.next_line_break(19));
     }
+
+    #[test]
+    fn word_range_for_offset() {
+        let a = "hello world, again";
+        assert_eq!(word_range(a, 3), 0..5);
+        assert_eq!(word_range(a, 6), 6..11);
+        assert_eq!(word_range(a, 5), 5..6);
+        assert_eq!(word_range(a, 11), 11..12);
+        assert_eq!(word_range(a, a.len()), 13..18);
+        assert_eq!(word_range("", 0), 0..0);
+    }
 }
@@ -0,0 +1,39 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+use parley::style::FontWeight;
+use vello::peniko::Color;
+
+use crate::ArcStr;
+
+/// The default styling of a piece of text.
+///
+/// This groups the properties which are most often set together, so that they can be
+/// applied at once with [`TextLayout::set_text_style`](super::TextLayout::set_text_style).
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextStyle {
+    /// The name of the font family, such as `"Roboto"`.
+    ///
+    /// If `None`, or if the font isn't available, a sans-serif font is used.
+    pub font_family: Option<ArcStr>,
+    /// The font size, in logical pixels.
+    pub font_size: f32,
+    /// The weight of the font, such as [`FontWeight::BOLD`].
+    pub font_weight: FontWeight,
+    /// The color of the text.
+    pub color: Color,
+    /// The height of each line, as a multiple of the line height of the font.
+    pub line_height: f32,
+}
+
+impl Default for TextStyle {
+    fn default() -> Self {
+        Self {
+            font_family: None,
+            font_size: crate::theme::TEXT_SIZE_NORMAL as f32,
+            font_weight: FontWeight::NORMAL,
+            color: crate::theme::TEXT_COLOR,
+            line_height: 1.0,
+        }
+    }
+}
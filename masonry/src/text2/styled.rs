@@ -0,0 +1,227 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Text made of differently styled spans.
+
+use std::ops::Range;
+
+use parley::context::RangedBuilder;
+use parley::style::{FontStyle, FontWeight, StyleProperty};
+use vello::peniko::Color;
+
+use super::layout::TextBrush;
+use super::TextStorage;
+
+/// The style of a span of [`StyledText`].
+///
+/// Properties which are `None` or `false` are those of the surrounding text, as set
+/// on the [`TextLayout`](super::TextLayout) displaying it.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TextSpanStyle {
+    /// The weight of the font, such as [`FontWeight::BOLD`].
+    pub font_weight: Option<FontWeight>,
+    /// Whether the text is italic.
+    pub italic: bool,
+    /// The color of the text, which is also used for its underline and strikethrough.
+    pub color: Option<Color>,
+    /// The font size, in logical pixels.
+    pub font_size: Option<f32>,
+    /// A multiplier for the line height given by the metrics of the font.
+    ///
+    /// A line is as tall as the tallest of the spans on it.
+    pub line_height: Option<f32>,
+    /// Whether the text is underlined.
+    pub underline: bool,
+    /// Whether the text is struck through.
+    pub strikethrough: bool,
+}
+
+impl TextSpanStyle {
+    /// Builder-style method to make the text bold.
+    pub fn bold(self) -> Self {
+        self.font_weight(FontWeight::BOLD)
+    }
+
+    /// Builder-style method to set the weight of the font.
+    pub fn font_weight(mut self, font_weight: FontWeight) -> Self {
+        self.font_weight = Some(font_weight);
+        self
+    }
+
+    /// Builder-style method to make the text italic.
+    pub fn italic(mut self) -> Self {
+        self.italic = true;
+        self
+    }
+
+    /// Builder-style method to set the color of the text.
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Builder-style method to set the font size, in logical pixels.
+    pub fn font_size(mut self, font_size: f32) -> Self {
+        self.font_size = Some(font_size);
+        self
+    }
+
+    /// Builder-style method to scale the line height given by the metrics of the font.
+    pub fn line_height(mut self, line_height: f32) -> Self {
+        self.line_height = Some(line_height);
+        self
+    }
+
+    /// Builder-style method to underline the text.
+    pub fn underline(mut self) -> Self {
+        self.underline = true;
+        self
+    }
+
+    /// Builder-style method to strike through the text.
+    pub fn strikethrough(mut self) -> Self {
+        self.strikethrough = true;
+        self
+    }
+
+    fn add_attributes<'b>(
+        &self,
+        builder: &mut RangedBuilder<'b, TextBrush, &'b str>,
+        range: Range<usize>,
+    ) {
+        if let Some(font_weight) = self.font_weight {
+            builder.push(&StyleProperty::FontWeight(font_weight), range.clone());
+        }
+        if self.italic {
+            builder.push(&StyleProperty::FontStyle(FontStyle::Italic), range.clone());
+        }
+        if let Some(color) = self.color {
+            builder.push(&StyleProperty::Brush(color.into()), range.clone());
+        }
+        if let Some(font_size) = self.font_size {
+            builder.push(&StyleProperty::FontSize(font_size), range.clone());
+        }
+        if let Some(line_height) = self.line_height {
+            builder.push(&StyleProperty::LineHeight(line_height), range.clone());
+        }
+        if self.underline {
+            builder.push(&StyleProperty::Underline(true), range.clone());
+        }
+        if self.strikethrough {
+            builder.push(&StyleProperty::Strikethrough(true), range);
+        }
+    }
+}
+
+/// Text made of spans which each have their own [`TextSpanStyle`].
+///
+/// This can be built from a list of `(text, style)` pairs:
+///
+/// ```
+/// use masonry::text2::{StyledText, TextSpanStyle};
+///
+/// let text: StyledText = [
+///     ("Error: ", TextSpanStyle::default().bold()),
+///     ("file not found", TextSpanStyle::default().italic()),
+/// ]
+/// .into_iter()
+/// .collect();
+/// assert_eq!(text.text(), "Error: file not found");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StyledText {
+    text: String,
+    /// The byte range of each span within `text`, in order.
+    spans: Vec<(Range<usize>, TextSpanStyle)>,
+}
+
+impl StyledText {
+    /// Create empty text.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a span at the end of the text.
+    pub fn push(&mut self, text: &str, style: TextSpanStyle) {
+        let start = self.text.len();
+        self.text.push_str(text);
+        self.spans.push((start..self.text.len(), style));
+    }
+
+    /// Builder-style method to add a span at the end of the text.
+    pub fn with_span(mut self, text: &str, style: TextSpanStyle) -> Self {
+        self.push(text, style);
+        self
+    }
+
+    /// The text of all spans, without styling.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// The byte range and style of each span.
+    pub fn spans(&self) -> impl Iterator<Item = (Range<usize>, &TextSpanStyle)> {
+        self.spans
+            .iter()
+            .map(|(range, style)| (range.clone(), style))
+    }
+}
+
+impl<S: AsRef<str>> FromIterator<(S, TextSpanStyle)> for StyledText {
+    fn from_iter<I: IntoIterator<Item = (S, TextSpanStyle)>>(iter: I) -> Self {
+        let mut text = Self::new();
+        for (span, style) in iter {
+            text.push(span.as_ref(), style);
+        }
+        text
+    }
+}
+
+impl From<Vec<(String, TextSpanStyle)>> for StyledText {
+    fn from(spans: Vec<(String, TextSpanStyle)>) -> Self {
+        spans.into_iter().collect()
+    }
+}
+
+impl From<&str> for StyledText {
+    fn from(text: &str) -> Self {
+        Self::new().with_span(text, TextSpanStyle::default())
+    }
+}
+
+impl TextStorage for StyledText {
+    fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    fn add_attributes<'b>(
+        &self,
+        mut builder: RangedBuilder<'b, TextBrush, &'b str>,
+    ) -> RangedBuilder<'b, TextBrush, &'b str> {
+        for (range, style) in &self.spans {
+            style.add_attributes(&mut builder, range.clone());
+        }
+        builder
+    }
+
+    fn maybe_eq(&self, other: &Self) -> bool {
+        self == other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spans_follow_each_other() {
+        let bold = TextSpanStyle::default().bold();
+        let text = StyledText::new()
+            .with_span("Hello ", TextSpanStyle::default())
+            .with_span("world", bold.clone());
+        assert_eq!(text.text(), "Hello world");
+        let spans: Vec<_> = text.spans().collect();
+        assert_eq!(spans[0].0, 0..6);
+        assert_eq!(spans[1], (6..11, &bold));
+    }
+}
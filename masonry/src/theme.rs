@@ -27,16 +27,22 @@ pub const DISABLED_FOREGROUND_LIGHT: Color = Color::rgb8(0x89, 0x89, 0x89);
 pub const DISABLED_FOREGROUND_DARK: Color = Color::rgb8(0x6f, 0x6f, 0x6f);
 pub const BUTTON_DARK: Color = Color::BLACK;
 pub const BUTTON_LIGHT: Color = Color::rgb8(0x21, 0x21, 0x21);
+pub const BUTTON_HOVER_DARK: Color = Color::rgb8(0x0c, 0x0c, 0x0c);
+pub const BUTTON_HOVER_LIGHT: Color = Color::rgb8(0x33, 0x33, 0x33);
 pub const DISABLED_BUTTON_DARK: Color = Color::rgb8(0x28, 0x28, 0x28);
 pub const DISABLED_BUTTON_LIGHT: Color = Color::rgb8(0x38, 0x38, 0x38);
 pub const BUTTON_BORDER_RADIUS: f64 = 4.;
 pub const BUTTON_BORDER_WIDTH: f64 = 2.;
 pub const BORDER_DARK: Color = Color::rgb8(0x3a, 0x3a, 0x3a);
 pub const BORDER_LIGHT: Color = Color::rgb8(0xa1, 0xa1, 0xa1);
+pub const BADGE_COLOR: Color = Color::rgb8(0xe6, 0x19, 0x4b);
+pub const RATING_COLOR: Color = Color::rgb8(0xff, 0xc1, 0x07);
+pub const DIVIDER_COLOR: Color = Color::rgba8(0x80, 0x80, 0x80, 0x80);
 pub const SELECTED_TEXT_BACKGROUND_COLOR: Color = Color::rgb8(0x43, 0x70, 0xA8);
 pub const SELECTED_TEXT_INACTIVE_BACKGROUND_COLOR: Color = Color::rgb8(0x74, 0x74, 0x74);
 pub const SELECTION_TEXT_COLOR: Color = Color::rgb8(0x00, 0x00, 0x00);
 pub const CURSOR_COLOR: Color = Color::WHITE;
+pub const LINK_COLOR: Color = Color::rgb8(0x5c, 0xc4, 0xff);
 pub const TEXT_SIZE_NORMAL: f64 = 15.0;
 pub const TEXT_SIZE_LARGE: f64 = 24.0;
 pub const BASIC_WIDGET_HEIGHT: f64 = 18.0;
@@ -54,10 +60,125 @@ pub const SCROLLBAR_PAD: f64 = 2.;
 pub const SCROLLBAR_MIN_SIZE: f64 = 45.;
 pub const SCROLLBAR_RADIUS: f64 = 5.;
 pub const SCROLLBAR_EDGE_WIDTH: f64 = 1.;
+/// The height of a line scrolled by a mouse wheel, unless a scrolling widget overrides it.
+pub const SCROLL_LINE_HEIGHT: f64 = 18.;
 pub const WIDGET_PADDING_VERTICAL: f64 = 10.0;
 pub const WIDGET_PADDING_HORIZONTAL: f64 = 8.0;
 pub const WIDGET_CONTROL_COMPONENT_PADDING: f64 = 4.0;
 
+/// The colors widgets are painted with, by their role rather than by widget.
+///
+/// The constants of this module are the colors of [`Theme::dark`], which is the default.
+/// A theme is set for the whole window with [`RenderRoot::set_theme`], and widgets read it
+/// with [`PaintCtx::theme`]. Colors set on a widget, such as with [`Button::with_color`],
+/// take precedence over the theme.
+///
+/// Labels, text boxes, and the controls of [`widget`](crate::widget) use the theme. The
+/// other widgets, such as [`Prose`], [`ProgressBar`], [`Spinner`] and [`Split`], are
+/// still painted with the constants of this module.
+///
+/// Gradients are given from top to bottom.
+///
+/// [`RenderRoot::set_theme`]: crate::render_root::RenderRoot::set_theme
+/// [`PaintCtx::theme`]: crate::PaintCtx::theme
+/// [`Button::with_color`]: crate::widget::Button::with_color
+/// [`Prose`]: crate::widget::Prose
+/// [`ProgressBar`]: crate::widget::ProgressBar
+/// [`Spinner`]: crate::widget::Spinner
+/// [`Split`]: crate::widget::Split
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Theme {
+    /// The background of the window.
+    pub background: Color,
+    /// The background of buttons and other controls.
+    pub surface: [Color; 2],
+    /// The background of controls under the pointer.
+    pub surface_hover: [Color; 2],
+    /// The background of controls while they're pressed.
+    pub surface_active: [Color; 2],
+    /// The background of disabled controls.
+    pub surface_disabled: [Color; 2],
+    /// The background of fields the user fills in, such as the box of a checkbox.
+    pub field: [Color; 2],
+    /// Text and marks drawn on the background and on surfaces.
+    pub on_surface: Color,
+    /// Text and marks of disabled widgets.
+    pub on_surface_disabled: Color,
+    /// The accent color, for focused and selected widgets.
+    pub primary: Color,
+    /// The background of the highlighted item of lists and menus, under text.
+    pub highlight: Color,
+    /// The border of controls.
+    pub border: Color,
+    /// The border of controls under the pointer.
+    pub border_hover: Color,
+}
+
+impl Theme {
+    /// Light text on dark surfaces, with the colors of this module's constants.
+    pub fn dark() -> Self {
+        Self {
+            background: WINDOW_BACKGROUND_COLOR,
+            surface: [BUTTON_LIGHT, BUTTON_DARK],
+            surface_hover: [BUTTON_HOVER_LIGHT, BUTTON_HOVER_DARK],
+            surface_active: [BUTTON_DARK, BUTTON_LIGHT],
+            surface_disabled: [DISABLED_BUTTON_LIGHT, DISABLED_BUTTON_DARK],
+            field: [BACKGROUND_LIGHT, BACKGROUND_DARK],
+            on_surface: TEXT_COLOR,
+            on_surface_disabled: DISABLED_TEXT_COLOR,
+            primary: PRIMARY_LIGHT,
+            highlight: PRIMARY_DARK,
+            border: BORDER_DARK,
+            border_hover: BORDER_LIGHT,
+        }
+    }
+
+    /// White text and borders on black, with yellow for the accent, for users who set
+    /// their platform to high contrast.
+    ///
+    /// See [`AppOptions::high_contrast_theme`](crate::event_loop_runner::AppOptions::high_contrast_theme).
+    pub fn high_contrast() -> Self {
+        Self {
+            background: Color::BLACK,
+            surface: [Color::BLACK, Color::BLACK],
+            surface_hover: [Color::rgb8(0x1a, 0x1a, 0x1a), Color::rgb8(0x1a, 0x1a, 0x1a)],
+            surface_active: [Color::rgb8(0x33, 0x33, 0x33), Color::rgb8(0x33, 0x33, 0x33)],
+            surface_disabled: [Color::BLACK, Color::BLACK],
+            field: [Color::BLACK, Color::BLACK],
+            on_surface: Color::WHITE,
+            on_surface_disabled: Color::rgb8(0x3f, 0xf2, 0x3f),
+            primary: Color::rgb8(0xff, 0xff, 0x00),
+            highlight: Color::rgb8(0x00, 0x46, 0xad),
+            border: Color::WHITE,
+            border_hover: Color::rgb8(0xff, 0xff, 0x00),
+        }
+    }
+
+    /// Dark text on light surfaces.
+    pub fn light() -> Self {
+        Self {
+            background: Color::rgb8(0xf3, 0xf3, 0xf3),
+            surface: [Color::rgb8(0xff, 0xff, 0xff), Color::rgb8(0xe8, 0xe8, 0xe8)],
+            surface_hover: [Color::rgb8(0xf4, 0xf4, 0xf4), Color::rgb8(0xdc, 0xdc, 0xdc)],
+            surface_active: [Color::rgb8(0xd0, 0xd0, 0xd0), Color::rgb8(0xe4, 0xe4, 0xe4)],
+            surface_disabled: [Color::rgb8(0xec, 0xec, 0xec), Color::rgb8(0xe4, 0xe4, 0xe4)],
+            field: [Color::rgb8(0xf6, 0xf6, 0xf6), Color::rgb8(0xff, 0xff, 0xff)],
+            on_surface: Color::rgb8(0x1e, 0x1e, 0x1e),
+            on_surface_disabled: Color::rgb8(0x8a, 0x8a, 0x8a),
+            primary: PRIMARY_DARK,
+            highlight: Color::rgb8(0xcc, 0xe4, 0xf7),
+            border: Color::rgb8(0xc2, 0xc2, 0xc2),
+            border_hover: Color::rgb8(0x70, 0x70, 0x70),
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
 static DEBUG_COLOR: &[Color] = &[
     Color::rgb8(230, 25, 75),
     Color::rgb8(60, 180, 75),
@@ -7,6 +7,7 @@
 
 use std::any::Any;
 use std::hash::Hash;
+use std::ops::RangeInclusive;
 
 /// Panic in debug and `tracing::error` in release mode.
 ///
@@ -64,6 +65,36 @@ impl From<bool> for Handled {
 
 // ---
 
+/// The bounds of `range` as `(min, max)`, with `min <= max` and both finite.
+///
+/// Widgets picking a number between two bounds call this on the bounds they're given,
+/// since [`f64::clamp`] panics when a bound is NaN. A bound which isn't finite is
+/// replaced by the other one, or both by 0 if neither is finite.
+pub(crate) fn finite_bounds(range: &RangeInclusive<f64>) -> (f64, f64) {
+    let (start, end) = (*range.start(), *range.end());
+    let (min, max) = match (start.is_finite(), end.is_finite()) {
+        (true, true) => (start, end.max(start)),
+        (true, false) => (start, start),
+        (false, true) => (end, end),
+        (false, false) => (0., 0.),
+    };
+    if !start.is_finite() || !end.is_finite() {
+        tracing::warn!("Bounds {range:?} aren't finite, using {min}..={max} instead.");
+    }
+    (min, max)
+}
+
+/// Clamp `value` between bounds returned by [`finite_bounds`], treating NaN as `min`.
+pub(crate) fn clamp(value: f64, min: f64, max: f64) -> f64 {
+    if value.is_nan() {
+        min
+    } else {
+        value.clamp(min, max)
+    }
+}
+
+// ---
+
 /// Trait extending Any, implemented for all types that implement Any.
 ///
 /// This is a band-aid to substitute for a lack of dyn trait upcasting.
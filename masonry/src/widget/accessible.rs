@@ -0,0 +1,240 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! A widget which describes its child to accessibility.
+
+use accesskit::{Live, Role};
+use smallvec::{smallvec, SmallVec};
+use tracing::{trace_span, Span};
+use vello::Scene;
+
+use crate::widget::{WidgetMut, WidgetPod, WidgetRef};
+use crate::{
+    AccessCtx, AccessEvent, ArcStr, BoxConstraints, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx,
+    PaintCtx, Point, PointerEvent, Size, StatusChange, TextEvent, Widget,
+};
+
+/// A container which adds an accessibility node around its child, with a role, label and
+/// description of its choosing.
+///
+/// This is how compositions of widgets which don't describe themselves, such as a row of
+/// icons or a custom drawn chart, can be made understandable to screen readers. The child's
+/// own node, if any, is a child of this one.
+///
+/// It can also be made a live region with [`with_live`](Self::with_live), so that screen
+/// readers announce changes to it, such as a label showing a count changing.
+pub struct Accessible<W: Widget> {
+    child: WidgetPod<W>,
+    role: Role,
+    label: Option<ArcStr>,
+    description: Option<ArcStr>,
+    live: Live,
+}
+
+impl<W: Widget> Accessible<W> {
+    /// Create a new container around `child`, with the [`GenericContainer`](Role::GenericContainer)
+    /// role and no label.
+    pub fn new(child: W) -> Self {
+        Self::from_child_pod(WidgetPod::new(child))
+    }
+
+    /// Create a new container around a child which has already been wrapped in a [`WidgetPod`].
+    pub fn from_child_pod(child: WidgetPod<W>) -> Self {
+        Self {
+            child,
+            role: Role::GenericContainer,
+            label: None,
+            description: None,
+            live: Live::Off,
+        }
+    }
+
+    /// Builder-style method to set the role of the node.
+    pub fn with_role(mut self, role: Role) -> Self {
+        self.role = role;
+        self
+    }
+
+    /// Builder-style method to set the label of the node, which is what screen readers
+    /// announce it as.
+    pub fn with_label(mut self, label: impl Into<ArcStr>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Builder-style method to set the description of the node, which is read after its label.
+    pub fn with_description(mut self, description: impl Into<ArcStr>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Builder-style method to make the node a live region.
+    ///
+    /// Screen readers announce the new label of this node or any of its descendants,
+    /// such as the text of a [`Label`](crate::widget::Label), when it changes. With
+    /// [`Live::Polite`] they wait until they have finished speaking, and with
+    /// [`Live::Assertive`] they interrupt what they are saying.
+    pub fn with_live(mut self, live: Live) -> Self {
+        self.live = live;
+        self
+    }
+
+    /// The label of the node.
+    pub fn label(&self) -> Option<&ArcStr> {
+        self.label.as_ref()
+    }
+
+    /// The description of the node.
+    pub fn description(&self) -> Option<&ArcStr> {
+        self.description.as_ref()
+    }
+
+    /// How changes to the node are announced.
+    pub fn live(&self) -> Live {
+        self.live
+    }
+}
+
+impl<W: Widget> WidgetMut<'_, Accessible<W>> {
+    pub fn child_mut(&mut self) -> WidgetMut<'_, W> {
+        self.ctx.get_mut(&mut self.widget.child)
+    }
+
+    /// Set the role of the node.
+    pub fn set_role(&mut self, role: Role) {
+        self.widget.role = role;
+        self.ctx.request_accessibility_update();
+    }
+
+    /// Set the label of the node, or remove it.
+    pub fn set_label(&mut self, label: Option<ArcStr>) {
+        self.widget.label = label;
+        self.ctx.request_accessibility_update();
+    }
+
+    /// Set the description of the node, or remove it.
+    pub fn set_description(&mut self, description: Option<ArcStr>) {
+        self.widget.description = description;
+        self.ctx.request_accessibility_update();
+    }
+
+    /// Set how changes to the node are announced, or [`Live::Off`] to not announce them.
+    pub fn set_live(&mut self, live: Live) {
+        self.widget.live = live;
+        self.ctx.request_accessibility_update();
+    }
+}
+
+impl<W: Widget> Widget for Accessible<W> {
+    fn on_pointer_event(&mut self, ctx: &mut EventCtx, event: &PointerEvent) {
+        self.child.on_pointer_event(ctx, event);
+    }
+
+    fn on_text_event(&mut self, ctx: &mut EventCtx, event: &TextEvent) {
+        self.child.on_text_event(ctx, event);
+    }
+
+    fn on_access_event(&mut self, ctx: &mut EventCtx, event: &AccessEvent) {
+        self.child.on_access_event(ctx, event);
+    }
+
+    fn on_status_change(&mut self, _ctx: &mut LifeCycleCtx, _event: &StatusChange) {}
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle) {
+        self.child.lifecycle(ctx, event);
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints) -> Size {
+        let size = self.child.layout(ctx, bc);
+        ctx.place_child(&mut self.child, Point::ORIGIN);
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, scene: &mut Scene) {
+        self.child.paint(ctx, scene);
+    }
+
+    fn accessibility_role(&self) -> Role {
+        self.role
+    }
+
+    fn accessibility(&mut self, ctx: &mut AccessCtx) {
+        let node = ctx.current_node();
+        if let Some(label) = &self.label {
+            node.set_name(label.to_string());
+        }
+        if let Some(description) = &self.description {
+            node.set_description(description.to_string());
+        }
+        if self.live != Live::Off {
+            node.set_live(self.live);
+        }
+        self.child.accessibility(ctx);
+    }
+
+    fn children(&self) -> SmallVec<[WidgetRef<'_, dyn Widget>; 16]> {
+        smallvec![self.child.as_dyn()]
+    }
+
+    fn make_trace_span(&self) -> Span {
+        trace_span!("Accessible")
+    }
+
+    fn get_debug_text(&self) -> Option<String> {
+        self.label.as_ref().map(|label| label.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{widget_ids, TestHarness};
+    use crate::widget::{Flex, Label, SizedBox};
+
+    #[test]
+    fn role_label_and_live() {
+        let [accessible_id] = widget_ids();
+        let widget = Flex::column().with_child_id(
+            Accessible::new(SizedBox::new(Label::new("42")))
+                .with_role(Role::Meter)
+                .with_label("Temperature")
+                .with_live(Live::Polite),
+            accessible_id,
+        );
+
+        let mut harness = TestHarness::create(widget);
+        let accessible = harness.get_widget(accessible_id);
+        assert_eq!(accessible.accessibility_role(), Role::Meter);
+        assert_eq!(
+            accessible
+                .downcast::<Accessible<SizedBox>>()
+                .unwrap()
+                .label(),
+            Some(&ArcStr::from("Temperature"))
+        );
+        assert_eq!(
+            accessible
+                .downcast::<Accessible<SizedBox>>()
+                .unwrap()
+                .live(),
+            Live::Polite
+        );
+
+        harness.edit_root_widget(|mut root| {
+            let mut flex = root.downcast::<Flex>();
+            let mut accessible = flex.child_mut(0).unwrap();
+            let mut accessible = accessible.downcast::<Accessible<SizedBox>>();
+            accessible.set_role(Role::Group);
+            accessible.set_label(None);
+        });
+        let accessible = harness.get_widget(accessible_id);
+        assert_eq!(accessible.accessibility_role(), Role::Group);
+        assert_eq!(
+            accessible
+                .downcast::<Accessible<SizedBox>>()
+                .unwrap()
+                .label(),
+            None
+        );
+    }
+}
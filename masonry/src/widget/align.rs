@@ -15,7 +15,7 @@ use vello::Scene;
 
 use crate::contexts::AccessCtx;
 use crate::paint_scene_helpers::UnitPoint;
-use crate::widget::{WidgetPod, WidgetRef};
+use crate::widget::{Axis, WidgetPod, WidgetRef};
 use crate::{
     AccessEvent, BoxConstraints, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx,
     PointerEvent, Rect, Size, StatusChange, TextEvent, Widget,
@@ -148,6 +148,15 @@ impl Widget for Align {
         my_size
     }
 
+    fn intrinsic_size(&mut self, ctx: &mut LayoutCtx, axis: Axis, cross_size: f64) -> f64 {
+        let size = self.child.intrinsic_size(ctx, axis, cross_size);
+        let factor = match axis {
+            Axis::Horizontal => self.width_factor,
+            Axis::Vertical => self.height_factor,
+        };
+        size * factor.unwrap_or(1.0)
+    }
+
     fn paint(&mut self, ctx: &mut PaintCtx, scene: &mut Scene) {
         self.child.paint(ctx, scene);
     }
@@ -0,0 +1,313 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! A widget which shows a small badge on the corner of its child.
+
+use accesskit::Role;
+use kurbo::{Circle, Point, Rect, Size, Vec2};
+use smallvec::{smallvec, SmallVec};
+use tracing::{trace, trace_span, Span};
+use vello::Scene;
+
+use crate::paint_scene_helpers::fill_color;
+use crate::widget::{Label, WidgetMut, WidgetPod, WidgetRef};
+use crate::{
+    theme, AccessCtx, AccessEvent, ArcStr, BoxConstraints, Color, EventCtx, LayoutCtx, LifeCycle,
+    LifeCycleCtx, PaintCtx, PointerEvent, StatusChange, TextEvent, Widget,
+};
+
+/// The largest count shown by [`BadgeContent::Count`]; larger counts are shown as `99+`.
+const MAX_COUNT: u32 = 99;
+
+/// What is shown in a [`Badge`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BadgeContent {
+    /// A plain dot, such as to mark something as unread.
+    Dot,
+    /// A number, such as the number of notifications. Counts over 99 are shown as `99+`.
+    Count(u32),
+    /// A short text, such as "new".
+    Text(ArcStr),
+}
+
+impl BadgeContent {
+    /// The text shown in the badge, if it isn't a dot.
+    pub fn text(&self) -> Option<ArcStr> {
+        match self {
+            BadgeContent::Dot => None,
+            BadgeContent::Count(count) if *count > MAX_COUNT => {
+                Some(format!("{MAX_COUNT}+").into())
+            }
+            BadgeContent::Count(count) => Some(count.to_string().into()),
+            BadgeContent::Text(text) => Some(text.clone()),
+        }
+    }
+}
+
+/// The appearance of a [`Badge`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct BadgeStyle {
+    /// The background color of the badge.
+    pub color: Color,
+    /// The color of the count or text in the badge.
+    pub text_color: Color,
+    /// The height of a badge with a count or text. A dot is half as large.
+    pub size: f64,
+}
+
+impl Default for BadgeStyle {
+    fn default() -> Self {
+        Self {
+            color: theme::BADGE_COLOR,
+            text_color: theme::TEXT_COLOR,
+            size: 18.,
+        }
+    }
+}
+
+impl BadgeStyle {
+    /// Builder-style method to set the background color of the badge.
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Builder-style method to set the color of the count or text in the badge.
+    pub fn text_color(mut self, text_color: Color) -> Self {
+        self.text_color = text_color;
+        self
+    }
+
+    /// Builder-style method to set the height of the badge.
+    pub fn size(mut self, size: f64) -> Self {
+        self.size = size.max(0.0);
+        self
+    }
+
+    fn text_size(&self) -> f32 {
+        (self.size * 0.65) as f32
+    }
+}
+
+/// A container which shows a small badge, such as a notification count, centered on the
+/// top right corner of its child.
+///
+/// The badge is painted as a passive overlay, so it isn't clipped by this widget's
+/// ancestors, doesn't take any space in the layout, and doesn't intercept clicks.
+///
+/// The text of the badge is reported to accessibility as this widget's description.
+pub struct Badge<W: Widget> {
+    child: WidgetPod<W>,
+    label: WidgetPod<Label>,
+    content: BadgeContent,
+    style: BadgeStyle,
+    /// Where the badge is drawn, in local coordinates.
+    badge_rect: Rect,
+}
+
+impl<W: Widget> Badge<W> {
+    /// Create a new badge showing `content` on `child`.
+    pub fn new(child: W, content: BadgeContent) -> Self {
+        Self::from_child_pod(WidgetPod::new(child), content)
+    }
+
+    /// Create a new badge on a child which has already been wrapped in a [`WidgetPod`].
+    pub fn from_child_pod(child: WidgetPod<W>, content: BadgeContent) -> Self {
+        let style = BadgeStyle::default();
+        Self {
+            child,
+            label: WidgetPod::new(badge_label(&content, &style)),
+            content,
+            style,
+            badge_rect: Rect::ZERO,
+        }
+    }
+
+    /// Builder-style method to set the appearance of the badge.
+    pub fn with_style(mut self, style: BadgeStyle) -> Self {
+        self.label = WidgetPod::new(badge_label(&self.content, &style));
+        self.style = style;
+        self
+    }
+
+    /// What is shown in the badge.
+    pub fn content(&self) -> &BadgeContent {
+        &self.content
+    }
+
+    /// Where the badge is drawn, in this widget's coordinate space.
+    pub fn badge_rect(&self) -> Rect {
+        self.badge_rect
+    }
+}
+
+fn badge_label(content: &BadgeContent, style: &BadgeStyle) -> Label {
+    Label::new(content.text().unwrap_or_else(|| "".into()))
+        .with_text_brush(style.text_color)
+        .with_text_size(style.text_size())
+}
+
+impl<W: Widget> WidgetMut<'_, Badge<W>> {
+    pub fn child_mut(&mut self) -> WidgetMut<'_, W> {
+        self.ctx.get_mut(&mut self.widget.child)
+    }
+
+    /// Set what is shown in the badge.
+    pub fn set_content(&mut self, content: BadgeContent) {
+        let text = content.text();
+        let is_dot = text.is_none();
+        self.ctx
+            .get_mut(&mut self.widget.label)
+            .set_text(text.unwrap_or_else(|| "".into()));
+        self.ctx.set_stashed(&mut self.widget.label, is_dot);
+        self.widget.content = content;
+        self.ctx.request_layout();
+        self.ctx.request_accessibility_update();
+    }
+
+    /// Set the appearance of the badge.
+    pub fn set_style(&mut self, style: BadgeStyle) {
+        {
+            let mut label = self.ctx.get_mut(&mut self.widget.label);
+            label.set_text_brush(style.text_color);
+            label.set_text_size(style.text_size());
+        }
+        self.widget.style = style;
+        self.ctx.request_layout();
+    }
+}
+
+impl<W: Widget> Widget for Badge<W> {
+    fn on_pointer_event(&mut self, ctx: &mut EventCtx, event: &PointerEvent) {
+        self.child.on_pointer_event(ctx, event);
+    }
+
+    fn on_text_event(&mut self, ctx: &mut EventCtx, event: &TextEvent) {
+        self.child.on_text_event(ctx, event);
+    }
+
+    fn on_access_event(&mut self, ctx: &mut EventCtx, event: &AccessEvent) {
+        self.child.on_access_event(ctx, event);
+    }
+
+    fn on_status_change(&mut self, _ctx: &mut LifeCycleCtx, _event: &StatusChange) {}
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle) {
+        self.child.lifecycle(ctx, event);
+        self.label.lifecycle(ctx, event);
+        if let LifeCycle::WidgetAdded = event {
+            // The label is only laid out and painted when the badge isn't a dot.
+            ctx.set_stashed(&mut self.label, self.content == BadgeContent::Dot);
+        }
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints) -> Size {
+        let size = self.child.layout(ctx, bc);
+        ctx.place_child(&mut self.child, Point::ORIGIN);
+
+        let corner = Point::new(size.width, 0.);
+        let height = self.style.size;
+        self.badge_rect = if self.content == BadgeContent::Dot {
+            Rect::from_center_size(corner, Size::new(height / 2., height / 2.))
+        } else {
+            let label_size = self.label.layout(ctx, &BoxConstraints::UNBOUNDED);
+            // Short texts are in a circle, and longer ones in a pill.
+            let width = (label_size.width + height / 3.).max(height);
+            let badge_rect = Rect::from_center_size(corner, Size::new(width, height));
+            let label_origin =
+                badge_rect.center() - Vec2::new(label_size.width, label_size.height) / 2.;
+            ctx.place_child(&mut self.label, label_origin);
+            badge_rect
+        };
+        trace!("Badge {:?} placed at {}", ctx.widget_id(), self.badge_rect);
+        ctx.set_passive_overlay_rect(self.badge_rect);
+
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, scene: &mut Scene) {
+        self.child.paint(ctx, scene);
+
+        let mut overlay = Scene::new();
+        if self.content == BadgeContent::Dot {
+            let circle = Circle::new(self.badge_rect.center(), self.badge_rect.height() / 2.);
+            fill_color(&mut overlay, &circle, self.style.color);
+        } else {
+            let pill = self
+                .badge_rect
+                .to_rounded_rect(self.badge_rect.height() / 2.);
+            fill_color(&mut overlay, &pill, self.style.color);
+            self.label.paint(ctx, &mut overlay);
+        }
+        ctx.paint_overlay(overlay);
+    }
+
+    fn accessibility_role(&self) -> Role {
+        Role::GenericContainer
+    }
+
+    fn accessibility(&mut self, ctx: &mut AccessCtx) {
+        if let Some(text) = self.content.text() {
+            ctx.current_node().set_description(text.to_string());
+        }
+        self.child.accessibility(ctx);
+        self.label.accessibility(ctx);
+    }
+
+    fn children(&self) -> SmallVec<[WidgetRef<'_, dyn Widget>; 16]> {
+        smallvec![self.child.as_dyn(), self.label.as_dyn()]
+    }
+
+    fn make_trace_span(&self) -> Span {
+        trace_span!("Badge")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{widget_ids, TestHarness};
+    use crate::widget::{Flex, SizedBox};
+
+    fn badge_rect(harness: &TestHarness, id: crate::WidgetId) -> Rect {
+        harness
+            .get_widget(id)
+            .downcast::<Badge<SizedBox>>()
+            .unwrap()
+            .deref()
+            .badge_rect()
+    }
+
+    #[test]
+    fn badge_on_top_right_corner() {
+        let [dot_id, count_id] = widget_ids();
+        let child = || SizedBox::empty().width(40.).height(30.);
+        let widget = Flex::column()
+            .with_child_id(Badge::new(child(), BadgeContent::Dot), dot_id)
+            .with_child_id(Badge::new(child(), BadgeContent::Count(3)), count_id);
+
+        let mut harness = TestHarness::create(widget);
+
+        // The badge doesn't change the size of its child.
+        let size = harness.get_widget(dot_id).state().layout_rect().size();
+        assert_eq!(size, Size::new(40., 30.));
+        assert_eq!(badge_rect(&harness, dot_id).center(), Point::new(40., 0.));
+        assert_eq!(badge_rect(&harness, dot_id).height(), 9.);
+
+        let count_rect = badge_rect(&harness, count_id);
+        assert_eq!(count_rect.center(), Point::new(40., 0.));
+        assert_eq!(count_rect.size(), Size::new(18., 18.));
+
+        // Large counts are capped, and make the badge wider.
+        harness.edit_root_widget(|mut root| {
+            let mut flex = root.downcast::<Flex>();
+            let mut badge = flex.child_mut(1).unwrap();
+            let mut badge = badge.downcast::<Badge<SizedBox>>();
+            badge.set_content(BadgeContent::Count(250));
+        });
+        assert_eq!(BadgeContent::Count(250).text().as_deref(), Some("99+"));
+        let count_rect = badge_rect(&harness, count_id);
+        assert_eq!(count_rect.center(), Point::new(40., 0.));
+        assert!(count_rect.width() > 18.);
+    }
+}
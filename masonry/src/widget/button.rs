@@ -9,24 +9,42 @@ use tracing::{trace, trace_span, Span};
 use vello::Scene;
 
 use crate::action::Action;
-use crate::paint_scene_helpers::{fill_lin_gradient, stroke, UnitPoint};
-use crate::text2::TextStorage;
-use crate::widget::{Label, WidgetMut, WidgetPod, WidgetRef};
+use crate::kurbo::{RoundedRectRadii, Shape};
+use crate::paint_scene_helpers::{fill_color, fill_lin_gradient, stroke, UnitPoint};
+use crate::widget::{Axis, Label, WidgetMut, WidgetPod, WidgetRef};
 use crate::{
-    theme, AccessCtx, AccessEvent, ArcStr, BoxConstraints, EventCtx, Insets, LayoutCtx, LifeCycle,
-    LifeCycleCtx, PaintCtx, PointerEvent, Size, StatusChange, TextEvent, Widget,
+    theme, AccessCtx, AccessEvent, ArcStr, BoxConstraints, BoxShadow, Color, CursorIcon, EventCtx,
+    Insets, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx, Point, PointerEvent, Size, StatusChange,
+    TextEvent, Widget,
 };
 
 // the minimum padding added to a button.
 // NOTE: these values are chosen to match the existing look of TextBox; these
 // should be reevaluated at some point.
-const LABEL_INSETS: Insets = Insets::uniform_xy(8., 2.);
+const CONTENT_INSETS: Insets = Insets::uniform_xy(8., 2.);
 
-/// A button with a text label.
+/// A button with a text label, or any other content such as an icon.
 ///
 /// Emits [`Action::ButtonPressed`] when pressed.
-pub struct Button {
-    label: WidgetPod<Label>,
+///
+/// The action is only emitted if the pointer is released over the button,
+/// so a press can be cancelled by dragging the pointer away.
+///
+/// The button's background changes when it is hovered and while it is pressed.
+/// By default these backgrounds are gradients from the [theme](crate::theme::Theme);
+/// each of them can be replaced with a solid color.
+///
+/// The whole button reacts to the pointer, not only its content, which is centered
+/// in the button with some padding. Clicks outside its rounded corners are ignored,
+/// so a button with a large [corner radius](Self::with_corner_radius) is round for
+/// the pointer too.
+pub struct Button<W: Widget = Label> {
+    child: WidgetPod<W>,
+    color: Option<Color>,
+    hover_color: Option<Color>,
+    active_color: Option<Color>,
+    corner_radius: RoundedRectRadii,
+    shadows: Vec<BoxShadow>,
 }
 
 impl Button {
@@ -55,24 +73,113 @@ impl Button {
     /// let button = Button::from_label(label);
     /// ```
     pub fn from_label(label: Label) -> Button {
+        Button::from_child(label)
+    }
+}
+
+impl<W: Widget> Button<W> {
+    /// Create a new button showing `child`, such as an [`Icon`](crate::widget::Icon) or a
+    /// [`Flex`](crate::widget::Flex) row with an icon and a label.
+    pub fn from_child(child: W) -> Self {
+        Self::from_child_pod(WidgetPod::new(child))
+    }
+
+    /// Create a new button showing a child which has already been wrapped in a [`WidgetPod`].
+    pub fn from_child_pod(child: WidgetPod<W>) -> Self {
         Button {
-            label: WidgetPod::new(label),
+            child,
+            color: None,
+            hover_color: None,
+            active_color: None,
+            corner_radius: RoundedRectRadii::from_single_radius(theme::BUTTON_BORDER_RADIUS),
+            shadows: Vec::new(),
         }
     }
+
+    /// Builder-style method for setting the background color.
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Builder-style method for setting the background color while the pointer is over the button.
+    pub fn with_hover_color(mut self, color: Color) -> Self {
+        self.hover_color = Some(color);
+        self
+    }
+
+    /// Builder-style method for setting the background color while the button is pressed.
+    pub fn with_active_color(mut self, color: Color) -> Self {
+        self.active_color = Some(color);
+        self
+    }
+
+    /// Builder-style method for setting the radius of the button's corners.
+    ///
+    /// This can be a single radius for all corners, or a different radius for each corner.
+    pub fn with_corner_radius(mut self, radius: impl Into<RoundedRectRadii>) -> Self {
+        self.corner_radius = radius.into();
+        self
+    }
+
+    /// Builder-style method for adding a shadow behind the button.
+    ///
+    /// This can be called several times to stack shadows.
+    pub fn with_shadow(mut self, shadow: BoxShadow) -> Self {
+        self.shadows.push(shadow);
+        self
+    }
 }
 
 impl WidgetMut<'_, Button> {
     /// Set the text.
     pub fn set_text(&mut self, new_text: impl Into<ArcStr>) {
-        self.label_mut().set_text(new_text);
+        self.child_mut().set_text(new_text);
     }
 
     pub fn label_mut(&mut self) -> WidgetMut<'_, Label> {
-        self.ctx.get_mut(&mut self.widget.label)
+        self.child_mut()
     }
 }
 
-impl Widget for Button {
+impl<W: Widget> WidgetMut<'_, Button<W>> {
+    pub fn child_mut(&mut self) -> WidgetMut<'_, W> {
+        self.ctx.get_mut(&mut self.widget.child)
+    }
+
+    /// Set the background color, or `None` to use the theme's.
+    pub fn set_color(&mut self, color: Option<Color>) {
+        self.widget.color = color;
+        self.ctx.request_paint();
+    }
+
+    /// Set the background color while the pointer is over the button, or `None` to use the theme's.
+    pub fn set_hover_color(&mut self, color: Option<Color>) {
+        self.widget.hover_color = color;
+        self.ctx.request_paint();
+    }
+
+    /// Set the background color while the button is pressed, or `None` to use the theme's.
+    pub fn set_active_color(&mut self, color: Option<Color>) {
+        self.widget.active_color = color;
+        self.ctx.request_paint();
+    }
+
+    /// Set the radius of the button's corners.
+    pub fn set_corner_radius(&mut self, radius: impl Into<RoundedRectRadii>) {
+        self.widget.corner_radius = radius.into();
+        self.ctx.request_paint();
+    }
+
+    /// Set the shadows drawn behind the button, from bottom to top.
+    pub fn set_shadows(&mut self, shadows: Vec<BoxShadow>) {
+        self.widget.shadows = shadows;
+        // The shadows change the paint insets.
+        self.ctx.request_layout();
+    }
+}
+
+impl<W: Widget> Widget for Button<W> {
     fn on_pointer_event(&mut self, ctx: &mut EventCtx, event: &PointerEvent) {
         match event {
             PointerEvent::PointerDown(_, _) => {
@@ -97,15 +204,15 @@ impl Widget for Button {
             }
             _ => (),
         }
-        self.label.on_pointer_event(ctx, event);
+        self.child.on_pointer_event(ctx, event);
     }
 
     fn on_text_event(&mut self, ctx: &mut EventCtx, event: &TextEvent) {
-        self.label.on_text_event(ctx, event);
+        self.child.on_text_event(ctx, event);
     }
 
     fn on_access_event(&mut self, ctx: &mut EventCtx, event: &AccessEvent) {
-        if event.target == ctx.widget_id() {
+        if event.target == ctx.widget_id() && !ctx.is_disabled() {
             match event.action {
                 accesskit::Action::Default => {
                     ctx.submit_action(Action::ButtonPressed);
@@ -114,7 +221,7 @@ impl Widget for Button {
                 _ => {}
             }
         }
-        self.label.on_access_event(ctx, event);
+        self.child.on_access_event(ctx, event);
     }
 
     fn on_status_change(&mut self, ctx: &mut LifeCycleCtx, _event: &StatusChange) {
@@ -122,69 +229,112 @@ impl Widget for Button {
     }
 
     fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle) {
-        self.label.lifecycle(ctx, event);
+        match event {
+            LifeCycle::WidgetAdded if !ctx.is_disabled() => ctx.set_cursor(&CursorIcon::Pointer),
+            LifeCycle::DisabledChanged(disabled) => {
+                // Disabled buttons can't be clicked, so they keep the default cursor.
+                if *disabled {
+                    ctx.clear_cursor();
+                } else {
+                    ctx.set_cursor(&CursorIcon::Pointer);
+                }
+                ctx.request_paint();
+            }
+            _ => (),
+        }
+        self.child.lifecycle(ctx, event);
     }
 
     fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints) -> Size {
-        let baseline = self.label.baseline_offset();
-        ctx.set_baseline_offset(baseline + LABEL_INSETS.y1);
+        let baseline = self.child.baseline_offset();
+        ctx.set_baseline_offset(baseline + CONTENT_INSETS.y1);
 
-        let padding = Size::new(LABEL_INSETS.x_value(), LABEL_INSETS.y_value());
-        let label_bc = bc.shrink(padding).loosen();
+        let padding = Size::new(CONTENT_INSETS.x_value(), CONTENT_INSETS.y_value());
+        let child_bc = bc.shrink(padding).loosen();
 
-        let label_size = self.label.layout(ctx, &label_bc);
+        let child_size = self.child.layout(ctx, &child_bc);
 
         // HACK: to make sure we look okay at default sizes when beside a textbox,
         // we make sure we will have at least the same height as the default textbox.
         let min_height = theme::BORDERED_WIDGET_HEIGHT;
 
         let button_size = bc.constrain(Size::new(
-            label_size.width + padding.width,
-            (label_size.height + padding.height).max(min_height),
+            child_size.width + padding.width,
+            (child_size.height + padding.height).max(min_height),
         ));
 
-        let label_offset = (button_size.to_vec2() - label_size.to_vec2()) / 2.0;
-        ctx.place_child(&mut self.label, label_offset.to_point());
+        let child_offset = (button_size.to_vec2() - child_size.to_vec2()) / 2.0;
+        ctx.place_child(&mut self.child, child_offset.to_point());
+        ctx.set_paint_insets(BoxShadow::union_paint_insets(&self.shadows));
 
         trace!("Computed button size: {}", button_size);
         button_size
     }
 
+    fn intrinsic_size(&mut self, ctx: &mut LayoutCtx, axis: Axis, cross_size: f64) -> f64 {
+        let padding = Size::new(CONTENT_INSETS.x_value(), CONTENT_INSETS.y_value());
+        let child_cross_size = (cross_size - axis.minor(padding)).max(0.0);
+        let size = self.child.intrinsic_size(ctx, axis, child_cross_size) + axis.major(padding);
+        match axis {
+            Axis::Horizontal => size,
+            // See the minimum height in `layout`.
+            Axis::Vertical => size.max(theme::BORDERED_WIDGET_HEIGHT),
+        }
+    }
+
     fn paint(&mut self, ctx: &mut PaintCtx, scene: &mut Scene) {
-        let is_active = ctx.is_active() && !ctx.is_disabled();
         let is_hot = ctx.is_hot();
+        // A press which was dragged off the button won't trigger it, so it isn't shown as pressed.
+        let is_active = ctx.is_active() && is_hot && !ctx.is_disabled();
         let size = ctx.size();
         let stroke_width = theme::BUTTON_BORDER_WIDTH;
 
         let rounded_rect = size
             .to_rect()
             .inset(-stroke_width / 2.0)
-            .to_rounded_rect(theme::BUTTON_BORDER_RADIUS);
+            .to_rounded_rect(self.corner_radius);
+
+        for shadow in &self.shadows {
+            shadow.paint(scene, size.to_rect(), self.corner_radius);
+        }
 
-        let bg_gradient = if ctx.is_disabled() {
-            [theme::DISABLED_BUTTON_LIGHT, theme::DISABLED_BUTTON_DARK]
+        let theme = ctx.theme();
+        let (bg_color, bg_gradient) = if ctx.is_disabled() {
+            (None, theme.surface_disabled)
         } else if is_active {
-            [theme::BUTTON_DARK, theme::BUTTON_LIGHT]
+            (self.active_color, theme.surface_active)
+        } else if is_hot {
+            (self.hover_color, theme.surface_hover)
         } else {
-            [theme::BUTTON_LIGHT, theme::BUTTON_DARK]
+            (self.color, theme.surface)
         };
 
         let border_color = if is_hot && !ctx.is_disabled() {
-            theme::BORDER_LIGHT
+            theme.border_hover
         } else {
-            theme::BORDER_DARK
+            theme.border
         };
 
         stroke(scene, &rounded_rect, border_color, stroke_width);
-        fill_lin_gradient(
-            scene,
-            &rounded_rect,
-            bg_gradient,
-            UnitPoint::TOP,
-            UnitPoint::BOTTOM,
-        );
+        if let Some(bg_color) = bg_color {
+            fill_color(scene, &rounded_rect, bg_color);
+        } else {
+            fill_lin_gradient(
+                scene,
+                &rounded_rect,
+                bg_gradient,
+                UnitPoint::TOP,
+                UnitPoint::BOTTOM,
+            );
+        }
+
+        self.child.paint(ctx, scene);
+    }
 
-        self.label.paint(ctx, scene);
+    fn hit_test(&self, pos: Point, size: Size) -> bool {
+        size.to_rect()
+            .to_rounded_rect(self.corner_radius)
+            .contains(pos)
     }
 
     fn accessibility_role(&self) -> Role {
@@ -192,17 +342,16 @@ impl Widget for Button {
     }
 
     fn accessibility(&mut self, ctx: &mut AccessCtx) {
-        let _name = self.label.widget().text().as_str().to_string();
         // We may want to add a name if it doesn't interfere with the child label
         // ctx.current_node().set_name(name);
         ctx.current_node()
             .set_default_action_verb(DefaultActionVerb::Click);
 
-        self.label.accessibility(ctx);
+        self.child.accessibility(ctx);
     }
 
     fn children(&self) -> SmallVec<[WidgetRef<'_, dyn Widget>; 16]> {
-        smallvec![self.label.as_dyn()]
+        smallvec![self.child.as_dyn()]
     }
 
     fn make_trace_span(&self) -> Span {
@@ -210,18 +359,20 @@ impl Widget for Button {
     }
 
     fn get_debug_text(&self) -> Option<String> {
-        Some(self.label.as_ref().text().as_str().to_string())
+        self.child.widget().get_debug_text()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use insta::assert_debug_snapshot;
+    use winit::event::MouseButton;
 
     use super::*;
     use crate::assert_render_snapshot;
     use crate::testing::{widget_ids, TestHarness, TestWidgetExt};
     use crate::theme::PRIMARY_LIGHT;
+    use crate::widget::{Flex, SizedBox};
 
     #[test]
     fn simple_button() {
@@ -242,6 +393,98 @@ mod tests {
         );
     }
 
+    #[test]
+    fn press_dragged_off_is_cancelled() {
+        let [button_id, other_id] = widget_ids();
+        let widget = Flex::column()
+            .with_child_id(Button::new("Hello"), button_id)
+            .with_child_id(Button::new("Other"), other_id);
+
+        let mut harness = TestHarness::create(widget);
+
+        harness.mouse_move_to(button_id);
+        harness.mouse_button_press(MouseButton::Left);
+        harness.mouse_move_to(other_id);
+        harness.mouse_button_release(MouseButton::Left);
+        assert_eq!(harness.pop_action(), None);
+
+        // Dragging back onto the button before releasing still presses it.
+        harness.mouse_move_to(button_id);
+        harness.mouse_button_press(MouseButton::Left);
+        harness.mouse_move_to(other_id);
+        harness.mouse_move_to(button_id);
+        harness.mouse_button_release(MouseButton::Left);
+        assert_eq!(
+            harness.pop_action(),
+            Some((Action::ButtonPressed, button_id))
+        );
+    }
+
+    #[test]
+    fn click_on_padding_presses_button() {
+        let [button_id] = widget_ids();
+        let button = Button::from_child(SizedBox::empty().width(10.0).height(10.0));
+        let widget = Flex::column().with_child_id(button, button_id);
+
+        let mut harness = TestHarness::create(widget);
+        let rect = harness.get_widget(button_id).state().window_layout_rect();
+        harness.mouse_move((rect.x0 + 2.0, rect.y0 + 2.0));
+        harness.mouse_button_press(MouseButton::Left);
+        harness.mouse_button_release(MouseButton::Left);
+        assert_eq!(
+            harness.pop_action(),
+            Some((Action::ButtonPressed, button_id))
+        );
+    }
+
+    #[test]
+    fn click_outside_rounded_corner_is_ignored() {
+        let [button_id] = widget_ids();
+        let button =
+            Button::from_child(SizedBox::empty().width(40.0).height(40.0)).with_corner_radius(30.0);
+        let widget = Flex::column().with_child_id(button, button_id);
+
+        let mut harness = TestHarness::create(widget);
+        let rect = harness.get_widget(button_id).state().window_layout_rect();
+        harness.mouse_move((rect.x0 + 2.0, rect.y0 + 2.0));
+        harness.mouse_button_press(MouseButton::Left);
+        harness.mouse_button_release(MouseButton::Left);
+        assert!(!harness.get_widget(button_id).state().is_hot);
+        assert_eq!(harness.pop_action(), None);
+
+        harness.mouse_move(rect.center());
+        harness.mouse_button_press(MouseButton::Left);
+        harness.mouse_button_release(MouseButton::Left);
+        assert_eq!(
+            harness.pop_action(),
+            Some((Action::ButtonPressed, button_id))
+        );
+    }
+
+    #[test]
+    fn disabled_button_ignores_clicks() {
+        let [button_id] = widget_ids();
+        let button = WidgetPod::new_with_id(Button::new("Hello"), button_id).with_disabled(true);
+        let widget = Flex::column().with_child_pod(button.boxed());
+
+        let mut harness = TestHarness::create(widget);
+        assert!(harness.get_widget(button_id).state().is_disabled());
+
+        harness.mouse_click_on(button_id);
+        assert_eq!(harness.pop_action(), None);
+
+        harness.edit_root_widget(|mut root| {
+            let mut flex = root.downcast::<Flex>();
+            let mut button = flex.child_mut(0).unwrap();
+            button.ctx.set_disabled(false);
+        });
+        harness.mouse_click_on(button_id);
+        assert_eq!(
+            harness.pop_action(),
+            Some((Action::ButtonPressed, button_id))
+        );
+    }
+
     #[test]
     fn edit_button() {
         let image_1 = {
@@ -15,8 +15,8 @@ use crate::paint_scene_helpers::{fill_lin_gradient, stroke, UnitPoint};
 use crate::text2::TextStorage;
 use crate::widget::{Label, WidgetMut, WidgetRef};
 use crate::{
-    theme, AccessCtx, AccessEvent, ArcStr, BoxConstraints, EventCtx, LayoutCtx, LifeCycle,
-    LifeCycleCtx, PaintCtx, PointerEvent, StatusChange, TextEvent, Widget, WidgetPod,
+    theme, AccessCtx, AccessEvent, ArcStr, BoxConstraints, CursorIcon, EventCtx, LayoutCtx,
+    LifeCycle, LifeCycleCtx, PaintCtx, PointerEvent, StatusChange, TextEvent, Widget, WidgetPod,
 };
 
 /// A checkbox that can be toggled.
@@ -110,6 +110,13 @@ impl Widget for Checkbox {
     }
 
     fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle) {
+        match event {
+            LifeCycle::WidgetAdded if !ctx.is_disabled() => ctx.set_cursor(&CursorIcon::Pointer),
+            // Disabled checkboxes can't be toggled, so they keep the default cursor.
+            LifeCycle::DisabledChanged(true) => ctx.clear_cursor(),
+            LifeCycle::DisabledChanged(false) => ctx.set_cursor(&CursorIcon::Pointer),
+            _ => (),
+        }
         self.label.lifecycle(ctx, event);
     }
 
@@ -140,18 +147,13 @@ impl Widget for Checkbox {
             .inset(-border_width / 2.)
             .to_rounded_rect(2.);
 
-        fill_lin_gradient(
-            scene,
-            &rect,
-            [theme::BACKGROUND_LIGHT, theme::BACKGROUND_DARK],
-            UnitPoint::TOP,
-            UnitPoint::BOTTOM,
-        );
+        let theme = ctx.theme();
+        fill_lin_gradient(scene, &rect, theme.field, UnitPoint::TOP, UnitPoint::BOTTOM);
 
         let border_color = if ctx.is_hot() && !ctx.is_disabled() {
-            theme::BORDER_LIGHT
+            theme.border_hover
         } else {
-            theme::BORDER_DARK
+            theme.border
         };
 
         stroke(scene, &rect, border_color, border_width);
@@ -174,9 +176,9 @@ impl Widget for Checkbox {
             };
 
             let brush = if ctx.is_disabled() {
-                theme::DISABLED_TEXT_COLOR
+                theme.on_surface_disabled
             } else {
-                theme::TEXT_COLOR
+                theme.on_surface
             };
 
             scene.stroke(&style, Affine::IDENTITY, brush, None, &path);
@@ -0,0 +1,746 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! A widget for picking a color, and a swatch showing one.
+
+use accesskit::Role;
+use kurbo::{Circle, Point, Rect};
+use smallvec::{smallvec, SmallVec};
+use tracing::{trace, trace_span, Span};
+use vello::Scene;
+use winit::event::MouseButton;
+use winit::keyboard::{Key, NamedKey};
+
+use crate::action::Action;
+use crate::paint_scene_helpers::{fill_color, fill_lin_gradient, stroke, UnitPoint};
+use crate::widget::{LineBreaking, Textbox, WidgetMut, WidgetPod, WidgetRef};
+use crate::{
+    theme, AccessCtx, AccessEvent, BoxConstraints, Color, CursorIcon, EventCtx, LayoutCtx,
+    LifeCycle, LifeCycleCtx, PaintCtx, PointerEvent, Size, StatusChange, TextEvent, Widget,
+};
+
+/// The width of the picker when the constraints don't bound it.
+const PICKER_WIDTH: f64 = 200.;
+/// The height of the saturation and value square.
+const SQUARE_HEIGHT: f64 = 150.;
+/// The height of the hue and alpha bars.
+const BAR_HEIGHT: f64 = 14.;
+/// The space between the parts of the picker.
+const SPACING: f64 = 8.;
+/// The size of the squares of the checkerboard drawn behind translucent colors.
+const CHECKER_SIZE: f64 = 4.;
+
+/// A widget for picking a color, with a square for the saturation and value, a bar for
+/// the hue, and a text box for the hex code of the color.
+///
+/// An alpha bar can be added with [`with_alpha`](Self::with_alpha). Without it, the
+/// alpha of the color is left as it is.
+///
+/// The hex code is committed when Enter is pressed or when the text box loses focus.
+/// It can have 3 or 6 digits, or 4 or 8 digits with the alpha bar, and the `#` is
+/// optional; if it isn't valid, the previous code is shown again. Next to the text box,
+/// a [`ColorSwatch`] shows the color.
+///
+/// When the color changes, this submits [`Action::ColorChanged`] with the new color.
+pub struct ColorPicker {
+    color: Color,
+    /// The hue in degrees, kept separately from the color so that it isn't lost when the
+    /// color is grey.
+    hue: f64,
+    /// The saturation, kept separately so that it isn't lost when the color is black.
+    saturation: f64,
+    value: f64,
+    alpha_enabled: bool,
+    dragging: Option<Area>,
+    swatch: WidgetPod<ColorSwatch>,
+    hex: WidgetPod<Textbox>,
+    /// Whether the text box had focus, so that the typed code is committed when it loses it.
+    was_editing: bool,
+}
+
+/// The part of a [`ColorPicker`] being dragged.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Area {
+    SaturationValue,
+    Hue,
+    Alpha,
+}
+
+/// A rectangle filled with a color. Clicking it copies the hex code of the color to the
+/// clipboard.
+pub struct ColorSwatch {
+    color: Color,
+}
+
+impl ColorPicker {
+    /// Create a new color picker showing `color`.
+    pub fn new(color: Color) -> Self {
+        let (hue, saturation, value) = color_to_hsv(color);
+        Self {
+            color,
+            hue: hue.unwrap_or(0.),
+            saturation: saturation.unwrap_or(0.),
+            value,
+            alpha_enabled: false,
+            dragging: None,
+            swatch: WidgetPod::new(ColorSwatch::new(color)),
+            hex: WidgetPod::new(
+                Textbox::new(format_hex(color)).with_line_break_mode(LineBreaking::Clip),
+            ),
+            was_editing: false,
+        }
+    }
+
+    /// Builder-style method to show a bar for the alpha of the color.
+    pub fn with_alpha(mut self, alpha_enabled: bool) -> Self {
+        self.alpha_enabled = alpha_enabled;
+        self
+    }
+
+    /// The current color.
+    pub fn color(&self) -> Color {
+        self.color
+    }
+
+    /// Make `color` the current color, returning whether it changed.
+    ///
+    /// The hue and saturation are kept when `color` doesn't determine them.
+    fn update_color(&mut self, color: Color) -> bool {
+        if color == self.color {
+            return false;
+        }
+        let (hue, saturation, value) = color_to_hsv(color);
+        self.hue = hue.unwrap_or(self.hue);
+        self.saturation = saturation.unwrap_or(self.saturation);
+        self.value = value;
+        self.color = color;
+        true
+    }
+
+    /// Recompute the color from the hue, saturation and value, returning whether it changed.
+    fn update_color_from_hsv(&mut self) -> bool {
+        let color = hsv_to_color(self.hue, self.saturation, self.value, self.color.a);
+        let changed = color != self.color;
+        self.color = color;
+        changed
+    }
+
+    /// The color of the hex code in the text box, if it is valid.
+    fn typed_color(&self) -> Option<Color> {
+        let color = parse_hex(self.hex.widget().text())?;
+        if color.a != 255 && !self.alpha_enabled {
+            return None;
+        }
+        // A code without alpha is opaque, but without the alpha bar the alpha is kept.
+        Some(if self.alpha_enabled {
+            color
+        } else {
+            Color {
+                a: self.color.a,
+                ..color
+            }
+        })
+    }
+
+    /// Submit the current color, and show it in the swatch and text box.
+    fn submit_color(&mut self, ctx: &mut EventCtx) {
+        trace!("ColorPicker {:?} set to {:?}", ctx.widget_id(), self.color);
+        ctx.submit_action(Action::ColorChanged(self.color));
+        ctx.request_accessibility_update();
+        ctx.request_paint();
+        ctx.get_mut(&mut self.swatch).set_color(self.color);
+        show_hex(ctx.get_mut(&mut self.hex), self.color);
+    }
+
+    /// Commit the hex code in the text box, submitting the color if it changed.
+    fn commit_hex(&mut self, ctx: &mut EventCtx) {
+        let color = self.typed_color().unwrap_or(self.color);
+        if self.update_color(color) {
+            self.submit_color(ctx);
+        } else {
+            show_hex(ctx.get_mut(&mut self.hex), self.color);
+        }
+    }
+
+    /// Change the part of the color controlled by `area` to match the position `pos`.
+    fn drag_to(&mut self, ctx: &mut EventCtx, area: Area, pos: Point) {
+        let width = ctx.size().width;
+        let rect = match area {
+            Area::SaturationValue => square_rect(width),
+            Area::Hue => hue_rect(width),
+            Area::Alpha => alpha_rect(width),
+        };
+        let x = ((pos.x - rect.x0) / rect.width()).clamp(0., 1.);
+        let y = ((pos.y - rect.y0) / rect.height()).clamp(0., 1.);
+        let changed = match area {
+            Area::SaturationValue => {
+                self.saturation = x;
+                self.value = 1. - y;
+                self.update_color_from_hsv()
+            }
+            Area::Hue => {
+                self.hue = x * 360.;
+                self.update_color_from_hsv()
+            }
+            Area::Alpha => {
+                let alpha = (x * 255.).round() as u8;
+                let changed = alpha != self.color.a;
+                self.color.a = alpha;
+                changed
+            }
+        };
+        if changed {
+            self.submit_color(ctx);
+        }
+    }
+
+    /// The area at `pos`, if any.
+    fn area_at(&self, width: f64, pos: Point) -> Option<Area> {
+        if square_rect(width).contains(pos) {
+            Some(Area::SaturationValue)
+        } else if hue_rect(width).contains(pos) {
+            Some(Area::Hue)
+        } else if self.alpha_enabled && alpha_rect(width).contains(pos) {
+            Some(Area::Alpha)
+        } else {
+            None
+        }
+    }
+
+    /// The top of the row with the swatch and the text box.
+    fn hex_row_y(&self) -> f64 {
+        if self.alpha_enabled {
+            alpha_rect(0.).y1 + SPACING
+        } else {
+            hue_rect(0.).y1 + SPACING
+        }
+    }
+}
+
+impl ColorSwatch {
+    /// Create a new swatch showing `color`.
+    pub fn new(color: Color) -> Self {
+        Self { color }
+    }
+
+    /// The color shown.
+    pub fn color(&self) -> Color {
+        self.color
+    }
+}
+
+fn square_rect(width: f64) -> Rect {
+    Rect::new(0., 0., width, SQUARE_HEIGHT)
+}
+
+fn hue_rect(width: f64) -> Rect {
+    let y0 = SQUARE_HEIGHT + SPACING;
+    Rect::new(0., y0, width, y0 + BAR_HEIGHT)
+}
+
+fn alpha_rect(width: f64) -> Rect {
+    let y0 = hue_rect(width).y1 + SPACING;
+    Rect::new(0., y0, width, y0 + BAR_HEIGHT)
+}
+
+/// Convert a hue in degrees, and a saturation and value between 0 and 1, to a color.
+fn hsv_to_color(hue: f64, saturation: f64, value: f64, alpha: u8) -> Color {
+    let hue = hue.rem_euclid(360.) / 60.;
+    let chroma = value * saturation;
+    let x = chroma * (1. - (hue % 2. - 1.).abs());
+    let (r, g, b) = match hue as u32 {
+        0 => (chroma, x, 0.),
+        1 => (x, chroma, 0.),
+        2 => (0., chroma, x),
+        3 => (0., x, chroma),
+        4 => (x, 0., chroma),
+        _ => (chroma, 0., x),
+    };
+    let m = value - chroma;
+    let Color { r, g, b, .. } = Color::rgb(r + m, g + m, b + m);
+    Color::rgba8(r, g, b, alpha)
+}
+
+/// Convert a color to its hue in degrees, saturation and value.
+///
+/// The hue is `None` for greys, and the saturation is `None` for black.
+fn color_to_hsv(color: Color) -> (Option<f64>, Option<f64>, f64) {
+    let r = color.r as f64 / 255.;
+    let g = color.g as f64 / 255.;
+    let b = color.b as f64 / 255.;
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let delta = max - min;
+    let hue = if delta == 0. {
+        None
+    } else if max == r {
+        Some(60. * ((g - b) / delta).rem_euclid(6.))
+    } else if max == g {
+        Some(60. * ((b - r) / delta + 2.))
+    } else {
+        Some(60. * ((r - g) / delta + 4.))
+    };
+    let saturation = (max > 0.).then(|| delta / max);
+    (hue, saturation, max)
+}
+
+/// The hex code of `color`, such as `#ff8000`, with the alpha only if it isn't opaque.
+fn format_hex(color: Color) -> String {
+    if color.a == 255 {
+        format!("#{:02x}{:02x}{:02x}", color.r, color.g, color.b)
+    } else {
+        format!(
+            "#{:02x}{:02x}{:02x}{:02x}",
+            color.r, color.g, color.b, color.a
+        )
+    }
+}
+
+/// Parse a hex code with 3, 4, 6 or 8 digits, with or without a `#`.
+fn parse_hex(text: &str) -> Option<Color> {
+    let text = text.trim();
+    let digits = text.strip_prefix('#').unwrap_or(text);
+    if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let digit = |idx: usize| u8::from_str_radix(&digits[idx..idx + 1], 16).unwrap();
+    let byte = |idx: usize| u8::from_str_radix(&digits[idx..idx + 2], 16).unwrap();
+    match digits.len() {
+        3 => Some(Color::rgb8(digit(0) * 17, digit(1) * 17, digit(2) * 17)),
+        4 => Some(Color::rgba8(
+            digit(0) * 17,
+            digit(1) * 17,
+            digit(2) * 17,
+            digit(3) * 17,
+        )),
+        6 => Some(Color::rgb8(byte(0), byte(2), byte(4))),
+        8 => Some(Color::rgba8(byte(0), byte(2), byte(4), byte(6))),
+        _ => None,
+    }
+}
+
+/// The color as the RGBA integer used by accessibility.
+fn accessibility_color(color: Color) -> u32 {
+    u32::from_be_bytes([color.r, color.g, color.b, color.a])
+}
+
+/// Show the hex code of `color` in the text box, selecting it if the text box is focused,
+/// so that typing replaces it.
+fn show_hex(mut textbox: WidgetMut<'_, Textbox>, color: Color) {
+    let text = format_hex(color);
+    if textbox.ctx.is_focused() {
+        textbox.set_text_properties(|editor| {
+            editor.set_text(text);
+            editor.select_all();
+        });
+    } else {
+        textbox.reset_text(text);
+    }
+}
+
+/// Fill `rect` with a checkerboard, to show through translucent colors.
+fn fill_checkerboard(scene: &mut Scene, rect: Rect) {
+    fill_color(scene, &rect, Color::WHITE);
+    let columns = (rect.width() / CHECKER_SIZE).ceil() as usize;
+    let rows = (rect.height() / CHECKER_SIZE).ceil() as usize;
+    for row in 0..rows {
+        for column in (row % 2..columns).step_by(2) {
+            let x0 = rect.x0 + column as f64 * CHECKER_SIZE;
+            let y0 = rect.y0 + row as f64 * CHECKER_SIZE;
+            let square = Rect::new(x0, y0, x0 + CHECKER_SIZE, y0 + CHECKER_SIZE).intersect(rect);
+            fill_color(scene, &square, Color::LIGHT_GRAY);
+        }
+    }
+}
+
+/// A ring around `center`, which stays visible on both light and dark colors.
+fn paint_handle(scene: &mut Scene, center: Point, radius: f64) {
+    stroke(scene, &Circle::new(center, radius), Color::BLACK, 3.);
+    stroke(scene, &Circle::new(center, radius), Color::WHITE, 1.5);
+}
+
+impl WidgetMut<'_, ColorPicker> {
+    /// Set the color.
+    ///
+    /// This replaces any hex code the user is typing.
+    pub fn set_color(&mut self, color: Color) {
+        if self.widget.update_color(color) {
+            self.ctx.get_mut(&mut self.widget.swatch).set_color(color);
+            show_hex(self.ctx.get_mut(&mut self.widget.hex), color);
+            self.ctx.request_paint();
+            self.ctx.request_accessibility_update();
+        }
+    }
+
+    /// Set whether to show a bar for the alpha of the color.
+    pub fn set_alpha(&mut self, alpha_enabled: bool) {
+        self.widget.alpha_enabled = alpha_enabled;
+        self.ctx.request_layout();
+    }
+}
+
+impl WidgetMut<'_, ColorSwatch> {
+    /// Set the color shown.
+    pub fn set_color(&mut self, color: Color) {
+        self.widget.color = color;
+        self.ctx.request_paint();
+        self.ctx.request_accessibility_update();
+    }
+}
+
+impl Widget for ColorPicker {
+    fn on_pointer_event(&mut self, ctx: &mut EventCtx, event: &PointerEvent) {
+        self.swatch.on_pointer_event(ctx, event);
+        self.hex.on_pointer_event(ctx, event);
+        if ctx.is_disabled() {
+            return;
+        }
+
+        match event {
+            PointerEvent::PointerDown(MouseButton::Left, state) if !ctx.is_handled() => {
+                let pos =
+                    Point::new(state.position.x, state.position.y) - ctx.window_origin().to_vec2();
+                if let Some(area) = self.area_at(ctx.size().width, pos) {
+                    self.dragging = Some(area);
+                    ctx.set_active(true);
+                    ctx.capture_pointer();
+                    self.drag_to(ctx, area, pos);
+                    ctx.set_handled();
+                }
+            }
+            PointerEvent::PointerMove(state) => {
+                if let Some(area) = self.dragging {
+                    let pos = Point::new(state.position.x, state.position.y)
+                        - ctx.window_origin().to_vec2();
+                    self.drag_to(ctx, area, pos);
+                }
+            }
+            PointerEvent::PointerUp(MouseButton::Left, _) if self.dragging.is_some() => {
+                self.dragging = None;
+                ctx.set_active(false);
+                ctx.release_pointer();
+            }
+            _ => (),
+        }
+    }
+
+    fn on_text_event(&mut self, ctx: &mut EventCtx, event: &TextEvent) {
+        if let TextEvent::KeyboardKey(key_event, _) = event {
+            if key_event.state.is_pressed()
+                && key_event.logical_key == Key::Named(NamedKey::Enter)
+                && self.hex.has_focus()
+                && !ctx.is_disabled()
+            {
+                self.commit_hex(ctx);
+                ctx.set_handled();
+                return;
+            }
+        }
+        self.swatch.on_text_event(ctx, event);
+        self.hex.on_text_event(ctx, event);
+    }
+
+    fn on_access_event(&mut self, ctx: &mut EventCtx, event: &AccessEvent) {
+        self.swatch.on_access_event(ctx, event);
+        self.hex.on_access_event(ctx, event);
+    }
+
+    fn on_status_change(&mut self, _ctx: &mut LifeCycleCtx, _event: &StatusChange) {}
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle) {
+        self.swatch.lifecycle(ctx, event);
+        self.hex.lifecycle(ctx, event);
+
+        // Commit the typed code when the text box loses focus.
+        let editing = self.hex.has_focus();
+        if self.was_editing && !editing {
+            let color = self.typed_color().unwrap_or(self.color);
+            if self.update_color(color) {
+                ctx.submit_action(Action::ColorChanged(self.color));
+                ctx.get_mut(&mut self.swatch).set_color(self.color);
+                ctx.request_accessibility_update();
+                ctx.request_paint();
+            }
+            show_hex(ctx.get_mut(&mut self.hex), self.color);
+        }
+        self.was_editing = editing;
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints) -> Size {
+        let width = if bc.is_width_bounded() {
+            bc.max().width
+        } else {
+            PICKER_WIDTH
+        };
+        let row_y = self.hex_row_y();
+
+        let swatch_width = theme::BORDERED_WIDGET_HEIGHT;
+        let text_width = (width - swatch_width - SPACING).max(0.);
+        let text_bc = BoxConstraints::new(
+            Size::new(text_width, 0.),
+            Size::new(text_width, f64::INFINITY),
+        );
+        let text_size = self.hex.layout(ctx, &text_bc);
+        let row_height = text_size.height.max(theme::BORDERED_WIDGET_HEIGHT);
+        ctx.place_child(&mut self.hex, Point::new(swatch_width + SPACING, row_y));
+
+        let swatch_size = Size::new(swatch_width, row_height);
+        self.swatch.layout(ctx, &BoxConstraints::tight(swatch_size));
+        ctx.place_child(&mut self.swatch, Point::new(0., row_y));
+
+        let size = bc.constrain(Size::new(width, row_y + row_height));
+        trace!("Computed size: {}", size);
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, scene: &mut Scene) {
+        let width = ctx.size().width;
+        let border = ctx.theme().border;
+
+        // The square is the pure hue, fading to white on the left and to black at the bottom.
+        let square = square_rect(width);
+        fill_color(scene, &square, hsv_to_color(self.hue, 1., 1., 255));
+        fill_lin_gradient(
+            scene,
+            &square,
+            [Color::WHITE, Color::WHITE.with_alpha_factor(0.)],
+            UnitPoint::LEFT,
+            UnitPoint::RIGHT,
+        );
+        fill_lin_gradient(
+            scene,
+            &square,
+            [Color::BLACK.with_alpha_factor(0.), Color::BLACK],
+            UnitPoint::TOP,
+            UnitPoint::BOTTOM,
+        );
+        stroke(scene, &square, border, 1.);
+        paint_handle(
+            scene,
+            Point::new(
+                square.x0 + self.saturation * square.width(),
+                square.y0 + (1. - self.value) * square.height(),
+            ),
+            5.,
+        );
+
+        let hue = hue_rect(width);
+        let hues: [Color; 7] =
+            std::array::from_fn(|idx| hsv_to_color(idx as f64 * 60., 1., 1., 255));
+        fill_lin_gradient(scene, &hue, hues, UnitPoint::LEFT, UnitPoint::RIGHT);
+        stroke(scene, &hue, border, 1.);
+        paint_handle(
+            scene,
+            Point::new(hue.x0 + self.hue / 360. * hue.width(), hue.center().y),
+            BAR_HEIGHT / 2.,
+        );
+
+        if self.alpha_enabled {
+            let alpha = alpha_rect(width);
+            let opaque = Color {
+                a: 255,
+                ..self.color
+            };
+            fill_checkerboard(scene, alpha);
+            fill_lin_gradient(
+                scene,
+                &alpha,
+                [opaque.with_alpha_factor(0.), opaque],
+                UnitPoint::LEFT,
+                UnitPoint::RIGHT,
+            );
+            stroke(scene, &alpha, border, 1.);
+            paint_handle(
+                scene,
+                Point::new(
+                    alpha.x0 + self.color.a as f64 / 255. * alpha.width(),
+                    alpha.center().y,
+                ),
+                BAR_HEIGHT / 2.,
+            );
+        }
+
+        self.swatch.paint(ctx, scene);
+        self.hex.paint(ctx, scene);
+    }
+
+    fn accessibility_role(&self) -> Role {
+        Role::ColorWell
+    }
+
+    fn accessibility(&mut self, ctx: &mut AccessCtx) {
+        ctx.current_node()
+            .set_color_value(accessibility_color(self.color));
+        self.swatch.accessibility(ctx);
+        self.hex.accessibility(ctx);
+    }
+
+    fn children(&self) -> SmallVec<[WidgetRef<'_, dyn Widget>; 16]> {
+        smallvec![self.swatch.as_dyn(), self.hex.as_dyn()]
+    }
+
+    fn make_trace_span(&self) -> Span {
+        trace_span!("ColorPicker")
+    }
+
+    fn get_debug_text(&self) -> Option<String> {
+        Some(format_hex(self.color))
+    }
+}
+
+impl Widget for ColorSwatch {
+    fn on_pointer_event(&mut self, ctx: &mut EventCtx, event: &PointerEvent) {
+        match event {
+            PointerEvent::PointerDown(MouseButton::Left, _) if !ctx.is_disabled() => {
+                ctx.set_active(true);
+                ctx.set_handled();
+            }
+            PointerEvent::PointerUp(MouseButton::Left, _) if ctx.is_active() => {
+                if ctx.is_hot() && !ctx.is_disabled() {
+                    trace!("ColorSwatch {:?} copied", ctx.widget_id());
+                    ctx.set_clipboard(format_hex(self.color));
+                }
+                ctx.set_active(false);
+            }
+            _ => (),
+        }
+    }
+
+    fn on_text_event(&mut self, _ctx: &mut EventCtx, _event: &TextEvent) {}
+
+    fn on_access_event(&mut self, ctx: &mut EventCtx, event: &AccessEvent) {
+        if event.target == ctx.widget_id()
+            && event.action == accesskit::Action::Default
+            && !ctx.is_disabled()
+        {
+            ctx.set_clipboard(format_hex(self.color));
+        }
+    }
+
+    fn on_status_change(&mut self, _ctx: &mut LifeCycleCtx, _event: &StatusChange) {}
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle) {
+        if let LifeCycle::WidgetAdded = event {
+            ctx.set_cursor(&CursorIcon::Pointer);
+        }
+    }
+
+    fn layout(&mut self, _ctx: &mut LayoutCtx, bc: &BoxConstraints) -> Size {
+        let side = theme::BORDERED_WIDGET_HEIGHT;
+        bc.constrain(Size::new(side, side))
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, scene: &mut Scene) {
+        let rect = ctx.size().to_rect();
+        if self.color.a != 255 {
+            fill_checkerboard(scene, rect);
+        }
+        fill_color(scene, &rect, self.color);
+        stroke(scene, &rect.inset(-0.5), ctx.theme().border, 1.);
+    }
+
+    fn accessibility_role(&self) -> Role {
+        Role::ColorWell
+    }
+
+    fn accessibility(&mut self, ctx: &mut AccessCtx) {
+        let node = ctx.current_node();
+        node.set_color_value(accessibility_color(self.color));
+        node.add_action(accesskit::Action::Default);
+    }
+
+    fn children(&self) -> SmallVec<[WidgetRef<'_, dyn Widget>; 16]> {
+        SmallVec::new()
+    }
+
+    fn make_trace_span(&self) -> Span {
+        trace_span!("ColorSwatch")
+    }
+
+    fn get_debug_text(&self) -> Option<String> {
+        Some(format_hex(self.color))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{widget_ids, TestHarness, TestWidgetExt};
+    use crate::widget::Flex;
+
+    fn hex_text(harness: &TestHarness, id: crate::WidgetId) -> String {
+        harness.get_widget(id).children()[1]
+            .downcast::<Textbox>()
+            .unwrap()
+            .deref()
+            .text()
+            .to_string()
+    }
+
+    #[test]
+    fn hue_bar_and_hex_code_change_color() {
+        let [picker_id, other_id] = widget_ids();
+        let widget = Flex::column()
+            .with_child(
+                ColorPicker::new(Color::rgb8(255, 0, 0))
+                    .with_alpha(true)
+                    .with_id(picker_id),
+            )
+            .with_child_id(Textbox::new("Other"), other_id);
+
+        let mut harness = TestHarness::create(widget);
+        let origin = harness
+            .get_widget(picker_id)
+            .state()
+            .window_layout_rect()
+            .origin();
+        let width = harness.get_widget(picker_id).state().layout_rect().width();
+
+        // The middle of the hue bar is cyan.
+        harness.mouse_move(origin + hue_rect(width).center().to_vec2());
+        harness.mouse_button_press(MouseButton::Left);
+        harness.mouse_button_release(MouseButton::Left);
+        assert_eq!(
+            harness.pop_action(),
+            Some((Action::ColorChanged(Color::rgb8(0, 255, 255)), picker_id))
+        );
+        assert_eq!(hex_text(&harness, picker_id), "#00ffff");
+
+        // An invalid code is replaced when the text box loses focus.
+        let hex_id = harness.get_widget(picker_id).children()[1].id();
+        harness.mouse_click_on(hex_id);
+        harness.keyboard_type_chars("zz");
+        while harness.pop_action().is_some() {}
+        harness.mouse_click_on(other_id);
+        assert_eq!(harness.pop_action(), None);
+        assert_eq!(hex_text(&harness, picker_id), "#00ffff");
+
+        // A valid one is committed.
+        harness.mouse_click_on(hex_id);
+        harness.keyboard_type_chars("80");
+        while harness.pop_action().is_some() {}
+        harness.mouse_click_on(other_id);
+        assert_eq!(
+            harness.pop_action(),
+            Some((
+                Action::ColorChanged(Color::rgba8(0, 255, 255, 128)),
+                picker_id
+            ))
+        );
+
+        // Clicking the swatch copies the code.
+        let swatch_id = harness.get_widget(picker_id).children()[0].id();
+        harness.mouse_click_on(swatch_id);
+        assert_eq!(crate::clipboard::get_text().as_deref(), Some("#00ffff80"));
+    }
+
+    #[test]
+    fn hex_codes() {
+        assert_eq!(parse_hex("#f80"), Some(Color::rgb8(255, 136, 0)));
+        assert_eq!(parse_hex(" 00ff0080 "), Some(Color::rgba8(0, 255, 0, 128)));
+        assert_eq!(parse_hex("#12345"), None);
+        assert_eq!(parse_hex("#gggggg"), None);
+        assert_eq!(format_hex(Color::rgba8(0, 255, 0, 128)), "#00ff0080");
+        assert_eq!(format_hex(Color::rgb8(18, 52, 86)), "#123456");
+    }
+}
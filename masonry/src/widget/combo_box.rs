@@ -0,0 +1,518 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! A text box which suggests matching options as the user types.
+
+use accesskit::Role;
+use smallvec::SmallVec;
+use tracing::{trace, trace_span, Span};
+use vello::Scene;
+use winit::event::MouseButton;
+use winit::keyboard::{Key, NamedKey};
+
+use crate::action::Action;
+use crate::event::PointerState;
+use crate::paint_scene_helpers::{fill_color, stroke};
+use crate::widget::{Label, LineBreaking, Textbox, WidgetId, WidgetMut, WidgetPod, WidgetRef};
+use crate::{
+    theme, AccessCtx, AccessEvent, ArcStr, BoxConstraints, EventCtx, Insets, LayoutCtx, LifeCycle,
+    LifeCycleCtx, PaintCtx, Point, PointerEvent, Rect, Size, StatusChange, TextEvent, Widget,
+};
+
+const ITEM_INSETS: Insets = Insets::uniform_xy(8., 2.);
+const POPUP_BORDER_WIDTH: f64 = 1.;
+const DEFAULT_MAX_SUGGESTIONS: usize = 8;
+
+/// A text box which shows a list of the options starting with the typed text, ignoring
+/// case, below it.
+///
+/// The list is painted as an overlay. While it's open, the up and down arrow keys move
+/// the highlight, enter accepts the highlighted option (or just closes the list, keeping
+/// the typed text), and escape closes it. Clicking an option accepts it. When the list is
+/// closed, the down arrow key opens it.
+///
+/// Submits [`Action::TextChanged`] when the text is edited or an option is accepted.
+pub struct ComboBox {
+    textbox: WidgetPod<Textbox>,
+    options: Vec<ArcStr>,
+    max_suggestions: usize,
+    /// The options matching the text when the list was last opened.
+    suggestions: Vec<WidgetPod<Label>>,
+    is_open: bool,
+    /// The suggestion under the pointer, or picked with the arrow keys, while the list is open.
+    highlighted: Option<usize>,
+    /// The text of the last submitted action, to detect edits.
+    text: String,
+    popup_size: Size,
+}
+
+impl ComboBox {
+    /// Create a new combo box with the given text, suggesting the given options.
+    pub fn new(
+        text: impl Into<String>,
+        options: impl IntoIterator<Item = impl Into<ArcStr>>,
+    ) -> Self {
+        let text = text.into();
+        Self {
+            textbox: WidgetPod::new(
+                Textbox::new(text.clone()).with_line_break_mode(LineBreaking::Clip),
+            ),
+            options: options.into_iter().map(Into::into).collect(),
+            max_suggestions: DEFAULT_MAX_SUGGESTIONS,
+            suggestions: Vec::new(),
+            is_open: false,
+            highlighted: None,
+            text,
+            popup_size: Size::ZERO,
+        }
+    }
+
+    /// Builder-style method to set how many matching options are shown at most.
+    ///
+    /// The default is 8.
+    pub fn with_max_suggestions(mut self, max_suggestions: usize) -> Self {
+        self.max_suggestions = max_suggestions;
+        self
+    }
+
+    /// The current text.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns `true` if the list of suggestions is currently shown.
+    pub fn is_open(&self) -> bool {
+        self.is_open
+    }
+
+    /// The suggestion highlighted with the arrow keys or the pointer, if the list is open.
+    pub fn highlighted_suggestion(&self) -> Option<&ArcStr> {
+        let idx = self.highlighted?;
+        Some(self.suggestions.get(idx)?.widget().text())
+    }
+
+    /// The id of the text box, which is the widget that gets focus.
+    pub(crate) fn textbox_id(&self) -> WidgetId {
+        self.textbox.id()
+    }
+
+    /// The options which start with the current text, ignoring case.
+    fn matches(&self) -> impl Iterator<Item = &ArcStr> {
+        let prefix = self.text.to_lowercase();
+        self.options
+            .iter()
+            .filter(move |option| option.to_lowercase().starts_with(&prefix))
+            .take(self.max_suggestions)
+    }
+
+    fn suggestion_at(&self, pos: Point) -> Option<usize> {
+        self.suggestions.iter().position(|suggestion| {
+            suggestion
+                .layout_rect()
+                .inflate(ITEM_INSETS.x0, ITEM_INSETS.y0)
+                .contains(pos)
+        })
+    }
+
+    fn open(&mut self, ctx: &mut EventCtx) {
+        self.suggestions = self
+            .matches()
+            .map(|option| WidgetPod::new(Label::new(option.clone())))
+            .collect();
+        self.highlighted = None;
+        if self.suggestions.is_empty() {
+            self.is_open = false;
+            ctx.close_overlay();
+        } else {
+            trace!("ComboBox {:?} opened", ctx.widget_id());
+            self.is_open = true;
+        }
+        ctx.children_changed();
+        ctx.request_layout();
+        ctx.request_accessibility_update();
+    }
+
+    fn close(&mut self, ctx: &mut EventCtx) {
+        trace!("ComboBox {:?} closed", ctx.widget_id());
+        self.is_open = false;
+        self.highlighted = None;
+        for suggestion in &mut self.suggestions {
+            ctx.set_stashed(suggestion, true);
+        }
+        ctx.close_overlay();
+        ctx.request_layout();
+        ctx.request_accessibility_update();
+    }
+
+    /// Replace the text with the suggestion at `idx`, and close the list.
+    fn accept(&mut self, ctx: &mut EventCtx, idx: usize) {
+        let Some(suggestion) = self.suggestions.get(idx) else {
+            return;
+        };
+        let text = suggestion.widget().text().to_string();
+        trace!("ComboBox {:?} accepted {:?}", ctx.widget_id(), text);
+        {
+            let mut textbox = ctx.get_mut(&mut self.textbox);
+            textbox.set_text_properties(|editor| {
+                editor.set_text(text.clone());
+                editor.move_caret_to_end();
+            });
+        }
+        self.submit_if_changed(ctx, text);
+        self.close(ctx);
+    }
+
+    fn submit_if_changed(&mut self, ctx: &mut EventCtx, text: String) {
+        if text != self.text {
+            self.text = text;
+            ctx.submit_action(Action::TextChanged(self.text.clone()));
+            ctx.request_accessibility_update();
+        }
+    }
+
+    /// Move the highlight by `delta` suggestions.
+    fn step(&mut self, ctx: &mut EventCtx, delta: isize) {
+        let Some(last) = self.suggestions.len().checked_sub(1) else {
+            return;
+        };
+        self.highlighted = Some(match self.highlighted {
+            Some(idx) => idx.saturating_add_signed(delta).min(last),
+            None if delta < 0 => last,
+            None => 0,
+        });
+        ctx.request_paint();
+    }
+}
+
+impl WidgetMut<'_, ComboBox> {
+    /// Set the text.
+    ///
+    /// This does nothing if the text is already shown, so that it doesn't disrupt typing.
+    /// Otherwise, the list is closed. It doesn't emit an action.
+    pub fn set_text(&mut self, text: impl Into<String>) {
+        let text = text.into();
+        if self.widget.textbox.widget().text() != text {
+            {
+                let mut textbox = self.ctx.get_mut(&mut self.widget.textbox);
+                if textbox.ctx.is_focused() {
+                    // Keep a caret, so that the user can go on typing.
+                    textbox.set_text_properties(|editor| {
+                        editor.set_text(text.clone());
+                        editor.move_caret_to_end();
+                    });
+                } else {
+                    textbox.reset_text(text.clone());
+                }
+            }
+            self.close();
+        }
+        self.widget.text = text;
+        self.ctx.request_accessibility_update();
+    }
+
+    /// Replace the options.
+    ///
+    /// If the list is open, it is closed.
+    pub fn set_options(&mut self, options: impl IntoIterator<Item = impl Into<ArcStr>>) {
+        self.widget.options = options.into_iter().map(Into::into).collect();
+        self.close();
+    }
+
+    /// Set how many matching options are shown at most.
+    pub fn set_max_suggestions(&mut self, max_suggestions: usize) {
+        self.widget.max_suggestions = max_suggestions;
+    }
+
+    fn close(&mut self) {
+        self.widget.is_open = false;
+        self.widget.highlighted = None;
+        self.widget.suggestions.clear();
+        self.ctx.close_overlay();
+        self.ctx.children_changed();
+        self.ctx.request_accessibility_update();
+    }
+}
+
+impl Widget for ComboBox {
+    fn on_pointer_event(&mut self, ctx: &mut EventCtx, event: &PointerEvent) {
+        let window_origin = ctx.window_origin().to_vec2();
+        let local_pos =
+            |state: &PointerState| Point::new(state.position.x, state.position.y) - window_origin;
+        match event {
+            // Clicks outside the list never reach us while it's open; they dismiss the overlay.
+            PointerEvent::PointerDown(MouseButton::Left, state) if self.is_open => {
+                if let Some(idx) = self.suggestion_at(local_pos(state)) {
+                    self.accept(ctx, idx);
+                    ctx.set_handled();
+                }
+            }
+            PointerEvent::PointerMove(state) if self.is_open => {
+                let highlighted = self.suggestion_at(local_pos(state));
+                if highlighted.is_some() && highlighted != self.highlighted {
+                    self.highlighted = highlighted;
+                    ctx.request_paint();
+                }
+            }
+            _ => (),
+        }
+        self.textbox.on_pointer_event(ctx, event);
+        for suggestion in &mut self.suggestions {
+            suggestion.on_pointer_event(ctx, event);
+        }
+    }
+
+    fn on_text_event(&mut self, ctx: &mut EventCtx, event: &TextEvent) {
+        if let TextEvent::KeyboardKey(key_event, _) = event {
+            if key_event.state.is_pressed() && !ctx.is_disabled() {
+                let handled = match &key_event.logical_key {
+                    Key::Named(NamedKey::ArrowDown) if !self.is_open => {
+                        self.open(ctx);
+                        true
+                    }
+                    Key::Named(NamedKey::ArrowDown) => {
+                        self.step(ctx, 1);
+                        true
+                    }
+                    Key::Named(NamedKey::ArrowUp) if self.is_open => {
+                        self.step(ctx, -1);
+                        true
+                    }
+                    Key::Named(NamedKey::Enter) if self.is_open => {
+                        match self.highlighted {
+                            Some(idx) => self.accept(ctx, idx),
+                            None => self.close(ctx),
+                        }
+                        true
+                    }
+                    Key::Named(NamedKey::Escape) if self.is_open => {
+                        self.close(ctx);
+                        true
+                    }
+                    _ => false,
+                };
+                if handled {
+                    ctx.set_handled();
+                    return;
+                }
+            }
+        }
+        self.textbox.on_text_event(ctx, event);
+
+        let text = self.textbox.widget().text();
+        if text != self.text {
+            let text = text.to_string();
+            self.submit_if_changed(ctx, text);
+            if self.text.is_empty() {
+                if self.is_open {
+                    self.close(ctx);
+                }
+            } else {
+                self.open(ctx);
+            }
+        }
+    }
+
+    fn on_access_event(&mut self, ctx: &mut EventCtx, event: &AccessEvent) {
+        if event.target == ctx.widget_id() {
+            match event.action {
+                accesskit::Action::Expand if !self.is_open => {
+                    self.open(ctx);
+                }
+                accesskit::Action::Collapse if self.is_open => {
+                    self.close(ctx);
+                }
+                _ => {}
+            }
+        }
+        self.textbox.on_access_event(ctx, event);
+        for suggestion in &mut self.suggestions {
+            suggestion.on_access_event(ctx, event);
+        }
+    }
+
+    fn on_status_change(&mut self, _ctx: &mut LifeCycleCtx, _event: &StatusChange) {}
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle) {
+        self.textbox.lifecycle(ctx, event);
+        for suggestion in &mut self.suggestions {
+            suggestion.lifecycle(ctx, event);
+        }
+        // The list is closed when it's dismissed, or when the text box loses focus.
+        let dismissed = matches!(event, LifeCycle::OverlayDismissed);
+        if self.is_open && (dismissed || !self.textbox.has_focus()) {
+            self.is_open = false;
+            self.highlighted = None;
+            for suggestion in &mut self.suggestions {
+                ctx.set_stashed(suggestion, true);
+            }
+            ctx.close_overlay();
+            ctx.request_layout();
+            ctx.request_accessibility_update();
+        }
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints) -> Size {
+        let width = if bc.is_width_bounded() {
+            bc.max().width
+        } else {
+            theme::WIDE_WIDGET_WIDTH
+        };
+        let text_bc = BoxConstraints::new(Size::new(width, 0.), Size::new(width, f64::INFINITY));
+        let text_size = self.textbox.layout(ctx, &text_bc);
+        ctx.place_child(&mut self.textbox, Point::ORIGIN);
+        let size = bc.constrain(text_size);
+
+        if self.is_open {
+            let suggestion_bc = BoxConstraints::UNBOUNDED;
+            let mut y = size.height + POPUP_BORDER_WIDTH;
+            let mut popup_width: f64 = 0.;
+            for suggestion in &mut self.suggestions {
+                let suggestion_size = suggestion.layout(ctx, &suggestion_bc);
+                let origin = Point::new(POPUP_BORDER_WIDTH + ITEM_INSETS.x0, y + ITEM_INSETS.y0);
+                ctx.place_child(suggestion, origin);
+                popup_width = popup_width.max(suggestion_size.width);
+                y += suggestion_size.height + ITEM_INSETS.y_value();
+            }
+            popup_width += ITEM_INSETS.x_value() + 2. * POPUP_BORDER_WIDTH;
+            self.popup_size = Size::new(
+                popup_width.max(size.width),
+                y - size.height + POPUP_BORDER_WIDTH,
+            );
+            ctx.set_overlay_rect(Rect::from_origin_size(
+                Point::new(0., size.height),
+                self.popup_size,
+            ));
+        }
+
+        trace!("Computed combo box size: {}", size);
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, scene: &mut Scene) {
+        self.textbox.paint(ctx, scene);
+
+        if !self.is_open {
+            return;
+        }
+        // The list is painted in an overlay, so that it isn't covered by other widgets
+        // or clipped by our ancestors.
+        let mut overlay = Scene::new();
+        let scene = &mut overlay;
+        let popup_rect = Rect::from_origin_size(Point::new(0., ctx.size().height), self.popup_size);
+        let rounded_rect = popup_rect
+            .inset(-POPUP_BORDER_WIDTH / 2.0)
+            .to_rounded_rect(theme::BUTTON_BORDER_RADIUS);
+        let theme = ctx.theme();
+        fill_color(scene, &rounded_rect, theme.field[0]);
+        stroke(scene, &rounded_rect, theme.border, POPUP_BORDER_WIDTH);
+
+        if let Some(suggestion) = self.highlighted.and_then(|idx| self.suggestions.get(idx)) {
+            let highlight = suggestion
+                .layout_rect()
+                .inflate(ITEM_INSETS.x0, ITEM_INSETS.y0)
+                .with_size((
+                    popup_rect.width() - 2. * POPUP_BORDER_WIDTH,
+                    suggestion.layout_rect().height() + ITEM_INSETS.y_value(),
+                ));
+            fill_color(scene, &highlight, theme.highlight);
+        }
+
+        for suggestion in &mut self.suggestions {
+            suggestion.paint(ctx, scene);
+        }
+        ctx.paint_overlay(overlay);
+    }
+
+    fn accessibility_role(&self) -> Role {
+        Role::ComboBox
+    }
+
+    fn accessibility(&mut self, ctx: &mut AccessCtx) {
+        let node = ctx.current_node();
+        node.set_value(self.text.clone());
+        node.set_expanded(self.is_open);
+        self.textbox.accessibility(ctx);
+        for suggestion in &mut self.suggestions {
+            suggestion.accessibility(ctx);
+        }
+    }
+
+    fn children(&self) -> SmallVec<[WidgetRef<'_, dyn Widget>; 16]> {
+        std::iter::once(self.textbox.as_dyn())
+            .chain(
+                self.suggestions
+                    .iter()
+                    .map(|suggestion| suggestion.as_dyn()),
+            )
+            .collect()
+    }
+
+    fn make_trace_span(&self) -> Span {
+        trace_span!("ComboBox")
+    }
+
+    fn get_debug_text(&self) -> Option<String> {
+        Some(self.text.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{widget_ids, TestHarness};
+    use crate::widget::Flex;
+
+    fn get_combo_box(harness: &TestHarness, id: crate::WidgetId) -> &ComboBox {
+        harness
+            .get_widget(id)
+            .downcast::<ComboBox>()
+            .unwrap()
+            .deref()
+    }
+
+    /// The actions submitted by the combo box, rather than by its text box.
+    fn combo_box_actions(harness: &mut TestHarness, id: crate::WidgetId) -> Vec<Action> {
+        std::iter::from_fn(|| harness.pop_action())
+            .filter_map(|(action, action_id)| (action_id == id).then_some(action))
+            .collect()
+    }
+
+    #[test]
+    fn typing_filters_suggestions() {
+        let [combo_id] = widget_ids();
+        let options = ["Apple", "apricot", "Banana", "Avocado"];
+        let widget = Flex::column().with_child_id(ComboBox::new("", options), combo_id);
+
+        let mut harness = TestHarness::create(widget);
+        let textbox_id = harness.get_widget(combo_id).children()[0].id();
+        harness.mouse_click_on(textbox_id);
+        harness.keyboard_type_chars("AP");
+
+        assert_eq!(
+            combo_box_actions(&mut harness, combo_id),
+            [
+                Action::TextChanged("A".into()),
+                Action::TextChanged("AP".into())
+            ]
+        );
+        let combo_box = get_combo_box(&harness, combo_id);
+        assert!(combo_box.is_open());
+        let suggestions: Vec<_> = combo_box
+            .suggestions
+            .iter()
+            .map(|suggestion| suggestion.widget().text().to_string())
+            .collect();
+        assert_eq!(suggestions, ["Apple", "apricot"]);
+
+        // Clicking a suggestion accepts it.
+        let second = harness.get_widget(combo_id).children()[2].id();
+        harness.mouse_click_on(second);
+        assert_eq!(
+            combo_box_actions(&mut harness, combo_id),
+            [Action::TextChanged("apricot".into())]
+        );
+        let combo_box = get_combo_box(&harness, combo_id);
+        assert!(!combo_box.is_open());
+        assert_eq!(combo_box.text(), "apricot");
+    }
+}
@@ -0,0 +1,388 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! A widget which shows a popup menu when right-clicked.
+
+use accesskit::Role;
+use smallvec::SmallVec;
+use tracing::{trace, trace_span, Span};
+use vello::Scene;
+
+use crate::action::Action;
+use crate::event::PointerState;
+use crate::paint_scene_helpers::{fill_color, stroke};
+use crate::widget::{Label, WidgetMut, WidgetPod, WidgetRef};
+use crate::{
+    theme, AccessCtx, AccessEvent, ArcStr, BoxConstraints, EventCtx, Insets, LayoutCtx, LifeCycle,
+    LifeCycleCtx, PaintCtx, Point, PointerEvent, Rect, Size, StatusChange, TextEvent, Widget,
+};
+
+const ITEM_INSETS: Insets = Insets::uniform_xy(8., 2.);
+const MENU_BORDER_WIDTH: f64 = 1.;
+
+/// A container which opens a popup menu when its child is right-clicked.
+///
+/// The menu is painted as an overlay at the pointer position, and is dismissed by any
+/// click, whether or not that click lands on one of its items.
+///
+/// Emits [`Action::ContextMenuItemSelected`] with the index of the clicked item.
+pub struct ContextMenu<W: Widget> {
+    child: WidgetPod<W>,
+    items: Vec<WidgetPod<Label>>,
+    /// The top-left corner of the open menu, in local coordinates.
+    menu_origin: Option<Point>,
+    menu_size: Size,
+    hovered_item: Option<usize>,
+}
+
+impl<W: Widget> ContextMenu<W> {
+    /// Create a new context menu around `child`, with one entry per item.
+    pub fn new(child: W, items: impl IntoIterator<Item = impl Into<ArcStr>>) -> Self {
+        Self::from_child_pod(WidgetPod::new(child), items)
+    }
+
+    /// Create a new context menu around a child which has already been wrapped in a [`WidgetPod`].
+    pub fn from_child_pod(
+        child: WidgetPod<W>,
+        items: impl IntoIterator<Item = impl Into<ArcStr>>,
+    ) -> Self {
+        Self {
+            child,
+            items: items
+                .into_iter()
+                .map(|text| WidgetPod::new(Label::new(text)))
+                .collect(),
+            menu_origin: None,
+            menu_size: Size::ZERO,
+            hovered_item: None,
+        }
+    }
+
+    /// Returns `true` if the menu is currently shown.
+    pub fn is_open(&self) -> bool {
+        self.menu_origin.is_some()
+    }
+
+    fn menu_rect(&self) -> Option<Rect> {
+        self.menu_origin
+            .map(|origin| Rect::from_origin_size(origin, self.menu_size))
+    }
+
+    fn item_at(&self, pos: Point) -> Option<usize> {
+        self.items
+            .iter()
+            .position(|item| item.layout_rect().inflate(ITEM_INSETS.x0, 0.).contains(pos))
+    }
+}
+
+impl<W: Widget> ContextMenu<W> {
+    fn open_menu(&mut self, ctx: &mut EventCtx, origin: Point) {
+        trace!("ContextMenu {:?} opened at {}", ctx.widget_id(), origin);
+        self.menu_origin = Some(origin);
+        self.hovered_item = None;
+        for item in &mut self.items {
+            ctx.set_stashed(item, false);
+        }
+        ctx.request_layout();
+    }
+
+    fn close_menu(&mut self, ctx: &mut EventCtx) {
+        trace!("ContextMenu {:?} closed", ctx.widget_id());
+        self.menu_origin = None;
+        self.hovered_item = None;
+        for item in &mut self.items {
+            ctx.set_stashed(item, true);
+        }
+        ctx.close_overlay();
+        ctx.request_layout();
+    }
+}
+
+impl<W: Widget> WidgetMut<'_, ContextMenu<W>> {
+    pub fn child_mut(&mut self) -> WidgetMut<'_, W> {
+        self.ctx.get_mut(&mut self.widget.child)
+    }
+
+    /// Replace the menu items.
+    ///
+    /// If the menu is open, it is closed.
+    pub fn set_items(&mut self, items: impl IntoIterator<Item = impl Into<ArcStr>>) {
+        self.widget.menu_origin = None;
+        self.widget.hovered_item = None;
+        self.widget.items = items
+            .into_iter()
+            .map(|text| WidgetPod::new(Label::new(text)))
+            .collect();
+        for item in &mut self.widget.items {
+            self.ctx.set_stashed(item, true);
+        }
+        self.ctx.close_overlay();
+        self.ctx.children_changed();
+        self.ctx.request_layout();
+    }
+}
+
+impl<W: Widget> Widget for ContextMenu<W> {
+    fn on_pointer_event(&mut self, ctx: &mut EventCtx, event: &PointerEvent) {
+        let window_origin = ctx.window_origin().to_vec2();
+        let local_pos =
+            |state: &PointerState| Point::new(state.position.x, state.position.y) - window_origin;
+        match event {
+            PointerEvent::PointerDown(_, state) if self.is_open() => {
+                if let Some(idx) = self.item_at(local_pos(state)) {
+                    ctx.submit_action(Action::ContextMenuItemSelected(idx));
+                }
+                self.close_menu(ctx);
+                ctx.set_handled();
+            }
+            PointerEvent::PointerMove(state) if self.is_open() => {
+                let hovered_item = self.item_at(local_pos(state));
+                if hovered_item != self.hovered_item {
+                    self.hovered_item = hovered_item;
+                    ctx.request_paint();
+                }
+            }
+            PointerEvent::PointerDown(_, state)
+                if event.is_secondary_button_press() && ctx.is_hot() && !ctx.is_disabled() =>
+            {
+                let origin = local_pos(state);
+                self.open_menu(ctx, origin);
+                ctx.set_handled();
+            }
+            _ => (),
+        }
+        self.child.on_pointer_event(ctx, event);
+        for item in &mut self.items {
+            item.on_pointer_event(ctx, event);
+        }
+    }
+
+    fn on_text_event(&mut self, ctx: &mut EventCtx, event: &TextEvent) {
+        self.child.on_text_event(ctx, event);
+        for item in &mut self.items {
+            item.on_text_event(ctx, event);
+        }
+    }
+
+    fn on_access_event(&mut self, ctx: &mut EventCtx, event: &AccessEvent) {
+        self.child.on_access_event(ctx, event);
+        for item in &mut self.items {
+            item.on_access_event(ctx, event);
+        }
+    }
+
+    fn on_status_change(&mut self, _ctx: &mut LifeCycleCtx, _event: &StatusChange) {}
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle) {
+        self.child.lifecycle(ctx, event);
+        for item in &mut self.items {
+            item.lifecycle(ctx, event);
+        }
+        match event {
+            // Items are only shown while the menu is open.
+            LifeCycle::WidgetAdded | LifeCycle::OverlayDismissed => {
+                self.menu_origin = None;
+                self.hovered_item = None;
+                for item in &mut self.items {
+                    ctx.set_stashed(item, true);
+                }
+                ctx.request_layout();
+            }
+            _ => {}
+        }
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints) -> Size {
+        let size = self.child.layout(ctx, bc);
+        ctx.place_child(&mut self.child, Point::ORIGIN);
+
+        if let Some(origin) = self.menu_origin {
+            let item_bc = BoxConstraints::UNBOUNDED;
+            let mut menu_size = Size::new(0., MENU_BORDER_WIDTH);
+            let mut item_sizes = Vec::with_capacity(self.items.len());
+            for item in &mut self.items {
+                let item_size = item.layout(ctx, &item_bc);
+                menu_size.width = menu_size.width.max(item_size.width);
+                item_sizes.push(item_size);
+            }
+            let mut y = origin.y + MENU_BORDER_WIDTH;
+            for (item, item_size) in self.items.iter_mut().zip(item_sizes) {
+                let item_origin = Point::new(
+                    origin.x + MENU_BORDER_WIDTH + ITEM_INSETS.x0,
+                    y + ITEM_INSETS.y0,
+                );
+                ctx.place_child(item, item_origin);
+                y += item_size.height + ITEM_INSETS.y_value();
+            }
+            menu_size.width += ITEM_INSETS.x_value() + 2. * MENU_BORDER_WIDTH;
+            menu_size.height = y - origin.y + MENU_BORDER_WIDTH;
+            self.menu_size = menu_size;
+            ctx.set_overlay_rect(Rect::from_origin_size(origin, menu_size));
+        }
+
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, scene: &mut Scene) {
+        self.child.paint(ctx, scene);
+
+        let Some(menu_rect) = self.menu_rect() else {
+            return;
+        };
+        // The menu is painted in an overlay, so that it isn't covered by other widgets
+        // or clipped by our ancestors.
+        let mut overlay = Scene::new();
+        let scene = &mut overlay;
+        let rounded_rect = menu_rect
+            .inset(-MENU_BORDER_WIDTH / 2.0)
+            .to_rounded_rect(theme::BUTTON_BORDER_RADIUS);
+        let theme = ctx.theme();
+        fill_color(scene, &rounded_rect, theme.field[0]);
+        stroke(scene, &rounded_rect, theme.border, MENU_BORDER_WIDTH);
+
+        if let Some(item) = self.hovered_item.and_then(|idx| self.items.get(idx)) {
+            let highlight = item
+                .layout_rect()
+                .inflate(ITEM_INSETS.x0, ITEM_INSETS.y0)
+                .with_size((
+                    menu_rect.width() - 2. * MENU_BORDER_WIDTH,
+                    item.layout_rect().height() + ITEM_INSETS.y_value(),
+                ));
+            fill_color(scene, &highlight, theme.highlight);
+        }
+
+        for item in &mut self.items {
+            item.paint(ctx, scene);
+        }
+        ctx.paint_overlay(overlay);
+    }
+
+    fn accessibility_role(&self) -> Role {
+        Role::GenericContainer
+    }
+
+    fn accessibility(&mut self, ctx: &mut AccessCtx) {
+        self.child.accessibility(ctx);
+        for item in &mut self.items {
+            item.accessibility(ctx);
+        }
+    }
+
+    fn children(&self) -> SmallVec<[WidgetRef<'_, dyn Widget>; 16]> {
+        std::iter::once(self.child.as_dyn())
+            .chain(self.items.iter().map(|item| item.as_dyn()))
+            .collect()
+    }
+
+    fn make_trace_span(&self) -> Span {
+        trace_span!("ContextMenu")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use winit::event::MouseButton;
+
+    use super::*;
+    use crate::testing::{widget_ids, TestHarness, TestWidgetExt};
+    use crate::widget::{Button, Flex, SizedBox};
+
+    #[test]
+    fn right_click_opens_menu() {
+        let [menu_id] = widget_ids();
+        let widget = ContextMenu::new(SizedBox::empty().width(100.).height(100.), ["Cut", "Copy"])
+            .with_id(menu_id);
+
+        let mut harness = TestHarness::create(widget);
+        harness.mouse_move((10., 10.));
+
+        // A left click doesn't open the menu.
+        harness.mouse_button_press(MouseButton::Left);
+        harness.mouse_button_release(MouseButton::Left);
+        let is_open = |harness: &TestHarness| {
+            harness
+                .get_widget(menu_id)
+                .downcast::<ContextMenu<SizedBox>>()
+                .unwrap()
+                .deref()
+                .is_open()
+        };
+        assert!(!is_open(&harness));
+
+        harness.mouse_button_press(MouseButton::Right);
+        harness.mouse_button_release(MouseButton::Right);
+        assert!(is_open(&harness));
+
+        // Clicking outside the menu closes it without selecting anything.
+        harness.mouse_move((90., 90.));
+        harness.mouse_button_press(MouseButton::Left);
+        harness.mouse_button_release(MouseButton::Left);
+        assert!(!is_open(&harness));
+        assert_eq!(harness.pop_action(), None);
+    }
+
+    #[test]
+    fn click_on_item() {
+        let [menu_id] = widget_ids();
+        let widget = ContextMenu::new(SizedBox::empty().width(100.).height(100.), ["Cut", "Copy"])
+            .with_id(menu_id);
+
+        let mut harness = TestHarness::create(widget);
+        harness.mouse_move((10., 10.));
+        harness.mouse_button_press(MouseButton::Right);
+        harness.mouse_button_release(MouseButton::Right);
+
+        let second_item = harness.get_widget(menu_id).children()[2]
+            .state()
+            .window_layout_rect();
+        harness.mouse_move(second_item.center());
+        harness.mouse_button_press(MouseButton::Left);
+        harness.mouse_button_release(MouseButton::Left);
+
+        assert_eq!(
+            harness.pop_action(),
+            Some((Action::ContextMenuItemSelected(1), menu_id))
+        );
+    }
+
+    #[test]
+    fn menu_covers_other_widgets() {
+        let [menu_id, button_id] = widget_ids();
+        let widget = Flex::column()
+            .with_child_id(
+                ContextMenu::new(SizedBox::empty().width(100.).height(10.), ["Cut", "Copy"]),
+                menu_id,
+            )
+            .with_child_id(Button::new("A long label below the menu"), button_id);
+
+        let mut harness = TestHarness::create(widget);
+        harness.mouse_move_to(menu_id);
+        harness.mouse_button_press(MouseButton::Right);
+        harness.mouse_button_release(MouseButton::Right);
+
+        // The first item is drawn above the button, and gets the click instead of it.
+        let first_item = harness.get_widget(menu_id).children()[1]
+            .state()
+            .window_layout_rect();
+        let button_rect = harness.get_widget(button_id).state().window_layout_rect();
+        assert!(first_item.intersect(button_rect).area() > 0.);
+        harness.mouse_move(first_item.center());
+        harness.mouse_button_press(MouseButton::Left);
+        harness.mouse_button_release(MouseButton::Left);
+        assert_eq!(
+            harness.pop_action(),
+            Some((Action::ContextMenuItemSelected(0), menu_id))
+        );
+        assert_eq!(harness.pop_action(), None);
+
+        // A click outside of the menu closes it, and doesn't reach the widget below.
+        harness.mouse_move_to(menu_id);
+        harness.mouse_button_press(MouseButton::Right);
+        harness.mouse_button_release(MouseButton::Right);
+        harness.mouse_move((button_rect.x0 + 2., button_rect.center().y));
+        harness.mouse_button_press(MouseButton::Left);
+        harness.mouse_button_release(MouseButton::Left);
+        assert_eq!(harness.pop_action(), None);
+        assert!(harness.get_widget(menu_id).children()[1].state().is_stashed);
+    }
+}
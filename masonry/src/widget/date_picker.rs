@@ -0,0 +1,1002 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! A calendar for picking a date, or a range of dates.
+
+use std::ops::RangeInclusive;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use accesskit::Role;
+use chrono::{Datelike, Days, Month, Months, NaiveDate, Weekday};
+use kurbo::{BezPath, Point, Rect};
+use smallvec::{smallvec, SmallVec};
+use tracing::{trace, trace_span, Span};
+use vello::Scene;
+use winit::event::MouseButton;
+use winit::keyboard::{Key, NamedKey};
+
+use crate::action::Action;
+use crate::paint_scene_helpers::{fill_color, stroke};
+use crate::text2::TextLayout;
+use crate::theme::Theme;
+use crate::widget::{WidgetMut, WidgetPod, WidgetRef};
+use crate::{
+    theme, AccessCtx, AccessEvent, ArcStr, BoxConstraints, CursorIcon, EventCtx, LayoutCtx,
+    LifeCycle, LifeCycleCtx, PaintCtx, PointerEvent, Size, StatusChange, TextEvent, Widget,
+};
+
+/// The width and height of each day.
+const CELL_SIZE: f64 = 32.;
+/// The height of the row with the month and the arrows.
+const HEADER_HEIGHT: f64 = 32.;
+/// The height of the row with the names of the days of the week.
+const WEEKDAY_HEIGHT: f64 = 20.;
+/// The grid always has six weeks, which is enough for any month, so that its size doesn't change.
+const WEEKS: usize = 6;
+
+const WEEKDAY_NAMES: [&str; 7] = [
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+    "Sunday",
+];
+
+/// A calendar showing the days of a month, for picking a date or a range of dates.
+///
+/// The arrows next to the month, and Page Up and Page Down, show the previous and next
+/// months. Days can be focused, and moved between with the arrow keys; Enter or Space
+/// picks the focused day. Days of the previous and next months are shown greyed out
+/// around the month, and can be picked too.
+///
+/// A picker made with [`new`](Self::new) selects a single date, and submits
+/// [`Action::DateSelected`] when a day is picked. One made with [`new_range`](Self::new_range)
+/// selects a range: the first day picked is one end, and the second is the other end, at
+/// which point [`Action::DateRangeSelected`] is submitted.
+///
+/// For accessibility, the days are the cells of a [grid](Role::Grid).
+pub struct DatePicker {
+    selection: Selection,
+    /// The first day of the month shown.
+    month: NaiveDate,
+    week_start: Weekday,
+    title: TextLayout<ArcStr>,
+    previous: WidgetPod<MonthButton>,
+    next: WidgetPod<MonthButton>,
+    grid: WidgetPod<MonthGrid>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Selection {
+    Date(Option<NaiveDate>),
+    /// The ends of a range. While the user is picking a range, only the start is set.
+    Range(Option<NaiveDate>, Option<NaiveDate>),
+}
+
+/// How a day is highlighted.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum Highlight {
+    #[default]
+    None,
+    /// The day is strictly between the ends of the selected range.
+    InRange,
+    Selected,
+}
+
+/// One of the arrows showing the previous or next month.
+struct MonthButton {
+    forward: bool,
+}
+
+/// The grid of days of a [`DatePicker`], with a row for the names of the days of the week.
+struct MonthGrid {
+    week_start: Weekday,
+    weekday_labels: Vec<TextLayout<ArcStr>>,
+    days: Vec<WidgetPod<Day>>,
+}
+
+/// A day of a [`MonthGrid`].
+///
+/// The picker handles input for its days; they're separate widgets so that they can be
+/// focused, and exposed to accessibility, on their own.
+struct Day {
+    /// The position of the day in the grid, from the top left.
+    index: usize,
+    state: DayState,
+    text: TextLayout<ArcStr>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct DayState {
+    date: NaiveDate,
+    /// Whether the day is in the month shown, rather than the previous or next one.
+    in_month: bool,
+    today: bool,
+    highlight: Highlight,
+}
+
+impl DatePicker {
+    /// Create a new picker for a single date, with `date` selected.
+    ///
+    /// The month of `date` is shown, or the current month if there is none.
+    pub fn new(date: Option<NaiveDate>) -> Self {
+        Self::with_selection(Selection::Date(date))
+    }
+
+    /// Create a new picker for a range of dates, with `range` selected.
+    ///
+    /// The month of the start of `range` is shown, or the current month if there is none.
+    pub fn new_range(range: Option<RangeInclusive<NaiveDate>>) -> Self {
+        Self::with_selection(range_selection(range))
+    }
+
+    fn with_selection(selection: Selection) -> Self {
+        let month = first_of_month(selection.start().unwrap_or_else(today));
+        let week_start = Weekday::Mon;
+        Self {
+            selection,
+            month,
+            week_start,
+            title: TextLayout::new(month_title(month).into(), theme::TEXT_SIZE_NORMAL as f32),
+            previous: WidgetPod::new(MonthButton { forward: false }),
+            next: WidgetPod::new(MonthButton { forward: true }),
+            grid: WidgetPod::new(MonthGrid::new(month, week_start, selection)),
+        }
+    }
+
+    /// Builder-style method to set the first day of the week, which is Monday by default.
+    pub fn with_week_start(mut self, week_start: Weekday) -> Self {
+        self.week_start = week_start;
+        self.grid = WidgetPod::new(MonthGrid::new(self.month, week_start, self.selection));
+        self
+    }
+
+    /// Builder-style method to show the month of `date`, instead of that of the selection.
+    pub fn with_month(mut self, date: NaiveDate) -> Self {
+        self.month = first_of_month(date);
+        self.title.set_text(month_title(self.month).into());
+        self.grid = WidgetPod::new(MonthGrid::new(self.month, self.week_start, self.selection));
+        self
+    }
+
+    /// The selected date, for a picker made with [`new`](Self::new).
+    pub fn selected_date(&self) -> Option<NaiveDate> {
+        match self.selection {
+            Selection::Date(date) => date,
+            Selection::Range(..) => None,
+        }
+    }
+
+    /// The selected range, for a picker made with [`new_range`](Self::new_range).
+    ///
+    /// While the user is picking a range, this is the previous one.
+    pub fn selected_range(&self) -> Option<RangeInclusive<NaiveDate>> {
+        match self.selection {
+            Selection::Range(Some(start), Some(end)) => Some(start..=end),
+            _ => None,
+        }
+    }
+
+    /// The first day of the month shown.
+    pub fn month(&self) -> NaiveDate {
+        self.month
+    }
+
+    /// Show the days of the current month, with the current selection, in the grid.
+    fn sync(&mut self, ctx: &mut EventCtx) {
+        self.title.set_text(month_title(self.month).into());
+        ctx.get_mut(&mut self.grid)
+            .show(self.month, self.week_start, self.selection);
+        ctx.request_layout();
+        ctx.request_accessibility_update();
+    }
+
+    /// Pick `date`, submitting the new selection if it is complete.
+    fn pick(&mut self, ctx: &mut EventCtx, date: NaiveDate) {
+        trace!("DatePicker {:?} picked {}", ctx.widget_id(), date);
+        match self.selection {
+            Selection::Date(selected) => {
+                if selected != Some(date) {
+                    self.selection = Selection::Date(Some(date));
+                    ctx.submit_action(Action::DateSelected(date));
+                }
+            }
+            Selection::Range(Some(start), None) => {
+                let (start, end) = if date < start {
+                    (date, start)
+                } else {
+                    (start, date)
+                };
+                self.selection = Selection::Range(Some(start), Some(end));
+                ctx.submit_action(Action::DateRangeSelected(start..=end));
+            }
+            Selection::Range(..) => self.selection = Selection::Range(Some(date), None),
+        }
+        self.sync(ctx);
+    }
+
+    /// Show the month of `date`, and focus its day.
+    fn focus_date(&mut self, ctx: &mut EventCtx, date: NaiveDate) {
+        let month = first_of_month(date);
+        if month != self.month {
+            self.month = month;
+            self.sync(ctx);
+        }
+        let grid = self.grid.widget();
+        if let Some(day) = grid.days.get(grid.index_of(date)) {
+            ctx.set_focus(day.id());
+        }
+    }
+
+    /// Show the month after the current one, or the one before it.
+    fn show_other_month(&mut self, ctx: &mut EventCtx, forward: bool) {
+        let month = if forward {
+            self.month.checked_add_months(Months::new(1))
+        } else {
+            self.month.checked_sub_months(Months::new(1))
+        };
+        if let Some(month) = month {
+            self.month = month;
+            self.sync(ctx);
+        }
+    }
+
+    fn grid_origin() -> Point {
+        Point::new(0., HEADER_HEIGHT)
+    }
+}
+
+impl Selection {
+    /// The selected date, or the start of the selected range.
+    fn start(self) -> Option<NaiveDate> {
+        match self {
+            Self::Date(date) => date,
+            Self::Range(start, _) => start,
+        }
+    }
+
+    fn highlight(self, date: NaiveDate) -> Highlight {
+        match self {
+            Self::Date(selected) if selected == Some(date) => Highlight::Selected,
+            Self::Range(start, end) if start == Some(date) || end == Some(date) => {
+                Highlight::Selected
+            }
+            Self::Range(Some(start), Some(end)) if start < date && date < end => Highlight::InRange,
+            _ => Highlight::None,
+        }
+    }
+}
+
+fn range_selection(range: Option<RangeInclusive<NaiveDate>>) -> Selection {
+    match range {
+        Some(range) => Selection::Range(Some(*range.start()), Some(*range.end())),
+        None => Selection::Range(None, None),
+    }
+}
+
+impl MonthGrid {
+    fn new(month: NaiveDate, week_start: Weekday, selection: Selection) -> Self {
+        let days = day_states(month, week_start, selection)
+            .enumerate()
+            .map(|(index, state)| {
+                WidgetPod::new(Day {
+                    index,
+                    state,
+                    text: day_text(state),
+                })
+            })
+            .collect();
+        Self {
+            week_start,
+            weekday_labels: weekday_labels(week_start),
+            days,
+        }
+    }
+
+    /// The index of the day showing `date`, which is out of bounds if it isn't shown.
+    fn index_of(&self, date: NaiveDate) -> usize {
+        let first = self.days[0].widget().state.date;
+        usize::try_from(date.signed_duration_since(first).num_days()).unwrap_or(usize::MAX)
+    }
+
+    /// The index of the day at `pos`, relative to the grid, if any.
+    fn day_at(&self, pos: Point) -> Option<usize> {
+        let x = pos.x / CELL_SIZE;
+        let y = (pos.y - WEEKDAY_HEIGHT) / CELL_SIZE;
+        if x < 0. || y < 0. || x >= 7. || y >= WEEKS as f64 {
+            return None;
+        }
+        Some(y as usize * 7 + x as usize)
+    }
+
+    /// The index of the focused day, if any.
+    fn focused_day(&self) -> Option<usize> {
+        self.days.iter().position(|day| day.has_focus())
+    }
+}
+
+/// The first day of the month of `date`.
+fn first_of_month(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).unwrap()
+}
+
+/// The current date, in UTC.
+fn today() -> NaiveDate {
+    let days = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs() / (24 * 60 * 60));
+    let epoch = NaiveDate::from_ymd_opt(1970, 1, 1).unwrap();
+    epoch.checked_add_days(Days::new(days)).unwrap_or(epoch)
+}
+
+fn month_name(date: NaiveDate) -> &'static str {
+    Month::try_from(date.month() as u8).unwrap().name()
+}
+
+fn month_title(month: NaiveDate) -> String {
+    format!("{} {}", month_name(month), month.year())
+}
+
+/// The states of the days of the grid showing `month`, starting with the first day of
+/// its first week.
+fn day_states(
+    month: NaiveDate,
+    week_start: Weekday,
+    selection: Selection,
+) -> impl Iterator<Item = DayState> {
+    let offset =
+        (month.weekday().num_days_from_monday() + 7 - week_start.num_days_from_monday()) % 7;
+    let first = month
+        .checked_sub_days(Days::new(offset.into()))
+        .unwrap_or(month);
+    let today = today();
+    (0..WEEKS as u64 * 7).map(move |idx| {
+        let date = first
+            .checked_add_days(Days::new(idx))
+            .unwrap_or(NaiveDate::MAX);
+        DayState {
+            date,
+            in_month: first_of_month(date) == month,
+            today: date == today,
+            highlight: selection.highlight(date),
+        }
+    })
+}
+
+fn day_text(state: DayState) -> TextLayout<ArcStr> {
+    TextLayout::new(
+        state.date.day().to_string().into(),
+        theme::TEXT_SIZE_NORMAL as f32,
+    )
+}
+
+fn day_text_color(theme: &Theme, state: DayState) -> crate::Color {
+    if state.in_month {
+        theme.on_surface
+    } else {
+        theme.on_surface_disabled
+    }
+}
+
+/// The two letter names of the days of the week, starting with `week_start`.
+fn weekday_labels(week_start: Weekday) -> Vec<TextLayout<ArcStr>> {
+    let mut weekday = week_start;
+    (0..7)
+        .map(|_| {
+            let name = &WEEKDAY_NAMES[weekday.num_days_from_monday() as usize][..2];
+            weekday = weekday.succ();
+            TextLayout::new(name.into(), theme::TEXT_SIZE_NORMAL as f32 * 0.8)
+        })
+        .collect()
+}
+
+/// The name of `date` for accessibility, such as "Friday 15 March 2024".
+fn date_name(date: NaiveDate) -> String {
+    format!(
+        "{} {} {} {}",
+        WEEKDAY_NAMES[date.weekday().num_days_from_monday() as usize],
+        date.day(),
+        month_name(date),
+        date.year()
+    )
+}
+
+/// Draw `text` centered in `rect`, in `color`.
+///
+/// The color comes from the theme, which can change without a new layout, so the text
+/// is rebuilt here if needed.
+fn draw_centered(
+    ctx: &mut PaintCtx,
+    scene: &mut Scene,
+    text: &mut TextLayout<ArcStr>,
+    color: crate::Color,
+    rect: Rect,
+) {
+    text.set_brush(color);
+    if text.needs_rebuild() {
+        text.rebuild(ctx.font_ctx());
+    }
+    let offset = (rect.size().to_vec2() - text.size().to_vec2()) / 2.0;
+    text.draw(scene, rect.origin() + offset);
+}
+
+impl WidgetMut<'_, DatePicker> {
+    /// Select `date`, making this a picker for a single date.
+    ///
+    /// The month of `date` is shown.
+    pub fn set_date(&mut self, date: Option<NaiveDate>) {
+        self.set_selection(Selection::Date(date));
+    }
+
+    /// Select `range`, making this a picker for a range of dates.
+    ///
+    /// The month of the start of `range` is shown. This replaces a range the user is
+    /// picking.
+    pub fn set_range(&mut self, range: Option<RangeInclusive<NaiveDate>>) {
+        self.set_selection(range_selection(range));
+    }
+
+    /// Set the first day of the week.
+    pub fn set_week_start(&mut self, week_start: Weekday) {
+        self.widget.week_start = week_start;
+        self.sync();
+    }
+
+    fn set_selection(&mut self, selection: Selection) {
+        if selection == self.widget.selection {
+            return;
+        }
+        self.widget.selection = selection;
+        if let Some(start) = selection.start() {
+            self.widget.month = first_of_month(start);
+        }
+        self.sync();
+    }
+
+    fn sync(&mut self) {
+        let picker = &mut *self.widget;
+        picker.title.set_text(month_title(picker.month).into());
+        self.ctx
+            .get_mut(&mut picker.grid)
+            .show(picker.month, picker.week_start, picker.selection);
+        self.ctx.request_layout();
+        self.ctx.request_accessibility_update();
+    }
+}
+
+impl WidgetMut<'_, MonthGrid> {
+    /// Show the days of `month`, highlighting `selection`.
+    fn show(&mut self, month: NaiveDate, week_start: Weekday, selection: Selection) {
+        let grid = &mut *self.widget;
+        if grid.week_start != week_start {
+            grid.week_start = week_start;
+            grid.weekday_labels = weekday_labels(week_start);
+            self.ctx.request_layout();
+        }
+        for (day, state) in grid
+            .days
+            .iter_mut()
+            .zip(day_states(month, week_start, selection))
+        {
+            self.ctx.get_mut(day).set_state(state);
+        }
+    }
+}
+
+impl WidgetMut<'_, Day> {
+    fn set_state(&mut self, state: DayState) {
+        let day = &mut *self.widget;
+        if day.state == state {
+            return;
+        }
+        if day.state.date.day() != state.date.day() || day.state.in_month != state.in_month {
+            day.text = day_text(state);
+            self.ctx.request_layout();
+        }
+        day.state = state;
+        self.ctx.request_paint();
+        self.ctx.request_accessibility_update();
+    }
+}
+
+impl Widget for DatePicker {
+    fn on_pointer_event(&mut self, ctx: &mut EventCtx, event: &PointerEvent) {
+        self.previous.on_pointer_event(ctx, event);
+        self.next.on_pointer_event(ctx, event);
+        self.grid.on_pointer_event(ctx, event);
+        if ctx.is_disabled() {
+            return;
+        }
+
+        if let PointerEvent::PointerDown(MouseButton::Left, state) = event {
+            let pos =
+                Point::new(state.position.x, state.position.y) - ctx.window_origin().to_vec2();
+            if self.previous.layout_rect().contains(pos) {
+                self.show_other_month(ctx, false);
+                ctx.set_handled();
+            } else if self.next.layout_rect().contains(pos) {
+                self.show_other_month(ctx, true);
+                ctx.set_handled();
+            } else if let Some(idx) = self
+                .grid
+                .widget()
+                .day_at(pos - Self::grid_origin().to_vec2())
+            {
+                let date = self.grid.widget().days[idx].widget().state.date;
+                self.pick(ctx, date);
+                self.focus_date(ctx, date);
+                ctx.set_handled();
+            }
+        }
+    }
+
+    fn on_text_event(&mut self, ctx: &mut EventCtx, event: &TextEvent) {
+        self.previous.on_text_event(ctx, event);
+        self.next.on_text_event(ctx, event);
+        self.grid.on_text_event(ctx, event);
+        if ctx.is_handled() || ctx.is_disabled() {
+            return;
+        }
+        let TextEvent::KeyboardKey(key_event, _) = event else {
+            return;
+        };
+        if !key_event.state.is_pressed() {
+            return;
+        }
+        let activate = matches!(
+            key_event.logical_key,
+            Key::Named(NamedKey::Enter | NamedKey::Space)
+        );
+
+        if self.previous.has_focus() || self.next.has_focus() {
+            if activate {
+                self.show_other_month(ctx, self.next.has_focus());
+                ctx.set_handled();
+            }
+            return;
+        }
+        let Some(idx) = self.grid.widget().focused_day() else {
+            return;
+        };
+        let date = self.grid.widget().days[idx].widget().state.date;
+        if activate {
+            self.pick(ctx, date);
+            ctx.set_handled();
+            return;
+        }
+        let target = match &key_event.logical_key {
+            Key::Named(NamedKey::ArrowLeft) => date.checked_sub_days(Days::new(1)),
+            Key::Named(NamedKey::ArrowRight) => date.checked_add_days(Days::new(1)),
+            Key::Named(NamedKey::ArrowUp) => date.checked_sub_days(Days::new(7)),
+            Key::Named(NamedKey::ArrowDown) => date.checked_add_days(Days::new(7)),
+            Key::Named(NamedKey::PageUp) => date.checked_sub_months(Months::new(1)),
+            Key::Named(NamedKey::PageDown) => date.checked_add_months(Months::new(1)),
+            _ => return,
+        };
+        if let Some(target) = target {
+            self.focus_date(ctx, target);
+        }
+        ctx.set_handled();
+    }
+
+    fn on_access_event(&mut self, ctx: &mut EventCtx, event: &AccessEvent) {
+        self.previous.on_access_event(ctx, event);
+        self.next.on_access_event(ctx, event);
+        self.grid.on_access_event(ctx, event);
+        if ctx.is_disabled() || event.action != accesskit::Action::Default {
+            return;
+        }
+        if event.target == self.previous.id() || event.target == self.next.id() {
+            self.show_other_month(ctx, event.target == self.next.id());
+        } else if let Some(day) = self
+            .grid
+            .widget()
+            .days
+            .iter()
+            .find(|day| day.id() == event.target)
+        {
+            let date = day.widget().state.date;
+            self.pick(ctx, date);
+        }
+    }
+
+    fn on_status_change(&mut self, _ctx: &mut LifeCycleCtx, _event: &StatusChange) {}
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle) {
+        self.previous.lifecycle(ctx, event);
+        self.next.lifecycle(ctx, event);
+        self.grid.lifecycle(ctx, event);
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints) -> Size {
+        let width = 7. * CELL_SIZE;
+        let button_bc = BoxConstraints::tight(Size::new(CELL_SIZE, HEADER_HEIGHT));
+        self.previous.layout(ctx, &button_bc);
+        self.next.layout(ctx, &button_bc);
+        ctx.place_child(&mut self.previous, Point::ORIGIN);
+        ctx.place_child(&mut self.next, Point::new(width - CELL_SIZE, 0.));
+
+        if self.title.needs_rebuild() {
+            self.title.rebuild(ctx.font_ctx());
+        }
+
+        let grid_size = Size::new(width, WEEKDAY_HEIGHT + WEEKS as f64 * CELL_SIZE);
+        self.grid.layout(ctx, &BoxConstraints::tight(grid_size));
+        ctx.place_child(&mut self.grid, Self::grid_origin());
+
+        let size = bc.constrain(Size::new(width, HEADER_HEIGHT + grid_size.height));
+        trace!("Computed size: {}", size);
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, scene: &mut Scene) {
+        let header = Rect::new(0., 0., ctx.size().width, HEADER_HEIGHT);
+        let color = ctx.theme().on_surface;
+        draw_centered(ctx, scene, &mut self.title, color, header);
+
+        self.previous.paint(ctx, scene);
+        self.next.paint(ctx, scene);
+        self.grid.paint(ctx, scene);
+    }
+
+    fn accessibility_role(&self) -> Role {
+        Role::Group
+    }
+
+    fn accessibility(&mut self, ctx: &mut AccessCtx) {
+        ctx.current_node().set_name(month_title(self.month));
+        self.previous.accessibility(ctx);
+        self.next.accessibility(ctx);
+        self.grid.accessibility(ctx);
+    }
+
+    fn children(&self) -> SmallVec<[WidgetRef<'_, dyn Widget>; 16]> {
+        smallvec![
+            self.previous.as_dyn(),
+            self.next.as_dyn(),
+            self.grid.as_dyn()
+        ]
+    }
+
+    fn make_trace_span(&self) -> Span {
+        trace_span!("DatePicker")
+    }
+
+    fn get_debug_text(&self) -> Option<String> {
+        match self.selection {
+            Selection::Date(date) => date.map(|date| date.to_string()),
+            Selection::Range(start, end) => Some(format!("{start:?}..={end:?}")),
+        }
+    }
+}
+
+impl Widget for MonthGrid {
+    fn on_pointer_event(&mut self, ctx: &mut EventCtx, event: &PointerEvent) {
+        for day in &mut self.days {
+            day.on_pointer_event(ctx, event);
+        }
+    }
+
+    fn on_text_event(&mut self, ctx: &mut EventCtx, event: &TextEvent) {
+        for day in &mut self.days {
+            day.on_text_event(ctx, event);
+        }
+    }
+
+    fn on_access_event(&mut self, ctx: &mut EventCtx, event: &AccessEvent) {
+        for day in &mut self.days {
+            day.on_access_event(ctx, event);
+        }
+    }
+
+    fn on_status_change(&mut self, _ctx: &mut LifeCycleCtx, _event: &StatusChange) {}
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle) {
+        for day in &mut self.days {
+            day.lifecycle(ctx, event);
+        }
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints) -> Size {
+        for label in &mut self.weekday_labels {
+            if label.needs_rebuild() {
+                label.rebuild(ctx.font_ctx());
+            }
+        }
+        let cell_bc = BoxConstraints::tight(Size::new(CELL_SIZE, CELL_SIZE));
+        for (idx, day) in self.days.iter_mut().enumerate() {
+            day.layout(ctx, &cell_bc);
+            let origin = Point::new(
+                (idx % 7) as f64 * CELL_SIZE,
+                WEEKDAY_HEIGHT + (idx / 7) as f64 * CELL_SIZE,
+            );
+            ctx.place_child(day, origin);
+        }
+        bc.constrain(Size::new(
+            7. * CELL_SIZE,
+            WEEKDAY_HEIGHT + WEEKS as f64 * CELL_SIZE,
+        ))
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, scene: &mut Scene) {
+        let color = ctx.theme().on_surface_disabled;
+        for (idx, label) in self.weekday_labels.iter_mut().enumerate() {
+            let x = idx as f64 * CELL_SIZE;
+            draw_centered(
+                ctx,
+                scene,
+                label,
+                color,
+                Rect::new(x, 0., x + CELL_SIZE, WEEKDAY_HEIGHT),
+            );
+        }
+        for day in &mut self.days {
+            day.paint(ctx, scene);
+        }
+    }
+
+    fn accessibility_role(&self) -> Role {
+        Role::Grid
+    }
+
+    fn accessibility(&mut self, ctx: &mut AccessCtx) {
+        let node = ctx.current_node();
+        node.set_row_count(WEEKS);
+        node.set_column_count(7);
+        for day in &mut self.days {
+            day.accessibility(ctx);
+        }
+    }
+
+    fn children(&self) -> SmallVec<[WidgetRef<'_, dyn Widget>; 16]> {
+        self.days.iter().map(|day| day.as_dyn()).collect()
+    }
+
+    fn make_trace_span(&self) -> Span {
+        trace_span!("MonthGrid")
+    }
+}
+
+impl Widget for Day {
+    fn on_pointer_event(&mut self, _ctx: &mut EventCtx, _event: &PointerEvent) {}
+
+    fn on_text_event(&mut self, _ctx: &mut EventCtx, _event: &TextEvent) {}
+
+    fn on_access_event(&mut self, _ctx: &mut EventCtx, _event: &AccessEvent) {}
+
+    fn on_status_change(&mut self, ctx: &mut LifeCycleCtx, _event: &StatusChange) {
+        ctx.request_paint();
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle) {
+        match event {
+            LifeCycle::WidgetAdded => {
+                ctx.set_cursor(&CursorIcon::Pointer);
+            }
+            LifeCycle::BuildFocusChain => {
+                ctx.register_for_focus();
+            }
+            _ => {}
+        }
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints) -> Size {
+        if self.text.needs_rebuild() {
+            self.text.rebuild(ctx.font_ctx());
+        }
+        bc.constrain(Size::new(CELL_SIZE, CELL_SIZE))
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, scene: &mut Scene) {
+        let rect = ctx.size().to_rect();
+        let shape = rect.inset(-2.).to_rounded_rect(4.);
+        let theme = ctx.theme();
+        match self.state.highlight {
+            Highlight::Selected => fill_color(scene, &shape, theme.highlight),
+            Highlight::InRange => fill_color(
+                scene,
+                &rect.inset((0., -2.)),
+                theme.highlight.with_alpha_factor(0.35),
+            ),
+            Highlight::None if ctx.is_hot() && !ctx.is_disabled() => {
+                fill_color(scene, &shape, theme.field[0]);
+            }
+            Highlight::None => {}
+        }
+        if self.state.today {
+            stroke(scene, &shape, theme.border_hover, 1.);
+        }
+        if ctx.is_focused() {
+            stroke(scene, &shape, theme.primary, 2.);
+        }
+        let color = day_text_color(theme, self.state);
+        draw_centered(ctx, scene, &mut self.text, color, rect);
+    }
+
+    fn accessibility_role(&self) -> Role {
+        Role::Cell
+    }
+
+    fn accessibility(&mut self, ctx: &mut AccessCtx) {
+        let node = ctx.current_node();
+        node.set_name(date_name(self.state.date));
+        node.set_row_index(self.index / 7);
+        node.set_column_index(self.index % 7);
+        node.set_selected(self.state.highlight != Highlight::None);
+        node.add_action(accesskit::Action::Default);
+    }
+
+    fn children(&self) -> SmallVec<[WidgetRef<'_, dyn Widget>; 16]> {
+        SmallVec::new()
+    }
+
+    fn make_trace_span(&self) -> Span {
+        trace_span!("Day")
+    }
+
+    fn get_debug_text(&self) -> Option<String> {
+        Some(self.state.date.to_string())
+    }
+}
+
+impl Widget for MonthButton {
+    fn on_pointer_event(&mut self, _ctx: &mut EventCtx, _event: &PointerEvent) {}
+
+    fn on_text_event(&mut self, _ctx: &mut EventCtx, _event: &TextEvent) {}
+
+    fn on_access_event(&mut self, _ctx: &mut EventCtx, _event: &AccessEvent) {}
+
+    fn on_status_change(&mut self, ctx: &mut LifeCycleCtx, _event: &StatusChange) {
+        ctx.request_paint();
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle) {
+        match event {
+            LifeCycle::WidgetAdded => {
+                ctx.set_cursor(&CursorIcon::Pointer);
+            }
+            LifeCycle::BuildFocusChain => {
+                ctx.register_for_focus();
+            }
+            _ => {}
+        }
+    }
+
+    fn layout(&mut self, _ctx: &mut LayoutCtx, bc: &BoxConstraints) -> Size {
+        bc.constrain(Size::new(CELL_SIZE, HEADER_HEIGHT))
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, scene: &mut Scene) {
+        let rect = ctx.size().to_rect();
+        let shape = rect.inset(-4.).to_rounded_rect(4.);
+        let theme = ctx.theme();
+        if ctx.is_hot() && !ctx.is_disabled() {
+            fill_color(scene, &shape, theme.field[0]);
+        }
+        if ctx.is_focused() {
+            stroke(scene, &shape, theme.primary, 2.);
+        }
+
+        // A chevron pointing towards the other month.
+        let center = rect.center();
+        let direction = if self.forward { 1. } else { -1. };
+        let mut chevron = BezPath::new();
+        chevron.move_to((center.x - 2. * direction, center.y - 5.));
+        chevron.line_to((center.x + 3. * direction, center.y));
+        chevron.line_to((center.x - 2. * direction, center.y + 5.));
+        let color = if ctx.is_disabled() {
+            theme.on_surface_disabled
+        } else {
+            theme.on_surface
+        };
+        stroke(scene, &chevron, color, 2.);
+    }
+
+    fn accessibility_role(&self) -> Role {
+        Role::Button
+    }
+
+    fn accessibility(&mut self, ctx: &mut AccessCtx) {
+        let node = ctx.current_node();
+        node.set_name(if self.forward {
+            "Next month"
+        } else {
+            "Previous month"
+        });
+        node.add_action(accesskit::Action::Default);
+    }
+
+    fn children(&self) -> SmallVec<[WidgetRef<'_, dyn Widget>; 16]> {
+        SmallVec::new()
+    }
+
+    fn make_trace_span(&self) -> Span {
+        trace_span!("MonthButton")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{widget_ids, TestHarness, TestWidgetExt};
+    use crate::widget::Flex;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    /// The center of the day at `idx` in the grid, relative to the picker.
+    fn day_center(idx: usize) -> kurbo::Vec2 {
+        kurbo::Vec2::new(
+            (idx % 7) as f64 * CELL_SIZE + CELL_SIZE / 2.,
+            HEADER_HEIGHT + WEEKDAY_HEIGHT + (idx / 7) as f64 * CELL_SIZE + CELL_SIZE / 2.,
+        )
+    }
+
+    fn click(harness: &mut TestHarness, pos: Point) {
+        harness.mouse_move(pos);
+        harness.mouse_button_press(MouseButton::Left);
+        harness.mouse_button_release(MouseButton::Left);
+    }
+
+    #[test]
+    fn click_picks_day_and_arrows_change_month() {
+        let [picker_id] = widget_ids();
+        let widget =
+            Flex::column().with_child(DatePicker::new(Some(date(2024, 3, 15))).with_id(picker_id));
+
+        let mut harness = TestHarness::create(widget);
+        let rect = harness.get_widget(picker_id).state().window_layout_rect();
+
+        // March 2024 starts on a Friday, so with weeks starting on Monday, the 20th is at
+        // index 4 + 19.
+        click(&mut harness, rect.origin() + day_center(23));
+        assert_eq!(
+            harness.pop_action(),
+            Some((Action::DateSelected(date(2024, 3, 20)), picker_id))
+        );
+
+        click(&mut harness, Point::new(rect.x1 - 4., rect.y0 + 4.));
+        assert_eq!(harness.pop_action(), None);
+        let picker = harness.get_widget(picker_id);
+        let picker = picker.downcast::<DatePicker>().unwrap();
+        assert_eq!(picker.month(), date(2024, 4, 1));
+        assert_eq!(picker.selected_date(), Some(date(2024, 3, 20)));
+
+        // The days are cells of a grid.
+        let grid = harness.get_widget(picker_id).children()[2];
+        assert_eq!(grid.accessibility_role(), Role::Grid);
+        assert_eq!(grid.children().len(), 42);
+        assert_eq!(grid.children()[0].accessibility_role(), Role::Cell);
+    }
+
+    #[test]
+    fn range_is_picked_in_either_order() {
+        let [picker_id] = widget_ids();
+        let widget = Flex::column().with_child(
+            DatePicker::new_range(None)
+                .with_month(date(2024, 3, 1))
+                .with_id(picker_id),
+        );
+
+        let mut harness = TestHarness::create(widget);
+        let origin = harness
+            .get_widget(picker_id)
+            .state()
+            .window_layout_rect()
+            .origin();
+
+        click(&mut harness, origin + day_center(23));
+        assert_eq!(harness.pop_action(), None);
+        click(&mut harness, origin + day_center(8));
+        assert_eq!(
+            harness.pop_action(),
+            Some((
+                Action::DateRangeSelected(date(2024, 3, 5)..=date(2024, 3, 20)),
+                picker_id
+            ))
+        );
+    }
+}
@@ -0,0 +1,176 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! A line separating other widgets.
+
+use accesskit::Role;
+use smallvec::SmallVec;
+use tracing::{trace, trace_span, Span};
+use vello::Scene;
+
+use crate::paint_scene_helpers::fill_color;
+use crate::widget::{Axis, WidgetMut, WidgetRef};
+use crate::{
+    theme, AccessCtx, AccessEvent, BoxConstraints, Color, EventCtx, LayoutCtx, LifeCycle,
+    LifeCycleCtx, PaintCtx, PointerEvent, Rect, Size, StatusChange, TextEvent, Widget,
+};
+
+/// A horizontal or vertical line separating other widgets.
+///
+/// A divider is `thickness` across, and as long as the minimum of its constraints along
+/// its axis. In a [`Flex`](crate::widget::Flex) container, a divider is always aligned
+/// with [`CrossAxisAlignment::Fill`](crate::widget::CrossAxisAlignment::Fill), so a
+/// horizontal divider in a column spans the width of the column, and a vertical divider
+/// in a row spans its height.
+pub struct Divider {
+    axis: Axis,
+    thickness: f64,
+    color: Color,
+    inset: f64,
+}
+
+impl Divider {
+    /// Create a divider whose line runs along `axis`.
+    pub fn new(axis: Axis) -> Self {
+        Self {
+            axis,
+            thickness: 1.0,
+            color: theme::DIVIDER_COLOR,
+            inset: 0.0,
+        }
+    }
+
+    /// Create a divider whose line runs horizontally.
+    pub fn horizontal() -> Self {
+        Self::new(Axis::Horizontal)
+    }
+
+    /// Create a divider whose line runs vertically.
+    pub fn vertical() -> Self {
+        Self::new(Axis::Vertical)
+    }
+
+    /// Builder-style method for setting the thickness of the line.
+    pub fn with_thickness(mut self, thickness: f64) -> Self {
+        self.thickness = thickness.max(0.0);
+        self
+    }
+
+    /// Builder-style method for setting the color of the line.
+    pub fn with_color(mut self, color: impl Into<Color>) -> Self {
+        self.color = color.into();
+        self
+    }
+
+    /// Builder-style method for setting how far the line stops short of both ends of
+    /// the divider.
+    pub fn with_inset(mut self, inset: f64) -> Self {
+        self.inset = inset.max(0.0);
+        self
+    }
+
+    /// The axis the line runs along.
+    pub fn axis(&self) -> Axis {
+        self.axis
+    }
+}
+
+impl WidgetMut<'_, Divider> {
+    /// Set the thickness of the line.
+    pub fn set_thickness(&mut self, thickness: f64) {
+        self.widget.thickness = thickness.max(0.0);
+        self.ctx.request_layout();
+    }
+
+    /// Set the color of the line.
+    pub fn set_color(&mut self, color: impl Into<Color>) {
+        self.widget.color = color.into();
+        self.ctx.request_paint();
+    }
+
+    /// Set how far the line stops short of both ends of the divider.
+    pub fn set_inset(&mut self, inset: f64) {
+        self.widget.inset = inset.max(0.0);
+        self.ctx.request_paint();
+    }
+}
+
+impl Widget for Divider {
+    fn on_pointer_event(&mut self, _ctx: &mut EventCtx, _event: &PointerEvent) {}
+
+    fn on_text_event(&mut self, _ctx: &mut EventCtx, _event: &TextEvent) {}
+
+    fn on_access_event(&mut self, _ctx: &mut EventCtx, _event: &AccessEvent) {}
+
+    fn on_status_change(&mut self, _ctx: &mut LifeCycleCtx, _event: &StatusChange) {}
+
+    fn lifecycle(&mut self, _ctx: &mut LifeCycleCtx, _event: &LifeCycle) {}
+
+    fn layout(&mut self, _ctx: &mut LayoutCtx, bc: &BoxConstraints) -> Size {
+        let len = self.axis.major(bc.min());
+        let size = bc.constrain(self.axis.pack(len, self.thickness));
+        trace!("Computed size: {}", size);
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, scene: &mut Scene) {
+        let size = ctx.size();
+        let len = self.axis.major(size);
+        if len <= 2. * self.inset {
+            return;
+        }
+        // The line is centered across the divider, in case it was made thicker.
+        let start = (self.axis.minor(size) - self.thickness) / 2.;
+        let (x0, y0) = self.axis.pack(self.inset, start);
+        let (x1, y1) = self.axis.pack(len - self.inset, start + self.thickness);
+        fill_color(scene, &Rect::new(x0, y0, x1, y1), self.color);
+    }
+
+    fn accessibility_role(&self) -> Role {
+        Role::Splitter
+    }
+
+    fn accessibility(&mut self, _ctx: &mut AccessCtx) {}
+
+    fn children(&self) -> SmallVec<[WidgetRef<'_, dyn Widget>; 16]> {
+        SmallVec::new()
+    }
+
+    fn make_trace_span(&self) -> Span {
+        trace_span!("Divider")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{widget_ids, TestHarness};
+    use crate::widget::{Flex, SizedBox};
+
+    #[test]
+    fn dividers_span_flex_cross_axis() {
+        let [horizontal, vertical] = widget_ids();
+        // The root widget is given the size of the window, so the dividers are in a
+        // column inside it.
+        let widget = Flex::column().with_child(
+            Flex::column()
+                .with_child(SizedBox::empty().width(100.0).height(10.0))
+                .with_child_id(Divider::horizontal().with_thickness(2.0), horizontal)
+                .with_child(
+                    Flex::row()
+                        .with_child(SizedBox::empty().width(10.0).height(30.0))
+                        .with_child_id(Divider::vertical(), vertical)
+                        .with_child(SizedBox::empty().width(10.0).height(30.0)),
+                ),
+        );
+
+        let harness = TestHarness::create(widget);
+
+        // The divider spans the widest sibling, without making the column any wider.
+        let horizontal_rect = harness.get_widget(horizontal).state().layout_rect();
+        assert_eq!(horizontal_rect.size(), Size::new(100.0, 2.0));
+
+        let vertical_rect = harness.get_widget(vertical).state().layout_rect();
+        assert_eq!(vertical_rect.size(), Size::new(1.0, 30.0));
+    }
+}
@@ -0,0 +1,238 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! A widget which accepts files dropped from other applications.
+
+use std::mem;
+use std::path::PathBuf;
+
+use accesskit::Role;
+use smallvec::{smallvec, SmallVec};
+use tracing::{trace, trace_span, Span};
+use vello::Scene;
+
+use crate::paint_scene_helpers::{fill_color, stroke};
+use crate::widget::{WidgetMut, WidgetPod, WidgetRef};
+use crate::{
+    theme, AccessCtx, AccessEvent, Action, BoxConstraints, Color, EventCtx, LayoutCtx, LifeCycle,
+    LifeCycleCtx, PaintCtx, Point, PointerEvent, Size, StatusChange, TextEvent, Widget,
+};
+
+const HIGHLIGHT_BORDER_WIDTH: f64 = 2.;
+
+/// A container which accepts files dragged onto its child from other applications.
+///
+/// While files are dragged over it, the child is highlighted. When they are dropped,
+/// an [`Action::FilesDropped`] is submitted with all of their paths.
+///
+/// If drop targets are nested, the innermost one under the pointer receives the files.
+pub struct DropTarget<W: Widget> {
+    child: WidgetPod<W>,
+    highlight_color: Color,
+    is_hovered: bool,
+    /// The files dropped since the last animation frame, which are submitted together.
+    dropped: Vec<PathBuf>,
+}
+
+impl<W: Widget> DropTarget<W> {
+    /// Create a new drop target around `child`.
+    pub fn new(child: W) -> Self {
+        Self::from_child_pod(WidgetPod::new(child))
+    }
+
+    /// Create a new drop target around a child which has already been wrapped in a [`WidgetPod`].
+    pub fn from_child_pod(child: WidgetPod<W>) -> Self {
+        Self {
+            child,
+            highlight_color: theme::PRIMARY_LIGHT,
+            is_hovered: false,
+            dropped: Vec::new(),
+        }
+    }
+
+    /// Set the color used to highlight the child while files are dragged over it.
+    pub fn with_highlight_color(mut self, color: impl Into<Color>) -> Self {
+        self.highlight_color = color.into();
+        self
+    }
+
+    /// Returns `true` if files are currently dragged over this widget.
+    pub fn is_hovered(&self) -> bool {
+        self.is_hovered
+    }
+}
+
+impl<W: Widget> WidgetMut<'_, DropTarget<W>> {
+    pub fn child_mut(&mut self) -> WidgetMut<'_, W> {
+        self.ctx.get_mut(&mut self.widget.child)
+    }
+
+    /// Set the color used to highlight the child while files are dragged over it.
+    pub fn set_highlight_color(&mut self, color: impl Into<Color>) {
+        self.widget.highlight_color = color.into();
+        self.ctx.request_paint();
+    }
+}
+
+impl<W: Widget> Widget for DropTarget<W> {
+    fn on_pointer_event(&mut self, ctx: &mut EventCtx, event: &PointerEvent) {
+        self.child.on_pointer_event(ctx, event);
+        // A drop target inside the child takes precedence.
+        let inner_target = ctx.is_handled();
+        match event {
+            PointerEvent::HoverFile(_, _) => {
+                self.is_hovered = !inner_target;
+                ctx.request_paint();
+                ctx.set_handled();
+            }
+            PointerEvent::DropFile(path, _) => {
+                self.is_hovered = false;
+                ctx.request_paint();
+                if !inner_target {
+                    trace!("File {:?} dropped on {:?}", path, ctx.widget_id());
+                    if self.dropped.is_empty() {
+                        // winit sends one event per file, so we wait for the next
+                        // frame to submit all of them at once.
+                        ctx.request_anim_frame();
+                    }
+                    self.dropped.push(path.clone());
+                    ctx.set_handled();
+                }
+            }
+            PointerEvent::HoverFileCancel(_) => {
+                self.is_hovered = false;
+                ctx.request_paint();
+            }
+            _ => (),
+        }
+    }
+
+    fn on_text_event(&mut self, ctx: &mut EventCtx, event: &TextEvent) {
+        self.child.on_text_event(ctx, event);
+    }
+
+    fn on_access_event(&mut self, ctx: &mut EventCtx, event: &AccessEvent) {
+        self.child.on_access_event(ctx, event);
+    }
+
+    fn on_status_change(&mut self, ctx: &mut LifeCycleCtx, event: &StatusChange) {
+        if let StatusChange::HotChanged(false) = event {
+            if self.is_hovered {
+                self.is_hovered = false;
+                ctx.request_paint();
+            }
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle) {
+        self.child.lifecycle(ctx, event);
+        if let LifeCycle::AnimFrame(_) = event {
+            if !self.dropped.is_empty() {
+                ctx.submit_action(Action::FilesDropped(mem::take(&mut self.dropped)));
+            }
+        }
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints) -> Size {
+        let size = self.child.layout(ctx, bc);
+        ctx.place_child(&mut self.child, Point::ORIGIN);
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, scene: &mut Scene) {
+        self.child.paint(ctx, scene);
+
+        if self.is_hovered {
+            let rect = ctx.size().to_rect().inset(-HIGHLIGHT_BORDER_WIDTH / 2.0);
+            fill_color(scene, &rect, self.highlight_color.with_alpha_factor(0.2));
+            stroke(scene, &rect, self.highlight_color, HIGHLIGHT_BORDER_WIDTH);
+        }
+    }
+
+    fn accessibility_role(&self) -> Role {
+        Role::GenericContainer
+    }
+
+    fn accessibility(&mut self, ctx: &mut AccessCtx) {
+        self.child.accessibility(ctx);
+    }
+
+    fn children(&self) -> SmallVec<[WidgetRef<'_, dyn Widget>; 16]> {
+        smallvec![self.child.as_dyn()]
+    }
+
+    fn make_trace_span(&self) -> Span {
+        trace_span!("DropTarget")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::WindowEvent;
+    use crate::testing::{widget_ids, TestHarness};
+    use crate::widget::{Flex, SizedBox};
+
+    fn is_hovered(harness: &TestHarness, id: crate::WidgetId) -> bool {
+        harness
+            .get_widget(id)
+            .downcast::<DropTarget<SizedBox>>()
+            .unwrap()
+            .deref()
+            .is_hovered()
+    }
+
+    #[test]
+    fn drop_files_on_target_under_pointer() {
+        let [target_id, other_id] = widget_ids();
+        let widget = Flex::column()
+            .with_child_id(
+                DropTarget::new(SizedBox::empty().width(40.).height(40.)),
+                target_id,
+            )
+            .with_child_id(
+                DropTarget::new(SizedBox::empty().width(40.).height(40.)),
+                other_id,
+            );
+
+        let mut harness = TestHarness::create(widget);
+        harness.mouse_move_to(target_id);
+        harness.hover_file("a.txt");
+        harness.hover_file("b.txt");
+        assert!(is_hovered(&harness, target_id));
+        assert!(!is_hovered(&harness, other_id));
+
+        harness.drop_file("a.txt");
+        harness.drop_file("b.txt");
+        assert!(!is_hovered(&harness, target_id));
+        assert_eq!(harness.pop_action(), None);
+
+        harness.process_window_event(WindowEvent::AnimFrame);
+        assert_eq!(
+            harness.pop_action(),
+            Some((
+                Action::FilesDropped(vec!["a.txt".into(), "b.txt".into()]),
+                target_id
+            ))
+        );
+        assert_eq!(harness.pop_action(), None);
+    }
+
+    #[test]
+    fn cancel_hover() {
+        let [target_id] = widget_ids();
+        let widget = Flex::column().with_child_id(
+            DropTarget::new(SizedBox::empty().width(40.).height(40.)),
+            target_id,
+        );
+
+        let mut harness = TestHarness::create(widget);
+        harness.mouse_move_to(target_id);
+        harness.hover_file("a.txt");
+        assert!(is_hovered(&harness, target_id));
+
+        harness.cancel_file_hover();
+        assert!(!is_hovered(&harness, target_id));
+        assert_eq!(harness.pop_action(), None);
+    }
+}
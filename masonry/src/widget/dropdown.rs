@@ -0,0 +1,527 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! A widget which lets the user pick one of several options.
+
+use accesskit::{DefaultActionVerb, Role};
+use kurbo::BezPath;
+use smallvec::SmallVec;
+use tracing::{trace, trace_span, Span};
+use vello::Scene;
+use winit::event::MouseButton;
+use winit::keyboard::{Key, NamedKey};
+
+use crate::action::Action;
+use crate::event::PointerState;
+use crate::paint_scene_helpers::{fill_color, fill_lin_gradient, stroke, UnitPoint};
+use crate::widget::{Label, WidgetMut, WidgetPod, WidgetRef};
+use crate::{
+    theme, AccessCtx, AccessEvent, ArcStr, BoxConstraints, EventCtx, Insets, LayoutCtx, LifeCycle,
+    LifeCycleCtx, PaintCtx, Point, PointerEvent, Rect, Size, StatusChange, TextEvent, Widget,
+};
+
+const LABEL_INSETS: Insets = Insets::uniform_xy(8., 2.);
+const ARROW_WIDTH: f64 = 12.;
+const ITEM_INSETS: Insets = Insets::uniform_xy(8., 2.);
+const POPUP_BORDER_WIDTH: f64 = 1.;
+
+/// A select control, which shows the current option and opens a list of all options when clicked.
+///
+/// The list is painted as an overlay below the widget. It is closed by clicking one of its
+/// options, or by clicking anywhere else.
+///
+/// When focused, the up and down arrow keys change the selection, and enter opens the list.
+/// While the list is open, the arrow keys move the highlight and enter selects the
+/// highlighted option.
+///
+/// Emits [`Action::DropdownSelected`] with the index of the newly selected option.
+pub struct Dropdown {
+    /// Shows the text of the selected option.
+    label: WidgetPod<Label>,
+    options: Vec<WidgetPod<Label>>,
+    selected: usize,
+    is_open: bool,
+    /// The option under the pointer, or picked with the arrow keys, while the list is open.
+    highlighted: Option<usize>,
+    popup_size: Size,
+}
+
+impl Dropdown {
+    /// Create a new dropdown with the given options, and the option at index `selected` selected.
+    ///
+    /// If `selected` is out of bounds, no option is shown until one is picked.
+    pub fn new(options: impl IntoIterator<Item = impl Into<ArcStr>>, selected: usize) -> Self {
+        let options: Vec<_> = options
+            .into_iter()
+            .map(|text| WidgetPod::new(Label::new(text)))
+            .collect();
+        let text = option_text(&options, selected);
+        Self {
+            label: WidgetPod::new(Label::new(text)),
+            options,
+            selected,
+            is_open: false,
+            highlighted: None,
+            popup_size: Size::ZERO,
+        }
+    }
+
+    /// The index of the selected option.
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    /// Returns `true` if the list of options is currently shown.
+    pub fn is_open(&self) -> bool {
+        self.is_open
+    }
+
+    fn option_at(&self, pos: Point) -> Option<usize> {
+        self.options.iter().position(|option| {
+            option
+                .layout_rect()
+                .inflate(ITEM_INSETS.x0, ITEM_INSETS.y0)
+                .contains(pos)
+        })
+    }
+}
+
+fn option_text(options: &[WidgetPod<Label>], idx: usize) -> ArcStr {
+    options
+        .get(idx)
+        .map(|option| option.widget().text().clone())
+        .unwrap_or_else(|| "".into())
+}
+
+impl Dropdown {
+    fn open(&mut self, ctx: &mut EventCtx) {
+        trace!("Dropdown {:?} opened", ctx.widget_id());
+        self.is_open = true;
+        self.highlighted = (self.selected < self.options.len()).then_some(self.selected);
+        for option in &mut self.options {
+            ctx.set_stashed(option, false);
+        }
+        ctx.request_layout();
+        ctx.request_accessibility_update();
+    }
+
+    fn close(&mut self, ctx: &mut EventCtx) {
+        trace!("Dropdown {:?} closed", ctx.widget_id());
+        self.is_open = false;
+        self.highlighted = None;
+        for option in &mut self.options {
+            ctx.set_stashed(option, true);
+        }
+        ctx.close_overlay();
+        ctx.request_layout();
+        ctx.request_accessibility_update();
+    }
+
+    fn select(&mut self, ctx: &mut EventCtx, idx: usize) {
+        if idx == self.selected || idx >= self.options.len() {
+            return;
+        }
+        trace!("Dropdown {:?} selected option {}", ctx.widget_id(), idx);
+        self.selected = idx;
+        let text = option_text(&self.options, idx);
+        ctx.get_mut(&mut self.label).set_text(text);
+        ctx.submit_action(Action::DropdownSelected(idx));
+        ctx.request_accessibility_update();
+    }
+
+    /// Move the highlight, or the selection if the list is closed, by `delta` options.
+    fn step(&mut self, ctx: &mut EventCtx, delta: isize) {
+        let Some(last) = self.options.len().checked_sub(1) else {
+            return;
+        };
+        let current = if self.is_open {
+            self.highlighted
+        } else {
+            (self.selected <= last).then_some(self.selected)
+        };
+        let next = match current {
+            Some(idx) => idx.saturating_add_signed(delta).min(last),
+            None if delta < 0 => last,
+            None => 0,
+        };
+        if self.is_open {
+            self.highlighted = Some(next);
+            ctx.request_paint();
+        } else {
+            self.select(ctx, next);
+        }
+    }
+}
+
+impl WidgetMut<'_, Dropdown> {
+    /// Replace the options.
+    ///
+    /// If the list is open, it is closed.
+    pub fn set_options(&mut self, options: impl IntoIterator<Item = impl Into<ArcStr>>) {
+        self.widget.is_open = false;
+        self.widget.highlighted = None;
+        self.widget.options = options
+            .into_iter()
+            .map(|text| WidgetPod::new(Label::new(text)))
+            .collect();
+        for option in &mut self.widget.options {
+            self.ctx.set_stashed(option, true);
+        }
+        let text = option_text(&self.widget.options, self.widget.selected);
+        self.ctx.get_mut(&mut self.widget.label).set_text(text);
+        self.ctx.close_overlay();
+        self.ctx.children_changed();
+        self.ctx.request_accessibility_update();
+    }
+
+    /// Set the index of the selected option.
+    ///
+    /// This doesn't emit an action.
+    pub fn set_selected(&mut self, selected: usize) {
+        self.widget.selected = selected;
+        let text = option_text(&self.widget.options, selected);
+        self.ctx.get_mut(&mut self.widget.label).set_text(text);
+        self.ctx.request_accessibility_update();
+    }
+}
+
+impl Widget for Dropdown {
+    fn on_pointer_event(&mut self, ctx: &mut EventCtx, event: &PointerEvent) {
+        let window_origin = ctx.window_origin().to_vec2();
+        let local_pos =
+            |state: &PointerState| Point::new(state.position.x, state.position.y) - window_origin;
+        match event {
+            // Clicks outside the list never reach us while it's open; they dismiss the overlay.
+            PointerEvent::PointerDown(MouseButton::Left, state) if self.is_open => {
+                if let Some(idx) = self.option_at(local_pos(state)) {
+                    self.select(ctx, idx);
+                    self.close(ctx);
+                }
+                ctx.set_handled();
+            }
+            PointerEvent::PointerMove(state) if self.is_open => {
+                if let Some(idx) = self.option_at(local_pos(state)) {
+                    if self.highlighted != Some(idx) {
+                        self.highlighted = Some(idx);
+                        ctx.request_paint();
+                    }
+                }
+            }
+            PointerEvent::PointerDown(MouseButton::Left, _)
+                if ctx.is_hot() && !ctx.is_disabled() =>
+            {
+                ctx.request_focus();
+                self.open(ctx);
+                ctx.set_handled();
+            }
+            _ => (),
+        }
+        self.label.on_pointer_event(ctx, event);
+        for option in &mut self.options {
+            option.on_pointer_event(ctx, event);
+        }
+    }
+
+    fn on_text_event(&mut self, ctx: &mut EventCtx, event: &TextEvent) {
+        if let TextEvent::KeyboardKey(key_event, _) = event {
+            if key_event.state.is_pressed() && !ctx.is_disabled() {
+                match &key_event.logical_key {
+                    Key::Named(NamedKey::ArrowUp) => {
+                        self.step(ctx, -1);
+                        ctx.set_handled();
+                    }
+                    Key::Named(NamedKey::ArrowDown) => {
+                        self.step(ctx, 1);
+                        ctx.set_handled();
+                    }
+                    Key::Named(NamedKey::Enter) if self.is_open => {
+                        if let Some(idx) = self.highlighted {
+                            self.select(ctx, idx);
+                        }
+                        self.close(ctx);
+                        ctx.set_handled();
+                    }
+                    Key::Named(NamedKey::Enter | NamedKey::Space) => {
+                        self.open(ctx);
+                        ctx.set_handled();
+                    }
+                    Key::Named(NamedKey::Escape) if self.is_open => {
+                        self.close(ctx);
+                        ctx.set_handled();
+                    }
+                    _ => (),
+                }
+            }
+        }
+        if let TextEvent::FocusChange(false) = event {
+            if self.is_open {
+                self.close(ctx);
+            }
+        }
+        self.label.on_text_event(ctx, event);
+        for option in &mut self.options {
+            option.on_text_event(ctx, event);
+        }
+    }
+
+    fn on_access_event(&mut self, ctx: &mut EventCtx, event: &AccessEvent) {
+        if event.target == ctx.widget_id() {
+            match event.action {
+                accesskit::Action::Default | accesskit::Action::Expand if !self.is_open => {
+                    self.open(ctx);
+                }
+                accesskit::Action::Default | accesskit::Action::Collapse if self.is_open => {
+                    self.close(ctx);
+                }
+                _ => {}
+            }
+        }
+        self.label.on_access_event(ctx, event);
+        for option in &mut self.options {
+            option.on_access_event(ctx, event);
+        }
+    }
+
+    fn on_status_change(&mut self, ctx: &mut LifeCycleCtx, _event: &StatusChange) {
+        ctx.request_paint();
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle) {
+        self.label.lifecycle(ctx, event);
+        for option in &mut self.options {
+            option.lifecycle(ctx, event);
+        }
+        match event {
+            // Options are only shown while the list is open.
+            LifeCycle::WidgetAdded | LifeCycle::OverlayDismissed => {
+                self.is_open = false;
+                self.highlighted = None;
+                for option in &mut self.options {
+                    ctx.set_stashed(option, true);
+                }
+                ctx.request_layout();
+                ctx.request_accessibility_update();
+            }
+            LifeCycle::BuildFocusChain => {
+                ctx.register_for_focus();
+            }
+            _ => {}
+        }
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints) -> Size {
+        let padding = Size::new(LABEL_INSETS.x_value() + ARROW_WIDTH, LABEL_INSETS.y_value());
+        let label_bc = bc.shrink(padding).loosen();
+        let label_size = self.label.layout(ctx, &label_bc);
+        ctx.set_baseline_offset(self.label.baseline_offset() + LABEL_INSETS.y1);
+
+        let size = bc.constrain(Size::new(
+            label_size.width + padding.width,
+            (label_size.height + padding.height).max(theme::BORDERED_WIDGET_HEIGHT),
+        ));
+        let label_origin = Point::new(LABEL_INSETS.x0, (size.height - label_size.height) / 2.);
+        ctx.place_child(&mut self.label, label_origin);
+
+        if self.is_open {
+            let option_bc = BoxConstraints::UNBOUNDED;
+            let mut option_sizes = Vec::with_capacity(self.options.len());
+            let mut popup_width: f64 = 0.;
+            for option in &mut self.options {
+                let option_size = option.layout(ctx, &option_bc);
+                popup_width = popup_width.max(option_size.width);
+                option_sizes.push(option_size);
+            }
+            let mut y = size.height + POPUP_BORDER_WIDTH;
+            for (option, option_size) in self.options.iter_mut().zip(option_sizes) {
+                let option_origin =
+                    Point::new(POPUP_BORDER_WIDTH + ITEM_INSETS.x0, y + ITEM_INSETS.y0);
+                ctx.place_child(option, option_origin);
+                y += option_size.height + ITEM_INSETS.y_value();
+            }
+            popup_width += ITEM_INSETS.x_value() + 2. * POPUP_BORDER_WIDTH;
+            self.popup_size = Size::new(
+                popup_width.max(size.width),
+                y - size.height + POPUP_BORDER_WIDTH,
+            );
+            ctx.set_overlay_rect(Rect::from_origin_size(
+                Point::new(0., size.height),
+                self.popup_size,
+            ));
+        }
+
+        trace!("Computed dropdown size: {}", size);
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, scene: &mut Scene) {
+        let size = ctx.size();
+        let theme = ctx.theme().clone();
+        let stroke_width = theme::TEXTBOX_BORDER_WIDTH;
+        let rounded_rect = size
+            .to_rect()
+            .inset(-stroke_width / 2.0)
+            .to_rounded_rect(theme::BUTTON_BORDER_RADIUS);
+        let bg_gradient = if ctx.is_disabled() {
+            theme.surface_disabled
+        } else {
+            theme.surface
+        };
+        let border_color = if (ctx.is_hot() || ctx.is_focused()) && !ctx.is_disabled() {
+            theme.border_hover
+        } else {
+            theme.border
+        };
+        fill_lin_gradient(
+            scene,
+            &rounded_rect,
+            bg_gradient,
+            UnitPoint::TOP,
+            UnitPoint::BOTTOM,
+        );
+        stroke(scene, &rounded_rect, border_color, stroke_width);
+
+        // A small downwards-pointing triangle on the right.
+        let arrow_center = Point::new(
+            size.width - LABEL_INSETS.x1 - ARROW_WIDTH / 2.,
+            size.height / 2.,
+        );
+        let mut arrow = BezPath::new();
+        arrow.move_to(arrow_center + (-4., -2.));
+        arrow.line_to(arrow_center + (4., -2.));
+        arrow.line_to(arrow_center + (0., 3.));
+        arrow.close_path();
+        let arrow_color = if ctx.is_disabled() {
+            theme.on_surface_disabled
+        } else {
+            theme.on_surface
+        };
+        fill_color(scene, &arrow, arrow_color);
+
+        self.label.paint(ctx, scene);
+
+        if !self.is_open {
+            return;
+        }
+        // The list is painted in an overlay, so that it isn't covered by other widgets
+        // or clipped by our ancestors.
+        let mut overlay = Scene::new();
+        let scene = &mut overlay;
+        let popup_rect = Rect::from_origin_size(Point::new(0., size.height), self.popup_size);
+        let rounded_rect = popup_rect
+            .inset(-POPUP_BORDER_WIDTH / 2.0)
+            .to_rounded_rect(theme::BUTTON_BORDER_RADIUS);
+        fill_color(scene, &rounded_rect, theme.field[0]);
+        stroke(scene, &rounded_rect, theme.border, POPUP_BORDER_WIDTH);
+
+        if let Some(option) = self.highlighted.and_then(|idx| self.options.get(idx)) {
+            let highlight = option
+                .layout_rect()
+                .inflate(ITEM_INSETS.x0, ITEM_INSETS.y0)
+                .with_size((
+                    popup_rect.width() - 2. * POPUP_BORDER_WIDTH,
+                    option.layout_rect().height() + ITEM_INSETS.y_value(),
+                ));
+            fill_color(scene, &highlight, theme.highlight);
+        }
+
+        for option in &mut self.options {
+            option.paint(ctx, scene);
+        }
+        ctx.paint_overlay(overlay);
+    }
+
+    fn accessibility_role(&self) -> Role {
+        Role::ComboBox
+    }
+
+    fn accessibility(&mut self, ctx: &mut AccessCtx) {
+        let node = ctx.current_node();
+        node.set_value(option_text(&self.options, self.selected).to_string());
+        node.set_expanded(self.is_open);
+        node.set_default_action_verb(DefaultActionVerb::Open);
+
+        // The options are reported as our children even while the list is closed,
+        // in which case they are marked hidden.
+        self.label.accessibility(ctx);
+        for option in &mut self.options {
+            option.accessibility(ctx);
+        }
+    }
+
+    fn children(&self) -> SmallVec<[WidgetRef<'_, dyn Widget>; 16]> {
+        std::iter::once(self.label.as_dyn())
+            .chain(self.options.iter().map(|option| option.as_dyn()))
+            .collect()
+    }
+
+    fn make_trace_span(&self) -> Span {
+        trace_span!("Dropdown")
+    }
+
+    fn get_debug_text(&self) -> Option<String> {
+        Some(option_text(&self.options, self.selected).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{widget_ids, TestHarness};
+    use crate::widget::{Button, Flex};
+
+    fn get_dropdown(harness: &TestHarness, id: crate::WidgetId) -> &Dropdown {
+        harness
+            .get_widget(id)
+            .downcast::<Dropdown>()
+            .unwrap()
+            .deref()
+    }
+
+    #[test]
+    fn click_selects_option() {
+        let [dropdown_id] = widget_ids();
+        let widget =
+            Flex::column().with_child_id(Dropdown::new(["Red", "Green", "Blue"], 0), dropdown_id);
+
+        let mut harness = TestHarness::create(widget);
+        harness.mouse_click_on(dropdown_id);
+        assert!(get_dropdown(&harness, dropdown_id).is_open());
+        assert_eq!(harness.focused_widget().map(|w| w.id()), Some(dropdown_id));
+
+        // The options are children 1..=3, after the selection label.
+        let third_option = harness.get_widget(dropdown_id).children()[3]
+            .state()
+            .window_layout_rect();
+        harness.mouse_move(third_option.center());
+        harness.mouse_button_press(MouseButton::Left);
+        harness.mouse_button_release(MouseButton::Left);
+
+        assert_eq!(
+            harness.pop_action(),
+            Some((Action::DropdownSelected(2), dropdown_id))
+        );
+        let dropdown = get_dropdown(&harness, dropdown_id);
+        assert!(!dropdown.is_open());
+        assert_eq!(dropdown.selected(), 2);
+        assert_eq!(&**dropdown.label.widget().text(), "Blue");
+    }
+
+    #[test]
+    fn outside_click_closes() {
+        let [dropdown_id, button_id] = widget_ids();
+        let widget = Flex::column()
+            .with_child_id(Dropdown::new(["Red", "Green"], 1), dropdown_id)
+            .with_spacer(100.)
+            .with_child_id(Button::new("Ok"), button_id);
+
+        let mut harness = TestHarness::create(widget);
+        harness.mouse_click_on(dropdown_id);
+        assert!(get_dropdown(&harness, dropdown_id).is_open());
+
+        // The click only dismisses the list, and doesn't press the button.
+        harness.mouse_click_on(button_id);
+        assert!(!get_dropdown(&harness, dropdown_id).is_open());
+        assert_eq!(get_dropdown(&harness, dropdown_id).selected(), 1);
+        assert_eq!(harness.pop_action(), None);
+    }
+}
@@ -12,7 +12,7 @@ use vello::Scene;
 use crate::kurbo::common::FloatExt;
 use crate::kurbo::Vec2;
 use crate::theme::get_debug_color;
-use crate::widget::{WidgetMut, WidgetRef};
+use crate::widget::{Divider, Spacer, WidgetMut, WidgetRef};
 use crate::{
     AccessCtx, AccessEvent, BoxConstraints, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx,
     Point, PointerEvent, Rect, Size, StatusChange, TextEvent, Widget, WidgetId, WidgetPod,
@@ -40,6 +40,7 @@ pub struct Flex {
 pub struct FlexParams {
     flex: f64,
     alignment: Option<CrossAxisAlignment>,
+    shrink: bool,
 }
 
 /// An axis in visual space.
@@ -159,12 +160,11 @@ impl Flex {
         self.with_child_pod(WidgetPod::new_with_id(Box::new(child), id))
     }
 
+    /// Builder-style method to add a child which is already wrapped in a [`WidgetPod`].
+    ///
+    /// A [`Spacer::flex`] is added as a flex child, and a [`Divider`] fills the cross axis.
     pub fn with_child_pod(mut self, widget: WidgetPod<Box<dyn Widget>>) -> Self {
-        let child = Child::Fixed {
-            widget,
-            alignment: None,
-        };
-        self.children.push(child);
+        self.children.push(Child::from_pod(widget));
         self
     }
 
@@ -177,6 +177,8 @@ impl Flex {
                 widget: WidgetPod::new(Box::new(child)),
                 alignment: params.alignment,
                 flex: params.flex,
+                shrink: params.shrink,
+                min_major: 0.0,
             }
         } else {
             // TODO
@@ -310,6 +312,8 @@ impl<'a> WidgetMut<'a, Flex> {
                 widget: WidgetPod::new(Box::new(child)),
                 alignment: params.alignment,
                 flex: params.flex,
+                shrink: params.shrink,
+                min_major: 0.0,
             }
         } else {
             // TODO
@@ -379,15 +383,11 @@ impl<'a> WidgetMut<'a, Flex> {
     }
 
     /// Add a non-flex child widget.
+    ///
+    /// A [`Spacer::flex`] is added as a flex child, and a [`Divider`] fills the cross axis.
     pub fn insert_child_pod(&mut self, idx: usize, widget: WidgetPod<Box<dyn Widget>>) {
-        let child = Child::Fixed {
-            widget,
-            alignment: None,
-        };
-        self.widget.children.insert(idx, child);
-        // TODO
-        self.ctx.widget_state.children_changed = true;
-        self.ctx.widget_state.needs_layout = true;
+        self.widget.children.insert(idx, Child::from_pod(widget));
+        self.ctx.children_changed();
     }
 
     pub fn insert_flex_child(
@@ -402,6 +402,8 @@ impl<'a> WidgetMut<'a, Flex> {
                 widget: WidgetPod::new(Box::new(child)),
                 alignment: params.alignment,
                 flex: params.flex,
+                shrink: params.shrink,
+                min_major: 0.0,
             }
         } else {
             // TODO
@@ -412,9 +414,7 @@ impl<'a> WidgetMut<'a, Flex> {
             }
         };
         self.widget.children.insert(idx, child);
-        // TODO
-        self.ctx.widget_state.children_changed = true;
-        self.ctx.widget_state.needs_layout = true;
+        self.ctx.children_changed();
     }
 
     // TODO - remove
@@ -450,6 +450,21 @@ impl<'a> WidgetMut<'a, Flex> {
         self.ctx.widget_state.needs_layout = true;
     }
 
+    /// Set the length of the fixed-size spacer at `idx`.
+    ///
+    /// Does nothing if the child at `idx` isn't a fixed-size spacer.
+    pub fn set_spacer_len(&mut self, idx: usize, mut len: f64) {
+        if len < 0.0 {
+            tracing::warn!("set_spacer_len called with negative length: {}", len);
+        }
+        len = len.clamp(0.0, f64::MAX);
+
+        if let Child::FixedSpacer(spacer_len, _) = &mut self.widget.children[idx] {
+            *spacer_len = len;
+            self.ctx.request_layout();
+        }
+    }
+
     /// Add an empty spacer widget with a specific `flex` factor.
     pub fn insert_flex_spacer(&mut self, idx: usize, flex: f64) {
         let flex = if flex >= 0.0 {
@@ -466,7 +481,7 @@ impl<'a> WidgetMut<'a, Flex> {
 
     pub fn remove_child(&mut self, idx: usize) {
         self.widget.children.remove(idx);
-        self.ctx.widget_state.needs_layout = true;
+        self.ctx.children_changed();
     }
 
     // FIXME - Remove Box
@@ -482,7 +497,7 @@ impl<'a> WidgetMut<'a, Flex> {
 
     pub fn clear(&mut self) {
         self.widget.children.clear();
-        self.ctx.widget_state.needs_layout = true;
+        self.ctx.children_changed();
     }
 }
 
@@ -532,7 +547,10 @@ impl Widget for Flex {
                 Child::Fixed { widget, alignment } => {
                     any_use_baseline &= *alignment == Some(CrossAxisAlignment::Baseline);
 
-                    let child_bc = self.direction.constraints(&loosened_bc, 0.0, f64::INFINITY);
+                    let child_bc = match spacer(widget) {
+                        Some(spacer) => spacer_constraints(self.direction, spacer.len()),
+                        None => self.direction.constraints(&loosened_bc, 0.0, f64::INFINITY),
+                    };
                     let child_size = widget.layout(ctx, &child_bc);
                     let baseline_offset = widget.baseline_offset();
 
@@ -558,7 +576,22 @@ impl Widget for Flex {
                     *calculated_size = calculated_size.max(0.0);
                     major_non_flex += *calculated_size;
                 }
-                Child::Flex { flex, .. } | Child::FlexedSpacer(flex, _) => flex_sum += *flex,
+                Child::Flex {
+                    widget,
+                    flex,
+                    shrink,
+                    min_major,
+                    ..
+                } => {
+                    *min_major = if *shrink {
+                        0.0
+                    } else {
+                        let cross_size = self.direction.minor(loosened_bc.max());
+                        widget.intrinsic_size(ctx, self.direction, cross_size)
+                    };
+                    flex_sum += *flex;
+                }
+                Child::FlexedSpacer(flex, _) => flex_sum += *flex,
             }
         }
 
@@ -567,16 +600,51 @@ impl Widget for Flex {
         let mut remainder: f64 = 0.0;
 
         let mut major_flex: f64 = 0.0;
-        let px_per_flex = remaining / flex_sum;
+        let mut px_per_flex = remaining / flex_sum;
+        // Children which can't shrink below their intrinsic size get that size instead of
+        // their share, and the other flex children share what is left. Each step can only
+        // add children to the first group, so this ends after at most one step per child.
+        loop {
+            let (frozen_major, frozen_flex) = self
+                .children
+                .iter()
+                .filter_map(|child| match child {
+                    Child::Flex {
+                        flex, min_major, ..
+                    } if *min_major > flex * px_per_flex => Some((*min_major, *flex)),
+                    _ => None,
+                })
+                .fold((0.0, 0.0), |(major, flex), (m, f)| (major + m, flex + f));
+            if frozen_flex == 0.0 {
+                break;
+            }
+            let next_px_per_flex = if flex_sum > frozen_flex {
+                (remaining - frozen_major).max(0.0) / (flex_sum - frozen_flex)
+            } else {
+                0.0
+            };
+            if next_px_per_flex == px_per_flex {
+                break;
+            }
+            px_per_flex = next_px_per_flex;
+        }
         // Measure flex children.
         for child in &mut self.children {
             match child {
-                Child::Flex { widget, flex, .. } => {
-                    let desired_major = (*flex) * px_per_flex + remainder;
+                Child::Flex {
+                    widget,
+                    flex,
+                    min_major,
+                    ..
+                } => {
+                    let desired_major = ((*flex) * px_per_flex).max(*min_major) + remainder;
                     let actual_major = desired_major.round();
                     remainder = desired_major - actual_major;
 
-                    let child_bc = self.direction.constraints(&loosened_bc, 0.0, actual_major);
+                    let child_bc = match spacer(widget) {
+                        Some(_) => spacer_constraints(self.direction, actual_major),
+                        None => self.direction.constraints(&loosened_bc, 0.0, actual_major),
+                    };
                     let child_size = widget.layout(ctx, &child_bc);
                     let baseline_offset = widget.baseline_offset();
 
@@ -710,6 +778,29 @@ impl Widget for Flex {
         my_size
     }
 
+    fn intrinsic_size(&mut self, ctx: &mut LayoutCtx, axis: Axis, cross_size: f64) -> f64 {
+        if axis == self.direction {
+            self.children
+                .iter_mut()
+                .map(|child| match child {
+                    Child::Fixed { widget, .. } | Child::Flex { widget, .. } => {
+                        widget.intrinsic_size(ctx, axis, cross_size)
+                    }
+                    Child::FixedSpacer(len, _) => len.max(0.0),
+                    Child::FlexedSpacer(..) => 0.0,
+                })
+                .sum()
+        } else {
+            // Non-flex children are laid out with an unbounded main axis, so their
+            // natural cross size is used.
+            self.children
+                .iter_mut()
+                .filter_map(|child| child.widget_mut())
+                .map(|widget| widget.intrinsic_size(ctx, axis, f64::INFINITY))
+                .fold(0.0, f64::max)
+        }
+    }
+
     fn paint(&mut self, ctx: &mut PaintCtx, scene: &mut Scene) {
         for child in self.children.iter_mut().filter_map(|x| x.widget_mut()) {
             child.paint(ctx, scene);
@@ -861,8 +952,21 @@ impl FlexParams {
         FlexParams {
             flex,
             alignment: alignment.into(),
+            shrink: true,
         }
     }
+
+    /// Set whether the child can be made smaller than its
+    /// [intrinsic size](Widget::intrinsic_size) along the main axis.
+    ///
+    /// By default, a flex child gets its share of the remaining space even if its
+    /// content needs more. A child which can't shrink, like a CSS item with
+    /// `flex-shrink: 0`, gets at least its intrinsic size, and the other flex children
+    /// share what is left.
+    pub fn shrink(mut self, shrink: bool) -> Self {
+        self.shrink = shrink;
+        self
+    }
 }
 
 impl CrossAxisAlignment {
@@ -990,12 +1094,49 @@ enum Child {
         widget: WidgetPod<Box<dyn Widget>>,
         alignment: Option<CrossAxisAlignment>,
         flex: f64,
+        shrink: bool,
+        /// The intrinsic main size of a child which can't shrink, computed during layout.
+        min_major: f64,
     },
     FixedSpacer(f64, f64),
     FlexedSpacer(f64, f64),
 }
 
+/// Returns the [`Spacer`] in `widget`, if it is one.
+fn spacer(widget: &WidgetPod<Box<dyn Widget>>) -> Option<&Spacer> {
+    let widget: &dyn Widget = &**widget.widget();
+    widget.as_any().downcast_ref()
+}
+
+/// The constraints of a [`Spacer`] child which is `len` long: it takes no space across
+/// the main axis.
+fn spacer_constraints(direction: Axis, len: f64) -> BoxConstraints {
+    BoxConstraints::tight(direction.pack(len, 0.0).into())
+}
+
+/// Returns whether `widget` is a [`Divider`], which spans the cross axis of the container.
+fn is_divider(widget: &WidgetPod<Box<dyn Widget>>) -> bool {
+    let widget: &dyn Widget = &**widget.widget();
+    widget.as_any().is::<Divider>()
+}
+
 impl Child {
+    fn from_pod(widget: WidgetPod<Box<dyn Widget>>) -> Self {
+        match spacer(&widget).and_then(Spacer::flex_factor) {
+            Some(flex) => Child::Flex {
+                widget,
+                alignment: None,
+                flex,
+                shrink: true,
+                min_major: 0.0,
+            },
+            None => Child::Fixed {
+                alignment: is_divider(&widget).then_some(CrossAxisAlignment::Fill),
+                widget,
+            },
+        }
+    }
+
     fn widget_mut(&mut self) -> Option<&mut WidgetPod<Box<dyn Widget>>> {
         match self {
             Child::Fixed { widget, .. } | Child::Flex { widget, .. } => Some(widget),
@@ -1012,10 +1153,15 @@ impl Child {
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
+    use assert_matches::assert_matches;
+
     use super::*;
     use crate::assert_render_snapshot;
-    use crate::testing::TestHarness;
-    use crate::widget::Label;
+    use crate::testing::{widget_ids, ModularWidget, TestHarness, TestWidgetExt};
+    use crate::widget::{Label, SizedBox};
+    use crate::{Action, Insets};
 
     #[test]
     #[allow(clippy::cognitive_complexity)]
@@ -1406,4 +1552,126 @@ mod tests {
 
         // TODO - test out-of-bounds access?
     }
+
+    #[test]
+    fn spacer_children() {
+        let [row, first, second, third] = widget_ids();
+        let widget = Flex::column().with_child_id(
+            Flex::row()
+                .with_child_id(SizedBox::empty().width(20.0).height(10.0), first)
+                .with_child_pod(WidgetPod::new(Box::new(Spacer::new(30.0))))
+                .with_child_id(SizedBox::empty().width(20.0).height(10.0), second)
+                .with_child_pod(WidgetPod::new(Box::new(Spacer::flex(1.0))))
+                .with_child_id(SizedBox::empty().width(20.0).height(10.0), third)
+                .must_fill_main_axis(true),
+            row,
+        );
+
+        let harness = TestHarness::create_with_size(widget, Size::new(200.0, 100.0));
+        let rect = |id| harness.get_widget(id).state().layout_rect();
+        assert_eq!(rect(first).x0, 0.0);
+        assert_eq!(rect(second).x0, 50.0);
+        // The flexible spacer pushes the last child to the end of the row.
+        assert_eq!(rect(third).x1, 200.0);
+        // Spacers take no space across the row.
+        assert_eq!(rect(row).height(), 10.0);
+    }
+
+    #[test]
+    fn edit_spacer_len() {
+        let [second] = widget_ids();
+        let widget = Flex::row()
+            .with_child(SizedBox::empty().width(20.0).height(10.0))
+            .with_spacer(10.0)
+            .with_child_id(SizedBox::empty().width(20.0).height(10.0), second);
+
+        let mut harness = TestHarness::create_with_size(widget, Size::new(200.0, 100.0));
+        assert_eq!(harness.get_widget(second).state().layout_rect().x0, 30.0);
+
+        harness.edit_root_widget(|mut flex| {
+            let mut flex = flex.downcast::<Flex>();
+            flex.set_spacer_len(1, 25.0);
+            // Children which aren't fixed-size spacers are left alone.
+            flex.set_spacer_len(0, 100.0);
+        });
+        assert_eq!(harness.get_widget(second).state().layout_rect().x0, 45.0);
+    }
+
+    #[test]
+    fn flex_child_without_shrink_keeps_intrinsic_size() {
+        let [fixed, other] = widget_ids();
+        let widget = Flex::row()
+            .with_flex_child(
+                SizedBox::empty().width(80.0).with_id(fixed),
+                FlexParams::new(1.0, None).shrink(false),
+            )
+            .with_flex_child(SizedBox::empty().expand_width().with_id(other), 1.0);
+
+        let harness = TestHarness::create_with_size(widget, Size::new(100.0, 50.0));
+        assert_eq!(
+            harness.get_widget(fixed).state().layout_rect().width(),
+            80.0
+        );
+        assert_eq!(
+            harness.get_widget(other).state().layout_rect().width(),
+            20.0
+        );
+
+        // By default, both children get half of the row.
+        let widget = Flex::row()
+            .with_flex_child(SizedBox::empty().width(80.0).with_id(fixed), 1.0)
+            .with_flex_child(SizedBox::empty().expand_width().with_id(other), 1.0);
+
+        let harness = TestHarness::create_with_size(widget, Size::new(100.0, 50.0));
+        assert_eq!(
+            harness.get_widget(fixed).state().layout_rect().width(),
+            50.0
+        );
+        assert_eq!(
+            harness.get_widget(other).state().layout_rect().width(),
+            50.0
+        );
+    }
+
+    #[test]
+    fn measuring_intrinsic_size_has_no_side_effects() {
+        let [measured, expanding] = widget_ids();
+        // Measured through the default `intrinsic_size`, which runs `layout`.
+        let child = ModularWidget::new(()).layout_fn(|_, ctx, bc| {
+            ctx.submit_action(Action::Other(Arc::new(())));
+            // Only measuring leaves the width unbounded.
+            if !bc.is_width_bounded() {
+                ctx.set_paint_insets(5.0);
+                ctx.set_baseline_offset(3.0);
+            }
+            Size::new(60.0, 10.0)
+        });
+        let widget = Flex::row()
+            .with_flex_child(
+                child.with_id(measured),
+                FlexParams::new(1.0, None).shrink(false),
+            )
+            .with_flex_child(
+                SizedBox::empty().expand_width().with_id(expanding),
+                FlexParams::new(1.0, None).shrink(false),
+            );
+
+        let mut harness = TestHarness::create_with_size(widget, Size::new(100.0, 50.0));
+        // Only the real layout submitted an action.
+        assert_matches!(harness.pop_action(), Some((Action::Other(_), id)) if id == measured);
+        assert_matches!(harness.pop_action(), None);
+        let state = harness.get_widget(measured).state();
+        assert_eq!(state.paint_insets, Insets::ZERO);
+        assert_eq!(state.baseline_offset, 0.0);
+
+        // The expanding child has no natural width, so it gets what's left.
+        assert_eq!(
+            harness.get_widget(measured).state().layout_rect().width(),
+            60.0
+        );
+        assert_eq!(
+            harness.get_widget(expanding).state().layout_rect().width(),
+            40.0
+        );
+    }
 }
@@ -0,0 +1,215 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! A clickable link.
+
+use accesskit::{DefaultActionVerb, Role};
+use kurbo::{Point, Size};
+use smallvec::SmallVec;
+use tracing::{trace, trace_span, Span};
+use vello::Scene;
+
+use crate::action::Action;
+use crate::text2::{StyledText, TextBrush, TextLayout, TextSpanStyle};
+use crate::widget::label::LABEL_X_PADDING;
+use crate::widget::{WidgetMut, WidgetRef};
+use crate::{
+    theme, AccessCtx, AccessEvent, ArcStr, BoxConstraints, Color, CursorIcon, EventCtx, LayoutCtx,
+    LifeCycle, LifeCycleCtx, PaintCtx, PointerEvent, StatusChange, TextEvent, Widget,
+};
+
+/// A link, displayed as underlined text.
+///
+/// Emits [`Action::LinkClicked`] when clicked. The widget doesn't know where the link
+/// leads: opening it is up to the application.
+///
+/// As with a [`Button`](super::Button), the action is only emitted if the pointer is
+/// released over the link.
+pub struct Hyperlink {
+    text: ArcStr,
+    text_layout: TextLayout<StyledText>,
+    brush: TextBrush,
+}
+
+impl Hyperlink {
+    /// Create a new link displaying `text`.
+    pub fn new(text: impl Into<ArcStr>) -> Self {
+        let text = text.into();
+        let mut text_layout =
+            TextLayout::new(underlined(&text), crate::theme::TEXT_SIZE_NORMAL as f32);
+        text_layout.set_brush(theme::LINK_COLOR);
+        Self {
+            text,
+            text_layout,
+            brush: theme::LINK_COLOR.into(),
+        }
+    }
+
+    pub fn text(&self) -> &ArcStr {
+        &self.text
+    }
+
+    /// Builder-style method for setting the color of the text.
+    ///
+    /// By default this is [`LINK_COLOR`](crate::theme::LINK_COLOR).
+    #[doc(alias = "with_text_color")]
+    pub fn with_text_brush(mut self, color: Color) -> Self {
+        self.brush = color.into();
+        self.text_layout.set_brush(color);
+        self
+    }
+
+    pub fn with_text_size(mut self, size: f32) -> Self {
+        self.text_layout.set_text_size(size);
+        self
+    }
+}
+
+fn underlined(text: &str) -> StyledText {
+    StyledText::new().with_span(text, TextSpanStyle::default().underline())
+}
+
+impl WidgetMut<'_, Hyperlink> {
+    pub fn text(&self) -> &ArcStr {
+        &self.widget.text
+    }
+
+    pub fn set_text(&mut self, new_text: impl Into<ArcStr>) {
+        self.widget.text = new_text.into();
+        let styled = underlined(&self.widget.text);
+        self.widget.text_layout.set_text(styled);
+        self.ctx.request_layout();
+        self.ctx.request_accessibility_update();
+    }
+
+    #[doc(alias = "set_text_color")]
+    pub fn set_text_brush(&mut self, brush: impl Into<TextBrush>) {
+        self.widget.brush = brush.into();
+        if !self.ctx.is_disabled() {
+            self.widget.text_layout.set_brush(self.widget.brush.clone());
+            // TODO: Parley seems to require a relayout when colours change
+            self.ctx.request_layout();
+        }
+    }
+
+    pub fn set_text_size(&mut self, size: f32) {
+        self.widget.text_layout.set_text_size(size);
+        self.ctx.request_layout();
+    }
+}
+
+impl Widget for Hyperlink {
+    fn on_pointer_event(&mut self, ctx: &mut EventCtx, event: &PointerEvent) {
+        match event {
+            PointerEvent::PointerDown(_, _) if !ctx.is_disabled() => {
+                ctx.set_active(true);
+                trace!("Hyperlink {:?} pressed", ctx.widget_id());
+            }
+            PointerEvent::PointerUp(_, _) => {
+                if ctx.is_active() && ctx.is_hot() && !ctx.is_disabled() {
+                    ctx.submit_action(Action::LinkClicked);
+                    trace!("Hyperlink {:?} clicked", ctx.widget_id());
+                }
+                ctx.set_active(false);
+            }
+            PointerEvent::PointerLeave(_) => {
+                ctx.set_active(false);
+            }
+            _ => (),
+        }
+    }
+
+    fn on_text_event(&mut self, _ctx: &mut EventCtx, _event: &TextEvent) {}
+
+    fn on_access_event(&mut self, ctx: &mut EventCtx, event: &AccessEvent) {
+        if event.target == ctx.widget_id() && event.action == accesskit::Action::Default {
+            ctx.submit_action(Action::LinkClicked);
+        }
+    }
+
+    fn on_status_change(&mut self, _ctx: &mut LifeCycleCtx, _event: &StatusChange) {}
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle) {
+        match event {
+            LifeCycle::WidgetAdded => {
+                ctx.set_cursor(&CursorIcon::Pointer);
+            }
+            LifeCycle::DisabledChanged(disabled) => {
+                if *disabled {
+                    self.text_layout
+                        .set_brush(crate::theme::DISABLED_TEXT_COLOR);
+                } else {
+                    self.text_layout.set_brush(self.brush.clone());
+                }
+                // TODO: Parley seems to require a relayout when colours change
+                ctx.request_layout();
+            }
+            _ => {}
+        }
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints) -> Size {
+        if self.text_layout.needs_rebuild() {
+            self.text_layout.rebuild(ctx.font_ctx());
+        }
+        let text_size = self.text_layout.size();
+        let size = bc.constrain(Size {
+            height: text_size.height,
+            width: text_size.width + 2. * LABEL_X_PADDING,
+        });
+        trace!("Computed layout: w={}, h={}", size.width, size.height);
+        size
+    }
+
+    fn paint(&mut self, _ctx: &mut PaintCtx, scene: &mut Scene) {
+        if self.text_layout.needs_rebuild() {
+            debug_panic!("Called Hyperlink paint before layout");
+        }
+        self.text_layout
+            .draw(scene, Point::new(LABEL_X_PADDING, 0.0));
+    }
+
+    fn accessibility_role(&self) -> Role {
+        Role::Link
+    }
+
+    fn accessibility(&mut self, ctx: &mut AccessCtx) {
+        let node = ctx.current_node();
+        node.set_name(self.text.to_string());
+        node.set_default_action_verb(DefaultActionVerb::Jump);
+    }
+
+    fn children(&self) -> SmallVec<[WidgetRef<'_, dyn Widget>; 16]> {
+        SmallVec::new()
+    }
+
+    fn make_trace_span(&self) -> Span {
+        trace_span!("Hyperlink")
+    }
+
+    fn get_debug_text(&self) -> Option<String> {
+        Some(self.text.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{widget_ids, TestHarness, TestWidgetExt};
+
+    #[test]
+    fn click_emits_action() {
+        let [link_id] = widget_ids();
+        let widget = Hyperlink::new("Docs").with_id(link_id);
+
+        let mut harness = TestHarness::create(widget);
+        assert_eq!(harness.pop_action(), None);
+
+        harness.mouse_click_on(link_id);
+        assert_eq!(harness.pop_action(), Some((Action::LinkClicked, link_id)));
+        assert_eq!(
+            harness.get_widget(link_id).get_debug_text().as_deref(),
+            Some("Docs")
+        );
+    }
+}
@@ -0,0 +1,148 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! A widget which shows an icon from an icon font.
+
+use accesskit::Role;
+use kurbo::{Point, Size};
+use smallvec::SmallVec;
+use tracing::{trace, trace_span, Span};
+use vello::Scene;
+
+use crate::text2::{IconGlyph, TextBrush, TextLayout};
+use crate::widget::{WidgetMut, WidgetRef};
+use crate::{
+    theme, AccessCtx, AccessEvent, ArcStr, BoxConstraints, EventCtx, LayoutCtx, LifeCycle,
+    LifeCycleCtx, PaintCtx, PointerEvent, StatusChange, TextEvent, Widget,
+};
+
+/// A widget showing an icon from an [`IconFont`](crate::text2::IconFont), by name.
+///
+/// The icon is drawn centered in a square of the given size. Icon fonts are registered
+/// with [`RenderRoot::add_icon_font`](crate::render_root::RenderRoot::add_icon_font);
+/// if no registered font has an icon with this name, nothing is drawn.
+pub struct Icon {
+    name: ArcStr,
+    size: f64,
+    /// The glyph found for `name` during the last layout.
+    glyph: Option<IconGlyph>,
+    text_layout: TextLayout<ArcStr>,
+}
+
+impl Icon {
+    /// Create a new icon showing the icon named `name`, with the normal text size.
+    pub fn new(name: impl Into<ArcStr>) -> Self {
+        let size = theme::TEXT_SIZE_NORMAL;
+        Self {
+            name: name.into(),
+            size,
+            glyph: None,
+            text_layout: TextLayout::new("".into(), size as f32),
+        }
+    }
+
+    /// Builder-style method for setting the width and height of the icon.
+    pub fn with_size(mut self, size: f64) -> Self {
+        self.size = size;
+        self.text_layout.set_text_size(size as f32);
+        self
+    }
+
+    /// Builder-style method for setting the color of the icon.
+    #[doc(alias = "with_color")]
+    pub fn with_brush(mut self, brush: impl Into<TextBrush>) -> Self {
+        self.text_layout.set_brush(brush);
+        self
+    }
+
+    /// Returns the name of the icon.
+    pub fn name(&self) -> &ArcStr {
+        &self.name
+    }
+}
+
+impl WidgetMut<'_, Icon> {
+    /// Set the name of the icon shown.
+    pub fn set_name(&mut self, name: impl Into<ArcStr>) {
+        self.widget.name = name.into();
+        self.ctx.request_layout();
+        self.ctx.request_accessibility_update();
+    }
+
+    /// Set the width and height of the icon.
+    pub fn set_size(&mut self, size: f64) {
+        self.widget.size = size;
+        self.widget.text_layout.set_text_size(size as f32);
+        self.ctx.request_layout();
+    }
+
+    /// Set the color of the icon.
+    #[doc(alias = "set_color")]
+    pub fn set_brush(&mut self, brush: impl Into<TextBrush>) {
+        self.widget.text_layout.set_brush(brush);
+        // TODO: Parley seems to require a relayout when colours change
+        self.ctx.request_layout();
+    }
+}
+
+impl Widget for Icon {
+    fn on_pointer_event(&mut self, _ctx: &mut EventCtx, _event: &PointerEvent) {}
+
+    fn on_text_event(&mut self, _ctx: &mut EventCtx, _event: &TextEvent) {}
+
+    fn on_access_event(&mut self, _ctx: &mut EventCtx, _event: &AccessEvent) {}
+
+    fn on_status_change(&mut self, _ctx: &mut LifeCycleCtx, _event: &StatusChange) {}
+
+    fn lifecycle(&mut self, _ctx: &mut LifeCycleCtx, _event: &LifeCycle) {}
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints) -> Size {
+        let glyph = ctx.icon_glyph(&self.name);
+        if glyph != self.glyph {
+            match &glyph {
+                Some(glyph) => {
+                    self.text_layout
+                        .set_text(glyph.character.to_string().into());
+                    self.text_layout.set_font_name(glyph.family.clone());
+                }
+                None => {
+                    trace!("No icon named {:?}", self.name);
+                    self.text_layout.set_text("".into());
+                }
+            }
+            self.glyph = glyph;
+        }
+        if self.text_layout.needs_rebuild() {
+            self.text_layout.rebuild(ctx.font_ctx());
+        }
+        bc.constrain(Size::new(self.size, self.size))
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, scene: &mut Scene) {
+        if self.text_layout.needs_rebuild() {
+            debug_panic!("Called Icon paint before layout");
+        }
+        let offset = (ctx.size().to_vec2() - self.text_layout.size().to_vec2()) / 2.0;
+        self.text_layout.draw(scene, Point::ORIGIN + offset);
+    }
+
+    fn accessibility_role(&self) -> Role {
+        Role::Image
+    }
+
+    fn accessibility(&mut self, ctx: &mut AccessCtx) {
+        ctx.current_node().set_name(self.name.to_string());
+    }
+
+    fn children(&self) -> SmallVec<[WidgetRef<'_, dyn Widget>; 16]> {
+        SmallVec::new()
+    }
+
+    fn make_trace_span(&self) -> Span {
+        trace_span!("Icon")
+    }
+
+    fn get_debug_text(&self) -> Option<String> {
+        Some(self.name.to_string())
+    }
+}
@@ -9,10 +9,12 @@ use parley::layout::Alignment;
 use parley::style::{FontFamily, FontStack};
 use smallvec::SmallVec;
 use tracing::trace;
+use unicode_segmentation::UnicodeSegmentation;
 use vello::peniko::BlendMode;
 use vello::Scene;
 
-use crate::text2::{TextBrush, TextLayout, TextStorage};
+use crate::text2::{TextBrush, TextDirection, TextLayout, TextStorage, TextStyle};
+use crate::theme::Theme;
 use crate::widget::{WidgetMut, WidgetRef};
 use crate::{
     AccessCtx, AccessEvent, ArcStr, BoxConstraints, Color, EventCtx, LayoutCtx, LifeCycle,
@@ -33,6 +35,35 @@ pub enum LineBreaking {
     Overflow,
 }
 
+/// What to do with text that doesn't fit in the label.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextOverflow {
+    /// Text outside the bounds of the label is hidden.
+    Clip,
+    /// The text is kept on one line, and if it's too wide, it's cut short and "…" is
+    /// added at its end.
+    Ellipsis,
+    /// The text is broken into at most this many lines, and if it needs more, it's cut
+    /// short and "…" is added at its end.
+    EllipsisLines(usize),
+}
+
+impl TextOverflow {
+    /// Cut the text short with "…" if it needs more than `max_lines` lines.
+    ///
+    /// This is [`Ellipsis`](Self::Ellipsis) for a single line, which is the most common
+    /// case, and [`EllipsisLines`](Self::EllipsisLines) otherwise. At least one line is kept.
+    pub fn max_lines(max_lines: usize) -> Self {
+        if max_lines <= 1 {
+            Self::Ellipsis
+        } else {
+            Self::EllipsisLines(max_lines)
+        }
+    }
+}
+
+const ELLIPSIS: &str = "\u{2026}";
+
 /// A widget displaying non-editable text.
 pub struct Label {
     // We hardcode the underlying storage type as `ArcStr` for `Label`
@@ -41,8 +72,12 @@ pub struct Label {
     // has rich text properties specified still needs to be designed)
     text_layout: TextLayout<ArcStr>,
     line_break_mode: LineBreaking,
+    text_overflow: Option<TextOverflow>,
+    /// The text which is displayed instead of the full text when it's cut short.
+    truncated_layout: Option<TextLayout<ArcStr>>,
     show_disabled: bool,
-    brush: TextBrush,
+    /// The brush set on the label, if any, which is used instead of the theme's text color.
+    brush: Option<TextBrush>,
 }
 
 impl Label {
@@ -51,8 +86,10 @@ impl Label {
         Self {
             text_layout: TextLayout::new(text.into(), crate::theme::TEXT_SIZE_NORMAL as f32),
             line_break_mode: LineBreaking::Overflow,
+            text_overflow: None,
+            truncated_layout: None,
             show_disabled: true,
-            brush: crate::theme::TEXT_COLOR.into(),
+            brush: None,
         }
     }
 
@@ -62,6 +99,7 @@ impl Label {
 
     #[doc(alias = "with_text_color")]
     pub fn with_text_brush(mut self, color: Color) -> Self {
+        self.brush = Some(color.into());
         self.text_layout.set_brush(color);
         self
     }
@@ -76,6 +114,31 @@ impl Label {
         self
     }
 
+    /// Set the base direction of the text, which decides which side it's aligned to.
+    ///
+    /// By default, the direction is that of the first strongly directional character.
+    pub fn with_text_direction(mut self, direction: TextDirection) -> Self {
+        self.text_layout.set_text_direction(direction);
+        self
+    }
+
+    /// Set the height of each line, as a multiple of the line height of the font.
+    ///
+    /// The default is `1.0`. This overrides the line height of a previous
+    /// [`with_text_style`](Self::with_text_style).
+    pub fn with_line_height(mut self, line_height: f32) -> Self {
+        self.text_layout.set_line_height(line_height);
+        self
+    }
+
+    /// Set the extra space added after each character, in logical pixels.
+    ///
+    /// This is an absolute length, which can be negative. The default is `0.0`.
+    pub fn with_letter_spacing(mut self, letter_spacing: f32) -> Self {
+        self.text_layout.set_letter_spacing(letter_spacing);
+        self
+    }
+
     pub fn with_font(mut self, font: FontStack<'static>) -> Self {
         self.text_layout.set_font(font);
         self
@@ -89,6 +152,31 @@ impl Label {
         self
     }
 
+    /// Set what happens to text that doesn't fit in the label.
+    ///
+    /// With [`TextOverflow::Ellipsis`] and [`TextOverflow::EllipsisLines`], the label's
+    /// [`LineBreaking`] mode is ignored.
+    pub fn with_text_overflow(mut self, text_overflow: TextOverflow) -> Self {
+        self.text_overflow = Some(text_overflow);
+        self
+    }
+
+    /// Limit the text to `max_lines` lines, cutting it short with "…" at the end of the
+    /// last one if it needs more.
+    ///
+    /// The label is only as tall as the lines which are kept. This is a shorthand for
+    /// [`with_text_overflow`](Self::with_text_overflow) with [`TextOverflow::max_lines`].
+    pub fn with_max_lines(self, max_lines: usize) -> Self {
+        self.with_text_overflow(TextOverflow::max_lines(max_lines))
+    }
+
+    /// Set the font, size, weight, color and line height of the text at once.
+    pub fn with_text_style(mut self, text_style: &TextStyle) -> Self {
+        self.brush = Some(text_style.color.into());
+        self.text_layout.set_text_style(text_style);
+        self
+    }
+
     /// Create a label with empty text.
     pub fn empty() -> Self {
         Self::new("")
@@ -115,12 +203,10 @@ impl WidgetMut<'_, Label> {
 
     #[doc(alias = "set_text_color")]
     pub fn set_text_brush(&mut self, brush: impl Into<TextBrush>) {
-        let brush = brush.into();
-        self.widget.brush = brush;
-        if !self.ctx.is_disabled() {
-            let brush = self.widget.brush.clone();
-            self.set_text_properties(|layout| layout.set_brush(brush));
-        }
+        self.widget.brush = Some(brush.into());
+        self.widget
+            .update_displayed_brush(self.ctx.theme(), self.ctx.is_disabled());
+        self.ctx.request_paint();
     }
     pub fn set_text_size(&mut self, size: f32) {
         self.set_text_properties(|layout| layout.set_text_size(size));
@@ -128,6 +214,18 @@ impl WidgetMut<'_, Label> {
     pub fn set_alignment(&mut self, alignment: Alignment) {
         self.set_text_properties(|layout| layout.set_text_alignment(alignment));
     }
+    /// Set the base direction of the text.
+    pub fn set_text_direction(&mut self, direction: TextDirection) {
+        self.set_text_properties(|layout| layout.set_text_direction(direction));
+    }
+    /// Set the height of each line, as a multiple of the line height of the font.
+    pub fn set_line_height(&mut self, line_height: f32) {
+        self.set_text_properties(|layout| layout.set_line_height(line_height));
+    }
+    /// Set the extra space added after each character, in logical pixels.
+    pub fn set_letter_spacing(&mut self, letter_spacing: f32) {
+        self.set_text_properties(|layout| layout.set_letter_spacing(letter_spacing));
+    }
     pub fn set_font(&mut self, font_stack: FontStack<'static>) {
         self.set_text_properties(|layout| layout.set_font(font_stack));
     }
@@ -138,6 +236,123 @@ impl WidgetMut<'_, Label> {
         self.widget.line_break_mode = line_break_mode;
         self.ctx.request_paint();
     }
+    /// Set what happens to text that doesn't fit in the label, or `None` to let
+    /// the [`LineBreaking`] mode decide.
+    pub fn set_text_overflow(&mut self, text_overflow: Option<TextOverflow>) {
+        self.widget.text_overflow = text_overflow;
+        self.widget.text_layout.invalidate();
+        self.ctx.request_layout();
+    }
+    /// Limit the text to `max_lines` lines, cutting it short with "…" if it needs more,
+    /// or `None` to show all of it.
+    pub fn set_max_lines(&mut self, max_lines: Option<usize>) {
+        self.set_text_overflow(max_lines.map(TextOverflow::max_lines));
+    }
+
+    /// Set the font, size, weight, color and line height of the text at once.
+    pub fn set_text_style(&mut self, text_style: &TextStyle) {
+        self.widget.brush = Some(text_style.color.into());
+        self.set_text_properties(|layout| layout.set_text_style(text_style));
+    }
+}
+
+impl Label {
+    /// The maximum number of lines, if the text is cut short when it needs more.
+    fn max_lines(&self) -> Option<usize> {
+        match self.text_overflow {
+            Some(TextOverflow::Ellipsis) => Some(1),
+            Some(TextOverflow::EllipsisLines(lines)) => Some(lines.max(1)),
+            Some(TextOverflow::Clip) | None => None,
+        }
+    }
+
+    fn clips(&self) -> bool {
+        self.line_break_mode == LineBreaking::Clip || self.text_overflow == Some(TextOverflow::Clip)
+    }
+
+    /// The layout which is painted, which may be cut short.
+    /// Set the brush the text is drawn with.
+    ///
+    /// The brush doesn't change the size of the text, so this only needs a repaint: the
+    /// text is rebuilt with the new brush in `paint`.
+    fn set_displayed_brush(&mut self, brush: TextBrush) {
+        self.text_layout.set_brush(brush.clone());
+        if let Some(truncated_layout) = &mut self.truncated_layout {
+            truncated_layout.set_brush(brush);
+        }
+    }
+
+    /// Show the text with the brush set on the label, or else the theme's text color, or
+    /// the theme's disabled text color while the label is disabled.
+    ///
+    /// This is called in `layout`, which runs again when the theme changes, and when the
+    /// brush or the disabled state changes.
+    fn update_displayed_brush(&mut self, theme: &Theme, disabled: bool) {
+        let brush = if disabled && self.show_disabled {
+            theme.on_surface_disabled.into()
+        } else {
+            self.brush
+                .clone()
+                .unwrap_or_else(|| theme.on_surface.into())
+        };
+        self.set_displayed_brush(brush);
+    }
+
+    fn displayed_layout(&mut self) -> &mut TextLayout<ArcStr> {
+        self.truncated_layout
+            .as_mut()
+            .unwrap_or(&mut self.text_layout)
+    }
+
+    /// Find the longest start of the text which fits in `max_lines` lines of `max_width`
+    /// once "…" is added to it.
+    ///
+    /// The text is cut in logical order, at grapheme boundaries, and Parley then lays out the
+    /// shortened text as usual. This means that in bidirectional text, the ellipsis ends up
+    /// where the text would have continued, according to the direction of the paragraph.
+    fn truncate(&mut self, fcx: &mut parley::FontContext, max_width: f32, max_lines: usize) {
+        let fits = |layout: &TextLayout<ArcStr>| {
+            let layout = layout.layout();
+            layout.len() <= max_lines && layout.width() <= max_width
+        };
+        self.truncated_layout = None;
+        if fits(&self.text_layout) {
+            return;
+        }
+
+        let text = self.text_layout.text().clone();
+        let shortened =
+            |end: usize| -> ArcStr { format!("{}{ELLIPSIS}", text[..end].trim_end()).into() };
+        // The shortened text can't go past the end of the last line which is kept, so only
+        // the text up to there is searched. This matters for long text cut to one line.
+        let kept_end = self
+            .text_layout
+            .layout()
+            .get(max_lines - 1)
+            .map_or(text.len(), |line| line.text_range().end);
+        let boundaries: Vec<usize> = text[..kept_end]
+            .grapheme_indices(true)
+            .map(|(i, _)| i)
+            .chain(std::iter::once(kept_end))
+            .collect();
+        let mut layout = self.text_layout.clone();
+        // Binary search for the last boundary at which the shortened text still fits.
+        // If none do, only the ellipsis is displayed.
+        let (mut low, mut high) = (0, boundaries.len());
+        while high - low > 1 {
+            let mid = (low + high) / 2;
+            layout.set_text(shortened(boundaries[mid]));
+            layout.rebuild(fcx);
+            if fits(&layout) {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+        layout.set_text(shortened(boundaries.get(low).copied().unwrap_or(0)));
+        layout.rebuild(fcx);
+        self.truncated_layout = Some(layout);
+    }
 }
 
 impl Widget for Label {
@@ -178,16 +393,8 @@ impl Widget for Label {
     fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle) {
         match event {
             LifeCycle::DisabledChanged(disabled) => {
-                if self.show_disabled {
-                    if *disabled {
-                        self.text_layout
-                            .set_brush(crate::theme::DISABLED_TEXT_COLOR);
-                    } else {
-                        self.text_layout.set_brush(self.brush.clone());
-                    }
-                }
-                // TODO: Parley seems to require a relayout when colours change
-                ctx.request_layout();
+                self.update_displayed_brush(ctx.theme(), *disabled);
+                ctx.request_paint();
             }
             LifeCycle::BuildFocusChain => {
                 if !self.text_layout.text().links().is_empty() {
@@ -199,8 +406,14 @@ impl Widget for Label {
     }
 
     fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints) -> Size {
+        let max_lines = self.max_lines();
         // Compute max_advance from box constraints
-        let max_advance = if self.line_break_mode != LineBreaking::WordWrap {
+        let max_advance = if max_lines.is_some() {
+            bc.max()
+                .width
+                .is_finite()
+                .then(|| bc.max().width as f32 - 2. * LABEL_X_PADDING as f32)
+        } else if self.line_break_mode != LineBreaking::WordWrap {
             None
         } else if bc.max().width.is_finite() {
             Some(bc.max().width as f32 - 2. * LABEL_X_PADDING as f32)
@@ -209,12 +422,35 @@ impl Widget for Label {
         } else {
             None
         };
+        self.update_displayed_brush(ctx.theme(), ctx.is_disabled());
         self.text_layout.set_max_advance(max_advance);
         if self.text_layout.needs_rebuild() {
             self.text_layout.rebuild(ctx.font_ctx());
+            match max_lines {
+                // Without a width to wrap at, the text can still have too many lines
+                // because of line breaks in it.
+                Some(max_lines) => {
+                    let max_width = max_advance.map_or(f32::INFINITY, |width| width.max(0.0));
+                    self.truncate(ctx.font_ctx(), max_width, max_lines);
+                }
+                None => self.truncated_layout = None,
+            }
+        }
+        // Parley aligns lines within the max advance, so for aligned text that is the width
+        // the label ends up with: the width of the text, or more if the constraints require it.
+        // Otherwise the lines would be aligned within a width the label doesn't have.
+        if max_lines.is_none() && self.text_layout.line_alignment() != Alignment::Start {
+            let text_width = self.text_layout.size().width;
+            let inner_width =
+                bc.constrain((text_width + 2. * LABEL_X_PADDING, 0.)).width - 2. * LABEL_X_PADDING;
+            self.text_layout
+                .set_max_advance(Some(inner_width.max(text_width) as f32));
+            if self.text_layout.needs_rebuild() {
+                self.text_layout.rebuild(ctx.font_ctx());
+            }
         }
         // We ignore trailing whitespace for a label
-        let text_size = self.text_layout.size();
+        let text_size = self.displayed_layout().size();
         let label_size = Size {
             height: text_size.height,
             width: text_size.width + 2. * LABEL_X_PADDING,
@@ -230,17 +466,24 @@ impl Widget for Label {
     }
 
     fn paint(&mut self, ctx: &mut PaintCtx, scene: &mut Scene) {
+        // Only the brush can have changed since the last layout, see `set_displayed_brush`.
         if self.text_layout.needs_rebuild() {
-            debug_panic!("Called Label paint before layout");
+            self.text_layout.rebuild(ctx.font_ctx());
+        }
+        if let Some(truncated_layout) = &mut self.truncated_layout {
+            if truncated_layout.needs_rebuild() {
+                truncated_layout.rebuild(ctx.font_ctx());
+            }
         }
-        if self.line_break_mode == LineBreaking::Clip {
+        let clips = self.clips();
+        if clips {
             let clip_rect = ctx.size().to_rect();
             scene.push_layer(BlendMode::default(), 1., Affine::IDENTITY, &clip_rect);
         }
-        self.text_layout
+        self.displayed_layout()
             .draw(scene, Point::new(LABEL_X_PADDING, 0.0));
 
-        if self.line_break_mode == LineBreaking::Clip {
+        if clips {
             scene.pop_layer();
         }
     }
@@ -270,7 +513,7 @@ mod tests {
 
     use super::*;
     use crate::assert_render_snapshot;
-    use crate::testing::TestHarness;
+    use crate::testing::{widget_ids, TestHarness, TestWidgetExt};
     use crate::theme::{PRIMARY_DARK, PRIMARY_LIGHT};
     use crate::widget::{Flex, SizedBox};
 
@@ -370,4 +613,218 @@ mod tests {
         // We don't use assert_eq because we don't want rich assert
         assert!(image_1 == image_2);
     }
+
+    #[test]
+    fn text_style_line_height() {
+        let [normal, tall] = widget_ids();
+        let text_style = TextStyle {
+            line_height: 2.0,
+            ..TextStyle::default()
+        };
+        let widget = Flex::column()
+            .with_child_id(Label::new("Hello"), normal)
+            .with_child_id(Label::new("Hello").with_text_style(&text_style), tall);
+
+        let harness = TestHarness::create(widget);
+        let height = |id| harness.get_widget(id).state().layout_rect().height();
+        assert!(
+            height(tall) > 1.5 * height(normal),
+            "{} is not taller than {}",
+            height(tall),
+            height(normal)
+        );
+    }
+
+    #[test]
+    fn text_overflow_ellipsis() {
+        let [one_line, two_lines, short, rtl] = widget_ids();
+        let text = "The quick brown fox jumps over the lazy dog";
+        let rtl_text = "שלום עולם שלום עולם שלום עולם";
+        let sized = |label: Label, id| SizedBox::new_with_id(label, id).width(100.0);
+        let widget = Flex::column()
+            .with_child(sized(
+                Label::new(text).with_text_overflow(TextOverflow::Ellipsis),
+                one_line,
+            ))
+            .with_child(sized(
+                Label::new(text).with_text_overflow(TextOverflow::EllipsisLines(2)),
+                two_lines,
+            ))
+            .with_child(sized(
+                Label::new("Fox").with_text_overflow(TextOverflow::Ellipsis),
+                short,
+            ))
+            .with_child(sized(
+                Label::new(rtl_text).with_text_overflow(TextOverflow::Ellipsis),
+                rtl,
+            ));
+
+        let harness = TestHarness::create(widget);
+        let label = |id| {
+            let label = harness.get_widget(id);
+            let label = label.downcast::<Label>().unwrap();
+            let layout = label.truncated_layout.as_ref();
+            let displayed = layout.map(|layout| layout.text().to_string());
+            let lines = layout.map(|layout| layout.layout().len());
+            (displayed, lines)
+        };
+
+        let (displayed, lines) = label(one_line);
+        let displayed = displayed.unwrap();
+        assert!(displayed.ends_with(ELLIPSIS), "{displayed:?}");
+        assert!(text.starts_with(displayed.trim_end_matches(ELLIPSIS)));
+        assert_eq!(lines, Some(1));
+
+        let (displayed_two_lines, lines) = label(two_lines);
+        let displayed_two_lines = displayed_two_lines.unwrap();
+        assert!(displayed_two_lines.len() > displayed.len());
+        assert_eq!(lines, Some(2));
+
+        assert_eq!(label(short), (None, None));
+
+        // Right-to-left text is cut at its logical end.
+        let (displayed, _) = label(rtl);
+        let displayed = displayed.unwrap();
+        assert!(rtl_text.starts_with(displayed.trim_end_matches(ELLIPSIS)));
+    }
+
+    #[test]
+    fn max_lines_clamps_height() {
+        let [clamped, two_lines, one_line] = widget_ids();
+        let widget = Flex::column()
+            .with_child_id(Label::new("One\nTwo\nThree").with_max_lines(2), clamped)
+            .with_child_id(Label::new("One\nTwo"), two_lines)
+            .with_child_id(Label::new("One\nTwo\nThree").with_max_lines(1), one_line);
+
+        let harness = TestHarness::create(widget);
+        let height = |id| harness.get_widget(id).state().layout_rect().height();
+        let displayed = |id| {
+            let label = harness.get_widget(id);
+            let label = label.downcast::<Label>().unwrap();
+            let layout = label.truncated_layout.as_ref().unwrap();
+            layout.text().to_string()
+        };
+
+        // Even without a width to wrap at, the label is only as tall as the kept lines.
+        assert_eq!(height(clamped), height(two_lines));
+        assert_eq!(displayed(clamped), format!("One\nTwo{ELLIPSIS}"));
+        assert_eq!(displayed(one_line), format!("One{ELLIPSIS}"));
+        assert!(height(one_line) < height(two_lines));
+    }
+
+    #[test]
+    fn color_change_only_repaints() {
+        let widget = Flex::column().with_child(Label::new("Hello"));
+
+        let mut harness = TestHarness::create(widget);
+        harness.edit_root_widget(|mut root| {
+            let mut flex = root.downcast::<Flex>();
+            let mut label = flex.child_mut(0).unwrap();
+            let mut label = label.downcast::<Label>();
+
+            label.set_text_brush(PRIMARY_LIGHT);
+            assert!(!label.ctx.widget_state.needs_layout);
+            assert!(label.ctx.widget_state.needs_paint);
+
+            label.set_text_size(30.0);
+            assert!(label.ctx.widget_state.needs_layout);
+        });
+        // The text is rebuilt with the new color when it's painted.
+        let _ = harness.paint_damage();
+    }
+
+    #[test]
+    fn aligned_text_within_label_width() {
+        let [wide, fitted] = widget_ids();
+        let widget = Flex::column()
+            .with_child(
+                SizedBox::new(
+                    Label::new("Right")
+                        .with_text_alignment(Alignment::End)
+                        .with_id(wide),
+                )
+                .width(200.0),
+            )
+            .with_child(
+                Label::new("A longer line\nShort")
+                    .with_text_alignment(Alignment::Middle)
+                    .with_id(fitted),
+            );
+
+        let harness = TestHarness::create(widget);
+        let lines = |id| {
+            let label = harness.get_widget(id);
+            let label = label.downcast::<Label>().unwrap();
+            let layout = label.text_layout.layout();
+            let lines: Vec<_> = layout.lines().map(|line| *line.metrics()).collect();
+            (layout.width() as f64, lines)
+        };
+
+        // In a box wider than the text, the text is aligned within the box.
+        assert_eq!(
+            harness.get_widget(wide).state().layout_rect().width(),
+            200.0
+        );
+        let (text_width, wide_lines) = lines(wide);
+        assert_eq!(wide_lines.len(), 1);
+        let offset = wide_lines[0].offset as f64;
+        assert!((offset + text_width - (200.0 - 2. * LABEL_X_PADDING)).abs() < 0.01);
+
+        // In a label sized to its text, lines are aligned within the widest one.
+        let (text_width, fitted_lines) = lines(fitted);
+        let fitted_width = harness.get_widget(fitted).state().layout_rect().width();
+        assert!((fitted_width - (text_width + 2. * LABEL_X_PADDING)).abs() < 1.0);
+        assert_eq!(fitted_lines.len(), 2);
+        assert!(fitted_lines[0].offset < 1.0);
+        assert!(fitted_lines[1].offset > 10.0);
+    }
+
+    #[test]
+    fn rtl_text_direction() {
+        let [rtl, ltr, mixed] = widget_ids();
+        let hebrew = "שלום עולם";
+        let widget = Flex::column()
+            .with_child(SizedBox::new(Label::new(hebrew).with_id(rtl)).width(200.0))
+            .with_child(
+                SizedBox::new(
+                    Label::new(hebrew)
+                        .with_text_direction(TextDirection::Ltr)
+                        .with_id(ltr),
+                )
+                .width(200.0),
+            )
+            .with_child(Label::new("abc שלום def").with_id(mixed));
+
+        let harness = TestHarness::create(widget);
+        let layout = |id| {
+            let label = harness.get_widget(id);
+            let label = label.downcast::<Label>().unwrap();
+            label.text_layout.clone()
+        };
+
+        // The direction is detected from the text, and right-to-left text is aligned
+        // to the right by default.
+        let rtl_layout = layout(rtl);
+        assert!(rtl_layout.is_rtl());
+        let line = *rtl_layout.layout().get(0).unwrap().metrics();
+        assert!(
+            (line.offset + rtl_layout.layout().width() - (200.0 - 2. * LABEL_X_PADDING as f32))
+                .abs()
+                < 0.01
+        );
+        let ltr_layout = layout(ltr);
+        assert!(!ltr_layout.is_rtl());
+        assert_eq!(ltr_layout.layout().get(0).unwrap().metrics().offset, 0.0);
+
+        // A right-to-left word in left-to-right text is reordered, so its first letter is
+        // on its right.
+        let mixed_layout = layout(mixed);
+        assert!(!mixed_layout.is_rtl());
+        let first = mixed_layout.cursor_for_text_position("abc ".len());
+        let last = mixed_layout.cursor_for_text_position("abc של".len() + "ו".len());
+        assert!(first.is_rtl);
+        assert!(first.offset > last.offset, "{first:?} {last:?}");
+        let after = mixed_layout.cursor_for_text_position("abc שלום ".len());
+        assert!(after.offset > first.offset);
+    }
 }
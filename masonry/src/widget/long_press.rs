@@ -0,0 +1,209 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! A widget which reports when its child is pressed and held.
+
+use std::time::Duration;
+
+use accesskit::Role;
+use smallvec::{smallvec, SmallVec};
+use tracing::{trace, trace_span, Span};
+use vello::Scene;
+use winit::event::MouseButton;
+
+use crate::widget::{WidgetMut, WidgetPod, WidgetRef};
+use crate::{
+    AccessCtx, AccessEvent, Action, BoxConstraints, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx,
+    PaintCtx, Point, PointerEvent, Size, StatusChange, TextEvent, Widget,
+};
+
+/// How far the pointer can move while pressed, in logical pixels, before the press
+/// stops counting as a long press.
+const MAX_MOVE_DISTANCE: f64 = 8.;
+
+/// A container which submits [`Action::LongPressed`] when its child is held down.
+///
+/// The action is submitted once the primary button has stayed pressed for the
+/// configured duration, without the pointer moving more than a few pixels or leaving
+/// the child. The child still receives every event, so a long press on a button also
+/// counts as a click if the button is released over it.
+pub struct LongPress<W: Widget> {
+    child: WidgetPod<W>,
+    duration: Duration,
+    /// Where the press started and for how long it has been held, while waiting for it to
+    /// become a long press.
+    press: Option<(Point, Duration)>,
+}
+
+impl<W: Widget> LongPress<W> {
+    /// How long the child must be pressed, unless set with [`duration`](Self::duration).
+    pub const DEFAULT_DURATION: Duration = Duration::from_millis(500);
+
+    /// Create a new long press detector around `child`.
+    pub fn new(child: W) -> Self {
+        Self::from_child_pod(WidgetPod::new(child))
+    }
+
+    /// Create a new long press detector around a child which has already been wrapped in
+    /// a [`WidgetPod`].
+    pub fn from_child_pod(child: WidgetPod<W>) -> Self {
+        Self {
+            child,
+            duration: Self::DEFAULT_DURATION,
+            press: None,
+        }
+    }
+
+    /// Set how long the child must be pressed.
+    pub fn duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    /// Returns `true` if the child is pressed and may become a long press.
+    pub fn is_pressed(&self) -> bool {
+        self.press.is_some()
+    }
+}
+
+impl<W: Widget> WidgetMut<'_, LongPress<W>> {
+    pub fn child_mut(&mut self) -> WidgetMut<'_, W> {
+        self.ctx.get_mut(&mut self.widget.child)
+    }
+
+    /// Set how long the child must be pressed.
+    pub fn set_duration(&mut self, duration: Duration) {
+        self.widget.duration = duration;
+    }
+}
+
+impl<W: Widget> Widget for LongPress<W> {
+    fn on_pointer_event(&mut self, ctx: &mut EventCtx, event: &PointerEvent) {
+        match event {
+            PointerEvent::PointerDown(MouseButton::Left, state) if !ctx.is_disabled() => {
+                let pos = Point::new(state.position.x, state.position.y);
+                self.press = Some((pos, Duration::ZERO));
+                ctx.request_anim_frame();
+            }
+            PointerEvent::PointerMove(state) => {
+                let pos = Point::new(state.position.x, state.position.y);
+                if let Some((start, _)) = self.press {
+                    if start.distance(pos) > MAX_MOVE_DISTANCE {
+                        trace!("LongPress {:?} cancelled by move", ctx.widget_id());
+                        self.press = None;
+                    }
+                }
+            }
+            PointerEvent::PointerUp(MouseButton::Left, _) | PointerEvent::PointerLeave(_) => {
+                self.press = None;
+            }
+            _ => (),
+        }
+        self.child.on_pointer_event(ctx, event);
+    }
+
+    fn on_text_event(&mut self, ctx: &mut EventCtx, event: &TextEvent) {
+        self.child.on_text_event(ctx, event);
+    }
+
+    fn on_access_event(&mut self, ctx: &mut EventCtx, event: &AccessEvent) {
+        self.child.on_access_event(ctx, event);
+    }
+
+    fn on_status_change(&mut self, _ctx: &mut LifeCycleCtx, event: &StatusChange) {
+        if let StatusChange::HotChanged(false) = event {
+            self.press = None;
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle) {
+        self.child.lifecycle(ctx, event);
+        if let LifeCycle::AnimFrame(interval) = event {
+            let Some((_, held)) = self.press.as_mut() else {
+                return;
+            };
+            *held += Duration::from_nanos(*interval);
+            if *held >= self.duration {
+                trace!("LongPress {:?} long pressed", ctx.widget_id());
+                self.press = None;
+                ctx.submit_action(Action::LongPressed);
+            } else {
+                ctx.request_anim_frame();
+            }
+        }
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints) -> Size {
+        let size = self.child.layout(ctx, bc);
+        ctx.place_child(&mut self.child, Point::ORIGIN);
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, scene: &mut Scene) {
+        self.child.paint(ctx, scene);
+    }
+
+    fn accessibility_role(&self) -> Role {
+        Role::GenericContainer
+    }
+
+    fn accessibility(&mut self, ctx: &mut AccessCtx) {
+        self.child.accessibility(ctx);
+    }
+
+    fn children(&self) -> SmallVec<[WidgetRef<'_, dyn Widget>; 16]> {
+        smallvec![self.child.as_dyn()]
+    }
+
+    fn make_trace_span(&self) -> Span {
+        trace_span!("LongPress")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::WindowEvent;
+    use crate::testing::{widget_ids, TestHarness, TestWidgetExt};
+    use crate::widget::SizedBox;
+
+    #[test]
+    fn held_press_submits_action() {
+        let [press_id] = widget_ids();
+        let widget = LongPress::new(SizedBox::empty().width(40.).height(40.))
+            .duration(Duration::ZERO)
+            .with_id(press_id);
+
+        let mut harness = TestHarness::create(widget);
+        harness.mouse_move_to(press_id);
+        harness.mouse_button_press(MouseButton::Left);
+        assert_eq!(harness.pop_action(), None);
+
+        harness.process_window_event(WindowEvent::AnimFrame);
+        assert_eq!(harness.pop_action(), Some((Action::LongPressed, press_id)));
+
+        // The action is only submitted once per press.
+        harness.process_window_event(WindowEvent::AnimFrame);
+        assert_eq!(harness.pop_action(), None);
+    }
+
+    #[test]
+    fn release_or_move_cancels() {
+        let [press_id] = widget_ids();
+        let widget = LongPress::new(SizedBox::empty().width(40.).height(40.))
+            .duration(Duration::ZERO)
+            .with_id(press_id);
+
+        let mut harness = TestHarness::create(widget);
+        harness.mouse_move((5., 5.));
+        harness.mouse_button_press(MouseButton::Left);
+        harness.mouse_button_release(MouseButton::Left);
+        harness.process_window_event(WindowEvent::AnimFrame);
+        assert_eq!(harness.pop_action(), None);
+
+        harness.mouse_button_press(MouseButton::Left);
+        harness.mouse_move((30., 30.));
+        harness.process_window_event(WindowEvent::AnimFrame);
+        assert_eq!(harness.pop_action(), None);
+    }
+}
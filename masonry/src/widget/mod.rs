@@ -13,43 +13,94 @@ mod widget_state;
 #[cfg(test)]
 mod tests;
 
+mod accessible;
 mod align;
+mod badge;
 mod button;
 mod checkbox;
+mod color_picker;
+mod combo_box;
+mod context_menu;
+mod date_picker;
+mod divider;
+mod drop_target;
+mod dropdown;
 mod flex;
+mod hyperlink;
+mod icon;
 mod image;
 mod label;
+mod long_press;
+mod number_input;
 mod portal;
+mod progress_bar;
 mod prose;
+mod range_slider;
+mod rating;
+mod responsive;
+mod rich_text;
 mod root_widget;
 mod scroll_bar;
 mod sized_box;
+mod spacer;
 mod spinner;
+mod spinner_column;
 mod split;
+mod tag_input;
 mod textbox;
+mod time_picker;
+mod tooltip;
+mod virtual_list;
+mod z_stack;
 
-use crate::CursorIcon;
+use crate::Cursor;
 
 pub use self::image::Image;
+pub use accessible::Accessible;
 pub use align::Align;
+pub use badge::{Badge, BadgeContent, BadgeStyle};
 pub use button::Button;
 pub use checkbox::Checkbox;
+pub use color_picker::{ColorPicker, ColorSwatch};
+pub use combo_box::ComboBox;
+pub use context_menu::ContextMenu;
+pub use date_picker::DatePicker;
+pub use divider::Divider;
+pub use drop_target::DropTarget;
+pub use dropdown::Dropdown;
 pub use flex::{Axis, CrossAxisAlignment, Flex, FlexParams, MainAxisAlignment};
-pub use label::{Label, LineBreaking};
+pub use hyperlink::Hyperlink;
+pub use icon::Icon;
+pub use label::{Label, LineBreaking, TextOverflow};
+pub use long_press::LongPress;
+pub use number_input::NumberInput;
 pub use portal::Portal;
+pub use progress_bar::ProgressBar;
 pub use prose::Prose;
+pub use range_slider::RangeSlider;
+pub use rating::Rating;
+pub use responsive::{BreakpointClass, Breakpoints, Responsive};
+pub use rich_text::RichText;
 pub use root_widget::RootWidget;
 pub use scroll_bar::ScrollBar;
 pub use sized_box::SizedBox;
+pub use spacer::Spacer;
 pub use spinner::Spinner;
+pub use spinner_column::SpinnerColumn;
 pub use split::Split;
+pub use tag_input::TagInput;
 pub use textbox::Textbox;
+pub use time_picker::TimePicker;
+pub use tooltip::Tooltip;
+pub use virtual_list::VirtualList;
 pub use widget_mut::WidgetMut;
 pub use widget_pod::WidgetPod;
 pub use widget_ref::WidgetRef;
 pub use widget_state::WidgetState;
+pub use z_stack::ZStack;
 
 pub use sized_box::BackgroundBrush;
+pub use widget::PressureSensitive;
 #[doc(hidden)]
 pub use widget::{Widget, WidgetId};
 
@@ -60,9 +111,9 @@ pub(crate) enum CursorChange {
     Default,
     /// Someone set a cursor, but if a child widget also set their cursor then we'll use theirs
     /// instead of ours.
-    Set(CursorIcon),
+    Set(Cursor),
     /// Someone set a cursor, and we'll use it regardless of what the children say.
-    Override(CursorIcon),
+    Override(Cursor),
 }
 
 use crate::{Affine, Size};
@@ -90,9 +141,9 @@ pub enum FillStrat {
 
 // TODO
 impl CursorChange {
-    pub fn cursor(&self) -> Option<CursorIcon> {
+    pub fn cursor(&self) -> Option<Cursor> {
         match self {
-            CursorChange::Set(c) | CursorChange::Override(c) => Some(*c),
+            CursorChange::Set(c) | CursorChange::Override(c) => Some(c.clone()),
             CursorChange::Default => None,
         }
     }
@@ -0,0 +1,413 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! A text box for entering a number, with buttons to step it up and down.
+
+use std::ops::RangeInclusive;
+
+use accesskit::Role;
+use kurbo::{BezPath, Line, Point, Rect};
+use smallvec::{smallvec, SmallVec};
+use tracing::{trace, trace_span, Span};
+use vello::Scene;
+use winit::event::MouseButton;
+use winit::keyboard::{Key, NamedKey};
+
+use crate::action::Action;
+use crate::paint_scene_helpers::{fill_color, fill_lin_gradient, stroke, UnitPoint};
+use crate::util::{clamp, finite_bounds};
+use crate::widget::{LineBreaking, Textbox, WidgetMut, WidgetPod, WidgetRef};
+use crate::{
+    theme, AccessCtx, AccessEvent, BoxConstraints, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx,
+    PaintCtx, PointerEvent, Size, StatusChange, TextEvent, Widget,
+};
+
+/// A text box for entering a number within a range, with buttons to step it up and down.
+///
+/// Typed text is committed when Enter is pressed or when the text box loses focus: it is
+/// clamped to the range, and if it isn't a number, the previous value is shown again. The
+/// up and down arrow keys, and the ▲ and ▼ buttons, change the value by the step.
+///
+/// When the value changes, this submits [`Action::NumberChanged`] with the new value.
+pub struct NumberInput {
+    value: f64,
+    min: f64,
+    max: f64,
+    step: f64,
+    textbox: WidgetPod<Textbox>,
+    /// Whether the text box had focus, so that typed text is committed when it loses it.
+    was_editing: bool,
+}
+
+impl NumberInput {
+    /// Create a new number input showing `value`, which can be set to numbers in `range`,
+    /// and is changed by `step` by the arrow keys and buttons.
+    ///
+    /// `value` is clamped to `range`. Bounds which aren't finite are replaced by the
+    /// other bound.
+    pub fn new(value: f64, range: RangeInclusive<f64>, step: f64) -> Self {
+        let (min, max) = finite_bounds(&range);
+        let value = clamp(value, min, max);
+        Self {
+            value,
+            min,
+            max,
+            step: step.abs(),
+            textbox: WidgetPod::new(
+                Textbox::new(format_value(value)).with_line_break_mode(LineBreaking::Clip),
+            ),
+            was_editing: false,
+        }
+    }
+
+    /// The current value.
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    /// Clamp `value` to the range and make it the current value, returning whether the
+    /// value changed.
+    fn update_value(&mut self, value: f64) -> bool {
+        let value = clamp(value, self.min, self.max);
+        let changed = value != self.value;
+        self.value = value;
+        changed
+    }
+
+    /// The value typed in the text box, if it is a number.
+    fn typed_value(&self) -> Option<f64> {
+        let text = self.textbox.widget().text();
+        text.trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|value| !value.is_nan())
+    }
+
+    /// Set the value to `value`, show it in the text box, and submit it if it changed.
+    fn set_value_and_submit(&mut self, ctx: &mut EventCtx, value: f64) {
+        if self.update_value(value) {
+            trace!("NumberInput {:?} set to {}", ctx.widget_id(), self.value);
+            ctx.submit_action(Action::NumberChanged(self.value));
+            ctx.request_accessibility_update();
+            ctx.request_paint();
+        }
+        show_value(ctx.get_mut(&mut self.textbox), self.value);
+    }
+
+    /// The width of the ▲ and ▼ buttons.
+    fn stepper_width() -> f64 {
+        theme::BASIC_WIDGET_HEIGHT
+    }
+}
+
+fn format_value(value: f64) -> String {
+    value.to_string()
+}
+
+/// Show `value` in the text box, selecting it if the text box is focused, so that typing
+/// replaces it.
+fn show_value(mut textbox: WidgetMut<'_, Textbox>, value: f64) {
+    let text = format_value(value);
+    if textbox.ctx.is_focused() {
+        textbox.set_text_properties(|editor| {
+            editor.set_text(text);
+            editor.select_all();
+        });
+    } else {
+        textbox.reset_text(text);
+    }
+}
+
+/// A triangle pointing up or down, filling `rect`.
+fn triangle(rect: Rect, up: bool) -> BezPath {
+    let (tip_y, base_y) = if up {
+        (rect.y0, rect.y1)
+    } else {
+        (rect.y1, rect.y0)
+    };
+    let mut path = BezPath::new();
+    path.move_to((rect.center().x, tip_y));
+    path.line_to((rect.x1, base_y));
+    path.line_to((rect.x0, base_y));
+    path.close_path();
+    path
+}
+
+impl WidgetMut<'_, NumberInput> {
+    /// Set the value.
+    ///
+    /// `value` is clamped to the range. This replaces any text the user is typing.
+    pub fn set_value(&mut self, value: f64) {
+        if self.widget.update_value(value) {
+            show_value(
+                self.ctx.get_mut(&mut self.widget.textbox),
+                self.widget.value,
+            );
+            self.ctx.request_paint();
+            self.ctx.request_accessibility_update();
+        }
+    }
+
+    /// Set the numbers the value can be set to.
+    ///
+    /// The value is clamped to the new range. Bounds which aren't finite are replaced by
+    /// the other bound.
+    pub fn set_range(&mut self, range: RangeInclusive<f64>) {
+        (self.widget.min, self.widget.max) = finite_bounds(&range);
+        let value = self.widget.value;
+        self.set_value(value);
+        // The buttons are greyed out at the ends of the range.
+        self.ctx.request_paint();
+        self.ctx.request_accessibility_update();
+    }
+
+    /// Set how much the arrow keys and buttons change the value.
+    pub fn set_step(&mut self, step: f64) {
+        self.widget.step = step.abs();
+        self.ctx.request_accessibility_update();
+    }
+}
+
+impl Widget for NumberInput {
+    fn on_pointer_event(&mut self, ctx: &mut EventCtx, event: &PointerEvent) {
+        self.textbox.on_pointer_event(ctx, event);
+        if ctx.is_disabled() || ctx.is_handled() {
+            return;
+        }
+
+        if let PointerEvent::PointerDown(MouseButton::Left, state) = event {
+            let size = ctx.size();
+            let local_pos = Point::new(state.position.x, state.position.y) - ctx.window_origin();
+            if local_pos.x >= size.width - Self::stepper_width() {
+                let value = self.typed_value().unwrap_or(self.value);
+                let step = if local_pos.y < size.height / 2. {
+                    self.step
+                } else {
+                    -self.step
+                };
+                self.set_value_and_submit(ctx, value + step);
+                ctx.set_handled();
+            }
+        }
+    }
+
+    fn on_text_event(&mut self, ctx: &mut EventCtx, event: &TextEvent) {
+        if let TextEvent::KeyboardKey(key_event, _) = event {
+            if key_event.state.is_pressed() && !ctx.is_disabled() {
+                let step = match &key_event.logical_key {
+                    Key::Named(NamedKey::ArrowUp) => Some(self.step),
+                    Key::Named(NamedKey::ArrowDown) => Some(-self.step),
+                    Key::Named(NamedKey::Enter) => Some(0.),
+                    _ => None,
+                };
+                if let Some(step) = step {
+                    let value = self.typed_value().unwrap_or(self.value);
+                    self.set_value_and_submit(ctx, value + step);
+                    ctx.set_handled();
+                    return;
+                }
+            }
+        }
+        self.textbox.on_text_event(ctx, event);
+    }
+
+    fn on_access_event(&mut self, ctx: &mut EventCtx, event: &AccessEvent) {
+        if event.target == ctx.widget_id() && !ctx.is_disabled() {
+            match event.action {
+                accesskit::Action::Increment => {
+                    self.set_value_and_submit(ctx, self.value + self.step);
+                }
+                accesskit::Action::Decrement => {
+                    self.set_value_and_submit(ctx, self.value - self.step);
+                }
+                accesskit::Action::SetValue => {
+                    if let Some(value) = event.numeric_value() {
+                        self.set_value_and_submit(ctx, value);
+                    }
+                }
+                _ => {}
+            }
+        }
+        self.textbox.on_access_event(ctx, event);
+    }
+
+    fn on_status_change(&mut self, _ctx: &mut LifeCycleCtx, _event: &StatusChange) {}
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle) {
+        self.textbox.lifecycle(ctx, event);
+
+        // Commit the typed text when the text box loses focus.
+        let editing = self.textbox.has_focus();
+        if self.was_editing && !editing {
+            let value = self.typed_value().unwrap_or(self.value);
+            if self.update_value(value) {
+                ctx.submit_action(Action::NumberChanged(self.value));
+                ctx.request_accessibility_update();
+                ctx.request_paint();
+            }
+            show_value(ctx.get_mut(&mut self.textbox), self.value);
+        }
+        self.was_editing = editing;
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints) -> Size {
+        let width = if bc.is_width_bounded() {
+            bc.max().width
+        } else {
+            theme::WIDE_WIDGET_WIDTH
+        };
+        let text_width = (width - Self::stepper_width()).max(0.);
+        let text_bc = BoxConstraints::new(
+            Size::new(text_width, 0.),
+            Size::new(text_width, f64::INFINITY),
+        );
+        let text_size = self.textbox.layout(ctx, &text_bc);
+        ctx.place_child(&mut self.textbox, Point::ORIGIN);
+
+        let size = bc.constrain(Size::new(
+            text_size.width + Self::stepper_width(),
+            text_size.height.max(theme::BORDERED_WIDGET_HEIGHT),
+        ));
+        trace!("Computed size: {}", size);
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, scene: &mut Scene) {
+        self.textbox.paint(ctx, scene);
+
+        let size = ctx.size();
+        let stepper = Rect::new(
+            size.width - Self::stepper_width(),
+            0.,
+            size.width,
+            size.height,
+        )
+        .inset(-0.5);
+        let theme = ctx.theme();
+        fill_lin_gradient(
+            scene,
+            &stepper,
+            theme.surface,
+            UnitPoint::TOP,
+            UnitPoint::BOTTOM,
+        );
+        stroke(scene, &stepper, theme.border_hover, 1.);
+        let middle = stepper.center().y;
+        stroke(
+            scene,
+            &Line::new((stepper.x0, middle), (stepper.x1, middle)),
+            theme.border_hover,
+            1.,
+        );
+
+        // The arrows are greyed out when they can't change the value.
+        let arrow_color = |enabled: bool| {
+            if enabled && !ctx.is_disabled() {
+                theme.on_surface
+            } else {
+                theme.on_surface_disabled
+            }
+        };
+        let arrow_size = Size::new(8., 5.);
+        let up = Rect::from_center_size((stepper.center().x, middle / 2.), arrow_size);
+        let down =
+            Rect::from_center_size((stepper.center().x, (middle + stepper.y1) / 2.), arrow_size);
+        fill_color(
+            scene,
+            &triangle(up, true),
+            arrow_color(self.value < self.max),
+        );
+        fill_color(
+            scene,
+            &triangle(down, false),
+            arrow_color(self.value > self.min),
+        );
+    }
+
+    fn accessibility_role(&self) -> Role {
+        Role::SpinButton
+    }
+
+    fn accessibility(&mut self, ctx: &mut AccessCtx) {
+        let node = ctx.current_node();
+        node.set_numeric_value(self.value);
+        node.set_min_numeric_value(self.min);
+        node.set_max_numeric_value(self.max);
+        if self.step > 0. {
+            node.set_numeric_value_step(self.step);
+        }
+        node.add_action(accesskit::Action::Increment);
+        node.add_action(accesskit::Action::Decrement);
+        node.add_action(accesskit::Action::SetValue);
+        self.textbox.accessibility(ctx);
+    }
+
+    fn children(&self) -> SmallVec<[WidgetRef<'_, dyn Widget>; 16]> {
+        smallvec![self.textbox.as_dyn()]
+    }
+
+    fn make_trace_span(&self) -> Span {
+        trace_span!("NumberInput")
+    }
+
+    fn get_debug_text(&self) -> Option<String> {
+        Some(format_value(self.value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{widget_ids, TestHarness, TestWidgetExt};
+    use crate::widget::Flex;
+
+    fn text(harness: &TestHarness, id: crate::WidgetId) -> String {
+        harness.get_widget(id).children()[0]
+            .downcast::<Textbox>()
+            .unwrap()
+            .deref()
+            .text()
+            .to_string()
+    }
+
+    #[test]
+    fn nan_bounds_are_replaced() {
+        let input = NumberInput::new(3.0, 0.0..=f64::NAN, 1.0);
+        assert_eq!(input.value(), 0.0);
+    }
+
+    #[test]
+    fn typed_value_is_clamped() {
+        let [input_id, other_id] = widget_ids();
+        let widget = Flex::column()
+            .with_child(NumberInput::new(5., 0.0..=10.0, 1.).with_id(input_id))
+            .with_child_id(Textbox::new("Other"), other_id);
+
+        let mut harness = TestHarness::create(widget);
+        let rect = harness.get_widget(input_id).state().window_layout_rect();
+
+        // The top half of the stepper steps up, and the bottom half steps down.
+        harness.mouse_move((rect.x1 - 4., rect.y0 + 4.));
+        harness.mouse_button_press(MouseButton::Left);
+        harness.mouse_button_release(MouseButton::Left);
+        assert_eq!(
+            harness.pop_action(),
+            Some((Action::NumberChanged(6.), input_id))
+        );
+        assert_eq!(text(&harness, input_id), "6");
+
+        // Typed text is committed when the text box loses focus.
+        harness.mouse_move((rect.x0 + 20., rect.center().y));
+        harness.mouse_button_press(MouseButton::Left);
+        harness.mouse_button_release(MouseButton::Left);
+        harness.keyboard_type_chars("0");
+        assert_eq!(text(&harness, input_id), "60");
+        while harness.pop_action().is_some() {}
+        harness.mouse_click_on(other_id);
+        assert_eq!(
+            harness.pop_action(),
+            Some((Action::NumberChanged(10.), input_id))
+        );
+        assert_eq!(text(&harness, input_id), "10");
+    }
+}
@@ -12,11 +12,11 @@ use tracing::{trace_span, Span};
 use vello::peniko::BlendMode;
 use vello::Scene;
 
-use crate::kurbo::{Point, Rect, Size, Vec2};
+use crate::kurbo::{Point, Rect, RoundedRectRadii, Size, Vec2};
 use crate::widget::{Axis, ScrollBar, WidgetMut, WidgetRef};
 use crate::{
-    AccessCtx, AccessEvent, BoxConstraints, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx,
-    PointerEvent, StatusChange, TextEvent, Widget, WidgetPod,
+    theme, AccessCtx, AccessEvent, BoxConstraints, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx,
+    PaintCtx, PointerEvent, StatusChange, TextEvent, Widget, WidgetPod,
 };
 
 // TODO - refactor - see issue #15
@@ -34,10 +34,13 @@ pub struct Portal<W: Widget> {
     constrain_horizontal: bool,
     constrain_vertical: bool,
     must_fill: bool,
+    corner_radius: RoundedRectRadii,
     scrollbar_horizontal: WidgetPod<ScrollBar>,
     scrollbar_horizontal_visible: bool,
     scrollbar_vertical: WidgetPod<ScrollBar>,
     scrollbar_vertical_visible: bool,
+    /// The distance scrolled for each line of [`ScrollDelta::Lines`](crate::ScrollDelta::Lines).
+    line_height: f64,
 }
 
 impl<W: Widget> Portal<W> {
@@ -48,11 +51,13 @@ impl<W: Widget> Portal<W> {
             constrain_horizontal: false,
             constrain_vertical: false,
             must_fill: false,
+            corner_radius: RoundedRectRadii::from_single_radius(0.0),
             // TODO - remove
             scrollbar_horizontal: WidgetPod::new(ScrollBar::new(Axis::Horizontal, 1.0, 1.0)),
             scrollbar_horizontal_visible: false,
             scrollbar_vertical: WidgetPod::new(ScrollBar::new(Axis::Vertical, 1.0, 1.0)),
             scrollbar_vertical_visible: false,
+            line_height: theme::SCROLL_LINE_HEIGHT,
         }
     }
 
@@ -101,6 +106,24 @@ impl<W: Widget> Portal<W> {
         self.must_fill = must_fill;
         self
     }
+
+    /// Builder-style method for rounding off the corners of the visible area.
+    ///
+    /// The content is clipped to the rounded rectangle. This can be a single radius
+    /// for all corners, or a different radius for each corner.
+    pub fn with_corner_radius(mut self, radius: impl Into<RoundedRectRadii>) -> Self {
+        self.corner_radius = radius.into();
+        self
+    }
+
+    /// Builder-style method for setting how far a mouse wheel scrolls for each line.
+    ///
+    /// Defaults to [`theme::SCROLL_LINE_HEIGHT`]. The number of lines scrolled for each
+    /// step of the wheel comes from the platform's settings.
+    pub fn with_line_height(mut self, line_height: f64) -> Self {
+        self.line_height = line_height;
+        self
+    }
 }
 
 fn compute_pan_range(mut viewport: Range<f64>, target: Range<f64>) -> Range<f64> {
@@ -193,6 +216,17 @@ impl<W: Widget> WidgetMut<'_, Portal<W>> {
         self.ctx.request_layout();
     }
 
+    /// Round off the corners of the visible area.
+    pub fn set_corner_radius(&mut self, radius: impl Into<RoundedRectRadii>) {
+        self.widget.corner_radius = radius.into();
+        self.ctx.request_paint();
+    }
+
+    /// Set how far a mouse wheel scrolls for each line.
+    pub fn set_line_height(&mut self, line_height: f64) {
+        self.widget.line_height = line_height;
+    }
+
     pub fn set_viewport_pos(&mut self, position: Point) -> bool {
         let portal_size = self.ctx.widget_state.layout_rect().size();
         let content_size = self.widget.child.layout_rect().size();
@@ -240,22 +274,30 @@ impl<W: Widget> Widget for Portal<W> {
         let portal_size = ctx.size();
         let content_size = self.child.layout_rect().size();
 
+        self.child.on_pointer_event(ctx, event);
+
         match event {
-            PointerEvent::MouseWheel(delta, _) => {
-                self.set_viewport_pos_raw(
+            // A scrolling widget inside the child gets the wheel first, and only lets it
+            // through once it can't scroll any further, so that nested portals chain.
+            PointerEvent::MouseWheel(delta, _) if !ctx.is_handled() => {
+                let scrolled = self.set_viewport_pos_raw(
                     portal_size,
                     content_size,
-                    self.viewport_pos + Vec2::new(delta.x, delta.y),
+                    self.viewport_pos + delta.to_pixels(self.line_height),
                 );
-                // TODO - horizontal scrolling?
-                ctx.get_mut(&mut self.scrollbar_vertical)
-                    .set_cursor_progress(self.viewport_pos.y / (content_size - portal_size).height);
-                ctx.request_layout();
+                if scrolled {
+                    // TODO - horizontal scrolling?
+                    ctx.get_mut(&mut self.scrollbar_vertical)
+                        .set_cursor_progress(
+                            self.viewport_pos.y / (content_size - portal_size).height,
+                        );
+                    ctx.request_layout();
+                    ctx.set_handled();
+                }
             }
             _ => (),
         }
 
-        self.child.on_pointer_event(ctx, event);
         self.scrollbar_horizontal.on_pointer_event(ctx, event);
         self.scrollbar_vertical.on_pointer_event(ctx, event);
 
@@ -370,7 +412,7 @@ impl<W: Widget> Widget for Portal<W> {
 
     fn paint(&mut self, ctx: &mut PaintCtx, scene: &mut Scene) {
         // TODO - also clip the invalidated region
-        let clip_rect = ctx.size().to_rect();
+        let clip_rect = ctx.size().to_rounded_rect(self.corner_radius);
 
         scene.push_layer(BlendMode::default(), 1., Affine::IDENTITY, &clip_rect);
         self.child.paint(ctx, scene);
@@ -428,8 +470,10 @@ mod tests {
 
     use super::*;
     use crate::assert_render_snapshot;
+    use crate::event::PointerState;
     use crate::testing::{widget_ids, TestHarness};
     use crate::widget::{Button, Flex, SizedBox};
+    use crate::ScrollDelta;
 
     fn button(text: &'static str) -> impl Widget {
         SizedBox::new(Button::new(text)).width(70.0).height(40.0)
@@ -502,6 +546,71 @@ mod tests {
         assert_render_snapshot!(harness, "button_list_scroll_to_item_13");
     }
 
+    #[test]
+    fn wheel_lines_scroll_by_line_height() {
+        let widget =
+            Portal::new(SizedBox::empty().width(100.0).height(1000.0)).with_line_height(10.0);
+
+        let mut harness = TestHarness::create_with_size(widget, Size::new(100., 100.));
+        harness.process_pointer_event(PointerEvent::MouseWheel(
+            ScrollDelta::Lines(Vec2::new(0.0, 3.0)),
+            PointerState::empty(),
+        ));
+        let viewport_pos = |harness: &TestHarness| {
+            let portal = harness.root_widget();
+            portal
+                .downcast::<Portal<SizedBox>>()
+                .unwrap()
+                .get_viewport_pos()
+        };
+        assert_eq!(viewport_pos(&harness), Point::new(0.0, 30.0));
+
+        // Pixel deltas ignore the line height.
+        harness.mouse_wheel(Vec2::new(0.0, 5.0));
+        assert_eq!(viewport_pos(&harness), Point::new(0.0, 35.0));
+    }
+
+    #[test]
+    fn nested_portals_chain_wheel_scrolling() {
+        let [inner] = widget_ids();
+        let inner_portal = Portal::new(SizedBox::empty().width(100.0).height(100.0));
+        let widget = Portal::new(
+            Flex::column()
+                .with_child(SizedBox::empty().width(100.0).height(50.0))
+                .with_child(
+                    SizedBox::from_child_pod(WidgetPod::new_with_id(Box::new(inner_portal), inner))
+                        .height(50.0),
+                )
+                .with_child(SizedBox::empty().width(100.0).height(200.0)),
+        );
+
+        let mut harness = TestHarness::create_with_size(widget, Size::new(100., 100.));
+        let viewport_pos = |harness: &TestHarness| {
+            let outer = harness.root_widget();
+            let outer = outer.downcast::<Portal<Flex>>().unwrap();
+            let inner = harness.get_widget(inner);
+            let inner = inner.downcast::<Portal<SizedBox>>().unwrap();
+            (inner.get_viewport_pos().y, outer.get_viewport_pos().y)
+        };
+
+        // The inner portal scrolls until it reaches its end, and only then the outer one.
+        harness.mouse_move_to(inner);
+        harness.mouse_wheel(Vec2::new(0.0, 30.0));
+        assert_eq!(viewport_pos(&harness), (30.0, 0.0));
+        harness.mouse_wheel(Vec2::new(0.0, 30.0));
+        assert_eq!(viewport_pos(&harness), (50.0, 0.0));
+        harness.mouse_wheel(Vec2::new(0.0, 10.0));
+        assert_eq!(viewport_pos(&harness), (50.0, 10.0));
+
+        // The same goes for scrolling back up.
+        harness.mouse_wheel(Vec2::new(0.0, -30.0));
+        assert_eq!(viewport_pos(&harness), (20.0, 10.0));
+        harness.mouse_wheel(Vec2::new(0.0, -30.0));
+        assert_eq!(viewport_pos(&harness), (0.0, 10.0));
+        harness.mouse_wheel(Vec2::new(0.0, -30.0));
+        assert_eq!(viewport_pos(&harness), (0.0, 0.0));
+    }
+
     // Helper function for panning tests
     fn make_range(repr: &str) -> Range<f64> {
         let repr = &repr[repr.find('_').unwrap()..];
@@ -0,0 +1,163 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! A progress bar widget.
+
+use accesskit::Role;
+use smallvec::SmallVec;
+use tracing::{trace, trace_span, Span};
+use vello::Scene;
+
+use crate::paint_scene_helpers::{fill_lin_gradient, stroke, UnitPoint};
+use crate::widget::{WidgetMut, WidgetRef};
+use crate::{
+    theme, AccessCtx, AccessEvent, BoxConstraints, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx,
+    PaintCtx, PointerEvent, Rect, Size, StatusChange, TextEvent, Widget,
+};
+
+/// A horizontal bar showing how much of a task is done.
+///
+/// For tasks whose progress isn't known, use a [`Spinner`](super::Spinner) instead.
+pub struct ProgressBar {
+    /// A value between 0 and 1.
+    progress: f64,
+}
+
+impl ProgressBar {
+    /// Create a new progress bar, filled up to `progress`.
+    ///
+    /// `progress` is clamped to the range `[0, 1]`.
+    pub fn new(progress: f64) -> Self {
+        Self {
+            progress: clamp_progress(progress),
+        }
+    }
+
+    /// The fraction of the bar which is filled, between 0 and 1.
+    pub fn progress(&self) -> f64 {
+        self.progress
+    }
+}
+
+fn clamp_progress(progress: f64) -> f64 {
+    if progress.is_nan() {
+        0.0
+    } else {
+        progress.clamp(0.0, 1.0)
+    }
+}
+
+impl WidgetMut<'_, ProgressBar> {
+    /// Set the fraction of the bar which is filled.
+    ///
+    /// `progress` is clamped to the range `[0, 1]`.
+    pub fn set_progress(&mut self, progress: f64) {
+        let progress = clamp_progress(progress);
+        if progress != self.widget.progress {
+            self.widget.progress = progress;
+            self.ctx.request_paint();
+            self.ctx.request_accessibility_update();
+        }
+    }
+}
+
+impl Widget for ProgressBar {
+    fn on_pointer_event(&mut self, _ctx: &mut EventCtx, _event: &PointerEvent) {}
+
+    fn on_text_event(&mut self, _ctx: &mut EventCtx, _event: &TextEvent) {}
+
+    fn on_access_event(&mut self, _ctx: &mut EventCtx, _event: &AccessEvent) {}
+
+    fn on_status_change(&mut self, _ctx: &mut LifeCycleCtx, _event: &StatusChange) {}
+
+    fn lifecycle(&mut self, _ctx: &mut LifeCycleCtx, _event: &LifeCycle) {}
+
+    fn layout(&mut self, _ctx: &mut LayoutCtx, bc: &BoxConstraints) -> Size {
+        let width = if bc.is_width_bounded() {
+            bc.max().width
+        } else {
+            theme::WIDE_WIDGET_WIDTH
+        };
+        let size = bc.constrain(Size::new(width, theme::BASIC_WIDGET_HEIGHT));
+
+        trace!("Computed size: {}", size);
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, scene: &mut Scene) {
+        let border_width = 1.;
+        let track = ctx
+            .size()
+            .to_rect()
+            .inset(-border_width / 2.)
+            .to_rounded_rect(theme::PROGRESS_BAR_RADIUS);
+
+        fill_lin_gradient(
+            scene,
+            &track,
+            [theme::BACKGROUND_LIGHT, theme::BACKGROUND_DARK],
+            UnitPoint::TOP,
+            UnitPoint::BOTTOM,
+        );
+        stroke(scene, &track, theme::BORDER_DARK, border_width);
+
+        if self.progress > 0.0 {
+            let track_rect = track.rect();
+            let filled = Rect::new(
+                track_rect.x0,
+                track_rect.y0,
+                track_rect.x0 + track_rect.width() * self.progress,
+                track_rect.y1,
+            )
+            .to_rounded_rect(theme::PROGRESS_BAR_RADIUS);
+            fill_lin_gradient(
+                scene,
+                &filled,
+                [theme::PRIMARY_LIGHT, theme::PRIMARY_DARK],
+                UnitPoint::TOP,
+                UnitPoint::BOTTOM,
+            );
+        }
+    }
+
+    fn accessibility_role(&self) -> Role {
+        Role::ProgressIndicator
+    }
+
+    fn accessibility(&mut self, ctx: &mut AccessCtx) {
+        let node = ctx.current_node();
+        node.set_numeric_value(self.progress);
+        node.set_min_numeric_value(0.0);
+        node.set_max_numeric_value(1.0);
+        node.set_value(format!("{:.0}%", self.progress * 100.0));
+    }
+
+    fn children(&self) -> SmallVec<[WidgetRef<'_, dyn Widget>; 16]> {
+        SmallVec::new()
+    }
+
+    fn make_trace_span(&self) -> Span {
+        trace_span!("ProgressBar")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TestHarness;
+
+    #[test]
+    fn progress_is_clamped() {
+        assert_eq!(ProgressBar::new(-0.5).progress(), 0.0);
+        assert_eq!(ProgressBar::new(0.25).progress(), 0.25);
+        assert_eq!(ProgressBar::new(1.5).progress(), 1.0);
+        assert_eq!(ProgressBar::new(f64::NAN).progress(), 0.0);
+
+        let mut harness = TestHarness::create(ProgressBar::new(0.5));
+        harness.edit_root_widget(|mut bar| {
+            bar.downcast::<ProgressBar>().set_progress(3.0);
+        });
+        let bar = harness.root_widget();
+        assert_eq!(bar.downcast::<ProgressBar>().unwrap().progress(), 1.0);
+    }
+}
@@ -1,7 +1,9 @@
 // Copyright 2018 the Xilem Authors and the Druid Authors
 // SPDX-License-Identifier: Apache-2.0
 
-use accesskit::Role;
+use std::ops::Range;
+
+use accesskit::{NodeBuilder, Role, TextPosition, TextSelection};
 use kurbo::{Affine, Point, Size};
 use parley::{
     layout::Alignment,
@@ -9,13 +11,14 @@ use parley::{
 };
 use smallvec::SmallVec;
 use tracing::trace;
+use unicode_segmentation::UnicodeSegmentation;
 use vello::{peniko::BlendMode, Scene};
 
 use crate::{
-    text2::{TextBrush, TextStorage, TextWithSelection},
+    text2::{TextBrush, TextDirection, TextStorage, TextStyle, TextWithSelection},
     widget::label::LABEL_X_PADDING,
     AccessCtx, AccessEvent, ArcStr, BoxConstraints, CursorIcon, EventCtx, LayoutCtx, LifeCycle,
-    LifeCycleCtx, PaintCtx, PointerEvent, StatusChange, TextEvent, Widget,
+    LifeCycleCtx, PaintCtx, PointerEvent, StatusChange, TextEvent, Widget, WidgetId,
 };
 
 use super::{LineBreaking, WidgetMut, WidgetRef};
@@ -32,6 +35,9 @@ pub struct Prose {
     line_break_mode: LineBreaking,
     show_disabled: bool,
     brush: TextBrush,
+    selectable: bool,
+    /// The accessibility node holding the text, which the selection refers to.
+    text_node_id: WidgetId,
 }
 
 impl Prose {
@@ -41,6 +47,8 @@ impl Prose {
             line_break_mode: LineBreaking::WordWrap,
             show_disabled: true,
             brush: crate::theme::TEXT_COLOR.into(),
+            selectable: true,
+            text_node_id: WidgetId::next(),
         }
     }
 
@@ -49,6 +57,14 @@ impl Prose {
         self.text_layout.text()
     }
 
+    /// The byte range of the selected text, if any is selected.
+    pub fn selection(&self) -> Option<Range<usize>> {
+        self.text_layout
+            .selection
+            .filter(|selection| !selection.is_caret())
+            .map(|selection| selection.range())
+    }
+
     #[doc(alias = "with_text_color")]
     pub fn with_text_brush(mut self, brush: impl Into<TextBrush>) -> Self {
         self.brush = brush.into();
@@ -66,6 +82,31 @@ impl Prose {
         self
     }
 
+    /// Set the base direction of the text, which decides which side it's aligned to.
+    ///
+    /// By default, the direction is that of the first strongly directional character.
+    pub fn with_text_direction(mut self, direction: TextDirection) -> Self {
+        self.text_layout.set_text_direction(direction);
+        self
+    }
+
+    /// Set the height of each line, as a multiple of the line height of the font.
+    ///
+    /// The default is `1.0`. This overrides the line height of a previous
+    /// [`with_text_style`](Self::with_text_style).
+    pub fn with_line_height(mut self, line_height: f32) -> Self {
+        self.text_layout.set_line_height(line_height);
+        self
+    }
+
+    /// Set the extra space added after each character, in logical pixels.
+    ///
+    /// This is an absolute length, which can be negative. The default is `0.0`.
+    pub fn with_letter_spacing(mut self, letter_spacing: f32) -> Self {
+        self.text_layout.set_letter_spacing(letter_spacing);
+        self
+    }
+
     pub fn with_font(mut self, font: FontStack<'static>) -> Self {
         self.text_layout.set_font(font);
         self
@@ -78,6 +119,21 @@ impl Prose {
         self.line_break_mode = line_break_mode;
         self
     }
+
+    /// Set the font, size, weight, color and line height of the text at once.
+    pub fn with_text_style(mut self, text_style: &TextStyle) -> Self {
+        self.brush = text_style.color.into();
+        self.text_layout.set_text_style(text_style);
+        self
+    }
+
+    /// Set whether the user can select the text, and copy it with Ctrl+C (Cmd+C on macOS).
+    ///
+    /// Text is selectable by default.
+    pub fn with_selectable(mut self, selectable: bool) -> Self {
+        self.selectable = selectable;
+        self
+    }
 }
 
 impl WidgetMut<'_, Prose> {
@@ -123,6 +179,18 @@ impl WidgetMut<'_, Prose> {
     pub fn set_alignment(&mut self, alignment: Alignment) {
         self.set_text_properties(|layout| layout.set_text_alignment(alignment));
     }
+    /// Set the base direction of the text.
+    pub fn set_text_direction(&mut self, direction: TextDirection) {
+        self.set_text_properties(|layout| layout.set_text_direction(direction));
+    }
+    /// Set the height of each line, as a multiple of the line height of the font.
+    pub fn set_line_height(&mut self, line_height: f32) {
+        self.set_text_properties(|layout| layout.set_line_height(line_height));
+    }
+    /// Set the extra space added after each character, in logical pixels.
+    pub fn set_letter_spacing(&mut self, letter_spacing: f32) {
+        self.set_text_properties(|layout| layout.set_letter_spacing(letter_spacing));
+    }
     pub fn set_font(&mut self, font_stack: FontStack<'static>) {
         self.set_text_properties(|layout| layout.set_font(font_stack));
     }
@@ -133,6 +201,31 @@ impl WidgetMut<'_, Prose> {
         self.widget.line_break_mode = line_break_mode;
         self.ctx.request_paint();
     }
+
+    /// Set the font, size, weight, color and line height of the text at once.
+    pub fn set_text_style(&mut self, text_style: &TextStyle) {
+        self.widget.brush = text_style.color.into();
+        let brush = self.widget.brush.clone();
+        let disabled = self.ctx.is_disabled() && self.widget.show_disabled;
+        self.set_text_properties(|layout| {
+            layout.set_text_style(text_style);
+            if disabled {
+                layout.set_brush(crate::theme::DISABLED_TEXT_COLOR);
+            } else {
+                layout.set_brush(brush);
+            }
+        });
+    }
+
+    /// Set whether the user can select the text. Making it unselectable clears the selection.
+    pub fn set_selectable(&mut self, selectable: bool) {
+        self.widget.selectable = selectable;
+        if !selectable {
+            self.widget.text_layout.focus_lost();
+            self.ctx.request_layout();
+            self.ctx.request_accessibility_update();
+        }
+    }
 }
 
 impl Widget for Prose {
@@ -141,25 +234,29 @@ impl Widget for Prose {
         let inner_origin = Point::new(window_origin.x + LABEL_X_PADDING, window_origin.y);
         match event {
             PointerEvent::PointerDown(button, state) => {
-                if !ctx.is_disabled() {
+                if !ctx.is_disabled() && self.selectable {
                     // TODO: Start tracking currently pressed link?
                     let made_change = self.text_layout.pointer_down(inner_origin, state, *button);
                     if made_change {
                         ctx.request_layout();
                         ctx.request_paint();
+                        ctx.request_accessibility_update();
                         ctx.request_focus();
                         ctx.set_active(true);
                     }
                 }
             }
             PointerEvent::PointerMove(state) => {
-                if !ctx.is_disabled() {
+                if !self.selectable {
+                    ctx.clear_cursor();
+                } else if !ctx.is_disabled() {
                     // TODO: Set cursor if over link
                     ctx.set_cursor(&CursorIcon::Text);
                     if ctx.is_active() && self.text_layout.pointer_move(inner_origin, state) {
                         // We might have changed text colours, so we need to re-request a layout
                         ctx.request_layout();
                         ctx.request_paint();
+                        ctx.request_accessibility_update();
                     }
                 }
             }
@@ -178,6 +275,9 @@ impl Widget for Prose {
     }
 
     fn on_text_event(&mut self, ctx: &mut EventCtx, event: &TextEvent) {
+        if !self.selectable {
+            return;
+        }
         // If focused on a link and enter pressed, follow it?
         let result = self.text_layout.text_event(event);
         if result.is_handled() {
@@ -185,6 +285,7 @@ impl Widget for Prose {
             // TODO: only some handlers need this repaint
             ctx.request_layout();
             ctx.request_paint();
+            ctx.request_accessibility_update();
         }
     }
 
@@ -198,6 +299,7 @@ impl Widget for Prose {
             StatusChange::FocusChanged(false) => {
                 self.text_layout.focus_lost();
                 ctx.request_layout();
+                ctx.request_accessibility_update();
                 // TODO: Stop focusing on any links
             }
             StatusChange::FocusChanged(true) => {
@@ -284,8 +386,33 @@ impl Widget for Prose {
     }
 
     fn accessibility(&mut self, ctx: &mut AccessCtx) {
-        ctx.current_node()
-            .set_name(self.text().as_str().to_string());
+        let text = self.text().as_str();
+        ctx.current_node().set_name(text.to_string());
+
+        // Assistive technologies find the selected text through a child node holding the
+        // text, whose characters are grapheme clusters.
+        let mut text_node = NodeBuilder::new(Role::InlineTextBox);
+        text_node.set_value(text.to_string());
+        let character_lengths: Vec<u8> = text
+            .graphemes(true)
+            .map(|grapheme| u8::try_from(grapheme.len()).unwrap_or(u8::MAX))
+            .collect();
+        text_node.set_character_lengths(character_lengths);
+        ctx.tree_update
+            .nodes
+            .push((self.text_node_id.into(), text_node.build()));
+        ctx.current_node().push_child(self.text_node_id.into());
+
+        if let Some(selection) = self.text_layout.selection {
+            let position = |offset: usize| TextPosition {
+                node: self.text_node_id.into(),
+                character_index: text[..offset].graphemes(true).count(),
+            };
+            ctx.current_node().set_text_selection(TextSelection {
+                anchor: position(selection.anchor),
+                focus: position(selection.active),
+            });
+        }
     }
 
     fn children(&self) -> SmallVec<[WidgetRef<'_, dyn Widget>; 16]> {
@@ -296,3 +423,92 @@ impl Widget for Prose {
         Some(self.text_layout.text().as_str().chars().take(100).collect())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use winit::event::MouseButton;
+
+    use super::*;
+    use crate::testing::{widget_ids, TestHarness};
+    use crate::widget::{Flex, SizedBox};
+
+    fn drag_across(harness: &mut TestHarness) {
+        harness.mouse_move((LABEL_X_PADDING, 5.0));
+        harness.mouse_button_press(MouseButton::Left);
+        harness.mouse_move((1000.0, 5.0));
+        harness.mouse_button_release(MouseButton::Left);
+    }
+
+    fn selection(harness: &TestHarness) -> Option<Range<usize>> {
+        harness
+            .root_widget()
+            .downcast::<Prose>()
+            .unwrap()
+            .deref()
+            .selection()
+    }
+
+    #[test]
+    fn drag_selects_text() {
+        let mut harness = TestHarness::create(Prose::new("Hello world"));
+        assert_eq!(selection(&harness), None);
+
+        drag_across(&mut harness);
+        assert_eq!(selection(&harness), Some(0..11));
+    }
+
+    #[test]
+    fn unselectable_text() {
+        let mut harness = TestHarness::create(Prose::new("Hello world").with_selectable(false));
+        drag_across(&mut harness);
+        assert_eq!(selection(&harness), None);
+        assert_eq!(harness.cursor(), &crate::Cursor::default());
+    }
+
+    #[test]
+    fn double_click_selects_word() {
+        let mut harness = TestHarness::create(Prose::new("Hello world"));
+        // Past the end of "world".
+        harness.mouse_move((200.0, 5.0));
+        harness.mouse_button_press(MouseButton::Left);
+        harness.mouse_button_release(MouseButton::Left);
+        assert_eq!(selection(&harness), None);
+
+        harness.mouse_button_press(MouseButton::Left);
+        harness.mouse_button_release(MouseButton::Left);
+        assert_eq!(selection(&harness), Some(6..11));
+
+        harness.mouse_button_press(MouseButton::Left);
+        harness.mouse_button_release(MouseButton::Left);
+        assert_eq!(selection(&harness), Some(0..11));
+    }
+
+    #[test]
+    fn line_height_and_letter_spacing() {
+        let [normal, tall, narrow, wide] = widget_ids();
+        let text = "The quick brown fox jumps over the lazy dog";
+        let paragraph = |prose: Prose, id| SizedBox::new_with_id(prose, id).width(100.0);
+        let widget = Flex::column()
+            .with_child(paragraph(Prose::new(text), normal))
+            .with_child(paragraph(Prose::new(text).with_line_height(1.5), tall))
+            .with_child_id(Prose::new("Hello"), narrow)
+            .with_child_id(Prose::new("Hello").with_letter_spacing(10.0), wide);
+
+        let harness = TestHarness::create(widget);
+        let size = |id| harness.get_widget(id).state().layout_rect().size();
+
+        // The lines break at the same places, so the paragraph is about 1.5 times as tall;
+        // the height of each line is rounded to whole pixels.
+        let (normal, tall) = (size(normal).height, size(tall).height);
+        assert!(
+            (tall / normal - 1.5).abs() < 0.1,
+            "{tall} is not 1.5 times {normal}"
+        );
+        // Each of the five characters is followed by 10 more pixels.
+        let (narrow, wide) = (size(narrow).width, size(wide).width);
+        assert!(
+            (wide - narrow - 50.0).abs() < 1.0,
+            "{wide} is not 50 more than {narrow}"
+        );
+    }
+}
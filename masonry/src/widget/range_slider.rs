@@ -0,0 +1,633 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! A slider with two thumbs, for picking a range of values.
+
+use std::ops::RangeInclusive;
+
+use accesskit::Role;
+use kurbo::{Circle, Point, Rect};
+use smallvec::{smallvec, SmallVec};
+use tracing::{trace, trace_span, Span};
+use vello::Scene;
+use winit::event::MouseButton;
+use winit::keyboard::{Key, NamedKey};
+
+use crate::action::Action;
+use crate::paint_scene_helpers::{fill_color, fill_lin_gradient, stroke, UnitPoint};
+use crate::util::{clamp, finite_bounds};
+use crate::widget::{WidgetMut, WidgetPod, WidgetRef};
+use crate::{
+    theme, AccessCtx, AccessEvent, BoxConstraints, CursorIcon, EventCtx, LayoutCtx, LifeCycle,
+    LifeCycleCtx, PaintCtx, PointerEvent, Size, StatusChange, TextEvent, Widget,
+};
+
+/// The height of the track the thumbs slide along.
+const TRACK_HEIGHT: f64 = 4.0;
+
+/// A horizontal slider with two thumbs, for picking a range of values between two bounds.
+///
+/// The thumbs can be dragged independently, but can't cross each other, and the track
+/// between them is highlighted. Each thumb can be focused, and moved with the arrow keys,
+/// Home and End, and is its own accessibility node.
+///
+/// When the user moves a thumb, this submits [`Action::RangeChanged`] with the new range.
+pub struct RangeSlider {
+    low: f64,
+    high: f64,
+    min: f64,
+    max: f64,
+    step: f64,
+    dragging: Option<ThumbKind>,
+    lower: WidgetPod<Thumb>,
+    upper: WidgetPod<Thumb>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ThumbKind {
+    Lower,
+    Upper,
+}
+
+/// One of the thumbs of a [`RangeSlider`].
+///
+/// The slider handles input for its thumbs; they're separate widgets so that they can be
+/// focused, and exposed to accessibility, on their own.
+struct Thumb {
+    name: &'static str,
+    value: f64,
+    /// The smallest and largest values this thumb can be moved to.
+    min: f64,
+    max: f64,
+    step: f64,
+}
+
+impl RangeSlider {
+    /// Create a new slider selecting `range`, out of the values in `bounds`.
+    ///
+    /// `range` is clamped to `bounds`. Bounds which aren't finite are replaced by the
+    /// other bound.
+    pub fn new(range: RangeInclusive<f64>, bounds: RangeInclusive<f64>) -> Self {
+        let (min, max) = finite_bounds(&bounds);
+        let mut slider = Self {
+            low: min,
+            high: max,
+            min,
+            max,
+            step: (max - min) / 100.,
+            dragging: None,
+            lower: WidgetPod::new(Thumb::new("Minimum")),
+            upper: WidgetPod::new(Thumb::new("Maximum")),
+        };
+        slider.set_range_internal(range);
+        slider.sync_thumbs_internal();
+        slider
+    }
+
+    /// Builder-style method to set how much the arrow keys move a thumb.
+    ///
+    /// By default, this is a hundredth of the bounds.
+    pub fn with_step(mut self, step: f64) -> Self {
+        self.step = step.abs();
+        self.sync_thumbs_internal();
+        self
+    }
+
+    /// The selected range.
+    pub fn range(&self) -> RangeInclusive<f64> {
+        self.low..=self.high
+    }
+
+    /// The smallest and largest values which can be selected.
+    pub fn bounds(&self) -> RangeInclusive<f64> {
+        self.min..=self.max
+    }
+
+    fn set_range_internal(&mut self, range: RangeInclusive<f64>) {
+        self.low = clamp(*range.start(), self.min, self.max);
+        self.high = clamp(*range.end(), self.low, self.max);
+    }
+
+    /// Update the thumbs without a context, before the widget is added to the tree.
+    fn sync_thumbs_internal(&mut self) {
+        let lower = self.lower.widget_mut();
+        (lower.value, lower.min, lower.max, lower.step) =
+            (self.low, self.min, self.high, self.step);
+        let upper = self.upper.widget_mut();
+        (upper.value, upper.min, upper.max, upper.step) =
+            (self.high, self.low, self.max, self.step);
+    }
+
+    fn value(&self, thumb: ThumbKind) -> f64 {
+        match thumb {
+            ThumbKind::Lower => self.low,
+            ThumbKind::Upper => self.high,
+        }
+    }
+
+    /// The distance from the edge of the slider to the start of the track.
+    fn track_inset() -> f64 {
+        theme::BASIC_WIDGET_HEIGHT / 2.
+    }
+
+    /// The horizontal position of the center of a thumb at `value`.
+    fn x_for_value(&self, width: f64, value: f64) -> f64 {
+        let track_len = (width - 2. * Self::track_inset()).max(0.);
+        let fraction = if self.max > self.min {
+            (value - self.min) / (self.max - self.min)
+        } else {
+            0.
+        };
+        Self::track_inset() + fraction * track_len
+    }
+
+    /// The value at the horizontal position `x`, clamped to the bounds.
+    fn value_for_x(&self, width: f64, x: f64) -> f64 {
+        let track_len = width - 2. * Self::track_inset();
+        if track_len <= 0. {
+            return self.min;
+        }
+        let fraction = ((x - Self::track_inset()) / track_len).clamp(0., 1.);
+        self.min + fraction * (self.max - self.min)
+    }
+
+    /// The thumb which a press at `x` grabs.
+    fn thumb_at(&self, width: f64, x: f64) -> ThumbKind {
+        let low_x = self.x_for_value(width, self.low);
+        let high_x = self.x_for_value(width, self.high);
+        if low_x == high_x {
+            // When the thumbs overlap, pick the one which can move towards the pointer.
+            if x < low_x {
+                ThumbKind::Lower
+            } else {
+                ThumbKind::Upper
+            }
+        } else if (x - low_x).abs() <= (x - high_x).abs() {
+            ThumbKind::Lower
+        } else {
+            ThumbKind::Upper
+        }
+    }
+
+    /// Move `thumb` to `value`, without crossing the other thumb, and submit the new range
+    /// if it changed.
+    fn move_thumb(&mut self, ctx: &mut EventCtx, thumb: ThumbKind, value: f64) {
+        let (low, high) = match thumb {
+            ThumbKind::Lower => (clamp(value, self.min, self.high), self.high),
+            ThumbKind::Upper => (self.low, clamp(value, self.low, self.max)),
+        };
+        if (low, high) == (self.low, self.high) {
+            return;
+        }
+        trace!(
+            "RangeSlider {:?} set to {}..={}",
+            ctx.widget_id(),
+            low,
+            high
+        );
+        self.low = low;
+        self.high = high;
+        ctx.get_mut(&mut self.lower)
+            .set_state(self.low, self.min, self.high, self.step);
+        ctx.get_mut(&mut self.upper)
+            .set_state(self.high, self.low, self.max, self.step);
+        ctx.submit_action(Action::RangeChanged(self.low..=self.high));
+        ctx.request_layout();
+    }
+
+    fn thumb_for_id(&self, id: crate::WidgetId) -> Option<ThumbKind> {
+        if id == self.lower.id() {
+            Some(ThumbKind::Lower)
+        } else if id == self.upper.id() {
+            Some(ThumbKind::Upper)
+        } else {
+            None
+        }
+    }
+}
+
+impl Thumb {
+    fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            value: 0.,
+            min: 0.,
+            max: 0.,
+            step: 0.,
+        }
+    }
+}
+
+impl WidgetMut<'_, RangeSlider> {
+    /// Set the selected range.
+    ///
+    /// `range` is clamped to the bounds.
+    pub fn set_range(&mut self, range: RangeInclusive<f64>) {
+        self.widget.set_range_internal(range);
+        self.sync_thumbs();
+    }
+
+    /// Set the smallest and largest values which can be selected.
+    ///
+    /// The selected range is clamped to the new bounds. Bounds which aren't finite are
+    /// replaced by the other bound.
+    pub fn set_bounds(&mut self, bounds: RangeInclusive<f64>) {
+        let range = self.widget.range();
+        (self.widget.min, self.widget.max) = finite_bounds(&bounds);
+        self.widget.set_range_internal(range);
+        self.sync_thumbs();
+    }
+
+    /// Set how much the arrow keys move a thumb.
+    pub fn set_step(&mut self, step: f64) {
+        self.widget.step = step.abs();
+        self.sync_thumbs();
+    }
+
+    fn sync_thumbs(&mut self) {
+        let slider = &mut *self.widget;
+        self.ctx.get_mut(&mut slider.lower).set_state(
+            slider.low,
+            slider.min,
+            slider.high,
+            slider.step,
+        );
+        self.ctx.get_mut(&mut slider.upper).set_state(
+            slider.high,
+            slider.low,
+            slider.max,
+            slider.step,
+        );
+        self.ctx.request_layout();
+    }
+}
+
+impl WidgetMut<'_, Thumb> {
+    fn set_state(&mut self, value: f64, min: f64, max: f64, step: f64) {
+        let thumb = &mut *self.widget;
+        if (thumb.value, thumb.min, thumb.max, thumb.step) != (value, min, max, step) {
+            (thumb.value, thumb.min, thumb.max, thumb.step) = (value, min, max, step);
+            self.ctx.request_accessibility_update();
+        }
+    }
+}
+
+impl Widget for RangeSlider {
+    fn on_pointer_event(&mut self, ctx: &mut EventCtx, event: &PointerEvent) {
+        self.lower.on_pointer_event(ctx, event);
+        self.upper.on_pointer_event(ctx, event);
+        if ctx.is_disabled() {
+            return;
+        }
+
+        let width = ctx.size().width;
+        match event {
+            PointerEvent::PointerDown(MouseButton::Left, state) => {
+                let x = state.position.x - ctx.window_origin().x;
+                let thumb = self.thumb_at(width, x);
+                self.dragging = Some(thumb);
+                ctx.set_active(true);
+                ctx.capture_pointer();
+                ctx.set_focus(match thumb {
+                    ThumbKind::Lower => self.lower.id(),
+                    ThumbKind::Upper => self.upper.id(),
+                });
+                self.move_thumb(ctx, thumb, self.value_for_x(width, x));
+                ctx.set_handled();
+            }
+            PointerEvent::PointerMove(state) => {
+                if let Some(thumb) = self.dragging {
+                    let x = state.position.x - ctx.window_origin().x;
+                    self.move_thumb(ctx, thumb, self.value_for_x(width, x));
+                }
+            }
+            PointerEvent::PointerUp(MouseButton::Left, _) if self.dragging.is_some() => {
+                self.dragging = None;
+                ctx.set_active(false);
+                ctx.release_pointer();
+                ctx.request_paint();
+            }
+            _ => (),
+        }
+    }
+
+    fn on_text_event(&mut self, ctx: &mut EventCtx, event: &TextEvent) {
+        self.lower.on_text_event(ctx, event);
+        self.upper.on_text_event(ctx, event);
+        if ctx.is_handled() || ctx.is_disabled() {
+            return;
+        }
+        let thumb = if self.lower.has_focus() {
+            ThumbKind::Lower
+        } else if self.upper.has_focus() {
+            ThumbKind::Upper
+        } else {
+            return;
+        };
+
+        if let TextEvent::KeyboardKey(key_event, _) = event {
+            if !key_event.state.is_pressed() {
+                return;
+            }
+            let value = self.value(thumb);
+            let new_value = match &key_event.logical_key {
+                Key::Named(NamedKey::ArrowLeft | NamedKey::ArrowDown) => value - self.step,
+                Key::Named(NamedKey::ArrowRight | NamedKey::ArrowUp) => value + self.step,
+                Key::Named(NamedKey::Home) => self.min,
+                Key::Named(NamedKey::End) => self.max,
+                _ => return,
+            };
+            self.move_thumb(ctx, thumb, new_value);
+            ctx.set_handled();
+        }
+    }
+
+    fn on_access_event(&mut self, ctx: &mut EventCtx, event: &AccessEvent) {
+        self.lower.on_access_event(ctx, event);
+        self.upper.on_access_event(ctx, event);
+        if ctx.is_disabled() {
+            return;
+        }
+        let Some(thumb) = self.thumb_for_id(event.target) else {
+            return;
+        };
+        let value = self.value(thumb);
+        match event.action {
+            accesskit::Action::Increment => self.move_thumb(ctx, thumb, value + self.step),
+            accesskit::Action::Decrement => self.move_thumb(ctx, thumb, value - self.step),
+            accesskit::Action::SetValue => {
+                if let Some(value) = event.numeric_value() {
+                    self.move_thumb(ctx, thumb, value);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn on_status_change(&mut self, _ctx: &mut LifeCycleCtx, _event: &StatusChange) {}
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle) {
+        if let LifeCycle::WidgetAdded = event {
+            ctx.set_cursor(&CursorIcon::Pointer);
+        }
+        self.lower.lifecycle(ctx, event);
+        self.upper.lifecycle(ctx, event);
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints) -> Size {
+        let width = if bc.is_width_bounded() {
+            bc.max().width
+        } else {
+            theme::WIDE_WIDGET_WIDTH
+        };
+        let size = bc.constrain(Size::new(width, theme::BASIC_WIDGET_HEIGHT));
+
+        let thumb_size = Size::new(theme::BASIC_WIDGET_HEIGHT, theme::BASIC_WIDGET_HEIGHT);
+        self.lower.layout(ctx, &BoxConstraints::tight(thumb_size));
+        self.upper.layout(ctx, &BoxConstraints::tight(thumb_size));
+        let y = (size.height - thumb_size.height) / 2.;
+        let low_x = self.x_for_value(size.width, self.low) - thumb_size.width / 2.;
+        let high_x = self.x_for_value(size.width, self.high) - thumb_size.width / 2.;
+        ctx.place_child(&mut self.lower, Point::new(low_x, y));
+        ctx.place_child(&mut self.upper, Point::new(high_x, y));
+
+        trace!("Computed size: {}", size);
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, scene: &mut Scene) {
+        let size = ctx.size();
+        let inset = Self::track_inset();
+        let y = (size.height - TRACK_HEIGHT) / 2.;
+        let track = Rect::new(inset, y, (size.width - inset).max(inset), y + TRACK_HEIGHT)
+            .to_rounded_rect(TRACK_HEIGHT / 2.);
+        let theme = ctx.theme();
+        fill_lin_gradient(
+            scene,
+            &track,
+            theme.field,
+            UnitPoint::TOP,
+            UnitPoint::BOTTOM,
+        );
+        stroke(scene, &track, theme.border, 1.);
+
+        let selected = Rect::new(
+            self.x_for_value(size.width, self.low),
+            y,
+            self.x_for_value(size.width, self.high),
+            y + TRACK_HEIGHT,
+        );
+        if selected.width() > 0. {
+            let color = if ctx.is_disabled() {
+                theme.on_surface_disabled
+            } else {
+                theme.primary
+            };
+            fill_color(scene, &selected.to_rounded_rect(TRACK_HEIGHT / 2.), color);
+        }
+
+        self.lower.paint(ctx, scene);
+        self.upper.paint(ctx, scene);
+    }
+
+    fn accessibility_role(&self) -> Role {
+        Role::Group
+    }
+
+    fn accessibility(&mut self, ctx: &mut AccessCtx) {
+        self.lower.accessibility(ctx);
+        self.upper.accessibility(ctx);
+    }
+
+    fn children(&self) -> SmallVec<[WidgetRef<'_, dyn Widget>; 16]> {
+        smallvec![self.lower.as_dyn(), self.upper.as_dyn()]
+    }
+
+    fn make_trace_span(&self) -> Span {
+        trace_span!("RangeSlider")
+    }
+
+    fn get_debug_text(&self) -> Option<String> {
+        Some(format!("{}..={}", self.low, self.high))
+    }
+}
+
+impl Widget for Thumb {
+    fn on_pointer_event(&mut self, _ctx: &mut EventCtx, _event: &PointerEvent) {}
+
+    fn on_text_event(&mut self, _ctx: &mut EventCtx, _event: &TextEvent) {}
+
+    fn on_access_event(&mut self, _ctx: &mut EventCtx, _event: &AccessEvent) {}
+
+    fn on_status_change(&mut self, ctx: &mut LifeCycleCtx, event: &StatusChange) {
+        if let StatusChange::FocusChanged(_) = event {
+            ctx.request_paint();
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle) {
+        if let LifeCycle::BuildFocusChain = event {
+            ctx.register_for_focus();
+        }
+    }
+
+    fn layout(&mut self, _ctx: &mut LayoutCtx, bc: &BoxConstraints) -> Size {
+        bc.constrain(Size::new(
+            theme::BASIC_WIDGET_HEIGHT,
+            theme::BASIC_WIDGET_HEIGHT,
+        ))
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, scene: &mut Scene) {
+        let size = ctx.size();
+        let circle = Circle::new(size.to_rect().center(), size.min_side() / 2. - 1.);
+        let theme = ctx.theme();
+        let color = if ctx.is_disabled() {
+            theme.on_surface_disabled
+        } else {
+            theme.on_surface
+        };
+        fill_color(scene, &circle, color);
+        if ctx.is_focused() {
+            stroke(scene, &circle, theme.primary, 2.);
+        } else {
+            stroke(scene, &circle, theme.border, 1.);
+        }
+    }
+
+    fn accessibility_role(&self) -> Role {
+        Role::Slider
+    }
+
+    fn accessibility(&mut self, ctx: &mut AccessCtx) {
+        let node = ctx.current_node();
+        node.set_name(self.name);
+        node.set_numeric_value(self.value);
+        node.set_min_numeric_value(self.min);
+        node.set_max_numeric_value(self.max);
+        if self.step > 0. {
+            node.set_numeric_value_step(self.step);
+        }
+        node.add_action(accesskit::Action::Focus);
+        node.add_action(accesskit::Action::Increment);
+        node.add_action(accesskit::Action::Decrement);
+        node.add_action(accesskit::Action::SetValue);
+    }
+
+    fn children(&self) -> SmallVec<[WidgetRef<'_, dyn Widget>; 16]> {
+        SmallVec::new()
+    }
+
+    fn make_trace_span(&self) -> Span {
+        trace_span!("Thumb")
+    }
+
+    fn get_debug_text(&self) -> Option<String> {
+        Some(self.value.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{widget_ids, TestHarness, TestWidgetExt};
+    use crate::widget::{Flex, SizedBox};
+
+    fn range(harness: &TestHarness, id: crate::WidgetId) -> RangeInclusive<f64> {
+        harness
+            .get_widget(id)
+            .downcast::<RangeSlider>()
+            .unwrap()
+            .deref()
+            .range()
+    }
+
+    #[test]
+    fn non_finite_bounds_are_replaced() {
+        let slider = RangeSlider::new(f64::NAN..=5.0, f64::NAN..=10.0);
+        assert_eq!(slider.bounds(), 10.0..=10.0);
+        assert_eq!(slider.range(), 10.0..=10.0);
+
+        let slider = RangeSlider::new(0.0..=5.0, 0.0..=f64::INFINITY);
+        assert_eq!(slider.bounds(), 0.0..=0.0);
+    }
+
+    #[test]
+    fn thumbs_dont_cross() {
+        let [slider_id] = widget_ids();
+        // The track is 100 pixels long, from 9 to 109, so each pixel is one unit.
+        let widget = Flex::column().with_child(
+            SizedBox::new(RangeSlider::new(20.0..=60.0, 0.0..=100.0).with_id(slider_id))
+                .width(118.),
+        );
+
+        let mut harness = TestHarness::create(widget);
+        let rect = harness.get_widget(slider_id).state().window_layout_rect();
+        let y = rect.center().y;
+
+        // Pressing near the lower thumb grabs it and focuses it.
+        harness.mouse_move((rect.x0 + 9. + 25., y));
+        harness.mouse_button_press(MouseButton::Left);
+        assert_eq!(
+            harness.pop_action(),
+            Some((Action::RangeChanged(25.0..=60.0), slider_id))
+        );
+        let lower_id = harness.get_widget(slider_id).children()[0].id();
+        assert_eq!(harness.focused_widget().map(|w| w.id()), Some(lower_id));
+
+        // Dragging it past the upper thumb stops at the upper thumb.
+        harness.mouse_move((rect.x0 + 9. + 90., y));
+        harness.mouse_button_release(MouseButton::Left);
+        assert_eq!(range(&harness, slider_id), 60.0..=60.0);
+
+        // When the thumbs overlap, pressing on the right moves the upper thumb.
+        harness.mouse_move((rect.x0 + 9. + 80., y));
+        harness.mouse_button_press(MouseButton::Left);
+        harness.mouse_button_release(MouseButton::Left);
+        assert_eq!(range(&harness, slider_id), 60.0..=80.0);
+
+        // The thumbs are centered on their values along the track.
+        let lower_rect = harness.get_widget(slider_id).children()[0]
+            .state()
+            .layout_rect();
+        let upper_rect = harness.get_widget(slider_id).children()[1]
+            .state()
+            .layout_rect();
+        assert!((lower_rect.center().x - (9. + 60.)).abs() < 1.);
+        assert!((upper_rect.center().x - (9. + 80.)).abs() < 1.);
+    }
+
+    #[test]
+    fn assistive_technology_sets_values() {
+        let [slider_id] = widget_ids();
+        let widget =
+            Flex::column().with_child_id(RangeSlider::new(20.0..=60.0, 0.0..=100.0), slider_id);
+
+        let mut harness = TestHarness::create(widget);
+        let upper_id = harness.get_widget(slider_id).children()[1].id();
+        let request = |action, data| accesskit::ActionRequest {
+            action,
+            target: upper_id.into(),
+            data,
+        };
+
+        harness.process_access_event(request(accesskit::Action::Focus, None));
+        assert_eq!(harness.focused_widget().map(|w| w.id()), Some(upper_id));
+
+        harness.process_access_event(request(
+            accesskit::Action::SetValue,
+            Some(accesskit::ActionData::NumericValue(75.)),
+        ));
+        assert_eq!(
+            harness.pop_action(),
+            Some((Action::RangeChanged(20.0..=75.0), slider_id))
+        );
+
+        // Values are clamped like dragged ones, and text values are parsed.
+        harness.process_access_event(request(
+            accesskit::Action::SetValue,
+            Some(accesskit::ActionData::Value("10".into())),
+        ));
+        assert_eq!(range(&harness, slider_id), 20.0..=20.0);
+    }
+}
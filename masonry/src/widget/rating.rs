@@ -0,0 +1,373 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! A star rating widget.
+
+use std::f64::consts::PI;
+
+use accesskit::Role;
+use kurbo::{BezPath, Point, Rect};
+use smallvec::SmallVec;
+use tracing::{trace, trace_span, Span};
+use vello::peniko::BlendMode;
+use vello::Scene;
+
+use crate::action::Action;
+use crate::paint_scene_helpers::{fill_color, stroke};
+use crate::widget::{WidgetMut, WidgetRef};
+use crate::{
+    theme, AccessCtx, AccessEvent, Affine, BoxConstraints, CursorIcon, EventCtx, LayoutCtx,
+    LifeCycle, LifeCycleCtx, PaintCtx, PointerEvent, Size, StatusChange, TextEvent, Widget,
+};
+
+/// The space between two stars.
+const STAR_GAP: f64 = 4.0;
+
+/// A row of stars, which the user can click to rate something between 0 and `max`.
+///
+/// While the pointer is over the widget, the stars preview the value a click would set.
+/// Clicking submits [`Action::RatingChanged`] with the new value.
+pub struct Rating {
+    value: f32,
+    max: u32,
+    half_stars: bool,
+    /// The value shown while the pointer is over the stars.
+    hover_value: Option<f32>,
+}
+
+impl Rating {
+    /// Create a new rating of `value` out of `max` stars.
+    ///
+    /// `value` is clamped to the range `[0, max]`.
+    pub fn new(value: f32, max: u32) -> Self {
+        Self {
+            value: clamp_value(value, max),
+            max,
+            half_stars: false,
+            hover_value: None,
+        }
+    }
+
+    /// Builder-style method to set whether the user can pick half stars.
+    ///
+    /// By default, clicking a star sets the rating to a whole number of stars.
+    pub fn with_half_stars(mut self, half_stars: bool) -> Self {
+        self.half_stars = half_stars;
+        self
+    }
+
+    /// The current rating, between 0 and [`max`](Self::max).
+    pub fn value(&self) -> f32 {
+        self.value
+    }
+
+    /// The number of stars.
+    pub fn max(&self) -> u32 {
+        self.max
+    }
+
+    /// The value the stars currently show, which is the value a click would set while
+    /// the pointer is over them.
+    pub fn shown_value(&self) -> f32 {
+        self.hover_value.unwrap_or(self.value)
+    }
+
+    /// The smallest step the user can change the rating by.
+    fn step(&self) -> f32 {
+        if self.half_stars {
+            0.5
+        } else {
+            1.0
+        }
+    }
+
+    /// The value set by clicking at `x`, in local coordinates.
+    fn value_at(&self, x: f64) -> f32 {
+        let star_size = theme::BASIC_WIDGET_HEIGHT;
+        let x = x.max(0.0);
+        let star = (x / (star_size + STAR_GAP)).floor();
+        let within_star = x - star * (star_size + STAR_GAP);
+        let value = if self.half_stars && within_star < star_size / 2. {
+            star + 0.5
+        } else {
+            star + 1.0
+        };
+        clamp_value(value as f32, self.max)
+    }
+
+    fn set_value_and_submit(&mut self, ctx: &mut EventCtx, value: f32) {
+        if value != self.value {
+            self.value = value;
+            ctx.submit_action(Action::RatingChanged(value));
+            ctx.request_accessibility_update();
+        }
+        ctx.request_paint();
+    }
+}
+
+fn clamp_value(value: f32, max: u32) -> f32 {
+    if value.is_nan() {
+        0.0
+    } else {
+        value.clamp(0.0, max as f32)
+    }
+}
+
+/// A five pointed star filling the square at `origin`, with a side of `size`.
+fn star_path(origin: Point, size: f64) -> BezPath {
+    let center = origin + (size / 2., size / 2.);
+    let outer_radius = size / 2.;
+    let inner_radius = outer_radius * 0.4;
+    let mut path = BezPath::new();
+    for i in 0..10 {
+        let radius = if i % 2 == 0 {
+            outer_radius
+        } else {
+            inner_radius
+        };
+        // Start with the top point.
+        let angle = -PI / 2. + i as f64 * PI / 5.;
+        let point = center + (radius * angle.cos(), radius * angle.sin());
+        if i == 0 {
+            path.move_to(point);
+        } else {
+            path.line_to(point);
+        }
+    }
+    path.close_path();
+    path
+}
+
+impl WidgetMut<'_, Rating> {
+    /// Set the rating.
+    ///
+    /// `value` is clamped to the range `[0, max]`.
+    pub fn set_value(&mut self, value: f32) {
+        let value = clamp_value(value, self.widget.max);
+        if value != self.widget.value {
+            self.widget.value = value;
+            self.ctx.request_paint();
+            self.ctx.request_accessibility_update();
+        }
+    }
+
+    /// Set the number of stars.
+    pub fn set_max(&mut self, max: u32) {
+        self.widget.max = max;
+        self.widget.value = clamp_value(self.widget.value, max);
+        self.ctx.request_layout();
+        self.ctx.request_accessibility_update();
+    }
+
+    /// Set whether the user can pick half stars.
+    pub fn set_half_stars(&mut self, half_stars: bool) {
+        self.widget.half_stars = half_stars;
+        self.ctx.request_accessibility_update();
+    }
+}
+
+impl Widget for Rating {
+    fn on_pointer_event(&mut self, ctx: &mut EventCtx, event: &PointerEvent) {
+        if ctx.is_disabled() {
+            return;
+        }
+        match event {
+            PointerEvent::PointerMove(state) => {
+                let x = state.position.x - ctx.window_origin().x;
+                let hover_value = ctx.is_hot().then(|| self.value_at(x));
+                if hover_value != self.hover_value {
+                    self.hover_value = hover_value;
+                    ctx.request_paint();
+                }
+            }
+            PointerEvent::PointerLeave(_) => {
+                self.hover_value = None;
+                ctx.request_paint();
+            }
+            PointerEvent::PointerDown(_, _) => {
+                ctx.set_active(true);
+            }
+            PointerEvent::PointerUp(_, state) => {
+                if ctx.is_active() && ctx.is_hot() {
+                    let x = state.position.x - ctx.window_origin().x;
+                    let value = self.value_at(x);
+                    trace!("Rating {:?} set to {}", ctx.widget_id(), value);
+                    self.set_value_and_submit(ctx, value);
+                }
+                ctx.set_active(false);
+            }
+            _ => (),
+        }
+    }
+
+    fn on_text_event(&mut self, _ctx: &mut EventCtx, _event: &TextEvent) {}
+
+    fn on_access_event(&mut self, ctx: &mut EventCtx, event: &AccessEvent) {
+        if event.target == ctx.widget_id() {
+            match event.action {
+                accesskit::Action::Increment => {
+                    let value = clamp_value(self.value + self.step(), self.max);
+                    self.set_value_and_submit(ctx, value);
+                }
+                accesskit::Action::Decrement => {
+                    let value = clamp_value(self.value - self.step(), self.max);
+                    self.set_value_and_submit(ctx, value);
+                }
+                accesskit::Action::SetValue => {
+                    if let Some(value) = event.numeric_value() {
+                        // Round to the nearest star, or half star.
+                        let step = self.step();
+                        let value = (value as f32 / step).round() * step;
+                        self.set_value_and_submit(ctx, clamp_value(value, self.max));
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn on_status_change(&mut self, ctx: &mut LifeCycleCtx, event: &StatusChange) {
+        if let StatusChange::HotChanged(false) = event {
+            if self.hover_value.take().is_some() {
+                ctx.request_paint();
+            }
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle) {
+        if let LifeCycle::WidgetAdded = event {
+            ctx.set_cursor(&CursorIcon::Pointer);
+        }
+    }
+
+    fn layout(&mut self, _ctx: &mut LayoutCtx, bc: &BoxConstraints) -> Size {
+        let star_size = theme::BASIC_WIDGET_HEIGHT;
+        let width = if self.max == 0 {
+            0.0
+        } else {
+            self.max as f64 * (star_size + STAR_GAP) - STAR_GAP
+        };
+        let size = bc.constrain(Size::new(width, star_size));
+        trace!("Computed size: {}", size);
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, scene: &mut Scene) {
+        let star_size = theme::BASIC_WIDGET_HEIGHT;
+        let (fill, outline) = if ctx.is_disabled() {
+            (
+                theme::DISABLED_FOREGROUND_DARK,
+                theme::DISABLED_FOREGROUND_DARK,
+            )
+        } else {
+            (theme::RATING_COLOR, theme::BORDER_LIGHT)
+        };
+        let shown_value = self.shown_value() as f64;
+
+        for star in 0..self.max {
+            let origin = Point::new(star as f64 * (star_size + STAR_GAP), 0.);
+            let path = star_path(origin, star_size);
+            let filled = (shown_value - star as f64).clamp(0.0, 1.0);
+            if filled >= 1.0 {
+                fill_color(scene, &path, fill);
+            } else if filled > 0.0 {
+                // Only fill the part of the star left of the value, such as its left half.
+                let clip_rect =
+                    Rect::from_origin_size(origin, Size::new(star_size * filled, star_size));
+                scene.push_layer(BlendMode::default(), 1., Affine::IDENTITY, &clip_rect);
+                fill_color(scene, &path, fill);
+                scene.pop_layer();
+                stroke(scene, &path, outline, 1.0);
+            } else {
+                stroke(scene, &path, outline, 1.0);
+            }
+        }
+    }
+
+    fn accessibility_role(&self) -> Role {
+        Role::Slider
+    }
+
+    fn accessibility(&mut self, ctx: &mut AccessCtx) {
+        let node = ctx.current_node();
+        node.set_numeric_value(self.value as f64);
+        node.set_min_numeric_value(0.0);
+        node.set_max_numeric_value(self.max as f64);
+        node.set_numeric_value_step(self.step() as f64);
+        node.add_action(accesskit::Action::Increment);
+        node.add_action(accesskit::Action::Decrement);
+        node.add_action(accesskit::Action::SetValue);
+    }
+
+    fn children(&self) -> SmallVec<[WidgetRef<'_, dyn Widget>; 16]> {
+        SmallVec::new()
+    }
+
+    fn make_trace_span(&self) -> Span {
+        trace_span!("Rating")
+    }
+
+    fn get_debug_text(&self) -> Option<String> {
+        Some(format!("{}/{}", self.value, self.max))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use winit::event::MouseButton;
+
+    use super::*;
+    use crate::testing::{widget_ids, TestHarness, TestWidgetExt};
+    use crate::widget::Flex;
+
+    fn shown_value(harness: &TestHarness, id: crate::WidgetId) -> f32 {
+        harness
+            .get_widget(id)
+            .downcast::<Rating>()
+            .unwrap()
+            .deref()
+            .shown_value()
+    }
+
+    #[test]
+    fn click_sets_rating() {
+        let [whole_id, half_id] = widget_ids();
+        let widget = Flex::column()
+            .with_child(Rating::new(1.0, 5).with_id(whole_id))
+            .with_child(Rating::new(1.0, 5).with_half_stars(true).with_id(half_id));
+
+        let mut harness = TestHarness::create(widget);
+
+        let whole_rect = harness.get_widget(whole_id).state().window_layout_rect();
+        assert_eq!(whole_rect.width(), 5. * 18. + 4. * STAR_GAP);
+
+        // Hovering the left half of the third star previews the rating it would set.
+        let third_star = whole_rect.x0 + 2. * (18. + STAR_GAP);
+        harness.mouse_move((third_star + 4., whole_rect.center().y));
+        assert_eq!(shown_value(&harness, whole_id), 3.0);
+        harness.mouse_button_press(MouseButton::Left);
+        harness.mouse_button_release(MouseButton::Left);
+        assert_eq!(
+            harness.pop_action(),
+            Some((Action::RatingChanged(3.0), whole_id))
+        );
+
+        // With half stars, the left half of a star sets a half star.
+        let half_rect = harness.get_widget(half_id).state().window_layout_rect();
+        harness.mouse_move((third_star + 4., half_rect.center().y));
+        assert_eq!(shown_value(&harness, half_id), 2.5);
+        assert_eq!(shown_value(&harness, whole_id), 3.0);
+        harness.mouse_button_press(MouseButton::Left);
+        harness.mouse_button_release(MouseButton::Left);
+        assert_eq!(
+            harness.pop_action(),
+            Some((Action::RatingChanged(2.5), half_id))
+        );
+
+        // Once the pointer leaves, the stars show the rating again.
+        harness.mouse_move((half_rect.x0 + 1., half_rect.center().y));
+        assert_eq!(shown_value(&harness, half_id), 0.5);
+        harness.mouse_move((300., 300.));
+        assert_eq!(shown_value(&harness, half_id), 2.5);
+    }
+}
@@ -0,0 +1,216 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! A widget which reports which size class its width falls in.
+
+use accesskit::Role;
+use smallvec::{smallvec, SmallVec};
+use tracing::{trace, trace_span, Span};
+use vello::Scene;
+
+use crate::widget::{Axis, WidgetMut, WidgetPod, WidgetRef};
+use crate::{
+    AccessCtx, AccessEvent, Action, BoxConstraints, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx,
+    PaintCtx, Point, PointerEvent, Size, StatusChange, TextEvent, Widget,
+};
+
+/// A range of widths, from narrowest to widest, as defined by [`Breakpoints`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum BreakpointClass {
+    Xs,
+    Sm,
+    Md,
+    Lg,
+    Xl,
+}
+
+/// The smallest width, in logical pixels, of each [`BreakpointClass`].
+///
+/// Widths smaller than `xs` are still in the `Xs` class.
+/// The default values are the breakpoints used by Bootstrap.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Breakpoints {
+    pub xs: f64,
+    pub sm: f64,
+    pub md: f64,
+    pub lg: f64,
+    pub xl: f64,
+}
+
+impl Default for Breakpoints {
+    fn default() -> Self {
+        Self {
+            xs: 0.,
+            sm: 576.,
+            md: 768.,
+            lg: 992.,
+            xl: 1200.,
+        }
+    }
+}
+
+impl Breakpoints {
+    /// Returns the class of the given width.
+    pub fn class(&self, width: f64) -> BreakpointClass {
+        if width >= self.xl {
+            BreakpointClass::Xl
+        } else if width >= self.lg {
+            BreakpointClass::Lg
+        } else if width >= self.md {
+            BreakpointClass::Md
+        } else if width >= self.sm {
+            BreakpointClass::Sm
+        } else {
+            BreakpointClass::Xs
+        }
+    }
+}
+
+/// A container which submits [`Action::BreakpointChanged`] when the width available to it
+/// moves to another [`BreakpointClass`], so that its owner can swap its child for a layout
+/// suited to the new width.
+///
+/// The class is computed from the maximum width of the constraints the container is laid
+/// out with, which is the width of the window when it is the root widget. The action is
+/// also submitted after the first layout.
+pub struct Responsive<W: Widget> {
+    child: WidgetPod<W>,
+    breakpoints: Breakpoints,
+    /// The class reported after the last layout.
+    class: Option<BreakpointClass>,
+}
+
+impl<W: Widget> Responsive<W> {
+    /// Create a new responsive container around `child`.
+    pub fn new(child: W, breakpoints: Breakpoints) -> Self {
+        Self::from_child_pod(WidgetPod::new(child), breakpoints)
+    }
+
+    /// Create a new responsive container around a child which has already been wrapped in
+    /// a [`WidgetPod`].
+    pub fn from_child_pod(child: WidgetPod<W>, breakpoints: Breakpoints) -> Self {
+        Self {
+            child,
+            breakpoints,
+            class: None,
+        }
+    }
+
+    /// The class of the width available to the container, as of the last layout.
+    pub fn class(&self) -> Option<BreakpointClass> {
+        self.class
+    }
+}
+
+impl<W: Widget> WidgetMut<'_, Responsive<W>> {
+    pub fn child_mut(&mut self) -> WidgetMut<'_, W> {
+        self.ctx.get_mut(&mut self.widget.child)
+    }
+
+    /// Set the widths at which the class changes.
+    pub fn set_breakpoints(&mut self, breakpoints: Breakpoints) {
+        self.widget.breakpoints = breakpoints;
+        self.ctx.request_layout();
+    }
+}
+
+impl<W: Widget> Widget for Responsive<W> {
+    fn on_pointer_event(&mut self, ctx: &mut EventCtx, event: &PointerEvent) {
+        self.child.on_pointer_event(ctx, event);
+    }
+
+    fn on_text_event(&mut self, ctx: &mut EventCtx, event: &TextEvent) {
+        self.child.on_text_event(ctx, event);
+    }
+
+    fn on_access_event(&mut self, ctx: &mut EventCtx, event: &AccessEvent) {
+        self.child.on_access_event(ctx, event);
+    }
+
+    fn on_status_change(&mut self, _ctx: &mut LifeCycleCtx, _event: &StatusChange) {}
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle) {
+        self.child.lifecycle(ctx, event);
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints) -> Size {
+        let class = self.breakpoints.class(bc.max().width);
+        if self.class != Some(class) {
+            trace!(
+                "Responsive {:?} breakpoint class changed to {:?}",
+                ctx.widget_id(),
+                class
+            );
+            self.class = Some(class);
+            ctx.submit_action(Action::BreakpointChanged(class));
+        }
+
+        let size = self.child.layout(ctx, bc);
+        ctx.place_child(&mut self.child, Point::ORIGIN);
+        size
+    }
+
+    fn intrinsic_size(&mut self, ctx: &mut LayoutCtx, axis: Axis, cross_size: f64) -> f64 {
+        // Measuring doesn't change the class, which is only reported after a real layout.
+        self.child.intrinsic_size(ctx, axis, cross_size)
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, scene: &mut Scene) {
+        self.child.paint(ctx, scene);
+    }
+
+    fn accessibility_role(&self) -> Role {
+        Role::GenericContainer
+    }
+
+    fn accessibility(&mut self, ctx: &mut AccessCtx) {
+        self.child.accessibility(ctx);
+    }
+
+    fn children(&self) -> SmallVec<[WidgetRef<'_, dyn Widget>; 16]> {
+        smallvec![self.child.as_dyn()]
+    }
+
+    fn make_trace_span(&self) -> Span {
+        trace_span!("Responsive")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use winit::dpi::PhysicalSize;
+
+    use super::*;
+    use crate::event::WindowEvent;
+    use crate::testing::{widget_ids, TestHarness, TestWidgetExt};
+    use crate::widget::SizedBox;
+
+    #[test]
+    fn resize_reports_class_changes() {
+        let [responsive_id] = widget_ids();
+        let widget =
+            Responsive::new(SizedBox::empty(), Breakpoints::default()).with_id(responsive_id);
+
+        let mut harness = TestHarness::create_with_size(widget, Size::new(400., 300.));
+        assert_eq!(
+            harness.pop_action(),
+            Some((
+                Action::BreakpointChanged(BreakpointClass::Xs),
+                responsive_id
+            ))
+        );
+
+        harness.process_window_event(WindowEvent::Resize(PhysicalSize::new(800, 300)));
+        assert_eq!(
+            harness.pop_action(),
+            Some((
+                Action::BreakpointChanged(BreakpointClass::Md),
+                responsive_id
+            ))
+        );
+
+        // Resizing within a class doesn't submit an action.
+        harness.process_window_event(WindowEvent::Resize(PhysicalSize::new(900, 600)));
+        assert_eq!(harness.pop_action(), None);
+    }
+}
@@ -0,0 +1,315 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! A widget displaying text with differently styled spans.
+
+use accesskit::Role;
+use kurbo::{Affine, Point, Size};
+use parley::layout::Alignment;
+use smallvec::SmallVec;
+use tracing::{trace, trace_span, Span};
+use vello::peniko::BlendMode;
+use vello::Scene;
+
+use crate::text2::{StyledText, TextBrush, TextLayout, TextStorage, TextStyle};
+use crate::widget::label::LABEL_X_PADDING;
+use crate::widget::{LineBreaking, WidgetMut, WidgetRef};
+use crate::{
+    AccessCtx, AccessEvent, BoxConstraints, Color, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx,
+    PaintCtx, PointerEvent, StatusChange, TextEvent, Widget,
+};
+
+/// A widget displaying non-editable [`StyledText`], such as text with bold or colored words.
+///
+/// The style of the whole text is set on the widget, and each span of the text can
+/// override parts of it.
+pub struct RichText {
+    text_layout: TextLayout<StyledText>,
+    line_break_mode: LineBreaking,
+    show_disabled: bool,
+    brush: TextBrush,
+}
+
+impl RichText {
+    /// Create a new widget displaying `text`.
+    pub fn new(text: impl Into<StyledText>) -> Self {
+        Self {
+            text_layout: TextLayout::new(text.into(), crate::theme::TEXT_SIZE_NORMAL as f32),
+            line_break_mode: LineBreaking::Overflow,
+            show_disabled: true,
+            brush: crate::theme::TEXT_COLOR.into(),
+        }
+    }
+
+    pub fn text(&self) -> &StyledText {
+        self.text_layout.text()
+    }
+
+    /// Set the color of the spans which don't have their own.
+    #[doc(alias = "with_text_color")]
+    pub fn with_text_brush(mut self, color: Color) -> Self {
+        self.brush = color.into();
+        self.text_layout.set_brush(color);
+        self
+    }
+
+    /// Set the size of the spans which don't have their own.
+    pub fn with_text_size(mut self, size: f32) -> Self {
+        self.text_layout.set_text_size(size);
+        self
+    }
+
+    pub fn with_text_alignment(mut self, alignment: Alignment) -> Self {
+        self.text_layout.set_text_alignment(alignment);
+        self
+    }
+
+    pub fn with_line_break_mode(mut self, line_break_mode: LineBreaking) -> Self {
+        self.line_break_mode = line_break_mode;
+        self
+    }
+
+    /// Set the font, size, weight, color and line height of the spans which don't
+    /// have their own.
+    pub fn with_text_style(mut self, text_style: &TextStyle) -> Self {
+        self.brush = text_style.color.into();
+        self.text_layout.set_text_style(text_style);
+        self
+    }
+}
+
+impl WidgetMut<'_, RichText> {
+    pub fn text(&self) -> &StyledText {
+        self.widget.text_layout.text()
+    }
+
+    pub fn set_text_properties<R>(
+        &mut self,
+        f: impl FnOnce(&mut TextLayout<StyledText>) -> R,
+    ) -> R {
+        let ret = f(&mut self.widget.text_layout);
+        if self.widget.text_layout.needs_rebuild() {
+            self.ctx.request_layout();
+        }
+        ret
+    }
+
+    pub fn set_text(&mut self, new_text: impl Into<StyledText>) {
+        let new_text = new_text.into();
+        self.set_text_properties(|layout| layout.set_text(new_text));
+    }
+
+    #[doc(alias = "set_text_color")]
+    pub fn set_text_brush(&mut self, brush: impl Into<TextBrush>) {
+        self.widget.brush = brush.into();
+        if !self.ctx.is_disabled() {
+            let brush = self.widget.brush.clone();
+            self.set_text_properties(|layout| layout.set_brush(brush));
+        }
+    }
+
+    pub fn set_text_size(&mut self, size: f32) {
+        self.set_text_properties(|layout| layout.set_text_size(size));
+    }
+
+    pub fn set_alignment(&mut self, alignment: Alignment) {
+        self.set_text_properties(|layout| layout.set_text_alignment(alignment));
+    }
+
+    pub fn set_line_break_mode(&mut self, line_break_mode: LineBreaking) {
+        self.widget.line_break_mode = line_break_mode;
+        self.ctx.request_layout();
+    }
+
+    /// Set the font, size, weight, color and line height of the spans which don't
+    /// have their own.
+    pub fn set_text_style(&mut self, text_style: &TextStyle) {
+        self.widget.brush = text_style.color.into();
+        let brush = self.widget.brush.clone();
+        let disabled = self.ctx.is_disabled() && self.widget.show_disabled;
+        self.set_text_properties(|layout| {
+            layout.set_text_style(text_style);
+            if disabled {
+                layout.set_brush(crate::theme::DISABLED_TEXT_COLOR);
+            } else {
+                layout.set_brush(brush);
+            }
+        });
+    }
+}
+
+impl Widget for RichText {
+    fn on_pointer_event(&mut self, _ctx: &mut EventCtx, _event: &PointerEvent) {}
+
+    fn on_text_event(&mut self, _ctx: &mut EventCtx, _event: &TextEvent) {}
+
+    fn on_access_event(&mut self, _ctx: &mut EventCtx, _event: &AccessEvent) {}
+
+    fn on_status_change(&mut self, _ctx: &mut LifeCycleCtx, _event: &StatusChange) {}
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle) {
+        if let LifeCycle::DisabledChanged(disabled) = event {
+            if self.show_disabled {
+                // Spans with their own color keep it.
+                if *disabled {
+                    self.text_layout
+                        .set_brush(crate::theme::DISABLED_TEXT_COLOR);
+                } else {
+                    self.text_layout.set_brush(self.brush.clone());
+                }
+            }
+            // TODO: Parley seems to require a relayout when colours change
+            ctx.request_layout();
+        }
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints) -> Size {
+        let max_advance = if self.line_break_mode != LineBreaking::WordWrap {
+            None
+        } else if bc.max().width.is_finite() {
+            Some(bc.max().width as f32 - 2. * LABEL_X_PADDING as f32)
+        } else if bc.min().width.is_sign_negative() {
+            Some(0.0)
+        } else {
+            None
+        };
+        self.text_layout.set_max_advance(max_advance);
+        if self.text_layout.needs_rebuild() {
+            self.text_layout.rebuild(ctx.font_ctx());
+        }
+        let text_size = self.text_layout.size();
+        let size = bc.constrain(Size {
+            height: text_size.height,
+            width: text_size.width + 2. * LABEL_X_PADDING,
+        });
+        trace!(
+            "Computed layout: max={:?}. w={}, h={}",
+            max_advance,
+            size.width,
+            size.height,
+        );
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, scene: &mut Scene) {
+        if self.text_layout.needs_rebuild() {
+            debug_panic!("Called RichText paint before layout");
+        }
+        let clips = self.line_break_mode == LineBreaking::Clip;
+        if clips {
+            let clip_rect = ctx.size().to_rect();
+            scene.push_layer(BlendMode::default(), 1., Affine::IDENTITY, &clip_rect);
+        }
+        self.text_layout
+            .draw(scene, Point::new(LABEL_X_PADDING, 0.0));
+        if clips {
+            scene.pop_layer();
+        }
+    }
+
+    fn accessibility_role(&self) -> Role {
+        Role::StaticText
+    }
+
+    fn accessibility(&mut self, ctx: &mut AccessCtx) {
+        ctx.current_node()
+            .set_name(self.text().as_str().to_string());
+    }
+
+    fn children(&self) -> SmallVec<[WidgetRef<'_, dyn Widget>; 16]> {
+        SmallVec::new()
+    }
+
+    fn make_trace_span(&self) -> Span {
+        trace_span!("RichText")
+    }
+
+    fn get_debug_text(&self) -> Option<String> {
+        Some(self.text().as_str().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{widget_ids, TestHarness};
+    use crate::text2::TextSpanStyle;
+    use crate::widget::{Flex, SizedBox};
+
+    fn width(harness: &TestHarness, id: crate::WidgetId) -> f64 {
+        harness.get_widget(id).state().layout_rect().width()
+    }
+
+    #[test]
+    fn spans_change_layout() {
+        let [plain_id, large_id] = widget_ids();
+        let plain = StyledText::new()
+            .with_span("Hello ", TextSpanStyle::default())
+            .with_span("world", TextSpanStyle::default());
+        let large = StyledText::new()
+            .with_span("Hello ", TextSpanStyle::default())
+            .with_span("world", TextSpanStyle::default().font_size(40.0));
+        let widget = Flex::column()
+            .with_child_id(RichText::new(plain.clone()), plain_id)
+            .with_child_id(RichText::new(large), large_id);
+
+        let mut harness = TestHarness::create(widget);
+        assert_eq!(
+            harness.get_widget(plain_id).get_debug_text().as_deref(),
+            Some("Hello world")
+        );
+        assert!(width(&harness, large_id) > width(&harness, plain_id));
+
+        harness.edit_root_widget(|mut root| {
+            let mut flex = root.downcast::<Flex>();
+            let mut large = flex.child_mut(1).unwrap();
+            large.downcast::<RichText>().set_text(plain);
+        });
+        assert_eq!(width(&harness, large_id), width(&harness, plain_id));
+    }
+
+    #[test]
+    fn spans_share_lines() {
+        let [mixed_id, large_id, spaced_id, wrapped_id] = widget_ids();
+        let small = TextSpanStyle::default().font_size(12.0);
+        let large = TextSpanStyle::default().font_size(30.0);
+        let mixed = StyledText::new()
+            .with_span("Hello ", small.clone())
+            .with_span("world", large.clone());
+        let spaced = StyledText::new()
+            .with_span("Hello ", small.clone().line_height(4.0))
+            .with_span("world", large.clone());
+        let widget = Flex::column()
+            .with_child_id(RichText::new(mixed.clone()), mixed_id)
+            .with_child_id(
+                RichText::new(StyledText::new().with_span("Hello world", large)),
+                large_id,
+            )
+            .with_child_id(RichText::new(spaced), spaced_id)
+            .with_child(
+                SizedBox::new_with_id(
+                    RichText::new(mixed.with_span(" again and again", small))
+                        .with_line_break_mode(LineBreaking::WordWrap),
+                    wrapped_id,
+                )
+                .width(100.0),
+            );
+
+        let harness = TestHarness::create(widget);
+        let height = |id| harness.get_widget(id).state().layout_rect().height();
+        let lines = |id| {
+            let rich_text = harness.get_widget(id);
+            let rich_text = rich_text.downcast::<RichText>().unwrap();
+            rich_text.text_layout.layout().len()
+        };
+
+        // Spans of different sizes share a baseline, on a line as tall as the largest.
+        assert_eq!(lines(mixed_id), 1);
+        assert_eq!(height(mixed_id), height(large_id));
+        // A span's line height can make its line taller than the largest span.
+        assert_eq!(lines(spaced_id), 1);
+        assert!(height(spaced_id) > height(mixed_id));
+        // All spans are wrapped as one paragraph.
+        assert!(lines(wrapped_id) > 1);
+    }
+}
@@ -7,15 +7,16 @@ use accesskit::Role;
 use kurbo::Affine;
 use smallvec::{smallvec, SmallVec};
 use tracing::{trace, trace_span, warn, Span};
-use vello::peniko::{BlendMode, Color, Fill, Gradient};
+use vello::peniko::{BlendMode, Color, ColorStop, ColorStops, Fill, Gradient};
 use vello::Scene;
 
 use crate::kurbo::RoundedRectRadii;
-use crate::paint_scene_helpers::{fill_color, stroke};
-use crate::widget::{WidgetId, WidgetMut, WidgetPod, WidgetRef};
+use crate::paint_scene_helpers::fill_color;
+use crate::widget::{Axis, WidgetId, WidgetMut, WidgetPod, WidgetRef};
 use crate::{
-    AccessCtx, AccessEvent, BoxConstraints, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx,
-    Point, PointerEvent, Size, StatusChange, TextEvent, Widget,
+    AccessCtx, AccessEvent, Border, BoxConstraints, BoxShadow, EventCtx, Insets, LayoutCtx,
+    LifeCycle, LifeCycleCtx, PaintCtx, Point, PointerEvent, Size, StatusChange, TextEvent, Vec2,
+    Widget,
 };
 
 // FIXME - Improve all doc in this module ASAP.
@@ -27,17 +28,23 @@ use crate::{
 pub enum BackgroundBrush {
     Color(Color),
     Gradient(Gradient),
+    /// A linear gradient fitted to the widget's bounds.
+    ///
+    /// See [`BackgroundBrush::linear_gradient`].
+    LinearGradient {
+        angle: f64,
+        stops: ColorStops,
+    },
+    /// A radial gradient fitted to the widget's bounds.
+    ///
+    /// See [`BackgroundBrush::radial_gradient`].
+    RadialGradient {
+        stops: ColorStops,
+    },
     PainterFn(Box<dyn FnMut(&mut PaintCtx)>),
 }
 
-/// Something that can be used as the border for a widget.
-struct BorderStyle {
-    width: f64,
-    color: Color,
-}
-
 // TODO - Have Widget type as generic argument
-// TODO - Add Padding
 
 /// A widget with predefined size.
 ///
@@ -48,37 +55,60 @@ struct BorderStyle {
 /// If not given a child, `SizedBox` will try to size itself as close to the specified height
 /// and width as possible given the parent's constraints. If height or width is not set,
 /// it will be treated as zero.
+///
+/// If an aspect ratio is set and only one of width or height is set, the other dimension
+/// follows from the ratio. If neither is set, the box takes the largest size with that ratio
+/// which fits in the parent's constraints. If both are set, the ratio is ignored.
+///
+/// A minimum and maximum size can be set for each dimension, which narrow the parent's
+/// constraints before any of the above is applied. Where they conflict with the parent's
+/// constraints, the parent's constraints win.
+///
+/// Padding is added between the border and the child, and counts towards the size of the box.
 pub struct SizedBox {
     child: Option<WidgetPod<Box<dyn Widget>>>,
     width: Option<f64>,
     height: Option<f64>,
+    aspect_ratio: Option<f64>,
+    min_width: Option<f64>,
+    min_height: Option<f64>,
+    max_width: Option<f64>,
+    max_height: Option<f64>,
     background: Option<BackgroundBrush>,
-    border: Option<BorderStyle>,
+    border: Option<Border>,
     corner_radius: RoundedRectRadii,
+    shadows: Vec<BoxShadow>,
+    padding: Insets,
 }
 
 impl SizedBox {
     /// Construct container with child, and both width and height not set.
     pub fn new(child: impl Widget) -> Self {
-        Self {
-            child: Some(WidgetPod::new(child).boxed()),
-            width: None,
-            height: None,
-            background: None,
-            border: None,
-            corner_radius: RoundedRectRadii::from_single_radius(0.0),
-        }
+        Self::from_child_pod(WidgetPod::new(child).boxed())
     }
 
     /// Construct container with child, and both width and height not set.
     pub fn new_with_id(child: impl Widget, id: WidgetId) -> Self {
+        Self::from_child_pod(WidgetPod::new_with_id(child, id).boxed())
+    }
+
+    /// Construct container with a child which is already in a [`WidgetPod`],
+    /// and both width and height not set.
+    pub fn from_child_pod(child: WidgetPod<Box<dyn Widget>>) -> Self {
         Self {
-            child: Some(WidgetPod::new_with_id(child, id).boxed()),
+            child: Some(child),
             width: None,
             height: None,
+            aspect_ratio: None,
+            min_width: None,
+            min_height: None,
+            max_width: None,
+            max_height: None,
             background: None,
             border: None,
             corner_radius: RoundedRectRadii::from_single_radius(0.0),
+            shadows: Vec::new(),
+            padding: Insets::ZERO,
         }
     }
 
@@ -92,9 +122,16 @@ impl SizedBox {
             child: None,
             width: None,
             height: None,
+            aspect_ratio: None,
+            min_width: None,
+            min_height: None,
+            max_width: None,
+            max_height: None,
             background: None,
             border: None,
             corner_radius: RoundedRectRadii::from_single_radius(0.0),
+            shadows: Vec::new(),
+            padding: Insets::ZERO,
         }
     }
 
@@ -110,6 +147,36 @@ impl SizedBox {
         self
     }
 
+    /// Keep the container at the given width-to-height ratio, e.g. `16. / 9.`.
+    ///
+    /// See the [type-level documentation](SizedBox) for how this interacts with width and height.
+    pub fn aspect_ratio(mut self, ratio: f64) -> Self {
+        debug_assert!(
+            ratio.is_finite() && ratio > 0.0,
+            "aspect ratio must be finite and positive, got {ratio}"
+        );
+        self.aspect_ratio = Some(ratio);
+        self
+    }
+
+    /// Keep the container at least this wide and/or tall.
+    ///
+    /// Pass `None` to leave a dimension unconstrained.
+    pub fn with_min_size(mut self, width: Option<f64>, height: Option<f64>) -> Self {
+        self.min_width = width;
+        self.min_height = height;
+        self
+    }
+
+    /// Keep the container at most this wide and/or tall.
+    ///
+    /// Pass `None` to leave a dimension unconstrained.
+    pub fn with_max_size(mut self, width: Option<f64>, height: Option<f64>) -> Self {
+        self.max_width = width;
+        self.max_height = height;
+        self
+    }
+
     /// Expand container to fit the parent.
     ///
     /// Only call this method if you want your widget to occupy all available
@@ -151,10 +218,16 @@ impl SizedBox {
 
     /// Builder-style method for painting a border around the widget with a color and width.
     pub fn border(mut self, color: impl Into<Color>, width: impl Into<f64>) -> Self {
-        self.border = Some(BorderStyle {
-            color: color.into(),
-            width: width.into(),
-        });
+        self.border = Some(Border::new(color, width.into()));
+        self
+    }
+
+    /// Builder-style method for painting a [`Border`] around the widget.
+    ///
+    /// Unlike [`border`](Self::border), this allows a different width on each side,
+    /// and dashed or dotted lines.
+    pub fn with_border(mut self, border: Border) -> Self {
+        self.border = Some(border);
         self
     }
 
@@ -164,6 +237,23 @@ impl SizedBox {
         self
     }
 
+    /// Builder-style method for adding a shadow behind this container.
+    ///
+    /// This can be called several times to stack shadows.
+    pub fn with_shadow(mut self, shadow: BoxShadow) -> Self {
+        self.shadows.push(shadow);
+        self
+    }
+
+    /// Builder-style method for adding space between the border and the child.
+    ///
+    /// This accepts a uniform value, a `(horizontal, vertical)` pair, or
+    /// `(left, top, right, bottom)` values.
+    pub fn with_padding(mut self, padding: impl Into<Insets>) -> Self {
+        self.padding = padding.into();
+        self
+    }
+
     // TODO - child()
 }
 
@@ -204,6 +294,40 @@ impl WidgetMut<'_, SizedBox> {
         self.ctx.request_layout();
     }
 
+    /// Keep the container at the given width-to-height ratio.
+    pub fn set_aspect_ratio(&mut self, ratio: f64) {
+        debug_assert!(
+            ratio.is_finite() && ratio > 0.0,
+            "aspect ratio must be finite and positive, got {ratio}"
+        );
+        self.widget.aspect_ratio = Some(ratio);
+        self.ctx.request_layout();
+    }
+
+    /// Clears aspect ratio.
+    pub fn clear_aspect_ratio(&mut self) {
+        self.widget.aspect_ratio = None;
+        self.ctx.request_layout();
+    }
+
+    /// Keep the container at least this wide and/or tall.
+    ///
+    /// Pass `None` to leave a dimension unconstrained.
+    pub fn set_min_size(&mut self, width: Option<f64>, height: Option<f64>) {
+        self.widget.min_width = width;
+        self.widget.min_height = height;
+        self.ctx.request_layout();
+    }
+
+    /// Keep the container at most this wide and/or tall.
+    ///
+    /// Pass `None` to leave a dimension unconstrained.
+    pub fn set_max_size(&mut self, width: Option<f64>, height: Option<f64>) {
+        self.widget.max_width = width;
+        self.widget.max_height = height;
+        self.ctx.request_layout();
+    }
+
     /// Set the background for this widget.
     ///
     /// This can be passed anything which can be represented by a [`BackgroundBrush`];
@@ -221,10 +345,13 @@ impl WidgetMut<'_, SizedBox> {
 
     /// Paint a border around the widget with a color and width.
     pub fn set_border(&mut self, color: impl Into<Color>, width: impl Into<f64>) {
-        self.widget.border = Some(BorderStyle {
-            color: color.into(),
-            width: width.into(),
-        });
+        self.widget.border = Some(Border::new(color, width.into()));
+        self.ctx.request_layout();
+    }
+
+    /// Paint a [`Border`] around the widget.
+    pub fn set_border_to(&mut self, border: Border) {
+        self.widget.border = Some(border);
         self.ctx.request_layout();
     }
 
@@ -240,6 +367,19 @@ impl WidgetMut<'_, SizedBox> {
         self.ctx.request_paint();
     }
 
+    /// Set the shadows drawn behind this container, from bottom to top.
+    pub fn set_shadows(&mut self, shadows: Vec<BoxShadow>) {
+        self.widget.shadows = shadows;
+        // The shadows change the paint insets.
+        self.ctx.request_layout();
+    }
+
+    /// Set the space between the border and the child.
+    pub fn set_padding(&mut self, padding: impl Into<Insets>) {
+        self.widget.padding = padding.into();
+        self.ctx.request_layout();
+    }
+
     // TODO - Doc
     pub fn child_mut(&mut self) -> Option<WidgetMut<'_, Box<dyn Widget>>> {
         let child = self.widget.child.as_mut()?;
@@ -248,10 +388,64 @@ impl WidgetMut<'_, SizedBox> {
 }
 
 impl SizedBox {
+    /// Narrows the parent's constraints by this box's minimum and maximum size.
+    fn min_max_constraints(&self, bc: &BoxConstraints) -> BoxConstraints {
+        let clamp_axis = |min: f64, max: f64, our_min: Option<f64>, our_max: Option<f64>| {
+            let new_min = our_min.map_or(min, |our_min| our_min.clamp(min, max));
+            let new_max = our_max.map_or(max, |our_max| our_max.clamp(new_min, max));
+            (new_min, new_max)
+        };
+        let (min_width, max_width) = clamp_axis(
+            bc.min().width,
+            bc.max().width,
+            self.min_width,
+            self.max_width,
+        );
+        let (min_height, max_height) = clamp_axis(
+            bc.min().height,
+            bc.max().height,
+            self.min_height,
+            self.max_height,
+        );
+        BoxConstraints::new(
+            Size::new(min_width, min_height),
+            Size::new(max_width, max_height),
+        )
+    }
+
+    /// Returns the width and height this box should have, taking the aspect ratio into account.
+    fn resolved_dimensions(&self, bc: &BoxConstraints) -> (Option<f64>, Option<f64>) {
+        let Some(ratio) = self.aspect_ratio else {
+            return (self.width, self.height);
+        };
+        match (self.width, self.height) {
+            (Some(_), Some(_)) => (self.width, self.height),
+            (Some(width), None) => {
+                let width = width.max(bc.min().width).min(bc.max().width);
+                (Some(width), Some(width / ratio))
+            }
+            (None, Some(height)) => {
+                let height = height.max(bc.min().height).min(bc.max().height);
+                (Some(height * ratio), Some(height))
+            }
+            (None, None) => {
+                let width = bc.max().width.min(bc.max().height * ratio);
+                if width.is_finite() {
+                    (Some(width), Some(width / ratio))
+                } else {
+                    // We have no bound to derive a size from.
+                    (None, None)
+                }
+            }
+        }
+    }
+
     fn child_constraints(&self, bc: &BoxConstraints) -> BoxConstraints {
+        let (width, height) = self.resolved_dimensions(bc);
+
         // if we don't have a width/height, we don't change that axis.
         // if we have a width/height, we clamp it on that axis.
-        let (min_width, max_width) = match self.width {
+        let (min_width, max_width) = match width {
             Some(width) => {
                 let w = width.max(bc.min().width).min(bc.max().width);
                 (w, w)
@@ -259,7 +453,7 @@ impl SizedBox {
             None => (bc.min().width, bc.max().width),
         };
 
-        let (min_height, max_height) = match self.height {
+        let (min_height, max_height) = match height {
             Some(height) => {
                 let h = height.max(bc.min().height).min(bc.max().height);
                 (h, h)
@@ -304,29 +498,33 @@ impl Widget for SizedBox {
 
     fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints) -> Size {
         // Shrink constraints by border offset
-        let border_width = match &self.border {
-            Some(border) => border.width,
-            None => 0.0,
+        let border = match &self.border {
+            Some(border) => border.widths.to_insets(),
+            None => Insets::ZERO,
         };
 
+        let bc = &self.min_max_constraints(bc);
         let child_bc = self.child_constraints(bc);
-        let child_bc = child_bc.shrink((2.0 * border_width, 2.0 * border_width));
-        let origin = Point::new(border_width, border_width);
+        let insets = self.padding.size() + border.size();
+        let child_bc = child_bc.shrink(insets);
+        let origin = Point::new(border.x0 + self.padding.x0, border.y0 + self.padding.y0);
 
         let mut size;
         match self.child.as_mut() {
             Some(child) => {
                 size = child.layout(ctx, &child_bc);
                 ctx.place_child(child, origin);
-                size = Size::new(
-                    size.width + 2.0 * border_width,
-                    size.height + 2.0 * border_width,
-                );
+                size += insets;
+            }
+            None => {
+                let (width, height) = self.resolved_dimensions(bc);
+                size = bc.constrain((width.unwrap_or(0.0), height.unwrap_or(0.0)));
             }
-            None => size = bc.constrain((self.width.unwrap_or(0.0), self.height.unwrap_or(0.0))),
         };
 
-        // TODO - figure out paint insets
+        // The paint rects of children are added to ours by `WidgetPod`.
+        ctx.set_paint_insets(BoxShadow::union_paint_insets(&self.shadows));
+
         // TODO - figure out baseline offset
 
         trace!("Computed size: {}", size);
@@ -341,9 +539,41 @@ impl Widget for SizedBox {
         size
     }
 
+    fn intrinsic_size(&mut self, ctx: &mut LayoutCtx, axis: Axis, cross_size: f64) -> f64 {
+        let border = match &self.border {
+            Some(border) => border.widths.to_insets(),
+            None => Insets::ZERO,
+        };
+        let insets = self.padding.size() + border.size();
+
+        // The box is measured with the constraints the default implementation would use.
+        let max: Size = axis.pack(f64::INFINITY, cross_size).into();
+        let bc = self.min_max_constraints(&BoxConstraints::new(Size::ZERO, max));
+        let (width, height) = self.resolved_dimensions(&bc);
+        let (size, cross) = match axis {
+            Axis::Horizontal => (width, height),
+            Axis::Vertical => (height, width),
+        };
+
+        let size = match (size, self.child.as_mut()) {
+            (Some(size), _) => size,
+            (None, Some(child)) => {
+                let cross = cross.unwrap_or(cross_size);
+                let child_cross_size = (cross - axis.minor(insets)).max(0.0);
+                child.intrinsic_size(ctx, axis, child_cross_size) + axis.major(insets)
+            }
+            (None, None) => 0.0,
+        };
+        size.clamp(axis.major(bc.min()), axis.major(bc.max()))
+    }
+
     fn paint(&mut self, ctx: &mut PaintCtx, scene: &mut Scene) {
         let corner_radius = self.corner_radius;
 
+        for shadow in &self.shadows {
+            shadow.paint(scene, ctx.size().to_rect(), corner_radius);
+        }
+
         if let Some(background) = self.background.as_mut() {
             let panel = ctx.size().to_rounded_rect(corner_radius);
 
@@ -355,13 +585,7 @@ impl Widget for SizedBox {
         }
 
         if let Some(border) = &self.border {
-            let border_width = border.width;
-            let border_rect = ctx
-                .size()
-                .to_rect()
-                .inset(border_width / -2.0)
-                .to_rounded_rect(corner_radius);
-            stroke(scene, &border_rect, border.color, border_width);
+            border.paint(scene, ctx.size(), corner_radius);
         };
 
         if let Some(ref mut child) = self.child {
@@ -395,9 +619,33 @@ impl Widget for SizedBox {
 // --- BackgroundBrush ---
 
 impl BackgroundBrush {
+    /// A linear gradient going across the widget in the direction of `angle_deg`.
+    ///
+    /// Like in CSS, an angle of 0 goes from the bottom to the top, and larger angles turn
+    /// clockwise, so 90 goes from left to right. The gradient line is long enough that
+    /// the first and last stops touch the corners of the widget.
+    ///
+    /// Each stop is a color and its offset along the gradient, between 0 and 1.
+    pub fn linear_gradient(angle_deg: f64, stops: &[(Color, f64)]) -> Self {
+        Self::LinearGradient {
+            angle: angle_deg,
+            stops: color_stops(stops),
+        }
+    }
+
+    /// A radial gradient from the center of the widget to its corners.
+    ///
+    /// Each stop is a color and its offset from the center, between 0 and 1.
+    pub fn radial_gradient(stops: &[(Color, f64)]) -> Self {
+        Self::RadialGradient {
+            stops: color_stops(stops),
+        }
+    }
+
     /// Draw this brush into a provided [`PaintCtx`].
     pub fn paint(&mut self, ctx: &mut PaintCtx, scene: &mut Scene) {
-        let bounds = ctx.size().to_rect();
+        let size = ctx.size();
+        let bounds = size.to_rect();
         match self {
             Self::Color(color) => fill_color(scene, &bounds, *color),
             Self::Gradient(grad) => scene.fill(
@@ -407,11 +655,45 @@ impl BackgroundBrush {
                 Some(Affine::IDENTITY),
                 &bounds,
             ),
+            Self::LinearGradient { angle, stops } => {
+                let (start, end) = linear_gradient_line(size, *angle);
+                let grad = Gradient::new_linear(start, end).with_stops(&stops[..]);
+                scene.fill(Fill::NonZero, Affine::IDENTITY, &grad, None, &bounds);
+            }
+            Self::RadialGradient { stops } => {
+                let radius = (size.to_vec2() / 2.).hypot();
+                let grad =
+                    Gradient::new_radial(bounds.center(), radius as f32).with_stops(&stops[..]);
+                scene.fill(Fill::NonZero, Affine::IDENTITY, &grad, None, &bounds);
+            }
             Self::PainterFn(painter) => painter(ctx),
         }
     }
 }
 
+fn color_stops(stops: &[(Color, f64)]) -> ColorStops {
+    stops
+        .iter()
+        .map(|&(color, offset)| ColorStop {
+            offset: offset as f32,
+            color,
+        })
+        .collect()
+}
+
+/// The start and end points of a linear gradient at `angle_deg` over a box of the given size.
+///
+/// This follows the CSS `linear-gradient` rules, so that the corners of the box are
+/// exactly at the start and end of the gradient.
+fn linear_gradient_line(size: Size, angle_deg: f64) -> (Point, Point) {
+    let angle = angle_deg.to_radians();
+    let direction = Vec2::new(angle.sin(), -angle.cos());
+    let length = (size.width * direction.x).abs() + (size.height * direction.y).abs();
+    let center = size.to_rect().center();
+    let half_line = direction * (length / 2.);
+    (center - half_line, center + half_line)
+}
+
 impl From<Color> for BackgroundBrush {
     fn from(src: Color) -> BackgroundBrush {
         BackgroundBrush::Color(src)
@@ -441,6 +723,41 @@ mod tests {
     use crate::testing::TestHarness;
     use crate::widget::Label;
 
+    #[test]
+    fn linear_gradient_reaches_corners() {
+        let assert_near = |(start, end): (Point, Point), expected: (Point, Point)| {
+            assert!(
+                (start - expected.0).hypot() < 1e-9,
+                "{start:?} != {:?}",
+                expected.0
+            );
+            assert!(
+                (end - expected.1).hypot() < 1e-9,
+                "{end:?} != {:?}",
+                expected.1
+            );
+        };
+        let size = Size::new(100., 50.);
+
+        assert_near(
+            linear_gradient_line(size, 0.),
+            (Point::new(50., 50.), Point::new(50., 0.)),
+        );
+        assert_near(
+            linear_gradient_line(size, 90.),
+            (Point::new(0., 25.), Point::new(100., 25.)),
+        );
+        assert_near(
+            linear_gradient_line(size, 180.),
+            (Point::new(50., 0.), Point::new(50., 50.)),
+        );
+        // For a square at 45 degrees, the line goes from one corner to the other.
+        assert_near(
+            linear_gradient_line(Size::new(10., 10.), 45.),
+            (Point::new(0., 10.), Point::new(10., 0.)),
+        );
+    }
+
     #[test]
     fn expand() {
         let expand = SizedBox::new(Label::new("hello!")).expand();
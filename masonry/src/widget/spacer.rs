@@ -0,0 +1,99 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Empty space between the children of a flex container.
+
+use accesskit::Role;
+use smallvec::SmallVec;
+use tracing::{trace_span, Span};
+use vello::Scene;
+
+use crate::widget::{WidgetMut, WidgetRef};
+use crate::{
+    AccessCtx, AccessEvent, BoxConstraints, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx,
+    PointerEvent, Size, StatusChange, TextEvent, Widget,
+};
+
+/// Empty space between the children of a [`Flex`](crate::widget::Flex) container.
+///
+/// A fixed spacer takes `len` along the container's main axis, and no space across it.
+/// A flexible spacer, made with [`Spacer::flex`], is added to the container as a flex
+/// child, so it takes a share of the space the other children leave over.
+///
+/// Unlike the spacers added with [`Flex::with_spacer`](crate::widget::Flex::with_spacer),
+/// this is a widget, so it can be inserted and removed like any other child.
+pub struct Spacer {
+    len: f64,
+    flex: Option<f64>,
+}
+
+impl Spacer {
+    /// Create a spacer which is `len` long along the main axis of its container.
+    pub fn new(len: f64) -> Self {
+        Self {
+            len: len.max(0.0),
+            flex: None,
+        }
+    }
+
+    /// Create a spacer which grows to fill the space left over in its container, like
+    /// a flex child with the given flex factor.
+    pub fn flex(flex: f64) -> Self {
+        Self {
+            len: 0.0,
+            flex: Some(flex),
+        }
+    }
+
+    /// The length of a fixed spacer.
+    pub fn len(&self) -> f64 {
+        self.len
+    }
+
+    /// The flex factor of a flexible spacer.
+    pub fn flex_factor(&self) -> Option<f64> {
+        self.flex
+    }
+}
+
+impl WidgetMut<'_, Spacer> {
+    /// Set the length of a fixed spacer.
+    pub fn set_len(&mut self, len: f64) {
+        self.widget.len = len.max(0.0);
+        self.ctx.request_layout();
+    }
+}
+
+impl Widget for Spacer {
+    fn on_pointer_event(&mut self, _ctx: &mut EventCtx, _event: &PointerEvent) {}
+
+    fn on_text_event(&mut self, _ctx: &mut EventCtx, _event: &TextEvent) {}
+
+    fn on_access_event(&mut self, _ctx: &mut EventCtx, _event: &AccessEvent) {}
+
+    fn on_status_change(&mut self, _ctx: &mut LifeCycleCtx, _event: &StatusChange) {}
+
+    fn lifecycle(&mut self, _ctx: &mut LifeCycleCtx, _event: &LifeCycle) {}
+
+    // A Flex container lays spacers out with tight constraints along its main axis.
+    // Elsewhere, a spacer is a square.
+    fn layout(&mut self, _ctx: &mut LayoutCtx, bc: &BoxConstraints) -> Size {
+        bc.constrain(Size::new(self.len, self.len))
+    }
+
+    fn paint(&mut self, _ctx: &mut PaintCtx, _scene: &mut Scene) {}
+
+    fn accessibility_role(&self) -> Role {
+        Role::GenericContainer
+    }
+
+    fn accessibility(&mut self, _ctx: &mut AccessCtx) {}
+
+    fn children(&self) -> SmallVec<[WidgetRef<'_, dyn Widget>; 16]> {
+        SmallVec::new()
+    }
+
+    fn make_trace_span(&self) -> Span {
+        trace_span!("Spacer")
+    }
+}
@@ -24,6 +24,9 @@ use crate::{
 /// To customize the spinner's size, you can place it inside a [`SizedBox`]
 /// that has a fixed width and height.
 ///
+/// The spinner doesn't turn while the user asks for
+/// [reduced motion](crate::EventCtx::reduced_motion).
+///
 /// [`SizedBox`]: struct.SizedBox.html
 pub struct Spinner {
     t: f64,
@@ -84,6 +87,11 @@ impl Widget for Spinner {
                 ctx.request_paint();
             }
             LifeCycle::AnimFrame(interval) => {
+                // The spinner stands still until the user no longer asks for less motion,
+                // when every widget gets an animation frame again.
+                if ctx.reduced_motion() {
+                    return;
+                }
                 self.t += (*interval as f64) * 1e-9;
                 if self.t >= 1.0 {
                     self.t = 0.0;
@@ -154,6 +162,8 @@ impl Widget for Spinner {
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
     use super::*;
     use crate::assert_render_snapshot;
     use crate::testing::TestHarness;
@@ -171,6 +181,23 @@ mod tests {
         //assert_render_snapshot!(harness, "spinner_700ms");
     }
 
+    #[test]
+    fn reduced_motion_stops_spinner() {
+        fn t(harness: &TestHarness) -> f64 {
+            harness.root_widget().downcast::<Spinner>().unwrap().t
+        }
+
+        let mut harness = TestHarness::create(Spinner::new());
+        harness.set_reduced_motion(true);
+        harness.animate_by(Duration::from_millis(250));
+        harness.animate_by(Duration::from_millis(250));
+        assert_eq!(t(&harness), 0.0);
+
+        harness.set_reduced_motion(false);
+        harness.animate_by(Duration::from_millis(250));
+        assert_eq!(t(&harness), 0.25);
+    }
+
     #[test]
     fn edit_spinner() {
         let image_1 = {
@@ -0,0 +1,435 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! A scrollable column of items, like a picker wheel.
+
+use accesskit::Role;
+use kurbo::{Affine, Point};
+use smallvec::SmallVec;
+use tracing::{trace, trace_span, Span};
+use vello::peniko::BlendMode;
+use vello::Scene;
+use winit::event::MouseButton;
+use winit::keyboard::{Key, NamedKey};
+
+use crate::action::Action;
+use crate::paint_scene_helpers::{fill_color, stroke};
+use crate::text2::TextLayout;
+use crate::widget::{WidgetMut, WidgetRef};
+use crate::{
+    theme, AccessCtx, AccessEvent, ArcStr, BoxConstraints, EventCtx, LayoutCtx, LifeCycle,
+    LifeCycleCtx, PaintCtx, PointerEvent, Rect, ScrollDelta, Size, StatusChange, TextEvent, Widget,
+};
+
+/// The height of each item.
+const ROW_HEIGHT: f64 = 28.;
+/// The number of items shown at once, with the selected one in the middle.
+const VISIBLE_ROWS: usize = 5;
+/// How far the pointer must move while pressed before it drags the column, rather than
+/// clicking an item.
+const DRAG_THRESHOLD: f64 = 4.;
+const PADDING: f64 = 8.;
+
+/// A column of items which scrolls so that the selected item is in the middle, like the
+/// wheels of a mobile time picker.
+///
+/// The selection is changed by scrolling, by dragging the column, by clicking an item, or
+/// with the arrow keys, Home and End when focused. [`Action::SpinnerColumnSelected`] is
+/// submitted with the index of the newly selected item.
+pub struct SpinnerColumn {
+    items: Vec<TextLayout<ArcStr>>,
+    selected: usize,
+    /// The scroll position, as a fractional index of the item in the middle. This is only
+    /// different from `selected` while dragging.
+    offset: f64,
+    drag: Option<Drag>,
+    /// Pixels scrolled by a touchpad which haven't amounted to an item yet.
+    wheel_remainder: f64,
+    /// Whether changes are submitted as actions, rather than observed by a parent widget.
+    submits_actions: bool,
+    name: Option<ArcStr>,
+}
+
+struct Drag {
+    start_y: f64,
+    start_offset: f64,
+    /// Whether the pointer has moved far enough for this to be a drag rather than a click.
+    dragging: bool,
+}
+
+impl SpinnerColumn {
+    /// Create a new column with the given items, and the item at `selected` selected.
+    pub fn new(items: impl IntoIterator<Item = impl Into<ArcStr>>, selected: usize) -> Self {
+        let items: Vec<_> = items.into_iter().map(item_text).collect();
+        let selected = selected.min(items.len().saturating_sub(1));
+        Self {
+            items,
+            selected,
+            offset: selected as f64,
+            drag: None,
+            wheel_remainder: 0.,
+            submits_actions: true,
+            name: None,
+        }
+    }
+
+    /// Builder-style method to set the name the column is announced as by screen readers.
+    pub fn with_name(mut self, name: impl Into<ArcStr>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Don't submit actions when the selection changes, for a parent which observes it
+    /// through [`selected`](Self::selected) instead.
+    pub(crate) fn without_actions(mut self) -> Self {
+        self.submits_actions = false;
+        self
+    }
+
+    /// The index of the selected item.
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    /// The number of items.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns `true` if there are no items.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    fn last_index(&self) -> usize {
+        self.items.len().saturating_sub(1)
+    }
+
+    /// Select the item at `idx`, scrolling it to the middle, and submit the change.
+    fn select(&mut self, ctx: &mut EventCtx, idx: usize) {
+        let idx = idx.min(self.last_index());
+        self.offset = idx as f64;
+        ctx.request_paint();
+        if idx == self.selected {
+            return;
+        }
+        trace!("SpinnerColumn {:?} selected {}", ctx.widget_id(), idx);
+        self.selected = idx;
+        if self.submits_actions {
+            ctx.submit_action(Action::SpinnerColumnSelected(idx));
+        }
+        ctx.request_accessibility_update();
+    }
+
+    /// Select the item `steps` items after the selected one, or before it if negative.
+    fn step(&mut self, ctx: &mut EventCtx, steps: isize) {
+        self.select(ctx, self.selected.saturating_add_signed(steps));
+    }
+
+    /// The index of the item requested by a [`SetValue`](accesskit::Action::SetValue) action.
+    ///
+    /// This is the item with the requested text, ignoring case, or failing that the item
+    /// with the same number, so that "5" selects "05".
+    fn requested_item(&self, event: &AccessEvent) -> Option<usize> {
+        if let Some(accesskit::ActionData::Value(text)) = &event.data {
+            let text = text.trim();
+            if let Some(idx) = self
+                .items
+                .iter()
+                .position(|item| item.text().eq_ignore_ascii_case(text))
+            {
+                return Some(idx);
+            }
+        }
+        let value = event.numeric_value()?;
+        self.items
+            .iter()
+            .position(|item| item.text().trim().parse::<f64>().ok() == Some(value))
+    }
+
+    /// The index of the item at `y`, relative to the column, if any.
+    fn item_at(&self, y: f64, height: f64) -> Option<usize> {
+        let idx = (self.offset + (y - height / 2.) / ROW_HEIGHT).round();
+        (idx >= 0. && idx <= self.last_index() as f64 && !self.items.is_empty())
+            .then_some(idx as usize)
+    }
+}
+
+fn item_text(item: impl Into<ArcStr>) -> TextLayout<ArcStr> {
+    TextLayout::new(item.into(), theme::TEXT_SIZE_NORMAL as f32)
+}
+
+impl WidgetMut<'_, SpinnerColumn> {
+    /// Replace the items, keeping the selected index if it is still in range.
+    pub fn set_items(&mut self, items: impl IntoIterator<Item = impl Into<ArcStr>>) {
+        self.widget.items = items.into_iter().map(item_text).collect();
+        let selected = self.widget.selected.min(self.widget.last_index());
+        self.set_selected(selected);
+        self.ctx.request_layout();
+        self.ctx.request_accessibility_update();
+    }
+
+    /// Select the item at `selected`, without submitting an action.
+    pub fn set_selected(&mut self, selected: usize) {
+        let selected = selected.min(self.widget.last_index());
+        self.widget.selected = selected;
+        self.widget.offset = selected as f64;
+        self.widget.drag = None;
+        self.ctx.request_paint();
+        self.ctx.request_accessibility_update();
+    }
+}
+
+impl Widget for SpinnerColumn {
+    fn on_pointer_event(&mut self, ctx: &mut EventCtx, event: &PointerEvent) {
+        if ctx.is_disabled() || self.items.is_empty() {
+            return;
+        }
+        let origin_y = ctx.window_origin().y;
+        let local_y = |state: &crate::event::PointerState| state.position.y - origin_y;
+        match event {
+            PointerEvent::PointerDown(MouseButton::Left, state) => {
+                ctx.set_active(true);
+                ctx.capture_pointer();
+                self.drag = Some(Drag {
+                    start_y: local_y(state),
+                    start_offset: self.offset,
+                    dragging: false,
+                });
+                ctx.set_handled();
+            }
+            PointerEvent::PointerMove(state) if ctx.is_active() => {
+                let y = local_y(state);
+                if let Some(drag) = &mut self.drag {
+                    let distance = y - drag.start_y;
+                    drag.dragging |= distance.abs() > DRAG_THRESHOLD;
+                    if drag.dragging {
+                        self.offset = (drag.start_offset - distance / ROW_HEIGHT)
+                            .clamp(0., self.last_index() as f64);
+                        ctx.request_paint();
+                    }
+                }
+            }
+            PointerEvent::PointerUp(MouseButton::Left, state) if ctx.is_active() => {
+                ctx.set_active(false);
+                ctx.release_pointer();
+                let y = local_y(state);
+                match self.drag.take() {
+                    Some(Drag { dragging: true, .. }) => {
+                        self.select(ctx, self.offset.round() as usize);
+                    }
+                    Some(_) => {
+                        if let Some(idx) = self.item_at(y, ctx.size().height) {
+                            self.select(ctx, idx);
+                        }
+                    }
+                    None => {}
+                }
+            }
+            PointerEvent::MouseWheel(delta, _) if !ctx.is_handled() => {
+                let steps = match delta {
+                    ScrollDelta::Lines(lines) => lines.y.round(),
+                    ScrollDelta::Precise(pixels) => {
+                        self.wheel_remainder += pixels.y;
+                        let steps = (self.wheel_remainder / ROW_HEIGHT).trunc();
+                        self.wheel_remainder -= steps * ROW_HEIGHT;
+                        steps
+                    }
+                };
+                if steps != 0. {
+                    self.step(ctx, steps as isize);
+                }
+                ctx.set_handled();
+            }
+            _ => {}
+        }
+    }
+
+    fn on_text_event(&mut self, ctx: &mut EventCtx, event: &TextEvent) {
+        if let TextEvent::KeyboardKey(key_event, _) = event {
+            if key_event.state.is_pressed() && !ctx.is_disabled() {
+                match &key_event.logical_key {
+                    Key::Named(NamedKey::ArrowUp) => self.step(ctx, -1),
+                    Key::Named(NamedKey::ArrowDown) => self.step(ctx, 1),
+                    Key::Named(NamedKey::Home) => self.select(ctx, 0),
+                    Key::Named(NamedKey::End) => self.select(ctx, self.last_index()),
+                    _ => return,
+                }
+                ctx.set_handled();
+            }
+        }
+    }
+
+    fn on_access_event(&mut self, ctx: &mut EventCtx, event: &AccessEvent) {
+        if event.target == ctx.widget_id() && !ctx.is_disabled() {
+            match event.action {
+                accesskit::Action::Increment => self.step(ctx, 1),
+                accesskit::Action::Decrement => self.step(ctx, -1),
+                accesskit::Action::SetValue => {
+                    if let Some(idx) = self.requested_item(event) {
+                        self.select(ctx, idx);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn on_status_change(&mut self, ctx: &mut LifeCycleCtx, _event: &StatusChange) {
+        ctx.request_paint();
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle) {
+        if let LifeCycle::BuildFocusChain = event {
+            ctx.register_for_focus();
+        }
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints) -> Size {
+        let mut width: f64 = 0.;
+        for item in &mut self.items {
+            if item.needs_rebuild() {
+                item.rebuild(ctx.font_ctx());
+            }
+            width = width.max(item.size().width);
+        }
+        let size = bc.constrain(Size::new(
+            width + 2. * PADDING,
+            VISIBLE_ROWS as f64 * ROW_HEIGHT,
+        ));
+        trace!("Computed size: {}", size);
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, scene: &mut Scene) {
+        let size = ctx.size();
+        let middle = size.height / 2.;
+        let band = Rect::new(
+            0.,
+            middle - ROW_HEIGHT / 2.,
+            size.width,
+            middle + ROW_HEIGHT / 2.,
+        )
+        .to_rounded_rect(4.);
+        let theme = ctx.theme();
+        let text_color = theme.on_surface;
+        fill_color(scene, &band, theme.field[0]);
+        if ctx.is_focused() {
+            stroke(scene, &band, theme.primary, 1.);
+        }
+
+        // Items away from the middle are faded, and cut off at the edges.
+        let nearest = self.offset.round() as usize;
+        let clip_rect = size.to_rect();
+        scene.push_layer(BlendMode::default(), 1., Affine::IDENTITY, &clip_rect);
+        for pass in [false, true] {
+            if !pass {
+                scene.push_layer(BlendMode::default(), 0.4, Affine::IDENTITY, &clip_rect);
+            }
+            for (idx, item) in self.items.iter_mut().enumerate() {
+                if (idx == nearest) != pass {
+                    continue;
+                }
+                let y = middle + (idx as f64 - self.offset) * ROW_HEIGHT;
+                if y < -ROW_HEIGHT || y > size.height + ROW_HEIGHT {
+                    continue;
+                }
+                // The theme can change without a new layout.
+                item.set_brush(text_color);
+                if item.needs_rebuild() {
+                    item.rebuild(ctx.font_ctx());
+                }
+                let item_size = item.size();
+                item.draw(
+                    scene,
+                    Point::new(
+                        (size.width - item_size.width) / 2.,
+                        y - item_size.height / 2.,
+                    ),
+                );
+            }
+            if !pass {
+                scene.pop_layer();
+            }
+        }
+        scene.pop_layer();
+    }
+
+    fn accessibility_role(&self) -> Role {
+        Role::SpinButton
+    }
+
+    fn accessibility(&mut self, ctx: &mut AccessCtx) {
+        let node = ctx.current_node();
+        if let Some(name) = &self.name {
+            node.set_name(name.to_string());
+        }
+        if let Some(item) = self.items.get(self.selected) {
+            node.set_value(item.text().to_string());
+        }
+        node.add_action(accesskit::Action::Increment);
+        node.add_action(accesskit::Action::Decrement);
+        node.add_action(accesskit::Action::SetValue);
+    }
+
+    fn children(&self) -> SmallVec<[WidgetRef<'_, dyn Widget>; 16]> {
+        SmallVec::new()
+    }
+
+    fn make_trace_span(&self) -> Span {
+        trace_span!("SpinnerColumn")
+    }
+
+    fn get_debug_text(&self) -> Option<String> {
+        self.items
+            .get(self.selected)
+            .map(|item| item.text().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{widget_ids, TestHarness};
+    use crate::widget::Flex;
+    use crate::Vec2;
+
+    #[test]
+    fn wheel_drag_and_click_select() {
+        let [column_id] = widget_ids();
+        let items: Vec<String> = (0..10).map(|n| n.to_string()).collect();
+        let widget = Flex::column().with_child_id(SpinnerColumn::new(items, 3), column_id);
+
+        let mut harness = TestHarness::create(widget);
+        let rect = harness.get_widget(column_id).state().window_layout_rect();
+
+        // Each line scrolled selects the next item.
+        harness.mouse_move(rect.center());
+        harness.process_pointer_event(PointerEvent::MouseWheel(
+            ScrollDelta::Lines(Vec2::new(0., 2.)),
+            crate::event::PointerState::empty(),
+        ));
+        assert_eq!(
+            harness.pop_action(),
+            Some((Action::SpinnerColumnSelected(5), column_id))
+        );
+
+        // Dragging up by two rows scrolls two items further.
+        harness.mouse_button_press(MouseButton::Left);
+        harness.mouse_move(rect.center() - Vec2::new(0., 2. * ROW_HEIGHT));
+        harness.mouse_button_release(MouseButton::Left);
+        assert_eq!(
+            harness.pop_action(),
+            Some((Action::SpinnerColumnSelected(7), column_id))
+        );
+
+        // Clicking the item above the middle selects it.
+        harness.mouse_move(rect.center() - Vec2::new(0., ROW_HEIGHT));
+        harness.mouse_button_press(MouseButton::Left);
+        harness.mouse_button_release(MouseButton::Left);
+        assert_eq!(
+            harness.pop_action(),
+            Some((Action::SpinnerColumnSelected(6), column_id))
+        );
+    }
+}
@@ -370,6 +370,7 @@ impl Widget for Split {
                     if self.bar_hit_test(ctx.size(), state.position) {
                         ctx.set_handled();
                         ctx.set_active(true);
+                        ctx.capture_pointer();
                         // Save the delta between the mouse click position and the split point
                         self.click_offset = match self.split_axis {
                             Axis::Horizontal => state.position.x,
@@ -389,6 +390,7 @@ impl Widget for Split {
                     if ctx.is_active() {
                         ctx.set_handled();
                         ctx.set_active(false);
+                        ctx.release_pointer();
                         // Dependending on where the mouse cursor is when the button is released,
                         // the cursor might or might not need to be changed
                         self.is_bar_hover =
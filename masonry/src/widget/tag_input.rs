@@ -0,0 +1,540 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! A list of tags shown as removable chips, with a text box to add more.
+
+use accesskit::Role;
+use kurbo::{Line, Point, Rect};
+use smallvec::{smallvec, SmallVec};
+use tracing::{trace, trace_span, Span};
+use vello::Scene;
+use winit::event::MouseButton;
+use winit::keyboard::{Key, NamedKey};
+
+use crate::action::Action;
+use crate::paint_scene_helpers::{fill_color, stroke};
+use crate::widget::{ComboBox, Label, WidgetMut, WidgetPod, WidgetRef};
+use crate::{
+    theme, AccessCtx, AccessEvent, ArcStr, BoxConstraints, CursorIcon, EventCtx, Insets, LayoutCtx,
+    LifeCycle, LifeCycleCtx, PaintCtx, PointerEvent, Size, StatusChange, TextEvent, Widget,
+};
+
+const CHIP_INSETS: Insets = Insets::uniform_xy(8., 2.);
+const CLOSE_SIZE: f64 = 8.;
+const CLOSE_GAP: f64 = 6.;
+const SPACING: f64 = 4.;
+const MIN_INPUT_WIDTH: f64 = 60.;
+
+/// A list of tags, each shown as a chip which can be removed, followed by a text box
+/// for adding tags, which suggests matching options like a [`ComboBox`].
+///
+/// Typing a comma or pressing Enter adds the typed text, or the highlighted suggestion, as
+/// a tag, unless it's empty or already one of the tags. Backspace in the empty text box
+/// removes the last tag, and clicking the × of a chip removes its tag.
+///
+/// Each chip can be focused, by clicking it, with Tab, or with the left and right arrow
+/// keys from the empty text box and from the other chips. A focused chip is removed with
+/// Backspace or Delete.
+///
+/// When the user changes the tags, this submits [`Action::TagsChanged`] with the new tags.
+pub struct TagInput {
+    chips: Vec<WidgetPod<Chip>>,
+    suggestions: Vec<ArcStr>,
+    input: WidgetPod<ComboBox>,
+}
+
+/// One of the tags of a [`TagInput`].
+///
+/// The tag input handles input for its chips; they're separate widgets so that they can
+/// be focused, and exposed to accessibility, on their own.
+struct Chip {
+    label: WidgetPod<Label>,
+}
+
+impl TagInput {
+    /// Create a new tag input showing `tags`, which suggests the `suggestions` starting
+    /// with the typed text.
+    ///
+    /// Suggestions which are already tags aren't shown.
+    pub fn new(
+        tags: impl IntoIterator<Item = impl Into<ArcStr>>,
+        suggestions: impl IntoIterator<Item = impl Into<ArcStr>>,
+    ) -> Self {
+        let chips = tags
+            .into_iter()
+            .map(|tag| WidgetPod::new(Chip::new(tag.into())))
+            .collect();
+        let mut tag_input = Self {
+            chips,
+            suggestions: suggestions.into_iter().map(Into::into).collect(),
+            input: WidgetPod::new(ComboBox::new("", Vec::<ArcStr>::new())),
+        };
+        tag_input.input = WidgetPod::new(ComboBox::new("", tag_input.available_suggestions()));
+        tag_input
+    }
+
+    /// The current tags.
+    pub fn tags(&self) -> impl Iterator<Item = &ArcStr> {
+        self.chips.iter().map(|chip| chip.widget().text())
+    }
+
+    fn has_tag(&self, tag: &str) -> bool {
+        self.tags().any(|other| &**other == tag)
+    }
+
+    /// The suggestions which aren't already tags.
+    fn available_suggestions(&self) -> Vec<ArcStr> {
+        self.suggestions
+            .iter()
+            .filter(|suggestion| !self.has_tag(suggestion))
+            .cloned()
+            .collect()
+    }
+
+    /// Add `tag`, with surrounding whitespace trimmed, returning whether it was added.
+    fn push_tag(&mut self, tag: &str) -> bool {
+        let tag = tag.trim();
+        if tag.is_empty() || self.has_tag(tag) {
+            return false;
+        }
+        self.chips.push(WidgetPod::new(Chip::new(tag.into())));
+        true
+    }
+
+    fn tags_changed(&mut self, ctx: &mut EventCtx) {
+        let tags: Vec<String> = self.tags().map(|tag| tag.to_string()).collect();
+        trace!("TagInput {:?} tags changed to {:?}", ctx.widget_id(), tags);
+        let options = self.available_suggestions();
+        ctx.get_mut(&mut self.input).set_options(options);
+        ctx.submit_action(Action::TagsChanged(tags));
+        ctx.children_changed();
+        ctx.request_layout();
+    }
+
+    /// Add the highlighted suggestion, or else the typed text, as a tag, and clear the text.
+    fn commit_input(&mut self, ctx: &mut EventCtx) {
+        let input = self.input.widget();
+        let tag = match input.highlighted_suggestion() {
+            Some(suggestion) => suggestion.to_string(),
+            None => input.text().to_string(),
+        };
+        if self.push_tag(&tag) {
+            self.tags_changed(ctx);
+        }
+        ctx.get_mut(&mut self.input).set_text("");
+    }
+
+    /// Add the text typed before the last comma as tags, keeping the text after it.
+    fn commit_commas(&mut self, ctx: &mut EventCtx) {
+        let Some((committed, rest)) = self.input.widget().text().rsplit_once(',') else {
+            return;
+        };
+        let (committed, rest) = (committed.to_string(), rest.to_string());
+        let mut changed = false;
+        for tag in committed.split(',') {
+            changed |= self.push_tag(tag);
+        }
+        if changed {
+            self.tags_changed(ctx);
+        }
+        ctx.get_mut(&mut self.input).set_text(rest);
+    }
+
+    fn remove_tag(&mut self, ctx: &mut EventCtx, idx: usize) {
+        self.chips.remove(idx);
+        self.tags_changed(ctx);
+    }
+
+    /// Focus the chip at `idx`, or the text box if there's no such chip.
+    fn focus_chip_or_input(&self, ctx: &mut EventCtx, idx: usize) {
+        match self.chips.get(idx) {
+            Some(chip) => ctx.set_focus(chip.id()),
+            None => ctx.set_focus(self.input.widget().textbox_id()),
+        }
+    }
+
+    /// Handle a key press, returning whether it was handled.
+    fn handle_key(&mut self, ctx: &mut EventCtx, key: &Key) -> bool {
+        if self.input.has_focus() {
+            let last = self.chips.len().checked_sub(1);
+            let is_empty = self.input.widget().text().is_empty();
+            match (key, last) {
+                (Key::Named(NamedKey::Enter), _) => self.commit_input(ctx),
+                (Key::Named(NamedKey::Backspace), Some(last)) if is_empty => {
+                    self.remove_tag(ctx, last);
+                }
+                (Key::Named(NamedKey::ArrowLeft), Some(last)) if is_empty => {
+                    ctx.set_focus(self.chips[last].id());
+                }
+                _ => return false,
+            }
+            true
+        } else if let Some(idx) = self.chips.iter().position(|chip| chip.has_focus()) {
+            match key {
+                Key::Named(NamedKey::Backspace | NamedKey::Delete) => {
+                    self.remove_tag(ctx, idx);
+                    self.focus_chip_or_input(ctx, idx);
+                }
+                Key::Named(NamedKey::ArrowLeft) if idx > 0 => {
+                    ctx.set_focus(self.chips[idx - 1].id());
+                }
+                Key::Named(NamedKey::ArrowRight) => self.focus_chip_or_input(ctx, idx + 1),
+                _ => return false,
+            }
+            true
+        } else {
+            false
+        }
+    }
+}
+
+impl Chip {
+    fn new(text: ArcStr) -> Self {
+        Self {
+            label: WidgetPod::new(Label::new(text)),
+        }
+    }
+
+    fn text(&self) -> &ArcStr {
+        self.label.widget().text()
+    }
+
+    /// The area of the × which removes the chip, for a chip of the given size.
+    fn close_rect(size: Size) -> Rect {
+        let x1 = size.width - CHIP_INSETS.x1;
+        let y0 = (size.height - CLOSE_SIZE) / 2.;
+        Rect::new(x1 - CLOSE_SIZE, y0, x1, y0 + CLOSE_SIZE)
+    }
+}
+
+impl WidgetMut<'_, TagInput> {
+    /// Replace the tags.
+    ///
+    /// This doesn't submit an action.
+    pub fn set_tags(&mut self, tags: impl IntoIterator<Item = impl Into<ArcStr>>) {
+        self.widget.chips = tags
+            .into_iter()
+            .map(|tag| WidgetPod::new(Chip::new(tag.into())))
+            .collect();
+        self.ctx.children_changed();
+        self.update_options();
+    }
+
+    /// Replace the suggestions.
+    pub fn set_suggestions(&mut self, suggestions: impl IntoIterator<Item = impl Into<ArcStr>>) {
+        self.widget.suggestions = suggestions.into_iter().map(Into::into).collect();
+        self.update_options();
+    }
+
+    fn update_options(&mut self) {
+        let options = self.widget.available_suggestions();
+        self.ctx
+            .get_mut(&mut self.widget.input)
+            .set_options(options);
+    }
+}
+
+impl Widget for TagInput {
+    fn on_pointer_event(&mut self, ctx: &mut EventCtx, event: &PointerEvent) {
+        for chip in &mut self.chips {
+            chip.on_pointer_event(ctx, event);
+        }
+        self.input.on_pointer_event(ctx, event);
+        if ctx.is_handled() || ctx.is_disabled() {
+            return;
+        }
+
+        if let PointerEvent::PointerDown(MouseButton::Left, state) = event {
+            let pos =
+                Point::new(state.position.x, state.position.y) - ctx.window_origin().to_vec2();
+            let Some(idx) = self
+                .chips
+                .iter()
+                .position(|chip| chip.layout_rect().contains(pos))
+            else {
+                return;
+            };
+            let rect = self.chips[idx].layout_rect();
+            // The × is small, so presses just around it count too.
+            let close_rect = Chip::close_rect(rect.size()).inflate(CLOSE_GAP / 2., CLOSE_GAP / 2.);
+            if close_rect.contains(pos - rect.origin().to_vec2()) {
+                self.remove_tag(ctx, idx);
+            } else {
+                ctx.set_focus(self.chips[idx].id());
+            }
+            ctx.set_handled();
+        }
+    }
+
+    fn on_text_event(&mut self, ctx: &mut EventCtx, event: &TextEvent) {
+        if let TextEvent::KeyboardKey(key_event, _) = event {
+            if key_event.state.is_pressed()
+                && !ctx.is_disabled()
+                && self.handle_key(ctx, &key_event.logical_key)
+            {
+                ctx.set_handled();
+                return;
+            }
+        }
+        for chip in &mut self.chips {
+            chip.on_text_event(ctx, event);
+        }
+        self.input.on_text_event(ctx, event);
+
+        if self.input.widget().text().contains(',') {
+            self.commit_commas(ctx);
+        }
+    }
+
+    fn on_access_event(&mut self, ctx: &mut EventCtx, event: &AccessEvent) {
+        for chip in &mut self.chips {
+            chip.on_access_event(ctx, event);
+        }
+        self.input.on_access_event(ctx, event);
+    }
+
+    fn on_status_change(&mut self, _ctx: &mut LifeCycleCtx, _event: &StatusChange) {}
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle) {
+        for chip in &mut self.chips {
+            chip.lifecycle(ctx, event);
+        }
+        self.input.lifecycle(ctx, event);
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints) -> Size {
+        let width = if bc.is_width_bounded() {
+            bc.max().width
+        } else {
+            theme::WIDE_WIDGET_WIDTH * 2.
+        };
+
+        // The chips flow from left to right, wrapping onto new rows as needed, and the text
+        // box takes the rest of the last row, or a row of its own if too little is left.
+        let chip_bc = BoxConstraints::new(Size::ZERO, Size::new(width, f64::INFINITY));
+        let (mut x, mut y, mut row_height) = (0., 0., 0_f64);
+        for chip in &mut self.chips {
+            let chip_size = chip.layout(ctx, &chip_bc);
+            if x > 0. && x + chip_size.width > width {
+                (x, y, row_height) = (0., y + row_height + SPACING, 0.);
+            }
+            ctx.place_child(chip, Point::new(x, y));
+            x += chip_size.width + SPACING;
+            row_height = row_height.max(chip_size.height);
+        }
+        if x > 0. && width - x < MIN_INPUT_WIDTH {
+            (x, y, row_height) = (0., y + row_height + SPACING, 0.);
+        }
+        let input_width = width - x;
+        let input_bc = BoxConstraints::new(
+            Size::new(input_width, 0.),
+            Size::new(input_width, f64::INFINITY),
+        );
+        let input_size = self.input.layout(ctx, &input_bc);
+        ctx.place_child(&mut self.input, Point::new(x, y));
+        row_height = row_height.max(input_size.height);
+
+        let size = bc.constrain(Size::new(width, y + row_height));
+        trace!("Computed tag input size: {}", size);
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, scene: &mut Scene) {
+        for chip in &mut self.chips {
+            chip.paint(ctx, scene);
+        }
+        self.input.paint(ctx, scene);
+    }
+
+    fn accessibility_role(&self) -> Role {
+        Role::List
+    }
+
+    fn accessibility(&mut self, ctx: &mut AccessCtx) {
+        for chip in &mut self.chips {
+            chip.accessibility(ctx);
+        }
+        self.input.accessibility(ctx);
+    }
+
+    fn children(&self) -> SmallVec<[WidgetRef<'_, dyn Widget>; 16]> {
+        self.chips
+            .iter()
+            .map(|chip| chip.as_dyn())
+            .chain(std::iter::once(self.input.as_dyn()))
+            .collect()
+    }
+
+    fn make_trace_span(&self) -> Span {
+        trace_span!("TagInput")
+    }
+
+    fn get_debug_text(&self) -> Option<String> {
+        let tags: Vec<&str> = self.tags().map(|tag| &**tag).collect();
+        Some(tags.join(", "))
+    }
+}
+
+impl Widget for Chip {
+    fn on_pointer_event(&mut self, ctx: &mut EventCtx, event: &PointerEvent) {
+        self.label.on_pointer_event(ctx, event);
+    }
+
+    fn on_text_event(&mut self, ctx: &mut EventCtx, event: &TextEvent) {
+        self.label.on_text_event(ctx, event);
+    }
+
+    fn on_access_event(&mut self, ctx: &mut EventCtx, event: &AccessEvent) {
+        self.label.on_access_event(ctx, event);
+    }
+
+    fn on_status_change(&mut self, ctx: &mut LifeCycleCtx, event: &StatusChange) {
+        if let StatusChange::FocusChanged(_) = event {
+            ctx.request_paint();
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle) {
+        match event {
+            LifeCycle::WidgetAdded => {
+                ctx.set_cursor(&CursorIcon::Pointer);
+            }
+            LifeCycle::BuildFocusChain => {
+                ctx.register_for_focus();
+            }
+            _ => {}
+        }
+        self.label.lifecycle(ctx, event);
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints) -> Size {
+        let label_size = self.label.layout(ctx, &BoxConstraints::UNBOUNDED);
+        let size = bc.constrain(Size::new(
+            CHIP_INSETS.x0 + label_size.width + CLOSE_GAP + CLOSE_SIZE + CHIP_INSETS.x1,
+            (label_size.height + CHIP_INSETS.y_value()).max(theme::BORDERED_WIDGET_HEIGHT),
+        ));
+        let label_y = (size.height - label_size.height) / 2.;
+        ctx.place_child(&mut self.label, Point::new(CHIP_INSETS.x0, label_y));
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, scene: &mut Scene) {
+        let size = ctx.size();
+        let pill = size.to_rect().to_rounded_rect(size.height / 2.);
+        let theme = ctx.theme();
+        fill_color(scene, &pill, theme.field[0]);
+        if ctx.is_focused() {
+            stroke(
+                scene,
+                &pill
+                    .rect()
+                    .inset(-1.)
+                    .to_rounded_rect(size.height / 2. - 1.),
+                theme.primary,
+                2.,
+            );
+        }
+
+        let close = Self::close_rect(size);
+        let color = if ctx.is_disabled() {
+            theme.on_surface_disabled
+        } else {
+            theme.on_surface
+        };
+        stroke(
+            scene,
+            &Line::new((close.x0, close.y0), (close.x1, close.y1)),
+            color,
+            1.5,
+        );
+        stroke(
+            scene,
+            &Line::new((close.x0, close.y1), (close.x1, close.y0)),
+            color,
+            1.5,
+        );
+
+        self.label.paint(ctx, scene);
+    }
+
+    fn accessibility_role(&self) -> Role {
+        Role::ListItem
+    }
+
+    fn accessibility(&mut self, ctx: &mut AccessCtx) {
+        let node = ctx.current_node();
+        node.set_name(self.text().to_string());
+        node.add_action(accesskit::Action::Focus);
+        self.label.accessibility(ctx);
+    }
+
+    fn children(&self) -> SmallVec<[WidgetRef<'_, dyn Widget>; 16]> {
+        smallvec![self.label.as_dyn()]
+    }
+
+    fn make_trace_span(&self) -> Span {
+        trace_span!("Chip")
+    }
+
+    fn get_debug_text(&self) -> Option<String> {
+        Some(self.text().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{widget_ids, TestHarness};
+    use crate::widget::Flex;
+
+    fn tags(harness: &TestHarness, id: crate::WidgetId) -> Vec<String> {
+        harness
+            .get_widget(id)
+            .downcast::<TagInput>()
+            .unwrap()
+            .deref()
+            .tags()
+            .map(|tag| tag.to_string())
+            .collect()
+    }
+
+    /// The actions submitted by the tag input, rather than by its text box.
+    fn tag_input_actions(harness: &mut TestHarness, id: crate::WidgetId) -> Vec<Action> {
+        std::iter::from_fn(|| harness.pop_action())
+            .filter_map(|(action, action_id)| (action_id == id).then_some(action))
+            .collect()
+    }
+
+    #[test]
+    fn comma_adds_tags_and_close_button_removes_them() {
+        let [tag_input_id] = widget_ids();
+        let tag_input = TagInput::new(["rust"], ["Rust", "Python"]);
+        let widget = Flex::column().with_child_id(tag_input, tag_input_id);
+
+        let mut harness = TestHarness::create(widget);
+        let input = harness.get_widget(tag_input_id).children()[1];
+        let textbox_id = input.children()[0].id();
+        harness.mouse_click_on(textbox_id);
+        // Existing tags aren't added again, and the text after the last comma stays typed.
+        harness.keyboard_type_chars("python, rust,go");
+        assert_eq!(
+            tag_input_actions(&mut harness, tag_input_id),
+            [Action::TagsChanged(vec!["rust".into(), "python".into()])]
+        );
+        assert_eq!(tags(&harness, tag_input_id), ["rust", "python"]);
+        let input = harness.get_widget(tag_input_id).children()[2];
+        assert_eq!(input.downcast::<ComboBox>().unwrap().deref().text(), "go");
+
+        let first_chip = harness.get_widget(tag_input_id).children()[0];
+        let rect = first_chip.state().window_layout_rect();
+        let close = Chip::close_rect(rect.size()).center() + rect.origin().to_vec2();
+        harness.mouse_move(close);
+        harness.mouse_button_press(MouseButton::Left);
+        harness.mouse_button_release(MouseButton::Left);
+        assert_eq!(
+            tag_input_actions(&mut harness, tag_input_id),
+            [Action::TagsChanged(vec!["python".into()])]
+        );
+        assert_eq!(tags(&harness, tag_input_id), ["python"]);
+    }
+}
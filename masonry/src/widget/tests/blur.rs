@@ -0,0 +1,35 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::testing::{widget_ids, ModularWidget, TestHarness, TestWidgetExt as _};
+use crate::widget::Label;
+use crate::*;
+
+#[test]
+fn set_blur_from_event() {
+    let [blurred] = widget_ids();
+
+    let widget = ModularWidget::new(())
+        .pointer_event_fn(|_, ctx, event| {
+            if let PointerEvent::PointerDown(..) = event {
+                ctx.set_blur(4.0);
+            }
+        })
+        .layout_fn(|_, _, _| Size::new(10.0, 10.0))
+        .with_id(blurred);
+
+    let mut harness = TestHarness::create(widget);
+    assert_eq!(harness.get_widget(blurred).state().blur, 0.0);
+
+    harness.mouse_click_on(blurred);
+    assert_eq!(harness.get_widget(blurred).state().blur, 4.0);
+}
+
+#[test]
+fn blur_is_clamped_and_kept_when_boxed() {
+    let pod = WidgetPod::new(Label::new("hello")).with_blur(-1.0);
+    assert_eq!(pod.state.blur, 0.0);
+
+    let pod = WidgetPod::new(Label::new("hello")).with_blur(3.0).boxed();
+    assert_eq!(pod.state.blur, 3.0);
+}
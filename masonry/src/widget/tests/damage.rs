@@ -0,0 +1,37 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::testing::{widget_ids, ModularWidget, TestHarness};
+use crate::widget::{Flex, SizedBox};
+use crate::*;
+
+#[test]
+fn only_repainted_widgets_are_damaged() {
+    let [repainted, other] = widget_ids();
+    let widget = Flex::row()
+        .with_child_id(
+            ModularWidget::new(())
+                .pointer_event_fn(|_, ctx, event| {
+                    if let PointerEvent::PointerDown(..) = event {
+                        ctx.request_paint();
+                    }
+                })
+                .layout_fn(|_, _, _| Size::new(10.0, 10.0)),
+            repainted,
+        )
+        .with_child_id(SizedBox::empty().width(10.0).height(10.0), other);
+
+    let mut harness = TestHarness::create_with_size(widget, Size::new(100.0, 100.0));
+
+    // The first paint covers the whole window, then nothing changes.
+    assert_eq!(
+        harness.paint_damage(),
+        Some(Rect::new(0.0, 0.0, 100.0, 100.0))
+    );
+    assert_eq!(harness.paint_damage(), None);
+
+    harness.mouse_click_on(repainted);
+    let repainted_rect = harness.get_widget(repainted).state().window_layout_rect();
+    assert_eq!(harness.paint_damage(), Some(repainted_rect));
+    assert_eq!(harness.paint_damage(), None);
+}
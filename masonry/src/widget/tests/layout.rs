@@ -3,9 +3,11 @@
 
 //! Tests related to layout.
 
+use crate::event::WindowEvent;
 use crate::kurbo::{Insets, Size};
-use crate::testing::{widget_ids, ModularWidget, TestHarness, TestWidgetExt};
-use crate::widget::{Flex, SizedBox};
+use crate::testing::{widget_ids, ModularWidget, TestHarness, TestWidgetExt, HARNESS_DEFAULT_SIZE};
+use crate::widget::{Button, Flex, SizedBox};
+use crate::{Border, BorderWidths, BoxConstraints, BoxShadow, Color, ScaleCoefficient};
 
 #[test]
 fn layout_simple() {
@@ -68,6 +70,225 @@ fn layout_insets() {
     assert_eq!(parent_paint_rect.y1, BOX_WIDTH + 20.0);
 }
 
+#[test]
+fn layout_shadow_insets() {
+    const BOX_WIDTH: f64 = 50.;
+
+    let [box_id, button_id] = widget_ids();
+    let shadow = BoxShadow::new((4., 6.), 3., 1., Color::BLACK);
+
+    let widget = Flex::column()
+        .with_child_id(
+            SizedBox::empty()
+                .width(BOX_WIDTH)
+                .height(BOX_WIDTH)
+                .with_shadow(shadow),
+            box_id,
+        )
+        .with_child_id(Button::new("Hello").with_shadow(shadow), button_id);
+
+    let mut harness = TestHarness::create(widget);
+
+    // The shadow extends `blur + spread` past each side, and is moved by its offset.
+    let shadow_insets = Insets::new(0., 0., 8., 10.);
+    for id in [box_id, button_id] {
+        let state = harness.get_widget(id).state();
+        assert_eq!(state.paint_rect(), state.layout_rect() + shadow_insets);
+    }
+
+    let set_box_shadows = |harness: &mut TestHarness, shadows: Vec<BoxShadow>| {
+        harness.edit_root_widget(|mut root| {
+            let mut flex = root.downcast::<Flex>();
+            let mut sized_box = flex.child_mut(0).unwrap();
+            sized_box.downcast::<SizedBox>().set_shadows(shadows);
+        });
+    };
+
+    set_box_shadows(&mut harness, Vec::new());
+    let state = harness.get_widget(box_id).state();
+    assert_eq!(state.paint_rect(), state.layout_rect());
+
+    set_box_shadows(&mut harness, vec![shadow]);
+    let state = harness.get_widget(box_id).state();
+    assert_eq!(state.paint_rect(), state.layout_rect() + shadow_insets);
+}
+
 // TODO - insets + flex
 // TODO - viewport
 // TODO - insets + viewport
+
+#[test]
+fn layout_aspect_ratio() {
+    let [only_width, only_height, no_size, both] = widget_ids();
+
+    let widget = Flex::column()
+        .with_child_id(
+            SizedBox::empty().width(160.).aspect_ratio(16. / 9.),
+            only_width,
+        )
+        .with_child_id(SizedBox::empty().height(50.).aspect_ratio(2.), only_height)
+        .with_child_id(
+            SizedBox::empty().width(30.).height(30.).aspect_ratio(2.),
+            both,
+        );
+    let harness = TestHarness::create(widget);
+
+    // The missing dimension follows from the ratio.
+    assert_eq!(
+        harness.get_widget(only_width).state().layout_rect().size(),
+        Size::new(160., 90.)
+    );
+    assert_eq!(
+        harness.get_widget(only_height).state().layout_rect().size(),
+        Size::new(100., 50.)
+    );
+    // An explicit width and height take precedence over the ratio.
+    assert_eq!(
+        harness.get_widget(both).state().layout_rect().size(),
+        Size::new(30., 30.)
+    );
+
+    // Without explicit dimensions, the box fills as much of the available space as the ratio allows.
+    let harness = TestHarness::create_with_size(
+        Flex::column().with_child_id(SizedBox::empty().aspect_ratio(2.), no_size),
+        Size::new(400., 400.),
+    );
+    assert_eq!(
+        harness.get_widget(no_size).state().layout_rect().size(),
+        Size::new(400., 200.)
+    );
+}
+
+#[test]
+fn layout_aspect_ratio_zero_space() {
+    let [boxed] = widget_ids();
+
+    let harness = TestHarness::create_with_size(
+        SizedBox::empty().aspect_ratio(2.).with_id(boxed),
+        Size::ZERO,
+    );
+    assert_eq!(
+        harness.get_widget(boxed).state().layout_rect().size(),
+        Size::ZERO
+    );
+}
+
+#[test]
+fn layout_min_max_size() {
+    let [too_small, too_large, height_only] = widget_ids();
+
+    let sidebar = |content: SizedBox| {
+        SizedBox::new(content)
+            .with_min_size(Some(200.), None)
+            .with_max_size(Some(400.), None)
+    };
+    let widget = Flex::row()
+        .with_child_id(
+            sidebar(SizedBox::empty().width(100.).height(10.)),
+            too_small,
+        )
+        .with_child_id(
+            sidebar(SizedBox::empty().expand_width().height(10.)),
+            too_large,
+        )
+        .with_child_id(
+            SizedBox::empty()
+                .width(50.)
+                .height(300.)
+                .with_max_size(None, Some(100.)),
+            height_only,
+        );
+    let harness = TestHarness::create(widget);
+
+    assert_eq!(
+        harness.get_widget(too_small).state().layout_rect().size(),
+        Size::new(200., 10.)
+    );
+    assert_eq!(
+        harness.get_widget(too_large).state().layout_rect().size(),
+        Size::new(400., 10.)
+    );
+    // Only the constrained dimension is affected.
+    assert_eq!(
+        harness.get_widget(height_only).state().layout_rect().size(),
+        Size::new(50., 100.)
+    );
+}
+
+#[test]
+fn layout_padding() {
+    let [parent, child] = widget_ids();
+
+    let widget = SizedBox::new_with_id(SizedBox::empty().width(20.).height(10.), child)
+        .border(Color::BLACK, 1.)
+        .with_padding((1., 2., 3., 4.))
+        .with_id(parent);
+    let harness = TestHarness::create(Flex::column().with_child(widget));
+
+    let child_rect = harness.get_widget(child).state().layout_rect();
+    assert_eq!((child_rect.x0, child_rect.y0), (2., 3.));
+    assert_eq!(child_rect.size(), Size::new(20., 10.));
+    assert_eq!(
+        harness.get_widget(parent).state().layout_rect().size(),
+        Size::new(26., 18.)
+    );
+}
+
+#[test]
+fn layout_border_widths() {
+    let [parent, child] = widget_ids();
+
+    let border = Border::new(Color::BLACK, 0.).with_widths(BorderWidths {
+        top: 1.,
+        right: 2.,
+        bottom: 3.,
+        left: 4.,
+    });
+    let widget = SizedBox::new_with_id(SizedBox::empty().width(20.).height(10.), child)
+        .with_border(border)
+        .with_id(parent);
+    let harness = TestHarness::create(Flex::column().with_child(widget));
+
+    let child_rect = harness.get_widget(child).state().layout_rect();
+    assert_eq!((child_rect.x0, child_rect.y0), (4., 1.));
+    assert_eq!(
+        harness.get_widget(parent).state().layout_rect().size(),
+        Size::new(26., 14.)
+    );
+}
+
+#[test]
+fn layout_with_loose_constraints() {
+    let [id] = widget_ids();
+    let widget = SizedBox::empty().width(50.).height(20.).with_id(id);
+    let mut harness = TestHarness::create(widget);
+    // The window gives tight constraints.
+    assert_eq!(
+        harness.get_widget(id).state().layout_rect().size(),
+        HARNESS_DEFAULT_SIZE
+    );
+
+    let bc = BoxConstraints::new(Size::ZERO, Size::new(100., 100.));
+    assert_eq!(harness.layout_with_constraints(bc), Size::new(50., 20.));
+    assert_eq!(
+        harness.get_widget(id).state().layout_rect().size(),
+        Size::new(50., 20.)
+    );
+}
+
+#[test]
+fn layout_snaps_to_physical_pixels() {
+    let [box_id] = widget_ids();
+
+    let widget = Flex::column()
+        .with_spacer(10.1)
+        .with_child_id(SizedBox::empty().width(20.).height(20.), box_id)
+        .with_flex_spacer(1.0);
+
+    let mut harness = TestHarness::create(widget);
+    assert_eq!(harness.get_widget(box_id).state().layout_rect().y0, 10.0);
+
+    harness.process_window_event(WindowEvent::Rescale(ScaleCoefficient::new(1.25)));
+    // 10.1 logical pixels are 12.625 physical pixels, which is rounded to 13.
+    assert_eq!(harness.get_widget(box_id).state().layout_rect().y0, 10.4);
+}
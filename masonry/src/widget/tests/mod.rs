@@ -3,9 +3,15 @@
 
 // TODO - See https://github.com/PoignardAzur/masonry-rs/issues/58
 
+mod blur;
+mod damage;
 mod layout;
 mod lifecycle_basic;
 mod lifecycle_disable;
 mod lifecycle_focus;
+mod opacity;
+mod pointer_capture;
+mod repaint;
 mod safety_rails;
 mod status_change;
+mod window_title;
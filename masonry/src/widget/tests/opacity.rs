@@ -0,0 +1,41 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::testing::{widget_ids, ModularWidget, TestHarness, TestWidgetExt as _};
+use crate::widget::Label;
+use crate::*;
+
+fn opacity(harness: &TestHarness, id: WidgetId) -> f64 {
+    harness.get_widget(id).state().opacity
+}
+
+#[test]
+fn set_opacity_from_event() {
+    let [faded] = widget_ids();
+
+    let widget = ModularWidget::new(())
+        .pointer_event_fn(|_, ctx, event| {
+            if let PointerEvent::PointerDown(..) = event {
+                ctx.set_opacity(0.5);
+            }
+        })
+        .layout_fn(|_, _, _| Size::new(10.0, 10.0))
+        .with_id(faded);
+
+    let mut harness = TestHarness::create(widget);
+    assert_eq!(opacity(&harness, faded), 1.0);
+
+    harness.mouse_click_on(faded);
+    assert_eq!(opacity(&harness, faded), 0.5);
+}
+
+#[test]
+fn opacity_is_clamped_and_kept_when_boxed() {
+    let pod = WidgetPod::new(Label::new("hello")).with_opacity(2.0);
+    assert_eq!(pod.state.opacity, 1.0);
+
+    let pod = WidgetPod::new(Label::new("hello"))
+        .with_opacity(0.25)
+        .boxed();
+    assert_eq!(pod.state.opacity, 0.25);
+}
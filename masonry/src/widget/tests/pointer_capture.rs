@@ -0,0 +1,113 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+use assert_matches::assert_matches;
+use winit::event::MouseButton;
+
+use crate::testing::{widget_ids, ModularWidget, Record, Recording, TestHarness, TestWidgetExt};
+use crate::widget::{Flex, SizedBox};
+use crate::*;
+
+fn next_pointer_event(recording: &Recording) -> Option<PointerEvent> {
+    while let Some(event) = recording.next() {
+        if let Record::PE(event) = event {
+            return Some(event);
+        }
+    }
+    None
+}
+
+/// A widget which captures the pointer while the left button is pressed on it.
+fn capturing_widget() -> ModularWidget<()> {
+    ModularWidget::new(())
+        .pointer_event_fn(|_, ctx, event| match event {
+            PointerEvent::PointerDown(MouseButton::Left, _) => ctx.capture_pointer(),
+            PointerEvent::PointerUp(MouseButton::Left, _) => ctx.release_pointer(),
+            _ => (),
+        })
+        .layout_fn(|_, _, _| Size::new(10.0, 10.0))
+}
+
+#[test]
+fn captured_pointer_bypasses_hit_testing() {
+    let [capturing, other] = widget_ids();
+    let capturing_rec = Recording::default();
+    let other_rec = Recording::default();
+
+    let widget = Flex::column()
+        .with_child_id(capturing_widget().record(&capturing_rec), capturing)
+        .with_spacer(20.0)
+        .with_child_id(
+            SizedBox::empty()
+                .width(10.0)
+                .height(10.0)
+                .record(&other_rec),
+            other,
+        );
+
+    let mut harness = TestHarness::create(widget);
+    harness.mouse_move_to(capturing);
+    harness.mouse_button_press(MouseButton::Left);
+    capturing_rec.clear();
+
+    // While captured, events go to the capturing widget, even when the pointer is over
+    // another one, which doesn't become hot.
+    harness.mouse_move_to(other);
+    assert_matches!(
+        next_pointer_event(&capturing_rec),
+        Some(PointerEvent::PointerMove(_))
+    );
+    assert!(!harness.get_widget(capturing).state().is_hot);
+    assert!(!harness.get_widget(other).state().is_hot);
+    assert_matches!(next_pointer_event(&other_rec), None);
+
+    harness.mouse_button_release(MouseButton::Left);
+    assert_matches!(
+        next_pointer_event(&capturing_rec),
+        Some(PointerEvent::PointerUp(_, _))
+    );
+    assert_matches!(next_pointer_event(&other_rec), None);
+
+    // Once released, events are routed by position again.
+    harness.mouse_move_to(other);
+    assert!(harness.get_widget(other).state().is_hot);
+    assert_matches!(
+        next_pointer_event(&other_rec),
+        Some(PointerEvent::PointerMove(_))
+    );
+    assert_matches!(next_pointer_event(&capturing_rec), None);
+}
+
+#[test]
+fn removing_capturing_widget_releases_pointer() {
+    let [capturing, other] = widget_ids();
+    let other_rec = Recording::default();
+
+    let widget = Flex::column()
+        .with_child_id(capturing_widget(), capturing)
+        .with_spacer(20.0)
+        .with_child_id(
+            SizedBox::empty()
+                .width(10.0)
+                .height(10.0)
+                .record(&other_rec),
+            other,
+        );
+
+    let mut harness = TestHarness::create(widget);
+    harness.mouse_move_to(capturing);
+    harness.mouse_button_press(MouseButton::Left);
+
+    harness.edit_root_widget(|mut root| {
+        root.downcast::<Flex>().remove_child(0);
+    });
+    other_rec.clear();
+
+    // The capture went away with the widget, so events are routed by position again.
+    harness.mouse_move_to(other);
+    assert!(harness.get_widget(other).state().is_hot);
+    assert_matches!(
+        next_pointer_event(&other_rec),
+        Some(PointerEvent::PointerMove(_))
+    );
+}
@@ -0,0 +1,70 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+use winit::dpi::PhysicalSize;
+
+use crate::event::WindowEvent;
+use crate::paint_scene_helpers::fill_color;
+use crate::testing::{widget_ids, ModularWidget, Record, Recording, TestHarness, TestWidgetExt};
+use crate::widget::Flex;
+use crate::*;
+
+fn painted(recording: &Recording) -> bool {
+    recording
+        .drain()
+        .iter()
+        .any(|record| matches!(record, Record::Paint))
+}
+
+#[test]
+fn relayout_without_changes_reuses_painted_fragment() {
+    let [child] = widget_ids();
+    let recording = Recording::default();
+    // A widget which grows when clicked.
+    let widget = Flex::column().with_child_id(
+        ModularWidget::new(10.0)
+            .pointer_event_fn(|width, ctx, event| {
+                if let PointerEvent::PointerDown(..) = event {
+                    *width = 20.0;
+                    ctx.request_layout();
+                }
+            })
+            .layout_fn(|width, _, _| Size::new(*width, 10.0))
+            .record(&recording),
+        child,
+    );
+
+    let mut harness = TestHarness::create_with_size(widget, Size::new(100.0, 100.0));
+    let _ = harness.paint_damage();
+    assert!(painted(&recording));
+
+    // The child moves to stay centered, but keeps its size, so it isn't repainted.
+    harness.process_window_event(WindowEvent::Resize(PhysicalSize::new(200, 100)));
+    let _ = harness.paint_damage();
+    let records = recording.drain();
+    assert!(records
+        .iter()
+        .any(|record| matches!(record, Record::Layout(_))));
+    assert!(!records.iter().any(|record| matches!(record, Record::Paint)));
+
+    // Once its size changes, it is.
+    harness.mouse_click_on(child);
+    let _ = harness.paint_damage();
+    assert!(painted(&recording));
+}
+
+#[test]
+fn paint_scene_without_gpu() {
+    let n_paths = |fills: usize| {
+        let widget = ModularWidget::new(())
+            .layout_fn(|_, _, _| Size::new(10.0, 10.0))
+            .paint_fn(move |_, ctx, scene| {
+                for _ in 0..fills {
+                    fill_color(scene, &ctx.size().to_rect(), Color::WHITE);
+                }
+            });
+        let mut harness = TestHarness::create(Flex::column().with_child(widget));
+        harness.paint_scene().encoding().n_paths
+    };
+    assert_eq!(n_paths(2), n_paths(0) + 2);
+}
@@ -2,11 +2,14 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use assert_matches::assert_matches;
+use smallvec::smallvec;
 use winit::event::MouseButton;
 
-use crate::event::{PointerEvent, PointerState};
-use crate::testing::{widget_ids, Record, Recording, TestHarness, TestWidgetExt as _};
-use crate::widget::{Button, Flex, Label, SizedBox};
+use crate::event::{PointerEvent, PointerState, WindowEvent};
+use crate::testing::{
+    widget_ids, ModularWidget, Record, Recording, TestHarness, TestWidgetExt as _,
+};
+use crate::widget::{Button, CrossAxisAlignment, Flex, Label, SizedBox};
 use crate::*;
 
 fn is_hot(harness: &TestHarness, id: WidgetId) -> bool {
@@ -293,3 +296,134 @@ fn get_pointer_events_while_active() {
     harness.mouse_move_to(empty_2);
     assert_matches!(next_pointer_event(&button_rec), None);
 }
+
+#[test]
+fn cursor_follows_hot_widget() {
+    let [custom, empty] = widget_ids();
+
+    let image = CustomCursorImage::from_rgba(vec![0xff; 4 * 4 * 4], 4, 4, 1, 1).unwrap();
+    let custom_cursor = {
+        let image = image.clone();
+        ModularWidget::new(())
+            .pointer_event_fn(move |_, ctx, _| ctx.set_custom_cursor(&image))
+            .layout_fn(|_, _, _| Size::new(10.0, 10.0))
+    };
+
+    let widget = Flex::row()
+        .with_child_id(custom_cursor, custom)
+        .with_child_id(SizedBox::empty().width(10.0).height(10.0), empty);
+
+    let mut harness = TestHarness::create(widget);
+    assert_eq!(harness.cursor(), &Cursor::default());
+
+    harness.mouse_move_to(custom);
+    assert_eq!(harness.cursor(), &Cursor::Custom(image));
+
+    // The cursor is reset once the pointer leaves the widget.
+    harness.mouse_move_to(empty);
+    assert_eq!(harness.cursor(), &Cursor::default());
+}
+
+#[test]
+fn innermost_cursor_wins() {
+    let [button, empty] = widget_ids();
+
+    let row = Flex::row()
+        .with_child_id(Button::new("hello"), button)
+        .with_child_id(SizedBox::empty().width(10.0).height(10.0), empty);
+    let container = ModularWidget::new(WidgetPod::new(row))
+        .pointer_event_fn(|child, ctx, event| child.on_pointer_event(ctx, event))
+        .lifecycle_fn(|child, ctx, event| {
+            if let LifeCycle::WidgetAdded = event {
+                ctx.set_cursor(&CursorIcon::Crosshair);
+            }
+            child.lifecycle(ctx, event);
+        })
+        .layout_fn(|child, ctx, bc| {
+            let size = child.layout(ctx, bc);
+            ctx.place_child(child, Point::ZERO);
+            size
+        })
+        .children_fn(|child| smallvec![child.as_dyn()]);
+
+    let mut harness = TestHarness::create(container);
+
+    harness.mouse_move_to(button);
+    assert_eq!(harness.cursor(), &Cursor::Icon(CursorIcon::Pointer));
+
+    harness.mouse_move_to(empty);
+    assert_eq!(harness.cursor(), &Cursor::Icon(CursorIcon::Crosshair));
+}
+
+#[test]
+fn disabled_button_keeps_default_cursor() {
+    let [button, empty] = widget_ids();
+
+    let row = Flex::row()
+        .with_child_pod(
+            WidgetPod::new_with_id(Button::new("hello"), button)
+                .boxed()
+                .with_disabled(true),
+        )
+        .with_child_id(SizedBox::empty().width(10.0).height(10.0), empty);
+
+    let mut harness = TestHarness::create(row);
+
+    harness.mouse_move_to(button);
+    assert_eq!(harness.cursor(), &Cursor::default());
+
+    harness.edit_root_widget(|mut root| {
+        let mut flex = root.downcast::<Flex>();
+        flex.child_mut(0).unwrap().ctx.set_disabled(false);
+    });
+    harness.mouse_move_to(empty);
+    harness.mouse_move_to(button);
+    assert_eq!(harness.cursor(), &Cursor::Icon(CursorIcon::Pointer));
+}
+
+#[test]
+fn pointer_down_counts_clicks() {
+    let [button] = widget_ids();
+    let button_rec = Recording::default();
+    let widget = Flex::row().with_child_id(Button::new("hello").record(&button_rec), button);
+
+    let mut harness = TestHarness::create(widget);
+    let next_down_count = || loop {
+        match button_rec.next() {
+            Some(Record::PE(PointerEvent::PointerDown(_, state))) => return Some(state.count),
+            Some(_) => {}
+            None => return None,
+        }
+    };
+
+    harness.mouse_click_on(button);
+    harness.mouse_click_on(button);
+    assert_eq!(next_down_count(), Some(1));
+    assert_eq!(next_down_count(), Some(2));
+
+    // A click elsewhere ends the series.
+    harness.mouse_move((700.0, 700.0));
+    harness.mouse_button_press(MouseButton::Left);
+    harness.mouse_button_release(MouseButton::Left);
+    harness.mouse_click_on(button);
+    assert_eq!(next_down_count(), Some(1));
+}
+
+#[test]
+fn rescale_updates_hot_state() {
+    let [left, right] = widget_ids();
+    let widget = Flex::row()
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .with_child_id(SizedBox::empty().width(25.0).height(10.0), left)
+        .with_child_id(SizedBox::empty().width(25.0).height(10.0), right);
+
+    let mut harness = TestHarness::create_with_size(widget, Size::new(100.0, 100.0));
+    harness.mouse_move((30.0, 5.0));
+    assert!(!is_hot(&harness, left));
+    assert!(is_hot(&harness, right));
+
+    // The pointer stays at the same physical position, which is now over the left widget.
+    harness.process_window_event(WindowEvent::Rescale(ScaleCoefficient::new(2.0)));
+    assert!(is_hot(&harness, left));
+    assert!(!is_hot(&harness, right));
+}
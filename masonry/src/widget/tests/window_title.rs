@@ -0,0 +1,27 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::event::PointerEvent;
+use crate::testing::{widget_ids, ModularWidget, TestHarness, TestWidgetExt as _};
+
+#[test]
+fn set_window_title_from_event() {
+    let [id] = widget_ids();
+    let widget = ModularWidget::new(0)
+        .pointer_event_fn(|clicks, ctx, event| {
+            if let PointerEvent::PointerDown(_, _) = event {
+                *clicks += 1;
+                ctx.set_window_title("Untitled");
+                ctx.set_window_title(format!("Document {clicks}"));
+            }
+        })
+        .with_id(id);
+
+    let mut harness = TestHarness::create(widget);
+    assert_eq!(harness.pop_window_title(), None);
+
+    // Only the last title set during an event is applied.
+    harness.mouse_click_on(id);
+    assert_eq!(harness.pop_window_title(), Some("Document 1".into()));
+    assert_eq!(harness.pop_window_title(), None);
+}
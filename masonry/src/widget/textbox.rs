@@ -9,13 +9,11 @@ use parley::{
 };
 use smallvec::SmallVec;
 use tracing::trace;
-use vello::{
-    peniko::{BlendMode, Color},
-    Scene,
-};
+use vello::{peniko::BlendMode, Scene};
 
 use crate::{
     text2::{TextBrush, TextEditor, TextStorage, TextWithSelection},
+    theme::Theme,
     AccessCtx, AccessEvent, BoxConstraints, CursorIcon, EventCtx, LayoutCtx, LifeCycle,
     LifeCycleCtx, PaintCtx, PointerEvent, StatusChange, TextEvent, Widget,
 };
@@ -45,7 +43,8 @@ pub struct Textbox {
     editor: TextEditor<String>,
     line_break_mode: LineBreaking,
     show_disabled: bool,
-    brush: TextBrush,
+    /// The brush set on the textbox, or `None` to use the theme's text color.
+    brush: Option<TextBrush>,
 }
 
 impl Textbox {
@@ -54,7 +53,7 @@ impl Textbox {
             editor: TextEditor::new(initial_text.into(), crate::theme::TEXT_SIZE_NORMAL as f32),
             line_break_mode: LineBreaking::WordWrap,
             show_disabled: true,
-            brush: crate::theme::TEXT_COLOR.into(),
+            brush: None,
         }
     }
 
@@ -63,10 +62,25 @@ impl Textbox {
         self.editor.text()
     }
 
+    /// Show the text with the brush set on the textbox, or else the theme's text color,
+    /// or the theme's disabled text color while the textbox is disabled.
+    ///
+    /// This is called in `layout`, which runs again when the theme, the brush or the
+    /// disabled state changes.
+    fn update_displayed_brush(&mut self, theme: &Theme, disabled: bool) {
+        let brush = if disabled && self.show_disabled {
+            theme.on_surface_disabled.into()
+        } else {
+            self.brush
+                .clone()
+                .unwrap_or_else(|| theme.on_surface.into())
+        };
+        self.editor.set_brush(brush);
+    }
+
     #[doc(alias = "with_text_color")]
     pub fn with_text_brush(mut self, brush: impl Into<TextBrush>) -> Self {
-        self.brush = brush.into();
-        self.editor.set_brush(self.brush.clone());
+        self.brush = Some(brush.into());
         self
     }
 
@@ -129,12 +143,9 @@ impl WidgetMut<'_, Textbox> {
 
     #[doc(alias = "set_text_color")]
     pub fn set_text_brush(&mut self, brush: impl Into<TextBrush>) {
-        let brush = brush.into();
-        self.widget.brush = brush;
-        if !self.ctx.is_disabled() {
-            let brush = self.widget.brush.clone();
-            self.set_text_properties(|layout| layout.set_brush(brush));
-        }
+        self.widget.brush = Some(brush.into());
+        // The brush is applied in `layout`.
+        self.ctx.request_layout();
     }
     pub fn set_text_size(&mut self, size: f32) {
         self.set_text_properties(|layout| layout.set_text_size(size));
@@ -233,15 +244,8 @@ impl Widget for Textbox {
 
     fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle) {
         match event {
-            LifeCycle::DisabledChanged(disabled) => {
-                if self.show_disabled {
-                    if *disabled {
-                        self.editor.set_brush(crate::theme::DISABLED_TEXT_COLOR);
-                    } else {
-                        self.editor.set_brush(self.brush.clone());
-                    }
-                }
-                // TODO: Parley seems to require a relayout when colours change
+            LifeCycle::DisabledChanged(_) if self.show_disabled => {
+                // The brush is applied in `layout`.
                 ctx.request_layout();
             }
             LifeCycle::BuildFocusChain => {
@@ -265,6 +269,7 @@ impl Widget for Textbox {
         } else {
             None
         };
+        self.update_displayed_brush(ctx.theme(), ctx.is_disabled());
         self.editor.set_max_advance(max_advance);
         if self.editor.needs_rebuild() {
             self.editor.rebuild(ctx.font_ctx());
@@ -302,7 +307,7 @@ impl Widget for Textbox {
         scene.stroke(
             &Stroke::new(1.0),
             Affine::IDENTITY,
-            Color::WHITE,
+            ctx.theme().on_surface,
             None,
             &outline_rect,
         );
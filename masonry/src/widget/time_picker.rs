@@ -0,0 +1,333 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! A widget for picking a time of day.
+
+use accesskit::Role;
+use chrono::{NaiveTime, Timelike};
+use kurbo::Point;
+use smallvec::SmallVec;
+use tracing::{trace, trace_span, Span};
+use vello::Scene;
+
+use crate::action::Action;
+use crate::widget::{SpinnerColumn, WidgetMut, WidgetPod, WidgetRef};
+use crate::{
+    AccessCtx, AccessEvent, BoxConstraints, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx,
+    PointerEvent, Size, StatusChange, TextEvent, Widget,
+};
+
+/// The space between columns.
+const COLUMN_GAP: f64 = 4.;
+
+/// A widget for picking a time of day, with a [`SpinnerColumn`] each for the hours and
+/// minutes, and optionally the seconds and AM/PM.
+///
+/// [`Action::TimeChanged`] is submitted whenever the user changes the time.
+pub struct TimePicker {
+    time: NaiveTime,
+    twelve_hour: bool,
+    hours: WidgetPod<SpinnerColumn>,
+    minutes: WidgetPod<SpinnerColumn>,
+    seconds: Option<WidgetPod<SpinnerColumn>>,
+    period: Option<WidgetPod<SpinnerColumn>>,
+}
+
+impl TimePicker {
+    /// Create a new time picker showing `time`, or midnight if there is none.
+    ///
+    /// It has columns for the hours and minutes, with hours from 00 to 23.
+    pub fn new(time: Option<NaiveTime>) -> Self {
+        let time = time.unwrap_or(NaiveTime::MIN);
+        Self {
+            time,
+            twelve_hour: false,
+            hours: WidgetPod::new(hours_column(time, false)),
+            minutes: WidgetPod::new(sexagesimal_column("Minutes", time.minute())),
+            seconds: None,
+            period: None,
+        }
+    }
+
+    /// Builder-style method to show a column for the seconds.
+    ///
+    /// Without it, the seconds of the time are kept as they are.
+    pub fn with_seconds(mut self, seconds: bool) -> Self {
+        self.seconds = seconds.then(|| WidgetPod::new(seconds_column(self.time)));
+        self
+    }
+
+    /// Builder-style method to show hours from 12 to 11, with a column for AM and PM.
+    pub fn with_twelve_hour(mut self, twelve_hour: bool) -> Self {
+        self.twelve_hour = twelve_hour;
+        self.hours = WidgetPod::new(hours_column(self.time, twelve_hour));
+        self.period = twelve_hour.then(|| WidgetPod::new(period_column(self.time)));
+        self
+    }
+
+    /// The time shown.
+    pub fn time(&self) -> NaiveTime {
+        self.time
+    }
+
+    fn columns_mut(&mut self) -> impl Iterator<Item = &mut WidgetPod<SpinnerColumn>> {
+        [Some(&mut self.hours), Some(&mut self.minutes)]
+            .into_iter()
+            .chain([self.seconds.as_mut(), self.period.as_mut()])
+            .flatten()
+    }
+
+    /// The time selected in the columns.
+    fn selected_time(&self) -> NaiveTime {
+        let mut hour = self.hours.widget().selected() as u32;
+        if let Some(period) = &self.period {
+            hour += 12 * period.widget().selected() as u32;
+        }
+        let minute = self.minutes.widget().selected() as u32;
+        let second = match &self.seconds {
+            Some(seconds) => seconds.widget().selected() as u32,
+            None => self.time.second(),
+        };
+        NaiveTime::from_hms_opt(hour, minute, second).unwrap_or(self.time)
+    }
+
+    /// Submit the time selected in the columns, if it changed.
+    fn update_time(&mut self, ctx: &mut EventCtx) {
+        let time = self.selected_time();
+        if time != self.time {
+            trace!("TimePicker {:?} changed to {}", ctx.widget_id(), time);
+            self.time = time;
+            ctx.submit_action(Action::TimeChanged(time));
+            ctx.request_accessibility_update();
+        }
+    }
+}
+
+fn hours_column(time: NaiveTime, twelve_hour: bool) -> SpinnerColumn {
+    if twelve_hour {
+        let items = std::iter::once("12".to_string()).chain((1..12).map(|h| h.to_string()));
+        SpinnerColumn::new(items, time.hour() as usize % 12)
+    } else {
+        SpinnerColumn::new((0..24).map(|h| format!("{h:02}")), time.hour() as usize)
+    }
+    .with_name("Hours")
+    .without_actions()
+}
+
+/// A column of minutes or seconds, from 00 to 59.
+fn sexagesimal_column(name: &str, selected: u32) -> SpinnerColumn {
+    SpinnerColumn::new((0..60).map(|n| format!("{n:02}")), selected as usize)
+        .with_name(name)
+        .without_actions()
+}
+
+fn seconds_column(time: NaiveTime) -> SpinnerColumn {
+    sexagesimal_column("Seconds", time.second())
+}
+
+fn period_column(time: NaiveTime) -> SpinnerColumn {
+    SpinnerColumn::new(["AM", "PM"], time.hour() as usize / 12)
+        .with_name("AM/PM")
+        .without_actions()
+}
+
+impl WidgetMut<'_, TimePicker> {
+    /// Show `time`, or midnight if there is none, without submitting an action.
+    pub fn set_time(&mut self, time: Option<NaiveTime>) {
+        let time = time.unwrap_or(NaiveTime::MIN);
+        let picker = &mut *self.widget;
+        picker.time = time;
+        let hour = if picker.twelve_hour {
+            time.hour() % 12
+        } else {
+            time.hour()
+        };
+        self.ctx
+            .get_mut(&mut picker.hours)
+            .set_selected(hour as usize);
+        self.ctx
+            .get_mut(&mut picker.minutes)
+            .set_selected(time.minute() as usize);
+        if let Some(seconds) = &mut picker.seconds {
+            self.ctx
+                .get_mut(seconds)
+                .set_selected(time.second() as usize);
+        }
+        if let Some(period) = &mut picker.period {
+            self.ctx
+                .get_mut(period)
+                .set_selected(time.hour() as usize / 12);
+        }
+        self.ctx.request_accessibility_update();
+    }
+
+    /// Set whether to show a column for the seconds.
+    pub fn set_seconds(&mut self, seconds: bool) {
+        let picker = &mut *self.widget;
+        if picker.seconds.is_some() != seconds {
+            picker.seconds = seconds.then(|| WidgetPod::new(seconds_column(picker.time)));
+            self.ctx.children_changed();
+        }
+    }
+
+    /// Set whether to show hours from 12 to 11, with a column for AM and PM.
+    pub fn set_twelve_hour(&mut self, twelve_hour: bool) {
+        let picker = &mut *self.widget;
+        if picker.twelve_hour != twelve_hour {
+            picker.twelve_hour = twelve_hour;
+            picker.hours = WidgetPod::new(hours_column(picker.time, twelve_hour));
+            picker.period = twelve_hour.then(|| WidgetPod::new(period_column(picker.time)));
+            self.ctx.children_changed();
+        }
+    }
+}
+
+impl Widget for TimePicker {
+    fn on_pointer_event(&mut self, ctx: &mut EventCtx, event: &PointerEvent) {
+        for column in self.columns_mut() {
+            column.on_pointer_event(ctx, event);
+        }
+        self.update_time(ctx);
+    }
+
+    fn on_text_event(&mut self, ctx: &mut EventCtx, event: &TextEvent) {
+        for column in self.columns_mut() {
+            column.on_text_event(ctx, event);
+        }
+        self.update_time(ctx);
+    }
+
+    fn on_access_event(&mut self, ctx: &mut EventCtx, event: &AccessEvent) {
+        for column in self.columns_mut() {
+            column.on_access_event(ctx, event);
+        }
+        self.update_time(ctx);
+    }
+
+    fn on_status_change(&mut self, _ctx: &mut LifeCycleCtx, _event: &StatusChange) {}
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle) {
+        for column in self.columns_mut() {
+            column.lifecycle(ctx, event);
+        }
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints) -> Size {
+        let column_bc = bc.loosen();
+        let mut x = 0.;
+        let mut height: f64 = 0.;
+        for column in self.columns_mut() {
+            if x > 0. {
+                x += COLUMN_GAP;
+            }
+            let size = column.layout(ctx, &column_bc);
+            ctx.place_child(column, Point::new(x, 0.));
+            x += size.width;
+            height = height.max(size.height);
+        }
+        let size = bc.constrain(Size::new(x, height));
+        trace!("Computed size: {}", size);
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, scene: &mut Scene) {
+        for column in self.columns_mut() {
+            column.paint(ctx, scene);
+        }
+    }
+
+    fn accessibility_role(&self) -> Role {
+        Role::Group
+    }
+
+    fn accessibility(&mut self, ctx: &mut AccessCtx) {
+        let time = &self.time;
+        let mut value = if self.twelve_hour {
+            format!("{}:{:02}", (time.hour() + 11) % 12 + 1, time.minute())
+        } else {
+            format!("{:02}:{:02}", time.hour(), time.minute())
+        };
+        if self.seconds.is_some() {
+            value += &format!(":{:02}", time.second());
+        }
+        if self.twelve_hour {
+            value += if time.hour() < 12 { " AM" } else { " PM" };
+        }
+        ctx.current_node().set_value(value);
+        for column in self.columns_mut() {
+            column.accessibility(ctx);
+        }
+    }
+
+    fn children(&self) -> SmallVec<[WidgetRef<'_, dyn Widget>; 16]> {
+        [Some(&self.hours), Some(&self.minutes)]
+            .into_iter()
+            .chain([self.seconds.as_ref(), self.period.as_ref()])
+            .flatten()
+            .map(|column| column.as_dyn())
+            .collect()
+    }
+
+    fn make_trace_span(&self) -> Span {
+        trace_span!("TimePicker")
+    }
+
+    fn get_debug_text(&self) -> Option<String> {
+        Some(self.time.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use winit::event::MouseButton;
+
+    use super::*;
+    use crate::testing::{widget_ids, TestHarness};
+    use crate::widget::Flex;
+    use crate::{ScrollDelta, Vec2};
+
+    #[test]
+    fn columns_change_time() {
+        let [picker_id] = widget_ids();
+        let time = NaiveTime::from_hms_opt(11, 30, 15).unwrap();
+        let widget = Flex::column().with_child_id(
+            TimePicker::new(Some(time)).with_twelve_hour(true),
+            picker_id,
+        );
+
+        let mut harness = TestHarness::create(widget);
+        let picker = harness.get_widget(picker_id);
+        let [_, minutes, period] = picker.children()[..] else {
+            panic!("expected hour, minute and AM/PM columns");
+        };
+        let minutes_rect = minutes.state().window_layout_rect();
+        let period_rect = period.state().window_layout_rect();
+
+        // The columns don't submit actions of their own.
+        harness.mouse_move(minutes_rect.center());
+        harness.process_pointer_event(PointerEvent::MouseWheel(
+            ScrollDelta::Lines(Vec2::new(0., 1.)),
+            crate::event::PointerState::empty(),
+        ));
+        assert_eq!(
+            harness.pop_action(),
+            Some((
+                Action::TimeChanged(NaiveTime::from_hms_opt(11, 31, 15).unwrap()),
+                picker_id
+            ))
+        );
+        assert_eq!(harness.pop_action(), None);
+
+        // PM is below AM.
+        harness.mouse_move(period_rect.center() + Vec2::new(0., 28.));
+        harness.mouse_button_press(MouseButton::Left);
+        harness.mouse_button_release(MouseButton::Left);
+        assert_eq!(
+            harness.pop_action(),
+            Some((
+                Action::TimeChanged(NaiveTime::from_hms_opt(23, 31, 15).unwrap()),
+                picker_id
+            ))
+        );
+    }
+}
@@ -0,0 +1,319 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! A widget which shows a short text when its child is hovered.
+
+use std::time::Duration;
+
+use accesskit::Role;
+use smallvec::{smallvec, SmallVec};
+use tracing::{trace, trace_span, Span};
+use vello::Scene;
+
+use crate::paint_scene_helpers::{fill_color, stroke};
+use crate::widget::{Label, WidgetMut, WidgetPod, WidgetRef};
+use crate::{
+    theme, AccessCtx, AccessEvent, ArcStr, BoxConstraints, EventCtx, Insets, LayoutCtx, LifeCycle,
+    LifeCycleCtx, PaintCtx, Point, PointerEvent, Rect, Size, StatusChange, TextEvent, Vec2, Widget,
+};
+
+const LABEL_INSETS: Insets = Insets::uniform_xy(6., 2.);
+const BORDER_WIDTH: f64 = 1.;
+/// Where the tooltip is placed relative to the pointer, so that it isn't hidden by the cursor.
+const POINTER_OFFSET: Vec2 = Vec2::new(0., 20.);
+
+/// A container which shows a tooltip after the pointer has hovered its child for a while.
+///
+/// The tooltip is painted as a passive overlay next to the pointer, so it isn't clipped by
+/// this widget's ancestors and doesn't intercept clicks. It is hidden when the pointer leaves
+/// the child, and on any click or scroll.
+///
+/// The tooltip text is reported to accessibility as this widget's description.
+pub struct Tooltip<W: Widget> {
+    child: WidgetPod<W>,
+    label: WidgetPod<Label>,
+    delay: Duration,
+    /// How long the pointer has been hovering, while waiting to show the tooltip.
+    hover_time: Option<Duration>,
+    is_shown: bool,
+    /// The last pointer position, in local coordinates.
+    pointer_pos: Point,
+}
+
+impl<W: Widget> Tooltip<W> {
+    /// How long the pointer must hover the child before the tooltip is shown, unless
+    /// set with [`delay`](Self::delay).
+    pub const DEFAULT_DELAY: Duration = Duration::from_millis(500);
+
+    /// Create a new tooltip with the given text around `child`.
+    pub fn new(child: W, text: impl Into<ArcStr>) -> Self {
+        Self::from_child_pod(WidgetPod::new(child), text)
+    }
+
+    /// Create a new tooltip around a child which has already been wrapped in a [`WidgetPod`].
+    pub fn from_child_pod(child: WidgetPod<W>, text: impl Into<ArcStr>) -> Self {
+        Self {
+            child,
+            label: WidgetPod::new(Label::new(text)),
+            delay: Self::DEFAULT_DELAY,
+            hover_time: None,
+            is_shown: false,
+            pointer_pos: Point::ORIGIN,
+        }
+    }
+
+    /// Set how long the pointer must hover the child before the tooltip is shown.
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Returns `true` if the tooltip is currently shown.
+    pub fn is_shown(&self) -> bool {
+        self.is_shown
+    }
+
+    fn tooltip_rect(&self) -> Rect {
+        self.label.layout_rect() + LABEL_INSETS + Insets::uniform(BORDER_WIDTH)
+    }
+}
+
+impl<W: Widget> Tooltip<W> {
+    /// Hide the tooltip and stop waiting to show it, until the pointer hovers the child again.
+    fn hide(&mut self, ctx: &mut LifeCycleCtx) {
+        self.hover_time = None;
+        if self.is_shown {
+            trace!("Tooltip {:?} hidden", ctx.widget_id());
+            self.is_shown = false;
+            ctx.set_stashed(&mut self.label, true);
+            ctx.close_overlay();
+            ctx.request_layout();
+        }
+    }
+}
+
+impl<W: Widget> WidgetMut<'_, Tooltip<W>> {
+    pub fn child_mut(&mut self) -> WidgetMut<'_, W> {
+        self.ctx.get_mut(&mut self.widget.child)
+    }
+
+    /// Set the tooltip text.
+    pub fn set_text(&mut self, text: impl Into<ArcStr>) {
+        self.ctx.get_mut(&mut self.widget.label).set_text(text);
+        self.ctx.request_accessibility_update();
+    }
+
+    /// Set how long the pointer must hover the child before the tooltip is shown.
+    pub fn set_delay(&mut self, delay: Duration) {
+        self.widget.delay = delay;
+    }
+}
+
+impl<W: Widget> Widget for Tooltip<W> {
+    fn on_pointer_event(&mut self, ctx: &mut EventCtx, event: &PointerEvent) {
+        match event {
+            PointerEvent::PointerMove(state) if !self.is_shown => {
+                let pos = Point::new(state.position.x, state.position.y);
+                self.pointer_pos = pos - ctx.window_origin().to_vec2();
+            }
+            PointerEvent::PointerDown(_, _) | PointerEvent::MouseWheel(_, _) => {
+                // Don't show the tooltip again until the pointer re-enters.
+                self.hover_time = None;
+                if self.is_shown {
+                    trace!("Tooltip {:?} hidden", ctx.widget_id());
+                    self.is_shown = false;
+                    ctx.set_stashed(&mut self.label, true);
+                    ctx.close_overlay();
+                    ctx.request_layout();
+                }
+            }
+            _ => (),
+        }
+        self.child.on_pointer_event(ctx, event);
+        self.label.on_pointer_event(ctx, event);
+    }
+
+    fn on_text_event(&mut self, ctx: &mut EventCtx, event: &TextEvent) {
+        self.child.on_text_event(ctx, event);
+        self.label.on_text_event(ctx, event);
+    }
+
+    fn on_access_event(&mut self, ctx: &mut EventCtx, event: &AccessEvent) {
+        self.child.on_access_event(ctx, event);
+        self.label.on_access_event(ctx, event);
+    }
+
+    fn on_status_change(&mut self, ctx: &mut LifeCycleCtx, event: &StatusChange) {
+        match event {
+            StatusChange::HotChanged(true) => {
+                self.hover_time = Some(Duration::ZERO);
+                ctx.request_anim_frame();
+            }
+            StatusChange::HotChanged(false) => self.hide(ctx),
+            _ => (),
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle) {
+        self.child.lifecycle(ctx, event);
+        self.label.lifecycle(ctx, event);
+        match event {
+            LifeCycle::WidgetAdded => {
+                // The label is only laid out and painted while the tooltip is shown.
+                ctx.set_stashed(&mut self.label, true);
+            }
+            LifeCycle::AnimFrame(interval) => {
+                let Some(hover_time) = self.hover_time.as_mut() else {
+                    return;
+                };
+                *hover_time += Duration::from_nanos(*interval);
+                if *hover_time >= self.delay {
+                    trace!("Tooltip {:?} shown", ctx.widget_id());
+                    self.hover_time = None;
+                    self.is_shown = true;
+                    ctx.set_stashed(&mut self.label, false);
+                    ctx.request_layout();
+                } else {
+                    ctx.request_anim_frame();
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints) -> Size {
+        let size = self.child.layout(ctx, bc);
+        ctx.place_child(&mut self.child, Point::ORIGIN);
+
+        if self.is_shown {
+            self.label.layout(ctx, &BoxConstraints::UNBOUNDED);
+            let label_origin = self.pointer_pos
+                + POINTER_OFFSET
+                + Vec2::new(
+                    LABEL_INSETS.x0 + BORDER_WIDTH,
+                    LABEL_INSETS.y0 + BORDER_WIDTH,
+                );
+            ctx.place_child(&mut self.label, label_origin);
+            ctx.set_passive_overlay_rect(self.tooltip_rect());
+        }
+
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, scene: &mut Scene) {
+        self.child.paint(ctx, scene);
+
+        if !self.is_shown {
+            return;
+        }
+        let mut overlay = Scene::new();
+        let rounded_rect = self
+            .tooltip_rect()
+            .inset(-BORDER_WIDTH / 2.0)
+            .to_rounded_rect(theme::BUTTON_BORDER_RADIUS);
+        let theme = ctx.theme();
+        fill_color(&mut overlay, &rounded_rect, theme.field[1]);
+        stroke(
+            &mut overlay,
+            &rounded_rect,
+            theme.border_hover,
+            BORDER_WIDTH,
+        );
+        self.label.paint(ctx, &mut overlay);
+        ctx.paint_overlay(overlay);
+    }
+
+    fn accessibility_role(&self) -> Role {
+        Role::GenericContainer
+    }
+
+    fn accessibility(&mut self, ctx: &mut AccessCtx) {
+        let text = self.label.widget().text().to_string();
+        ctx.current_node().set_description(text);
+        self.child.accessibility(ctx);
+        self.label.accessibility(ctx);
+    }
+
+    fn children(&self) -> SmallVec<[WidgetRef<'_, dyn Widget>; 16]> {
+        smallvec![self.child.as_dyn(), self.label.as_dyn()]
+    }
+
+    fn make_trace_span(&self) -> Span {
+        trace_span!("Tooltip")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use winit::event::MouseButton;
+
+    use super::*;
+    use crate::event::WindowEvent;
+    use crate::testing::{widget_ids, TestHarness, TestWidgetExt};
+    use crate::widget::{Button, Flex, SizedBox};
+    use crate::Action;
+
+    fn is_shown(harness: &TestHarness, id: crate::WidgetId) -> bool {
+        harness
+            .get_widget(id)
+            .downcast::<Tooltip<SizedBox>>()
+            .unwrap()
+            .deref()
+            .is_shown()
+    }
+
+    #[test]
+    fn shown_after_hover() {
+        let [tooltip_id, button_id, other_id] = widget_ids();
+        let widget = Flex::column()
+            .with_child_id(
+                Tooltip::new(Button::new("Save").with_id(button_id), "Save the file")
+                    .delay(Duration::ZERO),
+                tooltip_id,
+            )
+            .with_spacer(50.)
+            .with_child_id(Button::new("Other"), other_id);
+
+        let mut harness = TestHarness::create(widget);
+        harness.mouse_move_to(button_id);
+        assert!(!is_shown(&harness, tooltip_id));
+
+        harness.process_window_event(WindowEvent::AnimFrame);
+        assert!(is_shown(&harness, tooltip_id));
+
+        // The tooltip doesn't intercept clicks, but is hidden by them.
+        harness.mouse_button_press(MouseButton::Left);
+        harness.mouse_button_release(MouseButton::Left);
+        assert!(!is_shown(&harness, tooltip_id));
+        assert_eq!(
+            harness.pop_action(),
+            Some((Action::ButtonPressed, button_id))
+        );
+
+        // It isn't shown again until the pointer leaves and comes back.
+        harness.process_window_event(WindowEvent::AnimFrame);
+        assert!(!is_shown(&harness, tooltip_id));
+        harness.mouse_move_to(other_id);
+        harness.mouse_move_to(button_id);
+        harness.process_window_event(WindowEvent::AnimFrame);
+        assert!(is_shown(&harness, tooltip_id));
+
+        // Moving away hides it.
+        harness.mouse_move_to(other_id);
+        assert!(!is_shown(&harness, tooltip_id));
+    }
+
+    #[test]
+    fn waits_for_delay() {
+        let [tooltip_id, button_id] = widget_ids();
+        let widget = Tooltip::new(Button::new("Save").with_id(button_id), "Save the file")
+            .delay(Duration::from_secs(60))
+            .with_id(tooltip_id);
+
+        let mut harness = TestHarness::create(widget);
+        harness.mouse_move_to(button_id);
+        harness.process_window_event(WindowEvent::AnimFrame);
+        harness.process_window_event(WindowEvent::AnimFrame);
+        assert!(!is_shown(&harness, tooltip_id));
+    }
+}
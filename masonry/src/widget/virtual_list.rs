@@ -0,0 +1,401 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! A scrolling list which only holds the widgets of its visible items.
+
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+use accesskit::Role;
+use kurbo::Affine;
+use smallvec::SmallVec;
+use tracing::{trace, trace_span, Span};
+use vello::peniko::BlendMode;
+use vello::Scene;
+
+use crate::widget::{Axis, ScrollBar, WidgetMut, WidgetRef};
+use crate::{
+    theme, AccessCtx, AccessEvent, Action, BoxConstraints, EventCtx, LayoutCtx, LifeCycle,
+    LifeCycleCtx, PaintCtx, Point, PointerEvent, Size, StatusChange, TextEvent, Widget, WidgetPod,
+};
+
+/// The number of items kept on each side of the visible ones, so that short scrolls
+/// don't show missing items while the list is being updated.
+const BUFFER_ITEMS: usize = 4;
+
+/// A vertical scrolling list of `count` items of the same height, which only holds
+/// widgets for the items currently on screen.
+///
+/// The list doesn't create its items itself. Whenever the range of items it needs changes
+/// (because it was scrolled, resized, or its item count changed), it submits an
+/// [`Action::VisibleRangeChanged`] with the indices of the visible items plus a small buffer.
+/// The owner is expected to respond by adding the widgets for that range with
+/// [`insert_child`](WidgetMut::insert_child) and removing the others with
+/// [`remove_child`](WidgetMut::remove_child).
+///
+/// The list should be given a bounded height, e.g. as a flex child.
+/// Otherwise it grows to the size of all its items.
+pub struct VirtualList {
+    count: usize,
+    item_height: f64,
+    /// The widgets of the items currently held, keyed by item index.
+    children: BTreeMap<usize, WidgetPod<Box<dyn Widget>>>,
+    viewport_pos: f64,
+    /// The range last reported through [`Action::VisibleRangeChanged`].
+    visible_range: Range<usize>,
+    scrollbar: WidgetPod<ScrollBar>,
+    scrollbar_visible: bool,
+}
+
+impl VirtualList {
+    /// Create a new list of `count` items, each `item_height` tall.
+    ///
+    /// # Panics
+    ///
+    /// If `item_height` isn't strictly positive.
+    pub fn new(count: usize, item_height: f64) -> Self {
+        assert!(item_height > 0.0, "item height must be positive");
+        Self {
+            count,
+            item_height,
+            children: BTreeMap::new(),
+            viewport_pos: 0.0,
+            visible_range: 0..0,
+            scrollbar: WidgetPod::new(ScrollBar::new(Axis::Vertical, 1.0, 1.0)),
+            scrollbar_visible: false,
+        }
+    }
+
+    /// The number of items in the list.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// The height of each item.
+    pub fn item_height(&self) -> f64 {
+        self.item_height
+    }
+
+    /// The vertical scroll offset.
+    pub fn viewport_pos(&self) -> f64 {
+        self.viewport_pos
+    }
+
+    /// The range of items the list needs widgets for, as of the last layout.
+    pub fn visible_range(&self) -> Range<usize> {
+        self.visible_range.clone()
+    }
+
+    /// Returns the widget of the item at `index`, if the list holds one.
+    pub fn child(&self, index: usize) -> Option<WidgetRef<'_, dyn Widget>> {
+        self.children.get(&index).map(|child| child.as_dyn())
+    }
+
+    fn content_height(&self) -> f64 {
+        self.count as f64 * self.item_height
+    }
+
+    /// Clamp `pos` to the scrollable range, and return whether the scroll offset changed.
+    fn set_viewport_pos_raw(&mut self, viewport_height: f64, pos: f64) -> bool {
+        let max_pos = (self.content_height() - viewport_height).max(0.0);
+        let pos = pos.clamp(0.0, max_pos);
+        if (pos - self.viewport_pos).abs() > 1e-6 {
+            self.viewport_pos = pos;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn scroll_progress(&self, viewport_height: f64) -> f64 {
+        let max_pos = self.content_height() - viewport_height;
+        if max_pos > 0.0 {
+            self.viewport_pos / max_pos
+        } else {
+            0.0
+        }
+    }
+}
+
+impl WidgetMut<'_, VirtualList> {
+    /// Set the number of items in the list.
+    ///
+    /// Items past the new count are not removed; the list reports the new visible range
+    /// during the next layout.
+    pub fn set_count(&mut self, count: usize) {
+        self.widget.count = count;
+        self.ctx.request_layout();
+    }
+
+    /// Set the height of each item.
+    ///
+    /// # Panics
+    ///
+    /// If `item_height` isn't strictly positive.
+    pub fn set_item_height(&mut self, item_height: f64) {
+        assert!(item_height > 0.0, "item height must be positive");
+        self.widget.item_height = item_height;
+        self.ctx.request_layout();
+    }
+
+    /// Set the vertical scroll offset.
+    ///
+    /// The offset is clamped so that the list doesn't scroll past its last item.
+    pub fn set_viewport_pos(&mut self, pos: f64) -> bool {
+        let viewport_height = self.ctx.widget_state.size.height;
+        let changed = self.widget.set_viewport_pos_raw(viewport_height, pos);
+        if changed {
+            let progress = self.widget.scroll_progress(viewport_height);
+            self.ctx
+                .get_mut(&mut self.widget.scrollbar)
+                .set_cursor_progress(progress);
+            self.ctx.request_layout();
+        }
+        changed
+    }
+
+    /// Add the widget of the item at `index`, replacing any widget the list held for it.
+    pub fn insert_child(&mut self, index: usize, child: WidgetPod<Box<dyn Widget>>) {
+        self.widget.children.insert(index, child);
+        self.ctx.children_changed();
+    }
+
+    /// Remove the widget of the item at `index`, if the list holds one.
+    pub fn remove_child(&mut self, index: usize) {
+        if self.widget.children.remove(&index).is_some() {
+            self.ctx.children_changed();
+        }
+    }
+
+    pub fn child_mut(&mut self, index: usize) -> Option<WidgetMut<'_, Box<dyn Widget>>> {
+        let child = self.widget.children.get_mut(&index)?;
+        Some(self.ctx.get_mut(child))
+    }
+}
+
+impl Widget for VirtualList {
+    fn on_pointer_event(&mut self, ctx: &mut EventCtx, event: &PointerEvent) {
+        let viewport_height = ctx.size().height;
+
+        for child in self.children.values_mut() {
+            child.on_pointer_event(ctx, event);
+        }
+
+        // Like a portal, the list only scrolls if no scrolling widget inside it did.
+        if let PointerEvent::MouseWheel(delta, _) = event {
+            let delta = delta.to_pixels(theme::SCROLL_LINE_HEIGHT);
+            if !ctx.is_handled()
+                && self.set_viewport_pos_raw(viewport_height, self.viewport_pos + delta.y)
+            {
+                let progress = self.scroll_progress(viewport_height);
+                ctx.get_mut(&mut self.scrollbar)
+                    .set_cursor_progress(progress);
+                ctx.request_layout();
+                ctx.set_handled();
+            }
+        }
+        self.scrollbar.on_pointer_event(ctx, event);
+
+        if self.scrollbar.widget().moved {
+            let progress = self.scrollbar.widget().cursor_progress;
+            self.scrollbar.widget_mut().moved = false;
+            let max_pos = (self.content_height() - viewport_height).max(0.0);
+            self.viewport_pos = progress * max_pos;
+            ctx.request_layout();
+        }
+    }
+
+    fn on_text_event(&mut self, ctx: &mut EventCtx, event: &TextEvent) {
+        for child in self.children.values_mut() {
+            child.on_text_event(ctx, event);
+        }
+        self.scrollbar.on_text_event(ctx, event);
+    }
+
+    fn on_access_event(&mut self, ctx: &mut EventCtx, event: &AccessEvent) {
+        for child in self.children.values_mut() {
+            child.on_access_event(ctx, event);
+        }
+        self.scrollbar.on_access_event(ctx, event);
+    }
+
+    fn on_status_change(&mut self, _ctx: &mut LifeCycleCtx, _event: &StatusChange) {}
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle) {
+        if let LifeCycle::WidgetAdded = event {
+            ctx.register_as_portal();
+        }
+        for child in self.children.values_mut() {
+            child.lifecycle(ctx, event);
+        }
+        self.scrollbar.lifecycle(ctx, event);
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints) -> Size {
+        let content_height = self.content_height();
+        let size = bc.constrain(Size::new(bc.max().width, content_height));
+
+        // The count or size may have changed since the last scroll.
+        self.set_viewport_pos_raw(size.height, self.viewport_pos);
+
+        let first_visible = (self.viewport_pos / self.item_height).floor() as usize;
+        let last_visible = ((self.viewport_pos + size.height) / self.item_height).ceil() as usize;
+        let visible_range = first_visible.saturating_sub(BUFFER_ITEMS).min(self.count)
+            ..(last_visible + BUFFER_ITEMS).min(self.count);
+        if visible_range != self.visible_range {
+            trace!(
+                "VirtualList {:?} visible range changed to {:?}",
+                ctx.widget_id(),
+                visible_range
+            );
+            self.visible_range = visible_range.clone();
+            ctx.submit_action(Action::VisibleRangeChanged(visible_range));
+        }
+
+        let item_bc = BoxConstraints::tight(Size::new(size.width, self.item_height));
+        for (index, child) in &mut self.children {
+            child.layout(ctx, &item_bc);
+            let origin = Point::new(0.0, *index as f64 * self.item_height - self.viewport_pos);
+            ctx.place_child(child, origin);
+        }
+
+        self.scrollbar_visible = size.height < content_height;
+        if self.scrollbar_visible {
+            self.scrollbar.widget_mut().portal_size = size.height;
+            self.scrollbar.widget_mut().content_size = content_height;
+            let scrollbar_size = self.scrollbar.layout(ctx, bc);
+            ctx.place_child(
+                &mut self.scrollbar,
+                Point::new(size.width - scrollbar_size.width, 0.0),
+            );
+        } else {
+            ctx.skip_child(&mut self.scrollbar);
+        }
+
+        size
+    }
+
+    fn intrinsic_size(&mut self, _ctx: &mut LayoutCtx, axis: Axis, _cross_size: f64) -> f64 {
+        match axis {
+            // Measuring doesn't change the visible range, which only follows real layouts.
+            Axis::Vertical => self.content_height(),
+            // The list takes the width it's given, whatever its items.
+            Axis::Horizontal => 0.0,
+        }
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, scene: &mut Scene) {
+        let clip_rect = ctx.size().to_rect();
+
+        scene.push_layer(BlendMode::default(), 1., Affine::IDENTITY, &clip_rect);
+        for child in self.children.values_mut() {
+            child.paint(ctx, scene);
+        }
+        scene.pop_layer();
+
+        if self.scrollbar_visible {
+            self.scrollbar.paint(ctx, scene);
+        } else {
+            ctx.skip_child(&mut self.scrollbar);
+        }
+    }
+
+    fn accessibility_role(&self) -> Role {
+        Role::List
+    }
+
+    fn accessibility(&mut self, ctx: &mut AccessCtx) {
+        ctx.current_node().set_clips_children();
+        ctx.current_node().set_scroll_y(self.viewport_pos);
+
+        for child in self.children.values_mut() {
+            child.accessibility(ctx);
+        }
+        self.scrollbar.accessibility(ctx);
+    }
+
+    fn children(&self) -> SmallVec<[WidgetRef<'_, dyn Widget>; 16]> {
+        self.children
+            .values()
+            .map(|child| child.as_dyn())
+            .chain(std::iter::once(self.scrollbar.as_dyn()))
+            .collect()
+    }
+
+    fn make_trace_span(&self) -> Span {
+        trace_span!("VirtualList")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TestHarness;
+    use crate::widget::Label;
+    use crate::Vec2;
+
+    fn fill_range(harness: &mut TestHarness, range: Range<usize>) {
+        harness.edit_root_widget(|mut root| {
+            let mut list = root.downcast::<VirtualList>();
+            let held: Vec<usize> = list.widget.children.keys().copied().collect();
+            for index in held {
+                if !range.contains(&index) {
+                    list.remove_child(index);
+                }
+            }
+            for index in range {
+                if list.widget.children.contains_key(&index) {
+                    continue;
+                }
+                let label = Label::new(format!("Item {index}"));
+                list.insert_child(index, WidgetPod::new(Box::new(label)));
+            }
+        });
+    }
+
+    #[test]
+    fn reports_visible_range() {
+        let widget = VirtualList::new(1000, 20.);
+
+        let mut harness = TestHarness::create_with_size(widget, Size::new(100., 100.));
+        let list_id = harness.root_widget().id();
+        // 5 items are visible, plus the buffer after them.
+        assert_eq!(
+            harness.pop_action(),
+            Some((Action::VisibleRangeChanged(0..9), list_id))
+        );
+        fill_range(&mut harness, 0..9);
+        assert_eq!(harness.pop_action(), None);
+        // The items, and the scrollbar.
+        assert_eq!(harness.root_widget().children().len(), 10);
+
+        harness.mouse_move((50., 50.));
+        harness.mouse_wheel(Vec2::new(0., 300.));
+        assert_eq!(
+            harness.pop_action(),
+            Some((Action::VisibleRangeChanged(11..24), list_id))
+        );
+        fill_range(&mut harness, 11..24);
+        assert_eq!(harness.root_widget().children().len(), 14);
+    }
+
+    #[test]
+    fn clamps_to_count() {
+        let widget = VirtualList::new(3, 20.);
+
+        let mut harness = TestHarness::create_with_size(widget, Size::new(100., 100.));
+        let list_id = harness.root_widget().id();
+        assert_eq!(
+            harness.pop_action(),
+            Some((Action::VisibleRangeChanged(0..3), list_id))
+        );
+
+        harness.edit_root_widget(|mut root| {
+            root.downcast::<VirtualList>().set_count(0);
+        });
+        assert_eq!(
+            harness.pop_action(),
+            Some((Action::VisibleRangeChanged(0..0), list_id))
+        );
+    }
+}
@@ -12,7 +12,7 @@ use tracing::{trace_span, Span};
 use vello::Scene;
 
 use crate::event::{AccessEvent, PointerEvent, StatusChange, TextEvent};
-use crate::widget::WidgetRef;
+use crate::widget::{Axis, WidgetRef};
 use crate::{
     AccessCtx, AsAny, BoxConstraints, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx,
     Point, Size,
@@ -106,6 +106,28 @@ pub trait Widget: AsAny {
     /// The layout strategy is strongly inspired by Flutter.
     fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints) -> Size;
 
+    /// Compute the natural size of the widget along `axis`, given `cross_size` on the
+    /// other axis.
+    ///
+    /// Containers call this through [`WidgetPod::intrinsic_size`](crate::WidgetPod::intrinsic_size)
+    /// when they need to know how much space a child wants before deciding on its
+    /// constraints, for instance to keep a flex child from shrinking below its content.
+    ///
+    /// The default implementation runs [`layout`](Self::layout) with constraints which are
+    /// unbounded along `axis` and at most `cross_size` on the other axis. The widget and its
+    /// children are only measured then: their state, including their paint insets and
+    /// baseline, is left as it was, and the actions and overlays they submit are dropped. Widgets which keep state of their own in `layout`,
+    /// and containers which can combine the intrinsic sizes of their children, should
+    /// override it.
+    ///
+    /// A result which isn't finite, e.g. from a widget which expands to fill the space it's
+    /// given, is treated as 0.
+    fn intrinsic_size(&mut self, ctx: &mut LayoutCtx, axis: Axis, cross_size: f64) -> f64 {
+        let max: Size = axis.pack(f64::INFINITY, cross_size).into();
+        let size = self.layout(ctx, &BoxConstraints::new(Size::ZERO, max));
+        axis.major(size)
+    }
+
     /// Paint the widget appearance.
     ///
     /// Container widgets can paint a background before recursing to their
@@ -114,6 +136,18 @@ pub trait Widget: AsAny {
     /// the render context, which is especially useful for scrolling.
     fn paint(&mut self, ctx: &mut PaintCtx, scene: &mut Scene);
 
+    /// Return `true` if `pos` is inside the shape of the widget, given its `size`.
+    ///
+    /// `pos` is in local coordinates. A widget is only hot, and only gets pointer
+    /// events, while the pointer is inside its shape (unless it's active).
+    ///
+    /// The default implementation uses the whole layout rect. Widgets which paint a
+    /// rounded or circular shape can override it, so that clicks just outside the
+    /// painted shape don't activate them.
+    fn hit_test(&self, pos: Point, size: Size) -> bool {
+        size.to_rect().contains(pos)
+    }
+
     fn accessibility_role(&self) -> Role;
 
     fn accessibility(&mut self, ctx: &mut AccessCtx);
@@ -151,7 +185,9 @@ pub trait Widget: AsAny {
 
     // --- Auto-generated implementations ---
 
-    /// Return which child, if any, has the given `pos` in its layout rect.
+    /// Return which child, if any, has the given `pos` in its shape.
+    ///
+    /// See [`hit_test`](Self::hit_test).
     ///
     /// The child return is a direct child, not eg a grand-child. The position is in
     /// relative coordinates. (Eg `(0,0)` is the top-left corner of `self`).
@@ -160,9 +196,12 @@ pub trait Widget: AsAny {
     /// efficiently.
     fn get_child_at_pos(&self, pos: Point) -> Option<WidgetRef<'_, dyn Widget>> {
         // layout_rect() is in parent coordinate space
-        self.children()
-            .into_iter()
-            .find(|child| child.state().layout_rect().contains(pos))
+        self.children().into_iter().find(|child| {
+            let rect = child.state().layout_rect();
+            child
+                .deref()
+                .hit_test(pos - rect.origin().to_vec2(), rect.size())
+        })
     }
 
     /// Get the (verbose) type name of the widget for debugging purposes.
@@ -204,6 +243,33 @@ pub trait Widget: AsAny {
     }
 }
 
+/// A widget which reacts to the force applied by the pointer.
+///
+/// Pressure is only reported by devices which support it, such as pen tablets,
+/// touchscreens and force-sensitive touchpads. Widgets implementing this trait
+/// should forward their pointer events to [`on_pressure_event`](Self::on_pressure_event)
+/// from [`Widget::on_pointer_event`].
+pub trait PressureSensitive: Widget {
+    /// Called when the pointer is pressed on or dragged across this widget
+    /// with a known force.
+    ///
+    /// `force` is normalized between `0.0` and `1.0`.
+    fn on_pressure_change(&mut self, ctx: &mut EventCtx, force: f64);
+
+    /// Call [`on_pressure_change`](Self::on_pressure_change) if the event carries
+    /// a pressure value.
+    fn on_pressure_event(&mut self, ctx: &mut EventCtx, event: &PointerEvent) {
+        match event {
+            PointerEvent::PointerDown(_, state) | PointerEvent::PointerMove(state) => {
+                if let Some(force) = state.force {
+                    self.on_pressure_change(ctx, force);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
 #[cfg(not(tarpaulin_include))]
 impl WidgetId {
     /// Allocate a new, unique `WidgetId`.
@@ -273,10 +339,18 @@ impl Widget for Box<dyn Widget> {
         self.deref_mut().layout(ctx, bc)
     }
 
+    fn intrinsic_size(&mut self, ctx: &mut LayoutCtx, axis: Axis, cross_size: f64) -> f64 {
+        self.deref_mut().intrinsic_size(ctx, axis, cross_size)
+    }
+
     fn paint(&mut self, ctx: &mut PaintCtx, scene: &mut Scene) {
         self.deref_mut().paint(ctx, scene);
     }
 
+    fn hit_test(&self, pos: Point, size: Size) -> bool {
+        self.deref().hit_test(pos, size)
+    }
+
     fn accessibility_role(&self) -> Role {
         self.deref().accessibility_role()
     }
@@ -13,7 +13,7 @@ use crate::Widget;
 /// This helps Masonry make sure that internal metadata is propagated after every widget
 /// change.
 ///
-/// You can create a `WidgetMut` from [`TestHarness`](crate::testing::TestHarness),
+/// You can create a `WidgetMut` from `TestHarness` (with the `test-util` feature),
 /// [`EventCtx`](crate::EventCtx), [`LifeCycleCtx`](crate::LifeCycleCtx) or from a parent
 /// `WidgetMut` with [`WidgetCtx`](crate::WidgetCtx).
 ///
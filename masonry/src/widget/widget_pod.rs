@@ -3,18 +3,19 @@
 
 use accesskit::{NodeBuilder, NodeId};
 use tracing::{info_span, trace, warn};
+use vello::peniko::Mix;
 use vello::Scene;
 use winit::dpi::LogicalPosition;
 
 use crate::event::{AccessEvent, PointerEvent, TextEvent};
-use crate::kurbo::{Affine, Insets, Point, Rect, Shape, Size};
+use crate::kurbo::{Affine, Insets, Point, Rect, Size, Vec2};
 use crate::paint_scene_helpers::stroke;
 use crate::render_root::RenderRootState;
 use crate::theme::get_debug_color;
-use crate::widget::{WidgetRef, WidgetState};
+use crate::widget::{Axis, WidgetRef, WidgetState};
 use crate::{
-    AccessCtx, BoxConstraints, EventCtx, InternalLifeCycle, LayoutCtx, LifeCycle, LifeCycleCtx,
-    PaintCtx, StatusChange, Widget, WidgetId,
+    AccessCtx, BoxConstraints, ClipShape, EventCtx, InternalLifeCycle, LayoutCtx, LifeCycle,
+    LifeCycleCtx, PaintCtx, ScaleCoefficient, StatusChange, Widget, WidgetId,
 };
 
 // TODO - rewrite links in doc
@@ -31,6 +32,9 @@ pub struct WidgetPod<W> {
     pub(crate) state: WidgetState,
     pub(crate) inner: W,
     pub(crate) fragment: Scene,
+    /// `fragment` blurred by `blurred_radius`, kept until either of them changes.
+    blurred_fragment: Scene,
+    blurred_radius: f64,
 }
 
 // ---
@@ -54,9 +58,50 @@ impl<W: Widget> WidgetPod<W> {
             state,
             inner,
             fragment: Scene::new(),
+            blurred_fragment: Scene::new(),
+            blurred_radius: 0.0,
         }
     }
 
+    /// Builder-style method for setting the opacity this widget and its descendants
+    /// are painted with.
+    ///
+    /// See [`EventCtx::set_opacity`](crate::EventCtx::set_opacity).
+    pub fn with_opacity(mut self, opacity: f64) -> Self {
+        self.state.opacity = opacity.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Builder-style method for clipping the painted content of this widget and its
+    /// descendants to `clip`.
+    ///
+    /// See [`EventCtx::set_clip`](crate::EventCtx::set_clip).
+    pub fn with_clip(mut self, clip: Option<ClipShape>) -> Self {
+        self.state.clip = clip;
+        self
+    }
+
+    /// Builder-style method for blurring the painted content of this widget and its
+    /// descendants by `radius` pixels.
+    ///
+    /// Vello has no blur filter, so this is a sampled approximation: the content is
+    /// painted 25 times at offsets within `radius`, whatever the radius, and the result
+    /// is reused until the content changes. Large radii show the individual copies.
+    ///
+    /// See [`EventCtx::set_blur`](crate::EventCtx::set_blur).
+    pub fn with_blur(mut self, radius: f64) -> Self {
+        self.state.blur = radius.max(0.0);
+        self
+    }
+
+    /// Builder-style method for creating this widget explicitly disabled.
+    ///
+    /// See [`EventCtx::set_disabled`](crate::EventCtx::set_disabled).
+    pub fn with_disabled(mut self, disabled: bool) -> Self {
+        self.state.is_explicitly_disabled_new = disabled;
+        self
+    }
+
     /// Read-only access to state. We don't mark the field as `pub` because
     /// we want to control mutation.
     pub(crate) fn state(&self) -> &WidgetState {
@@ -218,8 +263,9 @@ impl<W: Widget> WidgetPod<W> {
     // - A concept of "cursor moved to inner widget" (though I think that's not super useful outside the browser).
     // - Multiple pointers handling.
 
-    /// Determines if the provided `mouse_pos` is inside `rect`
-    /// and if so updates the hot state and sends `LifeCycle::HotChanged`.
+    /// Determines if the provided `mouse_pos` is inside the shape of the widget
+    /// (see [`Widget::hit_test`]) and if so updates the hot state and sends
+    /// `LifeCycle::HotChanged`.
     ///
     /// Return `true` if the hot state changed.
     ///
@@ -233,7 +279,10 @@ impl<W: Widget> WidgetPod<W> {
         let rect = inner_state.layout_rect() + inner_state.parent_window_origin.to_vec2();
         let had_hot = inner_state.is_hot;
         inner_state.is_hot = match mouse_pos {
-            Some(pos) => rect.winding(Point::new(pos.x, pos.y)) != 0,
+            Some(pos) => inner.hit_test(
+                Point::new(pos.x, pos.y) - rect.origin().to_vec2(),
+                rect.size(),
+            ),
             None => false,
         };
         // FIXME - don't send event, update flags instead
@@ -325,7 +374,12 @@ impl<W: Widget + 'static> WidgetPod<W> {
     /// Convert a `WidgetPod` containing a widget of a specific concrete type
     /// into a dynamically boxed widget.
     pub fn boxed(self) -> WidgetPod<Box<dyn Widget>> {
-        WidgetPod::new_with_id(Box::new(self.inner), self.state.id)
+        let inner: Box<dyn Widget> = Box::new(self.inner);
+        WidgetPod::new_with_id(inner, self.state.id)
+            .with_opacity(self.state.opacity)
+            .with_clip(self.state.clip)
+            .with_blur(self.state.blur)
+            .with_disabled(self.state.is_explicitly_disabled_new)
     }
 }
 
@@ -374,17 +428,38 @@ impl<W: Widget> WidgetPod<W> {
             PointerEvent::PointerEnter(pointer_state) => Some(pointer_state.position),
             PointerEvent::PointerLeave(_) => None,
             PointerEvent::MouseWheel(_, pointer_state) => Some(pointer_state.position),
-            PointerEvent::HoverFile(_, _) => None,
-            PointerEvent::DropFile(_, _) => None,
+            // Files are dropped on the widget under the last known pointer position.
+            PointerEvent::HoverFile(_, pointer_state) => Some(pointer_state.position),
+            PointerEvent::DropFile(_, pointer_state) => Some(pointer_state.position),
             PointerEvent::HoverFileCancel(_) => None,
         };
-        let hot_changed = WidgetPod::update_hot_state(
-            &mut self.inner,
-            &mut self.state,
-            parent_ctx.global_state,
-            hot_pos,
-        );
-        let call_inner = (had_active || self.state.is_hot || hot_changed) && !self.state.is_stashed;
+        let call_inner = if let Some(target) = parent_ctx.global_state.capture_target {
+            // A widget captured the pointer, so the event only goes to it and its ancestors.
+            // Their hot state still follows the pointer, but other widgets are left untouched.
+            let on_capture_path =
+                self.state.id == target || self.state.children.may_contain(&target);
+            if on_capture_path {
+                WidgetPod::update_hot_state(
+                    &mut self.inner,
+                    &mut self.state,
+                    parent_ctx.global_state,
+                    hot_pos,
+                );
+            }
+            on_capture_path
+        } else if let Some(target) = parent_ctx.global_state.overlay_target {
+            // The pointer is over an overlay, so the event goes to the overlay's owner,
+            // and the hot state of the widgets below is left untouched.
+            self.state.id == target || self.state.children.may_contain(&target)
+        } else {
+            let hot_changed = WidgetPod::update_hot_state(
+                &mut self.inner,
+                &mut self.state,
+                parent_ctx.global_state,
+                hot_pos,
+            );
+            (had_active || self.state.is_hot || hot_changed) && !self.state.is_stashed
+        };
         //let call_inner = true;
 
         if call_inner {
@@ -630,6 +705,23 @@ impl<W: Widget> WidgetPod<W> {
                         self.state.children_disabled_changed
                     }
                 }
+                InternalLifeCycle::RouteOverlayDismissed { owner } => {
+                    if *owner == self.state.id {
+                        self.call_widget_method_with_checks("lifecycle", |widget_pod| {
+                            let mut inner_ctx = LifeCycleCtx {
+                                global_state: parent_ctx.global_state,
+                                widget_state: &mut widget_pod.state,
+                            };
+
+                            widget_pod
+                                .inner
+                                .lifecycle(&mut inner_ctx, &LifeCycle::OverlayDismissed);
+                        });
+                        false
+                    } else {
+                        self.state.children.may_contain(owner)
+                    }
+                }
                 InternalLifeCycle::RouteFocusChanged { old, new } => {
                     let this_changed = if *old == Some(self.state.id) {
                         Some(false)
@@ -682,6 +774,7 @@ impl<W: Widget> WidgetPod<W> {
                 self.state.update_focus_chain = true;
                 self.state.needs_layout = true;
                 self.state.needs_paint = true;
+                self.state.is_damaged = true;
                 self.state.needs_window_origin = true;
                 self.state.needs_accessibility_update = true;
                 self.state.request_accessibility_update = true;
@@ -725,6 +818,9 @@ impl<W: Widget> WidgetPod<W> {
             }
             // This is called by children when going up the widget tree.
             LifeCycle::RequestPanToChild(_) => false,
+            // This is only meant for the owner of the overlay, which gets it through
+            // RouteOverlayDismissed.
+            LifeCycle::OverlayDismissed => false,
         };
 
         // widget_pod is a reborrow of `self`
@@ -843,10 +939,27 @@ impl<W: Widget> WidgetPod<W> {
         self.mark_as_visited();
         self.check_initialized("layout");
 
+        if parent_ctx.global_state.measuring {
+            // The widget is only measured, see `Widget::intrinsic_size`. It's laid out for
+            // real afterwards, so its state is left as it was.
+            let mut inner_ctx = LayoutCtx {
+                widget_state: &mut self.state,
+                global_state: parent_ctx.global_state,
+                mouse_pos: parent_ctx.mouse_pos,
+            };
+            let size = self.inner.layout(&mut inner_ctx, bc);
+            parent_ctx.global_state.debug_logger.pop_span();
+            return size;
+        }
+
+        // A widget which is only laid out again because its parent was keeps the fragment
+        // it painted last time, unless its size changes.
+        let requested_layout = self.state.needs_layout;
+        let prev_size = self.state.size;
+        let prev_paint_rect = self.state.local_paint_rect;
+
         self.state.needs_layout = false;
         self.state.is_expecting_place_child_call = true;
-        // TODO - Not everything that has been re-laid out needs to be repainted.
-        self.state.needs_paint = true;
         self.state.request_accessibility_update = true;
         self.state.needs_accessibility_update = true;
 
@@ -871,6 +984,14 @@ impl<W: Widget> WidgetPod<W> {
             .local_paint_rect
             .union(new_size.to_rect() + self.state.paint_insets);
 
+        if requested_layout
+            || new_size != prev_size
+            || self.state.local_paint_rect != prev_paint_rect
+        {
+            self.state.needs_paint = true;
+            self.state.is_damaged = true;
+        }
+
         if cfg!(debug_assertions) {
             for child in self.inner.children() {
                 if child.state().is_expecting_place_child_call {
@@ -885,8 +1006,10 @@ impl<W: Widget> WidgetPod<W> {
 
                 // TODO - This check might be redundant with the code updating local_paint_rect
                 let child_rect = child.state().paint_rect();
+                // Stashed children aren't laid out, so their paint rect may be stale.
                 if !rect_contains(&self.state.local_paint_rect, &child_rect)
                     && !self.state.is_portal
+                    && !child.state().is_stashed
                 {
                     debug_panic!(
                         "Error in '{}' #{}: paint_rect {:?} doesn't contain paint_rect {:?} of child widget '{}' #{}",
@@ -929,6 +1052,42 @@ impl<W: Widget> WidgetPod<W> {
         new_size
     }
 
+    /// Compute the natural size of a widget along `axis`, given `cross_size` on the other
+    /// axis.
+    ///
+    /// See [`Widget::intrinsic_size`]. This doesn't lay the widget out: containers must
+    /// still call [`layout`](Self::layout) and place the child afterwards.
+    pub fn intrinsic_size(
+        &mut self,
+        parent_ctx: &mut LayoutCtx,
+        axis: Axis,
+        cross_size: f64,
+    ) -> f64 {
+        let _span = self.inner.make_trace_span().entered();
+
+        if self.state.is_stashed {
+            return 0.;
+        }
+        self.check_initialized("intrinsic_size");
+
+        let mut inner_ctx = LayoutCtx {
+            widget_state: &mut self.state,
+            global_state: parent_ctx.global_state,
+            mouse_pos: parent_ctx.mouse_pos,
+        };
+        let was_measuring = std::mem::replace(&mut inner_ctx.global_state.measuring, true);
+        let size = self.inner.intrinsic_size(&mut inner_ctx, axis, cross_size);
+        inner_ctx.global_state.measuring = was_measuring;
+
+        parent_ctx.widget_state.merge_up(&mut self.state);
+        // Widgets which expand to fill the space they're given have no natural size.
+        if size.is_finite() {
+            size.max(0.)
+        } else {
+            0.
+        }
+    }
+
     fn log_layout_issues(&self, size: Size) {
         if size.width.is_infinite() {
             let name = self.inner.type_name();
@@ -968,8 +1127,20 @@ impl<W: Widget> WidgetPod<W> {
         self.mark_as_visited();
         self.check_initialized("paint");
 
+        if parent_ctx.global_state.repaint_all {
+            self.state.needs_paint = true;
+            self.state.is_damaged = true;
+        }
         if self.state.needs_paint {
             self.state.needs_paint = false;
+            if self.state.is_damaged {
+                self.state.is_damaged = false;
+                let blur = self.state.blur;
+                let paint_rect = (self.state.local_paint_rect
+                    + self.state.window_origin().to_vec2())
+                .inflate(blur, blur);
+                parent_ctx.global_state.add_damage(paint_rect);
+            }
             self.call_widget_method_with_checks("paint", |widget_pod| {
                 // TODO - Handle invalidation regions
                 let mut inner_ctx = PaintCtx {
@@ -984,6 +1155,8 @@ impl<W: Widget> WidgetPod<W> {
                 widget_pod
                     .inner
                     .paint(&mut inner_ctx, &mut widget_pod.fragment);
+                // The blurred copy is out of date.
+                widget_pod.blurred_radius = 0.0;
 
                 if parent_ctx.debug_paint {
                     widget_pod.debug_paint_layout_bounds(widget_pod.state.size);
@@ -992,7 +1165,44 @@ impl<W: Widget> WidgetPod<W> {
         }
 
         let transform = Affine::translate(self.state.origin.to_vec2());
-        scene.append(&self.fragment, Some(transform));
+        let opacity = self.state.opacity;
+        if opacity <= 0.0 {
+            return;
+        }
+        let blur = self.state.blur;
+        if opacity >= 1.0 && self.state.clip.is_none() && blur <= 0.0 {
+            scene.append(&self.fragment, Some(transform));
+            return;
+        }
+
+        // Layers which only clip are cheaper to draw than ones which blend.
+        let blend = if opacity >= 1.0 {
+            Mix::Clip
+        } else {
+            Mix::Normal
+        };
+        match &self.state.clip {
+            Some(clip) => {
+                let clip = clip.to_path(self.state.size);
+                scene.push_layer(blend, opacity as f32, transform, &clip);
+            }
+            None => {
+                let bounds = self.state.local_paint_rect.inflate(blur, blur);
+                scene.push_layer(blend, opacity as f32, transform, &bounds);
+            }
+        }
+        if blur > 0.0 {
+            if self.blurred_radius != blur {
+                let bounds = self.state.local_paint_rect.inflate(blur, blur);
+                self.blurred_fragment.reset();
+                append_blurred(&mut self.blurred_fragment, &self.fragment, bounds, blur);
+                self.blurred_radius = blur;
+            }
+            scene.append(&self.blurred_fragment, Some(transform));
+        } else {
+            scene.append(&self.fragment, Some(transform));
+        }
+        scene.pop_layer();
     }
 
     fn debug_paint_layout_bounds(&mut self, size: Size) {
@@ -1025,14 +1235,14 @@ impl<W: Widget> WidgetPod<W> {
             );
 
             self.call_widget_method_with_checks("accessibility", |widget_pod| {
-                let current_node = widget_pod.build_access_node(parent_ctx.scale_factor);
+                let current_node =
+                    widget_pod.build_access_node(parent_ctx.global_state.scale_factor);
                 let mut inner_ctx = AccessCtx {
                     global_state: parent_ctx.global_state,
                     widget_state: &mut widget_pod.state,
                     tree_update: parent_ctx.tree_update,
                     current_node,
                     rebuild_all: parent_ctx.rebuild_all,
-                    scale_factor: parent_ctx.scale_factor,
                 };
                 widget_pod.inner.accessibility(&mut inner_ctx);
 
@@ -1054,7 +1264,7 @@ impl<W: Widget> WidgetPod<W> {
         self.state.needs_accessibility_update = false;
     }
 
-    fn build_access_node(&mut self, scale_factor: f64) -> NodeBuilder {
+    fn build_access_node(&mut self, scale_factor: ScaleCoefficient) -> NodeBuilder {
         let mut node = NodeBuilder::new(self.inner.accessibility_role());
         node.set_bounds(to_accesskit_rect(
             self.state.window_layout_rect(),
@@ -1078,14 +1288,18 @@ impl<W: Widget> WidgetPod<W> {
         if self.state.is_stashed {
             node.set_hidden();
         }
+        // Focusable widgets can be focused by assistive technology, see `root_on_access_event`.
+        if self.state.focus_chain.contains(&self.state.id) {
+            node.add_action(accesskit::Action::Focus);
+        }
 
         node
     }
 }
 
-fn to_accesskit_rect(r: Rect, scale_factor: f64) -> accesskit::Rect {
-    let s = scale_factor;
-    accesskit::Rect::new(s * r.x0, s * r.y0, s * r.x1, s * r.y1)
+fn to_accesskit_rect(r: Rect, scale_factor: ScaleCoefficient) -> accesskit::Rect {
+    let r = scale_factor.to_physical_rect(r);
+    accesskit::Rect::new(r.x0, r.y0, r.x1, r.y1)
 }
 
 // TODO - negative rects?
@@ -1096,3 +1310,42 @@ fn rect_contains(larger: &Rect, smaller: &Rect) -> bool {
         && smaller.y0 >= larger.y0
         && smaller.y1 <= larger.y1
 }
+
+/// How many copies of the content are painted to approximate a blur, whatever its radius.
+const BLUR_SAMPLES: usize = 1 + BLUR_RINGS * BLUR_RING_SAMPLES;
+const BLUR_RINGS: usize = 3;
+const BLUR_RING_SAMPLES: usize = 8;
+
+/// The [`BLUR_SAMPLES`] offsets `fragment` is painted at to approximate a blur of
+/// `radius` pixels.
+///
+/// Samples are denser near the center, which gives a falloff close to a Gaussian.
+fn blur_offsets(radius: f64) -> impl Iterator<Item = Vec2> {
+    std::iter::once(Vec2::ZERO).chain((0..BLUR_RINGS).flat_map(move |ring| {
+        let r = radius * (ring + 1) as f64 / BLUR_RINGS as f64;
+        // Stagger the rings so that their samples don't line up.
+        let stagger = ring as f64 * 0.5;
+        (0..BLUR_RING_SAMPLES).map(move |i| {
+            let angle = (i as f64 + stagger) * std::f64::consts::TAU / BLUR_RING_SAMPLES as f64;
+            Vec2::from_angle(angle) * r
+        })
+    }))
+}
+
+/// Paint `fragment` blurred by `radius` pixels into `scene`, clipped to `bounds`.
+///
+/// Vello can't blur arbitrary content, so this paints `fragment` at [`BLUR_SAMPLES`]
+/// offsets instead. The k-th copy is painted over the previous ones with an opacity of
+/// `1/k`, so that opaque areas end up as the average of all copies.
+///
+/// The result is cached by the caller, so this only runs when the content or the radius
+/// change, not every time the parent is painted.
+fn append_blurred(scene: &mut Scene, fragment: &Scene, bounds: Rect, radius: f64) {
+    debug_assert_eq!(blur_offsets(radius).count(), BLUR_SAMPLES);
+    for (i, offset) in blur_offsets(radius).enumerate() {
+        let alpha = 1.0 / (i + 1) as f32;
+        scene.push_layer(Mix::Normal, alpha, Affine::IDENTITY, &bounds);
+        scene.append(fragment, Some(Affine::translate(offset)));
+        scene.pop_layer();
+    }
+}
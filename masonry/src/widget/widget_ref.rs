@@ -140,7 +140,11 @@ impl<'w> WidgetRef<'w, dyn Widget> {
         let mut pos = pos;
         let mut innermost_widget: WidgetRef<'w, dyn Widget> = *self;
 
-        if !self.state().layout_rect().contains(pos) {
+        let rect = self.state().layout_rect();
+        if !self
+            .deref()
+            .hit_test(pos - rect.origin().to_vec2(), rect.size())
+        {
             return None;
         }
 
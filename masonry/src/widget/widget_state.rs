@@ -9,7 +9,7 @@ use crate::bloom::Bloom;
 use crate::kurbo::{Insets, Point, Rect, Size};
 use crate::text_helpers::TextFieldRegistration;
 use crate::widget::CursorChange;
-use crate::{CursorIcon, WidgetId};
+use crate::{ClipShape, Cursor, WidgetId};
 
 // FIXME #5 - Make a note documenting this: the only way to get a &mut WidgetState should be in a pass.
 // A pass should reborrow the parent widget state (to avoid crossing wires) and call merge_up at
@@ -81,6 +81,9 @@ pub struct WidgetState {
 
     pub(crate) needs_layout: bool,
     pub(crate) needs_paint: bool,
+    /// This widget's own content changed, as opposed to `needs_paint` being set because
+    /// a descendant needs to be repainted. Used to track which areas of the window changed.
+    pub(crate) is_damaged: bool,
     pub(crate) needs_accessibility_update: bool,
 
     /// Because of some scrolling or something, `parent_window_origin` needs to be updated.
@@ -103,7 +106,7 @@ pub struct WidgetState {
     /// The result of merging up children cursors. This gets cleared when merging state up (unlike
     /// `cursor_change`, which is persistent).
     // TODO - Remove and handle in WidgetRoot instead
-    pub(crate) cursor: Option<CursorIcon>,
+    pub(crate) cursor: Option<Cursor>,
 
     pub(crate) text_registrations: Vec<TextFieldRegistration>,
 
@@ -129,6 +132,15 @@ pub struct WidgetState {
     // TODO - document
     pub(crate) is_stashed: bool,
 
+    /// The opacity this widget and its descendants are painted with, between 0 and 1.
+    pub(crate) opacity: f64,
+
+    /// The shape this widget and its descendants are clipped to when painted, if any.
+    pub(crate) clip: Option<ClipShape>,
+
+    /// How far the painted content of this widget and its descendants is blurred, in pixels.
+    pub(crate) blur: f64,
+
     // --- DEBUG INFO ---
     // Used in event/lifecycle/etc methods that are expected to be called recursively
     // on a widget's children, to make sure each child was visited.
@@ -163,6 +175,7 @@ impl WidgetState {
             is_hot: false,
             needs_layout: false,
             needs_paint: false,
+            is_damaged: false,
             needs_accessibility_update: false,
             needs_window_origin: false,
             is_active: false,
@@ -179,6 +192,9 @@ impl WidgetState {
             text_registrations: Vec::new(),
             update_focus_chain: false,
             is_stashed: false,
+            opacity: 1.0,
+            clip: None,
+            blur: 0.0,
             #[cfg(debug_assertions)]
             needs_visit: VisitBool(false.into()),
             #[cfg(debug_assertions)]
@@ -233,14 +249,14 @@ impl WidgetState {
         // things will be recalculated just from `cursor_change`.
         let child_cursor = child_state.take_cursor();
         if let CursorChange::Override(cursor) = &self.cursor_change {
-            self.cursor = Some(*cursor);
+            self.cursor = Some(cursor.clone());
         } else if child_state.has_active || child_state.is_hot {
             self.cursor = child_cursor;
         }
 
         if self.cursor.is_none() {
             if let CursorChange::Set(cursor) = &self.cursor_change {
-                self.cursor = Some(*cursor);
+                self.cursor = Some(cursor.clone());
             }
         }
     }
@@ -248,7 +264,7 @@ impl WidgetState {
     /// Because of how cursor merge logic works, we need to handle the leaf case;
     /// in that case there will be nothing in the `cursor` field (as `merge_up`
     /// is never called) and so we need to also check the `cursor_change` field.
-    fn take_cursor(&mut self) -> Option<CursorIcon> {
+    fn take_cursor(&mut self) -> Option<Cursor> {
         self.cursor.take().or_else(|| self.cursor_change.cursor())
     }
 
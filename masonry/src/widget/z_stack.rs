@@ -0,0 +1,301 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+//! A widget which stacks its children on top of each other.
+
+use accesskit::Role;
+use kurbo::{Insets, Rect, Size};
+use smallvec::SmallVec;
+use tracing::{trace, trace_span, Span};
+use vello::Scene;
+
+use crate::paint_scene_helpers::UnitPoint;
+use crate::widget::{WidgetMut, WidgetRef};
+use crate::{
+    AccessCtx, AccessEvent, BoxConstraints, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx,
+    PointerEvent, StatusChange, TextEvent, Widget, WidgetPod,
+};
+
+/// A container which stacks its children on top of each other, such as a badge over
+/// an avatar.
+///
+/// The stack is as large as its largest child, and each child is aligned within it,
+/// either with its own alignment or with the alignment of the stack.
+///
+/// Children are painted in order, so the last child is on top. Pointer events go
+/// to the children in the opposite order, so the topmost child under the pointer gets
+/// them first; if it handles an event, the children below it don't get that event.
+pub struct ZStack {
+    children: Vec<Child>,
+    alignment: UnitPoint,
+}
+
+struct Child {
+    widget: WidgetPod<Box<dyn Widget>>,
+    alignment: Option<UnitPoint>,
+}
+
+impl ZStack {
+    /// Create a new empty stack, which centers its children.
+    pub fn new() -> Self {
+        Self {
+            children: Vec::new(),
+            alignment: UnitPoint::CENTER,
+        }
+    }
+
+    /// Builder-style method to set how children without their own alignment are aligned.
+    pub fn with_alignment(mut self, alignment: UnitPoint) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    /// Builder-style method to add a child on top of the others.
+    pub fn with_child(self, child: impl Widget) -> Self {
+        self.with_child_pod(WidgetPod::new(Box::new(child)))
+    }
+
+    /// Builder-style method to add a child on top of the others, with its own alignment.
+    pub fn with_aligned_child(self, child: impl Widget, alignment: UnitPoint) -> Self {
+        self.with_aligned_child_pod(WidgetPod::new(Box::new(child)), alignment)
+    }
+
+    /// Builder-style method to add a child which has already been wrapped in a
+    /// [`WidgetPod`] on top of the others.
+    pub fn with_child_pod(mut self, widget: WidgetPod<Box<dyn Widget>>) -> Self {
+        self.children.push(Child {
+            widget,
+            alignment: None,
+        });
+        self
+    }
+
+    /// Builder-style method to add a child which has already been wrapped in a
+    /// [`WidgetPod`] on top of the others, with its own alignment.
+    pub fn with_aligned_child_pod(
+        mut self,
+        widget: WidgetPod<Box<dyn Widget>>,
+        alignment: UnitPoint,
+    ) -> Self {
+        self.children.push(Child {
+            widget,
+            alignment: Some(alignment),
+        });
+        self
+    }
+
+    /// The number of children.
+    pub fn len(&self) -> usize {
+        self.children.len()
+    }
+
+    /// Returns `true` if the stack has no children.
+    pub fn is_empty(&self) -> bool {
+        self.children.is_empty()
+    }
+}
+
+impl Default for ZStack {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WidgetMut<'_, ZStack> {
+    /// Set how children without their own alignment are aligned.
+    pub fn set_alignment(&mut self, alignment: UnitPoint) {
+        self.widget.alignment = alignment;
+        self.ctx.request_layout();
+    }
+
+    /// Set the alignment of the child at `idx`, or `None` to use the alignment of the stack.
+    pub fn set_child_alignment(&mut self, idx: usize, alignment: Option<UnitPoint>) {
+        let child = &mut self.widget.children[idx];
+        if child.alignment != alignment {
+            child.alignment = alignment;
+            self.ctx.request_layout();
+        }
+    }
+
+    /// Add a child on top of the others.
+    pub fn add_child(&mut self, child: impl Widget) {
+        let idx = self.widget.children.len();
+        self.insert_child_pod(idx, WidgetPod::new(Box::new(child)));
+    }
+
+    /// Insert a child at `idx`, so that it's above the children before it.
+    pub fn insert_child_pod(&mut self, idx: usize, widget: WidgetPod<Box<dyn Widget>>) {
+        self.widget.children.insert(
+            idx,
+            Child {
+                widget,
+                alignment: None,
+            },
+        );
+        self.ctx.children_changed();
+    }
+
+    pub fn remove_child(&mut self, idx: usize) {
+        self.widget.children.remove(idx);
+        self.ctx.children_changed();
+    }
+
+    // FIXME - Remove Box
+    pub fn child_mut(&mut self, idx: usize) -> Option<WidgetMut<'_, Box<dyn Widget>>> {
+        let child = &mut self.widget.children.get_mut(idx)?.widget;
+        Some(self.ctx.get_mut(child))
+    }
+
+    pub fn clear(&mut self) {
+        self.widget.children.clear();
+        self.ctx.children_changed();
+    }
+}
+
+impl Widget for ZStack {
+    fn on_pointer_event(&mut self, ctx: &mut EventCtx, event: &PointerEvent) {
+        // The topmost child gets the event first.
+        for child in self.children.iter_mut().rev() {
+            child.widget.on_pointer_event(ctx, event);
+        }
+    }
+
+    fn on_text_event(&mut self, ctx: &mut EventCtx, event: &TextEvent) {
+        for child in self.children.iter_mut().rev() {
+            child.widget.on_text_event(ctx, event);
+        }
+    }
+
+    fn on_access_event(&mut self, ctx: &mut EventCtx, event: &AccessEvent) {
+        for child in self.children.iter_mut().rev() {
+            child.widget.on_access_event(ctx, event);
+        }
+    }
+
+    fn on_status_change(&mut self, _ctx: &mut LifeCycleCtx, _event: &StatusChange) {}
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle) {
+        for child in &mut self.children {
+            child.widget.lifecycle(ctx, event);
+        }
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints) -> Size {
+        let loosened_bc = bc.loosen();
+        let mut max_size = Size::ZERO;
+        let mut child_sizes = Vec::with_capacity(self.children.len());
+        for child in &mut self.children {
+            let child_size = child.widget.layout(ctx, &loosened_bc);
+            max_size.width = max_size.width.max(child_size.width);
+            max_size.height = max_size.height.max(child_size.height);
+            child_sizes.push(child_size);
+        }
+        let size = bc.constrain(max_size);
+
+        let mut paint_insets = Insets::ZERO;
+        for (child, child_size) in self.children.iter_mut().zip(child_sizes) {
+            let extra_width = (size.width - child_size.width).max(0.);
+            let extra_height = (size.height - child_size.height).max(0.);
+            let origin = child
+                .alignment
+                .unwrap_or(self.alignment)
+                .resolve(Rect::new(0., 0., extra_width, extra_height))
+                .expand();
+            ctx.place_child(&mut child.widget, origin);
+
+            let child_insets = child.widget.compute_parent_paint_insets(size);
+            paint_insets.x0 = paint_insets.x0.max(child_insets.x0);
+            paint_insets.y0 = paint_insets.y0.max(child_insets.y0);
+            paint_insets.x1 = paint_insets.x1.max(child_insets.x1);
+            paint_insets.y1 = paint_insets.y1.max(child_insets.y1);
+        }
+        ctx.set_paint_insets(paint_insets);
+
+        trace!("Computed layout: size={}", size);
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, scene: &mut Scene) {
+        for child in &mut self.children {
+            child.widget.paint(ctx, scene);
+        }
+    }
+
+    fn accessibility_role(&self) -> Role {
+        Role::GenericContainer
+    }
+
+    fn accessibility(&mut self, ctx: &mut AccessCtx) {
+        for child in &mut self.children {
+            child.widget.accessibility(ctx);
+        }
+    }
+
+    fn children(&self) -> SmallVec<[WidgetRef<'_, dyn Widget>; 16]> {
+        self.children
+            .iter()
+            .map(|child| child.widget.as_dyn())
+            .collect()
+    }
+
+    fn make_trace_span(&self) -> Span {
+        trace_span!("ZStack")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    use winit::event::MouseButton;
+
+    use super::*;
+    use crate::testing::{widget_ids, ModularWidget, TestHarness, TestWidgetExt};
+    use crate::widget::Flex;
+
+    /// A widget of the given size, which counts and handles the pointer presses it gets.
+    fn pressable(size: Size, presses: Rc<Cell<u32>>) -> ModularWidget<Rc<Cell<u32>>> {
+        ModularWidget::new(presses)
+            .layout_fn(move |_, _, bc| bc.constrain(size))
+            .pointer_event_fn(|presses, ctx, event| {
+                if let PointerEvent::PointerDown(_, _) = event {
+                    presses.set(presses.get() + 1);
+                    ctx.set_handled();
+                }
+            })
+    }
+
+    #[test]
+    fn topmost_child_gets_events_first() {
+        let [back_id, front_id] = widget_ids();
+        let back_presses = Rc::new(Cell::new(0));
+        let front_presses = Rc::new(Cell::new(0));
+        let z_stack = ZStack::new()
+            .with_child(pressable(Size::new(100., 100.), back_presses.clone()).with_id(back_id))
+            .with_aligned_child(
+                pressable(Size::new(40., 40.), front_presses.clone()).with_id(front_id),
+                UnitPoint::TOP_RIGHT,
+            );
+        let widget = Flex::column().with_child(z_stack);
+
+        let mut harness = TestHarness::create(widget);
+
+        let back_rect = harness.get_widget(back_id).state().window_layout_rect();
+        let front_rect = harness.get_widget(front_id).state().window_layout_rect();
+        assert_eq!(back_rect.size(), Size::new(100., 100.));
+        assert_eq!(front_rect.origin(), back_rect.origin() + (60., 0.));
+
+        // Where the children overlap, only the topmost one gets the press.
+        harness.mouse_move(front_rect.center());
+        harness.mouse_button_press(MouseButton::Left);
+        harness.mouse_button_release(MouseButton::Left);
+        assert_eq!((back_presses.get(), front_presses.get()), (0, 1));
+
+        // Elsewhere, the child below gets it.
+        harness.mouse_move(back_rect.origin() + (10., 90.));
+        harness.mouse_button_press(MouseButton::Left);
+        harness.mouse_button_release(MouseButton::Left);
+        assert_eq!((back_presses.get(), front_presses.get()), (1, 1));
+    }
+}
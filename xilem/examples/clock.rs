@@ -0,0 +1,58 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::{Duration, SystemTime};
+
+use masonry::widget::{CrossAxisAlignment, MainAxisAlignment};
+use xilem::{
+    view::{button, flex, label, timer},
+    EventLoop, MasonryView, RunError, Xilem,
+};
+
+struct Clock {
+    now: SystemTime,
+    running: bool,
+}
+
+/// Format the time of day of `time` as `HH:MM:SS`, in UTC.
+fn time_of_day(time: SystemTime) -> String {
+    let secs = time
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+        % (24 * 60 * 60);
+    format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+fn app_logic(clock: &mut Clock) -> impl MasonryView<Clock> {
+    // The timer is only part of the tree while the clock is running,
+    // so pausing the clock stops it.
+    let ticker = clock.running.then(|| {
+        timer(Duration::from_secs(1), |clock: &mut Clock| {
+            clock.now = SystemTime::now();
+        })
+    });
+    flex((
+        label(format!("{} UTC", time_of_day(clock.now))),
+        button(
+            if clock.running { "Pause" } else { "Resume" },
+            |clock: &mut Clock| {
+                clock.running = !clock.running;
+                clock.now = SystemTime::now();
+            },
+        ),
+        ticker,
+    ))
+    .cross_axis_alignment(CrossAxisAlignment::Center)
+    .main_axis_alignment(MainAxisAlignment::Center)
+}
+
+fn main() -> Result<(), RunError> {
+    let clock = Clock {
+        now: SystemTime::now(),
+        running: true,
+    };
+    let app = Xilem::new(clock, app_logic);
+    app.run_windowed(EventLoop::with_user_event(), "Clock".into())?;
+    Ok(())
+}
@@ -1,11 +1,11 @@
 // Copyright 2024 the Xilem Authors
 // SPDX-License-Identifier: Apache-2.0
 
+use accesskit::Live;
 use masonry::widget::{CrossAxisAlignment, MainAxisAlignment};
-use winit::error::EventLoopError;
 use xilem::{
-    view::{button, flex, label},
-    EventLoop, MasonryView, Xilem,
+    view::{button, flex, label, AccessibilityExt},
+    EventLoop, MasonryView, RunError, Xilem,
 };
 
 fn app_logic(data: &mut i32) -> impl MasonryView<i32> {
@@ -13,7 +13,8 @@ fn app_logic(data: &mut i32) -> impl MasonryView<i32> {
         button("-", |data| {
             *data -= 1;
         }),
-        label(format!("count: {}", data)),
+        // Announce the new count to screen reader users when it changes.
+        label(format!("count: {}", data)).accessibility_live(Live::Polite),
         button("+", |data| {
             *data += 1;
         }),
@@ -23,7 +24,7 @@ fn app_logic(data: &mut i32) -> impl MasonryView<i32> {
     .main_axis_alignment(MainAxisAlignment::Center)
 }
 
-fn main() -> Result<(), EventLoopError> {
+fn main() -> Result<(), RunError> {
     let app = Xilem::new(0, app_logic);
     app.run_windowed(EventLoop::with_user_event(), "Centered Flex".into())?;
     Ok(())
@@ -4,10 +4,9 @@
 // On Windows platform, don't show a console when opening the app.
 #![windows_subsystem = "windows"]
 
-use xilem::view::{button, checkbox, flex, label, prose, textbox};
-use xilem::{
-    Axis, BoxedMasonryView, Color, EventLoop, EventLoopBuilder, MasonryView, TextAlignment, Xilem,
-};
+use masonry::clipboard;
+use xilem::view::{adapt, button, checkbox, flex, for_each, label, prose, textbox, Either};
+use xilem::{Axis, Color, EventLoop, EventLoopBuilder, MasonryView, TextAlignment, Xilem};
 
 const LOREM: &str = r"Lorem ipsum dolor sit amet, consectetur adipiscing elit. Morbi cursus mi sed euismod euismod. Orci varius natoque penatibus et magnis dis parturient montes, nascetur ridiculus mus. Nullam placerat efficitur tellus at semper. Morbi ac risus magna. Donec ut cursus ex. Etiam quis posuere tellus. Mauris posuere dui et turpis mollis, vitae luctus tellus consectetur. Lorem ipsum dolor sit amet, consectetur adipiscing elit. Curabitur eu facilisis nisl.
 
@@ -30,9 +29,9 @@ fn app_logic(data: &mut AppData) -> impl MasonryView<AppData> {
         Axis::Vertical
     };
 
-    let sequence = (0..count)
-        .map(|x| button(format!("+{x}"), move |data: &mut AppData| data.count += x))
-        .collect::<Vec<_>>();
+    let sequence = for_each(0..count, |_, x| {
+        button(format!("+{x}"), move |data: &mut AppData| data.count += x)
+    });
     flex((
         flex((
             label("Label")
@@ -47,21 +46,35 @@ fn app_logic(data: &mut AppData) -> impl MasonryView<AppData> {
                 data.textbox_contents = new_value;
             },
         ),
+        flex((
+            button("Copy", |data: &mut AppData| {
+                clipboard::set_text(data.textbox_contents.clone());
+            }),
+            button("Paste", |data: &mut AppData| {
+                if let Some(text) = clipboard::get_text() {
+                    data.textbox_contents = text;
+                }
+            }),
+        ))
+        .direction(Axis::Horizontal),
         prose(LOREM).alignment(TextAlignment::Middle),
         button(button_label, |data: &mut AppData| data.count += 1),
-        checkbox("Check me", data.active, |data: &mut AppData, checked| {
-            data.active = checked;
-        }),
+        adapt(
+            |data: &mut AppData| &mut data.active,
+            checkbox("Check me", data.active, |active: &mut bool, checked| {
+                *active = checked;
+            }),
+        ),
         toggleable(data),
         button("Decrement", |data: &mut AppData| data.count -= 1),
-        button("Reset", |data: &mut AppData| data.count = 0),
+        (count != 0).then(|| button("Reset", |data: &mut AppData| data.count = 0)),
         flex(sequence).direction(axis),
     ))
 }
 
 fn toggleable(data: &mut AppData) -> impl MasonryView<AppData> {
-    let inner_view: BoxedMasonryView<_, _> = if data.active {
-        Box::new(
+    if data.active {
+        Either::A(
             flex((
                 button("Deactivate", |data: &mut AppData| {
                     data.active = false;
@@ -73,9 +86,8 @@ fn toggleable(data: &mut AppData) -> impl MasonryView<AppData> {
             .direction(Axis::Horizontal),
         )
     } else {
-        Box::new(button("Activate", |data: &mut AppData| data.active = true))
-    };
-    inner_view
+        Either::B(button("Activate", |data: &mut AppData| data.active = true))
+    }
 }
 
 struct AppData {
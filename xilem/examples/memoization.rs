@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::sync::Arc;
-use xilem::view::{button, flex, memoize};
+use xilem::view::{button, flex, memoize, static_view};
 use xilem::{AnyMasonryView, EventLoop, MasonryView, Xilem};
 
 // There are currently two ways to do memoization
@@ -47,8 +47,9 @@ fn decrease_button(state: &AppState) -> impl MasonryView<AppState> {
     })
 }
 
+// The reset button never changes, so it only needs to be built once
 fn reset_button() -> impl MasonryView<AppState> {
-    button("reset", |data: &mut AppState| data.count = 0)
+    static_view(|| button("reset", |data: &mut AppState| data.count = 0))
 }
 
 fn app_logic(state: &mut AppState) -> impl MasonryView<AppState> {
@@ -4,7 +4,7 @@
 // On Windows platform, don't show a console when opening the app.
 #![windows_subsystem = "windows"]
 
-use xilem::view::{button, checkbox, flex, textbox};
+use xilem::view::{button, checkbox, flex, for_each, padding, textbox};
 use xilem::{Axis, EventLoop, MasonryView, Xilem};
 
 struct Task {
@@ -47,26 +47,21 @@ fn app_logic(task_list: &mut TaskList) -> impl MasonryView<TaskList> {
     ))
     .direction(Axis::Vertical);
 
-    let tasks = task_list
-        .tasks
-        .iter()
-        .enumerate()
-        .map(|(i, task)| {
-            let checkbox = checkbox(
-                task.description.clone(),
-                task.done,
-                move |data: &mut TaskList, checked| {
-                    data.tasks[i].done = checked;
-                },
-            );
-            let delete_button = button("Delete", move |data: &mut TaskList| {
-                data.tasks.remove(i);
-            });
-            flex((checkbox, delete_button)).direction(Axis::Horizontal)
-        })
-        .collect::<Vec<_>>();
+    let tasks = for_each(&task_list.tasks, |i, task| {
+        let checkbox = checkbox(
+            task.description.clone(),
+            task.done,
+            move |data: &mut TaskList, checked| {
+                data.tasks[i].done = checked;
+            },
+        );
+        let delete_button = button("Delete", move |data: &mut TaskList| {
+            data.tasks.remove(i);
+        });
+        flex((checkbox, delete_button)).direction(Axis::Horizontal)
+    });
 
-    flex((first_line, tasks))
+    padding((16., 8.), flex((first_line, tasks)))
 }
 
 fn main() {
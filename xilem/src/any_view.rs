@@ -20,8 +20,8 @@ use crate::{MasonryView, MessageResult, ViewCx, ViewId};
 /// or used to implement conditional display and switching of views.
 ///
 /// Note that `Option` can also be used for conditionally displaying
-/// views in a [`ViewSequence`](crate::ViewSequence).
-// TODO: Mention `Either` when we have implemented that?
+/// views in a [`ViewSequence`](crate::ViewSequence), and [`Either`](crate::view::Either)
+/// for switching between two views without boxing them.
 pub type BoxedMasonryView<State, Action = ()> = Box<dyn AnyMasonryView<State, Action>>;
 
 impl<State: 'static, Action: 'static> MasonryView<State, Action>
@@ -168,6 +168,7 @@ where
             });
         } else {
             // Otherwise, replace the element.
+            cx.forget_child(ViewId::for_type::<V>(dyn_state.generation));
 
             // Increase the generation, because the underlying widget has been swapped out.
             // Overflow condition: Impossible to overflow, as u64 only ever incremented by 1
@@ -213,6 +214,10 @@ pub struct DynWidget {
 }
 
 impl DynWidget {
+    pub(crate) fn new(inner: WidgetPod<Box<dyn Widget>>) -> Self {
+        Self { inner }
+    }
+
     pub(crate) fn replace_inner(
         this: &mut WidgetMut<'_, Self>,
         widget: WidgetPod<Box<dyn Widget>>,
@@ -2,32 +2,43 @@
 // SPDX-License-Identifier: Apache-2.0
 
 #![allow(clippy::comparison_chain)]
-use std::{any::Any, collections::HashMap};
+use std::{any::Any, collections::HashMap, sync::Arc};
 
 use masonry::{
     app_driver::AppDriver,
-    event_loop_runner,
+    event_loop_runner::{self, AppOptions, InputOptions, RenderOptions},
+    ext_event::ExtEventSink,
+    vello::peniko::Image,
     widget::{RootWidget, WidgetMut},
-    Widget, WidgetId, WidgetPod,
+    Size, Widget, WidgetId, WidgetPod,
+};
+pub use masonry::{
+    paint_scene_helpers::UnitPoint,
+    text2::IconFont,
+    theme::Theme,
+    widget::{Axis, BadgeContent, BadgeStyle, BreakpointClass, Breakpoints},
+    ClickSettings, Color, Padding, TextAlignment,
 };
-pub use masonry::{widget::Axis, Color, TextAlignment};
 use winit::{
     dpi::LogicalSize,
-    error::EventLoopError,
-    window::{Window, WindowAttributes},
+    window::{Fullscreen, Icon, Window, WindowAttributes},
 };
 
 mod any_view;
 mod id;
+mod offscreen;
 mod sequence;
 mod vec_splice;
 pub use any_view::{AnyMasonryView, BoxedMasonryView};
 pub mod view;
 pub use id::ViewId;
+pub use offscreen::OffscreenRenderer;
 pub use sequence::{ElementSplice, ViewSequence};
 pub use vec_splice::VecSplice;
 
-pub use masonry::event_loop_runner::{EventLoop, EventLoopBuilder};
+pub use masonry::event_loop_runner::{
+    EventLoop, EventLoopBuilder, MsaaLevel, PresentMode, RunError,
+};
 
 pub struct Xilem<State, Logic, View>
 where
@@ -35,6 +46,11 @@ where
 {
     root_widget: RootWidget<View::Element>,
     driver: MasonryDriver<State, Logic, View, View::ViewState>,
+    /// The attributes used by [`Xilem::run_windowed`].
+    window_attributes: WindowAttributes,
+    app_options: AppOptions,
+    render_options: RenderOptions,
+    input_options: InputOptions,
 }
 
 pub struct MasonryDriver<State, Logic, View, ViewState> {
@@ -76,20 +92,7 @@ where
                 }
             };
             if rebuild {
-                let next_view = (self.logic)(&mut self.state);
-                let mut root = ctx.get_root::<RootWidget<View::Element>>();
-
-                self.view_cx.view_tree_changed = false;
-                next_view.rebuild(
-                    &mut self.view_state,
-                    &mut self.view_cx,
-                    &self.current_view,
-                    root.get_element(),
-                );
-                if cfg!(debug_assertions) && !self.view_cx.view_tree_changed {
-                    tracing::debug!("Nothing changed as result of action");
-                }
-                self.current_view = next_view;
+                self.rebuild(ctx.get_root::<RootWidget<View::Element>>());
             }
         } else {
             eprintln!("Got action {action:?} for unknown widget. Did you forget to use `with_action_widget`?");
@@ -97,6 +100,29 @@ where
     }
 }
 
+impl<State, Logic, View> MasonryDriver<State, Logic, View, View::ViewState>
+where
+    Logic: FnMut(&mut State) -> View,
+    View: MasonryView<State>,
+{
+    /// Run the app logic, and update the widget tree under `root` to match the new view.
+    fn rebuild(&mut self, mut root: WidgetMut<RootWidget<View::Element>>) {
+        let next_view = (self.logic)(&mut self.state);
+
+        self.view_cx.view_tree_changed = false;
+        next_view.rebuild(
+            &mut self.view_state,
+            &mut self.view_cx,
+            &self.current_view,
+            root.get_element(),
+        );
+        if cfg!(debug_assertions) && !self.view_cx.view_tree_changed {
+            tracing::debug!("Nothing changed as result of action");
+        }
+        self.current_view = next_view;
+    }
+}
+
 impl<State, Logic, View> Xilem<State, Logic, View>
 where
     Logic: FnMut(&mut State) -> View,
@@ -104,11 +130,7 @@ where
 {
     pub fn new(mut state: State, mut logic: Logic) -> Self {
         let first_view = logic(&mut state);
-        let mut view_cx = ViewCx {
-            id_path: vec![],
-            widget_map: HashMap::new(),
-            view_tree_changed: false,
-        };
+        let mut view_cx = ViewCx::new();
         let (pod, view_state) = first_view.build(&mut view_cx);
         let root_widget = RootWidget::from_pod(pod);
         Xilem {
@@ -120,7 +142,169 @@ where
                 view_state,
             },
             root_widget,
+            window_attributes: Window::default_attributes()
+                .with_resizable(true)
+                .with_min_inner_size(LogicalSize::new(600., 800.)),
+            app_options: AppOptions::default(),
+            render_options: RenderOptions::default(),
+            input_options: InputOptions::default(),
+        }
+    }
+
+    /// Set the smallest size the window can be resized to.
+    pub fn with_min_size(mut self, size: LogicalSize<f64>) -> Self {
+        self.window_attributes.min_inner_size = Some(size.into());
+        self
+    }
+
+    /// Set the largest size the window can be resized to.
+    pub fn with_max_size(mut self, size: LogicalSize<f64>) -> Self {
+        self.window_attributes.max_inner_size = Some(size.into());
+        self
+    }
+
+    /// Set whether the user can resize the window.
+    pub fn with_resizable(mut self, resizable: bool) -> Self {
+        self.window_attributes.resizable = resizable;
+        self
+    }
+
+    /// Set whether the window starts in borderless fullscreen, on the current monitor.
+    pub fn with_fullscreen(mut self, fullscreen: bool) -> Self {
+        self.window_attributes.fullscreen = fullscreen.then_some(Fullscreen::Borderless(None));
+        self
+    }
+
+    /// Set the icon shown in the window's title bar and in the taskbar.
+    pub fn with_window_icon(mut self, icon: Icon) -> Self {
+        self.window_attributes.window_icon = Some(icon);
+        self
+    }
+
+    /// Set how frames are presented to the window.
+    ///
+    /// The default is [`PresentMode::AutoVsync`]. Real-time applications may prefer
+    /// [`PresentMode::AutoNoVsync`] or [`PresentMode::Mailbox`] for lower latency.
+    pub fn with_present_mode(mut self, present_mode: PresentMode) -> Self {
+        self.render_options.present_mode = present_mode;
+        self
+    }
+
+    /// Set the level of multisample antialiasing used to render the window.
+    ///
+    /// The default is [`MsaaLevel::Off`], which uses area antialiasing instead.
+    pub fn with_msaa(mut self, msaa: MsaaLevel) -> Self {
+        self.render_options.msaa = msaa;
+        self
+    }
+
+    /// Set whether to run the rendering pipeline on the CPU, rather than in compute shaders.
+    ///
+    /// This is off by default, but is turned on automatically when no GPU can be used and
+    /// the window is rendered with a software adapter instead.
+    pub fn with_cpu_rendering(mut self, enabled: bool) -> Self {
+        self.render_options.use_cpu = enabled;
+        self
+    }
+
+    /// Set how close in time and space consecutive clicks must be to count as a double click.
+    ///
+    /// The default groups clicks less than 500ms and 4 logical pixels apart.
+    pub fn with_click_settings(mut self, click_settings: ClickSettings) -> Self {
+        self.input_options.click_settings = click_settings;
+        self
+    }
+
+    /// Set whether to invert the direction of mouse wheel and touchpad scrolling, so
+    /// that content follows the fingers on a touchpad.
+    ///
+    /// This is off by default, which scrolls in the direction reported by the platform.
+    pub fn with_natural_scrolling(mut self, enabled: bool) -> Self {
+        self.input_options.natural_scrolling = enabled;
+        self
+    }
+
+    /// Set whether to show the frame rate and a graph of how long recent frames took to
+    /// lay out, paint and render, in the top right corner of the window.
+    ///
+    /// This is meant for finding performance problems, and is off by default.
+    pub fn with_debug_overlay(mut self, enabled: bool) -> Self {
+        self.app_options.debug_overlay = enabled;
+        self
+    }
+
+    /// Register the fonts in the contents of a font file, such as one included with
+    /// `include_bytes!`, so that text views can use them by family name.
+    ///
+    /// Text whose font family isn't registered or installed falls back to the system's
+    /// sans-serif font.
+    pub fn with_font(mut self, data: impl Into<Arc<[u8]>>) -> Self {
+        self.app_options.fonts.push(data.into());
+        self
+    }
+
+    /// Register an icon font, so that its icons can be shown with [`view::icon`].
+    ///
+    /// If several icon fonts have an icon with the same name, the last one registered is used.
+    pub fn with_icon_font(mut self, font: IconFont) -> Self {
+        self.app_options.icon_fonts.push(font);
+        self
+    }
+
+    /// Set the colors widgets are painted with, such as [`Theme::light`].
+    ///
+    /// The default is [`Theme::dark`]. Colors set on a view, such as the color of a
+    /// button, take precedence over the theme. See [`Theme`] for the widgets which
+    /// don't use it yet.
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.app_options.theme = theme;
+        self
+    }
+
+    /// Set the theme used while the platform's high contrast setting is on, or `None` to
+    /// keep using the [theme](Self::with_theme).
+    ///
+    /// The default is [`Theme::high_contrast`].
+    pub fn with_high_contrast_theme(mut self, theme: Option<Theme>) -> Self {
+        self.app_options.high_contrast_theme = theme;
+        self
+    }
+
+    /// Render the current view to an image, without a window, such as for golden image
+    /// tests or thumbnails.
+    ///
+    /// The view is laid out at `size` logical pixels, and the image has `scale_factor`
+    /// pixels per logical pixel. It is in the RGBA8 format, so its data can be saved as a
+    /// PNG with a crate such as `image`. The fonts registered with [`with_font`](Self::with_font)
+    /// and [`with_icon_font`](Self::with_icon_font), and the [theme](Self::with_theme), are used.
+    ///
+    /// To render the view for several states, use [`into_offscreen_renderer`](Self::into_offscreen_renderer)
+    /// instead.
+    ///
+    /// # Panics
+    ///
+    /// If no compatible GPU device can be found.
+    pub fn render_to_image(self, size: Size, scale_factor: f64) -> Image {
+        self.into_offscreen_renderer(size)
+            .with_scale_factor(scale_factor)
+            .render()
+    }
+
+    /// Create a renderer which lays out the app's views at `size` and renders them to
+    /// images, without a window.
+    ///
+    /// Like [`render_to_image`](Self::render_to_image), this uses the fonts and theme
+    /// registered on the app.
+    pub fn into_offscreen_renderer(self, size: Size) -> OffscreenRenderer<State, Logic, View> {
+        let mut renderer = masonry::offscreen::OffscreenRenderer::new(self.root_widget, size)
+            .with_theme(self.app_options.theme);
+        for font in &self.app_options.fonts {
+            renderer.add_font(font);
+        }
+        for font in &self.app_options.icon_fonts {
+            renderer.add_icon_font(font);
         }
+        OffscreenRenderer::new(self.driver, renderer)
     }
 
     // TODO: Make windows a specific view
@@ -130,32 +314,45 @@ where
         // This might need to be generic over the event type?
         event_loop: EventLoopBuilder,
         window_title: String,
-    ) -> Result<(), EventLoopError>
+    ) -> Result<(), RunError>
     where
         State: 'static,
         Logic: 'static,
         View: 'static,
     {
-        let window_size = LogicalSize::new(600., 800.);
-        let window_attributes = Window::default_attributes()
-            .with_title(window_title)
-            .with_resizable(true)
-            .with_min_inner_size(window_size);
+        let window_attributes = self.window_attributes.clone().with_title(window_title);
         self.run_windowed_in(event_loop, window_attributes)
     }
 
     // TODO: Make windows into a custom view
+    /// Run the app in a window with the given attributes.
+    ///
+    /// This ignores the window options set on this `Xilem`, but uses its present mode
+    /// and antialiasing options.
     pub fn run_windowed_in(
         self,
-        event_loop: EventLoopBuilder,
+        mut event_loop: EventLoopBuilder,
         window_attributes: WindowAttributes,
-    ) -> Result<(), EventLoopError>
+    ) -> Result<(), RunError>
     where
         State: 'static,
         Logic: 'static,
         View: 'static,
     {
-        event_loop_runner::run(event_loop, window_attributes, self.root_widget, self.driver)
+        let event_loop = event_loop.build()?;
+        self.driver
+            .view_cx
+            .ext_event_sink
+            .set_proxy(event_loop.create_proxy());
+        event_loop_runner::run_with_options(
+            window_attributes,
+            self.app_options,
+            self.render_options,
+            self.input_options,
+            event_loop,
+            self.root_widget,
+            self.driver,
+        )
     }
 }
 pub trait MasonryView<State, Action = ()>: Send + Sync + 'static {
@@ -184,20 +381,38 @@ pub trait MasonryView<State, Action = ()>: Send + Sync + 'static {
 pub struct ViewCx {
     /// The map from a widgets id to its position in the View tree.
     ///
-    /// This includes only the widgets which might send actions
-    /// This is currently never cleaned up
+    /// This includes only the widgets which might send actions.
+    /// Widgets are forgotten when the views which built them are torn down, see
+    /// [`forget_child`](Self::forget_child).
     widget_map: HashMap<WidgetId, Vec<ViewId>>,
     id_path: Vec<ViewId>,
     view_tree_changed: bool,
+    ext_event_sink: ExtEventSink,
 }
 
 impl ViewCx {
+    /// A context for building a new view tree.
+    pub(crate) fn new() -> Self {
+        Self {
+            id_path: vec![],
+            widget_map: HashMap::new(),
+            view_tree_changed: false,
+            ext_event_sink: ExtEventSink::new(),
+        }
+    }
+
     pub fn mark_changed(&mut self) {
         if cfg!(debug_assertions) {
             self.view_tree_changed = true;
         }
     }
 
+    /// A handle which can be moved to other threads, to send actions to widgets
+    /// once the app is running.
+    pub fn ext_event_sink(&self) -> &ExtEventSink {
+        &self.ext_event_sink
+    }
+
     pub fn with_leaf_action_widget<E: Widget>(
         &mut self,
         f: impl FnOnce(&mut Self) -> WidgetPod<E>,
@@ -216,6 +431,25 @@ impl ViewCx {
         value
     }
 
+    /// Forget the widgets built by the child view with `id`, whose widgets were just
+    /// removed from the tree, so that the widget map doesn't keep growing.
+    ///
+    /// Views which tear down a child, such as [`Option`] and [`Either`](crate::view::Either),
+    /// call this with the id they built the child with.
+    pub fn forget_child(&mut self, id: ViewId) {
+        let depth = self.id_path.len();
+        let id_path = &self.id_path;
+        self.widget_map.retain(|_, path| {
+            let in_child = path.len() > depth
+                && path[depth].routing_id() == id.routing_id()
+                && path[..depth]
+                    .iter()
+                    .zip(id_path)
+                    .all(|(a, b)| a.routing_id() == b.routing_id());
+            !in_child
+        });
+    }
+
     pub fn with_id<R>(&mut self, id: ViewId, f: impl FnOnce(&mut Self) -> R) -> R {
         self.id_path.push(id);
         let res = f(self);
@@ -233,3 +467,106 @@ pub enum MessageResult<A> {
     Nop,
     Stale(Box<dyn Any>),
 }
+
+#[cfg(test)]
+mod tests {
+    use masonry::testing::TestHarness;
+    use masonry::widget::Flex;
+
+    use super::*;
+    use crate::view::{button, either, flex, label};
+
+    #[test]
+    fn removed_option_branch_is_forgotten() {
+        let app = Xilem::new(true, |shown: &mut bool| {
+            flex(shown.then(|| button("hide", |shown: &mut bool| *shown = false)))
+        });
+        let mut driver = app.driver;
+        assert_eq!(driver.view_cx.widget_map.len(), 1);
+
+        let mut harness = TestHarness::create(app.root_widget);
+        driver.state = false;
+        harness.edit_root_widget(|mut root| driver.rebuild(root.downcast::<RootWidget<Flex>>()));
+        assert!(driver.view_cx.widget_map.is_empty());
+    }
+
+    #[test]
+    fn removed_sequence_items_are_forgotten() {
+        let app = Xilem::new(3, |count: &mut usize| {
+            let buttons = (0..*count)
+                .map(|_| button("remove", |count: &mut usize| *count -= 1))
+                .collect::<Vec<_>>();
+            flex(buttons)
+        });
+        let mut driver = app.driver;
+        assert_eq!(driver.view_cx.widget_map.len(), 3);
+
+        let mut harness = TestHarness::create(app.root_widget);
+        driver.state = 1;
+        harness.edit_root_widget(|mut root| driver.rebuild(root.downcast::<RootWidget<Flex>>()));
+        assert_eq!(driver.view_cx.widget_map.len(), 1);
+        let (&id, _) = driver.view_cx.widget_map.iter().next().unwrap();
+        assert!(harness.try_get_widget(id).is_some());
+    }
+
+    #[test]
+    fn replaced_either_branch_is_forgotten() {
+        let app = Xilem::new(true, |first: &mut bool| {
+            flex(either(
+                *first,
+                button("first", |first: &mut bool| *first = false),
+                button("second", |first: &mut bool| *first = true),
+            ))
+        });
+        let mut driver = app.driver;
+        let (&first_id, _) = driver.view_cx.widget_map.iter().next().unwrap();
+
+        let mut harness = TestHarness::create(app.root_widget);
+        driver.state = false;
+        harness.edit_root_widget(|mut root| driver.rebuild(root.downcast::<RootWidget<Flex>>()));
+        assert_eq!(driver.view_cx.widget_map.len(), 1);
+        assert!(!driver.view_cx.widget_map.contains_key(&first_id));
+    }
+
+    #[test]
+    fn option_toggled_back_builds_a_new_child() {
+        let app = Xilem::new(true, |shown: &mut bool| {
+            flex((
+                label("first"),
+                shown.then(|| button("hide", |shown: &mut bool| *shown = false)),
+            ))
+        });
+        let mut driver = app.driver;
+        let mut harness = TestHarness::create(app.root_widget);
+        let (&first_id, first_path) = driver.view_cx.widget_map.iter().next().unwrap();
+        let first_path = first_path.clone();
+
+        let mut set_shown = |harness: &mut TestHarness, shown: bool| {
+            driver.state = shown;
+            harness
+                .edit_root_widget(|mut root| driver.rebuild(root.downcast::<RootWidget<Flex>>()));
+        };
+        set_shown(&mut harness, false);
+        assert!(harness.try_get_widget(first_id).is_none());
+        set_shown(&mut harness, true);
+
+        // The button is built again, rather than reusing the removed one or its state.
+        let (&second_id, _) = driver.view_cx.widget_map.iter().next().unwrap();
+        assert_ne!(first_id, second_id);
+        assert!(harness.try_get_widget(first_id).is_none());
+        // The new button was laid out after the label.
+        let button_rect = harness.get_widget(second_id).state().layout_rect();
+        assert!(button_rect.y0 > 0.0 && !button_rect.is_empty());
+
+        // Messages for the removed button are stale.
+        let result = MasonryView::message(
+            &driver.current_view,
+            &mut driver.view_state,
+            &first_path,
+            Box::new(masonry::Action::ButtonPressed),
+            &mut driver.state,
+        );
+        assert!(matches!(result, MessageResult::Stale(_)));
+        assert!(driver.state);
+    }
+}
@@ -0,0 +1,134 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+use masonry::{offscreen, vello::peniko::Image, Action, Color, ScaleCoefficient, Size};
+
+use crate::{MasonryDriver, MasonryView};
+
+/// Renders the views of an app to images, without a window.
+///
+/// This can be used to generate preview thumbnails, or to export the state of the UI.
+/// It is created with [`Xilem::into_offscreen_renderer`](crate::Xilem::into_offscreen_renderer),
+/// and uses the fonts and theme registered on the app. Each call to [`render`](Self::render)
+/// runs the app logic on the app state and reconciles the result with the previous view,
+/// as a windowed app would.
+pub struct OffscreenRenderer<State, Logic, View>
+where
+    View: MasonryView<State>,
+{
+    driver: MasonryDriver<State, Logic, View, View::ViewState>,
+    renderer: offscreen::OffscreenRenderer,
+}
+
+impl<State, Logic, View> OffscreenRenderer<State, Logic, View>
+where
+    Logic: FnMut(&mut State) -> View,
+    View: MasonryView<State>,
+{
+    pub(crate) fn new(
+        driver: MasonryDriver<State, Logic, View, View::ViewState>,
+        renderer: offscreen::OffscreenRenderer,
+    ) -> Self {
+        Self { driver, renderer }
+    }
+
+    /// Set the color drawn behind the view.
+    ///
+    /// The default is the background color of the app's theme.
+    pub fn with_background_color(mut self, color: Color) -> Self {
+        self.renderer = self.renderer.with_background_color(color);
+        self
+    }
+
+    /// Set the number of image pixels per logical pixel, which is 1 by default.
+    ///
+    /// The view is still laid out at the logical size, so a factor of 2 renders it as
+    /// on a high density display.
+    pub fn with_scale_factor(mut self, scale_factor: f64) -> Self {
+        self.set_scale_factor(scale_factor);
+        self
+    }
+
+    /// Set the number of image pixels per logical pixel.
+    pub fn set_scale_factor(&mut self, scale_factor: f64) {
+        self.renderer
+            .set_scale_factor(ScaleCoefficient::new(scale_factor));
+    }
+
+    /// Set the logical size the view is laid out at.
+    pub fn set_size(&mut self, size: Size) {
+        self.renderer.set_size(size);
+    }
+
+    /// The app state.
+    pub fn state(&self) -> &State {
+        &self.driver.state
+    }
+
+    /// The app state, to change it before the next [`render`](Self::render).
+    pub fn state_mut(&mut self) -> &mut State {
+        &mut self.driver.state
+    }
+
+    /// Build the view for the app state, and return an image of it.
+    ///
+    /// The messages sent by tasks and timers since the last render are handled first.
+    /// The image is in the RGBA8 format.
+    ///
+    /// # Panics
+    ///
+    /// If no compatible GPU device can be found.
+    pub fn render(&mut self) -> Image {
+        // There's no event loop to deliver these, so we pass them to the driver here.
+        for (payload, target) in self.driver.view_cx.ext_event_sink.take_pending() {
+            let payload: Box<dyn std::any::Any> = payload;
+            self.renderer
+                .handle_action(&mut self.driver, target, Action::Other(payload.into()));
+        }
+        let driver = &mut self.driver;
+        self.renderer
+            .edit_root_widget(|mut root| driver.rebuild(root.downcast()));
+        self.renderer.render()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use masonry::theme;
+
+    use crate::view::{flex, label};
+    use crate::Xilem;
+
+    use super::*;
+
+    #[test]
+    fn render_hello_to_image() {
+        // Rendering needs a GPU, like the render tests of Masonry.
+        if std::env::var("SKIP_RENDER_TESTS").is_ok_and(|it| !it.is_empty()) {
+            return;
+        }
+        let app = Xilem::new((), |_: &mut ()| flex(label("Hello, world!")));
+        let image = app.render_to_image(Size::new(200., 100.), 2.0);
+        assert_eq!((image.width, image.height), (400, 200));
+
+        // Something other than the background was drawn.
+        let background = theme::WINDOW_BACKGROUND_COLOR;
+        let background = [background.r, background.g, background.b, background.a];
+        let data = image.data.data();
+        assert_eq!(data.len(), 400 * 200 * 4);
+        assert!(data.chunks_exact(4).any(|pixel| pixel != background));
+    }
+
+    #[test]
+    fn render_changed_state() {
+        if std::env::var("SKIP_RENDER_TESTS").is_ok_and(|it| !it.is_empty()) {
+            return;
+        }
+        let app = Xilem::new(0, |count: &mut i32| flex(label(format!("Count: {count}"))));
+        let mut renderer = app.into_offscreen_renderer(Size::new(200., 100.));
+        let before = renderer.render();
+        *renderer.state_mut() += 1;
+        let after = renderer.render();
+        assert_ne!(before.data.data(), after.data.data());
+    }
+}
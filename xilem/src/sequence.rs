@@ -115,6 +115,14 @@ pub struct OptionSeqState<InnerState> {
     generation: u64,
 }
 
+/// An optional part of a sequence.
+///
+/// `None` adds no elements, and `Some(seq)` adds the elements of `seq`, so this can be
+/// used to conditionally include a child, e.g. in a [`flex`](crate::view::flex), without
+/// a placeholder widget.
+///
+/// When this goes from `None` to `Some`, the inner sequence is built from scratch,
+/// so no state is kept from a previous `Some`.
 impl<State, Action, Marker, VT: ViewSequence<State, Action, Marker>>
     ViewSequence<State, Action, (WasASequence, Marker)> for Option<VT>
 {
@@ -159,6 +167,7 @@ impl<State, Action, Marker, VT: ViewSequence<State, Action, Marker>>
                 // Maybe replace with `prev.cleanup`?
                 let count = prev.count();
                 elements.delete(count);
+                cx.forget_child(ViewId::for_type::<VT>(seq_state.generation));
                 seq_state.inner = None;
                 cx.mark_changed();
             }
@@ -248,7 +257,10 @@ impl<T, A, Marker, VT: ViewSequence<T, A, Marker>> ViewSequence<T, A, (WasASeque
         let n = self.len();
         if n < prev.len() {
             let n_delete = prev[n..].iter().map(ViewSequence::count).sum();
-            seq_state.inner_with_generations.drain(n..);
+            for (i, (_, generation)) in seq_state.inner_with_generations.drain(n..).enumerate() {
+                let id = create_vector_view_id(n + i, generation);
+                cx.forget_child(ViewId::for_type::<VT>(id));
+            }
             elements.delete(n_delete);
             cx.mark_changed();
         } else if n > prev.len() {
@@ -316,7 +328,7 @@ impl<T, A, Marker, VT: ViewSequence<T, A, Marker>> ViewSequence<T, A, (WasASeque
 
 /// Turns an index and a generation into a packed id, suitable for use in
 /// [`ViewId`]s
-fn create_vector_view_id(index: usize, generation: u32) -> u64 {
+pub(crate) fn create_vector_view_id(index: usize, generation: u32) -> u64 {
     let id_low: u32 = index.try_into().expect(
         "Can't have more than 4294967295 (u32::MAX-1) views in a single vector backed sequence",
     );
@@ -326,7 +338,7 @@ fn create_vector_view_id(index: usize, generation: u32) -> u64 {
 }
 
 /// Undoes [`create_vector_view_id`]
-fn view_id_to_index_generation(view_id: u64) -> (usize, u32) {
+pub(crate) fn view_id_to_index_generation(view_id: u64) -> (usize, u32) {
     let id_low_ix = view_id as u32;
     let id_high_gen = (view_id >> 32) as u32;
     (id_low_ix as usize, id_high_gen)
@@ -0,0 +1,151 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+use accesskit::{Live, Role};
+use masonry::{widget::WidgetMut, ArcStr, WidgetPod};
+
+use crate::{MasonryView, MessageResult, ViewCx, ViewId};
+
+/// Describe `child` to accessibility, with a node around it whose role, label and
+/// description are set with the methods of [`Accessible`].
+///
+/// By default the node is a [`GenericContainer`](Role::GenericContainer) without a label.
+///
+/// See also [`AccessibilityExt`].
+pub fn accessible<V>(child: V) -> Accessible<V> {
+    Accessible {
+        child,
+        role: Role::GenericContainer,
+        label: None,
+        description: None,
+        live: Live::Off,
+    }
+}
+
+/// Adds [`accessibility_label`](Self::accessibility_label),
+/// [`accessibility_role`](Self::accessibility_role),
+/// [`accessibility_description`](Self::accessibility_description) and
+/// [`accessibility_live`](Self::accessibility_live) to views.
+pub trait AccessibilityExt: Sized {
+    /// Describe this view to screen readers with `label`.
+    fn accessibility_label(self, label: impl Into<ArcStr>) -> Accessible<Self> {
+        accessible(self).accessibility_label(label)
+    }
+
+    /// Expose this view to accessibility with the given role.
+    fn accessibility_role(self, role: Role) -> Accessible<Self> {
+        accessible(self).accessibility_role(role)
+    }
+
+    /// Describe this view to screen readers with `description`, which is read after its label.
+    fn accessibility_description(self, description: impl Into<ArcStr>) -> Accessible<Self> {
+        accessible(self).accessibility_description(description)
+    }
+
+    /// Make this view a live region, so that screen readers announce changes to it, such as
+    /// the text of a label changing.
+    fn accessibility_live(self, live: Live) -> Accessible<Self> {
+        accessible(self).accessibility_live(live)
+    }
+}
+
+impl<V: Send + Sync + 'static> AccessibilityExt for V {}
+
+pub struct Accessible<V> {
+    child: V,
+    role: Role,
+    label: Option<ArcStr>,
+    description: Option<ArcStr>,
+    live: Live,
+}
+
+// These take precedence over the methods of `AccessibilityExt`, so that chaining them
+// describes a single node.
+impl<V> Accessible<V> {
+    /// Set the label of the node, which is what screen readers announce it as.
+    pub fn accessibility_label(mut self, label: impl Into<ArcStr>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Set the role of the node.
+    pub fn accessibility_role(mut self, role: Role) -> Self {
+        self.role = role;
+        self
+    }
+
+    /// Set the description of the node, which is read after its label.
+    pub fn accessibility_description(mut self, description: impl Into<ArcStr>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Make the node a live region, so that screen readers announce changes to the label
+    /// of it or its descendants.
+    ///
+    /// With [`Live::Polite`] they wait until they have finished speaking, and with
+    /// [`Live::Assertive`] they interrupt what they are saying.
+    pub fn accessibility_live(mut self, live: Live) -> Self {
+        self.live = live;
+        self
+    }
+}
+
+impl<V, State, Action> MasonryView<State, Action> for Accessible<V>
+where
+    V: MasonryView<State, Action>,
+{
+    type Element = masonry::widget::Accessible<V::Element>;
+    type ViewState = V::ViewState;
+
+    fn build(&self, cx: &mut ViewCx) -> (WidgetPod<Self::Element>, Self::ViewState) {
+        let (child, child_state) = self.child.build(cx);
+        let mut widget = masonry::widget::Accessible::from_child_pod(child)
+            .with_role(self.role)
+            .with_live(self.live);
+        if let Some(label) = &self.label {
+            widget = widget.with_label(label.clone());
+        }
+        if let Some(description) = &self.description {
+            widget = widget.with_description(description.clone());
+        }
+        (WidgetPod::new(widget), child_state)
+    }
+
+    fn rebuild(
+        &self,
+        view_state: &mut Self::ViewState,
+        cx: &mut ViewCx,
+        prev: &Self,
+        mut element: WidgetMut<Self::Element>,
+    ) {
+        if prev.role != self.role {
+            element.set_role(self.role);
+            cx.mark_changed();
+        }
+        if prev.label != self.label {
+            element.set_label(self.label.clone());
+            cx.mark_changed();
+        }
+        if prev.description != self.description {
+            element.set_description(self.description.clone());
+            cx.mark_changed();
+        }
+        if prev.live != self.live {
+            element.set_live(self.live);
+            cx.mark_changed();
+        }
+        self.child
+            .rebuild(view_state, cx, &prev.child, element.child_mut());
+    }
+
+    fn message(
+        &self,
+        view_state: &mut Self::ViewState,
+        id_path: &[ViewId],
+        message: Box<dyn std::any::Any>,
+        app_state: &mut State,
+    ) -> MessageResult<Action> {
+        self.child.message(view_state, id_path, message, app_state)
+    }
+}
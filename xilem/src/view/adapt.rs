@@ -0,0 +1,87 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{any::Any, marker::PhantomData};
+
+use masonry::{widget::WidgetMut, WidgetPod};
+
+use crate::{MasonryView, MessageResult, ViewCx, ViewId};
+
+/// Embed a view written against `ChildState` in a view tree whose state is `ParentState`.
+///
+/// `map` is called with the parent state whenever the child view handles a message, and
+/// returns the part of it the child works on. This allows reusable components to only
+/// depend on the state they need:
+///
+/// ```ignore
+/// fn counter_view(count: i32) -> impl MasonryView<i32> {
+///     button(format!("clicked {count} times"), |count: &mut i32| *count += 1)
+/// }
+///
+/// fn app_logic(state: &mut AppState) -> impl MasonryView<AppState> {
+///     flex((
+///         adapt(|state: &mut AppState| &mut state.counter, counter_view(state.counter)),
+///         label(state.title.clone()),
+///     ))
+/// }
+/// ```
+///
+/// Actions returned by the child view are passed through unchanged; use
+/// [`map_action`](crate::view::map_action) to turn them into actions of the parent.
+pub fn adapt<ParentState, ChildState, V, F>(
+    map: F,
+    child: V,
+) -> Adapt<ParentState, ChildState, V, F>
+where
+    F: Fn(&mut ParentState) -> &mut ChildState,
+{
+    Adapt {
+        map,
+        child,
+        phantom: PhantomData,
+    }
+}
+
+pub struct Adapt<ParentState, ChildState, V, F> {
+    map: F,
+    child: V,
+    phantom: PhantomData<fn() -> (ParentState, ChildState)>,
+}
+
+impl<ParentState, ChildState, Action, V, F> MasonryView<ParentState, Action>
+    for Adapt<ParentState, ChildState, V, F>
+where
+    ParentState: 'static,
+    ChildState: 'static,
+    V: MasonryView<ChildState, Action>,
+    F: Fn(&mut ParentState) -> &mut ChildState + Send + Sync + 'static,
+{
+    type Element = V::Element;
+    type ViewState = V::ViewState;
+
+    fn build(&self, cx: &mut ViewCx) -> (WidgetPod<Self::Element>, Self::ViewState) {
+        self.child.build(cx)
+    }
+
+    fn rebuild(
+        &self,
+        view_state: &mut Self::ViewState,
+        cx: &mut ViewCx,
+        prev: &Self,
+        element: WidgetMut<Self::Element>,
+    ) {
+        self.child.rebuild(view_state, cx, &prev.child, element);
+    }
+
+    fn message(
+        &self,
+        view_state: &mut Self::ViewState,
+        id_path: &[ViewId],
+        message: Box<dyn Any>,
+        app_state: &mut ParentState,
+    ) -> MessageResult<Action> {
+        let child_state = (self.map)(app_state);
+        self.child
+            .message(view_state, id_path, message, child_state)
+    }
+}
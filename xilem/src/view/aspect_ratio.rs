@@ -0,0 +1,76 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+use masonry::{widget::WidgetMut, WidgetPod};
+
+use crate::{MasonryView, MessageResult, ViewCx, ViewId};
+
+/// Keep `child` at the given width-to-height ratio, e.g. `16. / 9.`.
+///
+/// The view takes the largest size with that ratio which fits in the space given by its
+/// parent. If the parent's constraints don't allow that ratio, they take precedence.
+pub fn aspect_ratio<V>(ratio: f64, child: V) -> AspectRatio<V> {
+    AspectRatio { ratio, child }
+}
+
+/// Adds [`with_aspect_ratio`](Self::with_aspect_ratio) to views.
+pub trait AspectRatioExt: Sized {
+    /// Keep this view at the ratio of `width` to `height`, e.g. `(16., 9.)`.
+    ///
+    /// See [`aspect_ratio`].
+    fn with_aspect_ratio(self, width: f64, height: f64) -> AspectRatio<Self> {
+        aspect_ratio(width / height, self)
+    }
+}
+
+impl<V: Send + Sync + 'static> AspectRatioExt for V {}
+
+pub struct AspectRatio<V> {
+    ratio: f64,
+    child: V,
+}
+
+impl<V, State, Action> MasonryView<State, Action> for AspectRatio<V>
+where
+    V: MasonryView<State, Action>,
+{
+    type Element = masonry::widget::SizedBox;
+    type ViewState = V::ViewState;
+
+    fn build(&self, cx: &mut ViewCx) -> (WidgetPod<Self::Element>, Self::ViewState) {
+        let (child, child_state) = self.child.build(cx);
+        let widget =
+            masonry::widget::SizedBox::from_child_pod(child.boxed()).aspect_ratio(self.ratio);
+        (WidgetPod::new(widget), child_state)
+    }
+
+    fn rebuild(
+        &self,
+        view_state: &mut Self::ViewState,
+        cx: &mut ViewCx,
+        prev: &Self,
+        mut element: WidgetMut<Self::Element>,
+    ) {
+        if prev.ratio != self.ratio {
+            element.set_aspect_ratio(self.ratio);
+            cx.mark_changed();
+        }
+        let mut child = element
+            .child_mut()
+            .expect("AspectRatio element should have a child");
+        let child = child
+            .try_downcast::<V::Element>()
+            .expect("AspectRatio child should have the child view's element type");
+        self.child.rebuild(view_state, cx, &prev.child, child);
+    }
+
+    fn message(
+        &self,
+        view_state: &mut Self::ViewState,
+        id_path: &[ViewId],
+        message: Box<dyn std::any::Any>,
+        app_state: &mut State,
+    ) -> MessageResult<Action> {
+        self.child.message(view_state, id_path, message, app_state)
+    }
+}
@@ -0,0 +1,77 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+use masonry::widget::{BadgeContent, BadgeStyle, WidgetMut};
+use masonry::WidgetPod;
+
+use crate::{MasonryView, MessageResult, ViewCx, ViewId};
+
+/// Show `content`, such as a notification count, in a small badge on the top right
+/// corner of `child`.
+///
+/// The badge is drawn above the rest of the window, and doesn't change the size of `child`.
+pub fn badge<V>(child: V, content: BadgeContent) -> Badge<V> {
+    Badge {
+        child,
+        content,
+        style: BadgeStyle::default(),
+    }
+}
+
+pub struct Badge<V> {
+    child: V,
+    content: BadgeContent,
+    style: BadgeStyle,
+}
+
+impl<V> Badge<V> {
+    /// Set the color and size of the badge.
+    pub fn style(mut self, style: BadgeStyle) -> Self {
+        self.style = style;
+        self
+    }
+}
+
+impl<V, State, Action> MasonryView<State, Action> for Badge<V>
+where
+    V: MasonryView<State, Action>,
+{
+    type Element = masonry::widget::Badge<V::Element>;
+    type ViewState = V::ViewState;
+
+    fn build(&self, cx: &mut ViewCx) -> (WidgetPod<Self::Element>, Self::ViewState) {
+        let (child, child_state) = self.child.build(cx);
+        let widget = masonry::widget::Badge::from_child_pod(child, self.content.clone())
+            .with_style(self.style.clone());
+        (WidgetPod::new(widget), child_state)
+    }
+
+    fn rebuild(
+        &self,
+        view_state: &mut Self::ViewState,
+        cx: &mut ViewCx,
+        prev: &Self,
+        mut element: WidgetMut<Self::Element>,
+    ) {
+        if prev.content != self.content {
+            element.set_content(self.content.clone());
+            cx.mark_changed();
+        }
+        if prev.style != self.style {
+            element.set_style(self.style.clone());
+            cx.mark_changed();
+        }
+        self.child
+            .rebuild(view_state, cx, &prev.child, element.child_mut());
+    }
+
+    fn message(
+        &self,
+        view_state: &mut Self::ViewState,
+        id_path: &[ViewId],
+        message: Box<dyn std::any::Any>,
+        app_state: &mut State,
+    ) -> MessageResult<Action> {
+        self.child.message(view_state, id_path, message, app_state)
+    }
+}
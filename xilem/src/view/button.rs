@@ -1,62 +1,182 @@
 // Copyright 2024 the Xilem Authors
 // SPDX-License-Identifier: Apache-2.0
 
-use masonry::{widget::WidgetMut, ArcStr, WidgetPod};
+use masonry::{kurbo::RoundedRectRadii, theme, widget::WidgetMut, ArcStr, BoxShadow, WidgetPod};
 
-use crate::{MasonryView, MessageResult, ViewCx, ViewId};
+use crate::view::{label, Label};
+use crate::{Color, MasonryView, MessageResult, ViewCx, ViewId};
 
-pub fn button<F, State, Action>(label: impl Into<ArcStr>, callback: F) -> Button<F>
+/// A button with a text label, which calls `callback` when clicked.
+///
+/// See [`button_content`] for buttons showing other views, such as icons.
+pub fn button<F, State, Action>(text: impl Into<ArcStr>, callback: F) -> Button<Label, F>
+where
+    F: Fn(&mut State) -> Action + Send + 'static,
+{
+    button_content(label(text), callback)
+}
+
+/// A button showing `content`, such as an [`icon`](crate::view::icon) or a
+/// [`flex`](crate::view::flex) row with an icon and a label, which calls `callback`
+/// when clicked.
+///
+/// Clicks anywhere on the button call `callback`, including on the padding around
+/// `content`.
+pub fn button_content<V, F, State, Action>(content: V, callback: F) -> Button<V, F>
 where
     F: Fn(&mut State) -> Action + Send + 'static,
 {
     Button {
-        label: label.into(),
+        content,
         callback,
+        color: None,
+        hover_color: None,
+        active_color: None,
+        corner_radius: RoundedRectRadii::from_single_radius(theme::BUTTON_BORDER_RADIUS),
+        shadows: Vec::new(),
+        disabled: false,
     }
 }
 
-pub struct Button<F> {
-    label: ArcStr,
+pub struct Button<V, F> {
+    content: V,
     callback: F,
+    color: Option<Color>,
+    hover_color: Option<Color>,
+    active_color: Option<Color>,
+    corner_radius: RoundedRectRadii,
+    shadows: Vec<BoxShadow>,
+    disabled: bool,
+}
+
+impl<V, F> Button<V, F> {
+    /// Set the background color.
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Set the background color while the pointer is over the button.
+    pub fn with_hover_color(mut self, color: Color) -> Self {
+        self.hover_color = Some(color);
+        self
+    }
+
+    /// Set the background color while the button is pressed.
+    pub fn with_active_color(mut self, color: Color) -> Self {
+        self.active_color = Some(color);
+        self
+    }
+
+    /// Set the radius of the button's corners.
+    ///
+    /// This can be a single radius for all corners, or a different radius for each corner.
+    pub fn corner_radius(mut self, radius: impl Into<RoundedRectRadii>) -> Self {
+        self.corner_radius = radius.into();
+        self
+    }
+
+    /// Add a shadow behind the button.
+    ///
+    /// This can be called several times to stack shadows.
+    pub fn with_shadow(mut self, shadow: BoxShadow) -> Self {
+        self.shadows.push(shadow);
+        self
+    }
+
+    /// Set whether the button is disabled, such as `.disabled(!form.is_valid())` for a
+    /// submit button.
+    ///
+    /// A disabled button is greyed out, and clicking it doesn't call the callback.
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
 }
 
-impl<F, State, Action> MasonryView<State, Action> for Button<F>
+impl<V, F, State, Action> MasonryView<State, Action> for Button<V, F>
 where
+    V: MasonryView<State, Action>,
     F: Fn(&mut State) -> Action + Send + Sync + 'static,
 {
-    type Element = masonry::widget::Button;
-    type ViewState = ();
+    type Element = masonry::widget::Button<V::Element>;
+    type ViewState = V::ViewState;
 
     fn build(&self, cx: &mut ViewCx) -> (WidgetPod<Self::Element>, Self::ViewState) {
-        cx.with_leaf_action_widget(|_| {
-            WidgetPod::new(masonry::widget::Button::new(self.label.clone()))
-        })
+        let mut content_state = None;
+        let element = cx.with_action_widget(|cx| {
+            let (content, state) =
+                cx.with_id(ViewId::for_type::<V>(0), |cx| self.content.build(cx));
+            content_state = Some(state);
+            let mut button = masonry::widget::Button::from_child_pod(content)
+                .with_corner_radius(self.corner_radius);
+            for shadow in &self.shadows {
+                button = button.with_shadow(*shadow);
+            }
+            if let Some(color) = self.color {
+                button = button.with_color(color);
+            }
+            if let Some(color) = self.hover_color {
+                button = button.with_hover_color(color);
+            }
+            if let Some(color) = self.active_color {
+                button = button.with_active_color(color);
+            }
+            WidgetPod::new(button).with_disabled(self.disabled)
+        });
+        (element, content_state.unwrap())
     }
 
     fn rebuild(
         &self,
-        _view_state: &mut Self::ViewState,
+        view_state: &mut Self::ViewState,
         cx: &mut ViewCx,
         prev: &Self,
         mut element: WidgetMut<Self::Element>,
     ) {
-        if prev.label != self.label {
-            element.set_text(self.label.clone());
+        if prev.color != self.color {
+            element.set_color(self.color);
+            cx.mark_changed();
+        }
+        if prev.hover_color != self.hover_color {
+            element.set_hover_color(self.hover_color);
+            cx.mark_changed();
+        }
+        if prev.active_color != self.active_color {
+            element.set_active_color(self.active_color);
             cx.mark_changed();
         }
+        if prev.corner_radius != self.corner_radius {
+            element.set_corner_radius(self.corner_radius);
+            cx.mark_changed();
+        }
+        if prev.shadows != self.shadows {
+            element.set_shadows(self.shadows.clone());
+            cx.mark_changed();
+        }
+        if prev.disabled != self.disabled {
+            element.ctx.set_disabled(self.disabled);
+            cx.mark_changed();
+        }
+        cx.with_id(ViewId::for_type::<V>(0), |cx| {
+            self.content
+                .rebuild(view_state, cx, &prev.content, element.child_mut());
+        });
     }
 
     fn message(
         &self,
-        _view_state: &mut Self::ViewState,
+        view_state: &mut Self::ViewState,
         id_path: &[ViewId],
         message: Box<dyn std::any::Any>,
         app_state: &mut State,
     ) -> crate::MessageResult<Action> {
-        debug_assert!(
-            id_path.is_empty(),
-            "id path should be empty in Button::message"
-        );
+        if let Some((first, rest)) = id_path.split_first() {
+            if first.routing_id() != 0 {
+                return MessageResult::Stale(message);
+            }
+            return self.content.message(view_state, rest, message, app_state);
+        }
         match message.downcast::<masonry::Action>() {
             Ok(action) => {
                 if let masonry::Action::ButtonPressed = *action {
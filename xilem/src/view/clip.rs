@@ -0,0 +1,68 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+use masonry::{widget::WidgetMut, ClipShape, WidgetPod};
+
+use crate::{MasonryView, MessageResult, ViewCx, ViewId};
+
+/// Clip the painted content of `child` and its descendants to `shape`.
+///
+/// This can be used for round avatars with [`ClipShape::Ellipse`], or other
+/// non-rectangular content with [`ClipShape::Custom`].
+///
+/// See also [`ClipExt::with_clip`].
+pub fn clip<V>(child: V, shape: ClipShape) -> Clip<V> {
+    Clip { child, shape }
+}
+
+/// Adds [`with_clip`](Self::with_clip) to views.
+pub trait ClipExt: Sized {
+    /// Clip the painted content of this view and its descendants to `shape`.
+    fn with_clip(self, shape: ClipShape) -> Clip<Self> {
+        clip(self, shape)
+    }
+}
+
+impl<V: Send + Sync + 'static> ClipExt for V {}
+
+pub struct Clip<V> {
+    child: V,
+    shape: ClipShape,
+}
+
+impl<V, State, Action> MasonryView<State, Action> for Clip<V>
+where
+    V: MasonryView<State, Action>,
+{
+    type Element = V::Element;
+    type ViewState = V::ViewState;
+
+    fn build(&self, cx: &mut ViewCx) -> (WidgetPod<Self::Element>, Self::ViewState) {
+        let (element, child_state) = self.child.build(cx);
+        (element.with_clip(Some(self.shape.clone())), child_state)
+    }
+
+    fn rebuild(
+        &self,
+        view_state: &mut Self::ViewState,
+        cx: &mut ViewCx,
+        prev: &Self,
+        mut element: WidgetMut<Self::Element>,
+    ) {
+        if prev.shape != self.shape {
+            element.ctx.set_clip(Some(self.shape.clone()));
+            cx.mark_changed();
+        }
+        self.child.rebuild(view_state, cx, &prev.child, element);
+    }
+
+    fn message(
+        &self,
+        view_state: &mut Self::ViewState,
+        id_path: &[ViewId],
+        message: Box<dyn std::any::Any>,
+        app_state: &mut State,
+    ) -> MessageResult<Action> {
+        self.child.message(view_state, id_path, message, app_state)
+    }
+}
@@ -0,0 +1,139 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+use masonry::{widget::WidgetMut, Color, WidgetPod};
+
+use crate::{MasonryView, MessageResult, ViewCx, ViewId};
+
+/// A widget for picking a color, with a square for the saturation and value, a hue bar,
+/// and a text box for the hex code of the color.
+///
+/// `on_change` is called with the new color whenever the user changes it.
+pub fn color_picker<F, State, Action>(color: Color, on_change: F) -> ColorPicker<F>
+where
+    F: Fn(&mut State, Color) -> Action + Send + 'static,
+{
+    ColorPicker {
+        color,
+        alpha: false,
+        on_change,
+    }
+}
+
+pub struct ColorPicker<F> {
+    color: Color,
+    alpha: bool,
+    on_change: F,
+}
+
+impl<F> ColorPicker<F> {
+    /// Show a bar for the alpha of the color.
+    ///
+    /// Without it, the alpha of `color` is kept as it is.
+    pub fn alpha(mut self, alpha: bool) -> Self {
+        self.alpha = alpha;
+        self
+    }
+}
+
+impl<F, State, Action> MasonryView<State, Action> for ColorPicker<F>
+where
+    F: Fn(&mut State, Color) -> Action + Send + Sync + 'static,
+{
+    type Element = masonry::widget::ColorPicker;
+    type ViewState = ();
+
+    fn build(&self, cx: &mut ViewCx) -> (WidgetPod<Self::Element>, Self::ViewState) {
+        cx.with_leaf_action_widget(|_| {
+            WidgetPod::new(masonry::widget::ColorPicker::new(self.color).with_alpha(self.alpha))
+        })
+    }
+
+    fn rebuild(
+        &self,
+        _view_state: &mut Self::ViewState,
+        cx: &mut ViewCx,
+        prev: &Self,
+        mut element: WidgetMut<Self::Element>,
+    ) {
+        if prev.color != self.color {
+            element.set_color(self.color);
+            cx.mark_changed();
+        }
+        if prev.alpha != self.alpha {
+            element.set_alpha(self.alpha);
+            cx.mark_changed();
+        }
+    }
+
+    fn message(
+        &self,
+        _view_state: &mut Self::ViewState,
+        id_path: &[ViewId],
+        message: Box<dyn std::any::Any>,
+        app_state: &mut State,
+    ) -> MessageResult<Action> {
+        debug_assert!(
+            id_path.is_empty(),
+            "id path should be empty in ColorPicker::message"
+        );
+        match message.downcast::<masonry::Action>() {
+            Ok(action) => {
+                if let masonry::Action::ColorChanged(color) = *action {
+                    MessageResult::Action((self.on_change)(app_state, color))
+                } else {
+                    tracing::error!("Wrong action type in ColorPicker::message: {action:?}");
+                    MessageResult::Stale(action)
+                }
+            }
+            Err(message) => {
+                tracing::error!("Wrong message type in ColorPicker::message");
+                MessageResult::Stale(message)
+            }
+        }
+    }
+}
+
+/// A rectangle filled with `color`. Clicking it copies the hex code of the color to the
+/// clipboard.
+pub fn color_swatch(color: Color) -> ColorSwatch {
+    ColorSwatch { color }
+}
+
+pub struct ColorSwatch {
+    color: Color,
+}
+
+impl<State, Action> MasonryView<State, Action> for ColorSwatch {
+    type Element = masonry::widget::ColorSwatch;
+    type ViewState = ();
+
+    fn build(&self, _cx: &mut ViewCx) -> (WidgetPod<Self::Element>, Self::ViewState) {
+        let widget_pod = WidgetPod::new(masonry::widget::ColorSwatch::new(self.color));
+        (widget_pod, ())
+    }
+
+    fn rebuild(
+        &self,
+        _view_state: &mut Self::ViewState,
+        cx: &mut ViewCx,
+        prev: &Self,
+        mut element: WidgetMut<Self::Element>,
+    ) {
+        if prev.color != self.color {
+            element.set_color(self.color);
+            cx.mark_changed();
+        }
+    }
+
+    fn message(
+        &self,
+        _view_state: &mut Self::ViewState,
+        _id_path: &[ViewId],
+        message: Box<dyn std::any::Any>,
+        _app_state: &mut State,
+    ) -> crate::MessageResult<Action> {
+        tracing::error!("Message arrived in ColorSwatch::message, but ColorSwatch doesn't consume any messages, this is a bug");
+        MessageResult::Stale(message)
+    }
+}
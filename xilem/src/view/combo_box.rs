@@ -0,0 +1,106 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+use masonry::{widget::WidgetMut, ArcStr, WidgetPod};
+
+use crate::{MasonryView, MessageResult, ViewCx, ViewId};
+
+/// A text box showing `value`, which suggests the `options` starting with the typed text.
+///
+/// `on_change` is called with the new text when the user types or picks a suggestion.
+pub fn combo_box<F, State, Action>(value: &str, options: &[String], on_change: F) -> ComboBox<F>
+where
+    F: Fn(&mut State, String) -> Action + Send + 'static,
+{
+    ComboBox {
+        value: value.to_string(),
+        options: options
+            .iter()
+            .map(|option| option.as_str().into())
+            .collect(),
+        max_suggestions: 8,
+        on_change,
+    }
+}
+
+pub struct ComboBox<F> {
+    value: String,
+    options: Vec<ArcStr>,
+    max_suggestions: usize,
+    on_change: F,
+}
+
+impl<F> ComboBox<F> {
+    /// Set how many matching options are shown at most, which defaults to 8.
+    pub fn max_suggestions(mut self, max_suggestions: usize) -> Self {
+        self.max_suggestions = max_suggestions;
+        self
+    }
+}
+
+impl<F, State, Action> MasonryView<State, Action> for ComboBox<F>
+where
+    F: Fn(&mut State, String) -> Action + Send + Sync + 'static,
+{
+    type Element = masonry::widget::ComboBox;
+    type ViewState = ();
+
+    fn build(&self, cx: &mut ViewCx) -> (WidgetPod<Self::Element>, Self::ViewState) {
+        cx.with_leaf_action_widget(|_| {
+            WidgetPod::new(
+                masonry::widget::ComboBox::new(self.value.clone(), self.options.iter().cloned())
+                    .with_max_suggestions(self.max_suggestions),
+            )
+        })
+    }
+
+    fn rebuild(
+        &self,
+        _view_state: &mut Self::ViewState,
+        cx: &mut ViewCx,
+        prev: &Self,
+        mut element: WidgetMut<Self::Element>,
+    ) {
+        // Like in `Textbox`, we compare to the element's text rather than to the
+        // previous value, so that the user's own edits don't reset the text.
+        if element.widget.text() != self.value {
+            element.set_text(self.value.clone());
+            cx.mark_changed();
+        }
+        if prev.options != self.options {
+            element.set_options(self.options.iter().cloned());
+            cx.mark_changed();
+        }
+        if prev.max_suggestions != self.max_suggestions {
+            element.set_max_suggestions(self.max_suggestions);
+            cx.mark_changed();
+        }
+    }
+
+    fn message(
+        &self,
+        _view_state: &mut Self::ViewState,
+        id_path: &[ViewId],
+        message: Box<dyn std::any::Any>,
+        app_state: &mut State,
+    ) -> MessageResult<Action> {
+        debug_assert!(
+            id_path.is_empty(),
+            "id path should be empty in ComboBox::message"
+        );
+        match message.downcast::<masonry::Action>() {
+            Ok(action) => {
+                if let masonry::Action::TextChanged(text) = *action {
+                    MessageResult::Action((self.on_change)(app_state, text))
+                } else {
+                    tracing::error!("Wrong action type in ComboBox::message: {action:?}");
+                    MessageResult::Stale(action)
+                }
+            }
+            Err(message) => {
+                tracing::error!("Wrong message type in ComboBox::message");
+                MessageResult::Stale(message)
+            }
+        }
+    }
+}
@@ -0,0 +1,99 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+use masonry::{widget::WidgetMut, ArcStr, WidgetPod};
+
+use crate::{MasonryView, MessageResult, ViewCx, ViewId};
+
+/// Wrap `child` so that right-clicking it opens a popup menu with the given `items`.
+///
+/// `callback` is called with the index of the selected item.
+pub fn context_menu<V, F, State, Action>(
+    child: V,
+    items: impl IntoIterator<Item = impl Into<ArcStr>>,
+    callback: F,
+) -> ContextMenu<V, F>
+where
+    F: Fn(&mut State, usize) -> Action + Send + 'static,
+{
+    ContextMenu {
+        child,
+        items: items.into_iter().map(Into::into).collect(),
+        callback,
+    }
+}
+
+pub struct ContextMenu<V, F> {
+    child: V,
+    items: Vec<ArcStr>,
+    callback: F,
+}
+
+impl<V, F, State, Action> MasonryView<State, Action> for ContextMenu<V, F>
+where
+    V: MasonryView<State, Action>,
+    F: Fn(&mut State, usize) -> Action + Send + Sync + 'static,
+{
+    type Element = masonry::widget::ContextMenu<V::Element>;
+    type ViewState = V::ViewState;
+
+    fn build(&self, cx: &mut ViewCx) -> (WidgetPod<Self::Element>, Self::ViewState) {
+        let mut child_state = None;
+        let element = cx.with_action_widget(|cx| {
+            let (child, state) = cx.with_id(ViewId::for_type::<V>(0), |cx| self.child.build(cx));
+            child_state = Some(state);
+            WidgetPod::new(masonry::widget::ContextMenu::from_child_pod(
+                child,
+                self.items.iter().cloned(),
+            ))
+        });
+        (element, child_state.unwrap())
+    }
+
+    fn rebuild(
+        &self,
+        view_state: &mut Self::ViewState,
+        cx: &mut ViewCx,
+        prev: &Self,
+        mut element: WidgetMut<Self::Element>,
+    ) {
+        if prev.items != self.items {
+            element.set_items(self.items.iter().cloned());
+            cx.mark_changed();
+        }
+        cx.with_id(ViewId::for_type::<V>(0), |cx| {
+            self.child
+                .rebuild(view_state, cx, &prev.child, element.child_mut());
+        });
+    }
+
+    fn message(
+        &self,
+        view_state: &mut Self::ViewState,
+        id_path: &[ViewId],
+        message: Box<dyn std::any::Any>,
+        app_state: &mut State,
+    ) -> crate::MessageResult<Action> {
+        if let Some((first, rest)) = id_path.split_first() {
+            if first.routing_id() != 0 {
+                tracing::error!("Invalid id path in ContextMenu::message");
+                return MessageResult::Stale(message);
+            }
+            return self.child.message(view_state, rest, message, app_state);
+        }
+        match message.downcast::<masonry::Action>() {
+            Ok(action) => {
+                if let masonry::Action::ContextMenuItemSelected(idx) = *action {
+                    MessageResult::Action((self.callback)(app_state, idx))
+                } else {
+                    tracing::error!("Wrong action type in ContextMenu::message: {action:?}");
+                    MessageResult::Stale(action)
+                }
+            }
+            Err(message) => {
+                tracing::error!("Wrong message type in ContextMenu::message");
+                MessageResult::Stale(message)
+            }
+        }
+    }
+}
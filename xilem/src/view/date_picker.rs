@@ -0,0 +1,190 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+use std::ops::RangeInclusive;
+
+use masonry::chrono::{NaiveDate, Weekday};
+use masonry::{widget::WidgetMut, WidgetPod};
+
+use crate::{MasonryView, MessageResult, ViewCx, ViewId};
+
+/// A calendar for picking a date, showing the month of `date` with arrows to show the
+/// other months.
+///
+/// `on_change` is called with the new date whenever the user picks one.
+pub fn date_picker<F, State, Action>(date: Option<NaiveDate>, on_change: F) -> DatePicker<F>
+where
+    F: Fn(&mut State, NaiveDate) -> Action + Send + 'static,
+{
+    DatePicker {
+        date,
+        week_start: Weekday::Mon,
+        on_change,
+    }
+}
+
+pub struct DatePicker<F> {
+    date: Option<NaiveDate>,
+    week_start: Weekday,
+    on_change: F,
+}
+
+impl<F> DatePicker<F> {
+    /// Set the first day of the week, which is Monday by default.
+    pub fn week_start(mut self, week_start: Weekday) -> Self {
+        self.week_start = week_start;
+        self
+    }
+}
+
+impl<F, State, Action> MasonryView<State, Action> for DatePicker<F>
+where
+    F: Fn(&mut State, NaiveDate) -> Action + Send + Sync + 'static,
+{
+    type Element = masonry::widget::DatePicker;
+    type ViewState = ();
+
+    fn build(&self, cx: &mut ViewCx) -> (WidgetPod<Self::Element>, Self::ViewState) {
+        cx.with_leaf_action_widget(|_| {
+            WidgetPod::new(
+                masonry::widget::DatePicker::new(self.date).with_week_start(self.week_start),
+            )
+        })
+    }
+
+    fn rebuild(
+        &self,
+        _view_state: &mut Self::ViewState,
+        cx: &mut ViewCx,
+        prev: &Self,
+        mut element: WidgetMut<Self::Element>,
+    ) {
+        if prev.date != self.date {
+            element.set_date(self.date);
+            cx.mark_changed();
+        }
+        if prev.week_start != self.week_start {
+            element.set_week_start(self.week_start);
+            cx.mark_changed();
+        }
+    }
+
+    fn message(
+        &self,
+        _view_state: &mut Self::ViewState,
+        id_path: &[ViewId],
+        message: Box<dyn std::any::Any>,
+        app_state: &mut State,
+    ) -> MessageResult<Action> {
+        debug_assert!(
+            id_path.is_empty(),
+            "id path should be empty in DatePicker::message"
+        );
+        match message.downcast::<masonry::Action>() {
+            Ok(action) => {
+                if let masonry::Action::DateSelected(date) = *action {
+                    MessageResult::Action((self.on_change)(app_state, date))
+                } else {
+                    tracing::error!("Wrong action type in DatePicker::message: {action:?}");
+                    MessageResult::Stale(action)
+                }
+            }
+            Err(message) => {
+                tracing::error!("Wrong message type in DatePicker::message");
+                MessageResult::Stale(message)
+            }
+        }
+    }
+}
+
+/// A calendar for picking a range of dates, by picking the days at either end.
+///
+/// `on_change` is called with the new range once the user has picked both ends.
+pub fn date_range_picker<F, State, Action>(
+    range: Option<RangeInclusive<NaiveDate>>,
+    on_change: F,
+) -> DateRangePicker<F>
+where
+    F: Fn(&mut State, RangeInclusive<NaiveDate>) -> Action + Send + 'static,
+{
+    DateRangePicker {
+        range,
+        week_start: Weekday::Mon,
+        on_change,
+    }
+}
+
+pub struct DateRangePicker<F> {
+    range: Option<RangeInclusive<NaiveDate>>,
+    week_start: Weekday,
+    on_change: F,
+}
+
+impl<F> DateRangePicker<F> {
+    /// Set the first day of the week, which is Monday by default.
+    pub fn week_start(mut self, week_start: Weekday) -> Self {
+        self.week_start = week_start;
+        self
+    }
+}
+
+impl<F, State, Action> MasonryView<State, Action> for DateRangePicker<F>
+where
+    F: Fn(&mut State, RangeInclusive<NaiveDate>) -> Action + Send + Sync + 'static,
+{
+    type Element = masonry::widget::DatePicker;
+    type ViewState = ();
+
+    fn build(&self, cx: &mut ViewCx) -> (WidgetPod<Self::Element>, Self::ViewState) {
+        cx.with_leaf_action_widget(|_| {
+            WidgetPod::new(
+                masonry::widget::DatePicker::new_range(self.range.clone())
+                    .with_week_start(self.week_start),
+            )
+        })
+    }
+
+    fn rebuild(
+        &self,
+        _view_state: &mut Self::ViewState,
+        cx: &mut ViewCx,
+        prev: &Self,
+        mut element: WidgetMut<Self::Element>,
+    ) {
+        if prev.range != self.range {
+            element.set_range(self.range.clone());
+            cx.mark_changed();
+        }
+        if prev.week_start != self.week_start {
+            element.set_week_start(self.week_start);
+            cx.mark_changed();
+        }
+    }
+
+    fn message(
+        &self,
+        _view_state: &mut Self::ViewState,
+        id_path: &[ViewId],
+        message: Box<dyn std::any::Any>,
+        app_state: &mut State,
+    ) -> MessageResult<Action> {
+        debug_assert!(
+            id_path.is_empty(),
+            "id path should be empty in DateRangePicker::message"
+        );
+        match message.downcast::<masonry::Action>() {
+            Ok(action) => {
+                if let masonry::Action::DateRangeSelected(range) = *action {
+                    MessageResult::Action((self.on_change)(app_state, range))
+                } else {
+                    tracing::error!("Wrong action type in DateRangePicker::message: {action:?}");
+                    MessageResult::Stale(action)
+                }
+            }
+            Err(message) => {
+                tracing::error!("Wrong message type in DateRangePicker::message");
+                MessageResult::Stale(message)
+            }
+        }
+    }
+}
@@ -0,0 +1,241 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+use std::any::Any;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use masonry::{ext_event::ExtEventSink, widget::WidgetMut, WidgetId, WidgetPod};
+
+use crate::view::TaskHandle;
+use crate::{MasonryView, MessageResult, ViewCx, ViewId};
+
+/// Hold back the messages of `child` until it has been quiet for `delay`, and then
+/// only handle the latest one.
+///
+/// This is useful to avoid doing expensive work on every keystroke, e.g. to search
+/// as the user types:
+///
+/// ```ignore
+/// debounce(
+///     textbox(state.query.clone(), |state: &mut AppState, query| {
+///         state.query = query;
+///         state.results = search(&state.query);
+///     }),
+///     Duration::from_millis(300),
+/// )
+/// ```
+///
+/// Earlier messages are dropped, so the child's callbacks should only depend on the
+/// latest one, as is the case for a textbox, which reports its whole text. If the view
+/// is removed from the tree while a message is held back, the message is dropped.
+pub fn debounce<V>(child: V, delay: Duration) -> Debounce<V> {
+    Debounce { child, delay }
+}
+
+pub struct Debounce<V> {
+    child: V,
+    delay: Duration,
+}
+
+pub struct DebounceState<ChildState> {
+    child_state: ChildState,
+    sink: ExtEventSink,
+    target: WidgetId,
+    /// The latest message of the child, with its id path, while it's held back.
+    pending: Option<(Vec<ViewId>, Box<dyn Any>)>,
+    /// Incremented for each message, to recognise the delays which were restarted.
+    generation: u64,
+    /// When the held-back message is due, with its generation, shared with the timer.
+    deadline: Arc<Mutex<Option<(Instant, u64)>>>,
+    /// The thread waiting for the deadline, started with the first message.
+    timer: Option<TaskHandle>,
+}
+
+/// The payload of the action sent once the child has been quiet long enough.
+struct Elapsed(u64);
+
+/// Start the thread which sends [`Elapsed`] to `target` whenever `deadline` is reached.
+///
+/// The thread waits for the deadline to be set, and must be unparked after each change.
+fn spawn_timer(
+    sink: ExtEventSink,
+    target: WidgetId,
+    deadline: Arc<Mutex<Option<(Instant, u64)>>>,
+) -> TaskHandle {
+    TaskHandle::spawn(move |cancelled: &AtomicBool| {
+        // The handle unparks this thread when it is dropped, so we
+        // re-check the flag after each wake up.
+        while !cancelled.load(Ordering::Acquire) {
+            let Some((at, generation)) = *deadline.lock().unwrap() else {
+                thread::park();
+                continue;
+            };
+            let now = Instant::now();
+            if now < at {
+                thread::park_timeout(at - now);
+                continue;
+            }
+            {
+                let mut deadline = deadline.lock().unwrap();
+                // A newer message may have moved the deadline in the meantime.
+                if deadline.is_some_and(|(_, current)| current != generation) {
+                    continue;
+                }
+                *deadline = None;
+            }
+            if sink
+                .submit_action(Box::new(Elapsed(generation)), target)
+                .is_err()
+            {
+                tracing::debug!("App exited before debounce delay elapsed");
+                return;
+            }
+        }
+    })
+}
+
+impl<V, State, Action> MasonryView<State, Action> for Debounce<V>
+where
+    V: MasonryView<State, Action>,
+{
+    type Element = masonry::widget::SizedBox;
+    type ViewState = DebounceState<V::ViewState>;
+
+    fn build(&self, cx: &mut ViewCx) -> (WidgetPod<Self::Element>, Self::ViewState) {
+        let mut child_state = None;
+        let element = cx.with_action_widget(|cx| {
+            let (child, state) = cx.with_id(ViewId::for_type::<V>(0), |cx| self.child.build(cx));
+            child_state = Some(state);
+            WidgetPod::new(masonry::widget::SizedBox::from_child_pod(child.boxed()))
+        });
+        let view_state = DebounceState {
+            child_state: child_state.unwrap(),
+            sink: cx.ext_event_sink().clone(),
+            target: element.id(),
+            pending: None,
+            generation: 0,
+            deadline: Arc::new(Mutex::new(None)),
+            timer: None,
+        };
+        (element, view_state)
+    }
+
+    fn rebuild(
+        &self,
+        view_state: &mut Self::ViewState,
+        cx: &mut ViewCx,
+        prev: &Self,
+        mut element: WidgetMut<Self::Element>,
+    ) {
+        let mut child = element
+            .child_mut()
+            .expect("Debounce element should have a child");
+        let child = child
+            .try_downcast::<V::Element>()
+            .expect("Debounce child should have the child view's element type");
+        cx.with_id(ViewId::for_type::<V>(0), |cx| {
+            self.child
+                .rebuild(&mut view_state.child_state, cx, &prev.child, child);
+        });
+    }
+
+    fn message(
+        &self,
+        view_state: &mut Self::ViewState,
+        id_path: &[ViewId],
+        message: Box<dyn Any>,
+        app_state: &mut State,
+    ) -> MessageResult<Action> {
+        if let Some((first, rest)) = id_path.split_first() {
+            if first.routing_id() != 0 {
+                tracing::error!("Invalid id path in Debounce::message");
+                return MessageResult::Stale(message);
+            }
+            view_state.pending = Some((rest.to_vec(), message));
+            view_state.generation += 1;
+            // Moving the deadline restarts the delay.
+            *view_state.deadline.lock().unwrap() =
+                Some((Instant::now() + self.delay, view_state.generation));
+            let timer = view_state.timer.get_or_insert_with(|| {
+                spawn_timer(
+                    view_state.sink.clone(),
+                    view_state.target,
+                    view_state.deadline.clone(),
+                )
+            });
+            timer.unpark();
+            return MessageResult::Nop;
+        }
+        match message.downcast::<masonry::Action>() {
+            Ok(action) => {
+                let generation = match &*action {
+                    masonry::Action::Other(payload) => payload.downcast_ref::<Elapsed>(),
+                    _ => None,
+                };
+                match generation {
+                    Some(Elapsed(generation)) if *generation == view_state.generation => {
+                        let Some((path, message)) = view_state.pending.take() else {
+                            return MessageResult::Nop;
+                        };
+                        self.child
+                            .message(&mut view_state.child_state, &path, message, app_state)
+                    }
+                    // A newer message restarted the delay.
+                    Some(_) => MessageResult::Nop,
+                    None => {
+                        tracing::error!("Wrong action type in Debounce::message: {action:?}");
+                        MessageResult::Stale(action)
+                    }
+                }
+            }
+            Err(message) => {
+                tracing::error!("Wrong message type in Debounce::message");
+                MessageResult::Stale(message)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::view::button;
+
+    #[test]
+    fn only_latest_message_is_handled() {
+        let view = debounce(
+            button("press", |count: &mut i32| *count += 1),
+            Duration::from_secs(3600),
+        );
+        let mut cx = ViewCx::new();
+        let (_pod, mut view_state) = view.build(&mut cx);
+
+        // The debounce widget's path is a prefix of the button's.
+        let mut id_paths: Vec<_> = cx.widget_map.values().cloned().collect();
+        id_paths.sort_by_key(Vec::len);
+        let [own_path, button_path] = &id_paths[..] else {
+            panic!("expected two widgets, got {id_paths:?}");
+        };
+
+        let mut count = 0;
+        for _ in 0..3 {
+            let press = Box::new(masonry::Action::ButtonPressed);
+            let result = view.message(&mut view_state, button_path, press, &mut count);
+            assert!(matches!(result, MessageResult::Nop));
+        }
+        assert_eq!(count, 0);
+
+        let elapsed = |generation| Box::new(masonry::Action::Other(Arc::new(Elapsed(generation))));
+        // The delays restarted by later presses are ignored.
+        let result = view.message(&mut view_state, own_path, elapsed(2), &mut count);
+        assert!(matches!(result, MessageResult::Nop));
+        assert_eq!(count, 0);
+
+        let result = view.message(&mut view_state, own_path, elapsed(3), &mut count);
+        assert!(matches!(result, MessageResult::Action(())));
+        assert_eq!(count, 1);
+    }
+}
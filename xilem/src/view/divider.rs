@@ -0,0 +1,101 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+use masonry::widget::Axis;
+use masonry::{widget::WidgetMut, WidgetPod};
+
+use crate::{Color, MasonryView, MessageResult, ViewCx, ViewId};
+
+/// A horizontal line, which spans the width of the [`flex`](crate::view::flex) column
+/// it is in.
+pub fn divider() -> Divider {
+    Divider::new(Axis::Horizontal)
+}
+
+/// A vertical line, which spans the height of the [`flex`](crate::view::flex) row it
+/// is in.
+pub fn vertical_divider() -> Divider {
+    Divider::new(Axis::Vertical)
+}
+
+pub struct Divider {
+    axis: Axis,
+    thickness: f64,
+    color: Color,
+    inset: f64,
+}
+
+impl Divider {
+    fn new(axis: Axis) -> Self {
+        Self {
+            axis,
+            thickness: 1.0,
+            color: masonry::theme::DIVIDER_COLOR,
+            inset: 0.0,
+        }
+    }
+
+    /// Set the thickness of the line, which is 1 by default.
+    pub fn thickness(mut self, thickness: f64) -> Self {
+        self.thickness = thickness;
+        self
+    }
+
+    /// Set the color of the line.
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Set how far the line stops short of both of its ends.
+    pub fn inset(mut self, inset: f64) -> Self {
+        self.inset = inset;
+        self
+    }
+}
+
+impl<State, Action> MasonryView<State, Action> for Divider {
+    type Element = masonry::widget::Divider;
+    type ViewState = ();
+
+    fn build(&self, _cx: &mut ViewCx) -> (WidgetPod<Self::Element>, Self::ViewState) {
+        let widget = masonry::widget::Divider::new(self.axis)
+            .with_thickness(self.thickness)
+            .with_color(self.color)
+            .with_inset(self.inset);
+        (WidgetPod::new(widget), ())
+    }
+
+    fn rebuild(
+        &self,
+        _view_state: &mut Self::ViewState,
+        cx: &mut ViewCx,
+        prev: &Self,
+        mut element: WidgetMut<Self::Element>,
+    ) {
+        // The axis is fixed by the function which made the view, so it can't change.
+        if prev.thickness != self.thickness {
+            element.set_thickness(self.thickness);
+            cx.mark_changed();
+        }
+        if prev.color != self.color {
+            element.set_color(self.color);
+            cx.mark_changed();
+        }
+        if prev.inset != self.inset {
+            element.set_inset(self.inset);
+            cx.mark_changed();
+        }
+    }
+
+    fn message(
+        &self,
+        _view_state: &mut Self::ViewState,
+        _id_path: &[ViewId],
+        message: Box<dyn std::any::Any>,
+        _app_state: &mut State,
+    ) -> MessageResult<Action> {
+        tracing::error!("Message arrived in Divider::message, but Divider doesn't consume any messages, this is a bug");
+        MessageResult::Stale(message)
+    }
+}
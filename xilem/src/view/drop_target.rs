@@ -0,0 +1,110 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::PathBuf;
+
+use masonry::{widget::WidgetMut, WidgetPod};
+
+use crate::{Color, MasonryView, MessageResult, ViewCx, ViewId};
+
+/// Wrap `child` so that files dragged from other applications can be dropped on it.
+///
+/// `on_drop` is called with the paths of the dropped files. While files are dragged
+/// over `child`, it's highlighted.
+pub fn drop_target<V, F, State, Action>(child: V, on_drop: F) -> DropTarget<V, F>
+where
+    F: Fn(&mut State, Vec<PathBuf>) -> Action + Send + 'static,
+{
+    DropTarget {
+        child,
+        on_drop,
+        highlight_color: None,
+    }
+}
+
+pub struct DropTarget<V, F> {
+    child: V,
+    on_drop: F,
+    highlight_color: Option<Color>,
+}
+
+impl<V, F> DropTarget<V, F> {
+    /// Set the color used to highlight the child while files are dragged over it.
+    pub fn highlight_color(mut self, color: Color) -> Self {
+        self.highlight_color = Some(color);
+        self
+    }
+}
+
+impl<V, F, State, Action> MasonryView<State, Action> for DropTarget<V, F>
+where
+    V: MasonryView<State, Action>,
+    F: Fn(&mut State, Vec<PathBuf>) -> Action + Send + Sync + 'static,
+{
+    type Element = masonry::widget::DropTarget<V::Element>;
+    type ViewState = V::ViewState;
+
+    fn build(&self, cx: &mut ViewCx) -> (WidgetPod<Self::Element>, Self::ViewState) {
+        let mut child_state = None;
+        let element = cx.with_action_widget(|cx| {
+            let (child, state) = cx.with_id(ViewId::for_type::<V>(0), |cx| self.child.build(cx));
+            child_state = Some(state);
+            let mut widget = masonry::widget::DropTarget::from_child_pod(child);
+            if let Some(color) = self.highlight_color {
+                widget = widget.with_highlight_color(color);
+            }
+            WidgetPod::new(widget)
+        });
+        (element, child_state.unwrap())
+    }
+
+    fn rebuild(
+        &self,
+        view_state: &mut Self::ViewState,
+        cx: &mut ViewCx,
+        prev: &Self,
+        mut element: WidgetMut<Self::Element>,
+    ) {
+        if prev.highlight_color != self.highlight_color {
+            element.set_highlight_color(
+                self.highlight_color
+                    .unwrap_or(masonry::theme::PRIMARY_LIGHT),
+            );
+            cx.mark_changed();
+        }
+        cx.with_id(ViewId::for_type::<V>(0), |cx| {
+            self.child
+                .rebuild(view_state, cx, &prev.child, element.child_mut());
+        });
+    }
+
+    fn message(
+        &self,
+        view_state: &mut Self::ViewState,
+        id_path: &[ViewId],
+        message: Box<dyn std::any::Any>,
+        app_state: &mut State,
+    ) -> crate::MessageResult<Action> {
+        if let Some((first, rest)) = id_path.split_first() {
+            if first.routing_id() != 0 {
+                tracing::error!("Invalid id path in DropTarget::message");
+                return MessageResult::Stale(message);
+            }
+            return self.child.message(view_state, rest, message, app_state);
+        }
+        match message.downcast::<masonry::Action>() {
+            Ok(action) => {
+                if let masonry::Action::FilesDropped(paths) = *action {
+                    MessageResult::Action((self.on_drop)(app_state, paths))
+                } else {
+                    tracing::error!("Wrong action type in DropTarget::message: {action:?}");
+                    MessageResult::Stale(action)
+                }
+            }
+            Err(message) => {
+                tracing::error!("Wrong message type in DropTarget::message");
+                MessageResult::Stale(message)
+            }
+        }
+    }
+}
@@ -0,0 +1,91 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+use masonry::{widget::WidgetMut, ArcStr, WidgetPod};
+
+use crate::{MasonryView, MessageResult, ViewCx, ViewId};
+
+/// A select control showing `options`, with the option at index `selected` selected.
+///
+/// `callback` is called with the index of the option the user picked.
+pub fn dropdown<F, State, Action>(
+    options: impl IntoIterator<Item = impl Into<ArcStr>>,
+    selected: usize,
+    callback: F,
+) -> Dropdown<F>
+where
+    F: Fn(&mut State, usize) -> Action + Send + 'static,
+{
+    Dropdown {
+        options: options.into_iter().map(Into::into).collect(),
+        selected,
+        callback,
+    }
+}
+
+pub struct Dropdown<F> {
+    options: Vec<ArcStr>,
+    selected: usize,
+    callback: F,
+}
+
+impl<F, State, Action> MasonryView<State, Action> for Dropdown<F>
+where
+    F: Fn(&mut State, usize) -> Action + Send + Sync + 'static,
+{
+    type Element = masonry::widget::Dropdown;
+    type ViewState = ();
+
+    fn build(&self, cx: &mut ViewCx) -> (WidgetPod<Self::Element>, Self::ViewState) {
+        cx.with_leaf_action_widget(|_| {
+            WidgetPod::new(masonry::widget::Dropdown::new(
+                self.options.iter().cloned(),
+                self.selected,
+            ))
+        })
+    }
+
+    fn rebuild(
+        &self,
+        _view_state: &mut Self::ViewState,
+        cx: &mut ViewCx,
+        prev: &Self,
+        mut element: WidgetMut<Self::Element>,
+    ) {
+        if prev.options != self.options {
+            element.set_options(self.options.iter().cloned());
+            cx.mark_changed();
+        }
+        if prev.selected != self.selected {
+            element.set_selected(self.selected);
+            cx.mark_changed();
+        }
+    }
+
+    fn message(
+        &self,
+        _view_state: &mut Self::ViewState,
+        id_path: &[ViewId],
+        message: Box<dyn std::any::Any>,
+        app_state: &mut State,
+    ) -> MessageResult<Action> {
+        debug_assert!(
+            id_path.is_empty(),
+            "id path should be empty in Dropdown::message"
+        );
+        match message.downcast::<masonry::Action>() {
+            Ok(action) => {
+                if let masonry::Action::DropdownSelected(idx) = *action {
+                    MessageResult::Action((self.callback)(app_state, idx))
+                } else {
+                    tracing::error!("Wrong action type in Dropdown::message: {action:?}");
+                    MessageResult::Stale(action)
+                }
+            }
+            Err(message) => {
+                tracing::error!("Wrong message type in Dropdown::message");
+                MessageResult::Stale(message)
+            }
+        }
+    }
+}
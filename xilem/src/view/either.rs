@@ -0,0 +1,142 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+use masonry::widget::WidgetMut;
+use masonry::{Widget, WidgetPod};
+
+use crate::any_view::DynWidget;
+use crate::{MasonryView, MessageResult, ViewCx, ViewId};
+
+/// Show `a` if `condition` is true, and `b` otherwise.
+///
+/// Both views are created eagerly; use [`Either`] directly to only create the one shown.
+///
+/// To show either a view or nothing, use an `Option` of the view among the children of a
+/// container, e.g. `flex((header, show_details.then(|| details)))`.
+pub fn either<A, B>(condition: bool, a: A, b: B) -> Either<A, B> {
+    if condition {
+        Either::A(a)
+    } else {
+        Either::B(b)
+    }
+}
+
+/// A view which is one of two view types.
+///
+/// This allows switching between two kinds of views, e.g. a spinner and the loaded
+/// content, without boxing them as a [`BoxedMasonryView`](crate::BoxedMasonryView).
+///
+/// When the shown variant changes, the widgets of the previous one are removed
+/// and the new view is built from scratch.
+pub enum Either<A, B> {
+    A(A),
+    B(B),
+}
+
+pub struct EitherState<StateA, StateB> {
+    inner: Either<StateA, StateB>,
+    /// Incremented each time the shown variant changes, so that messages sent to
+    /// the widgets of the previous variant are recognized as stale.
+    generation: u64,
+}
+
+impl<State, Action, A, B> MasonryView<State, Action> for Either<A, B>
+where
+    A: MasonryView<State, Action>,
+    B: MasonryView<State, Action>,
+{
+    type Element = DynWidget;
+    type ViewState = EitherState<A::ViewState, B::ViewState>;
+
+    fn build(&self, cx: &mut ViewCx) -> (WidgetPod<Self::Element>, Self::ViewState) {
+        let (element, view_state) = build_variant(self, cx, 0);
+        (WidgetPod::new(DynWidget::new(element)), view_state)
+    }
+
+    fn rebuild(
+        &self,
+        view_state: &mut Self::ViewState,
+        cx: &mut ViewCx,
+        prev: &Self,
+        mut element: WidgetMut<Self::Element>,
+    ) {
+        let generation = view_state.generation;
+        match (self, prev, &mut view_state.inner) {
+            (Either::A(view), Either::A(prev), Either::A(state)) => {
+                DynWidget::downcast(&mut element, |element| {
+                    let element = element.expect("Either element should match the shown view");
+                    cx.with_id(ViewId::for_type::<A>(generation), |cx| {
+                        view.rebuild(state, cx, prev, element);
+                    });
+                });
+            }
+            (Either::B(view), Either::B(prev), Either::B(state)) => {
+                DynWidget::downcast(&mut element, |element| {
+                    let element = element.expect("Either element should match the shown view");
+                    cx.with_id(ViewId::for_type::<B>(generation), |cx| {
+                        view.rebuild(state, cx, prev, element);
+                    });
+                });
+            }
+            _ => {
+                match prev {
+                    Either::A(_) => cx.forget_child(ViewId::for_type::<A>(generation)),
+                    Either::B(_) => cx.forget_child(ViewId::for_type::<B>(generation)),
+                }
+                // Overflow condition: Impossible to overflow, as u64 only ever incremented by 1
+                // and starting at 0.
+                let (new_element, new_state) = build_variant(self, cx, generation.wrapping_add(1));
+                *view_state = new_state;
+                DynWidget::replace_inner(&mut element, new_element);
+                cx.mark_changed();
+            }
+        }
+    }
+
+    fn message(
+        &self,
+        view_state: &mut Self::ViewState,
+        id_path: &[ViewId],
+        message: Box<dyn std::any::Any>,
+        app_state: &mut State,
+    ) -> MessageResult<Action> {
+        let (start, rest) = id_path
+            .split_first()
+            .expect("Id path has elements for Either");
+        if start.routing_id() != view_state.generation {
+            return MessageResult::Stale(message);
+        }
+        match (self, &mut view_state.inner) {
+            (Either::A(view), Either::A(state)) => view.message(state, rest, message, app_state),
+            (Either::B(view), Either::B(state)) => view.message(state, rest, message, app_state),
+            _ => MessageResult::Stale(message),
+        }
+    }
+}
+
+type BoxedWidgetPod = WidgetPod<Box<dyn Widget>>;
+
+/// Build the shown variant of `view`, returning its boxed widget and its state.
+fn build_variant<State, Action, A, B>(
+    view: &Either<A, B>,
+    cx: &mut ViewCx,
+    generation: u64,
+) -> (BoxedWidgetPod, EitherState<A::ViewState, B::ViewState>)
+where
+    A: MasonryView<State, Action>,
+    B: MasonryView<State, Action>,
+{
+    let (element, inner) = match view {
+        Either::A(view) => {
+            let (element, state) =
+                cx.with_id(ViewId::for_type::<A>(generation), |cx| view.build(cx));
+            (element.boxed(), Either::A(state))
+        }
+        Either::B(view) => {
+            let (element, state) =
+                cx.with_id(ViewId::for_type::<B>(generation), |cx| view.build(cx));
+            (element.boxed(), Either::B(state))
+        }
+    };
+    (element, EitherState { inner, generation })
+}
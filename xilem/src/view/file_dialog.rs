@@ -0,0 +1,74 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+use std::io;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use masonry::file_dialog::{self, FileFilter};
+
+use crate::view::{run_blocking, task};
+use crate::MasonryView;
+
+/// Ask the user for a file to open, and call `on_chosen` with the path they picked,
+/// `None` if they cancelled, or an error if the dialog couldn't be shown.
+///
+/// The dialog is shown when this view is built, without blocking the app while it's
+/// open. Only the files matching one of `filters` are shown, or all files if it's empty.
+///
+/// This view doesn't display anything itself, so it's usually only part of the tree
+/// while the app is waiting for the user's choice:
+///
+/// ```ignore
+/// let dialog = state.choosing_file.then(|| {
+///     open_file(vec![FileFilter::new("Text", &["txt"])], |state: &mut AppState, path| {
+///         state.choosing_file = false;
+///         match path {
+///             Ok(path) => state.path = path,
+///             Err(err) => state.error = Some(err.to_string()),
+///         }
+///     })
+/// });
+/// ```
+///
+/// See [`masonry::file_dialog`] to change how dialogs are shown.
+pub fn open_file<OnChosen, State, Action>(
+    filters: Vec<FileFilter>,
+    on_chosen: OnChosen,
+) -> impl MasonryView<State, Action>
+where
+    OnChosen: Fn(&mut State, io::Result<Option<PathBuf>>) -> Action + Send + Sync + 'static,
+{
+    let filters: Arc<[FileFilter]> = filters.into();
+    task(
+        move || {
+            let filters = filters.clone();
+            // The dialog blocks until it's closed, so it gets a thread of its own.
+            run_blocking(move || file_dialog::open_file_dialog(&filters))
+        },
+        on_chosen,
+    )
+}
+
+/// Ask the user where to save a file, and call `on_chosen` with the path they picked,
+/// `None` if they cancelled, or an error if the dialog couldn't be shown.
+///
+/// `file_name` is the name suggested to the user. This works like [`open_file`].
+pub fn save_file<OnChosen, State, Action>(
+    filters: Vec<FileFilter>,
+    file_name: Option<String>,
+    on_chosen: OnChosen,
+) -> impl MasonryView<State, Action>
+where
+    OnChosen: Fn(&mut State, io::Result<Option<PathBuf>>) -> Action + Send + Sync + 'static,
+{
+    let filters: Arc<[FileFilter]> = filters.into();
+    task(
+        move || {
+            let filters = filters.clone();
+            let file_name = file_name.clone();
+            run_blocking(move || file_dialog::save_file_dialog(&filters, file_name.as_deref()))
+        },
+        on_chosen,
+    )
+}
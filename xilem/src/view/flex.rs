@@ -19,15 +19,31 @@ pub fn flex<VT, Marker>(sequence: VT) -> Flex<VT, Marker> {
         cross_axis_alignment: CrossAxisAlignment::Center,
         main_axis_alignment: MainAxisAlignment::Start,
         fill_major_axis: false,
+        spacing: None,
     }
 }
 
+/// A [`flex`] which lays out `sequence` from left to right.
+///
+/// The space between the children can be set with [`Flex::spacing`].
+pub fn h_stack<VT, Marker>(sequence: VT) -> Flex<VT, Marker> {
+    flex(sequence).direction(Axis::Horizontal)
+}
+
+/// A [`flex`] which lays out `sequence` from top to bottom.
+///
+/// The space between the children can be set with [`Flex::spacing`].
+pub fn v_stack<VT, Marker>(sequence: VT) -> Flex<VT, Marker> {
+    flex(sequence).direction(Axis::Vertical)
+}
+
 pub struct Flex<VT, Marker> {
     sequence: VT,
     axis: Axis,
     cross_axis_alignment: CrossAxisAlignment,
     main_axis_alignment: MainAxisAlignment,
     fill_major_axis: bool,
+    spacing: Option<f64>,
     phantom: PhantomData<fn() -> Marker>,
 }
 
@@ -50,6 +66,29 @@ impl<VT, Marker> Flex<VT, Marker> {
         self.fill_major_axis = fill_major_axis;
         self
     }
+
+    /// Set the space between the children along the main axis.
+    ///
+    /// By default, this is the theme's widget padding for the direction of the flex.
+    pub fn spacing(mut self, spacing: f64) -> Self {
+        self.spacing = Some(spacing);
+        self
+    }
+
+    /// Set the space between the children along the main axis.
+    ///
+    /// This is the same as [`spacing`](Self::spacing), named like the builder methods of
+    /// other views.
+    pub fn with_spacing(self, spacing: f64) -> Self {
+        self.spacing(spacing)
+    }
+
+    fn resolved_spacing(&self) -> f64 {
+        self.spacing.unwrap_or(match self.axis {
+            Axis::Vertical => masonry::theme::WIDGET_PADDING_VERTICAL,
+            Axis::Horizontal => masonry::theme::WIDGET_PADDING_HORIZONTAL,
+        })
+    }
 }
 
 impl<State, Action, Marker: 'static, Seq: Sync> MasonryView<State, Action> for Flex<Seq, Marker>
@@ -67,6 +106,7 @@ where
         let mut scratch = Vec::new();
         let mut splice = VecSplice::new(&mut elements, &mut scratch);
         let seq_state = self.sequence.build(cx, &mut splice);
+        let spacing = self.resolved_spacing();
         let mut view = widget::Flex::for_axis(self.axis)
             .cross_axis_alignment(self.cross_axis_alignment)
             .must_fill_main_axis(self.fill_major_axis)
@@ -77,7 +117,7 @@ where
             "ViewSequence shouldn't leave splice in strange state"
         );
         for item in elements.drain(..) {
-            view = view.with_child_pod(item).with_default_spacer();
+            view = view.with_child_pod(item).with_spacer(spacing);
         }
         (WidgetPod::new(view), seq_state)
     }
@@ -116,7 +156,19 @@ where
             element.set_must_fill_main_axis(self.fill_major_axis);
             cx.mark_changed();
         }
-        let mut splice = FlexSplice { ix: 0, element };
+        let spacing = self.resolved_spacing();
+        if prev.resolved_spacing() != spacing {
+            // Each child is followed by a spacer.
+            for ix in (1..element.widget.len()).step_by(2) {
+                element.set_spacer_len(ix, spacing);
+            }
+            cx.mark_changed();
+        }
+        let mut splice = FlexSplice {
+            ix: 0,
+            element,
+            spacing,
+        };
         self.sequence
             .rebuild(view_state, cx, &prev.sequence, &mut splice);
     }
@@ -125,12 +177,13 @@ where
 struct FlexSplice<'w> {
     ix: usize,
     element: WidgetMut<'w, widget::Flex>,
+    spacing: f64,
 }
 
 impl ElementSplice for FlexSplice<'_> {
     fn push(&mut self, element: WidgetPod<Box<dyn masonry::Widget>>) {
         self.element.insert_child_pod(self.ix, element);
-        self.element.insert_default_spacer(self.ix);
+        self.element.insert_spacer(self.ix, self.spacing);
         self.ix += 2;
     }
 
@@ -0,0 +1,30 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+/// Create a sequence with one view per item of `items`, where `view` is called
+/// with the index and value of each item.
+///
+/// The result can be used directly as the children of a container such as [`flex`](super::flex),
+/// or as one element of a tuple of children:
+///
+/// ```ignore
+/// flex((
+///     label("Tasks"),
+///     for_each(&tasks, |i, task| {
+///         button(task.name.clone(), move |data: &mut Data| data.tasks[i].done = true)
+///     }),
+/// ))
+/// ```
+///
+/// Views are matched up with their previous version by position, so inserting or removing
+/// an item rebuilds every view after it. Use [`keyed`](super::keyed) to match them by key instead.
+pub fn for_each<I, V>(items: I, mut view: impl FnMut(usize, I::Item) -> V) -> Vec<V>
+where
+    I: IntoIterator,
+{
+    items
+        .into_iter()
+        .enumerate()
+        .map(|(index, item)| view(index, item))
+        .collect()
+}
@@ -0,0 +1,68 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+use masonry::{widget::WidgetMut, ArcStr, WidgetPod};
+
+use crate::{Color, MasonryView, MessageResult, ViewCx, ViewId};
+
+/// Show the icon named `name` from the icon fonts registered with
+/// [`Xilem::with_icon_font`](crate::Xilem::with_icon_font), in a `size` by `size` square.
+///
+/// Nothing is drawn if no registered font has an icon with this name.
+pub fn icon(name: impl Into<ArcStr>, size: f64, color: Color) -> Icon {
+    Icon {
+        name: name.into(),
+        size,
+        color,
+    }
+}
+
+pub struct Icon {
+    name: ArcStr,
+    size: f64,
+    color: Color,
+}
+
+impl<State, Action> MasonryView<State, Action> for Icon {
+    type Element = masonry::widget::Icon;
+    type ViewState = ();
+
+    fn build(&self, _cx: &mut ViewCx) -> (WidgetPod<Self::Element>, Self::ViewState) {
+        let widget = masonry::widget::Icon::new(self.name.clone())
+            .with_size(self.size)
+            .with_brush(self.color);
+        (WidgetPod::new(widget), ())
+    }
+
+    fn rebuild(
+        &self,
+        _view_state: &mut Self::ViewState,
+        cx: &mut ViewCx,
+        prev: &Self,
+        mut element: WidgetMut<Self::Element>,
+    ) {
+        if prev.name != self.name {
+            element.set_name(self.name.clone());
+            cx.mark_changed();
+        }
+        if prev.size != self.size {
+            element.set_size(self.size);
+            cx.mark_changed();
+        }
+        if prev.color != self.color {
+            element.set_brush(self.color);
+            cx.mark_changed();
+        }
+    }
+
+    fn message(
+        &self,
+        _view_state: &mut Self::ViewState,
+        _id_path: &[ViewId],
+        message: Box<dyn std::any::Any>,
+        _app_state: &mut State,
+    ) -> crate::MessageResult<Action> {
+        tracing::error!("Message arrived in Icon::message, but Icon doesn't consume any messages, this is a bug");
+        MessageResult::Stale(message)
+    }
+}
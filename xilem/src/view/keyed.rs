@@ -0,0 +1,220 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::sequence::WasASequence;
+use crate::{ElementSplice, MessageResult, ViewCx, ViewId, ViewSequence};
+
+/// A sequence of views, each tagged with a key which identifies it across rebuilds.
+///
+/// When the sequence is rebuilt, children are matched up with their previous version by
+/// key rather than by position, so inserting or removing an item keeps the widgets and
+/// state of every other item:
+///
+/// ```ignore
+/// flex(keyed(
+///     tasks.iter().map(|task| (task.id, label(task.name.clone()))),
+/// ))
+/// ```
+///
+/// Children whose key is no longer present are removed, and their state dropped.
+///
+/// Widgets can't currently be moved within their parent, so when children are reordered,
+/// the largest set of children which kept their relative order is preserved, and the other
+/// children are rebuilt from scratch at their new position.
+///
+/// Keys should be unique; if a key is repeated, only its first child is matched.
+pub fn keyed<K, VT>(children: impl IntoIterator<Item = (K, VT)>) -> Keyed<K, VT> {
+    Keyed {
+        children: children.into_iter().collect(),
+    }
+}
+
+pub struct Keyed<K, VT> {
+    children: Vec<(K, VT)>,
+}
+
+pub struct KeyedState<K, InnerState> {
+    /// The key, routing id and state of each child, in the same order as the children.
+    children: Vec<(K, u64, InnerState)>,
+    next_id: u64,
+}
+
+impl<K, InnerState> KeyedState<K, InnerState> {
+    fn new_id(&mut self) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        id
+    }
+}
+
+impl<T, A, Marker, K, VT> ViewSequence<T, A, (WasASequence, Marker)> for Keyed<K, VT>
+where
+    K: Eq + Hash + Clone + Send + 'static,
+    VT: ViewSequence<T, A, Marker>,
+{
+    type SeqState = KeyedState<K, VT::SeqState>;
+
+    fn build(&self, cx: &mut ViewCx, elements: &mut dyn ElementSplice) -> Self::SeqState {
+        let mut seq_state = KeyedState {
+            children: Vec::with_capacity(self.children.len()),
+            next_id: 0,
+        };
+        for (key, child) in &self.children {
+            let id = seq_state.new_id();
+            let state = cx.with_id(ViewId::for_type::<VT>(id), |cx| child.build(cx, elements));
+            seq_state.children.push((key.clone(), id, state));
+        }
+        seq_state
+    }
+
+    fn rebuild(
+        &self,
+        seq_state: &mut Self::SeqState,
+        cx: &mut ViewCx,
+        prev: &Self,
+        elements: &mut dyn ElementSplice,
+    ) {
+        let mut prev_indices: HashMap<&K, usize> = HashMap::with_capacity(prev.children.len());
+        for (index, (key, _)) in prev.children.iter().enumerate() {
+            prev_indices.entry(key).or_insert(index);
+        }
+        // Removing each matched key ensures a repeated key is only matched once.
+        let matches: Vec<Option<usize>> = self
+            .children
+            .iter()
+            .map(|(key, _)| prev_indices.remove(key))
+            .collect();
+        let kept = kept_children(&matches);
+
+        // The states of the previous children which aren't kept are dropped with this.
+        let mut prev_states: Vec<_> = seq_state.children.drain(..).map(Some).collect();
+        // The index of the next previous child which still has its elements.
+        let mut prev_ix = 0;
+        // Delete the elements of the previous children from `prev_ix` to `end`.
+        let delete_until = |prev_ix: usize, end: usize, elements: &mut dyn ElementSplice| {
+            let count = prev.children[prev_ix..end]
+                .iter()
+                .map(|(_, child)| child.count())
+                .sum();
+            elements.delete(count);
+            prev_ix < end
+        };
+        let mut changed = false;
+        let mut children = Vec::with_capacity(self.children.len());
+        for (((key, child), prev_index), kept) in self.children.iter().zip(&matches).zip(kept) {
+            match prev_index {
+                Some(prev_index) if kept => {
+                    changed |= delete_until(prev_ix, *prev_index, elements);
+                    let (_, id, mut state) = prev_states[*prev_index].take().unwrap();
+                    cx.with_id(ViewId::for_type::<VT>(id), |cx| {
+                        child.rebuild(&mut state, cx, &prev.children[*prev_index].1, elements);
+                    });
+                    prev_ix = prev_index + 1;
+                    children.push((key.clone(), id, state));
+                }
+                _ => {
+                    let id = seq_state.new_id();
+                    let state =
+                        cx.with_id(ViewId::for_type::<VT>(id), |cx| child.build(cx, elements));
+                    children.push((key.clone(), id, state));
+                    changed = true;
+                }
+            }
+        }
+        changed |= delete_until(prev_ix, prev.children.len(), elements);
+        // The previous children which weren't kept had their elements deleted.
+        for (_, id, _) in prev_states.into_iter().flatten() {
+            cx.forget_child(ViewId::for_type::<VT>(id));
+        }
+        seq_state.children = children;
+        if changed {
+            cx.mark_changed();
+        }
+    }
+
+    fn message(
+        &self,
+        seq_state: &mut Self::SeqState,
+        id_path: &[ViewId],
+        message: Box<dyn std::any::Any>,
+        app_state: &mut T,
+    ) -> MessageResult<A> {
+        let (start, rest) = id_path
+            .split_first()
+            .expect("Id path has elements for keyed sequence");
+        let index = seq_state
+            .children
+            .iter()
+            .position(|(_, id, _)| *id == start.routing_id());
+        match index {
+            Some(index) => {
+                let (_, _, state) = &mut seq_state.children[index];
+                self.children[index]
+                    .1
+                    .message(state, rest, message, app_state)
+            }
+            // The child has been removed since the message was sent.
+            None => MessageResult::Stale(message),
+        }
+    }
+
+    fn count(&self) -> usize {
+        self.children.iter().map(|(_, child)| child.count()).sum()
+    }
+}
+
+/// Given the previous index of each new child, if it had one, decide which children
+/// keep their widgets and state.
+///
+/// These are the longest run of children whose previous indices are increasing,
+/// as all of them can stay in place while the other children are removed or built.
+fn kept_children(prev_indices: &[Option<usize>]) -> Vec<bool> {
+    // `tails[len]` is the position of the smallest previous index which ends an
+    // increasing run of length `len + 1`.
+    let mut tails: Vec<usize> = Vec::new();
+    let mut predecessors: Vec<Option<usize>> = vec![None; prev_indices.len()];
+    for (position, prev_index) in prev_indices.iter().enumerate() {
+        let Some(prev_index) = prev_index else {
+            continue;
+        };
+        let len = tails.partition_point(|&tail| prev_indices[tail].unwrap() < *prev_index);
+        predecessors[position] = len.checked_sub(1).map(|len| tails[len]);
+        if len == tails.len() {
+            tails.push(position);
+        } else {
+            tails[len] = position;
+        }
+    }
+
+    let mut kept = vec![false; prev_indices.len()];
+    let mut position = tails.last().copied();
+    while let Some(current) = position {
+        kept[current] = true;
+        position = predecessors[current];
+    }
+    kept
+}
+
+#[cfg(test)]
+mod tests {
+    use super::kept_children;
+
+    #[test]
+    fn insertion_keeps_every_child() {
+        let kept = kept_children(&[Some(0), None, Some(1), Some(2)]);
+        assert_eq!(kept, [true, false, true, true]);
+    }
+
+    #[test]
+    fn reorder_keeps_longest_ordered_run() {
+        // The last child moved to the front.
+        let kept = kept_children(&[Some(3), Some(0), Some(1), Some(2)]);
+        assert_eq!(kept, [false, true, true, true]);
+        // Removed children leave gaps in the previous indices.
+        let kept = kept_children(&[Some(4), Some(1), None, Some(0), Some(2)]);
+        assert_eq!(kept, [false, false, false, true, true]);
+    }
+}
@@ -1,15 +1,26 @@
 // Copyright 2024 the Xilem Authors
 // SPDX-License-Identifier: Apache-2.0
 
-use masonry::{widget::WidgetMut, ArcStr, WidgetPod};
+use masonry::{
+    text2::{TextDirection, TextStyle},
+    theme,
+    widget::{TextOverflow, WidgetMut},
+    ArcStr, WidgetPod,
+};
 
 use crate::{Color, MasonryView, MessageResult, TextAlignment, ViewCx, ViewId};
 
 pub fn label(label: impl Into<ArcStr>) -> Label {
     Label {
         label: label.into(),
-        text_color: Color::WHITE,
+        text_color: theme::TEXT_COLOR,
+        text_size: None,
+        text_style: None,
         alignment: TextAlignment::default(),
+        direction: TextDirection::Auto,
+        line_height: None,
+        letter_spacing: 0.0,
+        text_overflow: None,
         disabled: false,
     }
 }
@@ -17,22 +28,101 @@ pub fn label(label: impl Into<ArcStr>) -> Label {
 pub struct Label {
     label: ArcStr,
     text_color: Color,
+    text_size: Option<f32>,
+    text_style: Option<TextStyle>,
     alignment: TextAlignment,
+    direction: TextDirection,
+    line_height: Option<f32>,
+    letter_spacing: f32,
+    text_overflow: Option<TextOverflow>,
     disabled: bool,
     // TODO: add more attributes of `masonry::widget::Label`
 }
 
 impl Label {
+    /// Set the color of the text, which defaults to [`theme::TEXT_COLOR`].
+    ///
+    /// Changing only the color repaints the label without laying it out again.
     pub fn color(mut self, color: Color) -> Self {
         self.text_color = color;
         self
     }
 
+    /// Set the font size of the text, in logical pixels.
+    ///
+    /// This defaults to the size of the [`text_style`](Self::text_style), or to
+    /// [`theme::TEXT_SIZE_NORMAL`].
+    pub fn text_size(mut self, text_size: f32) -> Self {
+        self.text_size = Some(text_size);
+        self
+    }
+
+    /// Set the font, size, weight, color and line height of the text.
+    ///
+    /// The color can be overridden by calling [`color`](Self::color) afterwards, the
+    /// size by calling [`text_size`](Self::text_size), and the line height by calling
+    /// [`line_height`](Self::line_height).
+    pub fn text_style(mut self, text_style: TextStyle) -> Self {
+        self.text_color = text_style.color;
+        self.text_style = Some(text_style);
+        self
+    }
+
+    /// Set how lines are aligned within the width of the label.
+    ///
+    /// A label is only as wide as its text unless its parent makes it wider, for example
+    /// with [`sized_box`](crate::view::sized_box), in which case the text is aligned
+    /// within that width. Otherwise each line is aligned within the widest line.
     pub fn alignment(mut self, alignment: TextAlignment) -> Self {
         self.alignment = alignment;
         self
     }
 
+    /// Set the base direction of the text.
+    ///
+    /// By default, the direction is detected from the first strongly directional
+    /// character, so that right-to-left text such as Arabic or Hebrew is aligned to the
+    /// right. Runs in the other direction are reordered within each line.
+    pub fn direction(mut self, direction: TextDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Set what happens to text that doesn't fit in the label, such as cutting it
+    /// short with "…".
+    pub fn text_overflow(mut self, text_overflow: TextOverflow) -> Self {
+        self.text_overflow = Some(text_overflow);
+        self
+    }
+
+    /// Limit the text to `max_lines` lines, cutting it short with "…" at the end of the
+    /// last one if it needs more.
+    ///
+    /// The label is only as tall as the lines which are kept, which suits previews such
+    /// as the description on a card.
+    pub fn max_lines(mut self, max_lines: usize) -> Self {
+        self.text_overflow = Some(TextOverflow::max_lines(max_lines));
+        self
+    }
+
+    /// Set the height of each line, as a multiple of the line height of the font.
+    ///
+    /// This is relative rather than absolute, so `1.5` makes lines half again as tall,
+    /// whatever the font size. It defaults to the line height of the
+    /// [`text_style`](Self::text_style), or to `1.0`.
+    pub fn line_height(mut self, line_height: f32) -> Self {
+        self.line_height = Some(line_height);
+        self
+    }
+
+    /// Set the extra space added after each character, in logical pixels.
+    ///
+    /// This is an absolute length, which can be negative. It defaults to `0.0`.
+    pub fn letter_spacing(mut self, letter_spacing: f32) -> Self {
+        self.letter_spacing = letter_spacing;
+        self
+    }
+
     pub fn disabled(mut self) -> Self {
         self.disabled = true;
         self
@@ -44,12 +134,25 @@ impl<State, Action> MasonryView<State, Action> for Label {
     type ViewState = ();
 
     fn build(&self, _cx: &mut ViewCx) -> (WidgetPod<Self::Element>, Self::ViewState) {
-        let widget_pod = WidgetPod::new(
-            masonry::widget::Label::new(self.label.clone())
-                .with_text_brush(self.text_color)
-                .with_text_alignment(self.alignment),
-        );
-        (widget_pod, ())
+        let mut widget = masonry::widget::Label::new(self.label.clone());
+        if let Some(text_style) = &self.text_style {
+            widget = widget.with_text_style(text_style);
+        }
+        if let Some(text_size) = self.text_size {
+            widget = widget.with_text_size(text_size);
+        }
+        if let Some(text_overflow) = self.text_overflow {
+            widget = widget.with_text_overflow(text_overflow);
+        }
+        if let Some(line_height) = self.line_height {
+            widget = widget.with_line_height(line_height);
+        }
+        let widget = widget
+            .with_letter_spacing(self.letter_spacing)
+            .with_text_brush(self.text_color)
+            .with_text_alignment(self.alignment)
+            .with_text_direction(self.direction);
+        (WidgetPod::new(widget), ())
     }
 
     fn rebuild(
@@ -67,14 +170,56 @@ impl<State, Action> MasonryView<State, Action> for Label {
         //     element.set_disabled(self.disabled);
         //     cx.mark_changed();
         // }
-        if prev.text_color != self.text_color {
+        if prev.text_style != self.text_style {
+            let default_style = TextStyle::default();
+            element.set_text_style(self.text_style.as_ref().unwrap_or(&default_style));
+            // The style resets the color, size and line height, which may have been overridden.
             element.set_text_brush(self.text_color);
+            if let Some(text_size) = self.text_size {
+                element.set_text_size(text_size);
+            }
+            if let Some(line_height) = self.line_height {
+                element.set_line_height(line_height);
+            }
+            cx.mark_changed();
+        } else {
+            if prev.text_color != self.text_color {
+                element.set_text_brush(self.text_color);
+                cx.mark_changed();
+            }
+            if prev.text_size != self.text_size {
+                let default_size = self
+                    .text_style
+                    .as_ref()
+                    .map_or(theme::TEXT_SIZE_NORMAL as f32, |style| style.font_size);
+                element.set_text_size(self.text_size.unwrap_or(default_size));
+                cx.mark_changed();
+            }
+            if prev.line_height != self.line_height {
+                let default_line_height = self
+                    .text_style
+                    .as_ref()
+                    .map_or(1.0, |style| style.line_height);
+                element.set_line_height(self.line_height.unwrap_or(default_line_height));
+                cx.mark_changed();
+            }
+        }
+        if prev.letter_spacing != self.letter_spacing {
+            element.set_letter_spacing(self.letter_spacing);
             cx.mark_changed();
         }
         if prev.alignment != self.alignment {
             element.set_alignment(self.alignment);
             cx.mark_changed();
         }
+        if prev.direction != self.direction {
+            element.set_text_direction(self.direction);
+            cx.mark_changed();
+        }
+        if prev.text_overflow != self.text_overflow {
+            element.set_text_overflow(self.text_overflow);
+            cx.mark_changed();
+        }
     }
 
     fn message(
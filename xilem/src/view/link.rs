@@ -0,0 +1,160 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+use masonry::{theme, widget::WidgetMut, ArcStr, WidgetPod};
+
+use crate::{Color, MasonryView, MessageResult, ViewCx, ViewId};
+
+/// A link which opens `url` in the user's browser when clicked.
+///
+/// The URL is opened with the platform's default handler, so it can also be a
+/// `mailto:` address or a local file.
+pub fn link(text: impl Into<ArcStr>, url: impl Into<String>) -> Link<OpenUrl> {
+    Link {
+        text: text.into(),
+        target: OpenUrl(url.into()),
+        color: theme::LINK_COLOR,
+    }
+}
+
+/// A link which calls `callback` when clicked, e.g. to navigate to another page of the app.
+pub fn link_action<F, State, Action>(text: impl Into<ArcStr>, callback: F) -> Link<F>
+where
+    F: Fn(&mut State) -> Action + Send + 'static,
+{
+    Link {
+        text: text.into(),
+        target: callback,
+        color: theme::LINK_COLOR,
+    }
+}
+
+pub struct Link<F> {
+    text: ArcStr,
+    target: F,
+    color: Color,
+}
+
+impl<F> Link<F> {
+    /// Set the color of the text.
+    ///
+    /// By default this is [`LINK_COLOR`](masonry::theme::LINK_COLOR).
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+}
+
+/// The target of a link created with [`link`].
+pub struct OpenUrl(String);
+
+/// What happens when a [`Link`] is clicked.
+pub trait LinkTarget<State, Action>: Send + Sync + 'static {
+    fn activate(&self, app_state: &mut State) -> MessageResult<Action>;
+}
+
+impl<State, Action> LinkTarget<State, Action> for OpenUrl {
+    fn activate(&self, _app_state: &mut State) -> MessageResult<Action> {
+        open_url(&self.0);
+        MessageResult::Nop
+    }
+}
+
+impl<F, State, Action> LinkTarget<State, Action> for F
+where
+    F: Fn(&mut State) -> Action + Send + Sync + 'static,
+{
+    fn activate(&self, app_state: &mut State) -> MessageResult<Action> {
+        MessageResult::Action(self(app_state))
+    }
+}
+
+/// Open `url` with the platform's default handler, without waiting for it.
+fn open_url(url: &str) {
+    open_url_with(url, |url| open::that_detached(url));
+}
+
+/// Pass `url` to `open` as it is, logging the error if it couldn't be opened.
+///
+/// The URL isn't given to a shell, so characters such as `&` in it aren't interpreted.
+fn open_url_with(url: &str, open: impl FnOnce(&str) -> std::io::Result<()>) {
+    if let Err(err) = open(url) {
+        tracing::error!("Couldn't open link {url}: {err}");
+    }
+}
+
+impl<F, State, Action> MasonryView<State, Action> for Link<F>
+where
+    F: LinkTarget<State, Action>,
+{
+    type Element = masonry::widget::Hyperlink;
+    type ViewState = ();
+
+    fn build(&self, cx: &mut ViewCx) -> (WidgetPod<Self::Element>, Self::ViewState) {
+        cx.with_leaf_action_widget(|_| {
+            WidgetPod::new(
+                masonry::widget::Hyperlink::new(self.text.clone()).with_text_brush(self.color),
+            )
+        })
+    }
+
+    fn rebuild(
+        &self,
+        _view_state: &mut Self::ViewState,
+        cx: &mut ViewCx,
+        prev: &Self,
+        mut element: WidgetMut<Self::Element>,
+    ) {
+        if prev.text != self.text {
+            element.set_text(self.text.clone());
+            cx.mark_changed();
+        }
+        if prev.color != self.color {
+            element.set_text_brush(self.color);
+            cx.mark_changed();
+        }
+    }
+
+    fn message(
+        &self,
+        _view_state: &mut Self::ViewState,
+        id_path: &[ViewId],
+        message: Box<dyn std::any::Any>,
+        app_state: &mut State,
+    ) -> crate::MessageResult<Action> {
+        debug_assert!(
+            id_path.is_empty(),
+            "id path should be empty in Link::message"
+        );
+        match message.downcast::<masonry::Action>() {
+            Ok(action) => {
+                if let masonry::Action::LinkClicked = *action {
+                    self.target.activate(app_state)
+                } else {
+                    tracing::error!("Wrong action type in Link::message: {action:?}");
+                    MessageResult::Stale(action)
+                }
+            }
+            Err(message) => {
+                tracing::error!("Wrong message type in Link::message");
+                MessageResult::Stale(message)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn url_reaches_handler_unchanged() {
+        let url = "https://example.com/?a=1&b=2|c^d";
+        let mut opened = None;
+        open_url_with(url, |url| {
+            opened = Some(url.to_string());
+            Ok(())
+        });
+        assert_eq!(opened.as_deref(), Some(url));
+    }
+}
@@ -0,0 +1,113 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use masonry::{widget::WidgetMut, WidgetPod};
+
+use crate::{MasonryView, MessageResult, ViewCx, ViewId};
+
+/// Wrap `child` so that `callback` is called when it's pressed and held for `duration`.
+///
+/// The press is cancelled if the pointer is released, moves more than a few pixels,
+/// or leaves `child` before then. This is typically used to open a menu on touch
+/// screens, where there is no right click.
+///
+/// See also [`LongPressExt::on_long_press`].
+pub fn long_press<V, F, State, Action>(child: V, duration: Duration, callback: F) -> LongPress<V, F>
+where
+    F: Fn(&mut State) -> Action + Send + 'static,
+{
+    LongPress {
+        child,
+        duration,
+        callback,
+    }
+}
+
+/// Adds [`on_long_press`](Self::on_long_press) to views.
+pub trait LongPressExt: Sized {
+    /// Call `callback` when this view is pressed and held for `duration`.
+    fn on_long_press<F, State, Action>(self, duration: Duration, callback: F) -> LongPress<Self, F>
+    where
+        F: Fn(&mut State) -> Action + Send + 'static,
+    {
+        long_press(self, duration, callback)
+    }
+}
+
+impl<V: Send + Sync + 'static> LongPressExt for V {}
+
+pub struct LongPress<V, F> {
+    child: V,
+    duration: Duration,
+    callback: F,
+}
+
+impl<V, F, State, Action> MasonryView<State, Action> for LongPress<V, F>
+where
+    V: MasonryView<State, Action>,
+    F: Fn(&mut State) -> Action + Send + Sync + 'static,
+{
+    type Element = masonry::widget::LongPress<V::Element>;
+    type ViewState = V::ViewState;
+
+    fn build(&self, cx: &mut ViewCx) -> (WidgetPod<Self::Element>, Self::ViewState) {
+        let mut child_state = None;
+        let element = cx.with_action_widget(|cx| {
+            let (child, state) = cx.with_id(ViewId::for_type::<V>(0), |cx| self.child.build(cx));
+            child_state = Some(state);
+            WidgetPod::new(
+                masonry::widget::LongPress::from_child_pod(child).duration(self.duration),
+            )
+        });
+        (element, child_state.unwrap())
+    }
+
+    fn rebuild(
+        &self,
+        view_state: &mut Self::ViewState,
+        cx: &mut ViewCx,
+        prev: &Self,
+        mut element: WidgetMut<Self::Element>,
+    ) {
+        if prev.duration != self.duration {
+            element.set_duration(self.duration);
+            cx.mark_changed();
+        }
+        cx.with_id(ViewId::for_type::<V>(0), |cx| {
+            self.child
+                .rebuild(view_state, cx, &prev.child, element.child_mut());
+        });
+    }
+
+    fn message(
+        &self,
+        view_state: &mut Self::ViewState,
+        id_path: &[ViewId],
+        message: Box<dyn std::any::Any>,
+        app_state: &mut State,
+    ) -> crate::MessageResult<Action> {
+        if let Some((first, rest)) = id_path.split_first() {
+            if first.routing_id() != 0 {
+                tracing::error!("Invalid id path in LongPress::message");
+                return MessageResult::Stale(message);
+            }
+            return self.child.message(view_state, rest, message, app_state);
+        }
+        match message.downcast::<masonry::Action>() {
+            Ok(action) => {
+                if let masonry::Action::LongPressed = *action {
+                    MessageResult::Action((self.callback)(app_state))
+                } else {
+                    tracing::error!("Wrong action type in LongPress::message: {action:?}");
+                    MessageResult::Stale(action)
+                }
+            }
+            Err(message) => {
+                tracing::error!("Wrong message type in LongPress::message");
+                MessageResult::Stale(message)
+            }
+        }
+    }
+}
@@ -0,0 +1,163 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{any::Any, marker::PhantomData};
+
+use masonry::{widget::WidgetMut, WidgetPod};
+
+use crate::{MasonryView, MessageResult, ViewCx, ViewId};
+
+/// Turn the actions returned by `child` into actions of the parent view.
+///
+/// `map` is called with the app state and each action the child returns. Together with
+/// [`adapt`](crate::view::adapt), this allows reusable components to report what happened
+/// to their parent without knowing about it:
+///
+/// ```ignore
+/// enum CounterAction {
+///     Reset,
+/// }
+///
+/// fn counter_view(count: i32) -> impl MasonryView<i32, CounterAction> {
+///     button(format!("reset {count}"), |_: &mut i32| CounterAction::Reset)
+/// }
+///
+/// fn app_logic(state: &mut AppState) -> impl MasonryView<AppState> {
+///     map_action(
+///         adapt(|state: &mut AppState| &mut state.counter, counter_view(state.counter)),
+///         |state: &mut AppState, CounterAction::Reset| state.counter = 0,
+///     )
+/// }
+/// ```
+///
+/// See [`map_message`] to also decide whether the parent is rebuilt.
+pub fn map_action<State, ParentAction, ChildAction, V, F>(
+    child: V,
+    map: F,
+) -> MapMessage<
+    ParentAction,
+    ChildAction,
+    V,
+    impl Fn(&mut State, ChildAction) -> MessageResult<ParentAction> + Send + Sync + 'static,
+>
+where
+    State: 'static,
+    ParentAction: 'static,
+    ChildAction: 'static,
+    V: MasonryView<State, ChildAction>,
+    F: Fn(&mut State, ChildAction) -> ParentAction + Send + Sync + 'static,
+{
+    map_message(child, move |state: &mut State, action| {
+        MessageResult::Action(map(state, action))
+    })
+}
+
+/// Handle the actions returned by `child`, and decide what the parent view returns.
+///
+/// Unlike [`map_action`], `map` returns a [`MessageResult`], so it can also
+/// request a rebuild without producing an action, or ignore the action entirely.
+/// Messages which don't produce an action are passed through unchanged.
+pub fn map_message<State, ParentAction, ChildAction, V, F>(
+    child: V,
+    map: F,
+) -> MapMessage<ParentAction, ChildAction, V, F>
+where
+    V: MasonryView<State, ChildAction>,
+    F: Fn(&mut State, ChildAction) -> MessageResult<ParentAction> + Send + Sync + 'static,
+{
+    MapMessage {
+        child,
+        map,
+        phantom: PhantomData,
+    }
+}
+
+pub struct MapMessage<ParentAction, ChildAction, V, F> {
+    child: V,
+    map: F,
+    phantom: PhantomData<fn(ChildAction) -> ParentAction>,
+}
+
+impl<State, ParentAction, ChildAction, V, F> MasonryView<State, ParentAction>
+    for MapMessage<ParentAction, ChildAction, V, F>
+where
+    ParentAction: 'static,
+    ChildAction: 'static,
+    V: MasonryView<State, ChildAction>,
+    F: Fn(&mut State, ChildAction) -> MessageResult<ParentAction> + Send + Sync + 'static,
+{
+    type Element = V::Element;
+    type ViewState = V::ViewState;
+
+    fn build(&self, cx: &mut ViewCx) -> (WidgetPod<Self::Element>, Self::ViewState) {
+        self.child.build(cx)
+    }
+
+    fn rebuild(
+        &self,
+        view_state: &mut Self::ViewState,
+        cx: &mut ViewCx,
+        prev: &Self,
+        element: WidgetMut<Self::Element>,
+    ) {
+        self.child.rebuild(view_state, cx, &prev.child, element);
+    }
+
+    fn message(
+        &self,
+        view_state: &mut Self::ViewState,
+        id_path: &[ViewId],
+        message: Box<dyn Any>,
+        app_state: &mut State,
+    ) -> MessageResult<ParentAction> {
+        match self.child.message(view_state, id_path, message, app_state) {
+            MessageResult::Action(action) => (self.map)(app_state, action),
+            MessageResult::RequestRebuild => MessageResult::RequestRebuild,
+            MessageResult::Nop => MessageResult::Nop,
+            MessageResult::Stale(message) => MessageResult::Stale(message),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::view::{button, flex};
+
+    struct Pressed;
+
+    /// A reusable component which only reports that it was pressed.
+    fn component<State>() -> impl MasonryView<State, Pressed> {
+        button("press", |_: &mut State| Pressed)
+    }
+
+    #[test]
+    fn same_component_different_maps() {
+        let view = flex((
+            map_message(component(), |count: &mut i32, Pressed| {
+                *count += 1;
+                MessageResult::Action(())
+            }),
+            map_message(component(), |count: &mut i32, Pressed| {
+                *count -= 10;
+                MessageResult::Nop
+            }),
+        ));
+        let mut cx = ViewCx::new();
+        let (_pod, mut view_state) = view.build(&mut cx);
+
+        // The id of each button is the position of its view in the tuple.
+        let mut id_paths: Vec<_> = cx.widget_map.values().cloned().collect();
+        id_paths.sort_by_key(|path| path.last().unwrap().routing_id());
+        assert_eq!(id_paths.len(), 2);
+
+        let mut count = 0;
+        let press = || Box::new(masonry::Action::ButtonPressed);
+        let result = view.message(&mut view_state, &id_paths[0], press(), &mut count);
+        assert!(matches!(result, MessageResult::Action(())));
+        assert_eq!(count, 1);
+        let result = view.message(&mut view_state, &id_paths[1], press(), &mut count);
+        assert!(matches!(result, MessageResult::Nop));
+        assert_eq!(count, -9);
+    }
+}
@@ -7,6 +7,9 @@ use masonry::{widget::WidgetMut, WidgetPod};
 
 use crate::{MasonryView, MessageResult, ViewCx, ViewId};
 
+/// A view which is only rebuilt when its data changes.
+///
+/// See [`memoize`] for details.
 pub struct Memoize<D, F> {
     data: D,
     child_cb: F,
@@ -103,6 +106,26 @@ where
 }
 
 /// Memoize the view, until the `data` changes (in which case `view` is called again)
+///
+/// On rebuild, `data` is compared with the data of the previous view, and if they are equal,
+/// `view` isn't called and the whole subtree is left untouched. This is useful for large,
+/// mostly static parts of the UI surrounding a small dynamic part:
+///
+/// ```ignore
+/// fn sidebar(state: &AppState) -> impl MasonryView<AppState> {
+///     memoize(state.selected_tab, |selected_tab| {
+///         flex((
+///             label(format!("Tab {selected_tab}")),
+///             button("Next tab", |state: &mut AppState| state.selected_tab += 1),
+///         ))
+///     })
+/// }
+/// ```
+///
+/// The subtree is also rebuilt if one of its views handles a message and asks for a rebuild.
+///
+/// As the closures passed to `view` can't be compared, `view` may not capture anything:
+/// everything the subtree depends on must be part of `data`. This is checked at compile time.
 pub fn memoize<D, V, F>(data: D, view: F) -> Memoize<D, F>
 where
     F: Fn(&D) -> V + Send,
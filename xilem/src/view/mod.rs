@@ -1,25 +1,142 @@
 // Copyright 2024 the Xilem Authors
 // SPDX-License-Identifier: Apache-2.0
 
+mod accessible;
+pub use accessible::*;
+
+mod adapt;
+pub use adapt::*;
+
 mod arc;
 
+mod aspect_ratio;
+pub use aspect_ratio::*;
+
+mod badge;
+pub use badge::*;
+
 mod button;
 pub use button::*;
 
 mod checkbox;
 pub use checkbox::*;
 
+mod clip;
+pub use clip::*;
+
+mod color_picker;
+pub use color_picker::*;
+
+mod combo_box;
+pub use combo_box::*;
+
+mod context_menu;
+pub use context_menu::*;
+
+mod date_picker;
+pub use date_picker::*;
+
+mod debounce;
+pub use debounce::*;
+
+mod divider;
+pub use divider::*;
+
+mod drop_target;
+pub use drop_target::*;
+
+mod dropdown;
+pub use dropdown::*;
+
+mod either;
+pub use either::*;
+
+mod file_dialog;
+pub use file_dialog::*;
+
 mod flex;
 pub use flex::*;
 
+mod for_each;
+pub use for_each::*;
+
+mod icon;
+pub use icon::*;
+
+mod keyed;
+pub use keyed::*;
+
 mod label;
 pub use label::*;
 
+mod link;
+pub use link::*;
+
+mod long_press;
+pub use long_press::*;
+
+mod map_action;
+pub use map_action::*;
+
 mod memoize;
 pub use memoize::*;
 
+mod number_input;
+pub use number_input::*;
+
+mod opacity;
+pub use opacity::*;
+
+mod progress_bar;
+pub use progress_bar::*;
+
 mod prose;
 pub use prose::*;
 
+mod range_slider;
+pub use range_slider::*;
+
+mod rating;
+pub use rating::*;
+
+mod responsive;
+pub use responsive::*;
+
+mod rich_text;
+pub use rich_text::*;
+
+mod sized_box;
+pub use sized_box::*;
+
+mod spacer;
+pub use spacer::*;
+
+mod spinner;
+pub use spinner::*;
+
+mod spinner_column;
+pub use spinner_column::*;
+
+mod tag_input;
+pub use tag_input::*;
+
+mod task;
+pub use task::*;
+
 mod textbox;
 pub use textbox::*;
+
+mod time_picker;
+pub use time_picker::*;
+
+mod timer;
+pub use timer::*;
+
+mod tooltip;
+pub use tooltip::*;
+
+mod virtual_list;
+pub use virtual_list::*;
+
+mod z_stack;
+pub use z_stack::*;
@@ -0,0 +1,102 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+use std::ops::RangeInclusive;
+
+use masonry::{widget::WidgetMut, WidgetPod};
+
+use crate::{MasonryView, MessageResult, ViewCx, ViewId};
+
+/// A text box for entering a number in `range`, with buttons which change it by `step`.
+///
+/// Typed text is committed, clamped to `range`, when Enter is pressed or the text box
+/// loses focus. `on_change` is called with the new value whenever it changes.
+pub fn number_input<F, State, Action>(
+    value: f64,
+    range: RangeInclusive<f64>,
+    step: f64,
+    on_change: F,
+) -> NumberInput<F>
+where
+    F: Fn(&mut State, f64) -> Action + Send + 'static,
+{
+    NumberInput {
+        value,
+        range,
+        step,
+        on_change,
+    }
+}
+
+pub struct NumberInput<F> {
+    value: f64,
+    range: RangeInclusive<f64>,
+    step: f64,
+    on_change: F,
+}
+
+impl<F, State, Action> MasonryView<State, Action> for NumberInput<F>
+where
+    F: Fn(&mut State, f64) -> Action + Send + Sync + 'static,
+{
+    type Element = masonry::widget::NumberInput;
+    type ViewState = ();
+
+    fn build(&self, cx: &mut ViewCx) -> (WidgetPod<Self::Element>, Self::ViewState) {
+        cx.with_leaf_action_widget(|_| {
+            WidgetPod::new(masonry::widget::NumberInput::new(
+                self.value,
+                self.range.clone(),
+                self.step,
+            ))
+        })
+    }
+
+    fn rebuild(
+        &self,
+        _view_state: &mut Self::ViewState,
+        cx: &mut ViewCx,
+        prev: &Self,
+        mut element: WidgetMut<Self::Element>,
+    ) {
+        if prev.range != self.range {
+            element.set_range(self.range.clone());
+            cx.mark_changed();
+        }
+        if prev.value != self.value {
+            element.set_value(self.value);
+            cx.mark_changed();
+        }
+        if prev.step != self.step {
+            element.set_step(self.step);
+            cx.mark_changed();
+        }
+    }
+
+    fn message(
+        &self,
+        _view_state: &mut Self::ViewState,
+        id_path: &[ViewId],
+        message: Box<dyn std::any::Any>,
+        app_state: &mut State,
+    ) -> MessageResult<Action> {
+        debug_assert!(
+            id_path.is_empty(),
+            "id path should be empty in NumberInput::message"
+        );
+        match message.downcast::<masonry::Action>() {
+            Ok(action) => {
+                if let masonry::Action::NumberChanged(value) = *action {
+                    MessageResult::Action((self.on_change)(app_state, value))
+                } else {
+                    tracing::error!("Wrong action type in NumberInput::message: {action:?}");
+                    MessageResult::Stale(action)
+                }
+            }
+            Err(message) => {
+                tracing::error!("Wrong message type in NumberInput::message");
+                MessageResult::Stale(message)
+            }
+        }
+    }
+}
@@ -0,0 +1,68 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+use masonry::{widget::WidgetMut, WidgetPod};
+
+use crate::{MasonryView, MessageResult, ViewCx, ViewId};
+
+/// Paint `child` and its descendants with the given opacity, between 0 and 1.
+///
+/// Changing the opacity over successive rebuilds can be used to fade a view in or out.
+/// A fully transparent view still takes part in layout and receives events.
+///
+/// See also [`OpacityExt::with_opacity`].
+pub fn opacity<V>(child: V, opacity: f64) -> Opacity<V> {
+    Opacity { child, opacity }
+}
+
+/// Adds [`with_opacity`](Self::with_opacity) to views.
+pub trait OpacityExt: Sized {
+    /// Paint this view and its descendants with the given opacity, between 0 and 1.
+    fn with_opacity(self, opacity: f64) -> Opacity<Self> {
+        self::opacity(self, opacity)
+    }
+}
+
+impl<V: Send + Sync + 'static> OpacityExt for V {}
+
+pub struct Opacity<V> {
+    child: V,
+    opacity: f64,
+}
+
+impl<V, State, Action> MasonryView<State, Action> for Opacity<V>
+where
+    V: MasonryView<State, Action>,
+{
+    type Element = V::Element;
+    type ViewState = V::ViewState;
+
+    fn build(&self, cx: &mut ViewCx) -> (WidgetPod<Self::Element>, Self::ViewState) {
+        let (element, child_state) = self.child.build(cx);
+        (element.with_opacity(self.opacity), child_state)
+    }
+
+    fn rebuild(
+        &self,
+        view_state: &mut Self::ViewState,
+        cx: &mut ViewCx,
+        prev: &Self,
+        mut element: WidgetMut<Self::Element>,
+    ) {
+        if prev.opacity != self.opacity {
+            element.ctx.set_opacity(self.opacity);
+            cx.mark_changed();
+        }
+        self.child.rebuild(view_state, cx, &prev.child, element);
+    }
+
+    fn message(
+        &self,
+        view_state: &mut Self::ViewState,
+        id_path: &[ViewId],
+        message: Box<dyn std::any::Any>,
+        app_state: &mut State,
+    ) -> MessageResult<Action> {
+        self.child.message(view_state, id_path, message, app_state)
+    }
+}
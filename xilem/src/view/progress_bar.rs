@@ -0,0 +1,51 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+use masonry::{widget::WidgetMut, WidgetPod};
+
+use crate::{MasonryView, MessageResult, ViewCx, ViewId};
+
+/// A horizontal bar filled up to `progress`, a fraction between 0 and 1.
+///
+/// Values outside of that range are clamped.
+pub fn progress_bar(progress: f64) -> ProgressBar {
+    ProgressBar { progress }
+}
+
+pub struct ProgressBar {
+    progress: f64,
+}
+
+impl<State, Action> MasonryView<State, Action> for ProgressBar {
+    type Element = masonry::widget::ProgressBar;
+    type ViewState = ();
+
+    fn build(&self, _cx: &mut ViewCx) -> (WidgetPod<Self::Element>, Self::ViewState) {
+        let widget_pod = WidgetPod::new(masonry::widget::ProgressBar::new(self.progress));
+        (widget_pod, ())
+    }
+
+    fn rebuild(
+        &self,
+        _view_state: &mut Self::ViewState,
+        cx: &mut ViewCx,
+        prev: &Self,
+        mut element: WidgetMut<Self::Element>,
+    ) {
+        if prev.progress != self.progress {
+            element.set_progress(self.progress);
+            cx.mark_changed();
+        }
+    }
+
+    fn message(
+        &self,
+        _view_state: &mut Self::ViewState,
+        _id_path: &[ViewId],
+        message: Box<dyn std::any::Any>,
+        _app_state: &mut State,
+    ) -> crate::MessageResult<Action> {
+        tracing::error!("Message arrived in ProgressBar::message, but ProgressBar doesn't consume any messages, this is a bug");
+        MessageResult::Stale(message)
+    }
+}
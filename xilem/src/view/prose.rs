@@ -1,7 +1,11 @@
 // Copyright 2024 the Xilem Authors
 // SPDX-License-Identifier: Apache-2.0
 
-use masonry::{text2::TextBrush, widget::WidgetMut, ArcStr, WidgetPod};
+use masonry::{
+    text2::{TextBrush, TextDirection, TextStyle},
+    widget::WidgetMut,
+    ArcStr, WidgetPod,
+};
 
 use crate::{Color, MasonryView, MessageResult, TextAlignment, ViewCx, ViewId};
 
@@ -9,7 +13,12 @@ pub fn prose(label: impl Into<ArcStr>) -> Prose {
     Prose {
         label: label.into(),
         text_brush: Color::WHITE.into(),
+        text_style: None,
         alignment: TextAlignment::default(),
+        direction: TextDirection::Auto,
+        line_height: None,
+        letter_spacing: 0.0,
+        selectable: true,
         disabled: false,
     }
 }
@@ -17,7 +26,12 @@ pub fn prose(label: impl Into<ArcStr>) -> Prose {
 pub struct Prose {
     label: ArcStr,
     text_brush: TextBrush,
+    text_style: Option<TextStyle>,
     alignment: TextAlignment,
+    direction: TextDirection,
+    line_height: Option<f32>,
+    letter_spacing: f32,
+    selectable: bool,
     disabled: bool,
     // TODO: add more attributes of `masonry::widget::Label`
 }
@@ -29,11 +43,58 @@ impl Prose {
         self
     }
 
+    /// Set the font, size, weight, color and line height of the text.
+    ///
+    /// The color can be overridden by calling [`brush`](Self::brush) afterwards, and the
+    /// line height by calling [`line_height`](Self::line_height).
+    pub fn text_style(mut self, text_style: TextStyle) -> Self {
+        self.text_brush = text_style.color.into();
+        self.text_style = Some(text_style);
+        self
+    }
+
     pub fn alignment(mut self, alignment: TextAlignment) -> Self {
         self.alignment = alignment;
         self
     }
 
+    /// Set the base direction of the text.
+    ///
+    /// By default, the direction is detected from the first strongly directional
+    /// character, so that right-to-left text such as Arabic or Hebrew is aligned to the
+    /// right. Runs in the other direction are reordered within each line.
+    pub fn direction(mut self, direction: TextDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Set whether the user can select the text and copy it.
+    ///
+    /// Selectable text can be selected by dragging, or by double clicking a word or triple
+    /// clicking a line. Text is selectable by default.
+    pub fn selectable(mut self, selectable: bool) -> Self {
+        self.selectable = selectable;
+        self
+    }
+
+    /// Set the height of each line, as a multiple of the line height of the font.
+    ///
+    /// This is relative rather than absolute, so `1.5` makes lines half again as tall,
+    /// whatever the font size. It defaults to the line height of the
+    /// [`text_style`](Self::text_style), or to `1.0`.
+    pub fn line_height(mut self, line_height: f32) -> Self {
+        self.line_height = Some(line_height);
+        self
+    }
+
+    /// Set the extra space added after each character, in logical pixels.
+    ///
+    /// This is an absolute length, which can be negative. It defaults to `0.0`.
+    pub fn letter_spacing(mut self, letter_spacing: f32) -> Self {
+        self.letter_spacing = letter_spacing;
+        self
+    }
+
     pub fn disabled(mut self) -> Self {
         self.disabled = true;
         self
@@ -45,12 +106,20 @@ impl<State, Action> MasonryView<State, Action> for Prose {
     type ViewState = ();
 
     fn build(&self, _cx: &mut ViewCx) -> (WidgetPod<Self::Element>, Self::ViewState) {
-        let widget_pod = WidgetPod::new(
-            masonry::widget::Prose::new(self.label.clone())
-                .with_text_brush(self.text_brush.clone())
-                .with_text_alignment(self.alignment),
-        );
-        (widget_pod, ())
+        let mut widget = masonry::widget::Prose::new(self.label.clone());
+        if let Some(text_style) = &self.text_style {
+            widget = widget.with_text_style(text_style);
+        }
+        if let Some(line_height) = self.line_height {
+            widget = widget.with_line_height(line_height);
+        }
+        let widget = widget
+            .with_letter_spacing(self.letter_spacing)
+            .with_text_brush(self.text_brush.clone())
+            .with_text_alignment(self.alignment)
+            .with_text_direction(self.direction)
+            .with_selectable(self.selectable);
+        (WidgetPod::new(widget), ())
     }
 
     fn rebuild(
@@ -68,14 +137,45 @@ impl<State, Action> MasonryView<State, Action> for Prose {
         //     element.set_disabled(self.disabled);
         //     cx.mark_changed();
         // }
-        if prev.text_brush != self.text_brush {
+        if prev.text_style != self.text_style {
+            let default_style = TextStyle::default();
+            element.set_text_style(self.text_style.as_ref().unwrap_or(&default_style));
+            // The style resets the color and line height, which may have been overridden.
             element.set_text_brush(self.text_brush.clone());
+            if let Some(line_height) = self.line_height {
+                element.set_line_height(line_height);
+            }
+            cx.mark_changed();
+        } else {
+            if prev.text_brush != self.text_brush {
+                element.set_text_brush(self.text_brush.clone());
+                cx.mark_changed();
+            }
+            if prev.line_height != self.line_height {
+                let default_line_height = self
+                    .text_style
+                    .as_ref()
+                    .map_or(1.0, |style| style.line_height);
+                element.set_line_height(self.line_height.unwrap_or(default_line_height));
+                cx.mark_changed();
+            }
+        }
+        if prev.letter_spacing != self.letter_spacing {
+            element.set_letter_spacing(self.letter_spacing);
             cx.mark_changed();
         }
         if prev.alignment != self.alignment {
             element.set_alignment(self.alignment);
             cx.mark_changed();
         }
+        if prev.direction != self.direction {
+            element.set_text_direction(self.direction);
+            cx.mark_changed();
+        }
+        if prev.selectable != self.selectable {
+            element.set_selectable(self.selectable);
+            cx.mark_changed();
+        }
     }
 
     fn message(
@@ -0,0 +1,115 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+use std::ops::RangeInclusive;
+
+use masonry::{widget::WidgetMut, WidgetPod};
+
+use crate::{MasonryView, MessageResult, ViewCx, ViewId};
+
+/// A slider with two thumbs, selecting `range` out of the values in `bounds`.
+///
+/// `on_change` is called with the new range whenever the user moves a thumb. The thumbs
+/// can't cross each other.
+pub fn range_slider<F, State, Action>(
+    range: RangeInclusive<f64>,
+    bounds: RangeInclusive<f64>,
+    on_change: F,
+) -> RangeSlider<F>
+where
+    F: Fn(&mut State, RangeInclusive<f64>) -> Action + Send + 'static,
+{
+    RangeSlider {
+        range,
+        bounds,
+        step: None,
+        on_change,
+    }
+}
+
+pub struct RangeSlider<F> {
+    range: RangeInclusive<f64>,
+    bounds: RangeInclusive<f64>,
+    step: Option<f64>,
+    on_change: F,
+}
+
+impl<F> RangeSlider<F> {
+    /// Set how much the arrow keys move a thumb.
+    ///
+    /// By default, this is a hundredth of the bounds.
+    pub fn step(mut self, step: f64) -> Self {
+        self.step = Some(step);
+        self
+    }
+
+    fn resolved_step(&self) -> f64 {
+        self.step
+            .unwrap_or((self.bounds.end() - self.bounds.start()) / 100.)
+    }
+}
+
+impl<F, State, Action> MasonryView<State, Action> for RangeSlider<F>
+where
+    F: Fn(&mut State, RangeInclusive<f64>) -> Action + Send + Sync + 'static,
+{
+    type Element = masonry::widget::RangeSlider;
+    type ViewState = ();
+
+    fn build(&self, cx: &mut ViewCx) -> (WidgetPod<Self::Element>, Self::ViewState) {
+        cx.with_leaf_action_widget(|_| {
+            WidgetPod::new(
+                masonry::widget::RangeSlider::new(self.range.clone(), self.bounds.clone())
+                    .with_step(self.resolved_step()),
+            )
+        })
+    }
+
+    fn rebuild(
+        &self,
+        _view_state: &mut Self::ViewState,
+        cx: &mut ViewCx,
+        prev: &Self,
+        mut element: WidgetMut<Self::Element>,
+    ) {
+        if prev.bounds != self.bounds {
+            element.set_bounds(self.bounds.clone());
+            cx.mark_changed();
+        }
+        if prev.range != self.range {
+            element.set_range(self.range.clone());
+            cx.mark_changed();
+        }
+        if prev.resolved_step() != self.resolved_step() {
+            element.set_step(self.resolved_step());
+            cx.mark_changed();
+        }
+    }
+
+    fn message(
+        &self,
+        _view_state: &mut Self::ViewState,
+        id_path: &[ViewId],
+        message: Box<dyn std::any::Any>,
+        app_state: &mut State,
+    ) -> MessageResult<Action> {
+        debug_assert!(
+            id_path.is_empty(),
+            "id path should be empty in RangeSlider::message"
+        );
+        match message.downcast::<masonry::Action>() {
+            Ok(action) => {
+                if let masonry::Action::RangeChanged(range) = *action {
+                    MessageResult::Action((self.on_change)(app_state, range))
+                } else {
+                    tracing::error!("Wrong action type in RangeSlider::message: {action:?}");
+                    MessageResult::Stale(action)
+                }
+            }
+            Err(message) => {
+                tracing::error!("Wrong message type in RangeSlider::message");
+                MessageResult::Stale(message)
+            }
+        }
+    }
+}
@@ -0,0 +1,100 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+use masonry::{widget::WidgetMut, WidgetPod};
+
+use crate::{MasonryView, MessageResult, ViewCx, ViewId};
+
+/// A row of `max` stars showing `value`, which the user can click to change the rating.
+///
+/// `on_change` is called with the new value when a star is clicked.
+pub fn rating<F, State, Action>(value: f32, max: u32, on_change: F) -> Rating<F>
+where
+    F: Fn(&mut State, f32) -> Action + Send + 'static,
+{
+    Rating {
+        value,
+        max,
+        half_stars: false,
+        on_change,
+    }
+}
+
+pub struct Rating<F> {
+    value: f32,
+    max: u32,
+    half_stars: bool,
+    on_change: F,
+}
+
+impl<F> Rating<F> {
+    /// Set whether the user can pick half stars, by clicking the left half of a star.
+    pub fn half_stars(mut self, half_stars: bool) -> Self {
+        self.half_stars = half_stars;
+        self
+    }
+}
+
+impl<F, State, Action> MasonryView<State, Action> for Rating<F>
+where
+    F: Fn(&mut State, f32) -> Action + Send + Sync + 'static,
+{
+    type Element = masonry::widget::Rating;
+    type ViewState = ();
+
+    fn build(&self, cx: &mut ViewCx) -> (WidgetPod<Self::Element>, Self::ViewState) {
+        cx.with_leaf_action_widget(|_| {
+            WidgetPod::new(
+                masonry::widget::Rating::new(self.value, self.max).with_half_stars(self.half_stars),
+            )
+        })
+    }
+
+    fn rebuild(
+        &self,
+        _view_state: &mut Self::ViewState,
+        cx: &mut ViewCx,
+        prev: &Self,
+        mut element: WidgetMut<Self::Element>,
+    ) {
+        if prev.max != self.max {
+            element.set_max(self.max);
+            cx.mark_changed();
+        }
+        if prev.value != self.value {
+            element.set_value(self.value);
+            cx.mark_changed();
+        }
+        if prev.half_stars != self.half_stars {
+            element.set_half_stars(self.half_stars);
+            cx.mark_changed();
+        }
+    }
+
+    fn message(
+        &self,
+        _view_state: &mut Self::ViewState,
+        id_path: &[ViewId],
+        message: Box<dyn std::any::Any>,
+        app_state: &mut State,
+    ) -> MessageResult<Action> {
+        debug_assert!(
+            id_path.is_empty(),
+            "id path should be empty in Rating::message"
+        );
+        match message.downcast::<masonry::Action>() {
+            Ok(action) => {
+                if let masonry::Action::RatingChanged(value) = *action {
+                    MessageResult::Action((self.on_change)(app_state, value))
+                } else {
+                    tracing::error!("Wrong action type in Rating::message: {action:?}");
+                    MessageResult::Stale(action)
+                }
+            }
+            Err(message) => {
+                tracing::error!("Wrong message type in Rating::message");
+                MessageResult::Stale(message)
+            }
+        }
+    }
+}
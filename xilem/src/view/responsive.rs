@@ -0,0 +1,128 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+use masonry::{widget::WidgetMut, WidgetPod};
+
+use crate::{BreakpointClass, Breakpoints, MasonryView, MessageResult, ViewCx, ViewId};
+
+/// A view which shows `view_fn(class)`, where `class` is the [`BreakpointClass`] of the
+/// width available to it, which is the width of the window when it is the root view.
+///
+/// `view_fn` is called again whenever the view is rebuilt, and whenever the window is
+/// resized into another class. The width isn't known until the first layout, so the view
+/// is built as `view_fn(BreakpointClass::Xs)`. The class reported by that layout is
+/// handled before the first frame is painted, so the window never shows the `Xs` layout
+/// when it's wider.
+///
+/// `view_fn` doesn't receive the app state, since views are rebuilt without access to it.
+/// Data it needs from the state can be captured by the closure, which is created again
+/// each time the app logic runs.
+pub fn responsive<V, F>(breakpoints: Breakpoints, view_fn: F) -> Responsive<F>
+where
+    F: Fn(BreakpointClass) -> V,
+{
+    Responsive {
+        breakpoints,
+        view_fn,
+    }
+}
+
+pub struct Responsive<F> {
+    breakpoints: Breakpoints,
+    view_fn: F,
+}
+
+pub struct ResponsiveState<V, ViewState> {
+    /// The class last reported by the widget.
+    class: BreakpointClass,
+    child: V,
+    child_state: ViewState,
+}
+
+impl<State, Action, V, F> MasonryView<State, Action> for Responsive<F>
+where
+    V: MasonryView<State, Action>,
+    F: Fn(BreakpointClass) -> V + Send + Sync + 'static,
+{
+    type Element = masonry::widget::Responsive<V::Element>;
+    type ViewState = ResponsiveState<V, V::ViewState>;
+
+    fn build(&self, cx: &mut ViewCx) -> (WidgetPod<Self::Element>, Self::ViewState) {
+        let class = BreakpointClass::Xs;
+        let child = (self.view_fn)(class);
+        let mut child_state = None;
+        let element = cx.with_action_widget(|cx| {
+            let (child_pod, state) = cx.with_id(ViewId::for_type::<V>(0), |cx| child.build(cx));
+            child_state = Some(state);
+            WidgetPod::new(masonry::widget::Responsive::from_child_pod(
+                child_pod,
+                self.breakpoints,
+            ))
+        });
+        let view_state = ResponsiveState {
+            class,
+            child,
+            child_state: child_state.unwrap(),
+        };
+        (element, view_state)
+    }
+
+    fn rebuild(
+        &self,
+        view_state: &mut Self::ViewState,
+        cx: &mut ViewCx,
+        prev: &Self,
+        mut element: WidgetMut<Self::Element>,
+    ) {
+        if prev.breakpoints != self.breakpoints {
+            element.set_breakpoints(self.breakpoints);
+            cx.mark_changed();
+        }
+        let child = (self.view_fn)(view_state.class);
+        cx.with_id(ViewId::for_type::<V>(0), |cx| {
+            child.rebuild(
+                &mut view_state.child_state,
+                cx,
+                &view_state.child,
+                element.child_mut(),
+            );
+        });
+        view_state.child = child;
+    }
+
+    fn message(
+        &self,
+        view_state: &mut Self::ViewState,
+        id_path: &[ViewId],
+        message: Box<dyn std::any::Any>,
+        app_state: &mut State,
+    ) -> MessageResult<Action> {
+        if let Some((first, rest)) = id_path.split_first() {
+            if first.routing_id() != 0 {
+                return MessageResult::Stale(message);
+            }
+            return view_state
+                .child
+                .message(&mut view_state.child_state, rest, message, app_state);
+        }
+        match message.downcast::<masonry::Action>() {
+            Ok(action) => {
+                if let masonry::Action::BreakpointChanged(class) = *action {
+                    if class == view_state.class {
+                        MessageResult::Nop
+                    } else {
+                        view_state.class = class;
+                        MessageResult::RequestRebuild
+                    }
+                } else {
+                    tracing::error!("Wrong action type in Responsive::message: {action:?}");
+                    MessageResult::Stale(action)
+                }
+            }
+            Err(message) => {
+                tracing::error!("Wrong message type in Responsive::message");
+                MessageResult::Stale(message)
+            }
+        }
+    }
+}
@@ -0,0 +1,125 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+use masonry::{
+    text2::{StyledText, TextStyle},
+    widget::{LineBreaking, WidgetMut},
+    WidgetPod,
+};
+
+use crate::{Color, MasonryView, MessageResult, TextAlignment, ViewCx, ViewId};
+
+/// Display text made of differently styled spans, such as bold or colored words.
+///
+/// The text can be built from a list of `(text, style)` pairs:
+///
+/// ```ignore
+/// rich_text(vec![
+///     ("@alice".to_string(), TextSpanStyle::default().bold().color(Color::LIGHT_BLUE)),
+///     (" thanks!".to_string(), TextSpanStyle::default()),
+/// ])
+/// ```
+pub fn rich_text(spans: impl Into<StyledText>) -> RichText {
+    RichText {
+        spans: spans.into(),
+        text_color: Color::WHITE,
+        text_style: None,
+        alignment: TextAlignment::default(),
+        line_break_mode: LineBreaking::Overflow,
+    }
+}
+
+pub struct RichText {
+    spans: StyledText,
+    text_color: Color,
+    text_style: Option<TextStyle>,
+    alignment: TextAlignment,
+    line_break_mode: LineBreaking,
+}
+
+impl RichText {
+    /// Set the color of the spans which don't have their own.
+    pub fn color(mut self, color: Color) -> Self {
+        self.text_color = color;
+        self
+    }
+
+    /// Set the font, size, weight, color and line height of the spans which don't have
+    /// their own.
+    ///
+    /// The color can be overridden by calling [`color`](Self::color) afterwards.
+    pub fn text_style(mut self, text_style: TextStyle) -> Self {
+        self.text_color = text_style.color;
+        self.text_style = Some(text_style);
+        self
+    }
+
+    pub fn alignment(mut self, alignment: TextAlignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    pub fn line_break_mode(mut self, line_break_mode: LineBreaking) -> Self {
+        self.line_break_mode = line_break_mode;
+        self
+    }
+}
+
+impl<State, Action> MasonryView<State, Action> for RichText {
+    type Element = masonry::widget::RichText;
+    type ViewState = ();
+
+    fn build(&self, _cx: &mut ViewCx) -> (WidgetPod<Self::Element>, Self::ViewState) {
+        let mut widget = masonry::widget::RichText::new(self.spans.clone());
+        if let Some(text_style) = &self.text_style {
+            widget = widget.with_text_style(text_style);
+        }
+        let widget = widget
+            .with_text_brush(self.text_color)
+            .with_text_alignment(self.alignment)
+            .with_line_break_mode(self.line_break_mode);
+        (WidgetPod::new(widget), ())
+    }
+
+    fn rebuild(
+        &self,
+        _view_state: &mut Self::ViewState,
+        cx: &mut ViewCx,
+        prev: &Self,
+        mut element: WidgetMut<Self::Element>,
+    ) {
+        if prev.spans != self.spans {
+            element.set_text(self.spans.clone());
+            cx.mark_changed();
+        }
+        if prev.text_style != self.text_style {
+            let default_style = TextStyle::default();
+            element.set_text_style(self.text_style.as_ref().unwrap_or(&default_style));
+            // The style resets the color, which may have been overridden.
+            element.set_text_brush(self.text_color);
+            cx.mark_changed();
+        } else if prev.text_color != self.text_color {
+            element.set_text_brush(self.text_color);
+            cx.mark_changed();
+        }
+        if prev.alignment != self.alignment {
+            element.set_alignment(self.alignment);
+            cx.mark_changed();
+        }
+        if prev.line_break_mode != self.line_break_mode {
+            element.set_line_break_mode(self.line_break_mode);
+            cx.mark_changed();
+        }
+    }
+
+    fn message(
+        &self,
+        _view_state: &mut Self::ViewState,
+        _id_path: &[ViewId],
+        message: Box<dyn std::any::Any>,
+        _app_state: &mut State,
+    ) -> crate::MessageResult<Action> {
+        tracing::error!("Message arrived in RichText::message, but RichText doesn't consume any messages, this is a bug");
+        MessageResult::Stale(message)
+    }
+}
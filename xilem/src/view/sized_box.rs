@@ -0,0 +1,266 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+use masonry::{widget::WidgetMut, Border, BoxShadow, Insets, WidgetPod};
+
+use crate::{MasonryView, MessageResult, ViewCx, ViewId};
+
+/// Force `child` to the given width and/or height.
+///
+/// Pass `None` to let the child choose its size in that dimension.
+/// The constraints of the parent take precedence over the given size.
+pub fn sized_box<V>(width: Option<f64>, height: Option<f64>, child: V) -> SizedBox<V> {
+    SizedBox {
+        width,
+        height,
+        ..SizedBox::new(child)
+    }
+}
+
+/// Keep `child` at least this wide and/or tall.
+///
+/// Pass `None` to leave a dimension unconstrained.
+/// This can be combined with [`max_size`] by nesting the two views.
+pub fn min_size<V>(width: Option<f64>, height: Option<f64>, child: V) -> SizedBox<V> {
+    SizedBox {
+        min_size: (width, height),
+        ..SizedBox::new(child)
+    }
+}
+
+/// Keep `child` at most this wide and/or tall.
+///
+/// Pass `None` to leave a dimension unconstrained.
+/// This can be combined with [`min_size`] by nesting the two views.
+pub fn max_size<V>(width: Option<f64>, height: Option<f64>, child: V) -> SizedBox<V> {
+    SizedBox {
+        max_size: (width, height),
+        ..SizedBox::new(child)
+    }
+}
+
+/// Add empty space between the edges of this view and `child`.
+///
+/// The amount can be a uniform value, a `(horizontal, vertical)` pair,
+/// or `(left, top, right, bottom)` values.
+pub fn padding<V>(amount: impl Into<Insets>, child: V) -> SizedBox<V> {
+    SizedBox {
+        padding: amount.into(),
+        ..SizedBox::new(child)
+    }
+}
+
+/// Add empty space around `child`, pushing away its neighbours.
+///
+/// The amount can be a uniform value, a `(horizontal, vertical)` pair,
+/// or `(left, top, right, bottom)` values.
+///
+/// Unlike the result of [`padding`], the result can't be given a border, so the space
+/// always stays outside of everything `child` paints. See also [`SizedBox::with_margin`].
+pub fn margin<V>(amount: impl Into<Insets>, child: V) -> Margin<V> {
+    Margin(padding(amount, child))
+}
+
+/// Adds [`with_padding`](Self::with_padding) to views.
+pub trait PaddingExt: Sized {
+    /// Surround this view with empty space, e.g.
+    /// [`Padding::axes(8., 4.)`](crate::Padding::axes).
+    ///
+    /// This wraps the view in a [`padding`] view, so the space is inside the edges of
+    /// the wrapper, and outside of this view.
+    fn with_padding(self, amount: impl Into<Insets>) -> SizedBox<Self> {
+        padding(amount, self)
+    }
+}
+
+impl<V: Send + Sync + 'static> PaddingExt for V {}
+
+pub struct SizedBox<V> {
+    width: Option<f64>,
+    height: Option<f64>,
+    min_size: (Option<f64>, Option<f64>),
+    max_size: (Option<f64>, Option<f64>),
+    padding: Insets,
+    border: Option<Border>,
+    shadows: Vec<BoxShadow>,
+    blur: f64,
+    child: V,
+}
+
+impl<V> SizedBox<V> {
+    fn new(child: V) -> Self {
+        SizedBox {
+            width: None,
+            height: None,
+            min_size: (None, None),
+            max_size: (None, None),
+            padding: Insets::ZERO,
+            border: None,
+            shadows: Vec::new(),
+            blur: 0.0,
+            child,
+        }
+    }
+
+    /// Paint a border around the child, outside of the padding.
+    pub fn with_border(mut self, border: Border) -> Self {
+        self.border = Some(border);
+        self
+    }
+
+    /// Add a shadow behind the box.
+    ///
+    /// This can be called several times to stack shadows.
+    pub fn with_shadow(mut self, shadow: BoxShadow) -> Self {
+        self.shadows.push(shadow);
+        self
+    }
+
+    /// Blur the painted content of the box, including its child, by `radius` pixels.
+    ///
+    /// Only the box itself is blurred, not the views painted behind it, so a
+    /// semi-transparent blurred overlay doesn't give a "frosted glass" effect by itself.
+    /// Blurring is expensive, and best kept to small views.
+    pub fn with_blur(mut self, radius: f64) -> Self {
+        self.blur = radius;
+        self
+    }
+
+    /// Add empty space around this box, outside of its border.
+    ///
+    /// See [`margin`].
+    pub fn with_margin(self, amount: impl Into<Insets>) -> Margin<Self> {
+        margin(amount, self)
+    }
+}
+
+impl<V, State, Action> MasonryView<State, Action> for SizedBox<V>
+where
+    V: MasonryView<State, Action>,
+{
+    type Element = masonry::widget::SizedBox;
+    type ViewState = V::ViewState;
+
+    fn build(&self, cx: &mut ViewCx) -> (WidgetPod<Self::Element>, Self::ViewState) {
+        let (child, child_state) = self.child.build(cx);
+        let mut widget = masonry::widget::SizedBox::from_child_pod(child.boxed())
+            .with_min_size(self.min_size.0, self.min_size.1)
+            .with_max_size(self.max_size.0, self.max_size.1)
+            .with_padding(self.padding);
+        if let Some(width) = self.width {
+            widget = widget.width(width);
+        }
+        if let Some(height) = self.height {
+            widget = widget.height(height);
+        }
+        if let Some(border) = self.border {
+            widget = widget.with_border(border);
+        }
+        for shadow in &self.shadows {
+            widget = widget.with_shadow(*shadow);
+        }
+        (WidgetPod::new(widget).with_blur(self.blur), child_state)
+    }
+
+    fn rebuild(
+        &self,
+        view_state: &mut Self::ViewState,
+        cx: &mut ViewCx,
+        prev: &Self,
+        mut element: WidgetMut<Self::Element>,
+    ) {
+        if prev.width != self.width {
+            match self.width {
+                Some(width) => element.set_width(width),
+                None => element.unset_width(),
+            }
+            cx.mark_changed();
+        }
+        if prev.height != self.height {
+            match self.height {
+                Some(height) => element.set_height(height),
+                None => element.unset_height(),
+            }
+            cx.mark_changed();
+        }
+        if prev.min_size != self.min_size {
+            element.set_min_size(self.min_size.0, self.min_size.1);
+            cx.mark_changed();
+        }
+        if prev.max_size != self.max_size {
+            element.set_max_size(self.max_size.0, self.max_size.1);
+            cx.mark_changed();
+        }
+        if prev.padding != self.padding {
+            element.set_padding(self.padding);
+            cx.mark_changed();
+        }
+        if prev.border != self.border {
+            match self.border {
+                Some(border) => element.set_border_to(border),
+                None => element.clear_border(),
+            }
+            cx.mark_changed();
+        }
+        if prev.shadows != self.shadows {
+            element.set_shadows(self.shadows.clone());
+            cx.mark_changed();
+        }
+        if prev.blur != self.blur {
+            element.ctx.set_blur(self.blur);
+            cx.mark_changed();
+        }
+        let mut child = element
+            .child_mut()
+            .expect("SizedBox element should have a child");
+        let child = child
+            .try_downcast::<V::Element>()
+            .expect("SizedBox child should have the child view's element type");
+        self.child.rebuild(view_state, cx, &prev.child, child);
+    }
+
+    fn message(
+        &self,
+        view_state: &mut Self::ViewState,
+        id_path: &[ViewId],
+        message: Box<dyn std::any::Any>,
+        app_state: &mut State,
+    ) -> MessageResult<Action> {
+        self.child.message(view_state, id_path, message, app_state)
+    }
+}
+
+/// The view returned by [`margin`].
+pub struct Margin<V>(SizedBox<V>);
+
+impl<V, State, Action> MasonryView<State, Action> for Margin<V>
+where
+    V: MasonryView<State, Action>,
+{
+    type Element = masonry::widget::SizedBox;
+    type ViewState = V::ViewState;
+
+    fn build(&self, cx: &mut ViewCx) -> (WidgetPod<Self::Element>, Self::ViewState) {
+        self.0.build(cx)
+    }
+
+    fn rebuild(
+        &self,
+        view_state: &mut Self::ViewState,
+        cx: &mut ViewCx,
+        prev: &Self,
+        element: WidgetMut<Self::Element>,
+    ) {
+        self.0.rebuild(view_state, cx, &prev.0, element);
+    }
+
+    fn message(
+        &self,
+        view_state: &mut Self::ViewState,
+        id_path: &[ViewId],
+        message: Box<dyn std::any::Any>,
+        app_state: &mut State,
+    ) -> MessageResult<Action> {
+        self.0.message(view_state, id_path, message, app_state)
+    }
+}
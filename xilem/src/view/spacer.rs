@@ -0,0 +1,88 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+use masonry::{widget::WidgetMut, WidgetPod};
+
+use crate::{MasonryView, MessageResult, ViewCx, ViewId};
+
+/// Empty space which is `len` long along the main axis of a [`flex`](crate::view::flex),
+/// and takes no space across it.
+pub fn spacer(len: f64) -> Spacer {
+    Spacer { len }
+}
+
+/// Empty space which grows to fill what is left over in a [`flex`](crate::view::flex),
+/// pushing the views on either side of it apart.
+///
+/// Several fillers in the same container share the space equally.
+pub fn filler() -> Filler {
+    Filler
+}
+
+pub struct Spacer {
+    len: f64,
+}
+
+pub struct Filler;
+
+impl<State, Action> MasonryView<State, Action> for Spacer {
+    type Element = masonry::widget::Spacer;
+    type ViewState = ();
+
+    fn build(&self, _cx: &mut ViewCx) -> (WidgetPod<Self::Element>, Self::ViewState) {
+        (WidgetPod::new(masonry::widget::Spacer::new(self.len)), ())
+    }
+
+    fn rebuild(
+        &self,
+        _view_state: &mut Self::ViewState,
+        cx: &mut ViewCx,
+        prev: &Self,
+        mut element: WidgetMut<Self::Element>,
+    ) {
+        if prev.len != self.len {
+            element.set_len(self.len);
+            cx.mark_changed();
+        }
+    }
+
+    fn message(
+        &self,
+        _view_state: &mut Self::ViewState,
+        _id_path: &[ViewId],
+        message: Box<dyn std::any::Any>,
+        _app_state: &mut State,
+    ) -> MessageResult<Action> {
+        tracing::error!("Message arrived in Spacer::message, but Spacer doesn't consume any messages, this is a bug");
+        MessageResult::Stale(message)
+    }
+}
+
+impl<State, Action> MasonryView<State, Action> for Filler {
+    type Element = masonry::widget::Spacer;
+    type ViewState = ();
+
+    fn build(&self, _cx: &mut ViewCx) -> (WidgetPod<Self::Element>, Self::ViewState) {
+        (WidgetPod::new(masonry::widget::Spacer::flex(1.0)), ())
+    }
+
+    fn rebuild(
+        &self,
+        _view_state: &mut Self::ViewState,
+        _cx: &mut ViewCx,
+        _prev: &Self,
+        _element: WidgetMut<Self::Element>,
+    ) {
+    }
+
+    fn message(
+        &self,
+        _view_state: &mut Self::ViewState,
+        _id_path: &[ViewId],
+        message: Box<dyn std::any::Any>,
+        _app_state: &mut State,
+    ) -> MessageResult<Action> {
+        tracing::error!("Message arrived in Filler::message, but Filler doesn't consume any messages, this is a bug");
+        MessageResult::Stale(message)
+    }
+}
@@ -0,0 +1,62 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+use masonry::{widget::WidgetMut, WidgetPod};
+
+use crate::{Color, MasonryView, MessageResult, ViewCx, ViewId};
+
+/// An animated spinner, for showing that something is loading.
+///
+/// The spinner only requests animation frames while it is part of the widget tree.
+pub fn spinner() -> Spinner {
+    Spinner { color: None }
+}
+
+pub struct Spinner {
+    color: Option<Color>,
+}
+
+impl Spinner {
+    /// Set the color of the spinner.
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+}
+
+impl<State, Action> MasonryView<State, Action> for Spinner {
+    type Element = masonry::widget::Spinner;
+    type ViewState = ();
+
+    fn build(&self, _cx: &mut ViewCx) -> (WidgetPod<Self::Element>, Self::ViewState) {
+        let mut widget = masonry::widget::Spinner::new();
+        if let Some(color) = self.color {
+            widget = widget.with_color(color);
+        }
+        (WidgetPod::new(widget), ())
+    }
+
+    fn rebuild(
+        &self,
+        _view_state: &mut Self::ViewState,
+        cx: &mut ViewCx,
+        prev: &Self,
+        mut element: WidgetMut<Self::Element>,
+    ) {
+        if prev.color != self.color {
+            element.set_color(self.color.unwrap_or(masonry::theme::TEXT_COLOR));
+            cx.mark_changed();
+        }
+    }
+
+    fn message(
+        &self,
+        _view_state: &mut Self::ViewState,
+        _id_path: &[ViewId],
+        message: Box<dyn std::any::Any>,
+        _app_state: &mut State,
+    ) -> crate::MessageResult<Action> {
+        tracing::error!("Message arrived in Spinner::message, but Spinner doesn't consume any messages, this is a bug");
+        MessageResult::Stale(message)
+    }
+}
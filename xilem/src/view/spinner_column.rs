@@ -0,0 +1,93 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+use masonry::{widget::WidgetMut, WidgetPod};
+
+use crate::{MasonryView, MessageResult, ViewCx, ViewId};
+
+/// A column of items which scrolls so that the selected item is in the middle, like the
+/// wheels of a mobile time picker.
+///
+/// `on_change` is called with the index of the newly selected item whenever the user
+/// changes the selection.
+pub fn spinner_column<F, State, Action>(
+    items: &[String],
+    selected: usize,
+    on_change: F,
+) -> SpinnerColumn<F>
+where
+    F: Fn(&mut State, usize) -> Action + Send + 'static,
+{
+    SpinnerColumn {
+        items: items.to_vec(),
+        selected,
+        on_change,
+    }
+}
+
+pub struct SpinnerColumn<F> {
+    items: Vec<String>,
+    selected: usize,
+    on_change: F,
+}
+
+impl<F, State, Action> MasonryView<State, Action> for SpinnerColumn<F>
+where
+    F: Fn(&mut State, usize) -> Action + Send + Sync + 'static,
+{
+    type Element = masonry::widget::SpinnerColumn;
+    type ViewState = ();
+
+    fn build(&self, cx: &mut ViewCx) -> (WidgetPod<Self::Element>, Self::ViewState) {
+        cx.with_leaf_action_widget(|_| {
+            WidgetPod::new(masonry::widget::SpinnerColumn::new(
+                self.items.iter().map(String::as_str),
+                self.selected,
+            ))
+        })
+    }
+
+    fn rebuild(
+        &self,
+        _view_state: &mut Self::ViewState,
+        cx: &mut ViewCx,
+        prev: &Self,
+        mut element: WidgetMut<Self::Element>,
+    ) {
+        if prev.items != self.items {
+            element.set_items(self.items.iter().map(String::as_str));
+            cx.mark_changed();
+        }
+        if prev.selected != self.selected {
+            element.set_selected(self.selected);
+            cx.mark_changed();
+        }
+    }
+
+    fn message(
+        &self,
+        _view_state: &mut Self::ViewState,
+        id_path: &[ViewId],
+        message: Box<dyn std::any::Any>,
+        app_state: &mut State,
+    ) -> MessageResult<Action> {
+        debug_assert!(
+            id_path.is_empty(),
+            "id path should be empty in SpinnerColumn::message"
+        );
+        match message.downcast::<masonry::Action>() {
+            Ok(action) => {
+                if let masonry::Action::SpinnerColumnSelected(idx) = *action {
+                    MessageResult::Action((self.on_change)(app_state, idx))
+                } else {
+                    tracing::error!("Wrong action type in SpinnerColumn::message: {action:?}");
+                    MessageResult::Stale(action)
+                }
+            }
+            Err(message) => {
+                tracing::error!("Wrong message type in SpinnerColumn::message");
+                MessageResult::Stale(message)
+            }
+        }
+    }
+}
@@ -0,0 +1,98 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+use masonry::{widget::WidgetMut, ArcStr, WidgetPod};
+
+use crate::{MasonryView, MessageResult, ViewCx, ViewId};
+
+/// A list of removable tags showing `values`, with a text box to add tags, which suggests
+/// the `suggestions` starting with the typed text.
+///
+/// Typing a comma or pressing Enter adds a tag, and Backspace in the empty text box
+/// removes the last one. `on_change` is called with all the tags when the user changes them.
+pub fn tag_input<F, State, Action>(
+    values: &[String],
+    suggestions: &[String],
+    on_change: F,
+) -> TagInput<F>
+where
+    F: Fn(&mut State, Vec<String>) -> Action + Send + 'static,
+{
+    TagInput {
+        values: values.iter().map(|value| value.as_str().into()).collect(),
+        suggestions: suggestions
+            .iter()
+            .map(|suggestion| suggestion.as_str().into())
+            .collect(),
+        on_change,
+    }
+}
+
+pub struct TagInput<F> {
+    values: Vec<ArcStr>,
+    suggestions: Vec<ArcStr>,
+    on_change: F,
+}
+
+impl<F, State, Action> MasonryView<State, Action> for TagInput<F>
+where
+    F: Fn(&mut State, Vec<String>) -> Action + Send + Sync + 'static,
+{
+    type Element = masonry::widget::TagInput;
+    type ViewState = ();
+
+    fn build(&self, cx: &mut ViewCx) -> (WidgetPod<Self::Element>, Self::ViewState) {
+        cx.with_leaf_action_widget(|_| {
+            WidgetPod::new(masonry::widget::TagInput::new(
+                self.values.iter().cloned(),
+                self.suggestions.iter().cloned(),
+            ))
+        })
+    }
+
+    fn rebuild(
+        &self,
+        _view_state: &mut Self::ViewState,
+        cx: &mut ViewCx,
+        prev: &Self,
+        mut element: WidgetMut<Self::Element>,
+    ) {
+        // Like in `Textbox`, we compare to the element's tags rather than to the
+        // previous value, since the user changes them without a rebuild.
+        if !element.widget.tags().eq(&self.values) {
+            element.set_tags(self.values.iter().cloned());
+            cx.mark_changed();
+        }
+        if prev.suggestions != self.suggestions {
+            element.set_suggestions(self.suggestions.iter().cloned());
+            cx.mark_changed();
+        }
+    }
+
+    fn message(
+        &self,
+        _view_state: &mut Self::ViewState,
+        id_path: &[ViewId],
+        message: Box<dyn std::any::Any>,
+        app_state: &mut State,
+    ) -> MessageResult<Action> {
+        debug_assert!(
+            id_path.is_empty(),
+            "id path should be empty in TagInput::message"
+        );
+        match message.downcast::<masonry::Action>() {
+            Ok(action) => {
+                if let masonry::Action::TagsChanged(tags) = *action {
+                    MessageResult::Action((self.on_change)(app_state, tags))
+                } else {
+                    tracing::error!("Wrong action type in TagInput::message: {action:?}");
+                    MessageResult::Stale(action)
+                }
+            }
+            Err(message) => {
+                tracing::error!("Wrong message type in TagInput::message");
+                MessageResult::Stale(message)
+            }
+        }
+    }
+}
@@ -0,0 +1,570 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::VecDeque;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, OnceLock, PoisonError, TryLockError};
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread::{self, Thread};
+
+use masonry::{widget::WidgetMut, WidgetId, WidgetPod};
+
+use crate::{MasonryView, MessageResult, ViewCx, ViewId};
+
+/// Run the future returned by `make_future` in the background, and call `on_done`
+/// with its output once it completes.
+///
+/// The future is started when the view is first built, and isn't restarted when the
+/// view is rebuilt. If the view is removed from the tree before the future completes,
+/// the future is dropped without being polled again, and `on_done` is never called.
+///
+/// Futures are polled by a few threads shared by all tasks, without an async runtime.
+/// This means that:
+/// - The future mustn't rely on a specific runtime. In particular, the I/O and timers of
+///   `tokio` panic outside of a `tokio` runtime. Futures which only wait on channels,
+///   locks, or other futures woken through their [`Waker`] are supported.
+/// - The future mustn't block its thread for long between two `.await`s, since it
+///   would delay the other tasks.
+/// - A removed task is dropped right away, unless it's being polled at that moment, in
+///   which case it's dropped once that poll returns, or at the latest when it's next
+///   woken.
+/// - If the future panics, the panic is logged, the future is dropped, and `on_done`
+///   is never called. The other tasks keep running.
+///
+/// Blocking work, and futures which need a runtime of their own, should be run with
+/// [`run_blocking`], which runs them on a thread of their own:
+///
+/// ```ignore
+/// task(
+///     move || {
+///         let runtime = runtime.clone();
+///         run_blocking(move || runtime.block_on(fetch_weather()))
+///     },
+///     |state: &mut AppState, weather| state.weather = Some(weather),
+/// )
+/// ```
+///
+/// To start a task from a callback, such as a button press, set a field of the app state
+/// in the callback and include the task in the view tree depending on it. Use [`task_for`]
+/// to restart the task whenever some input changes.
+///
+/// This view doesn't display anything.
+pub fn task<F, Fut, OnDone, State, Action>(
+    make_future: F,
+    on_done: OnDone,
+) -> Task<F, OnDone, Fut::Output>
+where
+    F: Fn() -> Fut + Send + Sync + 'static,
+    Fut: Future + Send + 'static,
+    Fut::Output: Send + 'static,
+    OnDone: Fn(&mut State, Fut::Output) -> Action + Send + Sync + 'static,
+{
+    Task {
+        make_future,
+        on_done,
+        phantom: PhantomData,
+    }
+}
+
+pub struct Task<F, OnDone, Output> {
+    make_future: F,
+    on_done: OnDone,
+    phantom: PhantomData<fn() -> Output>,
+}
+
+/// Run the future returned by `make_future` for `input` in the background, and call
+/// `on_done` with its output once it completes.
+///
+/// This behaves like [`task`], except that whenever `input` changes, the running future
+/// is cancelled and a new one is started for the new input. The output of a cancelled
+/// future is never passed to `on_done`, even if it had already completed.
+///
+/// The future is polled without an async runtime, like the one of [`task`], so it mustn't
+/// use the I/O or timers of `tokio`, nor block between two `.await`s. Run such work with
+/// [`run_blocking`] instead:
+///
+/// ```ignore
+/// fn app_logic(state: &mut AppState) -> impl MasonryView<AppState> {
+///     flex((
+///         textbox(state.query.clone(), |state: &mut AppState, query| state.query = query),
+///         task_for(
+///             state.query.clone(),
+///             |query| search(query.clone()),
+///             |state: &mut AppState, results| state.results = results,
+///         ),
+///     ))
+/// }
+/// ```
+pub fn task_for<D, F, Fut, OnDone, State, Action>(
+    input: D,
+    make_future: F,
+    on_done: OnDone,
+) -> TaskFor<D, F, OnDone, Fut::Output>
+where
+    D: PartialEq + Send + Sync + 'static,
+    F: Fn(&D) -> Fut + Send + Sync + 'static,
+    Fut: Future + Send + 'static,
+    Fut::Output: Send + 'static,
+    OnDone: Fn(&mut State, Fut::Output) -> Action + Send + Sync + 'static,
+{
+    TaskFor {
+        input,
+        make_future,
+        on_done,
+        phantom: PhantomData,
+    }
+}
+
+pub struct TaskFor<D, F, OnDone, Output> {
+    input: D,
+    make_future: F,
+    on_done: OnDone,
+    phantom: PhantomData<fn() -> Output>,
+}
+
+pub struct TaskForState {
+    handle: FutureHandle,
+    target: WidgetId,
+    /// Incremented each time the task is restarted, to recognise the output of
+    /// cancelled futures.
+    generation: u64,
+}
+
+/// The handle to a thread run in the background by a view, such as the one of a
+/// [`timer`](super::timer), which stops the thread when dropped.
+pub struct TaskHandle {
+    cancelled: Arc<AtomicBool>,
+    thread: Thread,
+}
+
+impl TaskHandle {
+    /// Run `f` on a new thread.
+    ///
+    /// When the handle is dropped, the flag passed to `f` is set and the thread is unparked,
+    /// so `f` should check the flag whenever it wakes up.
+    pub(crate) fn spawn(f: impl FnOnce(&AtomicBool) + Send + 'static) -> Self {
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let thread = {
+            let cancelled = cancelled.clone();
+            thread::Builder::new()
+                .name("xilem task".into())
+                .spawn(move || f(&cancelled))
+                .expect("failed to spawn task thread")
+                .thread()
+                .clone()
+        };
+        TaskHandle { cancelled, thread }
+    }
+
+    /// Wake the thread up, for it to check the state it's waiting on again.
+    pub(crate) fn unpark(&self) {
+        self.thread.unpark();
+    }
+}
+
+impl Drop for TaskHandle {
+    fn drop(&mut self) {
+        self.cancelled.store(true, Ordering::Release);
+        self.thread.unpark();
+    }
+}
+
+/// A future spawned by [`spawn_future`], polled by the shared [`Executor`].
+struct Job {
+    future: Mutex<Option<Pin<Box<dyn Future<Output = ()> + Send>>>>,
+    /// Whether the job is in the queue of the executor, to only queue it once.
+    queued: AtomicBool,
+    cancelled: AtomicBool,
+}
+
+impl Wake for Job {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        if !self.queued.swap(true, Ordering::AcqRel) {
+            executor().push(self.clone());
+        }
+    }
+}
+
+impl Job {
+    /// Poll the future once, dropping it if it completes, panics or was cancelled.
+    fn run(self: Arc<Self>) {
+        self.queued.store(false, Ordering::Release);
+        let mut future = lock(&self.future);
+        let Some(running) = future.as_mut() else {
+            return;
+        };
+        if self.cancelled.load(Ordering::Acquire) {
+            *future = None;
+            return;
+        }
+        let waker = Waker::from(self.clone());
+        // A panicking future mustn't take down the executor's thread with it.
+        let done = match panic::catch_unwind(AssertUnwindSafe(|| {
+            running.as_mut().poll(&mut Context::from_waker(&waker))
+        })) {
+            Ok(poll) => poll.is_ready(),
+            Err(payload) => {
+                tracing::error!("Task panicked: {}", panic_message(&*payload));
+                true
+            }
+        };
+        // The handle may have been dropped while the future was being polled.
+        if done || self.cancelled.load(Ordering::Acquire) {
+            *future = None;
+        }
+    }
+}
+
+/// Lock `mutex`, even if a thread panicked while holding it.
+///
+/// The data guarded by the executor's locks stays consistent when a task panics, as the
+/// panic is caught while polling the task.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// The message of a panic with the given payload, if it has one.
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> &str {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message
+    } else {
+        "Box<dyn Any>"
+    }
+}
+
+/// The threads polling the futures of all tasks.
+struct Executor {
+    queue: Mutex<VecDeque<Arc<Job>>>,
+    available: Condvar,
+}
+
+/// The shared executor, starting its threads the first time it's used.
+fn executor() -> &'static Executor {
+    static EXECUTOR: OnceLock<Executor> = OnceLock::new();
+    EXECUTOR.get_or_init(|| {
+        let threads = thread::available_parallelism().map_or(2, |n| n.get().clamp(2, 4));
+        for _ in 0..threads {
+            thread::Builder::new()
+                .name("xilem task".into())
+                .spawn(|| executor().work())
+                .expect("failed to spawn task thread");
+        }
+        Executor {
+            queue: Mutex::new(VecDeque::new()),
+            available: Condvar::new(),
+        }
+    })
+}
+
+impl Executor {
+    fn push(&self, job: Arc<Job>) {
+        lock(&self.queue).push_back(job);
+        self.available.notify_one();
+    }
+
+    fn work(&self) -> ! {
+        loop {
+            let job = {
+                let mut queue = lock(&self.queue);
+                loop {
+                    match queue.pop_front() {
+                        Some(job) => break job,
+                        None => {
+                            queue = self
+                                .available
+                                .wait(queue)
+                                .unwrap_or_else(PoisonError::into_inner);
+                        }
+                    }
+                }
+            };
+            job.run();
+        }
+    }
+}
+
+/// The handle to the future of a [`task`] or [`task_for`] view, polled by the threads
+/// shared by all tasks, which drops the future when the handle is dropped.
+pub struct FutureHandle {
+    job: Arc<Job>,
+}
+
+impl Drop for FutureHandle {
+    fn drop(&mut self) {
+        self.job.cancelled.store(true, Ordering::Release);
+        // If the future is being polled, the executor drops it after the poll instead.
+        match self.job.future.try_lock() {
+            Ok(mut future) => *future = None,
+            Err(TryLockError::Poisoned(err)) => *err.into_inner() = None,
+            Err(TryLockError::WouldBlock) => {}
+        }
+    }
+}
+
+/// Run `f` on a new thread, returning a future which completes with its result.
+///
+/// This lets a [`task`] or [`task_for`] do blocking work without holding up the threads
+/// shared by all tasks. It's also the way to run a future which needs an async runtime,
+/// such as `tokio`, by blocking on it in `f` with a runtime of your own.
+///
+/// If `f` panics, the panic is resumed when the returned future is polled.
+pub fn run_blocking<T: Send + 'static>(
+    f: impl FnOnce() -> T + Send + 'static,
+) -> impl Future<Output = T> {
+    let shared = Arc::new(Mutex::new((None, None::<Waker>)));
+    {
+        let shared = shared.clone();
+        thread::Builder::new()
+            .name("xilem blocking task".into())
+            .spawn(move || {
+                let output = panic::catch_unwind(AssertUnwindSafe(f));
+                let mut shared = lock(&shared);
+                shared.0 = Some(output);
+                if let Some(waker) = shared.1.take() {
+                    waker.wake();
+                }
+            })
+            .expect("failed to spawn blocking task thread");
+    }
+    std::future::poll_fn(move |cx| {
+        let mut shared = lock(&shared);
+        match shared.0.take() {
+            Some(Ok(output)) => Poll::Ready(output),
+            Some(Err(payload)) => panic::resume_unwind(payload),
+            None => {
+                shared.1 = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    })
+}
+
+impl<F, Fut, OnDone, State, Action> MasonryView<State, Action> for Task<F, OnDone, Fut::Output>
+where
+    F: Fn() -> Fut + Send + Sync + 'static,
+    Fut: Future + Send + 'static,
+    Fut::Output: Send + 'static,
+    OnDone: Fn(&mut State, Fut::Output) -> Action + Send + Sync + 'static,
+{
+    type Element = masonry::widget::SizedBox;
+    type ViewState = FutureHandle;
+
+    fn build(&self, cx: &mut ViewCx) -> (WidgetPod<Self::Element>, Self::ViewState) {
+        let pod = cx.with_action_widget(|_| WidgetPod::new(masonry::widget::SizedBox::empty()));
+        let handle = spawn_future(cx, pod.id(), 0, (self.make_future)());
+        (pod, handle)
+    }
+
+    fn rebuild(
+        &self,
+        _view_state: &mut Self::ViewState,
+        _cx: &mut ViewCx,
+        _prev: &Self,
+        _element: WidgetMut<Self::Element>,
+    ) {
+    }
+
+    fn message(
+        &self,
+        _view_state: &mut Self::ViewState,
+        id_path: &[ViewId],
+        message: Box<dyn std::any::Any>,
+        app_state: &mut State,
+    ) -> MessageResult<Action> {
+        debug_assert!(
+            id_path.is_empty(),
+            "id path should be empty in Task::message"
+        );
+        match take_output::<Fut::Output>(message, "Task") {
+            Ok((_, output)) => MessageResult::Action((self.on_done)(app_state, output)),
+            Err(message) => MessageResult::Stale(message),
+        }
+    }
+}
+
+impl<D, F, Fut, OnDone, State, Action> MasonryView<State, Action>
+    for TaskFor<D, F, OnDone, Fut::Output>
+where
+    D: PartialEq + Send + Sync + 'static,
+    F: Fn(&D) -> Fut + Send + Sync + 'static,
+    Fut: Future + Send + 'static,
+    Fut::Output: Send + 'static,
+    OnDone: Fn(&mut State, Fut::Output) -> Action + Send + Sync + 'static,
+{
+    type Element = masonry::widget::SizedBox;
+    type ViewState = TaskForState;
+
+    fn build(&self, cx: &mut ViewCx) -> (WidgetPod<Self::Element>, Self::ViewState) {
+        let pod = cx.with_action_widget(|_| WidgetPod::new(masonry::widget::SizedBox::empty()));
+        let target = pod.id();
+        let handle = spawn_future(cx, target, 0, (self.make_future)(&self.input));
+        let view_state = TaskForState {
+            handle,
+            target,
+            generation: 0,
+        };
+        (pod, view_state)
+    }
+
+    fn rebuild(
+        &self,
+        view_state: &mut Self::ViewState,
+        cx: &mut ViewCx,
+        prev: &Self,
+        _element: WidgetMut<Self::Element>,
+    ) {
+        if prev.input != self.input {
+            view_state.generation += 1;
+            let future = (self.make_future)(&self.input);
+            // Replacing the handle cancels the previous future.
+            view_state.handle = spawn_future(cx, view_state.target, view_state.generation, future);
+        }
+    }
+
+    fn message(
+        &self,
+        view_state: &mut Self::ViewState,
+        id_path: &[ViewId],
+        message: Box<dyn std::any::Any>,
+        app_state: &mut State,
+    ) -> MessageResult<Action> {
+        debug_assert!(
+            id_path.is_empty(),
+            "id path should be empty in TaskFor::message"
+        );
+        match take_output::<Fut::Output>(message, "TaskFor") {
+            Ok((generation, output)) if generation == view_state.generation => {
+                MessageResult::Action((self.on_done)(app_state, output))
+            }
+            // The future completed just before being cancelled.
+            Ok(_) => MessageResult::Nop,
+            Err(message) => MessageResult::Stale(message),
+        }
+    }
+}
+
+/// Poll `future` on the shared executor, and submit its output as an action of `target`.
+///
+/// The output is tagged with `generation`, and can be retrieved with [`take_output`].
+fn spawn_future<Fut>(cx: &ViewCx, target: WidgetId, generation: u64, future: Fut) -> FutureHandle
+where
+    Fut: Future + Send + 'static,
+    Fut::Output: Send + 'static,
+{
+    let sink = cx.ext_event_sink().clone();
+    let future = async move {
+        let output = future.await;
+        // `Action::Other` isn't `Send`, so the output is wrapped
+        // in a `Mutex` to be taken out of it in `message`.
+        let payload = Box::new(Mutex::new(Some((generation, output))));
+        if sink.submit_action(payload, target).is_err() {
+            tracing::debug!("App exited before task completed");
+        }
+    };
+    spawn_job(future)
+}
+
+/// Poll `future` on the shared executor until it completes or the handle is dropped.
+fn spawn_job(future: impl Future<Output = ()> + Send + 'static) -> FutureHandle {
+    let job = Arc::new(Job {
+        future: Mutex::new(Some(Box::pin(future))),
+        queued: AtomicBool::new(false),
+        cancelled: AtomicBool::new(false),
+    });
+    Waker::from(job.clone()).wake();
+    FutureHandle { job }
+}
+
+/// Take the output of a future submitted by [`spawn_future`] out of `message`,
+/// with its generation.
+fn take_output<Output: 'static>(
+    message: Box<dyn std::any::Any>,
+    view_name: &str,
+) -> Result<(u64, Output), Box<dyn std::any::Any>> {
+    match message.downcast::<masonry::Action>() {
+        Ok(action) => {
+            let output = match &*action {
+                masonry::Action::Other(payload) => payload
+                    .downcast_ref::<Mutex<Option<(u64, Output)>>>()
+                    .and_then(|output| lock(output).take()),
+                _ => None,
+            };
+            output.ok_or_else(|| {
+                tracing::error!("Wrong action type in {view_name}::message: {action:?}");
+                action as Box<dyn std::any::Any>
+            })
+        }
+        Err(message) => {
+            tracing::error!("Wrong message type in {view_name}::message");
+            Err(message)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn job_completes() {
+        let (sender, receiver) = mpsc::channel();
+        let _handle = spawn_job(async move {
+            let output = run_blocking(|| 42).await;
+            sender.send(output).unwrap();
+        });
+        assert_eq!(receiver.recv_timeout(Duration::from_secs(5)), Ok(42));
+    }
+
+    #[test]
+    fn panicking_jobs_dont_stop_the_executor() {
+        // More panicking jobs than the executor has threads.
+        let _handles: Vec<_> = (0..8)
+            .map(|_| spawn_job(async { panic!("task panicked") }))
+            .collect();
+        let _blocking_handle = spawn_job(async {
+            run_blocking(|| panic!("blocking task panicked")).await;
+        });
+        let (sender, receiver) = mpsc::channel();
+        let _handle = spawn_job(async move {
+            sender.send(42).unwrap();
+        });
+        assert_eq!(receiver.recv_timeout(Duration::from_secs(5)), Ok(42));
+    }
+
+    #[test]
+    fn dropping_handle_drops_pending_future() {
+        let guard = Arc::new(());
+        let handle = {
+            let guard = guard.clone();
+            spawn_job(async move {
+                let _guard = guard;
+                std::future::pending::<()>().await;
+            })
+        };
+        // Wait for the future to be polled, so that it's pending rather than queued.
+        while handle.job.queued.load(Ordering::Acquire) {
+            thread::yield_now();
+        }
+        drop(handle);
+        for _ in 0..1000 {
+            if Arc::strong_count(&guard) == 1 {
+                return;
+            }
+            thread::sleep(Duration::from_millis(1));
+        }
+        panic!("the future wasn't dropped");
+    }
+}
@@ -0,0 +1,111 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+use masonry::chrono::NaiveTime;
+use masonry::{widget::WidgetMut, WidgetPod};
+
+use crate::{MasonryView, MessageResult, ViewCx, ViewId};
+
+/// A widget for picking a time of day, with scrollable columns for the hours and minutes.
+///
+/// `on_change` is called with the new time whenever the user changes it.
+pub fn time_picker<F, State, Action>(time: Option<NaiveTime>, on_change: F) -> TimePicker<F>
+where
+    F: Fn(&mut State, NaiveTime) -> Action + Send + 'static,
+{
+    TimePicker {
+        time,
+        seconds: false,
+        twelve_hour: false,
+        on_change,
+    }
+}
+
+pub struct TimePicker<F> {
+    time: Option<NaiveTime>,
+    seconds: bool,
+    twelve_hour: bool,
+    on_change: F,
+}
+
+impl<F> TimePicker<F> {
+    /// Show a column for the seconds.
+    ///
+    /// Without it, the seconds of `time` are kept as they are.
+    pub fn seconds(mut self, seconds: bool) -> Self {
+        self.seconds = seconds;
+        self
+    }
+
+    /// Show hours from 12 to 11, with a column for AM and PM.
+    pub fn twelve_hour(mut self, twelve_hour: bool) -> Self {
+        self.twelve_hour = twelve_hour;
+        self
+    }
+}
+
+impl<F, State, Action> MasonryView<State, Action> for TimePicker<F>
+where
+    F: Fn(&mut State, NaiveTime) -> Action + Send + Sync + 'static,
+{
+    type Element = masonry::widget::TimePicker;
+    type ViewState = ();
+
+    fn build(&self, cx: &mut ViewCx) -> (WidgetPod<Self::Element>, Self::ViewState) {
+        cx.with_leaf_action_widget(|_| {
+            WidgetPod::new(
+                masonry::widget::TimePicker::new(self.time)
+                    .with_seconds(self.seconds)
+                    .with_twelve_hour(self.twelve_hour),
+            )
+        })
+    }
+
+    fn rebuild(
+        &self,
+        _view_state: &mut Self::ViewState,
+        cx: &mut ViewCx,
+        prev: &Self,
+        mut element: WidgetMut<Self::Element>,
+    ) {
+        if prev.seconds != self.seconds {
+            element.set_seconds(self.seconds);
+            cx.mark_changed();
+        }
+        if prev.twelve_hour != self.twelve_hour {
+            element.set_twelve_hour(self.twelve_hour);
+            cx.mark_changed();
+        }
+        if prev.time != self.time {
+            element.set_time(self.time);
+            cx.mark_changed();
+        }
+    }
+
+    fn message(
+        &self,
+        _view_state: &mut Self::ViewState,
+        id_path: &[ViewId],
+        message: Box<dyn std::any::Any>,
+        app_state: &mut State,
+    ) -> MessageResult<Action> {
+        debug_assert!(
+            id_path.is_empty(),
+            "id path should be empty in TimePicker::message"
+        );
+        match message.downcast::<masonry::Action>() {
+            Ok(action) => {
+                if let masonry::Action::TimeChanged(time) = *action {
+                    MessageResult::Action((self.on_change)(app_state, time))
+                } else {
+                    tracing::error!("Wrong action type in TimePicker::message: {action:?}");
+                    MessageResult::Stale(action)
+                }
+            }
+            Err(message) => {
+                tracing::error!("Wrong message type in TimePicker::message");
+                MessageResult::Stale(message)
+            }
+        }
+    }
+}
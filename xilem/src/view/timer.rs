@@ -0,0 +1,130 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use masonry::{ext_event::ExtEventSink, widget::WidgetMut, WidgetId, WidgetPod};
+
+use crate::view::TaskHandle;
+use crate::{MasonryView, MessageResult, ViewCx, ViewId};
+
+/// Call `on_tick` every `interval`, for as long as this view is in the tree.
+///
+/// Ticks are scheduled relative to when the view was built (or when `interval` last
+/// changed), so they don't drift over time. If the app falls behind, missed ticks are
+/// skipped rather than delivered in a burst.
+///
+/// This view doesn't display anything.
+///
+/// # Panics
+///
+/// If `interval` is zero.
+pub fn timer<F, State, Action>(interval: Duration, on_tick: F) -> Timer<F>
+where
+    F: Fn(&mut State) -> Action + Send + Sync + 'static,
+{
+    assert!(!interval.is_zero(), "timer interval must not be zero");
+    Timer { interval, on_tick }
+}
+
+pub struct Timer<F> {
+    interval: Duration,
+    on_tick: F,
+}
+
+pub struct TimerState {
+    target: WidgetId,
+    handle: TaskHandle,
+}
+
+/// The payload of the actions sent by the timer thread.
+struct Tick;
+
+fn spawn_timer(sink: ExtEventSink, target: WidgetId, interval: Duration) -> TaskHandle {
+    TaskHandle::spawn(move |cancelled: &AtomicBool| {
+        let epoch = Instant::now();
+        let mut ticks = 1;
+        loop {
+            let deadline = epoch + interval * ticks;
+            // The handle unparks this thread when it is dropped, so we
+            // re-check the flag after each wake up.
+            loop {
+                if cancelled.load(Ordering::Acquire) {
+                    return;
+                }
+                let now = Instant::now();
+                if now >= deadline {
+                    break;
+                }
+                thread::park_timeout(deadline - now);
+            }
+            if sink.submit_action(Box::new(Tick), target).is_err() {
+                return;
+            }
+            let elapsed = epoch.elapsed().as_nanos() / interval.as_nanos();
+            ticks = u32::try_from(elapsed).unwrap_or(u32::MAX - 1) + 1;
+        }
+    })
+}
+
+impl<F, State, Action> MasonryView<State, Action> for Timer<F>
+where
+    F: Fn(&mut State) -> Action + Send + Sync + 'static,
+{
+    type Element = masonry::widget::SizedBox;
+    type ViewState = TimerState;
+
+    fn build(&self, cx: &mut ViewCx) -> (WidgetPod<Self::Element>, Self::ViewState) {
+        let pod = cx.with_action_widget(|_| WidgetPod::new(masonry::widget::SizedBox::empty()));
+        let target = pod.id();
+        let handle = spawn_timer(cx.ext_event_sink().clone(), target, self.interval);
+        (pod, TimerState { target, handle })
+    }
+
+    fn rebuild(
+        &self,
+        view_state: &mut Self::ViewState,
+        cx: &mut ViewCx,
+        prev: &Self,
+        _element: WidgetMut<Self::Element>,
+    ) {
+        if prev.interval != self.interval {
+            // Replacing the handle cancels the previous timer.
+            view_state.handle = spawn_timer(
+                cx.ext_event_sink().clone(),
+                view_state.target,
+                self.interval,
+            );
+        }
+    }
+
+    fn message(
+        &self,
+        _view_state: &mut Self::ViewState,
+        id_path: &[ViewId],
+        message: Box<dyn std::any::Any>,
+        app_state: &mut State,
+    ) -> MessageResult<Action> {
+        debug_assert!(
+            id_path.is_empty(),
+            "id path should be empty in Timer::message"
+        );
+        match message.downcast::<masonry::Action>() {
+            Ok(action) => {
+                if let masonry::Action::Other(payload) = &*action {
+                    if payload.is::<Tick>() {
+                        return MessageResult::Action((self.on_tick)(app_state));
+                    }
+                }
+                tracing::error!("Wrong action type in Timer::message: {action:?}");
+                MessageResult::Stale(action)
+            }
+            Err(message) => {
+                tracing::error!("Wrong message type in Timer::message");
+                MessageResult::Stale(message)
+            }
+        }
+    }
+}
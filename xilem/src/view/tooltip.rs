@@ -0,0 +1,89 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use masonry::{widget::WidgetMut, ArcStr, WidgetPod};
+
+use crate::{MasonryView, MessageResult, ViewCx, ViewId};
+
+/// Show `text` in a small floating label when the pointer hovers `child`.
+///
+/// See also [`TooltipExt::with_tooltip`].
+pub fn tooltip<V>(child: V, text: impl Into<ArcStr>) -> Tooltip<V> {
+    Tooltip {
+        child,
+        text: text.into(),
+        delay: None,
+    }
+}
+
+/// Adds [`with_tooltip`](Self::with_tooltip) to views.
+pub trait TooltipExt: Sized {
+    /// Show `text` in a small floating label when the pointer hovers this view.
+    fn with_tooltip(self, text: impl Into<ArcStr>) -> Tooltip<Self> {
+        tooltip(self, text)
+    }
+}
+
+impl<V: Send + Sync + 'static> TooltipExt for V {}
+
+pub struct Tooltip<V> {
+    child: V,
+    text: ArcStr,
+    delay: Option<Duration>,
+}
+
+impl<V> Tooltip<V> {
+    /// Set how long the pointer must hover before the tooltip is shown.
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = Some(delay);
+        self
+    }
+}
+
+impl<V, State, Action> MasonryView<State, Action> for Tooltip<V>
+where
+    V: MasonryView<State, Action>,
+{
+    type Element = masonry::widget::Tooltip<V::Element>;
+    type ViewState = V::ViewState;
+
+    fn build(&self, cx: &mut ViewCx) -> (WidgetPod<Self::Element>, Self::ViewState) {
+        let (child, child_state) = self.child.build(cx);
+        let mut widget = masonry::widget::Tooltip::from_child_pod(child, self.text.clone());
+        if let Some(delay) = self.delay {
+            widget = widget.delay(delay);
+        }
+        (WidgetPod::new(widget), child_state)
+    }
+
+    fn rebuild(
+        &self,
+        view_state: &mut Self::ViewState,
+        cx: &mut ViewCx,
+        prev: &Self,
+        mut element: WidgetMut<Self::Element>,
+    ) {
+        if prev.text != self.text {
+            element.set_text(self.text.clone());
+            cx.mark_changed();
+        }
+        if prev.delay != self.delay {
+            element.set_delay(self.delay.unwrap_or(Self::Element::DEFAULT_DELAY));
+            cx.mark_changed();
+        }
+        self.child
+            .rebuild(view_state, cx, &prev.child, element.child_mut());
+    }
+
+    fn message(
+        &self,
+        view_state: &mut Self::ViewState,
+        id_path: &[ViewId],
+        message: Box<dyn std::any::Any>,
+        app_state: &mut State,
+    ) -> MessageResult<Action> {
+        self.child.message(view_state, id_path, message, app_state)
+    }
+}
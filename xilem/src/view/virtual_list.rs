@@ -0,0 +1,161 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+use masonry::{widget::WidgetMut, WidgetPod};
+
+use crate::sequence::{create_vector_view_id, view_id_to_index_generation};
+use crate::{MasonryView, MessageResult, ViewCx, ViewId};
+
+/// A scrolling list of `count` items, each `item_height` tall, where the view of
+/// item `i` is `build(i)`.
+///
+/// Only the items currently on screen, plus a few on each side, are built.
+/// `build` is called again for each of those items whenever the list is rebuilt,
+/// and as it is scrolled.
+pub fn virtual_list<V, F>(count: usize, item_height: f64, build: F) -> VirtualList<F>
+where
+    F: Fn(usize) -> V,
+{
+    VirtualList {
+        count,
+        item_height,
+        build,
+    }
+}
+
+pub struct VirtualList<F> {
+    count: usize,
+    item_height: f64,
+    build: F,
+}
+
+pub struct VirtualListState<State, Action, V: MasonryView<State, Action>> {
+    /// The range of items the widget last asked for.
+    requested_range: Range<usize>,
+    /// The views of the built items, keyed by item index, with the generation of their id.
+    items: BTreeMap<usize, (V, V::ViewState, u32)>,
+    generation: u32,
+}
+
+impl<State, Action, V, F> MasonryView<State, Action> for VirtualList<F>
+where
+    V: MasonryView<State, Action>,
+    F: Fn(usize) -> V + Send + Sync + 'static,
+{
+    type Element = masonry::widget::VirtualList;
+    type ViewState = VirtualListState<State, Action, V>;
+
+    fn build(&self, cx: &mut ViewCx) -> (WidgetPod<Self::Element>, Self::ViewState) {
+        // The items are built once the widget reports which ones are visible.
+        let element = cx.with_action_widget(|_| {
+            WidgetPod::new(masonry::widget::VirtualList::new(
+                self.count,
+                self.item_height,
+            ))
+        });
+        let view_state = VirtualListState {
+            requested_range: 0..0,
+            items: BTreeMap::new(),
+            generation: 0,
+        };
+        (element, view_state)
+    }
+
+    fn rebuild(
+        &self,
+        view_state: &mut Self::ViewState,
+        cx: &mut ViewCx,
+        prev: &Self,
+        mut element: WidgetMut<Self::Element>,
+    ) {
+        if prev.count != self.count {
+            element.set_count(self.count);
+            cx.mark_changed();
+        }
+        if prev.item_height != self.item_height {
+            element.set_item_height(self.item_height);
+            cx.mark_changed();
+        }
+
+        let range = view_state.requested_range.start.min(self.count)
+            ..view_state.requested_range.end.min(self.count);
+        let out_of_range: Vec<usize> = view_state
+            .items
+            .keys()
+            .filter(|index| !range.contains(index))
+            .copied()
+            .collect();
+        for index in out_of_range {
+            if let Some((_, _, generation)) = view_state.items.remove(&index) {
+                cx.forget_child(ViewId::for_type::<V>(create_vector_view_id(
+                    index, generation,
+                )));
+            }
+            element.remove_child(index);
+            cx.mark_changed();
+        }
+
+        for index in range {
+            let view = (self.build)(index);
+            if let Some((prev_view, item_state, generation)) = view_state.items.get_mut(&index) {
+                let id = ViewId::for_type::<V>(create_vector_view_id(index, *generation));
+                let mut child = element
+                    .child_mut(index)
+                    .expect("VirtualList widget should hold every built item");
+                let Some(child) = child.try_downcast::<V::Element>() else {
+                    unreachable!("Tree structure tracking got wrong element type")
+                };
+                cx.with_id(id, |cx| view.rebuild(item_state, cx, prev_view, child));
+                *prev_view = view;
+            } else {
+                // Items which leave the range and come back get a new id, so that
+                // messages sent to their old widget are recognized as stale.
+                view_state.generation = view_state.generation.wrapping_add(1);
+                let generation = view_state.generation;
+                let id = ViewId::for_type::<V>(create_vector_view_id(index, generation));
+                let (child, item_state) = cx.with_id(id, |cx| view.build(cx));
+                element.insert_child(index, child.boxed());
+                view_state
+                    .items
+                    .insert(index, (view, item_state, generation));
+                cx.mark_changed();
+            }
+        }
+    }
+
+    fn message(
+        &self,
+        view_state: &mut Self::ViewState,
+        id_path: &[ViewId],
+        message: Box<dyn std::any::Any>,
+        app_state: &mut State,
+    ) -> MessageResult<Action> {
+        let Some((first, rest)) = id_path.split_first() else {
+            return match message.downcast::<masonry::Action>() {
+                Ok(action) => {
+                    if let masonry::Action::VisibleRangeChanged(range) = *action {
+                        view_state.requested_range = range;
+                        MessageResult::RequestRebuild
+                    } else {
+                        tracing::error!("Wrong action type in VirtualList::message: {action:?}");
+                        MessageResult::Stale(action)
+                    }
+                }
+                Err(message) => {
+                    tracing::error!("Wrong message type in VirtualList::message");
+                    MessageResult::Stale(message)
+                }
+            };
+        };
+        let (index, generation) = view_id_to_index_generation(first.routing_id());
+        match view_state.items.get_mut(&index) {
+            Some((view, item_state, item_generation)) if *item_generation == generation => {
+                view.message(item_state, rest, message, app_state)
+            }
+            _ => MessageResult::Stale(message),
+        }
+    }
+}
@@ -0,0 +1,146 @@
+// Copyright 2024 the Xilem Authors
+// SPDX-License-Identifier: Apache-2.0
+
+use std::marker::PhantomData;
+
+use masonry::widget::{self, WidgetMut};
+use masonry::{Widget, WidgetPod};
+
+use crate::{ElementSplice, MasonryView, UnitPoint, VecSplice, ViewSequence};
+
+/// Stack the views of `sequence` on top of each other, such as a badge over an avatar.
+///
+/// The stack is as large as its largest child. Later children are drawn on top of
+/// earlier ones, and get pointer events first.
+///
+/// Unlike a popup, the stack is a normal part of the layout.
+pub fn z_stack<VT, Marker>(sequence: VT) -> ZStack<VT, Marker> {
+    ZStack {
+        sequence,
+        alignment: UnitPoint::CENTER,
+        child_alignments: Vec::new(),
+        phantom: PhantomData,
+    }
+}
+
+pub struct ZStack<VT, Marker> {
+    sequence: VT,
+    alignment: UnitPoint,
+    child_alignments: Vec<Option<UnitPoint>>,
+    phantom: PhantomData<fn() -> Marker>,
+}
+
+impl<VT, Marker> ZStack<VT, Marker> {
+    /// Set how the children are aligned within the stack, which defaults to centering them.
+    pub fn alignment(mut self, alignment: UnitPoint) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    /// Align the child at index `idx` of the sequence differently from the others,
+    /// such as a badge in the top right corner.
+    ///
+    /// The index counts the widgets of the sequence, so for a tuple of views, it's the
+    /// position of the view in the tuple.
+    pub fn child_alignment(mut self, idx: usize, alignment: UnitPoint) -> Self {
+        if self.child_alignments.len() <= idx {
+            self.child_alignments.resize(idx + 1, None);
+        }
+        self.child_alignments[idx] = Some(alignment);
+        self
+    }
+
+    fn child_alignment_at(&self, idx: usize) -> Option<UnitPoint> {
+        self.child_alignments.get(idx).copied().flatten()
+    }
+}
+
+impl<State, Action, Marker: 'static, Seq: Sync> MasonryView<State, Action> for ZStack<Seq, Marker>
+where
+    Seq: ViewSequence<State, Action, Marker>,
+{
+    type Element = widget::ZStack;
+    type ViewState = Seq::SeqState;
+
+    fn build(&self, cx: &mut crate::ViewCx) -> (WidgetPod<Self::Element>, Self::ViewState) {
+        let mut elements = Vec::new();
+        let mut scratch = Vec::new();
+        let mut splice = VecSplice::new(&mut elements, &mut scratch);
+        let seq_state = self.sequence.build(cx, &mut splice);
+        debug_assert!(
+            scratch.is_empty(),
+            "ViewSequence shouldn't leave splice in strange state"
+        );
+        let mut view = widget::ZStack::new().with_alignment(self.alignment);
+        for (idx, item) in elements.drain(..).enumerate() {
+            view = match self.child_alignment_at(idx) {
+                Some(alignment) => view.with_aligned_child_pod(item, alignment),
+                None => view.with_child_pod(item),
+            };
+        }
+        (WidgetPod::new(view), seq_state)
+    }
+
+    fn message(
+        &self,
+        view_state: &mut Self::ViewState,
+        id_path: &[crate::ViewId],
+        message: Box<dyn std::any::Any>,
+        app_state: &mut State,
+    ) -> crate::MessageResult<Action> {
+        self.sequence
+            .message(view_state, id_path, message, app_state)
+    }
+
+    fn rebuild(
+        &self,
+        view_state: &mut Self::ViewState,
+        cx: &mut crate::ViewCx,
+        prev: &Self,
+        mut element: WidgetMut<Self::Element>,
+    ) {
+        if prev.alignment != self.alignment {
+            element.set_alignment(self.alignment);
+            cx.mark_changed();
+        }
+        let mut splice = ZStackSplice { ix: 0, element };
+        self.sequence
+            .rebuild(view_state, cx, &prev.sequence, &mut splice);
+        let mut element = splice.element;
+        // Children may have been added or removed, so all the alignments are set again.
+        for idx in 0..element.widget.len() {
+            element.set_child_alignment(idx, self.child_alignment_at(idx));
+        }
+        if prev.child_alignments != self.child_alignments {
+            cx.mark_changed();
+        }
+    }
+}
+
+struct ZStackSplice<'w> {
+    ix: usize,
+    element: WidgetMut<'w, widget::ZStack>,
+}
+
+impl ElementSplice for ZStackSplice<'_> {
+    fn push(&mut self, element: WidgetPod<Box<dyn Widget>>) {
+        self.element.insert_child_pod(self.ix, element);
+        self.ix += 1;
+    }
+
+    fn mutate(&mut self) -> WidgetMut<'_, Box<dyn Widget>> {
+        let child = self.element.child_mut(self.ix).unwrap();
+        self.ix += 1;
+        child
+    }
+
+    fn delete(&mut self, n: usize) {
+        for _ in 0..n {
+            self.element.remove_child(self.ix);
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.ix
+    }
+}